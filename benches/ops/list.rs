@@ -0,0 +1,117 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use criterion::Criterion;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use opendal::layers::ImmutableIndexLayer;
+use opendal::Operator;
+use opendal::Scheme;
+
+use super::utils::*;
+
+pub fn bench(c: &mut Criterion) {
+    for case in services() {
+        if case.1.is_none() {
+            println!("{} not set, ignore", case.0);
+            continue;
+        }
+
+        let op = case.1.unwrap();
+
+        bench_list(c, op.clone());
+    }
+
+    bench_list_immutable_index(c);
+}
+
+fn bench_list(c: &mut Criterion, op: Operator) {
+    if !op.metadata().can_list() {
+        println!("{} doesn't support list, ignore", op.metadata().scheme());
+        return;
+    }
+
+    let mut group = c.benchmark_group("list");
+
+    for count in [100, 1000, 10000] {
+        let dir = format!("{}/", uuid::Uuid::new_v4());
+        TOKIO.block_on(async {
+            futures::stream::iter(0..count)
+                .map(|i| {
+                    let o = op.object(&format!("{dir}file-{i}"));
+                    async move { o.write(vec![0; 16]).await }
+                })
+                .buffer_unordered(64)
+                .try_collect::<Vec<_>>()
+                .await
+                .expect("create test data");
+        });
+
+        group.throughput(criterion::Throughput::Elements(count as u64));
+        group.bench_with_input(count.to_string(), &(op.clone(), &dir), |b, (op, dir)| {
+            b.to_async(&*TOKIO).iter(|| async {
+                let entries = op
+                    .object(dir)
+                    .list()
+                    .await
+                    .unwrap()
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .unwrap();
+                assert_eq!(entries.len(), count);
+            })
+        });
+
+        TOKIO.block_on(async {
+            op.batch()
+                .remove_all(&dir)
+                .await
+                .expect("cleanup test data")
+        });
+    }
+
+    group.finish()
+}
+
+/// Memory doesn't support list, so we list its keys via an in-memory index
+/// to measure the overhead of ObjectLister itself without any setup.
+fn bench_list_immutable_index(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list_immutable_index");
+
+    for count in [100, 1000, 10000] {
+        let mut iil = ImmutableIndexLayer::default();
+        for i in 0..count {
+            iil.insert(format!("dir/file-{i}"));
+        }
+        let op = Operator::from_env(Scheme::Memory)
+            .expect("init memory must succeed")
+            .layer(iil);
+
+        group.throughput(criterion::Throughput::Elements(count as u64));
+        group.bench_with_input(count.to_string(), &op, |b, op| {
+            b.to_async(&*TOKIO).iter(|| async {
+                let entries = op
+                    .object("dir/")
+                    .list()
+                    .await
+                    .unwrap()
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .unwrap();
+                assert_eq!(entries.len(), count);
+            })
+        });
+    }
+
+    group.finish()
+}
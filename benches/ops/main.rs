@@ -11,6 +11,7 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+mod list;
 mod read;
 mod utils;
 mod write;
@@ -18,5 +19,5 @@ mod write;
 use criterion::criterion_group;
 use criterion::criterion_main;
 
-criterion_group!(benches, read::bench, write::bench);
+criterion_group!(benches, list::bench, read::bench, write::bench);
 criterion_main!(benches);
@@ -0,0 +1,84 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Bytes;
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+use opendal::raw::BytesContentRange;
+use opendal::raw::BytesRange;
+
+criterion_group!(benches, bench_bytes_content_range, bench_bytes_range);
+criterion_main!(benches);
+
+fn bench_bytes_content_range(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bytes_content_range");
+
+    for (name, input) in [
+        ("parse_range", "bytes 0-1023/4096"),
+        ("parse_unknown_size", "bytes 0-1023/*"),
+        ("parse_size_only", "bytes */4096"),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(input).parse::<BytesContentRange>().unwrap())
+        });
+    }
+
+    let bcr = BytesContentRange::default()
+        .with_range(1024, 2047)
+        .with_size(4096);
+    group.bench_function("header_value", |b| b.iter(|| black_box(bcr).header_value()));
+    group.bench_function("to_bytes_range", |b| {
+        b.iter(|| black_box(bcr).to_bytes_range())
+    });
+    group.bench_function("from_bytes_range", |b| {
+        b.iter(|| {
+            BytesContentRange::from_bytes_range(4096, black_box(BytesRange::new(Some(1024), None)))
+        })
+    });
+
+    group.finish()
+}
+
+fn bench_bytes_range(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bytes_range");
+
+    for (name, input) in [
+        ("parse_range", "bytes=0-1023"),
+        ("parse_offset", "bytes=1024-"),
+        ("parse_suffix", "bytes=-1024"),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(input).parse::<BytesRange>().unwrap())
+        });
+    }
+
+    let br = BytesRange::new(Some(1024), Some(1024));
+    group.bench_function("to_header", |b| b.iter(|| black_box(br).to_header()));
+    group.bench_function("advance", |b| {
+        b.iter(|| {
+            let mut br = black_box(br);
+            br.advance(512);
+            br
+        })
+    });
+
+    let bs = Bytes::from(vec![0; 4096]);
+    group.bench_function("apply_on_bytes", |b| {
+        b.iter(|| black_box(br).apply_on_bytes(bs.clone()))
+    });
+
+    group.finish()
+}
@@ -55,7 +55,21 @@ pub enum ErrorKind {
     Unsupported,
 
     /// The config for backend is invalid.
-    BackendConfigInvalid,
+    ///
+    /// Errors returned by services' `Builder::build()` will carry the name
+    /// of the missing or invalid config in context `field`, for example
+    /// `bucket` or `region`.
+    ConfigInvalid,
+
+    /// Path is invalid.
+    ///
+    /// For example, path like `../abc` goes beyond the root of service.
+    InvalidPath,
+    /// Argument of this call is invalid.
+    ///
+    /// For example, the expire of presign has passed or it's longer than
+    /// services allow. Retrying the same call won't help.
+    InvalidInput,
 
     /// Object is not found.
     ObjectNotFound,
@@ -65,9 +79,25 @@ pub enum ErrorKind {
     ObjectIsADirectory,
     /// Object is not a directory.
     ObjectNotADirectory,
+    /// Object's condition like `if-match` or `if-none-match` is not matched.
+    ///
+    /// For example, services returns `304 Not Modified` or `412 Precondition Failed`.
+    ///
+    /// The current `etag`, `last_modified` and `version_id` of object will be
+    /// carried in context if services return them, see [`Error::context`].
+    ConditionNotMatch,
+    /// Object's content doesn't match with the checksum reported by services.
+    ///
+    /// For example, the md5 of read content is not the same as `Content-MD5`.
+    ChecksumMismatch,
 }
 
 impl ErrorKind {
+    /// The config for backend is invalid.
+    #[deprecated(note = "use ErrorKind::ConfigInvalid instead")]
+    #[allow(non_upper_case_globals)]
+    pub const BackendConfigInvalid: ErrorKind = ErrorKind::ConfigInvalid;
+
     /// Convert self into static str.
     pub fn into_static(self) -> &'static str {
         self.into()
@@ -85,11 +115,15 @@ impl From<ErrorKind> for &'static str {
         match v {
             ErrorKind::Unexpected => "Unexpected",
             ErrorKind::Unsupported => "Unsupported",
-            ErrorKind::BackendConfigInvalid => "BackendConfigInvalid",
+            ErrorKind::ConfigInvalid => "ConfigInvalid",
+            ErrorKind::InvalidPath => "InvalidPath",
+            ErrorKind::InvalidInput => "InvalidInput",
             ErrorKind::ObjectNotFound => "ObjectNotFound",
             ErrorKind::ObjectPermissionDenied => "ObjectPermissionDenied",
             ErrorKind::ObjectIsADirectory => "ObjectIsADirectory",
             ErrorKind::ObjectNotADirectory => "ObjectNotADirectory",
+            ErrorKind::ConditionNotMatch => "ConditionNotMatch",
+            ErrorKind::ChecksumMismatch => "ChecksumMismatch",
         }
     }
 }
@@ -287,6 +321,16 @@ impl Error {
     pub fn is_temporary(&self) -> bool {
         self.status == ErrorStatus::Temporary
     }
+
+    /// Get the value of context by key.
+    ///
+    /// The first value will be returned if the key has been set many times.
+    pub fn context(&self, key: &str) -> Option<&str> {
+        self.context
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
 }
 
 impl From<Error> for io::Error {
@@ -294,6 +338,7 @@ impl From<Error> for io::Error {
         let kind = match err.kind() {
             ErrorKind::ObjectNotFound => io::ErrorKind::NotFound,
             ErrorKind::ObjectPermissionDenied => io::ErrorKind::PermissionDenied,
+            ErrorKind::InvalidInput => io::ErrorKind::InvalidInput,
             _ => io::ErrorKind::Other,
         };
 
@@ -48,6 +48,42 @@ impl Accessor for CacheAccessor {
         Some(self.inner.clone())
     }
 
+    /// Copy and rename inside service will bypass the cache policy, so we
+    /// remove the `Copy` and `Rename` capabilities to make sure they are
+    /// done via read, write and delete.
+    ///
+    /// Cache policy is async, the `Blocking` capability is removed too.
+    fn metadata(&self) -> AccessorMetadata {
+        let mut meta = self.inner.metadata();
+        meta.set_capabilities(
+            meta.capabilities()
+                - (AccessorCapability::Copy
+                    | AccessorCapability::Rename
+                    | AccessorCapability::Blocking),
+        );
+        meta
+    }
+
+    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "copy is not supported by cache layer",
+        )
+        .with_operation(Operation::Copy.into_static())
+        .with_context("from", from)
+        .with_context("to", to))
+    }
+
+    async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "rename is not supported by cache layer",
+        )
+        .with_operation(Operation::Rename.into_static())
+        .with_context("from", from)
+        .with_context("to", to))
+    }
+
     async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
         self.policy
             .on_create(self.inner.clone(), self.cache.clone(), path, args)
@@ -75,4 +111,60 @@ impl Accessor for CacheAccessor {
             .on_delete(self.inner.clone(), self.cache.clone(), path, args)
             .await
     }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        self.policy
+            .on_complete_multipart(self.inner.clone(), self.cache.clone(), path, args)
+            .await
+    }
+
+    /// Batch delete must go through the cache policy to keep cache
+    /// consistent, so we will delete objects one by one here.
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        match args.into_operation() {
+            BatchOperations::Delete(ops) => {
+                let mut results = Vec::with_capacity(ops.len());
+                for (path, op) in ops {
+                    let res = self
+                        .policy
+                        .on_delete(self.inner.clone(), self.cache.clone(), &path, op)
+                        .await;
+                    results.push((path, res));
+                }
+
+                Ok(RpBatch::new(BatchedResults::Delete(results)))
+            }
+        }
+    }
+
+    fn blocking_create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "blocking create is not supported by cache layer",
+        )
+        .with_operation(Operation::BlockingCreate.into_static())
+        .with_context("path", path))
+    }
+
+    fn blocking_write(&self, path: &str, _: OpWrite, _: BlockingBytesReader) -> Result<RpWrite> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "blocking write is not supported by cache layer",
+        )
+        .with_operation(Operation::BlockingWrite.into_static())
+        .with_context("path", path))
+    }
+
+    fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "blocking delete is not supported by cache layer",
+        )
+        .with_operation(Operation::BlockingDelete.into_static())
+        .with_context("path", path))
+    }
 }
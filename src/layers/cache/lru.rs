@@ -0,0 +1,481 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use futures::io::Cursor;
+use futures::AsyncReadExt;
+use parking_lot::Mutex;
+
+use super::policy::CacheResult;
+use super::*;
+use crate::raw::*;
+use crate::*;
+
+/// Default capacity of [`LruCachePolicy`]: 64 MiB.
+const DEFAULT_CAPACITY: u64 = 64 * 1024 * 1024;
+
+/// Default max object size of [`LruCachePolicy`]: 1 MiB.
+const DEFAULT_MAX_OBJECT_SIZE: u64 = 1024 * 1024;
+
+/// LruCachePolicy caches the whole content of small objects, and evicts the
+/// least recently read objects while the total size exceeds capacity.
+///
+/// # Behavior
+///
+/// - Only reads of the whole object whose content length is known and not
+///   larger than `max_object_size` will be cached, other reads bypass the
+///   cache.
+/// - Cached content is stored in the cache service of [`CacheLayer`], and
+///   the metadata returned by the first read is kept in memory so that
+///   cache hits return the same metadata.
+/// - `create`, `write` and `delete` on the same path invalidate the cached
+///   entry.
+/// - Reads will fall back to the underlying service if the cached content
+///   has been removed from the cache service.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::CacheLayer;
+/// use opendal::layers::LruCachePolicy;
+/// use opendal::Operator;
+/// use opendal::Scheme;
+///
+/// let _ = Operator::from_env(Scheme::Fs).expect("must init").layer(
+///     CacheLayer::new(Operator::from_env(Scheme::Memory).expect("must init")).with_policy(
+///         LruCachePolicy::new()
+///             .with_capacity(16 * 1024 * 1024)
+///             .with_max_object_size(64 * 1024),
+///     ),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct LruCachePolicy {
+    capacity: u64,
+    max_object_size: u64,
+    index: Arc<Mutex<LruIndex>>,
+}
+
+impl Default for LruCachePolicy {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            max_object_size: DEFAULT_MAX_OBJECT_SIZE,
+            index: Arc::default(),
+        }
+    }
+}
+
+impl LruCachePolicy {
+    /// Create a new LruCachePolicy with 64 MiB capacity and 1 MiB max
+    /// object size.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the total size in bytes of cached content, default to 64 MiB.
+    pub fn with_capacity(mut self, capacity: u64) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Set the max size in bytes of objects that can be cached, default to
+    /// 1 MiB.
+    pub fn with_max_object_size(mut self, max_object_size: u64) -> Self {
+        self.max_object_size = max_object_size;
+        self
+    }
+
+    /// Remove path from index and cache after `f` finished.
+    fn invalidate<T: Send + 'static>(
+        &self,
+        cache: Arc<dyn Accessor>,
+        path: &str,
+        f: CacheResult<T>,
+    ) -> CacheResult<T> {
+        let index = self.index.clone();
+        let path = path.to_string();
+
+        Box::pin(async move {
+            let res = f.await;
+            if index.lock().remove(&path) {
+                let _ = cache.delete(&path, OpDelete::new()).await;
+            }
+            res
+        })
+    }
+
+    /// Write content into cache and then add it into index.
+    ///
+    /// Content will not be cached if the path has been invalidated since
+    /// `epoch`, errors of the cache service are ignored.
+    async fn insert(
+        &self,
+        cache: Arc<dyn Accessor>,
+        path: &str,
+        epoch: u64,
+        bs: &[u8],
+        meta: &ObjectMetadata,
+    ) {
+        if self.index.lock().epoch != epoch {
+            return;
+        }
+
+        let size = bs.len() as u64;
+        let r = Box::new(Cursor::new(bs.to_vec()));
+        if cache.write(path, OpWrite::new(size), r).await.is_err() {
+            return;
+        }
+
+        let evicted = {
+            let mut index = self.index.lock();
+            if index.epoch != epoch {
+                None
+            } else {
+                Some(index.insert(path, size, meta.clone(), self.capacity))
+            }
+        };
+        let evicted = match evicted {
+            Some(v) => v,
+            None => vec![path.to_string()],
+        };
+        for path in evicted {
+            let _ = cache.delete(&path, OpDelete::new()).await;
+        }
+    }
+}
+
+impl CachePolicy for LruCachePolicy {
+    fn on_create(
+        &self,
+        inner: Arc<dyn Accessor>,
+        cache: Arc<dyn Accessor>,
+        path: &str,
+        args: OpCreate,
+    ) -> CacheResult<RpCreate> {
+        let p = path.to_string();
+        let f = Box::pin(async move { inner.create(&p, args).await });
+        self.invalidate(cache, path, f)
+    }
+
+    fn on_read(
+        &self,
+        inner: Arc<dyn Accessor>,
+        cache: Arc<dyn Accessor>,
+        path: &str,
+        args: OpRead,
+    ) -> CacheResult<(RpRead, BytesReader)> {
+        let policy = self.clone();
+        let path = path.to_string();
+
+        Box::pin(async move {
+            // Only the latest version of whole object is cached.
+            if !args.range().is_full() || args.version().is_some() {
+                return inner.read(&path, args).await;
+            }
+
+            let cached = policy.index.lock().get(&path);
+            if let Some(meta) = cached {
+                match cache.read(&path, OpRead::new()).await {
+                    Ok((_, r)) => return Ok((RpRead::with_metadata(meta), r)),
+                    Err(_) => {
+                        policy.index.lock().remove(&path);
+                    }
+                }
+            }
+
+            let epoch = policy.index.lock().epoch;
+            let (rp, r) = inner.read(&path, args).await?;
+            let meta = rp.into_metadata();
+            let size = match meta.content_length_raw() {
+                Some(size) if size <= policy.max_object_size && size <= policy.capacity => size,
+                _ => return Ok((RpRead::with_metadata(meta), r)),
+            };
+
+            let bs = read_content(&path, r, size).await?;
+            policy.insert(cache, &path, epoch, &bs, &meta).await;
+            Ok((RpRead::with_metadata(meta), Box::new(Cursor::new(bs))))
+        })
+    }
+
+    fn on_write(
+        &self,
+        inner: Arc<dyn Accessor>,
+        cache: Arc<dyn Accessor>,
+        path: &str,
+        args: OpWrite,
+        r: BytesReader,
+    ) -> CacheResult<RpWrite> {
+        let p = path.to_string();
+        let f = Box::pin(async move { inner.write(&p, args, r).await });
+        self.invalidate(cache, path, f)
+    }
+
+    fn on_delete(
+        &self,
+        inner: Arc<dyn Accessor>,
+        cache: Arc<dyn Accessor>,
+        path: &str,
+        args: OpDelete,
+    ) -> CacheResult<RpDelete> {
+        let p = path.to_string();
+        let f = Box::pin(async move { inner.delete(&p, args).await });
+        self.invalidate(cache, path, f)
+    }
+
+    fn on_complete_multipart(
+        &self,
+        inner: Arc<dyn Accessor>,
+        cache: Arc<dyn Accessor>,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> CacheResult<RpCompleteMultipart> {
+        let p = path.to_string();
+        let f = Box::pin(async move { inner.complete_multipart(&p, args).await });
+        self.invalidate(cache, path, f)
+    }
+}
+
+async fn read_content(path: &str, mut r: BytesReader, size: u64) -> Result<Vec<u8>> {
+    let mut bs = Vec::with_capacity(size as usize);
+    r.read_to_end(&mut bs).await.map_err(|err| {
+        Error::new(ErrorKind::Unexpected, "read content for cache failed")
+            .with_operation(Operation::Read.into_static())
+            .with_context("path", path)
+            .set_source(err)
+    })?;
+    Ok(bs)
+}
+
+/// LruIndex tracks the size and read order of cached objects.
+#[derive(Debug, Default)]
+struct LruIndex {
+    /// Increased every time an entry is invalidated, so that reads started
+    /// before the invalidation will not be cached.
+    epoch: u64,
+    tick: u64,
+    size: u64,
+    entries: HashMap<String, LruEntry>,
+    /// Paths in the order of last read.
+    order: BTreeMap<u64, String>,
+}
+
+#[derive(Debug)]
+struct LruEntry {
+    tick: u64,
+    size: u64,
+    meta: ObjectMetadata,
+}
+
+impl LruIndex {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Get the metadata of cached path and mark it as recently read.
+    fn get(&mut self, path: &str) -> Option<ObjectMetadata> {
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(path)?;
+
+        self.order.remove(&entry.tick);
+        self.order.insert(tick, path.to_string());
+        entry.tick = tick;
+        Some(entry.meta.clone())
+    }
+
+    /// Insert path into index, returns evicted paths.
+    fn insert(
+        &mut self,
+        path: &str,
+        size: u64,
+        meta: ObjectMetadata,
+        capacity: u64,
+    ) -> Vec<String> {
+        self.remove_entry(path);
+
+        let mut evicted = Vec::new();
+        while self.size + size > capacity {
+            let (_, p) = match self.order.iter().next() {
+                Some((tick, p)) => (*tick, p.clone()),
+                None => break,
+            };
+            self.remove_entry(&p);
+            evicted.push(p);
+        }
+
+        let tick = self.next_tick();
+        self.order.insert(tick, path.to_string());
+        self.entries
+            .insert(path.to_string(), LruEntry { tick, size, meta });
+        self.size += size;
+        evicted
+    }
+
+    /// Invalidate path, returns whether the path is cached.
+    fn remove(&mut self, path: &str) -> bool {
+        self.epoch += 1;
+        self.remove_entry(path)
+    }
+
+    fn remove_entry(&mut self, path: &str) -> bool {
+        match self.entries.remove(path) {
+            Some(entry) => {
+                self.order.remove(&entry.tick);
+                self.size -= entry.size;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use async_trait::async_trait;
+
+    use super::*;
+
+    /// Mock service that counts the read requests.
+    #[derive(Debug)]
+    struct MockService {
+        inner: Arc<dyn Accessor>,
+        reads: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        fn inner(&self) -> Option<Arc<dyn Accessor>> {
+            Some(self.inner.clone())
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.read(path, args).await
+        }
+    }
+
+    fn new_operator(policy: LruCachePolicy) -> (Operator, Arc<MockService>) {
+        let acc = Arc::new(MockService {
+            inner: Operator::from_env(Scheme::Memory)
+                .expect("build operator")
+                .inner(),
+            reads: AtomicUsize::new(0),
+        });
+        let cache = Operator::from_env(Scheme::Memory).expect("build operator");
+        let op = Operator::new(acc.clone()).layer(CacheLayer::new(cache).with_policy(policy));
+        (op, acc)
+    }
+
+    #[tokio::test]
+    async fn test_lru_cache() {
+        let (op, acc) = new_operator(LruCachePolicy::new());
+        // Blocking operations can't go through the cache policy.
+        assert!(!op.metadata().can_blocking());
+        let o = op.object("config.toml");
+        o.write("Hello, World!").await.expect("write must succeed");
+
+        assert_eq!(o.read().await.expect("read must succeed"), b"Hello, World!");
+        assert_eq!(o.read().await.expect("read must succeed"), b"Hello, World!");
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 1);
+
+        // Range read bypasses the cache.
+        assert_eq!(
+            o.range_read(0..5).await.expect("read must succeed"),
+            b"Hello"
+        );
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 2);
+
+        // Write invalidates the cache.
+        o.write("Hello, OpenDAL!")
+            .await
+            .expect("write must succeed");
+        assert_eq!(
+            o.read().await.expect("read must succeed"),
+            b"Hello, OpenDAL!"
+        );
+        assert_eq!(
+            o.read().await.expect("read must succeed"),
+            b"Hello, OpenDAL!"
+        );
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 3);
+
+        // Write via multipart invalidates the cache.
+        let mut w = o
+            .writer()
+            .await
+            .expect("writer must succeed")
+            .with_part_size(4);
+        w.append("Hello, Multipart!")
+            .await
+            .expect("append must succeed");
+        w.close().await.expect("close must succeed");
+        assert_eq!(
+            o.read().await.expect("read must succeed"),
+            b"Hello, Multipart!"
+        );
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 4);
+
+        // Delete invalidates the cache.
+        o.delete().await.expect("delete must succeed");
+        let err = o.read().await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_lru_cache_bypass_large_object() {
+        let (op, acc) = new_operator(LruCachePolicy::new().with_max_object_size(8));
+        let o = op.object("large");
+        o.write(vec![1; 16]).await.expect("write must succeed");
+
+        o.read().await.expect("read must succeed");
+        o.read().await.expect("read must succeed");
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_lru_cache_evict() {
+        let (op, acc) = new_operator(LruCachePolicy::new().with_capacity(24));
+        for path in ["a", "b", "c"] {
+            op.object(path)
+                .write(vec![1; 10])
+                .await
+                .expect("write must succeed");
+        }
+
+        // Cache a and b, and then make b the least recently read.
+        op.object("b").read().await.expect("read must succeed");
+        op.object("a").read().await.expect("read must succeed");
+        op.object("b").read().await.expect("read must succeed");
+        op.object("a").read().await.expect("read must succeed");
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 2);
+
+        // Caching c evicts b.
+        op.object("c").read().await.expect("read must succeed");
+        op.object("a").read().await.expect("read must succeed");
+        op.object("c").read().await.expect("read must succeed");
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 3);
+        op.object("b").read().await.expect("read must succeed");
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 4);
+    }
+}
@@ -21,3 +21,6 @@ use accessor::CacheAccessor;
 mod policy;
 pub use policy::CachePolicy;
 use policy::DefaultCachePolicy;
+
+mod lru;
+pub use lru::LruCachePolicy;
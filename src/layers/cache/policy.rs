@@ -80,6 +80,21 @@ pub trait CachePolicy: Send + Sync + Debug + 'static {
         let path = path.to_string();
         Box::pin(async move { inner.delete(&path, args).await })
     }
+
+    /// on_complete_multipart returns the cache policy on complete multipart
+    /// operation.
+    fn on_complete_multipart(
+        &self,
+        inner: Arc<dyn Accessor>,
+        cache: Arc<dyn Accessor>,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> CacheResult<RpCompleteMultipart> {
+        let _ = cache;
+
+        let path = path.to_string();
+        Box::pin(async move { inner.complete_multipart(&path, args).await })
+    }
 }
 
 impl<T: CachePolicy> CachePolicy for Arc<T> {
@@ -123,6 +138,17 @@ impl<T: CachePolicy> CachePolicy for Arc<T> {
     ) -> CacheResult<RpDelete> {
         self.as_ref().on_delete(inner, cache, path, args)
     }
+
+    fn on_complete_multipart(
+        &self,
+        inner: Arc<dyn Accessor>,
+        cache: Arc<dyn Accessor>,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> CacheResult<RpCompleteMultipart> {
+        self.as_ref()
+            .on_complete_multipart(inner, cache, path, args)
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,410 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io;
+use std::mem;
+use std::sync::Arc;
+
+use async_compression::futures::bufread::GzipEncoder;
+use async_compression::futures::bufread::ZstdEncoder;
+use async_compression::Level;
+use async_trait::async_trait;
+use futures::io::BufReader;
+use futures::io::Cursor;
+use futures::AsyncReadExt;
+
+use crate::raw::*;
+use crate::*;
+
+/// Part size used while writing compressed content via multipart: 8 MiB.
+///
+/// It's larger than the minimal part size of multipart upload (5 MiB in s3).
+const PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// CompressionLayer will compress objects at rest transparently.
+///
+/// # Behavior
+///
+/// - Content will be compressed while `write` and decompressed while `read`
+///   in streaming way.
+/// - Content type of the written object is kept as is, it's the type of
+///   original content.
+/// - [`ObjectMetadata::content_length`] returned by `stat` and `list` is the
+///   stored (compressed) size, the decompressed size is unknown. So the
+///   metadata returned by `read` doesn't carry a content length.
+/// - Range read with an offset will decompress and skip the leading content,
+///   reading the last N bytes is not supported.
+/// - Compressed content up to 8 MiB is buffered and written in one request.
+///   Larger content will be uploaded in parts if the underlying service
+///   supports multipart and the write doesn't carry options other than
+///   content type, otherwise it's buffered in memory.
+/// - Multipart operations are not exposed, blocking read and write are not
+///   supported. So [`AccessorCapability::Multipart`] and
+///   [`AccessorCapability::Blocking`] are removed from the capabilities.
+///
+/// # Notes
+///
+/// All objects read via this layer are expected to be written by the same
+/// algorithm, CompressionLayer doesn't detect whether an object is
+/// compressed.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::CompressionLayer;
+/// use opendal::Operator;
+/// use opendal::Scheme;
+///
+/// let _ = Operator::from_env(Scheme::Fs)
+///     .expect("must init")
+///     .layer(CompressionLayer::zstd(3));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CompressionLayer {
+    algo: CompressAlgorithm,
+    level: u32,
+}
+
+impl CompressionLayer {
+    /// Create a new CompressionLayer which compresses content with gzip in
+    /// `level`.
+    ///
+    /// `level` is the compression level of gzip, it will be clamped to `9`.
+    pub fn gzip(level: u32) -> Self {
+        Self {
+            algo: CompressAlgorithm::Gzip,
+            level,
+        }
+    }
+
+    /// Create a new CompressionLayer which compresses content with zstd in
+    /// `level`.
+    ///
+    /// `level` is the compression level of zstd, it will be clamped to
+    /// `21`.
+    pub fn zstd(level: u32) -> Self {
+        Self {
+            algo: CompressAlgorithm::Zstd,
+            level,
+        }
+    }
+}
+
+impl Layer for CompressionLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        Arc::new(CompressionAccessor {
+            inner,
+            algo: self.algo,
+            level: self.level,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CompressionAccessor {
+    inner: Arc<dyn Accessor>,
+    algo: CompressAlgorithm,
+    level: u32,
+}
+
+impl CompressionAccessor {
+    fn new_error(&self, kind: ErrorKind, message: &str, operation: Operation, path: &str) -> Error {
+        Error::new(kind, message)
+            .with_operation(operation.into_static())
+            .with_context("service", self.inner.metadata().scheme().into_static())
+            .with_context("path", path)
+    }
+
+    fn new_unsupported_error(&self, operation: Operation, path: &str) -> Error {
+        self.new_error(
+            ErrorKind::Unsupported,
+            "operation is not supported by compression layer",
+            operation,
+            path,
+        )
+    }
+
+    fn encode(&self, r: BytesReader) -> BytesReader {
+        let r = BufReader::new(r);
+        let level = Level::Precise(self.level);
+        match self.algo {
+            CompressAlgorithm::Zstd => Box::new(ZstdEncoder::with_quality(r, level)),
+            _ => Box::new(GzipEncoder::with_quality(r, level)),
+        }
+    }
+
+    /// Read at most `PART_SIZE` compressed bytes into `buf`.
+    async fn read_part(&self, r: &mut BytesReader, buf: &mut Vec<u8>, path: &str) -> Result<()> {
+        r.take(PART_SIZE as u64)
+            .read_to_end(buf)
+            .await
+            .map_err(|err| self.new_compress_error(err, path))?;
+        Ok(())
+    }
+
+    fn new_compress_error(&self, err: io::Error, path: &str) -> Error {
+        self.new_error(
+            ErrorKind::Unexpected,
+            "compress content failed",
+            Operation::Write,
+            path,
+        )
+        .with_context("algorithm", self.algo.extension())
+        .set_source(err)
+    }
+
+    /// Only content type can be kept while writing via multipart.
+    fn can_write_multipart(&self, args: &OpWrite) -> bool {
+        self.inner
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Multipart)
+            && args.content_disposition().is_none()
+            && args.expires().is_none()
+            && !args.if_not_exists()
+            && args.if_match().is_none()
+            && args.if_none_match().is_none()
+            && args.min_throughput().is_none()
+    }
+
+    /// Upload `buf` and the rest content of `r` part by part, the upload
+    /// will be aborted if any error happened.
+    async fn write_multipart_from(
+        &self,
+        path: &str,
+        args: OpWrite,
+        mut buf: Vec<u8>,
+        mut r: BytesReader,
+    ) -> Result<RpWrite> {
+        let mut op = OpCreateMultipart::new();
+        if let Some(v) = args.content_type() {
+            op = op.with_content_type(v);
+        }
+        let rp = self.inner.create_multipart(path, op).await?;
+        let upload_id = rp.upload_id().to_string();
+
+        let upload = async {
+            let mut parts = Vec::new();
+            let mut written = 0;
+            while !buf.is_empty() {
+                let size = buf.len() as u64;
+                let op = OpWriteMultipart::new(upload_id.clone(), parts.len() + 1, size);
+                let rp = self
+                    .inner
+                    .write_multipart(path, op, Box::new(Cursor::new(mem::take(&mut buf))))
+                    .await?;
+                parts.push(rp.into_object_part());
+                written += size;
+
+                self.read_part(&mut r, &mut buf, path).await?;
+            }
+
+            let op = OpCompleteMultipart::new(upload_id.clone(), parts);
+            self.inner.complete_multipart(path, op).await?;
+            Ok(written)
+        };
+
+        match upload.await {
+            Ok(written) => Ok(RpWrite::new(written)),
+            Err(err) => {
+                let op = OpAbortMultipart::new(upload_id.clone());
+                match self.inner.abort_multipart(path, op).await {
+                    Ok(_) => Err(err),
+                    Err(e) => Err(err.with_context("abort", e.to_string())),
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Accessor for CompressionAccessor {
+    fn inner(&self) -> Option<Arc<dyn Accessor>> {
+        Some(self.inner.clone())
+    }
+
+    fn metadata(&self) -> AccessorMetadata {
+        let mut meta = self.inner.metadata();
+        meta.set_capabilities(
+            meta.capabilities() - AccessorCapability::Multipart - AccessorCapability::Blocking,
+        );
+        meta
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
+        let br = args.range();
+        let offset = match (br.offset(), br.size()) {
+            (None, Some(_)) => {
+                return Err(self.new_error(
+                    ErrorKind::Unsupported,
+                    "read the last bytes is not supported by compression layer",
+                    Operation::Read,
+                    path,
+                ))
+            }
+            (offset, _) => offset.unwrap_or_default(),
+        };
+
+        let (rp, r) = self.inner.read(path, OpRead::new()).await?;
+        let mut r = DecompressReader::new(r, self.algo);
+        if offset > 0 {
+            futures::io::copy((&mut r).take(offset), &mut futures::io::sink())
+                .await
+                .map_err(|err| {
+                    self.new_error(
+                        ErrorKind::Unexpected,
+                        "decompress content failed",
+                        Operation::Read,
+                        path,
+                    )
+                    .with_context("algorithm", self.algo.extension())
+                    .set_source(err)
+                })?;
+        }
+
+        let mut meta = ObjectMetadata::new(ObjectMode::FILE);
+        if let Some(v) = rp.into_metadata().content_type() {
+            meta.set_content_type(v);
+        }
+        let r: BytesReader = match br.size() {
+            Some(size) => Box::new(r.take(size)),
+            None => Box::new(r),
+        };
+        Ok((RpRead::with_metadata(meta), r))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
+        let mut r = self.encode(r);
+
+        let mut buf = Vec::new();
+        self.read_part(&mut r, &mut buf, path).await?;
+        if buf.len() == PART_SIZE && self.can_write_multipart(&args) {
+            return self.write_multipart_from(path, args, buf, r).await;
+        }
+
+        r.read_to_end(&mut buf)
+            .await
+            .map_err(|err| self.new_compress_error(err, path))?;
+        let size = buf.len() as u64;
+        self.inner
+            .write(path, args.with_size(size), Box::new(Cursor::new(buf)))
+            .await
+    }
+
+    async fn create_multipart(
+        &self,
+        path: &str,
+        _: OpCreateMultipart,
+    ) -> Result<RpCreateMultipart> {
+        Err(self.new_unsupported_error(Operation::CreateMultipart, path))
+    }
+
+    fn blocking_read(&self, path: &str, _: OpRead) -> Result<(RpRead, BlockingBytesReader)> {
+        Err(self.new_unsupported_error(Operation::BlockingRead, path))
+    }
+
+    fn blocking_write(&self, path: &str, _: OpWrite, _: BlockingBytesReader) -> Result<RpWrite> {
+        Err(self.new_unsupported_error(Operation::BlockingWrite, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::io::Cursor;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_compression() {
+        let content = r#"{"key": "value"}"#.repeat(1024);
+
+        for layer in [CompressionLayer::gzip(6), CompressionLayer::zstd(3)] {
+            let inner = Operator::from_env(Scheme::Memory).expect("build operator");
+            let op = inner.clone().layer(layer);
+            assert!(!op.metadata().can_blocking());
+
+            op.object("data.json")
+                .write(content.clone())
+                .await
+                .expect("write must succeed");
+
+            let stored = inner
+                .object("data.json")
+                .read()
+                .await
+                .expect("read must succeed");
+            assert!(stored.len() < content.len());
+            let mut r = DecompressReader::new(Cursor::new(stored.clone()), layer.algo);
+            let mut bs = Vec::new();
+            r.read_to_end(&mut bs)
+                .await
+                .expect("decompress must succeed");
+            assert_eq!(bs, content.as_bytes());
+
+            let meta = op
+                .object("data.json")
+                .metadata()
+                .await
+                .expect("stat must succeed");
+            assert_eq!(meta.content_length(), stored.len() as u64);
+
+            let bs = op
+                .object("data.json")
+                .read()
+                .await
+                .expect("read must succeed");
+            assert_eq!(bs, content.as_bytes());
+
+            let bs = op
+                .object("data.json")
+                .range_read(16..32)
+                .await
+                .expect("range read must succeed");
+            assert_eq!(bs, &content.as_bytes()[16..32]);
+
+            let err = op
+                .object("data.json")
+                .range_read(..8)
+                .await
+                .expect_err("suffix range read must fail");
+            assert_eq!(err.kind(), ErrorKind::Unsupported);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compression_truncated() {
+        let inner = Operator::from_env(Scheme::Memory).expect("build operator");
+        let op = inner.clone().layer(CompressionLayer::gzip(6));
+
+        op.object("data.json")
+            .write(vec![1; 4096])
+            .await
+            .expect("write must succeed");
+        let stored = inner.object("data.json").read().await.unwrap();
+        inner
+            .object("data.json")
+            .write(stored[..stored.len() / 2].to_vec())
+            .await
+            .unwrap();
+
+        let err = op
+            .object("data.json")
+            .read()
+            .await
+            .expect_err("read truncated content must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+}
@@ -30,6 +30,11 @@ use crate::*;
 
 /// ConcurrentLimitLayer will add concurrent limit for OpenDAL.
 ///
+/// # Notes
+///
+/// Permits of `read` and `list` will be held until the returning reader or
+/// pager has been dropped, so that streaming bodies count against the limit.
+///
 /// # Examples
 ///
 /// ```
@@ -134,6 +139,26 @@ impl Accessor for ConcurrentLimitAccessor {
         self.inner.delete(path, args).await
     }
 
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.rename(from, to, args).await
+    }
+
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         let permit = self
             .semaphore
@@ -211,6 +236,66 @@ impl Accessor for ConcurrentLimitAccessor {
         self.inner.abort_multipart(path, args).await
     }
 
+    async fn list_multipart(&self, path: &str, args: OpListMultipart) -> Result<RpListMultipart> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.list_multipart(path, args).await
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.list_versions(path, args).await
+    }
+
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.get_tags(path, args).await
+    }
+
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.set_tags(path, args).await
+    }
+
+    async fn delete_tags(&self, path: &str, args: OpDeleteTags) -> Result<RpDeleteTags> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.delete_tags(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.batch(args).await
+    }
+
     fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
         let _permit = self
             .semaphore
@@ -262,6 +347,15 @@ impl Accessor for ConcurrentLimitAccessor {
         self.inner.blocking_delete(path, args)
     }
 
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let _permit = self
+            .semaphore
+            .try_acquire()
+            .expect("semaphore must be valid");
+
+        self.inner.blocking_copy(from, to, args)
+    }
+
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
         let permit = self
             .semaphore
@@ -370,3 +464,97 @@ impl BlockingObjectPage for BlockingConcurrentLimitPager {
         self.inner.next_page()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use futures::future::join_all;
+    use futures::FutureExt;
+
+    use super::*;
+    use crate::layers::Layer;
+    use crate::services::memory;
+
+    /// Track the max number of in-flight `stat`.
+    #[derive(Debug, Default)]
+    struct InFlight {
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+
+    #[derive(Debug)]
+    struct InFlightAccessor {
+        inner: Arc<dyn Accessor>,
+        in_flight: Arc<InFlight>,
+    }
+
+    #[async_trait]
+    impl Accessor for InFlightAccessor {
+        fn inner(&self) -> Option<Arc<dyn Accessor>> {
+            Some(self.inner.clone())
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            let current = self.in_flight.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.in_flight.max.fetch_max(current, Ordering::SeqCst);
+
+            // Yield so that other operations have the chance to run.
+            for _ in 0..16 {
+                tokio::task::yield_now().await;
+            }
+
+            let rp = self.inner.stat(path, args).await;
+            self.in_flight.current.fetch_sub(1, Ordering::SeqCst);
+            rp
+        }
+    }
+
+    fn new_accessor(permits: usize, in_flight: Arc<InFlight>) -> Arc<dyn Accessor> {
+        let acc = Arc::new(InFlightAccessor {
+            inner: Arc::new(memory::Builder::default().build().unwrap()),
+            in_flight,
+        });
+
+        ConcurrentLimitLayer::new(permits).layer(acc)
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_limit() {
+        let in_flight = Arc::new(InFlight::default());
+        let acc = new_accessor(4, in_flight.clone());
+
+        let futs = (0..32).map(|_| acc.stat("/", OpStat::new()));
+        for rp in join_all(futs).await {
+            rp.expect("stat must succeed");
+        }
+
+        let max = in_flight.max.load(Ordering::SeqCst);
+        assert!(max > 0 && max <= 4, "max in-flight operations is {max}");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_limit_hold_permit_for_reader() {
+        let acc = new_accessor(1, Arc::new(InFlight::default()));
+
+        acc.write(
+            "test",
+            OpWrite::new(5),
+            Box::new(futures::io::Cursor::new("Hello")),
+        )
+        .await
+        .expect("write must succeed");
+
+        let (_, r) = acc
+            .read("test", OpRead::new())
+            .await
+            .expect("read must succeed");
+
+        // The only permit is held by reader.
+        assert!(acc.delete("test", OpDelete::new()).now_or_never().is_none());
+
+        drop(r);
+        assert!(acc.delete("test", OpDelete::new()).now_or_never().is_some());
+    }
+}
@@ -118,6 +118,28 @@ impl ImmutableIndexAccessor {
 
         res.into_iter().collect()
     }
+
+    /// Return all files under the path, used for listing recursively.
+    fn files(&self, path: &str) -> Vec<String> {
+        self.set
+            .iter()
+            .filter(|v| v.starts_with(path) && !v.ends_with('/'))
+            .cloned()
+            .collect()
+    }
+
+    fn entries(&self, path: &str, args: &OpList) -> Vec<String> {
+        let mut path = path;
+        if path == "/" {
+            path = ""
+        }
+
+        if args.recursive() {
+            self.files(path)
+        } else {
+            self.children(path)
+        }
+    }
 }
 
 #[async_trait]
@@ -129,32 +151,24 @@ impl Accessor for ImmutableIndexAccessor {
     /// Add list capabilities for underlying storage services.
     fn metadata(&self) -> AccessorMetadata {
         let mut meta = self.inner.metadata();
-        meta.set_capabilities(meta.capabilities() | AccessorCapability::List);
+        meta.set_capabilities(
+            meta.capabilities() | AccessorCapability::List | AccessorCapability::ListRecursive,
+        );
 
         meta
     }
 
-    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, ObjectPager)> {
-        let mut path = path;
-        if path == "/" {
-            path = ""
-        }
-
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         Ok((
             RpList::default(),
-            Box::new(ImmutableDir::new(self.children(path))),
+            Box::new(ImmutableDir::new(self.entries(path, &args))),
         ))
     }
 
-    fn blocking_list(&self, path: &str, _: OpList) -> Result<(RpList, BlockingObjectPager)> {
-        let mut path = path;
-        if path == "/" {
-            path = ""
-        }
-
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
         Ok((
             RpList::default(),
-            Box::new(ImmutableDir::new(self.children(path))) as BlockingObjectPager,
+            Box::new(ImmutableDir::new(self.entries(path, &args))) as BlockingObjectPager,
         ))
     }
 }
@@ -405,4 +419,45 @@ mod tests {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_scan() -> Result<()> {
+        let _ = env_logger::try_init();
+
+        let mut iil = ImmutableIndexLayer::default();
+        for i in [
+            "dataset/stateful/ontime_2007_200.csv",
+            "dataset/stateful/ontime_2008_200.csv",
+            "dataset/stateless/ontime_2009_200.csv",
+            "readme.md",
+        ] {
+            iil.insert(i.to_string())
+        }
+
+        let op = Operator::from_iter(
+            Scheme::Http,
+            vec![("endpoint".to_string(), "https://xuanwo.io".to_string())].into_iter(),
+        )?
+        .layer(LoggingLayer::default())
+        .layer(iil);
+
+        let mut ds = op.object("dataset/").scan().await?;
+
+        let mut set = HashSet::new();
+        while let Some(entry) = ds.try_next().await? {
+            assert!(
+                set.insert(entry.path().to_string()),
+                "duplicated value: {}",
+                entry.path()
+            );
+        }
+
+        debug!("current files: {:?}", set);
+
+        assert_eq!(set.len(), 3);
+        assert!(set.contains("dataset/stateful/ontime_2007_200.csv"));
+        assert!(set.contains("dataset/stateful/ontime_2008_200.csv"));
+        assert!(set.contains("dataset/stateless/ontime_2009_200.csv"));
+        Ok(())
+    }
 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
 use std::fmt::Debug;
 use std::io;
 use std::io::Read;
@@ -19,10 +20,10 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use futures::AsyncRead;
-use log::debug;
 use log::log;
 use log::trace;
 use log::Level;
@@ -40,6 +41,12 @@ use crate::*;
 ///   - `finished`: the operation is successful.
 ///   - `errored`: the operation returns an expected error like `NotFound`.
 ///   - `failed`: the operation returns an unexpected error.
+/// - Every finished log entry carries the `elapsed` time of the operation,
+///   and error log entries also carry the error `kind` and whether it's
+///   `temporary`, so that retried errors can be distinguished.
+///
+/// Fields like `path`, `range` and `size` will be logged, but the content
+/// of read or written data will never be logged.
 ///
 /// # Todo
 ///
@@ -51,16 +58,22 @@ use crate::*;
 ///
 /// ```
 /// use anyhow::Result;
+/// use log::Level;
 /// use opendal::layers::LoggingLayer;
 /// use opendal::Operator;
 /// use opendal::Scheme;
 ///
 /// let _ = Operator::from_env(Scheme::Fs)
 ///     .expect("must init")
-///     .layer(LoggingLayer::default());
+///     .layer(
+///         LoggingLayer::default()
+///             .with_operation_level(Some(Level::Trace))
+///             .with_error_level(Some(Level::Info)),
+///     );
 /// ```
 #[derive(Debug, Copy, Clone)]
 pub struct LoggingLayer {
+    operation_level: Option<Level>,
     error_level: Option<Level>,
     failure_level: Option<Level>,
 }
@@ -68,6 +81,7 @@ pub struct LoggingLayer {
 impl Default for LoggingLayer {
     fn default() -> Self {
         Self {
+            operation_level: Some(Level::Debug),
             error_level: Some(Level::Warn),
             failure_level: Some(Level::Error),
         }
@@ -75,6 +89,15 @@ impl Default for LoggingLayer {
 }
 
 impl LoggingLayer {
+    /// Setting the log level of operations' normal progress like `started`
+    /// and `finished`.
+    ///
+    /// `None` means disable the log for these entries. Default to `Debug`.
+    pub fn with_operation_level(mut self, level: Option<Level>) -> Self {
+        self.operation_level = level;
+        self
+    }
+
     /// Setting the log level while expected error happened.
     ///
     /// For example: accessor returns ObjectNotFound.
@@ -103,6 +126,7 @@ impl Layer for LoggingLayer {
             scheme: meta.scheme(),
             inner,
 
+            operation_level: self.operation_level,
             error_level: self.error_level,
             failure_level: self.failure_level,
         })
@@ -114,28 +138,84 @@ struct LoggingAccessor {
     scheme: Scheme,
     inner: Arc<dyn Accessor>,
 
+    operation_level: Option<Level>,
     error_level: Option<Level>,
     failure_level: Option<Level>,
 }
 
 static LOGGING_TARGET: &str = "opendal::services";
 
+#[inline]
+fn err_status(err: &Error) -> &'static str {
+    if err.kind() == ErrorKind::Unexpected {
+        "failed"
+    } else {
+        "errored"
+    }
+}
+
+#[inline]
+fn err_level(
+    err: &Error,
+    error_level: Option<Level>,
+    failure_level: Option<Level>,
+) -> Option<Level> {
+    if err.kind() == ErrorKind::Unexpected {
+        failure_level
+    } else {
+        error_level
+    }
+}
+
 impl LoggingAccessor {
-    #[inline]
-    fn err_status(&self, err: &Error) -> &'static str {
-        if err.kind() == ErrorKind::Unexpected {
-            "failed"
-        } else {
-            "errored"
+    fn log_started(&self, op: Operation, fields: fmt::Arguments) {
+        if let Some(lvl) = self.operation_level {
+            log!(
+                target: LOGGING_TARGET,
+                lvl,
+                "service={} operation={} {} -> started",
+                self.scheme,
+                op,
+                fields
+            );
         }
     }
 
-    #[inline]
-    fn err_level(&self, err: &Error) -> Option<Level> {
-        if err.kind() == ErrorKind::Unexpected {
-            self.failure_level
-        } else {
-            self.error_level
+    fn log_finished(
+        &self,
+        op: Operation,
+        fields: fmt::Arguments,
+        start: Instant,
+        status: fmt::Arguments,
+    ) {
+        if let Some(lvl) = self.operation_level {
+            log!(
+                target: LOGGING_TARGET,
+                lvl,
+                "service={} operation={} {} elapsed={:?} -> {}",
+                self.scheme,
+                op,
+                fields,
+                start.elapsed(),
+                status
+            );
+        }
+    }
+
+    fn log_error(&self, op: Operation, fields: fmt::Arguments, start: Instant, err: &Error) {
+        if let Some(lvl) = err_level(err, self.error_level, self.failure_level) {
+            log!(
+                target: LOGGING_TARGET,
+                lvl,
+                "service={} operation={} {} elapsed={:?} kind={} temporary={} -> {}: {err:?}",
+                self.scheme,
+                op,
+                fields,
+                start.elapsed(),
+                err.kind(),
+                err.is_temporary(),
+                err_status(err)
+            );
         }
     }
 }
@@ -147,313 +227,267 @@ impl Accessor for LoggingAccessor {
     }
 
     fn metadata(&self) -> AccessorMetadata {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} -> started",
-            self.scheme,
-            Operation::Metadata
-        );
         let result = self.inner.metadata();
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} -> finished: {:?}",
-            self.scheme,
-            Operation::Metadata,
-            result
-        );
+        if let Some(lvl) = self.operation_level {
+            log!(
+                target: LOGGING_TARGET,
+                lvl,
+                "service={} operation={} -> finished: {:?}",
+                self.scheme,
+                Operation::Metadata,
+                result
+            );
+        }
 
         result
     }
 
     async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::Create,
-            path
-        );
+        let start = Instant::now();
+        self.log_started(Operation::Create, format_args!("path={path}"));
 
         self.inner
-            .create(path, args.clone())
+            .create(path, args)
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.scheme,
+                self.log_finished(
                     Operation::Create,
-                    path
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::Create,
-                        path,
-                        self.err_status(&err)
-                    )
-                }
+                self.log_error(Operation::Create, format_args!("path={path}"), start, &err);
                 err
             })
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} range={} -> started",
-            self.scheme,
+        let start = Instant::now();
+        self.log_started(
             Operation::Read,
-            path,
-            args.range()
+            format_args!("path={path} range={}", args.range()),
         );
 
         self.inner
             .read(path, args.clone())
             .await
             .map(|(rp, r)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} range={} -> got reader",
+                self.log_finished(
+                    Operation::Read,
+                    format_args!("path={path} range={}", args.range()),
+                    start,
+                    format_args!("got reader"),
+                );
+                let r = LoggingReader::new(
                     self.scheme,
                     Operation::Read,
                     path,
-                    args.range()
+                    args.range().size(),
+                    r,
+                    self.operation_level,
+                    self.failure_level,
                 );
-                (
-                    rp,
-                    Box::new(LoggingReader::new(
-                        self.scheme,
-                        Operation::Read,
-                        path,
-                        args.range().size(),
-                        r,
-                        self.failure_level,
-                    )) as BytesReader,
-                )
+                (rp, Box::new(r) as BytesReader)
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} range={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::Read,
-                        path,
-                        args.range(),
-                        self.err_status(&err)
-                    )
-                }
+                self.log_error(
+                    Operation::Read,
+                    format_args!("path={path} range={}", args.range()),
+                    start,
+                    &err,
+                );
                 err
             })
     }
 
     async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} size={:?} -> started",
-            self.scheme,
+        let start = Instant::now();
+        self.log_started(
             Operation::Write,
-            path,
-            args.size()
+            format_args!("path={path} size={}", args.size()),
         );
 
-        let reader = LoggingReader::new(
+        let r = Box::new(LoggingReader::new(
             self.scheme,
             Operation::Write,
             path,
             Some(args.size()),
             r,
+            self.operation_level,
             self.failure_level,
-        );
-        let r = Box::new(reader) as BytesReader;
+        )) as BytesReader;
 
         self.inner
             .write(path, args.clone(), r)
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} size={:?} -> written",
-                    self.scheme,
+                self.log_finished(
                     Operation::Write,
-                    path,
-                    args.size()
+                    format_args!("path={path} size={}", args.size()),
+                    start,
+                    format_args!("written"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} size={:?} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::Write,
-                        path,
-                        args.size(),
-                        self.err_status(&err)
-                    )
-                }
+                self.log_error(
+                    Operation::Write,
+                    format_args!("path={path} size={}", args.size()),
+                    start,
+                    &err,
+                );
                 err
             })
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::Stat,
-            path
-        );
+        let start = Instant::now();
+        self.log_started(Operation::Stat, format_args!("path={path}"));
 
         self.inner
-            .stat(path, args.clone())
+            .stat(path, args)
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished: {v:?}",
-                    self.scheme,
+                self.log_finished(
                     Operation::Stat,
-                    path
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished: {v:?}"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::Stat,
-                        path,
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(Operation::Stat, format_args!("path={path}"), start, &err);
                 err
             })
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::Delete,
-            path
-        );
+        let start = Instant::now();
+        self.log_started(Operation::Delete, format_args!("path={path}"));
 
         self.inner
-            .delete(path, args.clone())
+            .delete(path, args)
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.scheme,
+                self.log_finished(
                     Operation::Delete,
-                    path
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::Delete,
-                        path,
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(Operation::Delete, format_args!("path={path}"), start, &err);
+                err
+            })
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let start = Instant::now();
+        self.log_started(Operation::Copy, format_args!("from={from} to={to}"));
+
+        self.inner
+            .copy(from, to, args)
+            .await
+            .map(|v| {
+                self.log_finished(
+                    Operation::Copy,
+                    format_args!("from={from} to={to}"),
+                    start,
+                    format_args!("finished"),
+                );
+                v
+            })
+            .map_err(|err| {
+                self.log_error(
+                    Operation::Copy,
+                    format_args!("from={from} to={to}"),
+                    start,
+                    &err,
+                );
+                err
+            })
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let start = Instant::now();
+        self.log_started(Operation::Rename, format_args!("from={from} to={to}"));
+
+        self.inner
+            .rename(from, to, args)
+            .await
+            .map(|v| {
+                self.log_finished(
+                    Operation::Rename,
+                    format_args!("from={from} to={to}"),
+                    start,
+                    format_args!("finished"),
+                );
+                v
+            })
+            .map_err(|err| {
+                self.log_error(
+                    Operation::Rename,
+                    format_args!("from={from} to={to}"),
+                    start,
+                    &err,
+                );
                 err
             })
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::List,
-            path
-        );
+        let start = Instant::now();
+        self.log_started(Operation::List, format_args!("path={path}"));
 
         self.inner
             .list(path, args)
             .await
             .map(|(rp, v)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> start listing dir",
-                    self.scheme,
+                self.log_finished(
                     Operation::List,
-                    path
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("start listing dir"),
+                );
+                let pager = LoggingPager::new(
+                    self.scheme,
+                    path,
+                    v,
+                    self.operation_level,
+                    self.error_level,
+                    self.failure_level,
                 );
-                let streamer =
-                    LoggingPager::new(self.scheme, path, v, self.error_level, self.failure_level);
-                (rp, Box::new(streamer) as ObjectPager)
+                (rp, Box::new(pager) as ObjectPager)
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::List,
-                        path,
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(Operation::List, format_args!("path={path}"), start, &err);
                 err
             })
     }
 
     fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::Presign,
-            path
-        );
+        let start = Instant::now();
+        self.log_started(Operation::Presign, format_args!("path={path}"));
 
         self.inner
             .presign(path, args)
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished: {v:?}",
-                    self.scheme,
+                self.log_finished(
                     Operation::Presign,
-                    path
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished: {v:?}"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::Presign,
-                        path,
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(Operation::Presign, format_args!("path={path}"), start, &err);
                 err
             })
     }
@@ -463,39 +497,28 @@ impl Accessor for LoggingAccessor {
         path: &str,
         args: OpCreateMultipart,
     ) -> Result<RpCreateMultipart> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::CreateMultipart,
-            path
-        );
+        let start = Instant::now();
+        self.log_started(Operation::CreateMultipart, format_args!("path={path}"));
 
         self.inner
-            .create_multipart(path, args.clone())
+            .create_multipart(path, args)
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.scheme,
+                self.log_finished(
                     Operation::CreateMultipart,
-                    path
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::CreateMultipart,
-                        path,
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(
+                    Operation::CreateMultipart,
+                    format_args!("path={path}"),
+                    start,
+                    &err,
+                );
                 err
             })
     }
@@ -506,58 +529,56 @@ impl Accessor for LoggingAccessor {
         args: OpWriteMultipart,
         r: BytesReader,
     ) -> Result<RpWriteMultipart> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} upload_id={} part_number={:?} size={:?} -> started",
-            self.scheme,
+        let start = Instant::now();
+        self.log_started(
             Operation::WriteMultipart,
-            path,
-            args.upload_id(),
-            args.part_number(),
-            args.size()
+            format_args!(
+                "path={path} upload_id={} part_number={} size={}",
+                args.upload_id(),
+                args.part_number(),
+                args.size()
+            ),
         );
 
-        let r = LoggingReader::new(
+        let r = Box::new(LoggingReader::new(
             self.scheme,
-            Operation::Write,
+            Operation::WriteMultipart,
             path,
             Some(args.size()),
             r,
+            self.operation_level,
             self.failure_level,
-        );
-        let r = Box::new(r);
+        )) as BytesReader;
 
         self.inner
             .write_multipart(path, args.clone(), r)
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} upload_id={} part_number={:?} size={:?} -> written",
-                    self.scheme,
+                self.log_finished(
                     Operation::WriteMultipart,
-                    path,
-                    args.upload_id(),
-                    args.part_number(),
-                    args.size()
+                    format_args!(
+                        "path={path} upload_id={} part_number={} size={}",
+                        args.upload_id(),
+                        args.part_number(),
+                        args.size()
+                    ),
+                    start,
+                    format_args!("written"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                       "service={} operation={} path={} upload_id={} part_number={:?} size={:?} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::WriteMultipart,
-                        path,
+                self.log_error(
+                    Operation::WriteMultipart,
+                    format_args!(
+                        "path={path} upload_id={} part_number={} size={}",
                         args.upload_id(),
                         args.part_number(),
-                        args.size(),
-                        self.err_status(&err)
-                    );
-                }
+                        args.size()
+                    ),
+                    start,
+                    &err,
+                );
                 err
             })
     }
@@ -567,42 +588,31 @@ impl Accessor for LoggingAccessor {
         path: &str,
         args: OpCompleteMultipart,
     ) -> Result<RpCompleteMultipart> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} upload_id={} -> started",
-            self.scheme,
+        let start = Instant::now();
+        self.log_started(
             Operation::CompleteMultipart,
-            path,
-            args.upload_id(),
+            format_args!("path={path} upload_id={}", args.upload_id()),
         );
 
         self.inner
             .complete_multipart(path, args.clone())
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} upload_id={} -> finished",
-                    self.scheme,
+                self.log_finished(
                     Operation::CompleteMultipart,
-                    path,
-                    args.upload_id()
+                    format_args!("path={path} upload_id={}", args.upload_id()),
+                    start,
+                    format_args!("finished"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} upload_id={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::CompleteMultipart,
-                        path,
-                        args.upload_id(),
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(
+                    Operation::CompleteMultipart,
+                    format_args!("path={path} upload_id={}", args.upload_id()),
+                    start,
+                    &err,
+                );
                 err
             })
     }
@@ -612,103 +622,232 @@ impl Accessor for LoggingAccessor {
         path: &str,
         args: OpAbortMultipart,
     ) -> Result<RpAbortMultipart> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} upload_id={} -> started",
-            self.scheme,
+        let start = Instant::now();
+        self.log_started(
             Operation::AbortMultipart,
-            path,
-            args.upload_id()
+            format_args!("path={path} upload_id={}", args.upload_id()),
         );
 
         self.inner
             .abort_multipart(path, args.clone())
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} upload_id={} -> finished",
-                    self.scheme,
+                self.log_finished(
                     Operation::AbortMultipart,
-                    path,
-                    args.upload_id()
+                    format_args!("path={path} upload_id={}", args.upload_id()),
+                    start,
+                    format_args!("finished"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} upload_id={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::AbortMultipart,
-                        path,
-                        args.upload_id(),
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(
+                    Operation::AbortMultipart,
+                    format_args!("path={path} upload_id={}", args.upload_id()),
+                    start,
+                    &err,
+                );
+                err
+            })
+    }
+
+    async fn list_multipart(&self, path: &str, args: OpListMultipart) -> Result<RpListMultipart> {
+        let start = Instant::now();
+        self.log_started(Operation::ListMultipart, format_args!("path={path}"));
+
+        self.inner
+            .list_multipart(path, args)
+            .await
+            .map(|v| {
+                self.log_finished(
+                    Operation::ListMultipart,
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished: {} uploads", v.uploads().len()),
+                );
+                v
+            })
+            .map_err(|err| {
+                self.log_error(
+                    Operation::ListMultipart,
+                    format_args!("path={path}"),
+                    start,
+                    &err,
+                );
+                err
+            })
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        let start = Instant::now();
+        self.log_started(Operation::ListVersions, format_args!("path={path}"));
+
+        self.inner
+            .list_versions(path, args)
+            .await
+            .map(|v| {
+                self.log_finished(
+                    Operation::ListVersions,
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished: {} versions", v.versions().len()),
+                );
+                v
+            })
+            .map_err(|err| {
+                self.log_error(
+                    Operation::ListVersions,
+                    format_args!("path={path}"),
+                    start,
+                    &err,
+                );
+                err
+            })
+    }
+
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let start = Instant::now();
+        self.log_started(Operation::GetTags, format_args!("path={path}"));
+
+        self.inner
+            .get_tags(path, args)
+            .await
+            .map(|v| {
+                self.log_finished(
+                    Operation::GetTags,
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished: {} tags", v.tags().len()),
+                );
+                v
+            })
+            .map_err(|err| {
+                self.log_error(Operation::GetTags, format_args!("path={path}"), start, &err);
+                err
+            })
+    }
+
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        let start = Instant::now();
+        self.log_started(Operation::SetTags, format_args!("path={path}"));
+
+        self.inner
+            .set_tags(path, args)
+            .await
+            .map(|v| {
+                self.log_finished(
+                    Operation::SetTags,
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished"),
+                );
+                v
+            })
+            .map_err(|err| {
+                self.log_error(Operation::SetTags, format_args!("path={path}"), start, &err);
+                err
+            })
+    }
+
+    async fn delete_tags(&self, path: &str, args: OpDeleteTags) -> Result<RpDeleteTags> {
+        let start = Instant::now();
+        self.log_started(Operation::DeleteTags, format_args!("path={path}"));
+
+        self.inner
+            .delete_tags(path, args)
+            .await
+            .map(|v| {
+                self.log_finished(
+                    Operation::DeleteTags,
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished"),
+                );
+                v
+            })
+            .map_err(|err| {
+                self.log_error(
+                    Operation::DeleteTags,
+                    format_args!("path={path}"),
+                    start,
+                    &err,
+                );
+                err
+            })
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let (op, count) = match args.operation() {
+            BatchOperations::Delete(ops) => ("delete", ops.len()),
+        };
+
+        let start = Instant::now();
+        self.log_started(Operation::Batch, format_args!("op={op} count={count}"));
+
+        self.inner
+            .batch(args)
+            .await
+            .map(|v| {
+                self.log_finished(
+                    Operation::Batch,
+                    format_args!("op={op} count={count}"),
+                    start,
+                    format_args!("finished"),
+                );
+                v
+            })
+            .map_err(|err| {
+                self.log_error(
+                    Operation::Batch,
+                    format_args!("op={op} count={count}"),
+                    start,
+                    &err,
+                );
                 err
             })
     }
 
     fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::BlockingCreate,
-            path
-        );
+        let start = Instant::now();
+        self.log_started(Operation::BlockingCreate, format_args!("path={path}"));
 
         self.inner
             .blocking_create(path, args)
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.scheme,
+                self.log_finished(
                     Operation::BlockingCreate,
-                    path
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::BlockingCreate,
-                        path,
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(
+                    Operation::BlockingCreate,
+                    format_args!("path={path}"),
+                    start,
+                    &err,
+                );
                 err
             })
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, BlockingBytesReader)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} range={} -> started",
-            self.scheme,
+        let start = Instant::now();
+        self.log_started(
             Operation::BlockingRead,
-            path,
-            args.range(),
+            format_args!("path={path} range={}", args.range()),
         );
 
         self.inner
             .blocking_read(path, args.clone())
             .map(|(rp, r)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} range={} -> got reader",
-                    self.scheme,
+                self.log_finished(
                     Operation::BlockingRead,
-                    path,
-                    args.range(),
+                    format_args!("path={path} range={}", args.range()),
+                    start,
+                    format_args!("got reader"),
                 );
                 let r = BlockingLoggingReader::new(
                     self.scheme,
@@ -716,191 +855,169 @@ impl Accessor for LoggingAccessor {
                     path,
                     args.range().size(),
                     r,
+                    self.operation_level,
                     self.failure_level,
                 );
                 (rp, Box::new(r) as BlockingBytesReader)
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} range={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::BlockingRead,
-                        path,
-                        args.range(),
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(
+                    Operation::BlockingRead,
+                    format_args!("path={path} range={}", args.range()),
+                    start,
+                    &err,
+                );
                 err
             })
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite, r: BlockingBytesReader) -> Result<RpWrite> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} size={:?} -> started",
-            self.scheme,
+        let start = Instant::now();
+        self.log_started(
             Operation::BlockingWrite,
-            path,
-            args.size()
+            format_args!("path={path} size={}", args.size()),
         );
 
-        let reader = BlockingLoggingReader::new(
+        let r = Box::new(BlockingLoggingReader::new(
             self.scheme,
             Operation::BlockingWrite,
             path,
             Some(args.size()),
             r,
+            self.operation_level,
             self.failure_level,
-        );
-        let r = Box::new(reader) as BlockingBytesReader;
+        )) as BlockingBytesReader;
 
         self.inner
             .blocking_write(path, args.clone(), r)
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} size={:?} -> written",
-                    self.scheme,
+                self.log_finished(
                     Operation::BlockingWrite,
-                    path,
-                    args.size()
+                    format_args!("path={path} size={}", args.size()),
+                    start,
+                    format_args!("written"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} size={:?} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::BlockingWrite,
-                        path,
-                        args.size(),
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(
+                    Operation::BlockingWrite,
+                    format_args!("path={path} size={}", args.size()),
+                    start,
+                    &err,
+                );
                 err
             })
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::BlockingStat,
-            path
-        );
+        let start = Instant::now();
+        self.log_started(Operation::BlockingStat, format_args!("path={path}"));
 
         self.inner
             .blocking_stat(path, args)
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished: {v:?}",
-                    self.scheme,
+                self.log_finished(
                     Operation::BlockingStat,
-                    path
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished: {v:?}"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::BlockingStat,
-                        path,
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(
+                    Operation::BlockingStat,
+                    format_args!("path={path}"),
+                    start,
+                    &err,
+                );
                 err
             })
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::BlockingDelete,
-            path
-        );
+        let start = Instant::now();
+        self.log_started(Operation::BlockingDelete, format_args!("path={path}"));
 
         self.inner
             .blocking_delete(path, args)
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.scheme,
+                self.log_finished(
                     Operation::BlockingDelete,
-                    path
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("finished"),
                 );
                 v
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::BlockingDelete,
-                        path,
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(
+                    Operation::BlockingDelete,
+                    format_args!("path={path}"),
+                    start,
+                    &err,
+                );
+                err
+            })
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let start = Instant::now();
+        self.log_started(Operation::BlockingCopy, format_args!("from={from} to={to}"));
+
+        self.inner
+            .blocking_copy(from, to, args)
+            .map(|v| {
+                self.log_finished(
+                    Operation::BlockingCopy,
+                    format_args!("from={from} to={to}"),
+                    start,
+                    format_args!("finished"),
+                );
+                v
+            })
+            .map_err(|err| {
+                self.log_error(
+                    Operation::BlockingCopy,
+                    format_args!("from={from} to={to}"),
+                    start,
+                    &err,
+                );
                 err
             })
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::BlockingList,
-            path
-        );
+        let start = Instant::now();
+        self.log_started(Operation::BlockingList, format_args!("path={path}"));
 
         self.inner
             .blocking_list(path, args)
             .map(|(rp, v)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> got dir",
-                    self.scheme,
+                self.log_finished(
                     Operation::BlockingList,
-                    path
+                    format_args!("path={path}"),
+                    start,
+                    format_args!("got dir"),
                 );
-                let li = BlockingLoggingPager::new(
+                let pager = BlockingLoggingPager::new(
                     self.scheme,
                     path,
                     v,
+                    self.operation_level,
                     self.error_level,
                     self.failure_level,
                 );
-                (rp, Box::new(li) as BlockingObjectPager)
+                (rp, Box::new(pager) as BlockingObjectPager)
             })
             .map_err(|err| {
-                if let Some(lvl) = self.err_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
-                        self.scheme,
-                        Operation::BlockingList,
-                        path,
-                        self.err_status(&err)
-                    );
-                }
+                self.log_error(
+                    Operation::BlockingList,
+                    format_args!("path={path}"),
+                    start,
+                    &err,
+                );
                 err
             })
     }
@@ -914,6 +1031,8 @@ struct LoggingReader {
 
     size: Option<u64>,
     has_read: u64,
+    start: Instant,
+    operation_level: Option<Level>,
     failure_level: Option<Level>,
 
     inner: BytesReader,
@@ -926,6 +1045,7 @@ impl LoggingReader {
         path: &str,
         size: Option<u64>,
         reader: BytesReader,
+        operation_level: Option<Level>,
         failure_level: Option<Level>,
     ) -> Self {
         Self {
@@ -935,38 +1055,30 @@ impl LoggingReader {
 
             size,
             has_read: 0,
+            start: Instant::now(),
+            operation_level,
+            failure_level,
 
             inner: reader,
-            failure_level,
         }
     }
 }
 
 impl Drop for LoggingReader {
     fn drop(&mut self) {
-        if let Some(size) = self.size {
-            if size == self.has_read {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} has_read={} -> consumed reader fully",
-                    self.scheme,
-                    self.op,
-                    self.path,
-                    self.has_read
-                );
-
-                return;
-            }
+        if let Some(lvl) = self.operation_level {
+            log!(
+                target: LOGGING_TARGET,
+                lvl,
+                "service={} operation={} path={} has_read={} elapsed={:?} -> {}",
+                self.scheme,
+                self.op,
+                self.path,
+                self.has_read,
+                self.start.elapsed(),
+                reader_status(self.size, self.has_read)
+            );
         }
-
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} has_read={} -> dropped reader",
-            self.scheme,
-            self.op,
-            self.path,
-            self.has_read
-        );
     }
 }
 
@@ -997,11 +1109,12 @@ impl AsyncRead for LoggingReader {
                         log!(
                             target: LOGGING_TARGET,
                             lvl,
-                            "service={} operation={} path={} has_read={} -> failed: {err:?}",
+                            "service={} operation={} path={} has_read={} elapsed={:?} -> failed: {err:?}",
                             self.scheme,
                             self.op,
                             self.path,
                             self.has_read,
+                            self.start.elapsed(),
                         )
                     }
                     Poll::Ready(Err(err))
@@ -1030,9 +1143,11 @@ struct BlockingLoggingReader {
 
     size: Option<u64>,
     has_read: u64,
+    start: Instant,
+    operation_level: Option<Level>,
+    failure_level: Option<Level>,
 
     inner: BlockingBytesReader,
-    failure_level: Option<Level>,
 }
 
 impl BlockingLoggingReader {
@@ -1042,6 +1157,7 @@ impl BlockingLoggingReader {
         path: &str,
         size: Option<u64>,
         reader: BlockingBytesReader,
+        operation_level: Option<Level>,
         failure_level: Option<Level>,
     ) -> Self {
         Self {
@@ -1051,37 +1167,30 @@ impl BlockingLoggingReader {
 
             size,
             has_read: 0,
-            inner: reader,
+            start: Instant::now(),
+            operation_level,
             failure_level,
+
+            inner: reader,
         }
     }
 }
 
 impl Drop for BlockingLoggingReader {
     fn drop(&mut self) {
-        if let Some(size) = self.size {
-            if size == self.has_read {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} has_read={} -> consumed reader fully",
-                    self.scheme,
-                    self.op,
-                    self.path,
-                    self.has_read
-                );
-
-                return;
-            }
+        if let Some(lvl) = self.operation_level {
+            log!(
+                target: LOGGING_TARGET,
+                lvl,
+                "service={} operation={} path={} has_read={} elapsed={:?} -> {}",
+                self.scheme,
+                self.op,
+                self.path,
+                self.has_read,
+                self.start.elapsed(),
+                reader_status(self.size, self.has_read)
+            );
         }
-
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} has_read={} -> dropped reader",
-            self.scheme,
-            self.op,
-            self.path,
-            self.has_read
-        );
     }
 }
 
@@ -1107,11 +1216,12 @@ impl Read for BlockingLoggingReader {
                     log!(
                         target: LOGGING_TARGET,
                         lvl,
-                        "service={} operation={} path={} has_read={} -> failed: {err:?}",
+                        "service={} operation={} path={} has_read={} elapsed={:?} -> failed: {err:?}",
                         self.scheme,
                         self.op,
                         self.path,
                         self.has_read,
+                        self.start.elapsed(),
                     );
                 }
                 Err(err)
@@ -1120,11 +1230,22 @@ impl Read for BlockingLoggingReader {
     }
 }
 
+#[inline]
+fn reader_status(size: Option<u64>, has_read: u64) -> &'static str {
+    if size == Some(has_read) {
+        "consumed reader fully"
+    } else {
+        "dropped reader"
+    }
+}
+
 struct LoggingPager {
     scheme: Scheme,
     path: String,
     finished: bool,
+    start: Instant,
     inner: ObjectPager,
+    operation_level: Option<Level>,
     error_level: Option<Level>,
     failure_level: Option<Level>,
 }
@@ -1134,6 +1255,7 @@ impl LoggingPager {
         scheme: Scheme,
         path: &str,
         inner: ObjectPager,
+        operation_level: Option<Level>,
         error_level: Option<Level>,
         failure_level: Option<Level>,
     ) -> Self {
@@ -1141,7 +1263,9 @@ impl LoggingPager {
             scheme,
             path: path.to_string(),
             finished: false,
+            start: Instant::now(),
             inner,
+            operation_level,
             error_level,
             failure_level,
         }
@@ -1150,46 +1274,25 @@ impl LoggingPager {
 
 impl Drop for LoggingPager {
     fn drop(&mut self) {
-        if self.finished {
-            debug!(
-                target: LOGGING_TARGET,
-                "service={} operation={} path={} -> consumed dir fully",
-                self.scheme,
-                Operation::List,
-                self.path
-            );
-        } else {
-            debug!(
+        if let Some(lvl) = self.operation_level {
+            log!(
                 target: LOGGING_TARGET,
-                "service={} operation={} path={} -> dropped dir",
+                lvl,
+                "service={} operation={} path={} elapsed={:?} -> {}",
                 self.scheme,
                 Operation::List,
-                self.path
+                self.path,
+                self.start.elapsed(),
+                if self.finished {
+                    "consumed dir fully"
+                } else {
+                    "dropped dir"
+                }
             );
         }
     }
 }
 
-impl LoggingPager {
-    #[inline]
-    fn err_status(&self, err: &Error) -> &'static str {
-        if err.kind() == ErrorKind::Unexpected {
-            "failed"
-        } else {
-            "errored"
-        }
-    }
-
-    #[inline]
-    fn err_level(&self, err: &Error) -> Option<Level> {
-        if err.kind() == ErrorKind::Unexpected {
-            self.failure_level
-        } else {
-            self.error_level
-        }
-    }
-}
-
 #[async_trait]
 impl ObjectPage for LoggingPager {
     async fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
@@ -1197,35 +1300,45 @@ impl ObjectPage for LoggingPager {
 
         match &res {
             Ok(Some(des)) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> listed {} entries",
-                    self.scheme,
-                    Operation::List,
-                    self.path,
-                    des.len(),
-                );
+                if let Some(lvl) = self.operation_level {
+                    log!(
+                        target: LOGGING_TARGET,
+                        lvl,
+                        "service={} operation={} path={} -> listed {} entries",
+                        self.scheme,
+                        Operation::List,
+                        self.path,
+                        des.len(),
+                    );
+                }
             }
             Ok(None) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.scheme,
-                    Operation::List,
-                    self.path
-                );
+                if let Some(lvl) = self.operation_level {
+                    log!(
+                        target: LOGGING_TARGET,
+                        lvl,
+                        "service={} operation={} path={} elapsed={:?} -> finished",
+                        self.scheme,
+                        Operation::List,
+                        self.path,
+                        self.start.elapsed(),
+                    );
+                }
                 self.finished = true;
             }
             Err(err) => {
-                if let Some(lvl) = self.err_level(err) {
+                if let Some(lvl) = err_level(err, self.error_level, self.failure_level) {
                     log!(
                         target: LOGGING_TARGET,
                         lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
+                        "service={} operation={} path={} elapsed={:?} kind={} temporary={} -> {}: {err:?}",
                         self.scheme,
                         Operation::List,
                         self.path,
-                        self.err_status(err)
+                        self.start.elapsed(),
+                        err.kind(),
+                        err.is_temporary(),
+                        err_status(err)
                     )
                 }
             }
@@ -1239,7 +1352,9 @@ struct BlockingLoggingPager {
     scheme: Scheme,
     path: String,
     finished: bool,
+    start: Instant,
     inner: BlockingObjectPager,
+    operation_level: Option<Level>,
     error_level: Option<Level>,
     failure_level: Option<Level>,
 }
@@ -1249,6 +1364,7 @@ impl BlockingLoggingPager {
         scheme: Scheme,
         path: &str,
         inner: BlockingObjectPager,
+        operation_level: Option<Level>,
         error_level: Option<Level>,
         failure_level: Option<Level>,
     ) -> Self {
@@ -1256,7 +1372,9 @@ impl BlockingLoggingPager {
             scheme,
             path: path.to_string(),
             finished: false,
+            start: Instant::now(),
             inner,
+            operation_level,
             error_level,
             failure_level,
         }
@@ -1265,81 +1383,70 @@ impl BlockingLoggingPager {
 
 impl Drop for BlockingLoggingPager {
     fn drop(&mut self) {
-        if self.finished {
-            debug!(
+        if let Some(lvl) = self.operation_level {
+            log!(
                 target: LOGGING_TARGET,
-                "service={} operation={} path={} -> consumed dir fully",
+                lvl,
+                "service={} operation={} path={} elapsed={:?} -> {}",
                 self.scheme,
                 Operation::BlockingList,
-                self.path
-            );
-        } else {
-            debug!(
-                target: LOGGING_TARGET,
-                "service={} operation={} path={} -> dropped dir",
-                self.scheme,
-                Operation::BlockingList,
-                self.path
+                self.path,
+                self.start.elapsed(),
+                if self.finished {
+                    "consumed dir fully"
+                } else {
+                    "dropped dir"
+                }
             );
         }
     }
 }
 
-impl BlockingLoggingPager {
-    #[inline]
-    fn err_status(&self, err: &Error) -> &'static str {
-        if err.kind() == ErrorKind::Unexpected {
-            "failed"
-        } else {
-            "errored"
-        }
-    }
-
-    #[inline]
-    fn err_level(&self, err: &Error) -> Option<Level> {
-        if err.kind() == ErrorKind::Unexpected {
-            self.failure_level
-        } else {
-            self.error_level
-        }
-    }
-}
-
 impl BlockingObjectPage for BlockingLoggingPager {
     fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
         let res = self.inner.next_page();
 
         match &res {
             Ok(Some(des)) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> got {} entries",
-                    self.scheme,
-                    Operation::BlockingList,
-                    self.path,
-                    des.len(),
-                );
+                if let Some(lvl) = self.operation_level {
+                    log!(
+                        target: LOGGING_TARGET,
+                        lvl,
+                        "service={} operation={} path={} -> got {} entries",
+                        self.scheme,
+                        Operation::BlockingList,
+                        self.path,
+                        des.len(),
+                    );
+                }
             }
             Ok(None) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.scheme,
-                    Operation::BlockingList,
-                    self.path
-                );
+                if let Some(lvl) = self.operation_level {
+                    log!(
+                        target: LOGGING_TARGET,
+                        lvl,
+                        "service={} operation={} path={} elapsed={:?} -> finished",
+                        self.scheme,
+                        Operation::BlockingList,
+                        self.path,
+                        self.start.elapsed(),
+                    );
+                }
                 self.finished = true;
             }
             Err(err) => {
-                if let Some(lvl) = self.err_level(err) {
+                if let Some(lvl) = err_level(err, self.error_level, self.failure_level) {
                     log!(
                         target: LOGGING_TARGET,
                         lvl,
-                        "service={} operation={} path={} -> {}: {err:?}",
+                        "service={} operation={} path={} elapsed={:?} kind={} temporary={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingList,
                         self.path,
-                        self.err_status(err)
+                        self.start.elapsed(),
+                        err.kind(),
+                        err.is_temporary(),
+                        err_status(err)
                     )
                 }
             }
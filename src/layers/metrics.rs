@@ -60,6 +60,12 @@ static LABEL_ERROR_KIND: &str = "error_kind";
 /// - `error_kind`: [`ErrorKind`] of counted errors.
 ///   - only available for `opendal_errors_total`
 ///
+/// # Recorders
+///
+/// MetricsLayer only records via the [`metrics`] facade, so users need to
+/// install a recorder like `metrics-exporter-prometheus` to export them.
+/// Metric names follow prometheus naming conventions.
+///
 /// # Examples
 ///
 /// ```
@@ -114,6 +120,12 @@ struct MetricsHandler {
     requests_total_delete: Counter,
     requests_duration_seconds_delete: Histogram,
 
+    requests_total_copy: Counter,
+    requests_duration_seconds_copy: Histogram,
+
+    requests_total_rename: Counter,
+    requests_duration_seconds_rename: Histogram,
+
     requests_total_list: Counter,
     requests_duration_seconds_list: Histogram,
 
@@ -133,6 +145,24 @@ struct MetricsHandler {
     requests_total_abort_multipart: Counter,
     requests_duration_seconds_abort_multipart: Histogram,
 
+    requests_total_list_multipart: Counter,
+    requests_duration_seconds_list_multipart: Histogram,
+
+    requests_total_list_versions: Counter,
+    requests_duration_seconds_list_versions: Histogram,
+
+    requests_total_get_tags: Counter,
+    requests_duration_seconds_get_tags: Histogram,
+
+    requests_total_set_tags: Counter,
+    requests_duration_seconds_set_tags: Histogram,
+
+    requests_total_delete_tags: Counter,
+    requests_duration_seconds_delete_tags: Histogram,
+
+    requests_total_batch: Counter,
+    requests_duration_seconds_batch: Histogram,
+
     requests_total_blocking_create: Counter,
     requests_duration_seconds_blocking_create: Histogram,
 
@@ -150,6 +180,9 @@ struct MetricsHandler {
     requests_total_blocking_delete: Counter,
     requests_duration_seconds_blocking_delete: Histogram,
 
+    requests_total_blocking_copy: Counter,
+    requests_duration_seconds_blocking_copy: Histogram,
+
     requests_total_blocking_list: Counter,
     requests_duration_seconds_blocking_list: Histogram,
 }
@@ -235,6 +268,28 @@ impl MetricsHandler {
                 LABEL_OPERATION => Operation::Delete.into_static(),
             ),
 
+            requests_total_copy: register_counter!(
+                METRIC_REQUESTS_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::Copy.into_static(),
+            ),
+            requests_duration_seconds_copy: register_histogram!(
+                METRIC_REQUESTS_DURATION_SECONDS,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::Copy.into_static(),
+            ),
+
+            requests_total_rename: register_counter!(
+                METRIC_REQUESTS_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::Rename.into_static(),
+            ),
+            requests_duration_seconds_rename: register_histogram!(
+                METRIC_REQUESTS_DURATION_SECONDS,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::Rename.into_static(),
+            ),
+
             requests_total_list: register_counter!(
                 METRIC_REQUESTS_TOTAL,
                 LABEL_SERVICE => service,
@@ -307,6 +362,72 @@ impl MetricsHandler {
                 LABEL_OPERATION => Operation::AbortMultipart.into_static(),
             ),
 
+            requests_total_list_multipart: register_counter!(
+                METRIC_REQUESTS_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::ListMultipart.into_static(),
+            ),
+            requests_duration_seconds_list_multipart: register_histogram!(
+                METRIC_REQUESTS_DURATION_SECONDS,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::ListMultipart.into_static(),
+            ),
+
+            requests_total_list_versions: register_counter!(
+                METRIC_REQUESTS_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::ListVersions.into_static(),
+            ),
+            requests_duration_seconds_list_versions: register_histogram!(
+                METRIC_REQUESTS_DURATION_SECONDS,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::ListVersions.into_static(),
+            ),
+
+            requests_total_get_tags: register_counter!(
+                METRIC_REQUESTS_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::GetTags.into_static(),
+            ),
+            requests_duration_seconds_get_tags: register_histogram!(
+                METRIC_REQUESTS_DURATION_SECONDS,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::GetTags.into_static(),
+            ),
+
+            requests_total_set_tags: register_counter!(
+                METRIC_REQUESTS_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::SetTags.into_static(),
+            ),
+            requests_duration_seconds_set_tags: register_histogram!(
+                METRIC_REQUESTS_DURATION_SECONDS,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::SetTags.into_static(),
+            ),
+
+            requests_total_delete_tags: register_counter!(
+                METRIC_REQUESTS_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::DeleteTags.into_static(),
+            ),
+            requests_duration_seconds_delete_tags: register_histogram!(
+                METRIC_REQUESTS_DURATION_SECONDS,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::DeleteTags.into_static(),
+            ),
+
+            requests_total_batch: register_counter!(
+                METRIC_REQUESTS_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::Batch.into_static(),
+            ),
+            requests_duration_seconds_batch: register_histogram!(
+                METRIC_REQUESTS_DURATION_SECONDS,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::Batch.into_static(),
+            ),
+
             requests_total_blocking_create: register_counter!(
                 METRIC_REQUESTS_TOTAL,
                 LABEL_SERVICE => service,
@@ -372,6 +493,17 @@ impl MetricsHandler {
                 LABEL_OPERATION => Operation::BlockingDelete.into_static(),
             ),
 
+            requests_total_blocking_copy: register_counter!(
+                METRIC_REQUESTS_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::BlockingCopy.into_static(),
+            ),
+            requests_duration_seconds_blocking_copy: register_histogram!(
+                METRIC_REQUESTS_DURATION_SECONDS,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::BlockingCopy.into_static(),
+            ),
+
             requests_total_blocking_list: register_counter!(
                 METRIC_REQUESTS_TOTAL,
                 LABEL_SERVICE => service,
@@ -472,6 +604,10 @@ impl Accessor for MetricsAccessor {
         });
 
         result.map_err(|e| {
+            // Reader is not returned, so we record its duration here.
+            self.handle
+                .requests_duration_seconds_read
+                .record(start.elapsed().as_secs_f64());
             self.handle
                 .increment_errors_total(Operation::Read, e.kind());
             e
@@ -535,6 +671,38 @@ impl Accessor for MetricsAccessor {
         })
     }
 
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.handle.requests_total_copy.increment(1);
+
+        let start = Instant::now();
+        let result = self.inner.copy(from, to, args).await;
+        let dur = start.elapsed().as_secs_f64();
+
+        self.handle.requests_duration_seconds_copy.record(dur);
+
+        result.map_err(|e| {
+            self.handle
+                .increment_errors_total(Operation::Copy, e.kind());
+            e
+        })
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.handle.requests_total_rename.increment(1);
+
+        let start = Instant::now();
+        let result = self.inner.rename(from, to, args).await;
+        let dur = start.elapsed().as_secs_f64();
+
+        self.handle.requests_duration_seconds_rename.record(dur);
+
+        result.map_err(|e| {
+            self.handle
+                .increment_errors_total(Operation::Rename, e.kind());
+            e
+        })
+    }
+
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         self.handle.requests_total_list.increment(1);
 
@@ -667,6 +835,108 @@ impl Accessor for MetricsAccessor {
         })
     }
 
+    async fn list_multipart(&self, path: &str, args: OpListMultipart) -> Result<RpListMultipart> {
+        self.handle.requests_total_list_multipart.increment(1);
+
+        let start = Instant::now();
+        let result = self.inner.list_multipart(path, args).await;
+        let dur = start.elapsed().as_secs_f64();
+
+        self.handle
+            .requests_duration_seconds_list_multipart
+            .record(dur);
+
+        result.map_err(|e| {
+            self.handle
+                .increment_errors_total(Operation::ListMultipart, e.kind());
+            e
+        })
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        self.handle.requests_total_list_versions.increment(1);
+
+        let start = Instant::now();
+        let result = self.inner.list_versions(path, args).await;
+        let dur = start.elapsed().as_secs_f64();
+
+        self.handle
+            .requests_duration_seconds_list_versions
+            .record(dur);
+
+        result.map_err(|e| {
+            self.handle
+                .increment_errors_total(Operation::ListVersions, e.kind());
+            e
+        })
+    }
+
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.handle.requests_total_get_tags.increment(1);
+
+        let start = Instant::now();
+        let result = self.inner.get_tags(path, args).await;
+        let dur = start.elapsed().as_secs_f64();
+
+        self.handle.requests_duration_seconds_get_tags.record(dur);
+
+        result.map_err(|e| {
+            self.handle
+                .increment_errors_total(Operation::GetTags, e.kind());
+            e
+        })
+    }
+
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        self.handle.requests_total_set_tags.increment(1);
+
+        let start = Instant::now();
+        let result = self.inner.set_tags(path, args).await;
+        let dur = start.elapsed().as_secs_f64();
+
+        self.handle.requests_duration_seconds_set_tags.record(dur);
+
+        result.map_err(|e| {
+            self.handle
+                .increment_errors_total(Operation::SetTags, e.kind());
+            e
+        })
+    }
+
+    async fn delete_tags(&self, path: &str, args: OpDeleteTags) -> Result<RpDeleteTags> {
+        self.handle.requests_total_delete_tags.increment(1);
+
+        let start = Instant::now();
+        let result = self.inner.delete_tags(path, args).await;
+        let dur = start.elapsed().as_secs_f64();
+
+        self.handle
+            .requests_duration_seconds_delete_tags
+            .record(dur);
+
+        result.map_err(|e| {
+            self.handle
+                .increment_errors_total(Operation::DeleteTags, e.kind());
+            e
+        })
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.handle.requests_total_batch.increment(1);
+
+        let start = Instant::now();
+        let result = self.inner.batch(args).await;
+        let dur = start.elapsed().as_secs_f64();
+
+        self.handle.requests_duration_seconds_batch.record(dur);
+
+        result.map_err(|e| {
+            self.handle
+                .increment_errors_total(Operation::Batch, e.kind());
+            e
+        })
+    }
+
     fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
         self.handle.requests_total_blocking_create.increment(1);
 
@@ -704,6 +974,10 @@ impl Accessor for MetricsAccessor {
         });
 
         result.map_err(|e| {
+            // Reader is not returned, so we record its duration here.
+            self.handle
+                .requests_duration_seconds_blocking_read
+                .record(start.elapsed().as_secs_f64());
             self.handle
                 .increment_errors_total(Operation::BlockingRead, e.kind());
             e
@@ -773,6 +1047,24 @@ impl Accessor for MetricsAccessor {
         })
     }
 
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.handle.requests_total_blocking_copy.increment(1);
+
+        let start = Instant::now();
+        let result = self.inner.blocking_copy(from, to, args);
+        let dur = start.elapsed().as_secs_f64();
+
+        self.handle
+            .requests_duration_seconds_blocking_copy
+            .record(dur);
+
+        result.map_err(|e| {
+            self.handle
+                .increment_errors_total(Operation::BlockingCopy, e.kind());
+            e
+        })
+    }
+
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
         self.handle.requests_total_blocking_list.increment(1);
 
@@ -909,3 +1201,148 @@ impl Drop for BlockingMetricReader {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::atomic::Ordering;
+    use std::sync::Mutex;
+
+    use futures::AsyncReadExt;
+    use metrics::HistogramFn;
+    use metrics::Key;
+    use metrics::KeyName;
+    use metrics::Recorder;
+    use metrics::SharedString;
+    use metrics::Unit;
+
+    use super::*;
+
+    /// Histogram that only counts the observations.
+    #[derive(Default)]
+    struct Observations(AtomicU64);
+
+    impl HistogramFn for Observations {
+        fn record(&self, _: f64) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// TestRecorder will store all counters and histograms in a map
+    /// keyed by their name and sorted labels.
+    #[derive(Default)]
+    struct TestRecorder {
+        counters: Mutex<HashMap<String, Arc<AtomicU64>>>,
+        histograms: Mutex<HashMap<String, Arc<Observations>>>,
+    }
+
+    impl TestRecorder {
+        fn format_key(key: &Key) -> String {
+            let mut labels = key
+                .labels()
+                .map(|l| format!("{}={}", l.key(), l.value()))
+                .collect::<Vec<_>>();
+            labels.sort_unstable();
+            format!("{}{{{}}}", key.name(), labels.join(","))
+        }
+
+        fn counter(&self, key: &str) -> u64 {
+            self.counters
+                .lock()
+                .unwrap()
+                .get(key)
+                .map(|v| v.load(Ordering::Relaxed))
+                .unwrap_or_default()
+        }
+
+        fn observations(&self, key: &str) -> u64 {
+            self.histograms
+                .lock()
+                .unwrap()
+                .get(key)
+                .map(|v| v.0.load(Ordering::Relaxed))
+                .unwrap_or_default()
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key) -> Counter {
+            let v = self
+                .counters
+                .lock()
+                .unwrap()
+                .entry(Self::format_key(key))
+                .or_default()
+                .clone();
+            Counter::from_arc(v)
+        }
+
+        fn register_gauge(&self, _: &Key) -> metrics::Gauge {
+            metrics::Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key) -> Histogram {
+            let v = self
+                .histograms
+                .lock()
+                .unwrap()
+                .entry(Self::format_key(key))
+                .or_default()
+                .clone();
+            Histogram::from_arc(v)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_layer() -> anyhow::Result<()> {
+        let recorder: &'static TestRecorder = Box::leak(Box::new(TestRecorder::default()));
+        metrics::set_recorder(recorder).expect("recorder must be set only once");
+
+        let op = Operator::from_env(Scheme::Memory)?.layer(MetricsLayer);
+
+        op.object("test").write("Hello, World!").await?;
+
+        let mut r = op.object("test").reader().await?;
+        let mut bs = Vec::new();
+        r.read_to_end(&mut bs).await?;
+        drop(r);
+        assert_eq!(bs, b"Hello, World!");
+
+        let err = op
+            .object("not_exist")
+            .metadata()
+            .await
+            .expect_err("stat of not exist object must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+
+        assert_eq!(
+            recorder.counter("opendal_requests_total{operation=write,service=memory}"),
+            1
+        );
+        assert_eq!(
+            recorder.counter("opendal_bytes_total{operation=write,service=memory}"),
+            13
+        );
+        assert_eq!(
+            recorder.counter("opendal_bytes_total{operation=read,service=memory}"),
+            13
+        );
+        assert_eq!(
+            recorder
+                .observations("opendal_requests_duration_seconds{operation=read,service=memory}"),
+            1
+        );
+        assert_eq!(
+            recorder.counter(
+                "opendal_errors_total{error_kind=ObjectNotFound,operation=stat,service=memory}"
+            ),
+            1
+        );
+        Ok(())
+    }
+}
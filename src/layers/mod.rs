@@ -17,6 +17,11 @@
 mod layer;
 pub use layer::Layer;
 
+#[cfg(feature = "layers-compression")]
+mod compression;
+#[cfg(feature = "layers-compression")]
+pub use compression::CompressionLayer;
+
 mod concurrent_limit;
 pub use concurrent_limit::ConcurrentLimitLayer;
 
@@ -40,7 +45,14 @@ pub use self::retry::RetryLayer;
 mod subdir;
 pub use subdir::SubdirLayer;
 
+mod timeout;
+pub use timeout::TimeoutLayer;
+
 #[cfg(feature = "layers-tracing")]
 mod tracing;
 #[cfg(feature = "layers-tracing")]
 pub use self::tracing::TracingLayer;
+
+mod write_once;
+pub use write_once::WriteOnceLayer;
+pub use write_once::WriteOnceMode;
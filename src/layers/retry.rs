@@ -18,16 +18,21 @@ use std::fmt::Formatter;
 use std::future::Future;
 use std::io;
 use std::pin::Pin;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 use std::thread::sleep;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use backon::Backoff;
-use backon::Retryable;
+use backon::ExponentialBackoff;
+use futures::future::BoxFuture;
 use futures::ready;
 use futures::AsyncRead;
+use log::debug;
 use log::warn;
 use pin_project::pin_project;
 use tokio::time::Sleep;
@@ -37,20 +42,95 @@ use crate::*;
 
 /// RetryLayer will add retry for OpenDAL.
 ///
+/// # Notes
+///
+/// For `read`, content that is shorter than its declared content length
+/// will be resumed by sending a new request from the consumed position.
+///
+/// For `write`, requests will only be retried if the body hasn't been
+/// consumed. Retrying a consumed streaming body will produce truncated
+/// objects, so the error will be returned to users instead.
+///
+/// Only errors that [`Error::is_temporary`] returns `true` will be retried.
+///
+/// Use [`RetryLayer::with_notify`] to observe every retry, for example to
+/// emit metrics about retries.
+///
 /// # Examples
 ///
 /// ```
+/// use std::time::Duration;
+///
 /// use anyhow::Result;
-/// use backon::ExponentialBackoff;
 /// use opendal::layers::RetryLayer;
 /// use opendal::Operator;
 /// use opendal::Scheme;
 ///
 /// let _ = Operator::from_env(Scheme::Fs)
 ///     .expect("must init")
-///     .layer(RetryLayer::new(ExponentialBackoff::default()));
+///     .layer(
+///         RetryLayer::default()
+///             .with_max_times(5)
+///             .with_factor(2.0)
+///             .with_min_delay(Duration::from_millis(100))
+///             .with_max_delay(Duration::from_secs(10))
+///             .with_jitter(),
+///     );
 /// ```
-pub struct RetryLayer<B: Backoff + Send + Sync + Debug + 'static>(B);
+pub struct RetryLayer<B: Backoff + Send + Sync + Debug + 'static = ExponentialBackoff> {
+    backoff: B,
+    notify: Option<RetryNotify>,
+}
+
+/// Callback that will be invoked before every retry.
+type RetryNotify = Arc<dyn Fn(&Error, Duration, usize) + Send + Sync>;
+
+/// Create a retry layer with default exponential backoff:
+///
+/// - max_times: 3
+/// - factor: 2.0
+/// - min_delay: 1s
+/// - max_delay: 60s
+/// - jitter: false
+impl Default for RetryLayer {
+    fn default() -> Self {
+        Self::new(ExponentialBackoff::default())
+    }
+}
+
+impl RetryLayer {
+    /// Set max retry times of exponential backoff.
+    pub fn with_max_times(mut self, max_times: usize) -> Self {
+        self.backoff = self.backoff.with_max_times(max_times);
+        self
+    }
+
+    /// Set factor of exponential backoff.
+    pub fn with_factor(mut self, factor: f32) -> Self {
+        self.backoff = self.backoff.with_factor(factor);
+        self
+    }
+
+    /// Set min delay of exponential backoff.
+    pub fn with_min_delay(mut self, min_delay: Duration) -> Self {
+        self.backoff = self.backoff.with_min_delay(min_delay);
+        self
+    }
+
+    /// Set max delay of exponential backoff.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.backoff = self.backoff.with_max_delay(max_delay);
+        self
+    }
+
+    /// Enable full jitter of exponential backoff.
+    ///
+    /// A random jitter in `[0, min_delay)` will be added to every delay.
+    pub fn with_jitter(mut self) -> Self {
+        self.backoff = self.backoff.with_jitter();
+        self
+    }
+}
 
 impl<B> RetryLayer<B>
 where
@@ -71,7 +151,43 @@ where
     ///     .layer(RetryLayer::new(ExponentialBackoff::default()));
     /// ```
     pub fn new(b: B) -> Self {
-        Self(b)
+        Self {
+            backoff: b,
+            notify: None,
+        }
+    }
+
+    /// Set the callback that will be invoked before every retry.
+    ///
+    /// The callback will be called with the temporary error, the delay
+    /// before next retry and the retry attempt which starts from `1`.
+    /// Errors that are not temporary will never be retried or notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::AtomicUsize;
+    /// use std::sync::atomic::Ordering;
+    /// use std::sync::Arc;
+    ///
+    /// use opendal::layers::RetryLayer;
+    /// use opendal::Operator;
+    /// use opendal::Scheme;
+    ///
+    /// let retries = Arc::new(AtomicUsize::new(0));
+    /// let counter = retries.clone();
+    /// let _ = Operator::from_env(Scheme::Fs)
+    ///     .expect("must init")
+    ///     .layer(RetryLayer::default().with_notify(move |_, _, _| {
+    ///         counter.fetch_add(1, Ordering::Relaxed);
+    ///     }));
+    /// ```
+    pub fn with_notify(
+        mut self,
+        notify: impl Fn(&Error, Duration, usize) + Send + Sync + 'static,
+    ) -> Self {
+        self.notify = Some(Arc::new(notify));
+        self
     }
 }
 
@@ -82,7 +198,8 @@ where
     fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
         Arc::new(RetryAccessor {
             inner,
-            backoff: self.0.clone(),
+            backoff: self.backoff.clone(),
+            notify: self.notify.clone(),
         })
     }
 }
@@ -91,6 +208,89 @@ where
 struct RetryAccessor<B: Backoff + Debug + Send + Sync> {
     inner: Arc<dyn Accessor>,
     backoff: B,
+    notify: Option<RetryNotify>,
+}
+
+impl<B: Backoff + Debug + Send + Sync> RetryAccessor<B> {
+    fn backoff(&self, op: Operation) -> RetryBackoff<B> {
+        RetryBackoff {
+            inner: self.backoff.clone(),
+            op,
+            attempt: 0,
+        }
+    }
+
+    /// Retry `f` while it returns temporary errors.
+    ///
+    /// The last error will be returned as is after all retries failed.
+    async fn retry<T, F, Fut>(&self, op: Operation, path: &str, mut f: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut backoff = self.backoff(op);
+
+        loop {
+            let err = match f().await {
+                Ok(v) => return Ok(v),
+                Err(err) if err.is_temporary() => err,
+                Err(err) => return Err(err),
+            };
+
+            match backoff.next() {
+                None => return Err(err),
+                Some(dur) => {
+                    notify_retry(op, path, &err, dur, backoff.attempt, &self.notify);
+                    tokio::time::sleep(dur).await;
+                }
+            }
+        }
+    }
+
+    /// Blocking version of [`RetryAccessor::retry`].
+    fn blocking_retry<T>(
+        &self,
+        op: Operation,
+        path: &str,
+        mut f: impl FnMut() -> Result<T>,
+    ) -> Result<T> {
+        let mut backoff = self.backoff(op);
+
+        loop {
+            let err = match f() {
+                Ok(v) => return Ok(v),
+                Err(err) if err.is_temporary() => err,
+                Err(err) => return Err(err),
+            };
+
+            match backoff.next() {
+                None => return Err(err),
+                Some(dur) => {
+                    notify_retry(op, path, &err, dur, backoff.attempt, &self.notify);
+                    sleep(dur);
+                }
+            }
+        }
+    }
+}
+
+/// Log the retry and call user's notify callback if set.
+fn notify_retry(
+    op: Operation,
+    path: &str,
+    err: &Error,
+    dur: Duration,
+    attempt: usize,
+    notify: &Option<RetryNotify>,
+) {
+    warn!(
+        target: "opendal::service",
+        "operation={} path={} -> retry after {}s: error={:?}",
+        op, path, dur.as_secs_f64(), err);
+
+    if let Some(notify) = notify {
+        notify(err, dur, attempt)
+    }
 }
 
 impl<B: Backoff + Debug + Send + Sync> Debug for RetryAccessor<B> {
@@ -111,97 +311,98 @@ where
     }
 
     async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        { || self.inner.create(path, args.clone()) }
-            .retry(self.backoff.clone())
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Create, dur.as_secs_f64(), err)
-            })
-            .await
-            .map_err(|e| e.set_persistent())
+        self.retry(Operation::Create, path, || {
+            self.inner.create(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
-        let (rp, r) = { || self.inner.read(path, args.clone()) }
-            .retry(self.backoff.clone())
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Read, dur.as_secs_f64(), err)
+        let (rp, r) = self
+            .retry(Operation::Read, path, || {
+                self.inner.read(path, args.clone())
             })
             .await
             .map_err(|e| e.set_persistent())?;
 
-        Ok((
-            rp,
-            Box::new(RetryReader::new(r, Operation::Read, self.backoff.clone())) as BytesReader,
-        ))
+        let r = RetryReader::new(
+            r,
+            Operation::Read,
+            self.backoff(Operation::Read),
+            self.notify.clone(),
+        )
+        .with_resume(self.inner.clone(), path, args);
+        Ok((rp, Box::new(r) as BytesReader))
     }
 
     /// Return `Interrupted` Error even after retry.
     ///
     /// Allowing users to retry the write request from upper logic.
     async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
-        let r = Box::new(RetryReader::new(r, Operation::Write, self.backoff.clone()));
+        let r = Box::new(RetryReader::new(
+            r,
+            Operation::Write,
+            self.backoff(Operation::Write),
+            self.notify.clone(),
+        ));
         let r = Box::new(CloneableReader::new(r));
 
-        { || self.inner.write(path, args.clone(), r.clone()) }
-            .retry(self.backoff.clone())
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Write, dur.as_secs_f64(), err)
-            })
-            .await
+        let write = || async {
+            self.inner
+                .write(path, args.clone(), r.clone())
+                .await
+                .map_err(|err| {
+                    // Consumed body can't be sent again.
+                    if r.is_consumed() {
+                        err.set_persistent()
+                    } else {
+                        err
+                    }
+                })
+        };
+
+        self.retry(Operation::Write, path, write).await
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        { || self.inner.stat(path, args.clone()) }
-            .retry(self.backoff.clone())
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Stat, dur.as_secs_f64(), err)
-            })
-            .await
-            .map_err(|e| e.set_persistent())
+        self.retry(Operation::Stat, path, || {
+            self.inner.stat(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        { || self.inner.delete(path, args.clone()) }
-            .retry(self.backoff.clone())
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::Delete, dur.as_secs_f64(), err)
-            })
-            .await
-            .map_err(|e| e.set_persistent())
+        self.retry(Operation::Delete, path, || {
+            self.inner.delete(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.retry(Operation::Copy, from, || {
+            self.inner.copy(from, to, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.retry(Operation::Rename, from, || {
+            self.inner.rename(from, to, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
-        { || self.inner.list(path, args.clone()) }
-            .retry(self.backoff.clone())
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::List, dur.as_secs_f64(), err)
-            })
-            .await
-            .map_err(|e| e.set_persistent())
+        self.retry(Operation::List, path, || {
+            self.inner.list(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
     }
 
     async fn create_multipart(
@@ -209,17 +410,11 @@ where
         path: &str,
         args: OpCreateMultipart,
     ) -> Result<RpCreateMultipart> {
-        { || self.inner.create_multipart(path, args.clone()) }
-            .retry(self.backoff.clone())
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::CreateMultipart, dur.as_secs_f64(), err)
-            })
-            .await
-            .map_err(|e| e.set_persistent())
+        self.retry(Operation::CreateMultipart, path, || {
+            self.inner.create_multipart(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
     }
 
     async fn write_multipart(
@@ -240,17 +435,11 @@ where
         path: &str,
         args: OpCompleteMultipart,
     ) -> Result<RpCompleteMultipart> {
-        { || self.inner.complete_multipart(path, args.clone()) }
-            .retry(self.backoff.clone())
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::CompleteMultipart, dur.as_secs_f64(), err)
-            })
-            .await
-            .map_err(|e| e.set_persistent())
+        self.retry(Operation::CompleteMultipart, path, || {
+            self.inner.complete_multipart(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
     }
 
     async fn abort_multipart(
@@ -258,79 +447,69 @@ where
         path: &str,
         args: OpAbortMultipart,
     ) -> Result<RpAbortMultipart> {
-        { || self.inner.abort_multipart(path, args.clone()) }
-            .retry(self.backoff.clone())
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
-                    target: "opendal::service",
-                    "operation={} -> retry after {}s: error={:?}",
-                    Operation::AbortMultipart, dur.as_secs_f64(), err)
-            })
-            .await
-            .map_err(|e| e.set_persistent())
+        self.retry(Operation::AbortMultipart, path, || {
+            self.inner.abort_multipart(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
     }
 
-    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        let retry = self.backoff.clone();
-
-        let mut e = None;
-
-        for dur in retry {
-            let res = self.inner.blocking_create(path, args.clone());
+    async fn list_multipart(&self, path: &str, args: OpListMultipart) -> Result<RpListMultipart> {
+        self.retry(Operation::ListMultipart, path, || {
+            self.inner.list_multipart(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
+    }
 
-            match res {
-                Ok(v) => return Ok(v),
-                Err(err) => {
-                    let retryable = err.is_temporary();
-                    e = Some(err);
-
-                    if retryable {
-                        sleep(dur);
-                        warn!(
-                            target: "opendal::service",
-                            "operation={} path={} -> retry after {}s: error={:?}",
-                            Operation::BlockingCreate, path, dur.as_secs_f64(), e);
-                        continue;
-                    } else {
-                        return Err(e.unwrap());
-                    }
-                }
-            }
-        }
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        self.retry(Operation::ListVersions, path, || {
+            self.inner.list_versions(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
+    }
 
-        Err(e.unwrap())
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.retry(Operation::GetTags, path, || {
+            self.inner.get_tags(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
     }
 
-    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, BlockingBytesReader)> {
-        let retry = self.backoff.clone();
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        self.retry(Operation::SetTags, path, || {
+            self.inner.set_tags(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
+    }
 
-        let mut e = None;
+    async fn delete_tags(&self, path: &str, args: OpDeleteTags) -> Result<RpDeleteTags> {
+        self.retry(Operation::DeleteTags, path, || {
+            self.inner.delete_tags(path, args.clone())
+        })
+        .await
+        .map_err(|e| e.set_persistent())
+    }
 
-        for dur in retry {
-            let res = self.inner.blocking_read(path, args.clone());
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.retry(Operation::Batch, "", || self.inner.batch(args.clone()))
+            .await
+            .map_err(|e| e.set_persistent())
+    }
 
-            match res {
-                Ok(v) => return Ok(v),
-                Err(err) => {
-                    let retryable = err.is_temporary();
-                    e = Some(err);
-
-                    if retryable {
-                        sleep(dur);
-                        warn!(
-                            target: "opendal::service",
-                            "operation={} path={} -> retry after {}s: error={:?}",
-                            Operation::BlockingRead, path, dur.as_secs_f64(), e);
-                        continue;
-                    } else {
-                        return Err(e.unwrap());
-                    }
-                }
-            }
-        }
+    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.blocking_retry(Operation::BlockingCreate, path, || {
+            self.inner.blocking_create(path, args.clone())
+        })
+    }
 
-        Err(e.unwrap())
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, BlockingBytesReader)> {
+        self.blocking_retry(Operation::BlockingRead, path, || {
+            self.inner.blocking_read(path, args.clone())
+        })
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite, r: BlockingBytesReader) -> Result<RpWrite> {
@@ -338,96 +517,50 @@ where
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        let retry = self.backoff.clone();
-
-        let mut e = None;
-
-        for dur in retry {
-            let res = self.inner.blocking_stat(path, args.clone());
-
-            match res {
-                Ok(v) => return Ok(v),
-                Err(err) => {
-                    let retryable = err.is_temporary();
-                    e = Some(err);
-
-                    if retryable {
-                        sleep(dur);
-                        warn!(
-                            target: "opendal::service",
-                            "operation={} path={} -> retry after {}s: error={:?}",
-                            Operation::BlockingStat, path, dur.as_secs_f64(), e);
-                        continue;
-                    } else {
-                        return Err(e.unwrap());
-                    }
-                }
-            }
-        }
-
-        Err(e.unwrap())
+        self.blocking_retry(Operation::BlockingStat, path, || {
+            self.inner.blocking_stat(path, args.clone())
+        })
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        let retry = self.backoff.clone();
-
-        let mut e = None;
-
-        for dur in retry {
-            let res = self.inner.blocking_delete(path, args.clone());
-
-            match res {
-                Ok(v) => return Ok(v),
-                Err(err) => {
-                    let retryable = err.is_temporary();
-                    e = Some(err);
-
-                    if retryable {
-                        sleep(dur);
-                        warn!(
-                            target: "opendal::service",
-                            "operation={} path={} -> retry after {}s: error={:?}",
-                            Operation::BlockingDelete, path, dur.as_secs_f64(), e);
-                        continue;
-                    } else {
-                        return Err(e.unwrap());
-                    }
-                }
-            }
-        }
+        self.blocking_retry(Operation::BlockingDelete, path, || {
+            self.inner.blocking_delete(path, args.clone())
+        })
+    }
 
-        Err(e.unwrap())
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.blocking_retry(Operation::BlockingCopy, from, || {
+            self.inner.blocking_copy(from, to, args.clone())
+        })
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
-        let retry = self.backoff.clone();
+        self.blocking_retry(Operation::BlockingList, path, || {
+            self.inner.blocking_list(path, args.clone())
+        })
+    }
+}
 
-        let mut e = None;
+/// RetryBackoff will log the attempt number and delay of every retry.
+#[derive(Debug, Clone)]
+struct RetryBackoff<B: Backoff> {
+    inner: B,
+    op: Operation,
+    attempt: usize,
+}
 
-        for dur in retry {
-            let res = self.inner.blocking_list(path, args.clone());
+impl<B: Backoff> Iterator for RetryBackoff<B> {
+    type Item = Duration;
 
-            match res {
-                Ok(v) => return Ok(v),
-                Err(err) => {
-                    let retryable = err.is_temporary();
-                    e = Some(err);
-
-                    if retryable {
-                        sleep(dur);
-                        warn!(
-                            target: "opendal::service",
-                            "operation={} path={} -> retry after {}s: error={:?}",
-                            Operation::BlockingList, path, dur.as_secs_f64(), e);
-                        continue;
-                    } else {
-                        return Err(e.unwrap());
-                    }
-                }
-            }
-        }
+    fn next(&mut self) -> Option<Self::Item> {
+        let dur = self.inner.next()?;
+        self.attempt += 1;
 
-        Err(e.unwrap())
+        debug!(
+            target: "opendal::service",
+            "operation={} -> retry attempt={} after {}s",
+            self.op, self.attempt, dur.as_secs_f64());
+        Some(dur)
     }
 }
 
@@ -435,22 +568,71 @@ where
 struct RetryReader<B: Backoff + Debug + Send + Sync> {
     inner: BytesReader,
     op: Operation,
+    /// Resume the read from consumed position if content is truncated.
+    ///
+    /// Only available for `read`.
+    resume: Option<Resume>,
 
-    backoff: B,
-    retry: Option<B>,
+    backoff: RetryBackoff<B>,
+    retry: Option<RetryBackoff<B>>,
+    notify: Option<RetryNotify>,
     sleep: Option<Pin<Box<Sleep>>>,
 }
 
+struct Resume {
+    acc: Arc<dyn Accessor>,
+    path: String,
+    args: OpRead,
+    /// Bytes that have been returned to users.
+    consumed: u64,
+    sending: Option<BoxFuture<'static, Result<(RpRead, BytesReader)>>>,
+}
+
 impl<B: Backoff + Debug + Send + Sync> RetryReader<B> {
-    fn new(inner: BytesReader, op: Operation, backoff: B) -> Self {
+    fn new(
+        inner: BytesReader,
+        op: Operation,
+        backoff: RetryBackoff<B>,
+        notify: Option<RetryNotify>,
+    ) -> Self {
         Self {
             inner,
             op,
+            resume: None,
             backoff,
             retry: None,
+            notify,
             sleep: None,
         }
     }
+
+    fn with_resume(mut self, acc: Arc<dyn Accessor>, path: &str, args: OpRead) -> Self {
+        self.resume = Some(Resume {
+            acc,
+            path: path.to_string(),
+            args,
+            consumed: 0,
+            sending: None,
+        });
+        self
+    }
+}
+
+/// Get the next backoff duration and retry attempt, `None` means we have
+/// reached the limit.
+fn next_backoff<B: Backoff>(
+    backoff: &RetryBackoff<B>,
+    retry: &mut Option<RetryBackoff<B>>,
+) -> Option<(Duration, usize)> {
+    let retry_backoff = retry.get_or_insert_with(|| backoff.clone());
+    match retry_backoff.next() {
+        Some(dur) => Some((dur, retry_backoff.attempt)),
+        None => {
+            // Reset retry to none.
+            *retry = None;
+            None
+        }
+    }
 }
 
 impl<B> AsyncRead for RetryReader<B>
@@ -470,40 +652,82 @@ where
                 *this.sleep = None;
             }
 
+            if let Some(resume) = this.resume {
+                if let Some(fut) = &mut resume.sending {
+                    let res = ready!(fut.as_mut().poll(cx));
+                    resume.sending = None;
+
+                    match res {
+                        Ok((_, r)) => *this.inner = r,
+                        Err(err) if err.is_temporary() => {
+                            match next_backoff(this.backoff, this.retry) {
+                                None => return Poll::Ready(Err(err.set_persistent().into())),
+                                Some((dur, attempt)) => {
+                                    notify_retry(
+                                        *this.op,
+                                        &resume.path,
+                                        &err,
+                                        dur,
+                                        attempt,
+                                        this.notify,
+                                    );
+
+                                    *this.sleep = Some(Box::pin(tokio::time::sleep(dur)));
+                                    resume.sending = Some(resume.send());
+                                    continue;
+                                }
+                            }
+                        }
+                        Err(err) => {
+                            *this.retry = None;
+                            return Poll::Ready(Err(err.into()));
+                        }
+                    }
+                }
+            }
+
             let res = ready!(Pin::new(&mut *this.inner).poll_read(cx, buf));
 
             match res {
                 Ok(v) => {
                     // Reset retry to none.
                     *this.retry = None;
+                    if let Some(resume) = this.resume {
+                        resume.consumed += v as u64;
+                    }
 
                     return Poll::Ready(Ok(v));
                 }
                 Err(err) => {
                     let kind = err.kind();
 
-                    if kind == io::ErrorKind::Interrupted {
-                        let retry = if let Some(retry) = this.retry {
-                            retry
-                        } else {
-                            *this.retry = Some(this.backoff.clone());
-                            this.retry.as_mut().unwrap()
-                        };
-
-                        match retry.next() {
-                            None => {
-                                // Reset retry to none.
-                                *this.retry = None;
-
-                                return Poll::Ready(Err(err));
-                            }
-                            Some(dur) => {
-                                warn!(
-                                    target: "opendal::service",
-                                    "operation={}  -> retry after {}s: error={:?}",
-                                    *this.op, dur.as_secs_f64(), err);
+                    // Content is truncated, we need to send a new request.
+                    let resumable = kind == io::ErrorKind::UnexpectedEof && this.resume.is_some();
+
+                    if kind == io::ErrorKind::Interrupted || resumable {
+                        match next_backoff(this.backoff, this.retry) {
+                            None => return Poll::Ready(Err(err)),
+                            Some((dur, attempt)) => {
+                                let path = this.resume.as_ref().map(|v| v.path.as_str());
+                                let err =
+                                    Error::new(ErrorKind::Unexpected, "read from reader failed")
+                                        .with_operation(this.op.into_static())
+                                        .set_temporary()
+                                        .set_source(err);
+                                notify_retry(
+                                    *this.op,
+                                    path.unwrap_or_default(),
+                                    &err,
+                                    dur,
+                                    attempt,
+                                    this.notify,
+                                );
 
                                 *this.sleep = Some(Box::pin(tokio::time::sleep(dur)));
+                                if resumable {
+                                    let resume = this.resume.as_mut().expect("resume must exist");
+                                    resume.sending = Some(resume.send());
+                                }
                                 continue;
                             }
                         }
@@ -519,6 +743,29 @@ where
     }
 }
 
+impl Resume {
+    /// Send a new read request starts from consumed position.
+    fn send(&self) -> BoxFuture<'static, Result<(RpRead, BytesReader)>> {
+        let acc = self.acc.clone();
+        let path = self.path.clone();
+
+        let mut br = self.args.range();
+        br.advance(self.consumed);
+        // All content has been consumed, nothing left to read.
+        if br.is_empty() {
+            return Box::pin(async {
+                Ok((
+                    RpRead::new(0),
+                    Box::new(futures::io::empty()) as BytesReader,
+                ))
+            });
+        }
+        let args = self.args.clone().with_range(br);
+
+        Box::pin(async move { acc.read(&path, args).await })
+    }
+}
+
 /// CloneableReader makes a reader cloneable.
 ///
 /// # Safety
@@ -529,6 +776,8 @@ where
 #[derive(Clone)]
 struct CloneableReader {
     inner: Arc<RefCell<BytesReader>>,
+    /// Whether any bytes have been read from the inner reader.
+    consumed: Arc<AtomicBool>,
 }
 
 unsafe impl Send for CloneableReader {}
@@ -538,8 +787,13 @@ impl CloneableReader {
     fn new(r: BytesReader) -> Self {
         Self {
             inner: Arc::new(RefCell::new(r)),
+            consumed: Arc::new(AtomicBool::new(false)),
         }
     }
+
+    fn is_consumed(&self) -> bool {
+        self.consumed.load(Ordering::Relaxed)
+    }
 }
 
 impl AsyncRead for CloneableReader {
@@ -549,7 +803,11 @@ impl AsyncRead for CloneableReader {
         buf: &mut [u8],
     ) -> Poll<io::Result<usize>> {
         let mut r = (*self.inner).borrow_mut();
-        Pin::new(r.as_mut()).poll_read(cx, buf)
+        let res = ready!(Pin::new(r.as_mut()).poll_read(cx, buf));
+        if matches!(res, Ok(n) if n > 0) {
+            self.consumed.store(true, Ordering::Relaxed);
+        }
+        Poll::Ready(res)
     }
 }
 
@@ -581,6 +839,12 @@ mod tests {
 
     #[async_trait]
     impl Accessor for MockService {
+        fn metadata(&self) -> AccessorMetadata {
+            let mut am = AccessorMetadata::default();
+            am.set_capabilities(AccessorCapability::Read | AccessorCapability::Write);
+            am
+        }
+
         async fn read(&self, path: &str, _: OpRead) -> Result<(RpRead, BytesReader)> {
             let mut attempt = self.attempt.lock().unwrap();
             *attempt += 1;
@@ -593,19 +857,101 @@ mod tests {
             }
         }
 
-        async fn write(&self, path: &str, _: OpWrite, _: BytesReader) -> Result<RpWrite> {
-            let mut attempt = self.attempt.lock().unwrap();
-            *attempt += 1;
+        async fn write(&self, path: &str, _: OpWrite, mut r: BytesReader) -> Result<RpWrite> {
+            {
+                let mut attempt = self.attempt.lock().unwrap();
+                *attempt += 1;
+            }
 
             match path {
                 "retryable_error" => {
                     Err(Error::new(ErrorKind::Unexpected, "retryable_error").set_temporary())
                 }
+                "retryable_error_after_consumed" => {
+                    let mut buf = [0; 5];
+                    r.read_exact(&mut buf).await.expect("read must succeed");
+                    Err(Error::new(ErrorKind::Unexpected, "retryable_error").set_temporary())
+                }
+                _ => Err(Error::new(ErrorKind::Unexpected, "not_retryable_error")),
+            }
+        }
+
+        async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+            match path {
+                "retryable_error_twice" => {
+                    let mut attempt = self.attempt.lock().unwrap();
+                    *attempt += 1;
+
+                    if *attempt <= 2 {
+                        Err(Error::new(ErrorKind::Unexpected, "retryable_error").set_temporary())
+                    } else {
+                        Ok(RpStat::new(ObjectMetadata::new(ObjectMode::FILE)))
+                    }
+                }
                 _ => Err(Error::new(ErrorKind::Unexpected, "not_retryable_error")),
             }
         }
     }
 
+    #[tokio::test]
+    async fn test_retry_fail_twice_then_succeed() -> anyhow::Result<()> {
+        let _ = env_logger::try_init();
+
+        let srv = Arc::new(MockService::default());
+
+        let layer = RetryLayer::default()
+            .with_max_times(5)
+            .with_factor(1.5)
+            .with_min_delay(Duration::from_micros(1))
+            .with_max_delay(Duration::from_millis(1))
+            .with_jitter();
+        let op = Operator::new(srv.clone()).layer(layer);
+
+        op.object("retryable_error_twice")
+            .metadata()
+            .await
+            .expect("stat must succeed");
+        assert_eq!(*srv.attempt.lock().unwrap(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_notify() -> anyhow::Result<()> {
+        let _ = env_logger::try_init();
+
+        let srv = Arc::new(MockService::default());
+        let notified = Arc::new(Mutex::new(Vec::new()));
+
+        let backoff = ConstantBackoff::default()
+            .with_delay(Duration::from_micros(1))
+            .with_max_times(5);
+        let records = notified.clone();
+        let layer = RetryLayer::new(backoff).with_notify(move |err, dur, attempt| {
+            assert!(err.is_temporary());
+            records.lock().unwrap().push((dur, attempt));
+        });
+        let op = Operator::new(srv.clone()).layer(layer);
+
+        op.object("retryable_error_twice")
+            .metadata()
+            .await
+            .expect("stat must succeed");
+        // Two failures are injected before succeed.
+        assert_eq!(
+            *notified.lock().unwrap(),
+            vec![(Duration::from_micros(1), 1), (Duration::from_micros(1), 2)]
+        );
+
+        // Errors that are not temporary will not be notified.
+        notified.lock().unwrap().clear();
+        let result = op.object("not_retryable_error").read().await;
+        assert!(result.is_err());
+        assert!(notified.lock().unwrap().is_empty());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_retry_retryable_error() -> anyhow::Result<()> {
         let _ = env_logger::try_init();
@@ -647,6 +993,29 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_retry_write_consumed() -> anyhow::Result<()> {
+        let _ = env_logger::try_init();
+
+        let srv = Arc::new(MockService::default());
+
+        let backoff = ConstantBackoff::default()
+            .with_delay(Duration::from_micros(1))
+            .with_max_times(10);
+        let op = Operator::new(srv.clone()).layer(RetryLayer::new(backoff));
+
+        let bs = Box::new(Cursor::new("Hello, World!".as_bytes()));
+        let result = op
+            .object("retryable_error_after_consumed")
+            .write_from(13, bs)
+            .await;
+        assert!(result.is_err());
+        // The body has been consumed, we should not retry it.
+        assert_eq!(*srv.attempt.lock().unwrap(), 1);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_retry_not_retryable_error() -> anyhow::Result<()> {
         let srv = Arc::new(MockService::default());
@@ -673,6 +1042,12 @@ mod tests {
 
     #[async_trait]
     impl Accessor for MockReadService {
+        fn metadata(&self) -> AccessorMetadata {
+            let mut am = AccessorMetadata::default();
+            am.set_capabilities(AccessorCapability::Read | AccessorCapability::Write);
+            am
+        }
+
         async fn read(&self, _: &str, _: OpRead) -> Result<(RpRead, BytesReader)> {
             Ok((
                 RpRead::new(0),
@@ -783,4 +1158,76 @@ mod tests {
 
         Ok(())
     }
+
+    /// Return a body shorter than its declared content length for the
+    /// first request.
+    #[derive(Debug, Clone, Default)]
+    struct MockTruncatedService {
+        ranges: Arc<Mutex<Vec<BytesRange>>>,
+    }
+
+    #[async_trait]
+    impl Accessor for MockTruncatedService {
+        fn metadata(&self) -> AccessorMetadata {
+            let mut am = AccessorMetadata::default();
+            am.set_capabilities(AccessorCapability::Read);
+            am
+        }
+
+        async fn read(&self, _: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
+            let content = "Hello, World!".as_bytes();
+
+            let mut ranges = self.ranges.lock().unwrap();
+            ranges.push(args.range());
+
+            let offset = args.range().offset().unwrap_or_default() as usize;
+            let declared = content.len() - offset;
+            let body = if ranges.len() == 1 {
+                &content[offset..offset + 7]
+            } else {
+                &content[offset..]
+            };
+
+            let s = Box::pin(futures::stream::once(futures::future::ready(Ok::<
+                _,
+                io::Error,
+            >(
+                bytes::Bytes::from(body),
+            ))));
+            Ok((
+                RpRead::new(declared as u64),
+                Box::new(into_reader(s, Some(declared as u64))) as BytesReader,
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_truncated() -> anyhow::Result<()> {
+        let srv = Arc::new(MockTruncatedService::default());
+        let op = Operator::new(srv.clone());
+
+        let err = op.object("test").read().await.expect_err("read must fail");
+        assert!(err.is_temporary(), "truncated read must be retryable");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_read_truncated() -> anyhow::Result<()> {
+        let _ = env_logger::try_init();
+
+        let srv = Arc::new(MockTruncatedService::default());
+        let backoff = ConstantBackoff::default().with_delay(Duration::from_micros(1));
+        let op = Operator::new(srv.clone()).layer(RetryLayer::new(backoff));
+
+        let bs = op.object("test").read().await.expect("read must succeed");
+        assert_eq!(bs, "Hello, World!".as_bytes());
+        // The second request resumes from the truncated position.
+        assert_eq!(
+            *srv.ranges.lock().unwrap(),
+            vec![BytesRange::new(None, None), BytesRange::new(Some(7), None)]
+        );
+
+        Ok(())
+    }
 }
@@ -119,6 +119,20 @@ impl Accessor for SubdirAccessor {
         self.inner.delete(&path, args).await
     }
 
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let from = self.prepend_subdir(from);
+        let to = self.prepend_subdir(to);
+
+        self.inner.copy(&from, &to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let from = self.prepend_subdir(from);
+        let to = self.prepend_subdir(to);
+
+        self.inner.rename(&from, &to, args).await
+    }
+
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         let path = self.prepend_subdir(path);
         let (rp, pager) = self.inner.list(&path, args).await?;
@@ -173,6 +187,71 @@ impl Accessor for SubdirAccessor {
         self.inner.abort_multipart(&path, args).await
     }
 
+    async fn list_multipart(&self, path: &str, args: OpListMultipart) -> Result<RpListMultipart> {
+        let path = self.prepend_subdir(path);
+
+        let mut rp = self.inner.list_multipart(&path, args).await?;
+        for upload in rp.uploads_mut() {
+            let path = strip_subdir(&self.subdir, upload.path());
+            upload.set_path(&path);
+        }
+
+        Ok(rp)
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        let path = self.prepend_subdir(path);
+
+        let mut rp = self.inner.list_versions(&path, args).await?;
+        for version in rp.versions_mut() {
+            let path = strip_subdir(&self.subdir, version.path());
+            version.set_path(&path);
+        }
+
+        Ok(rp)
+    }
+
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let path = self.prepend_subdir(path);
+
+        self.inner.get_tags(&path, args).await
+    }
+
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        let path = self.prepend_subdir(path);
+
+        self.inner.set_tags(&path, args).await
+    }
+
+    async fn delete_tags(&self, path: &str, args: OpDeleteTags) -> Result<RpDeleteTags> {
+        let path = self.prepend_subdir(path);
+
+        self.inner.delete_tags(&path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let ops = match args.into_operation() {
+            BatchOperations::Delete(ops) => BatchOperations::Delete(
+                ops.into_iter()
+                    .map(|(path, op)| (self.prepend_subdir(&path), op))
+                    .collect(),
+            ),
+        };
+
+        let rp = self.inner.batch(OpBatch::new(ops)).await?;
+
+        let results = match rp.into_results() {
+            BatchedResults::Delete(results) => BatchedResults::Delete(
+                results
+                    .into_iter()
+                    .map(|(path, res)| (strip_subdir(&self.subdir, &path), res))
+                    .collect(),
+            ),
+        };
+
+        Ok(RpBatch::new(results))
+    }
+
     fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
         let path = self.prepend_subdir(path);
 
@@ -203,6 +282,13 @@ impl Accessor for SubdirAccessor {
         self.inner.blocking_delete(&path, args)
     }
 
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let from = self.prepend_subdir(from);
+        let to = self.prepend_subdir(to);
+
+        self.inner.blocking_copy(&from, &to, args)
+    }
+
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
         let path = self.prepend_subdir(path);
         let (rp, pager) = self.inner.blocking_list(&path, args)?;
@@ -0,0 +1,502 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::AsyncRead;
+use tokio::time::Sleep;
+
+use crate::raw::*;
+use crate::*;
+
+/// TimeoutLayer will add timeout for every async operation of OpenDAL.
+///
+/// # Notes
+///
+/// - The timeout of `read` only covers the time until the reader is
+///   returned. Use [`TimeoutLayer::with_io_timeout`] to guard the streaming
+///   body, the io timeout will be reset after every successful read, so
+///   downloads that keep making progress won't be killed.
+/// - The timeout of `write` covers the whole upload, please make sure it's
+///   large enough for the biggest object.
+/// - Blocking operations are passed through without timeout.
+///
+/// Operations that timed out will return a temporary [`ErrorKind::Unexpected`]
+/// error, so they can be retried by [`RetryLayer`][crate::layers::RetryLayer]
+/// which should be added after this layer.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::layers::TimeoutLayer;
+/// use opendal::Operator;
+/// use opendal::Scheme;
+///
+/// let _ = Operator::from_env(Scheme::Fs)
+///     .expect("must init")
+///     .layer(TimeoutLayer::new(Duration::from_secs(60)).with_io_timeout(Duration::from_secs(10)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeoutLayer {
+    timeout: Duration,
+    io_timeout: Option<Duration>,
+}
+
+impl TimeoutLayer {
+    /// Create a new TimeoutLayer with timeout for every operation.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            io_timeout: None,
+        }
+    }
+
+    /// Set timeout for every read from the reader returned by `read`.
+    pub fn with_io_timeout(mut self, timeout: Duration) -> Self {
+        self.io_timeout = Some(timeout);
+        self
+    }
+}
+
+impl Layer for TimeoutLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        Arc::new(TimeoutAccessor {
+            inner,
+            timeout: self.timeout,
+            io_timeout: self.io_timeout,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TimeoutAccessor {
+    inner: Arc<dyn Accessor>,
+    timeout: Duration,
+    io_timeout: Option<Duration>,
+}
+
+impl TimeoutAccessor {
+    async fn timeout<F, T>(&self, op: Operation, fut: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        tokio::time::timeout(self.timeout, fut)
+            .await
+            .map_err(|_| new_timeout_error(op, self.timeout))?
+    }
+}
+
+fn new_timeout_error(op: Operation, timeout: Duration) -> Error {
+    Error::new(ErrorKind::Unexpected, "operation timeout")
+        .with_operation(op.into_static())
+        .with_context("timeout", format!("{}s", timeout.as_secs_f64()))
+        .set_temporary()
+}
+
+#[async_trait]
+impl Accessor for TimeoutAccessor {
+    fn inner(&self) -> Option<Arc<dyn Accessor>> {
+        Some(self.inner.clone())
+    }
+
+    fn metadata(&self) -> AccessorMetadata {
+        self.inner.metadata()
+    }
+
+    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.timeout(Operation::Create, self.inner.create(path, args))
+            .await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
+        let (rp, r) = self
+            .timeout(Operation::Read, self.inner.read(path, args))
+            .await?;
+
+        let r = match self.io_timeout {
+            Some(timeout) => Box::new(TimeoutReader::new(r, timeout)) as BytesReader,
+            None => r,
+        };
+        Ok((rp, r))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
+        self.timeout(Operation::Write, self.inner.write(path, args, r))
+            .await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.timeout(Operation::Stat, self.inner.stat(path, args))
+            .await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.timeout(Operation::Delete, self.inner.delete(path, args))
+            .await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.timeout(Operation::Copy, self.inner.copy(from, to, args))
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.timeout(Operation::Rename, self.inner.rename(from, to, args))
+            .await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
+        let (rp, pager) = self
+            .timeout(Operation::List, self.inner.list(path, args))
+            .await?;
+
+        Ok((
+            rp,
+            Box::new(TimeoutPager::new(pager, self.timeout)) as ObjectPager,
+        ))
+    }
+
+    fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.inner.presign(path, args)
+    }
+
+    async fn create_multipart(
+        &self,
+        path: &str,
+        args: OpCreateMultipart,
+    ) -> Result<RpCreateMultipart> {
+        self.timeout(
+            Operation::CreateMultipart,
+            self.inner.create_multipart(path, args),
+        )
+        .await
+    }
+
+    async fn write_multipart(
+        &self,
+        path: &str,
+        args: OpWriteMultipart,
+        r: BytesReader,
+    ) -> Result<RpWriteMultipart> {
+        self.timeout(
+            Operation::WriteMultipart,
+            self.inner.write_multipart(path, args, r),
+        )
+        .await
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        self.timeout(
+            Operation::CompleteMultipart,
+            self.inner.complete_multipart(path, args),
+        )
+        .await
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        self.timeout(
+            Operation::AbortMultipart,
+            self.inner.abort_multipart(path, args),
+        )
+        .await
+    }
+
+    async fn list_multipart(&self, path: &str, args: OpListMultipart) -> Result<RpListMultipart> {
+        self.timeout(
+            Operation::ListMultipart,
+            self.inner.list_multipart(path, args),
+        )
+        .await
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        self.timeout(
+            Operation::ListVersions,
+            self.inner.list_versions(path, args),
+        )
+        .await
+    }
+
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.timeout(Operation::GetTags, self.inner.get_tags(path, args))
+            .await
+    }
+
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        self.timeout(Operation::SetTags, self.inner.set_tags(path, args))
+            .await
+    }
+
+    async fn delete_tags(&self, path: &str, args: OpDeleteTags) -> Result<RpDeleteTags> {
+        self.timeout(Operation::DeleteTags, self.inner.delete_tags(path, args))
+            .await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.timeout(Operation::Batch, self.inner.batch(args)).await
+    }
+
+    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.inner.blocking_create(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, BlockingBytesReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite, r: BlockingBytesReader) -> Result<RpWrite> {
+        self.inner.blocking_write(path, args, r)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+struct TimeoutReader {
+    inner: BytesReader,
+    timeout: Duration,
+    /// Deadline of current read, will be reset after every successful read.
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl TimeoutReader {
+    fn new(inner: BytesReader, timeout: Duration) -> Self {
+        Self {
+            inner,
+            timeout,
+            sleep: None,
+        }
+    }
+}
+
+impl AsyncRead for TimeoutReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if let Poll::Ready(res) = Pin::new(&mut (*self.inner)).poll_read(cx, buf) {
+            self.sleep = None;
+            return Poll::Ready(res);
+        }
+
+        let timeout = self.timeout;
+        let sleep = self
+            .sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep(timeout)));
+        match sleep.as_mut().poll(cx) {
+            Poll::Ready(_) => {
+                self.sleep = None;
+                Poll::Ready(Err(new_timeout_error(Operation::Read, timeout).into()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+struct TimeoutPager {
+    inner: ObjectPager,
+    timeout: Duration,
+}
+
+impl TimeoutPager {
+    fn new(inner: ObjectPager, timeout: Duration) -> Self {
+        Self { inner, timeout }
+    }
+}
+
+#[async_trait]
+impl ObjectPage for TimeoutPager {
+    async fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
+        tokio::time::timeout(self.timeout, self.inner.next_page())
+            .await
+            .map_err(|_| new_timeout_error(Operation::List, self.timeout))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::AsyncReadExt;
+
+    use super::*;
+    use crate::layers::Layer;
+    use crate::services::memory;
+
+    /// SlowAccessor will sleep `delay` before every stat and every read
+    /// from the returned reader.
+    #[derive(Debug)]
+    struct SlowAccessor {
+        inner: Arc<dyn Accessor>,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl Accessor for SlowAccessor {
+        fn inner(&self) -> Option<Arc<dyn Accessor>> {
+            Some(self.inner.clone())
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
+            let (rp, r) = self.inner.read(path, args).await?;
+            Ok((
+                rp,
+                Box::new(SlowReader {
+                    inner: r,
+                    delay: self.delay,
+                    sleep: None,
+                }) as BytesReader,
+            ))
+        }
+
+        async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
+            self.inner.write(path, args, r).await
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            tokio::time::sleep(self.delay).await;
+            self.inner.stat(path, args).await
+        }
+    }
+
+    /// SlowReader returns one byte after every `delay`.
+    struct SlowReader {
+        inner: BytesReader,
+        delay: Duration,
+        sleep: Option<Pin<Box<Sleep>>>,
+    }
+
+    impl AsyncRead for SlowReader {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let delay = self.delay;
+            let sleep = self
+                .sleep
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep(delay)));
+            futures::ready!(sleep.as_mut().poll(cx));
+            self.sleep = None;
+
+            let len = buf.len().min(1);
+            Pin::new(&mut (*self.inner)).poll_read(cx, &mut buf[..len])
+        }
+    }
+
+    async fn new_accessor(delay: Duration, layer: TimeoutLayer) -> Arc<dyn Accessor> {
+        let acc = Arc::new(SlowAccessor {
+            inner: Arc::new(memory::Builder::default().build().unwrap()),
+            delay,
+        });
+        let acc = layer.layer(acc);
+
+        acc.write(
+            "test",
+            OpWrite::new(5),
+            Box::new(futures::io::Cursor::new("Hello")),
+        )
+        .await
+        .expect("write must succeed");
+        acc
+    }
+
+    #[tokio::test]
+    async fn test_operation_timeout() {
+        let acc = new_accessor(
+            Duration::from_secs(3600),
+            TimeoutLayer::new(Duration::from_millis(10)),
+        )
+        .await;
+
+        let err = acc
+            .stat("test", OpStat::new())
+            .await
+            .expect_err("stat must timeout");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+    }
+
+    #[tokio::test]
+    async fn test_io_timeout() {
+        let acc = new_accessor(
+            Duration::from_millis(100),
+            TimeoutLayer::new(Duration::from_secs(3600)).with_io_timeout(Duration::from_millis(10)),
+        )
+        .await;
+
+        let (_, mut r) = acc
+            .read("test", OpRead::new())
+            .await
+            .expect("read must succeed");
+        let mut bs = Vec::new();
+        let err = r.read_to_end(&mut bs).await.expect_err("read must timeout");
+        let err = err
+            .into_inner()
+            .and_then(|e| e.downcast::<Error>().ok())
+            .expect("must be opendal error");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+    }
+
+    #[tokio::test]
+    async fn test_io_timeout_reset_on_progress() {
+        // Reading the whole content takes about 5 * 20ms, which is longer
+        // than io timeout, but every single read is within it.
+        let acc = new_accessor(
+            Duration::from_millis(20),
+            TimeoutLayer::new(Duration::from_secs(3600)).with_io_timeout(Duration::from_millis(60)),
+        )
+        .await;
+
+        let (_, mut r) = acc
+            .read("test", OpRead::new())
+            .await
+            .expect("read must succeed");
+        let mut bs = Vec::new();
+        r.read_to_end(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, b"Hello");
+    }
+}
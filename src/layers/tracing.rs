@@ -29,6 +29,25 @@ use crate::*;
 
 /// TracingLayer will add tracing for OpenDAL.
 ///
+/// # Notes
+///
+/// Every operation will be traced in a span named `opendal.<operation>`
+/// at `DEBUG` level, which carries the following fields:
+///
+/// - `scheme`: the scheme of underlying service.
+/// - `path`: the path of operation, or `from` and `to` for `copy` and `rename`.
+/// - `range`: the byte range of `read`.
+/// - `otel.status_code` and `error.kind`: set to `ERROR` and the
+///   [`ErrorKind`] of returning error if operation failed.
+///
+/// Spans of `read`, `write` and `list` are kept open until the returning
+/// reader or pager is dropped, so streaming bodies will be counted in.
+/// Parent span is taken from the current context, use
+/// `tracing-opentelemetry` to export them as OpenTelemetry spans.
+///
+/// Spans are only built while there is a subscriber interested in them,
+/// otherwise the cost is negligible.
+///
 /// # Examples
 ///
 /// ```
@@ -45,13 +64,15 @@ pub struct TracingLayer;
 
 impl Layer for TracingLayer {
     fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
-        Arc::new(TracingAccessor { inner })
+        let scheme = inner.metadata().scheme();
+        Arc::new(TracingAccessor { inner, scheme })
     }
 }
 
 #[derive(Debug, Clone)]
 struct TracingAccessor {
     inner: Arc<dyn Accessor>,
+    scheme: Scheme,
 }
 
 #[async_trait]
@@ -60,67 +81,145 @@ impl Accessor for TracingAccessor {
         Some(self.inner.clone())
     }
 
-    #[tracing::instrument(level = "debug")]
     fn metadata(&self) -> AccessorMetadata {
         self.inner.metadata()
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.create",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        self.inner.create(path, args).await
+        self.inner.create(path, args).await.map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.read",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, range = %args.range(), otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
-        self.inner.read(path, args).await.map(|(rp, r)| {
-            (
-                rp,
-                Box::new(TracingReader::new(Span::current(), r)) as BytesReader,
-            )
-        })
+        self.inner
+            .read(path, args)
+            .await
+            .map(|(rp, r)| {
+                (
+                    rp,
+                    Box::new(TracingReader::new(Span::current(), r)) as BytesReader,
+                )
+            })
+            .map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self, r))]
+    #[tracing::instrument(
+        name = "opendal.write",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, size = args.size(), otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
         let r = Box::new(TracingReader::new(Span::current(), r));
-        self.inner.write(path, args, r).await
+        self.inner.write(path, args, r).await.map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.stat",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        self.inner.stat(path, args).await
+        self.inner.stat(path, args).await.map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.delete",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        self.inner.delete(path, args).await
+        self.inner.delete(path, args).await.map_err(record_error)
+    }
+
+    #[tracing::instrument(
+        name = "opendal.copy",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, from = %from, to = %to, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner.copy(from, to, args).await.map_err(record_error)
+    }
+
+    #[tracing::instrument(
+        name = "opendal.rename",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, from = %from, to = %to, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .rename(from, to, args)
+            .await
+            .map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.list",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
-        self.inner.list(path, args).await.map(|(rp, s)| {
-            (
-                rp,
-                Box::new(TracingPager::new(Span::current(), s)) as ObjectPager,
-            )
-        })
+        self.inner
+            .list(path, args)
+            .await
+            .map(|(rp, s)| {
+                (
+                    rp,
+                    Box::new(TracingPager::new(Span::current(), s)) as ObjectPager,
+                )
+            })
+            .map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.presign",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
-        self.inner.presign(path, args)
+        self.inner.presign(path, args).map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.create_multipart",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     async fn create_multipart(
         &self,
         path: &str,
         args: OpCreateMultipart,
     ) -> Result<RpCreateMultipart> {
-        self.inner.create_multipart(path, args).await
+        self.inner
+            .create_multipart(path, args)
+            .await
+            .map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self, r))]
+    #[tracing::instrument(
+        name = "opendal.write_multipart",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, part_number = args.part_number(), size = args.size(), otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     async fn write_multipart(
         &self,
         path: &str,
@@ -128,68 +227,218 @@ impl Accessor for TracingAccessor {
         r: BytesReader,
     ) -> Result<RpWriteMultipart> {
         let r = Box::new(TracingReader::new(Span::current(), r));
-        self.inner.write_multipart(path, args, r).await
+        self.inner
+            .write_multipart(path, args, r)
+            .await
+            .map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.complete_multipart",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     async fn complete_multipart(
         &self,
         path: &str,
         args: OpCompleteMultipart,
     ) -> Result<RpCompleteMultipart> {
-        self.inner.complete_multipart(path, args).await
+        self.inner
+            .complete_multipart(path, args)
+            .await
+            .map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.abort_multipart",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     async fn abort_multipart(
         &self,
         path: &str,
         args: OpAbortMultipart,
     ) -> Result<RpAbortMultipart> {
-        self.inner.abort_multipart(path, args).await
+        self.inner
+            .abort_multipart(path, args)
+            .await
+            .map_err(record_error)
+    }
+
+    #[tracing::instrument(
+        name = "opendal.list_multipart",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
+    async fn list_multipart(&self, path: &str, args: OpListMultipart) -> Result<RpListMultipart> {
+        self.inner
+            .list_multipart(path, args)
+            .await
+            .map_err(record_error)
+    }
+
+    #[tracing::instrument(
+        name = "opendal.list_versions",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        self.inner
+            .list_versions(path, args)
+            .await
+            .map_err(record_error)
+    }
+
+    #[tracing::instrument(
+        name = "opendal.get_tags",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.inner.get_tags(path, args).await.map_err(record_error)
+    }
+
+    #[tracing::instrument(
+        name = "opendal.set_tags",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        self.inner.set_tags(path, args).await.map_err(record_error)
+    }
+
+    #[tracing::instrument(
+        name = "opendal.delete_tags",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
+    async fn delete_tags(&self, path: &str, args: OpDeleteTags) -> Result<RpDeleteTags> {
+        self.inner
+            .delete_tags(path, args)
+            .await
+            .map_err(record_error)
+    }
+
+    #[tracing::instrument(
+        name = "opendal.batch",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.inner.batch(args).await.map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.blocking_create",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        self.inner.blocking_create(path, args)
+        self.inner.blocking_create(path, args).map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.blocking_read",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, range = %args.range(), otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, BlockingBytesReader)> {
-        self.inner.blocking_read(path, args).map(|(rp, r)| {
-            (
-                rp,
-                Box::new(BlockingTracingReader::new(Span::current(), r)) as BlockingBytesReader,
-            )
-        })
+        self.inner
+            .blocking_read(path, args)
+            .map(|(rp, r)| {
+                (
+                    rp,
+                    Box::new(BlockingTracingReader::new(Span::current(), r)) as BlockingBytesReader,
+                )
+            })
+            .map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self, r))]
+    #[tracing::instrument(
+        name = "opendal.blocking_write",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, size = args.size(), otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     fn blocking_write(&self, path: &str, args: OpWrite, r: BlockingBytesReader) -> Result<RpWrite> {
-        self.inner.blocking_write(path, args, r)
+        let r = Box::new(BlockingTracingReader::new(Span::current(), r));
+        self.inner
+            .blocking_write(path, args, r)
+            .map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.blocking_stat",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        self.inner.blocking_stat(path, args)
+        self.inner.blocking_stat(path, args).map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.blocking_delete",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        self.inner.blocking_delete(path, args)
+        self.inner.blocking_delete(path, args).map_err(record_error)
+    }
+
+    #[tracing::instrument(
+        name = "opendal.blocking_copy",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, from = %from, to = %to, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .blocking_copy(from, to, args)
+            .map_err(record_error)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        name = "opendal.blocking_list",
+        level = "debug",
+        skip_all,
+        fields(scheme = %self.scheme, path = %path, otel.status_code = tracing::field::Empty, error.kind = tracing::field::Empty)
+    )]
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
-        self.inner.blocking_list(path, args).map(|(rp, it)| {
-            (
-                rp,
-                Box::new(BlockingTracingPager::new(Span::current(), it)) as BlockingObjectPager,
-            )
-        })
+        self.inner
+            .blocking_list(path, args)
+            .map(|(rp, it)| {
+                (
+                    rp,
+                    Box::new(BlockingTracingPager::new(Span::current(), it)) as BlockingObjectPager,
+                )
+            })
+            .map_err(record_error)
     }
 }
 
+/// Mark current span as failed with the kind of error.
+///
+/// The `otel.status_code` field will be picked up by
+/// `tracing-opentelemetry` as the status of exported span.
+fn record_error(err: Error) -> Error {
+    let span = Span::current();
+    span.record("otel.status_code", "ERROR");
+    span.record("error.kind", err.kind().into_static());
+    err
+}
+
 struct TracingReader {
     span: Span,
     inner: BytesReader,
@@ -277,3 +526,135 @@ impl BlockingObjectPage for BlockingTracingPager {
         self.inner.next_page()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+    use std::sync::Mutex;
+
+    use futures::AsyncReadExt;
+    use tracing::field::Field;
+    use tracing::field::Visit;
+    use tracing::span::Attributes;
+    use tracing::span::Id;
+    use tracing::span::Record;
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::Context;
+    use tracing_subscriber::prelude::*;
+    use tracing_subscriber::registry::LookupSpan;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockSpan {
+        name: String,
+        fields: HashMap<String, String>,
+        closed: bool,
+    }
+
+    /// Subscriber layer that records all spans in memory.
+    #[derive(Debug, Default, Clone)]
+    struct MockSubscriber {
+        spans: Arc<Mutex<HashMap<u64, MockSpan>>>,
+    }
+
+    impl MockSubscriber {
+        fn span(&self, name: &str) -> Option<(HashMap<String, String>, bool)> {
+            let spans = self.spans.lock().unwrap();
+            spans
+                .values()
+                .find(|v| v.name == name)
+                .map(|v| (v.fields.clone(), v.closed))
+        }
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> tracing_subscriber::Layer<S> for MockSubscriber {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _: Context<'_, S>) {
+            let mut span = MockSpan {
+                name: attrs.metadata().name().to_string(),
+                ..Default::default()
+            };
+            attrs.record(&mut FieldVisitor(&mut span.fields));
+            self.spans.lock().unwrap().insert(id.into_u64(), span);
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, _: Context<'_, S>) {
+            if let Some(span) = self.spans.lock().unwrap().get_mut(&id.into_u64()) {
+                values.record(&mut FieldVisitor(&mut span.fields));
+            }
+        }
+
+        fn on_close(&self, id: Id, _: Context<'_, S>) {
+            if let Some(span) = self.spans.lock().unwrap().get_mut(&id.into_u64()) {
+                span.closed = true;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tracing_spans() {
+        let subscriber = MockSubscriber::default();
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry().with(subscriber.clone()),
+        );
+
+        let op = Operator::from_env(Scheme::Memory)
+            .expect("build must succeed")
+            .layer(TracingLayer);
+        op.object("file")
+            .write("Hello, World!")
+            .await
+            .expect("write must succeed");
+
+        let (fields, _) = subscriber.span("opendal.write").expect("span must exist");
+        assert_eq!(fields["scheme"], "memory");
+        assert_eq!(fields["path"], "file");
+        assert_eq!(fields["size"], "13");
+        assert!(!fields.contains_key("otel.status_code"));
+
+        // Span of read must be kept open until the reader is dropped.
+        let (_, mut r) = op
+            .inner()
+            .read(
+                "file",
+                OpRead::new().with_range(BytesRange::new(Some(0), Some(5))),
+            )
+            .await
+            .expect("read must succeed");
+        let (fields, closed) = subscriber.span("opendal.read").expect("span must exist");
+        assert_eq!(fields["range"], "0-4");
+        assert!(!closed);
+
+        let mut bs = Vec::new();
+        r.read_to_end(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, b"Hello");
+        drop(r);
+        let (_, closed) = subscriber.span("opendal.read").expect("span must exist");
+        assert!(closed);
+
+        let err = op
+            .object("not_exist")
+            .metadata()
+            .await
+            .expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+        let (fields, _) = subscriber.span("opendal.stat").expect("span must exist");
+        assert_eq!(fields["path"], "not_exist");
+        assert_eq!(fields["otel.status_code"], "ERROR");
+        assert_eq!(fields["error.kind"], "ObjectNotFound");
+    }
+}
@@ -0,0 +1,427 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::io::Cursor;
+
+use crate::raw::*;
+use crate::*;
+
+/// WriteOnceLayer will turn the underlying storage into a write-once store.
+///
+/// # Behavior
+///
+/// - Writing to an existing object returns [`ErrorKind::ConditionNotMatch`].
+/// - Deleting any object returns [`ErrorKind::ObjectPermissionDenied`].
+/// - Presigning write operations returns [`ErrorKind::ObjectPermissionDenied`].
+/// - Copying to an existing object returns [`ErrorKind::ConditionNotMatch`].
+///
+/// Existence is checked by [`WriteOnceMode`]:
+///
+/// - [`WriteOnceMode::CheckThenWrite`] (default) will `stat` before write,
+///   it works on all services but concurrent writers could still overwrite
+///   each other.
+/// - [`WriteOnceMode::ConditionalCreate`] will write with `if_not_exists`,
+///   it's race safe but only works on services that have
+///   [`AccessorCapability::ConditionalWrite`]. Multipart is disabled in this
+///   mode.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::WriteOnceLayer;
+/// use opendal::layers::WriteOnceMode;
+/// use opendal::Operator;
+/// use opendal::Scheme;
+///
+/// let _ = Operator::from_env(Scheme::Fs)
+///     .expect("must init")
+///     .layer(WriteOnceLayer::new().with_mode(WriteOnceMode::ConditionalCreate));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct WriteOnceLayer {
+    mode: WriteOnceMode,
+}
+
+/// WriteOnceMode decides how [`WriteOnceLayer`] checks object existence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOnceMode {
+    /// Stat the object before writing.
+    CheckThenWrite,
+    /// Write the object with `if_not_exists`.
+    ConditionalCreate,
+}
+
+impl Default for WriteOnceMode {
+    fn default() -> Self {
+        Self::CheckThenWrite
+    }
+}
+
+impl WriteOnceLayer {
+    /// Create a new WriteOnceLayer with [`WriteOnceMode::CheckThenWrite`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the mode of WriteOnceLayer.
+    pub fn with_mode(mut self, mode: WriteOnceMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+impl Layer for WriteOnceLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        Arc::new(WriteOnceAccessor {
+            inner,
+            mode: self.mode,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WriteOnceAccessor {
+    inner: Arc<dyn Accessor>,
+    mode: WriteOnceMode,
+}
+
+impl WriteOnceAccessor {
+    fn new_error(&self, kind: ErrorKind, message: &str, operation: Operation, path: &str) -> Error {
+        Error::new(kind, message)
+            .with_operation(operation.into_static())
+            .with_context("service", self.inner.metadata().scheme().into_static())
+            .with_context("path", path)
+    }
+
+    fn new_delete_error(&self, operation: Operation, path: &str) -> Error {
+        self.new_error(
+            ErrorKind::ObjectPermissionDenied,
+            "delete is not allowed by write once layer",
+            operation,
+            path,
+        )
+    }
+
+    fn new_exists_error(&self, operation: Operation, path: &str) -> Error {
+        self.new_error(
+            ErrorKind::ConditionNotMatch,
+            "object already exists",
+            operation,
+            path,
+        )
+    }
+
+    /// Prepare the write args and check existence on demand.
+    async fn prepare_write(
+        &self,
+        operation: Operation,
+        path: &str,
+        args: OpWrite,
+    ) -> Result<OpWrite> {
+        match self.mode {
+            WriteOnceMode::CheckThenWrite => {
+                self.check_not_exists(operation, path).await?;
+                Ok(args)
+            }
+            WriteOnceMode::ConditionalCreate => {
+                self.check_conditional_write(operation, path)?;
+                Ok(args.with_if_not_exists(true))
+            }
+        }
+    }
+
+    fn blocking_prepare_write(
+        &self,
+        operation: Operation,
+        path: &str,
+        args: OpWrite,
+    ) -> Result<OpWrite> {
+        match self.mode {
+            WriteOnceMode::CheckThenWrite => {
+                self.blocking_check_not_exists(operation, path)?;
+                Ok(args)
+            }
+            WriteOnceMode::ConditionalCreate => {
+                self.check_conditional_write(operation, path)?;
+                Ok(args.with_if_not_exists(true))
+            }
+        }
+    }
+
+    async fn check_not_exists(&self, operation: Operation, path: &str) -> Result<()> {
+        match self.inner.stat(path, OpStat::new()).await {
+            Ok(_) => Err(self.new_exists_error(operation, path)),
+            Err(err) if err.kind() == ErrorKind::ObjectNotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn blocking_check_not_exists(&self, operation: Operation, path: &str) -> Result<()> {
+        match self.inner.blocking_stat(path, OpStat::new()) {
+            Ok(_) => Err(self.new_exists_error(operation, path)),
+            Err(err) if err.kind() == ErrorKind::ObjectNotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Copy inside service can't be done with `if_not_exists`, so it's
+    /// only allowed in [`WriteOnceMode::CheckThenWrite`].
+    fn check_copy(&self, operation: Operation, path: &str) -> Result<()> {
+        match self.mode {
+            WriteOnceMode::CheckThenWrite => Ok(()),
+            WriteOnceMode::ConditionalCreate => Err(self.new_error(
+                ErrorKind::Unsupported,
+                "copy is not supported in conditional create mode",
+                operation,
+                path,
+            )),
+        }
+    }
+
+    fn check_conditional_write(&self, operation: Operation, path: &str) -> Result<()> {
+        if self
+            .inner
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::ConditionalWrite)
+        {
+            Ok(())
+        } else {
+            Err(self.new_error(
+                ErrorKind::Unsupported,
+                "service doesn't support conditional write",
+                operation,
+                path,
+            ))
+        }
+    }
+
+    fn check_multipart(&self, operation: Operation, path: &str) -> Result<()> {
+        match self.mode {
+            WriteOnceMode::CheckThenWrite => Ok(()),
+            WriteOnceMode::ConditionalCreate => Err(self.new_error(
+                ErrorKind::Unsupported,
+                "multipart is not supported in conditional create mode",
+                operation,
+                path,
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl Accessor for WriteOnceAccessor {
+    fn inner(&self) -> Option<Arc<dyn Accessor>> {
+        Some(self.inner.clone())
+    }
+
+    fn metadata(&self) -> AccessorMetadata {
+        let mut meta = self.inner.metadata();
+        let mut cap = meta.capabilities() - AccessorCapability::Batch;
+        if self.mode == WriteOnceMode::ConditionalCreate {
+            cap -= AccessorCapability::Multipart | AccessorCapability::Copy;
+        }
+        meta.set_capabilities(cap);
+        meta
+    }
+
+    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        if args.mode() == ObjectMode::DIR {
+            return self.inner.create(path, args).await;
+        }
+
+        // Create a file will truncate the existing one, so we treat it as
+        // an empty write.
+        self.write(path, OpWrite::new(0), Box::new(Cursor::new(vec![])))
+            .await
+            .map(|_| RpCreate::default())
+    }
+
+    async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
+        let args = self.prepare_write(Operation::Write, path, args).await?;
+
+        self.inner.write(path, args, r).await
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        Err(self.new_delete_error(Operation::Delete, path))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.check_copy(Operation::Copy, to)?;
+        self.check_not_exists(Operation::Copy, to).await?;
+
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, _: &str, _: OpRename) -> Result<RpRename> {
+        Err(self.new_delete_error(Operation::Rename, from))
+    }
+
+    fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        match args.operation() {
+            PresignOperation::Write(_) | PresignOperation::WriteMultipart(_) => Err(self
+                .new_error(
+                    ErrorKind::ObjectPermissionDenied,
+                    "presign write is not allowed by write once layer",
+                    Operation::Presign,
+                    path,
+                )),
+            _ => self.inner.presign(path, args),
+        }
+    }
+
+    async fn create_multipart(
+        &self,
+        path: &str,
+        args: OpCreateMultipart,
+    ) -> Result<RpCreateMultipart> {
+        self.check_multipart(Operation::CreateMultipart, path)?;
+        self.check_not_exists(Operation::CreateMultipart, path)
+            .await?;
+
+        self.inner.create_multipart(path, args).await
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        self.check_multipart(Operation::CompleteMultipart, path)?;
+        // Check again in case of object has been created during uploading.
+        self.check_not_exists(Operation::CompleteMultipart, path)
+            .await?;
+
+        self.inner.complete_multipart(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        match args.operation() {
+            BatchOperations::Delete(ops) => Err(self.new_delete_error(
+                Operation::Batch,
+                ops.first().map(|(p, _)| p.as_str()).unwrap_or_default(),
+            )),
+        }
+    }
+
+    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        if args.mode() == ObjectMode::DIR {
+            return self.inner.blocking_create(path, args);
+        }
+
+        self.blocking_write(path, OpWrite::new(0), Box::new(io::Cursor::new(vec![])))
+            .map(|_| RpCreate::default())
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite, r: BlockingBytesReader) -> Result<RpWrite> {
+        let args = self.blocking_prepare_write(Operation::BlockingWrite, path, args)?;
+
+        self.inner.blocking_write(path, args, r)
+    }
+
+    fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        Err(self.new_delete_error(Operation::BlockingDelete, path))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.check_copy(Operation::BlockingCopy, to)?;
+        self.blocking_check_not_exists(Operation::BlockingCopy, to)?;
+
+        self.inner.blocking_copy(from, to, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::fs;
+    use crate::services::memory;
+
+    fn new_fs_operator(mode: WriteOnceMode) -> Operator {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let acc = fs::Builder::default()
+            .root(&dir.to_string_lossy())
+            .build()
+            .expect("fs must init");
+
+        Operator::new(acc).layer(WriteOnceLayer::new().with_mode(mode))
+    }
+
+    #[tokio::test]
+    async fn test_check_then_write() {
+        let op =
+            Operator::new(memory::Builder::default().build().unwrap()).layer(WriteOnceLayer::new());
+
+        let o = op.object("test");
+        o.write("Hello").await.expect("first write must succeed");
+
+        let err = o.write("World").await.expect_err("overwrite must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        let err = o.create().await.expect_err("create must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        assert_eq!(o.read().await.unwrap(), b"Hello");
+
+        let err = o.delete().await.expect_err("delete must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectPermissionDenied);
+        assert!(o.is_exist().await.unwrap());
+
+        op.object("dir/")
+            .create()
+            .await
+            .expect("create dir must succeed");
+        op.object("dir/")
+            .create()
+            .await
+            .expect("create dir must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_conditional_create() {
+        let op = new_fs_operator(WriteOnceMode::ConditionalCreate);
+        assert!(!op.metadata().can_multipart());
+
+        let o = op.object("test");
+        o.write("Hello").await.expect("first write must succeed");
+
+        let err = o.write("World").await.expect_err("overwrite must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        assert_eq!(o.read().await.unwrap(), b"Hello");
+
+        let err = o.blocking_write("World").expect_err("overwrite must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+        let err = o.blocking_delete().expect_err("delete must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectPermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_create_unsupported() {
+        let op = Operator::new(memory::Builder::default().build().unwrap())
+            .layer(WriteOnceLayer::new().with_mode(WriteOnceMode::ConditionalCreate));
+
+        let err = op
+            .object("test")
+            .write("Hello")
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+}
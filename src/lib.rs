@@ -22,8 +22,10 @@
 //!
 //! | Services | Description |
 //! | -------- | ----------- |
+//! | [alluxio][services::alluxio] | Alluxio services via REST API. |
 //! | [azblob][services::azblob] | Azure Storage Blob services. |
 //! | [azdfs][services::azdfs] | Azure Data Lake Storage Gen2 services. |
+//! | [b2][services::b2] | Backblaze B2 services via native API. |
 //! | [fs][services::fs] | POSIX alike file system. |
 //! | [ftp][services::ftp] | FTP and FTPS support. |
 //! | [gcs][services::gcs] | Google Cloud Storage service. |
@@ -31,13 +33,16 @@
 //! | [http][services::http] | HTTP read-only backend. |
 //! | [ipfs][services::ipfs] | IPFS HTTP Gateway support. |
 //! | [ipmfs][services::ipmfs] | IPFS Mutable File System support. |
+//! | [memcached][services::memcached] | Memcached service. |
 //! | [memory][services::memory] | In memory backend support. |
 //! | [moka][services::moka] | [moka](https://github.com/moka-rs/moka) backend support. |
 //! | [obs][services::obs] | Huawei Cloud OBS service. |
 //! | [oss][services::oss] | Aliyun Object Storage Service (OSS).|
+//! | [postgresql][services::postgresql] | PostgreSQL table service. |
 //! | [redis][services::redis] | Redis service. |
 //! | [rocksdb][services::rocksdb] | RocksDB service. |
 //! | [s3][services::s3] | AWS S3 alike services. |
+//! | [swift][services::swift] | OpenStack Swift services. |
 //!
 //! More services support is tracked at [opendal#5](https://github.com/datafuselabs/opendal/issues/5)
 //!
@@ -57,6 +62,7 @@
 //! | [RetryLayer][layers::RetryLayer] | Retry for failed operations. |
 //! | [SubdirLayer][layers::SubdirLayer] | Allow switching directory. |
 //! | [TracingLayer][layers::TracingLayer] | Tracing for every operations. |
+//! | [WriteOnceLayer][layers::WriteOnceLayer] | Reject overwrites and deletes. |
 //!
 //! # Optional features
 //!
@@ -70,9 +76,12 @@
 //!
 //! - `services-ftp`: Enable ftp service support.
 //! - `services-hdfs`: Enable hdfs service support.
+//! - `services-memcached`: Enable memcached service support.
 //! - `services-moka`: Enable moka service support.
 //! - `services-ipfs`: Enable ipfs service support.
+//! - `services-postgresql`: Enable postgresql service support.
 //! - `services-redis`: Enable redis service support.
+//! - `services-redis-tls`: Enable redis service support with TLS connections.
 //! - `services-rocksdb`: Enable rocksdb service support.
 //!
 //! ## Dependencies features
@@ -152,13 +161,19 @@ pub use operator::Operator;
 pub use operator::OperatorMetadata;
 
 mod object;
+pub use object::BlockingObjectLister;
+pub use object::BlockingObjectReader;
 pub use object::Object;
 pub use object::ObjectLister;
 pub use object::ObjectMetadata;
+pub use object::ObjectMetakey;
 pub use object::ObjectMode;
 pub use object::ObjectMultipart;
 pub use object::ObjectPart;
 pub use object::ObjectReader;
+pub use object::ObjectUpload;
+pub use object::ObjectVersion;
+pub use object::ObjectWriter;
 
 mod scheme;
 pub use scheme::Scheme;
@@ -169,14 +184,23 @@ pub use error::ErrorKind;
 pub use error::Result;
 
 mod ops;
+pub use ops::BatchOperations;
 pub use ops::OpAbortMultipart;
+pub use ops::OpBatch;
 pub use ops::OpCompleteMultipart;
+pub use ops::OpCopy;
 pub use ops::OpCreate;
 pub use ops::OpCreateMultipart;
 pub use ops::OpDelete;
+pub use ops::OpDeleteTags;
+pub use ops::OpGetTags;
 pub use ops::OpList;
+pub use ops::OpListMultipart;
+pub use ops::OpListVersions;
 pub use ops::OpPresign;
 pub use ops::OpRead;
+pub use ops::OpRename;
+pub use ops::OpSetTags;
 pub use ops::OpStat;
 pub use ops::OpWrite;
 pub use ops::OpWriteMultipart;
@@ -203,10 +227,10 @@ mod tests {
     fn assert_size() {
         assert_eq!(80, size_of::<AccessorMetadata>());
         assert_eq!(16, size_of::<Operator>());
-        assert_eq!(16, size_of::<BatchOperator>());
-        assert_eq!(184, size_of::<ObjectEntry>());
+        assert_eq!(24, size_of::<BatchOperator>());
+        assert_eq!(296, size_of::<ObjectEntry>());
         assert_eq!(48, size_of::<Object>());
-        assert_eq!(160, size_of::<ObjectMetadata>());
+        assert_eq!(272, size_of::<ObjectMetadata>());
         assert_eq!(1, size_of::<ObjectMode>());
         assert_eq!(64, size_of::<ObjectMultipart>());
         assert_eq!(32, size_of::<ObjectPart>());
@@ -36,6 +36,8 @@ pub struct ObjectLister {
     pager: Option<ObjectPager>,
 
     buf: VecDeque<ObjectEntry>,
+    /// Path of the last object that has been returned.
+    last: Option<String>,
     /// We will move `pager` inside future and return it back while future is ready.
     /// Thus, we should not allow calling other function while we already have
     /// a future.
@@ -50,6 +52,7 @@ impl ObjectLister {
             acc: op.inner(),
             pager: Some(pager),
             buf: VecDeque::default(),
+            last: None,
             fut: None,
         }
     }
@@ -59,6 +62,15 @@ impl ObjectLister {
         self.acc.clone().into()
     }
 
+    /// Get the continuation token of this lister, which is the path of
+    /// the last returned object.
+    ///
+    /// Returns `None` if no object has been returned yet. Users can persist
+    /// this token and resume listing via [`OpList::with_start_after`].
+    pub fn continuation_token(&self) -> Option<&str> {
+        self.last.as_deref()
+    }
+
     /// next_page can be used to fetch a new object page.
     ///
     /// # Notes
@@ -88,6 +100,9 @@ impl ObjectLister {
                 None => return Ok(None),
             }
         };
+        if let Some(oe) = entries.back() {
+            self.last = Some(oe.path().to_string());
+        }
 
         Ok(Some(
             entries
@@ -103,6 +118,7 @@ impl Stream for ObjectLister {
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         if let Some(oe) = self.buf.pop_front() {
+            self.last = Some(oe.path().to_string());
             return Poll::Ready(Some(Ok(oe.into_object(self.operator()))));
         }
 
@@ -134,10 +150,16 @@ impl Stream for ObjectLister {
     }
 }
 
+/// BlockingObjectLister is returned by `Object::blocking_list` to list objects.
+///
+/// User can use blocking object lister as `Iterator<Item = Result<Object>>` or
+/// call `next_page` directly.
 pub struct BlockingObjectLister {
     acc: Arc<dyn Accessor>,
     pager: BlockingObjectPager,
     buf: VecDeque<ObjectEntry>,
+    /// Path of the last object that has been returned.
+    last: Option<String>,
 }
 
 impl BlockingObjectLister {
@@ -147,6 +169,7 @@ impl BlockingObjectLister {
             acc,
             pager,
             buf: VecDeque::default(),
+            last: None,
         }
     }
 
@@ -155,6 +178,15 @@ impl BlockingObjectLister {
         self.acc.clone().into()
     }
 
+    /// Get the continuation token of this lister, which is the path of
+    /// the last returned object.
+    ///
+    /// Returns `None` if no object has been returned yet. Users can persist
+    /// this token and resume listing via [`OpList::with_start_after`].
+    pub fn continuation_token(&self) -> Option<&str> {
+        self.last.as_deref()
+    }
+
     /// next_page can be used to fetch a new object page.
     pub fn next_page(&mut self) -> Result<Option<Vec<Object>>> {
         let entries = if !self.buf.is_empty() {
@@ -168,6 +200,9 @@ impl BlockingObjectLister {
                 None => return Ok(None),
             }
         };
+        if let Some(oe) = entries.back() {
+            self.last = Some(oe.path().to_string());
+        }
 
         Ok(Some(
             entries
@@ -184,6 +219,7 @@ impl Iterator for BlockingObjectLister {
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(oe) = self.buf.pop_front() {
+            self.last = Some(oe.path().to_string());
             return Some(Ok(oe.into_object(self.operator())));
         }
 
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use flagset::flags;
+use flagset::FlagSet;
 use time::OffsetDateTime;
 
 use crate::raw::*;
@@ -44,12 +46,22 @@ pub struct ObjectMetadata {
     content_md5: Option<String>,
     /// Content Type of this object.
     content_type: Option<String>,
+    /// Content Disposition of this object.
+    content_disposition: Option<String>,
+    /// Cache Control of this object.
+    cache_control: Option<String>,
+    /// Content Encoding of this object.
+    content_encoding: Option<String>,
     /// Content Range of this object.
     content_range: Option<BytesContentRange>,
     /// Last Modified of this object.
     last_modified: Option<OffsetDateTime>,
+    /// Expires of this object.
+    expires: Option<OffsetDateTime>,
     /// ETag of this object.
     etag: Option<String>,
+    /// Version id of this object.
+    version_id: Option<String>,
 }
 
 impl ObjectMetadata {
@@ -63,9 +75,14 @@ impl ObjectMetadata {
             content_length: None,
             content_md5: None,
             content_type: None,
+            content_disposition: None,
+            cache_control: None,
+            content_encoding: None,
             content_range: None,
             last_modified: None,
+            expires: None,
             etag: None,
+            version_id: None,
         }
     }
 
@@ -86,6 +103,57 @@ impl ObjectMetadata {
         self
     }
 
+    /// Metakeys of fields that have been populated in this metadata.
+    ///
+    /// [`ObjectMetakey::Complete`] will be included if this metadata is
+    /// complete, which means all other fields are populated as much as
+    /// services can provide.
+    pub fn metakeys(&self) -> FlagSet<ObjectMetakey> {
+        let mut keys = FlagSet::default();
+
+        if self.complete {
+            keys |= ObjectMetakey::Complete;
+        }
+        if self.mode != ObjectMode::Unknown {
+            keys |= ObjectMetakey::Mode;
+        }
+        if self.content_length.is_some() {
+            keys |= ObjectMetakey::ContentLength;
+        }
+        if self.content_md5.is_some() {
+            keys |= ObjectMetakey::ContentMd5;
+        }
+        if self.content_type.is_some() {
+            keys |= ObjectMetakey::ContentType;
+        }
+        if self.content_disposition.is_some() {
+            keys |= ObjectMetakey::ContentDisposition;
+        }
+        if self.cache_control.is_some() {
+            keys |= ObjectMetakey::CacheControl;
+        }
+        if self.content_encoding.is_some() {
+            keys |= ObjectMetakey::ContentEncoding;
+        }
+        if self.content_range.is_some() {
+            keys |= ObjectMetakey::ContentRange;
+        }
+        if self.last_modified.is_some() {
+            keys |= ObjectMetakey::LastModified;
+        }
+        if self.expires.is_some() {
+            keys |= ObjectMetakey::Expires;
+        }
+        if self.etag.is_some() {
+            keys |= ObjectMetakey::Etag;
+        }
+        if self.version_id.is_some() {
+            keys |= ObjectMetakey::VersionId;
+        }
+
+        keys
+    }
+
     /// Object mode represent this object's mode.
     pub fn mode(&self) -> ObjectMode {
         self.mode
@@ -179,6 +247,75 @@ impl ObjectMetadata {
         self
     }
 
+    /// Content Disposition of this object.
+    ///
+    /// Content Disposition is defined by [RFC 6266](https://httpwg.org/specs/rfc6266.html).
+    pub fn content_disposition(&self) -> Option<&str> {
+        self.content_disposition.as_deref()
+    }
+
+    /// Set Content Disposition of this object.
+    ///
+    /// Content Disposition is defined by [RFC 6266](https://httpwg.org/specs/rfc6266.html).
+    pub fn set_content_disposition(&mut self, v: &str) -> &mut Self {
+        self.content_disposition = Some(v.to_string());
+        self
+    }
+
+    /// Set Content Disposition of this object.
+    ///
+    /// Content Disposition is defined by [RFC 6266](https://httpwg.org/specs/rfc6266.html).
+    pub fn with_content_disposition(mut self, v: &str) -> Self {
+        self.content_disposition = Some(v.to_string());
+        self
+    }
+
+    /// Cache Control of this object.
+    ///
+    /// Cache Control is defined by [RFC 9111](https://httpwg.org/specs/rfc9111.html#field.cache-control).
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    /// Set Cache Control of this object.
+    ///
+    /// Cache Control is defined by [RFC 9111](https://httpwg.org/specs/rfc9111.html#field.cache-control).
+    pub fn set_cache_control(&mut self, v: &str) -> &mut Self {
+        self.cache_control = Some(v.to_string());
+        self
+    }
+
+    /// Set Cache Control of this object.
+    ///
+    /// Cache Control is defined by [RFC 9111](https://httpwg.org/specs/rfc9111.html#field.cache-control).
+    pub fn with_cache_control(mut self, v: &str) -> Self {
+        self.cache_control = Some(v.to_string());
+        self
+    }
+
+    /// Content Encoding of this object.
+    ///
+    /// Content Encoding is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-encoding).
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.content_encoding.as_deref()
+    }
+
+    /// Set Content Encoding of this object.
+    ///
+    /// Content Encoding is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-encoding).
+    pub fn set_content_encoding(&mut self, v: &str) -> &mut Self {
+        self.content_encoding = Some(v.to_string());
+        self
+    }
+
+    /// Set Content Encoding of this object.
+    ///
+    /// Content Encoding is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-encoding).
+    pub fn with_content_encoding(mut self, v: &str) -> Self {
+        self.content_encoding = Some(v.to_string());
+        self
+    }
+
     /// Content Range of this object.
     ///
     /// Content Range is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-range).
@@ -230,17 +367,53 @@ impl ObjectMetadata {
         self
     }
 
+    /// Expires of this object.
+    ///
+    /// `Expires` is defined by [RFC 7234](https://httpwg.org/specs/rfc7234.html#header.expires)
+    /// Refer to [MDN Expires](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Expires) for more information.
+    ///
+    /// It's a caching hint instead of lifecycle of this object. Invalid
+    /// values like `0` will be ignored.
+    pub fn expires(&self) -> Option<OffsetDateTime> {
+        self.expires
+    }
+
+    /// Set Expires of this object.
+    ///
+    /// `Expires` is defined by [RFC 7234](https://httpwg.org/specs/rfc7234.html#header.expires)
+    /// Refer to [MDN Expires](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Expires) for more information.
+    pub fn set_expires(&mut self, expires: OffsetDateTime) -> &mut Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    /// Set Expires of this object.
+    ///
+    /// `Expires` is defined by [RFC 7234](https://httpwg.org/specs/rfc7234.html#header.expires)
+    /// Refer to [MDN Expires](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Expires) for more information.
+    pub fn with_expires(mut self, expires: OffsetDateTime) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
     /// ETag of this object.
     ///
     /// `ETag` is defined by [RFC 7232](https://httpwg.org/specs/rfc7232.html#header.etag)
     /// Refer to [MDN ETag](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/ETag) for more information.
     ///
-    /// OpenDAL will return this value AS-IS like the following:
+    /// OpenDAL will return this value with surrounding quotes stripped while
+    /// the weak prefix `W/` is kept:
+    ///
+    /// - `33a64df551425fcc55e4d42a148795d9f25f89d4`
+    /// - `W/0815`
     ///
-    /// - `"33a64df551425fcc55e4d42a148795d9f25f89d4"`
-    /// - `W/"0815"`
+    /// The returned value can be passed to [`OpRead::with_if_match`][crate::OpRead::with_if_match]
+    /// directly.
     ///
-    /// `"` is part of etag.
+    /// fs doesn't have etag, a synthesized one like `<mtime>-<length>` will
+    /// be returned so that conditional reads work. It's not guaranteed to
+    /// change on rewrite if mtime and length are not changed. memory always
+    /// returns `None`.
     pub fn etag(&self) -> Option<&str> {
         self.etag.as_deref()
     }
@@ -250,12 +423,11 @@ impl ObjectMetadata {
     /// `ETag` is defined by [RFC 7232](https://httpwg.org/specs/rfc7232.html#header.etag)
     /// Refer to [MDN ETag](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/ETag) for more information.
     ///
-    /// OpenDAL will return this value AS-IS like the following:
+    /// Services should strip surrounding quotes but keep the weak prefix
+    /// `W/` before setting, like the following:
     ///
-    /// - `"33a64df551425fcc55e4d42a148795d9f25f89d4"`
-    /// - `W/"0815"`
-    ///
-    /// `"` is part of etag, don't trim it before setting.
+    /// - `33a64df551425fcc55e4d42a148795d9f25f89d4`
+    /// - `W/0815`
     pub fn set_etag(&mut self, etag: &str) -> &mut Self {
         self.etag = Some(etag.to_string());
         self
@@ -266,14 +438,69 @@ impl ObjectMetadata {
     /// `ETag` is defined by [RFC 7232](https://httpwg.org/specs/rfc7232.html#header.etag)
     /// Refer to [MDN ETag](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/ETag) for more information.
     ///
-    /// OpenDAL will return this value AS-IS like the following:
-    ///
-    /// - `"33a64df551425fcc55e4d42a148795d9f25f89d4"`
-    /// - `W/"0815"`
+    /// Services should strip surrounding quotes but keep the weak prefix
+    /// `W/` before setting, like the following:
     ///
-    /// `"` is part of etag, don't trim it before setting.
+    /// - `33a64df551425fcc55e4d42a148795d9f25f89d4`
+    /// - `W/0815`
     pub fn with_etag(mut self, etag: &str) -> Self {
         self.etag = Some(etag.to_string());
         self
     }
+
+    /// Version id of this object.
+    ///
+    /// Only available for services that enabled versioning, for example:
+    ///
+    /// - s3: `x-amz-version-id`
+    /// - gcs: `generation`
+    pub fn version_id(&self) -> Option<&str> {
+        self.version_id.as_deref()
+    }
+
+    /// Set version id of this object.
+    pub fn set_version_id(&mut self, version_id: &str) -> &mut Self {
+        self.version_id = Some(version_id.to_string());
+        self
+    }
+
+    /// Set version id of this object.
+    pub fn with_version_id(mut self, version_id: &str) -> Self {
+        self.version_id = Some(version_id.to_string());
+        self
+    }
+}
+
+flags! {
+    /// ObjectMetakey describes the fields of [`ObjectMetadata`].
+    ///
+    /// Use [`ObjectMetadata::metakeys`] to check which fields are populated.
+    pub enum ObjectMetakey: u32 {
+        /// The metadata is complete.
+        Complete,
+        /// Key for [`ObjectMetadata::mode`].
+        Mode,
+        /// Key for [`ObjectMetadata::content_length`].
+        ContentLength,
+        /// Key for [`ObjectMetadata::content_md5`].
+        ContentMd5,
+        /// Key for [`ObjectMetadata::content_type`].
+        ContentType,
+        /// Key for [`ObjectMetadata::content_disposition`].
+        ContentDisposition,
+        /// Key for [`ObjectMetadata::cache_control`].
+        CacheControl,
+        /// Key for [`ObjectMetadata::content_encoding`].
+        ContentEncoding,
+        /// Key for [`ObjectMetadata::content_range`].
+        ContentRange,
+        /// Key for [`ObjectMetadata::last_modified`].
+        LastModified,
+        /// Key for [`ObjectMetadata::expires`].
+        Expires,
+        /// Key for [`ObjectMetadata::etag`].
+        Etag,
+        /// Key for [`ObjectMetadata::version_id`].
+        VersionId,
+    }
 }
@@ -17,18 +17,27 @@ pub use mode::ObjectMode;
 
 mod metadata;
 pub use metadata::ObjectMetadata;
+pub use metadata::ObjectMetakey;
 
 mod multipart;
 pub use multipart::ObjectMultipart;
 pub use multipart::ObjectPart;
+pub use multipart::ObjectUpload;
 
 #[allow(clippy::module_inception)]
 mod object;
 pub use object::Object;
 
 mod reader;
+pub use reader::BlockingObjectReader;
 pub use reader::ObjectReader;
 
 mod list;
 pub use list::BlockingObjectLister;
 pub use list::ObjectLister;
+
+mod writer;
+pub use writer::ObjectWriter;
+
+mod version;
+pub use version::ObjectVersion;
@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use futures::io::Cursor;
 use time::Duration;
+use time::OffsetDateTime;
 
 use crate::raw::*;
 use crate::*;
@@ -98,9 +99,12 @@ impl ObjectMultipart {
 
     /// Presign an operation for write multipart.
     ///
-    /// # TODO
+    /// The returned request uploads the part with `part_number` of this
+    /// upload, `uploadId` and `partNumber` are signed in the query, so
+    /// the url can only be used for this part.
     ///
-    /// User need to handle the response by self which may differ for different platforms.
+    /// Clients sending the request should collect the `ETag` header of
+    /// response and build [`ObjectPart`] for [`ObjectMultipart::complete`].
     pub fn presign_write(&self, part_number: usize, expire: Duration) -> Result<PresignedRequest> {
         let op = OpPresign::new(
             OpWriteMultipart::new(self.upload_id.clone(), part_number, 0),
@@ -139,3 +143,52 @@ impl ObjectPart {
         &self.etag
     }
 }
+
+/// ObjectUpload is an ongoing multipart upload returned by
+/// [`Operator::list_multipart_uploads`].
+#[derive(Debug, Clone)]
+pub struct ObjectUpload {
+    path: String,
+    upload_id: String,
+    initiated: Option<OffsetDateTime>,
+}
+
+impl ObjectUpload {
+    /// Create a new upload.
+    pub fn new(path: &str, upload_id: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            upload_id: upload_id.to_string(),
+            initiated: None,
+        }
+    }
+
+    /// Set the time when this upload was initiated.
+    pub fn with_initiated(mut self, initiated: OffsetDateTime) -> Self {
+        self.initiated = Some(initiated);
+        self
+    }
+
+    /// Get path of this upload.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Set path of this upload.
+    pub fn set_path(&mut self, path: &str) -> &mut Self {
+        self.path = path.to_string();
+        self
+    }
+
+    /// Get upload_id of this upload.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Get the time when this upload was initiated.
+    ///
+    /// `None` means the service doesn't return it.
+    pub fn initiated(&self) -> Option<OffsetDateTime> {
+        self.initiated
+    }
+}
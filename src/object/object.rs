@@ -12,12 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::ops::RangeBounds;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
+use std::time::Instant;
 
+use flagset::FlagSet;
+use futures::future;
+use futures::future::Either;
 use futures::io;
 use futures::io::Cursor;
 use time::Duration;
@@ -42,6 +50,13 @@ pub struct Object {
     meta: Arc<Mutex<ObjectMetadata>>,
 }
 
+/// Max count of tags on an object.
+const MAX_TAGS: usize = 10;
+/// Max characters of a tag key.
+const MAX_TAG_KEY_LENGTH: usize = 128;
+/// Max characters of a tag value.
+const MAX_TAG_VALUE_LENGTH: usize = 256;
+
 impl Object {
     /// Creates a new Object with normalized path.
     ///
@@ -169,7 +184,7 @@ impl Object {
             }
         }
 
-        let guard = self.metadata_ref().await?;
+        let guard = self.metadata_ref(ObjectMetakey::Complete).await?;
         Ok(guard.mode())
     }
 
@@ -188,7 +203,7 @@ impl Object {
             }
         }
 
-        let guard = self.blocking_metadata_ref()?;
+        let guard = self.blocking_metadata_ref(ObjectMetakey::Complete)?;
         Ok(guard.mode())
     }
 
@@ -201,6 +216,9 @@ impl Object {
     ///
     /// - Create on existing dir will succeed.
     /// - Create on existing file will overwrite and truncate it.
+    /// - Parents that don't exist will be created too, like `mkdir -p`.
+    /// - Services without native dirs (like s3) will create a zero-byte
+    ///   object whose key ends with `/` as dir marker.
     ///
     /// # Examples
     ///
@@ -247,6 +265,7 @@ impl Object {
                 .create(self.path(), OpCreate::new(ObjectMode::FILE))
                 .await?
         };
+        self.invalidate_metadata();
 
         Ok(())
     }
@@ -260,6 +279,9 @@ impl Object {
     ///
     /// - Create on existing dir will succeed.
     /// - Create on existing file will overwrite and truncate it.
+    /// - Parents that don't exist will be created too, like `mkdir -p`.
+    /// - Services without native dirs (like s3) will create a zero-byte
+    ///   object whose key ends with `/` as dir marker.
     ///
     /// # Examples
     ///
@@ -302,6 +324,7 @@ impl Object {
             self.acc
                 .blocking_create(self.path(), OpCreate::new(ObjectMode::FILE))?;
         };
+        self.invalidate_metadata();
 
         Ok(())
     }
@@ -365,6 +388,14 @@ impl Object {
     /// # Notes
     ///
     /// - The returning contnet's length may be smaller than the range specifed.
+    /// - `..` reads the whole object, `1024..` reads from offset 1024 to the
+    ///   end, `1024..2048` and `1024..=2047` read 1024 bytes from offset 1024.
+    /// - `..1024` and `..=1023` mean the last 1024 bytes of object, see [`BytesRange`].
+    /// - Zero-length range like `1024..1024` will return empty content
+    ///   after checking the object exists via `stat`, no `Range` will be
+    ///   sent to services.
+    /// - [`ErrorKind::Unexpected`] will be returned if the content length
+    ///   doesn't match with `Content-Range` returned by services.
     ///
     /// # Examples
     ///
@@ -384,38 +415,204 @@ impl Object {
     /// # }
     /// ```
     pub async fn range_read(&self, range: impl RangeBounds<u64>) -> Result<Vec<u8>> {
+        self.read_with(OpRead::new().with_range(range.into())).await
+    }
+
+    /// Read the last `n` bytes of object into a bytes.
+    ///
+    /// This is useful to read the footer of formats like parquet and zip.
+    /// The suffix range will be sent as `Range: bytes=-<n>` directly, so
+    /// it doesn't need to know the total size of object.
+    ///
+    /// # Notes
+    ///
+    /// - The whole object will be returned if it's smaller than `n`.
+    /// - Use [`Object::range_reader`] with `..n` to get a reader whose
+    ///   offset is backfilled from `Content-Range` returned by services.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let o = op.object("path/to/file");
+    /// # o.write(vec![0; 4096]).await?;
+    /// let bs = o.read_suffix(8).await?;
+    /// assert_eq!(bs.len(), 8);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_suffix(&self, n: u64) -> Result<Vec<u8>> {
+        self.read_with(OpRead::new().with_range(BytesRange::new(None, Some(n))))
+            .await
+    }
+
+    /// Read the object into a bytes with option like range and conditions.
+    ///
+    /// # Notes
+    ///
+    /// - The returning contnet's length may be smaller than the range specifed.
+    /// - [`ErrorKind::ConditionNotMatch`] will be returned if `if_match` or
+    ///   `if_none_match` is not matched.
+    /// - [`ErrorKind::Unsupported`] will be returned if `if_match` or
+    ///   `if_none_match` is set but services don't support conditional read,
+    ///   see [`OperatorMetadata::can_conditional_read`].
+    /// - [`ErrorKind::ChecksumMismatch`] will be returned if `verify_content_md5`
+    ///   is enabled and the content doesn't match with `content_md5`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::OpRead;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Fs)?;
+    /// let o = op.object("path/to/file");
+    /// let args = OpRead::new().with_if_none_match("\"33a64df551425fcc55e4d42a148795d9f25f89d4\"");
+    /// let bs = o.read_with(args).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_with(&self, args: OpRead) -> Result<Vec<u8>> {
         if !validate_path(self.path(), ObjectMode::FILE) {
             return Err(
                 Error::new(ErrorKind::ObjectIsADirectory, "read path is a directory")
-                    .with_operation("Object:range_read")
+                    .with_operation("Object::read_with")
                     .with_context("service", self.accessor().metadata().scheme().into_static())
                     .with_context("path", self.path()),
             );
         }
 
-        let br = BytesRange::from(range);
+        if (args.if_match().is_some() || args.if_none_match().is_some())
+            && !self
+                .accessor()
+                .metadata()
+                .capabilities()
+                .contains(AccessorCapability::ConditionalRead)
+        {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "conditional read is not supported")
+                    .with_operation("Object::read_with")
+                    .with_context("service", self.accessor().metadata().scheme().into_static())
+                    .with_context("path", self.path()),
+            );
+        }
 
-        // Add total size hint for OpRead.
-        let mut op = OpRead::new().with_range(br);
-        if let Ok(size) = self.content_length().await {
-            op = op.with_total_size_hint(size);
+        if args.version().is_some() {
+            self.check_versioning("Object::read_with", args.version())?;
+        }
+
+        let br = args.range();
+        // Zero-length range is valid but can't be sent as `Range` header,
+        // stat instead so that reading missing objects still fails.
+        if br.size() == Some(0) {
+            let mut op = OpStat::new();
+            if let Some(version) = args.version() {
+                op = op.with_version(version);
+            }
+            self.acc.stat(self.path(), op).await?;
+            return Ok(Vec::new());
+        }
+
+        let verify = args.verify_content_md5();
+        if verify && !br.is_full() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "content md5 can only be verified for whole object reads",
+            )
+            .with_operation("Object::read_with")
+            .with_context("service", self.accessor().metadata().scheme().into_static())
+            .with_context("path", self.path())
+            .with_context("range", br.to_string()));
+        }
+
+        // Add total size hint for OpRead, cached metadata only describes
+        // the latest version. Suffix range can be sent without knowing the
+        // total size, so we don't stat for it.
+        let mut op = args;
+        if op.version().is_none() && !br.is_suffix() {
+            if let Ok(size) = self.content_length().await {
+                op = op.with_total_size_hint(size);
+            }
         }
+        let version = op.version().map(|v| v.to_string());
 
         let (rp, s) = self.acc.read(self.path(), op).await?;
+        let meta = rp.into_metadata();
+
+        // Skip verification if services don't report content md5.
+        let expected = match (verify, meta.content_md5()) {
+            (false, _) => None,
+            (true, Some(v)) => Some(v.to_string()),
+            (true, None) => match &version {
+                Some(v) => self
+                    .stat_with(OpStat::new().with_version(v))
+                    .await?
+                    .content_md5()
+                    .map(|v| v.to_string()),
+                None => self.content_md5().await?,
+            },
+        };
+        let s: BytesReader = match expected {
+            Some(v) => Box::new(verify_md5_read(s, &v)),
+            None => s,
+        };
 
-        let buffer = Vec::with_capacity(rp.into_metadata().content_length() as usize);
+        let buffer = Vec::with_capacity(meta.content_length() as usize);
         let mut bs = Cursor::new(buffer);
 
         io::copy(s, &mut bs).await.map_err(|err| {
-            Error::new(ErrorKind::Unexpected, "read from storage")
-                .with_operation("Object:range_read")
+            // Keep checksum mismatch so that users can handle it.
+            let kind = err
+                .get_ref()
+                .and_then(|v| v.downcast_ref::<Error>())
+                .map(|v| v.kind())
+                .filter(|v| *v == ErrorKind::ChecksumMismatch)
+                .unwrap_or(ErrorKind::Unexpected);
+
+            // Truncated content could be fixed by reading again.
+            let temporary = err.kind() == io::ErrorKind::UnexpectedEof;
+
+            let err = Error::new(kind, "read from storage")
+                .with_operation("Object::read_with")
                 .with_context("service", self.accessor().metadata().scheme().into_static())
                 .with_context("path", self.path())
                 .with_context("range", &br.to_string())
-                .set_source(err)
+                .set_source(err);
+            if temporary {
+                err.set_temporary()
+            } else {
+                err
+            }
         })?;
 
-        Ok(bs.into_inner())
+        // Check content length for full reads, chunked responses that
+        // don't have content length are exempt.
+        let bs = bs.into_inner();
+        self.check_content_range("Object::read_with", &meta, bs.len() as u64)?;
+        if let Some(expect) = meta.content_length_raw().filter(|_| br.is_full()) {
+            if bs.len() as u64 != expect {
+                return Err(
+                    Error::new(ErrorKind::Unexpected, "read content length mismatch")
+                        .with_operation("Object::read_with")
+                        .with_context("service", self.accessor().metadata().scheme().into_static())
+                        .with_context("path", self.path())
+                        .with_context("expect", expect.to_string())
+                        .with_context("actual", bs.len().to_string())
+                        .set_temporary(),
+                );
+            }
+        }
+
+        Ok(bs)
     }
 
     /// Read the specified range of object into a bytes.
@@ -450,11 +647,19 @@ impl Object {
         }
 
         let br = BytesRange::from(range);
+        // Zero-length range can't be sent as `Range` header, stat instead
+        // so that reading missing objects still fails.
+        if br.size() == Some(0) {
+            self.acc.blocking_stat(self.path(), OpStat::new())?;
+            return Ok(Vec::new());
+        }
+
         let (rp, mut s) = self
             .acc
             .blocking_read(self.path(), OpRead::new().with_range(br))?;
+        let meta = rp.into_metadata();
 
-        let mut buffer = Vec::with_capacity(rp.into_metadata().content_length() as usize);
+        let mut buffer = Vec::with_capacity(meta.content_length() as usize);
         s.read_to_end(&mut buffer).map_err(|err| {
             Error::new(ErrorKind::Unexpected, "blocking range read failed")
                 .with_operation("Object::blocking_range_read")
@@ -463,12 +668,16 @@ impl Object {
                 .with_context("range", &br.to_string())
                 .set_source(err)
         })?;
+        self.check_content_range("Object::blocking_range_read", &meta, buffer.len() as u64)?;
 
         Ok(buffer)
     }
 
     /// Create a new reader which can read the whole object.
     ///
+    /// The returning [`ObjectReader`] implements `AsyncSeek` too, see its
+    /// docs for more details.
+    ///
     /// # Examples
     ///
     /// ```
@@ -486,7 +695,7 @@ impl Object {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn reader(&self) -> Result<impl BytesRead> {
+    pub async fn reader(&self) -> Result<ObjectReader> {
         self.range_reader(..).await
     }
 
@@ -508,7 +717,7 @@ impl Object {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn blocking_reader(&self) -> Result<impl BlockingBytesRead> {
+    pub fn blocking_reader(&self) -> Result<BlockingObjectReader> {
         self.blocking_range_reader(..)
     }
 
@@ -517,6 +726,12 @@ impl Object {
     /// # Notes
     ///
     /// - The returning contnet's length may be smaller than the range specifed.
+    /// - `..1024` means the last 1024 bytes of object, see [`BytesRange`].
+    /// - Zero-length range like `1024..1024` will return empty content
+    ///   after checking the object exists via `stat`, no `Range` will be
+    ///   sent to services.
+    /// - [`ErrorKind::Unexpected`] will be returned if the content length
+    ///   doesn't match with `Content-Range` returned by services.
     ///
     /// # Examples
     ///
@@ -545,15 +760,141 @@ impl Object {
             );
         }
 
-        // Add total size hint for OpRead.
-        let mut op = OpRead::new().with_range(range.into());
-        if let Ok(size) = self.content_length().await {
+        let mut br = BytesRange::from(range);
+
+        // Add total size hint for OpRead. Suffix range can be sent without
+        // knowing the total size, so only the cached one will be used.
+        let total_size = if br.is_suffix() {
+            self.meta
+                .lock()
+                .expect("lock must succeed")
+                .content_length_raw()
+        } else {
+            self.content_length().await.ok()
+        };
+        // Resolve suffix range like `bytes=-1024` so that reader can seek.
+        if let (None, Some(size), Some(total)) = (br.offset(), br.size(), total_size) {
+            br = BytesRange::new(Some(total.saturating_sub(size)), Some(size.min(total)));
+        }
+        if br.size() == Some(0) {
+            // Zero-length range can't be sent as `Range` header, stat
+            // instead so that reading missing objects still fails.
+            self.acc.stat(self.path(), OpStat::new()).await?;
+            return Ok(ObjectReader::new(
+                self.accessor(),
+                self.path(),
+                br.offset().unwrap_or_default(),
+                ObjectMetadata::new(ObjectMode::FILE).with_content_length(0),
+                Box::new(Cursor::new(Vec::new())),
+            ));
+        }
+
+        let mut op = OpRead::new().with_range(br);
+        if let Some(size) = total_size {
             op = op.with_total_size_hint(size);
         }
 
         let (rp, r) = self.acc.read(self.path(), op).await?;
+        let meta = rp.into_metadata();
+        if let Some(length) = meta.content_length_raw() {
+            self.check_content_range("Object::range_reader", &meta, length)?;
+        }
+
+        // Backfill the real offset of suffix range from `Content-Range`,
+        // fallback to the total size for services that don't return it.
+        let offset = if br.is_suffix() {
+            match meta.content_range().and_then(|v| v.range()) {
+                Some(v) => v.start,
+                None => {
+                    let total = self.content_length().await?;
+                    total.saturating_sub(br.size().unwrap_or_default())
+                }
+            }
+        } else {
+            br.offset().unwrap_or_default()
+        };
+
+        Ok(ObjectReader::new(
+            self.accessor(),
+            self.path(),
+            offset,
+            meta,
+            r,
+        ))
+    }
+
+    /// Create a new lazy reader which can read the specified range.
+    ///
+    /// Unlike [`Object::range_reader`], no read request will be sent until
+    /// the returning reader is polled for the first time. The reader
+    /// implements both `AsyncRead` and `AsyncSeek`, so it could be used with
+    /// `futures::io::copy` or `tokio::io::copy` directly.
+    ///
+    /// # Notes
+    ///
+    /// - The object's metadata will be fetched (or taken from cache) to
+    ///   resolve the range, and the range will be clamped to the object's
+    ///   content length.
+    /// - Positions of the reader are relative to the start of `range`.
+    /// - Seeking is a pure in memory operation, the next read after seeking
+    ///   will send a new ranged read at the new position.
+    /// - Seeking past the end of the range is allowed, the following reads
+    ///   return 0 without sending requests to services.
+    /// - [`ErrorKind::Unexpected`] will be returned while reading if the
+    ///   `Content-Range` returned by services doesn't match the requested
+    ///   position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let o = op.object("path/to/file");
+    /// # o.write(vec![0; 4096]).await?;
+    /// let r = o.reader_with_range(1024..2048).await?;
+    /// let n = futures::io::copy(r, &mut futures::io::sink()).await?;
+    /// assert_eq!(n, 1024);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn reader_with_range(&self, range: impl RangeBounds<u64>) -> Result<ObjectReader> {
+        if !validate_path(self.path(), ObjectMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::ObjectIsADirectory, "read path is a directory")
+                    .with_operation("Object::reader_with_range")
+                    .with_context("service", self.accessor().metadata().scheme().into_static())
+                    .with_context("path", self.path()),
+            );
+        }
 
-        Ok(ObjectReader::new(rp.into_metadata(), r))
+        let br = BytesRange::from(range);
+        let meta = self.metadata().await?;
+        let total = meta.content_length();
+
+        let (offset, size) = match (br.offset(), br.size()) {
+            (Some(offset), Some(size)) => {
+                let offset = offset.min(total);
+                (offset, size.min(total - offset))
+            }
+            (Some(offset), None) => {
+                let offset = offset.min(total);
+                (offset, total - offset)
+            }
+            (None, Some(size)) => (total.saturating_sub(size), size.min(total)),
+            (None, None) => (0, total),
+        };
+
+        Ok(ObjectReader::lazy(
+            self.accessor(),
+            self.path(),
+            offset,
+            meta.with_content_length(size),
+        ))
     }
 
     /// Create a new reader which can read the specified range.
@@ -577,7 +918,7 @@ impl Object {
     pub fn blocking_range_reader(
         &self,
         range: impl RangeBounds<u64>,
-    ) -> Result<impl BlockingBytesRead> {
+    ) -> Result<BlockingObjectReader> {
         if !validate_path(self.path(), ObjectMode::FILE) {
             return Err(
                 Error::new(ErrorKind::ObjectIsADirectory, "read path is a directory")
@@ -587,11 +928,11 @@ impl Object {
             );
         }
 
-        let (_, r) = self
+        let (rp, r) = self
             .acc
             .blocking_read(self.path(), OpRead::new().with_range(range.into()))?;
 
-        Ok(r)
+        Ok(BlockingObjectReader::new(rp.into_metadata(), r))
     }
 
     /// Create a reader which implements AsyncRead and AsyncSeek inside specified range.
@@ -629,6 +970,9 @@ impl Object {
 
     /// Read the whole object into a bytes with auto detected compress algorithm.
     ///
+    /// The algorithm is detected from the extension of path first, and then
+    /// from the `Content-Encoding` returned by the read response.
+    ///
     /// If we can't find the correct algorithm, we return `Ok(None)` instead.
     ///
     /// # Feature
@@ -654,16 +998,29 @@ impl Object {
     /// ```
     #[cfg(feature = "compress")]
     pub async fn decompress_read(&self) -> Result<Option<Vec<u8>>> {
-        let algo = match CompressAlgorithm::from_path(self.path()) {
+        let r = match self.decompress_reader().await? {
             None => return Ok(None),
-            Some(algo) => algo,
+            Some(r) => r,
         };
+        let mut bs = Cursor::new(Vec::new());
+
+        io::copy(r, &mut bs).await.map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "decompress read failed")
+                .with_operation("Object::decompress_read")
+                .with_context("service", self.accessor().metadata().scheme().into_static())
+                .with_context("path", self.path())
+                .set_source(err)
+        })?;
 
-        self.decompress_read_with(algo).await.map(Some)
+        Ok(Some(bs.into_inner()))
     }
 
     /// Create a reader with auto-detected compress algorithm.
     ///
+    /// The algorithm is detected from the extension of path first, and then
+    /// from the `Content-Encoding` returned by the read response. Data will
+    /// be decompressed while reading without buffering the whole object.
+    ///
     /// If we can't find the correct algorithm, we will return `Ok(None)`.
     ///
     /// # Feature
@@ -689,14 +1046,14 @@ impl Object {
     /// ```
     #[cfg(feature = "compress")]
     pub async fn decompress_reader(&self) -> Result<Option<impl BytesRead>> {
-        let algo = match CompressAlgorithm::from_path(self.path()) {
-            Some(v) => v,
-            None => return Ok(None),
-        };
-
         let r = self.reader().await?;
 
-        Ok(Some(DecompressReader::new(r, algo)))
+        let algo = CompressAlgorithm::from_path(self.path()).or_else(|| {
+            r.content_encoding()
+                .and_then(CompressAlgorithm::from_content_encoding)
+        });
+
+        Ok(algo.map(|algo| DecompressReader::new(r, algo)))
     }
 
     /// Read the whole object into a bytes with specific compress algorithm.
@@ -806,6 +1163,8 @@ impl Object {
     /// # Notes
     ///
     /// - Write will make sure all bytes has been written, or an error will be returned.
+    /// - If [`OpWrite::with_min_throughput`] is set, write will be aborted
+    ///   with a temporary error once throughput stays below the floor.
     ///
     /// # Examples
     ///
@@ -839,19 +1198,62 @@ impl Object {
             );
         }
 
+        self.check_append("Object::write_with", &args)?;
+        self.check_expire("Object::write_with", &args)?;
+
+        let append = args.append();
         let bs = bs.into();
         let r = Cursor::new(bs);
-        let rp = self.acc.write(self.path(), args, Box::new(r)).await?;
+        let rp = self
+            .write_with_watchdog("Object::write_with", args, Box::new(r))
+            .await?;
 
         // Always write latest metadata into cache.
+        //
+        // The length of object is unknown after append, reset the cache
+        // so that the next `metadata` call will fetch it from service.
         {
             let mut guard = self.meta.lock().expect("lock must succeed");
-            *guard = ObjectMetadata::new(ObjectMode::FILE).with_content_length(rp.written());
+            *guard = if append {
+                ObjectMetadata::new(ObjectMode::FILE)
+            } else {
+                ObjectMetadata::new(ObjectMode::FILE).with_content_length(rp.written())
+            };
         }
 
         Ok(())
     }
 
+    /// Append bytes to the end of object.
+    ///
+    /// Object will be created if it doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// - Only services with [`AccessorCapability::Append`] support append,
+    ///   others will return [`ErrorKind::Unsupported`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Fs)?;
+    /// let o = op.object("path/to/file");
+    /// o.append("Hello, ").await?;
+    /// o.append("World!").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn append(&self, bs: impl Into<Vec<u8>>) -> Result<()> {
+        let bs: Vec<u8> = bs.into();
+        let op = OpWrite::new(bs.len() as u64).with_append(true);
+        self.write_with(op, bs).await
+    }
+
     /// Write bytes into object.
     ///
     /// # Notes
@@ -919,6 +1321,10 @@ impl Object {
             );
         }
 
+        self.check_append("Object::blocking_write_with", &args)?;
+        self.check_expire("Object::blocking_write_with", &args)?;
+
+        let append = args.append();
         let bs = bs.into();
         let r = std::io::Cursor::new(bs);
         let rp = self.acc.blocking_write(self.path(), args, Box::new(r))?;
@@ -926,16 +1332,48 @@ impl Object {
         // Always write latest metadata into cache.
         {
             let mut guard = self.meta.lock().expect("lock must succeed");
-            *guard = ObjectMetadata::new(ObjectMode::FILE).with_content_length(rp.written());
+            *guard = if append {
+                ObjectMetadata::new(ObjectMode::FILE)
+            } else {
+                ObjectMetadata::new(ObjectMode::FILE).with_content_length(rp.written())
+            };
         }
         Ok(())
     }
 
+    /// Append bytes to the end of object in blocking way.
+    ///
+    /// Refer to [`Object::append`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Fs)?;
+    /// let o = op.object("path/to/file");
+    /// o.blocking_append("Hello, ")?;
+    /// o.blocking_append("World!")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_append(&self, bs: impl Into<Vec<u8>>) -> Result<()> {
+        let bs: Vec<u8> = bs.into();
+        let op = OpWrite::new(bs.len() as u64).with_append(true);
+        self.blocking_write_with(op, bs)
+    }
+
     /// Write data into object from a [`BytesRead`].
     ///
+    /// Data will be streamed into services without buffering in memory.
+    ///
     /// # Notes
     ///
     /// - Write will make sure all bytes has been written, or an error will be returned.
+    /// - `size` must be the exact size of `br`, an error will be returned if
+    ///   `br` yields fewer or more bytes.
     ///
     /// # Examples
     ///
@@ -968,18 +1406,66 @@ impl Object {
             );
         }
 
-        let _ = self
-            .acc
-            .write(self.path(), OpWrite::new(size), Box::new(br))
-            .await?;
+        let br = Box::new(verify_size_read(br, size));
+        let _ = self.acc.write(self.path(), OpWrite::new(size), br).await?;
+        self.invalidate_metadata();
         Ok(())
     }
 
+    /// Create a new writer which can coalesce many small appends.
+    ///
+    /// Refer to [`ObjectWriter`] for more about the behavior details.
+    ///
+    /// # Notes
+    ///
+    /// For services that don't support multipart (check
+    /// [`OperatorMetadata::can_multipart`][crate::OperatorMetadata::can_multipart]),
+    /// all appended data will be buffered in memory until `close`, and
+    /// `flush` is a no-op. Nothing will be written to the service before
+    /// `close` succeeded, so please use [`Object::write_from`] for large
+    /// objects on these services instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let o = op.object("path/to/file");
+    /// let mut w = o.writer().await?;
+    /// w.append(vec![0; 1024]).await?;
+    /// w.append(vec![1; 1024]).await?;
+    /// w.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn writer(&self) -> Result<ObjectWriter> {
+        if !validate_path(self.path(), ObjectMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::ObjectIsADirectory, "write path is a directory")
+                    .with_operation("Object::writer")
+                    .with_context("service", self.accessor().metadata().scheme().into_static())
+                    .with_context("path", self.path()),
+            );
+        }
+
+        // Object will be changed by this writer, cached metadata is not
+        // reliable anymore.
+        self.invalidate_metadata();
+        Ok(ObjectWriter::new(self.acc.clone(), self.path()))
+    }
+
     /// Write data into object from a [`BlockingBytesRead`].
     ///
     /// # Notes
     ///
     /// - Write will make sure all bytes has been written, or an error will be returned.
+    /// - `size` must be the exact size of `br`, an error will be returned if
+    ///   `br` yields fewer or more bytes.
     ///
     /// # Examples
     ///
@@ -1016,9 +1502,11 @@ impl Object {
             );
         }
 
+        let br = Box::new(verify_size_read(br, size));
         let _ = self
             .acc
-            .blocking_write(self.path(), OpWrite::new(size), Box::new(br))?;
+            .blocking_write(self.path(), OpWrite::new(size), br)?;
+        self.invalidate_metadata();
         Ok(())
     }
 
@@ -1026,7 +1514,8 @@ impl Object {
     ///
     /// # Notes
     ///
-    /// - Delete not existing error won't return errors.
+    /// - `delete` is idempotent: deleting a not existing object will return
+    ///   `Ok(())` instead of errors, so it's safe to call unconditionally.
     ///
     /// # Examples
     ///
@@ -1054,11 +1543,50 @@ impl Object {
         Ok(())
     }
 
+    /// Delete object with extra options.
+    ///
+    /// # Notes
+    ///
+    /// Deleting a specific version requires [`AccessorCapability::Versioning`],
+    /// [`ErrorKind::Unsupported`] will be returned otherwise. The version
+    /// will be removed permanently instead of leaving a delete marker.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::OpDelete;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::S3)?;
+    /// let args = OpDelete::new().with_version("3HL4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY");
+    /// op.object("test").delete_with(args).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_with(&self, args: OpDelete) -> Result<()> {
+        if args.version().is_some() {
+            self.check_versioning("Object::delete_with", args.version())?;
+        }
+
+        let _ = self.acc.delete(self.path(), args).await?;
+
+        // Latest version could be changed, drop the cached metadata.
+        {
+            let mut guard = self.meta.lock().expect("lock must succeed");
+            *guard = ObjectMetadata::new(ObjectMode::Unknown);
+        }
+        Ok(())
+    }
+
     /// Delete object.
     ///
     /// # Notes
     ///
-    /// - Delete not existing error won't return errors.
+    /// - `delete` is idempotent: deleting a not existing object will return
+    ///   `Ok(())` instead of errors, so it's safe to call unconditionally.
     ///
     /// # Examples
     ///
@@ -1085,28 +1613,331 @@ impl Object {
         Ok(())
     }
 
-    /// List current dir object.
+    /// Copy object to another path in the same operator.
     ///
-    /// This function will create a new handle to list objects.
+    /// # Notes
     ///
-    /// An error will be returned if object path doesn't end with `/`.
+    /// - Services with [`AccessorCapability::Copy`] will copy inside the
+    ///   service without transferring the content, otherwise the content
+    ///   will be read and written to the target again.
+    /// - `to` will be overwritten if it already exists.
+    /// - [`ErrorKind::ObjectNotFound`] will be returned if current object
+    ///   doesn't exist.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// # use opendal::services::memory;
+    /// ```
     /// # use anyhow::Result;
-    /// # use futures::io;
     /// # use opendal::Operator;
-    /// # use opendal::ObjectMode;
-    /// # use futures::TryStreamExt;
     /// # use opendal::Scheme;
     /// # #[tokio::main]
     /// # async fn main() -> Result<()> {
-    /// let op = Operator::from_env(Scheme::Memory)?;
-    /// let o = op.object("path/to/dir/");
-    /// let mut ds = o.list().await?;
-    /// // ObjectStreamer implements `futures::Stream`
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// # op.object("test").write("Hello, World!").await?;
+    /// op.object("test").copy("test_copy").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy(&self, to: &str) -> Result<()> {
+        let to = self.check_copy("Object::copy", to)?;
+        if to == self.path() {
+            return Ok(());
+        }
+
+        if self
+            .accessor()
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Copy)
+        {
+            self.acc.copy(self.path(), &to, OpCopy::new()).await?;
+            return Ok(());
+        }
+
+        let (rp, r) = self.acc.read(self.path(), OpRead::new()).await?;
+        let size = rp.into_metadata().content_length();
+        self.acc.write(&to, OpWrite::new(size), r).await?;
+        Ok(())
+    }
+
+    /// Copy object to another path in the same operator in blocking way.
+    ///
+    /// See [`Object::copy`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// op.object("test").blocking_copy("test_copy")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_copy(&self, to: &str) -> Result<()> {
+        let to = self.check_copy("Object::blocking_copy", to)?;
+        if to == self.path() {
+            return Ok(());
+        }
+
+        if self
+            .accessor()
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Copy)
+        {
+            self.acc.blocking_copy(self.path(), &to, OpCopy::new())?;
+            return Ok(());
+        }
+
+        let (rp, r) = self.acc.blocking_read(self.path(), OpRead::new())?;
+        let size = rp.into_metadata().content_length();
+        self.acc.blocking_write(&to, OpWrite::new(size), r)?;
+        Ok(())
+    }
+
+    /// Rename object to another path in the same operator.
+    ///
+    /// # Notes
+    ///
+    /// Services with [`AccessorCapability::Rename`] like `azdfs` will rename
+    /// inside service. Otherwise rename is implemented by [`Object::copy`]
+    /// and then [`Object::delete`], so it's not atomic: both paths could
+    /// exist if delete failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// # op.object("test").write("Hello, World!").await?;
+    /// op.object("test").rename("test_renamed").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename(&self, to: &str) -> Result<()> {
+        if normalize_path(to) == self.path() {
+            return self.check_copy("Object::rename", to).map(|_| ());
+        }
+
+        if self
+            .accessor()
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Rename)
+        {
+            let to = self.check_copy("Object::rename", to)?;
+            self.acc.rename(self.path(), &to, OpRename::new()).await?;
+
+            // Always write latest metadata into cache.
+            {
+                let mut guard = self.meta.lock().expect("lock must succeed");
+                *guard = ObjectMetadata::new(ObjectMode::Unknown);
+            }
+            return Ok(());
+        }
+
+        self.copy(to).await?;
+        self.delete().await
+    }
+
+    /// Rename object to another path in the same operator in blocking way.
+    ///
+    /// See [`Object::rename`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// op.object("test").blocking_rename("test_renamed")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_rename(&self, to: &str) -> Result<()> {
+        if normalize_path(to) == self.path() {
+            return self.check_copy("Object::blocking_rename", to).map(|_| ());
+        }
+
+        self.blocking_copy(to)?;
+        self.blocking_delete()
+    }
+
+    /// Get tags of object.
+    ///
+    /// An empty map will be returned if object doesn't have any tags.
+    ///
+    /// # Notes
+    ///
+    /// Only services with [`AccessorCapability::Tagging`] support tags,
+    /// [`ErrorKind::Unsupported`] will be returned otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::S3)?;
+    /// let tags = op.object("test").tags().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn tags(&self) -> Result<HashMap<String, String>> {
+        self.check_tagging("Object::tags")?;
+
+        let rp = self.acc.get_tags(self.path(), OpGetTags::new()).await?;
+        Ok(rp.into_tags())
+    }
+
+    /// Set tags of object, all existing tags will be replaced.
+    ///
+    /// # Notes
+    ///
+    /// Tags are validated before sending to services:
+    ///
+    /// - At most 10 tags are allowed.
+    /// - Tag key must contain 1 to 128 characters.
+    /// - Tag value must contain at most 256 characters.
+    ///
+    /// Services could have extra limits on characters of tags.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::S3)?;
+    /// let tags = HashMap::from([("project".to_string(), "ingestion".to_string())]);
+    /// op.object("test").set_tags(tags).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_tags(&self, tags: HashMap<String, String>) -> Result<()> {
+        self.check_tagging("Object::set_tags")?;
+        self.check_tags("Object::set_tags", &tags)?;
+
+        let _ = self.acc.set_tags(self.path(), OpSetTags::new(tags)).await?;
+        Ok(())
+    }
+
+    /// Delete all tags of object.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::S3)?;
+    /// op.object("test").delete_tags().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_tags(&self) -> Result<()> {
+        self.check_tagging("Object::delete_tags")?;
+
+        let _ = self
+            .acc
+            .delete_tags(self.path(), OpDeleteTags::new())
+            .await?;
+        Ok(())
+    }
+
+    /// List all versions of object, newest first.
+    ///
+    /// Delete markers are included, check them via
+    /// [`ObjectVersion::is_delete_marker`].
+    ///
+    /// # Notes
+    ///
+    /// Only services with [`AccessorCapability::Versioning`] support versions,
+    /// [`ErrorKind::Unsupported`] will be returned otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::S3)?;
+    /// for v in op.object("test").list_versions().await? {
+    ///     println!("{} {}", v.version_id(), v.is_latest());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_versions(&self) -> Result<Vec<ObjectVersion>> {
+        self.check_versioning("Object::list_versions", None)?;
+
+        let mut versions = Vec::new();
+        let mut op = OpListVersions::new();
+        loop {
+            let rp = self.acc.list_versions(self.path(), op).await?;
+
+            let next = match (rp.next_key_marker(), rp.next_version_id_marker()) {
+                (Some(key), Some(version_id)) => Some(
+                    OpListVersions::new()
+                        .with_key_marker(key)
+                        .with_version_id_marker(version_id),
+                ),
+                _ => None,
+            };
+            // Path is used as prefix, drop versions of other objects.
+            versions.extend(
+                rp.into_versions()
+                    .into_iter()
+                    .filter(|v| v.path() == self.path()),
+            );
+
+            match next {
+                Some(v) => op = v,
+                None => return Ok(versions),
+            }
+        }
+    }
+
+    /// List current dir object.
+    ///
+    /// This function will create a new handle to list objects.
+    ///
+    /// An error will be returned if object path doesn't end with `/`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::services::memory;
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// # use opendal::ObjectMode;
+    /// # use futures::TryStreamExt;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let op = Operator::from_env(Scheme::Memory)?;
+    /// let o = op.object("path/to/dir/");
+    /// let mut ds = o.list().await?;
+    /// // ObjectStreamer implements `futures::Stream`
     /// while let Some(de) = ds.try_next().await? {
     ///     match de.mode().await? {
     ///         ObjectMode::FILE => {
@@ -1122,21 +1953,123 @@ impl Object {
     /// # }
     /// ```
     pub async fn list(&self) -> Result<ObjectLister> {
+        self.list_with(OpList::new()).await
+    }
+
+    /// List current dir object with option.
+    ///
+    /// Some services support setting page size of list via
+    /// [`OpList::with_limit`], which could reduce the latency of first page.
+    /// The returning lister will still fetch the following pages while
+    /// users keep polling it.
+    ///
+    /// Listing can be resumed via [`OpList::with_start_after`] with the
+    /// [`ObjectLister::continuation_token`] of previous lister.
+    ///
+    /// All files under this dir will be returned if
+    /// [`OpList::with_recursive`] is set, refer to [`Object::scan`].
+    ///
+    /// An error will be returned if object path doesn't end with `/`, or
+    /// `start_after` is not a direct child of this dir.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use futures::TryStreamExt;
+    /// # use opendal::OpList;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let o = op.object("path/to/dir/");
+    /// let mut ds = o.list_with(OpList::new().with_limit(100)).await?;
+    /// while let Some(de) = ds.try_next().await? {
+    ///     println!("{}", de.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_with(&self, args: OpList) -> Result<ObjectLister> {
         if !validate_path(self.path(), ObjectMode::DIR) {
             return Err(Error::new(
                 ErrorKind::ObjectNotADirectory,
                 "the path trying to list is not a directory",
             )
-            .with_operation("Object::list")
+            .with_operation("Object::list_with")
             .with_context("service", self.accessor().metadata().scheme().into_static())
             .with_context("path", self.path()));
         }
+        self.check_start_after("Object::list_with", &args)?;
+
+        let pager = if args.recursive()
+            && (args.include_dirs()
+                || !self
+                    .accessor()
+                    .metadata()
+                    .capabilities()
+                    .contains(AccessorCapability::ListRecursive))
+        {
+            if args.start_after().is_some() {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "service doesn't support list recursively with start_after",
+                )
+                .with_operation("Object::list_with")
+                .with_context("service", self.accessor().metadata().scheme().into_static())
+                .with_context("path", self.path()));
+            }
 
-        let (_, pager) = self.acc.list(self.path(), OpList::new()).await?;
+            Box::new(
+                FlatWalker::new(self.accessor(), self.path(), args.limit())
+                    .with_include_dirs(args.include_dirs()),
+            )
+        } else {
+            let (_, pager) = self.acc.list(self.path(), args).await?;
+            pager
+        };
 
         Ok(ObjectLister::new(self.operator(), pager))
     }
 
+    /// Scan current dir object recursively.
+    ///
+    /// All files under this dir will be returned in a flat way, and dirs
+    /// will be omitted. Services with [`AccessorCapability::ListRecursive`]
+    /// like `s3` will list without delimiter, others like `fs` will walk
+    /// dirs one by one. There is no guarantee about the order of files.
+    ///
+    /// Use [`OpList::with_include_dirs`] with [`Object::list_with`] to
+    /// return dirs as well.
+    ///
+    /// The returning lister is lazy, pages are fetched while users keep
+    /// polling it, and it's safe to drop it at any time.
+    ///
+    /// An error will be returned if object path doesn't end with `/`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use futures::TryStreamExt;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let o = op.object("path/to/dir/");
+    /// let mut ds = o.scan().await?;
+    /// while let Some(de) = ds.try_next().await? {
+    ///     println!("{}", de.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scan(&self) -> Result<ObjectLister> {
+        self.list_with(OpList::new().with_recursive(true)).await
+    }
+
     /// List current dir object.
     ///
     /// This function will create a new handle to list objects.
@@ -1173,58 +2106,371 @@ impl Object {
     /// # }
     /// ```
     pub fn blocking_list(&self) -> Result<BlockingObjectLister> {
+        self.blocking_list_with(OpList::new())
+    }
+
+    /// List current dir object with option in blocking way.
+    ///
+    /// Refer to [`Object::list_with`] for more information.
+    pub fn blocking_list_with(&self, args: OpList) -> Result<BlockingObjectLister> {
         if !validate_path(self.path(), ObjectMode::DIR) {
             return Err(Error::new(
                 ErrorKind::ObjectNotADirectory,
                 "the path trying to list is not a directory",
             )
-            .with_operation("Object::blocking_list")
+            .with_operation("Object::blocking_list_with")
+            .with_context("service", self.accessor().metadata().scheme().into_static())
+            .with_context("path", self.path()));
+        }
+        self.check_start_after("Object::blocking_list_with", &args)?;
+        if args.recursive()
+            && (args.include_dirs()
+                || !self
+                    .accessor()
+                    .metadata()
+                    .capabilities()
+                    .contains(AccessorCapability::ListRecursive))
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "service doesn't support list recursively in blocking way",
+            )
+            .with_operation("Object::blocking_list_with")
+            .with_context("service", self.accessor().metadata().scheme().into_static())
+            .with_context("path", self.path()));
+        }
+
+        let (_, pager) = self.acc.blocking_list(self.path(), args)?;
+        Ok(BlockingObjectLister::new(self.acc.clone(), pager))
+    }
+
+    /// Drop the cached metadata so that the next `metadata` call will
+    /// fetch it from service.
+    fn invalidate_metadata(&self) {
+        let mut guard = self.meta.lock().expect("lock must succeed");
+        *guard = ObjectMetadata::new(ObjectMode::Unknown);
+    }
+
+    /// metadata_ref is used to get object metadata with mutex guard.
+    ///
+    /// Cached metadata will be returned if it's complete or contains all
+    /// `keys`, otherwise a `stat` will be sent.
+    ///
+    /// Called can decide to access or clone the content of object metadata.
+    /// But they can't pass the guard outside or across the await boundary.
+    ///
+    /// # Notes
+    ///
+    /// We return `MutexGuard<'_, ObjectMetadata>` here to make rustc 1.60 happy.
+    /// After MSRV bumped to higher version, we can elide this.
+    async fn metadata_ref(
+        &self,
+        keys: impl Into<FlagSet<ObjectMetakey>>,
+    ) -> Result<MutexGuard<'_, ObjectMetadata>> {
+        let keys = keys.into();
+
+        // Make sure the mutex guard has been dropped.
+        {
+            let guard = self.meta.lock().expect("lock must succeed");
+            if guard.is_complete() || guard.metakeys().contains(keys) {
+                return Ok(guard);
+            }
+        }
+
+        let rp = self.acc.stat(self.path(), OpStat::new()).await?;
+        // Metadata returned by `stat` contains all fields services can
+        // provide, mark it complete so that it can be reused.
+        let meta = rp.into_metadata().with_complete();
+
+        let mut guard = self.meta.lock().expect("lock must succeed");
+        *guard = meta;
+
+        Ok(guard)
+    }
+
+    /// Write into the underlying accessor, and abort the write if the
+    /// throughput is lower than required by [`OpWrite::min_throughput`].
+    async fn write_with_watchdog(
+        &self,
+        operation: &'static str,
+        args: OpWrite,
+        r: BytesReader,
+    ) -> Result<RpWrite> {
+        let (floor, duration) = match args.min_throughput() {
+            Some(v) => v,
+            None => return self.acc.write(self.path(), args, r).await,
+        };
+
+        let sent = Arc::new(AtomicU64::new(0));
+        let counter = sent.clone();
+        let r = Box::new(observe_read(r, move |e| {
+            if let ReadEvent::Read(n) = e {
+                counter.fetch_add(n as u64, Ordering::Relaxed);
+            }
+        }));
+
+        let size = args.size();
+        let write = self.acc.write(self.path(), args, r);
+        let watchdog = watch_throughput(sent, size, floor, duration);
+        futures::pin_mut!(write, watchdog);
+
+        match future::select(write, watchdog).await {
+            Either::Left((res, _)) => res,
+            Either::Right((actual, _)) => Err(Error::new(
+                ErrorKind::Unexpected,
+                "write throughput is lower than minimum throughput",
+            )
+            .with_operation(operation)
+            .with_context("service", self.accessor().metadata().scheme().into_static())
+            .with_context("path", self.path())
+            .with_context("min_throughput", floor.to_string())
+            .with_context("actual", actual.to_string())
+            .set_temporary()),
+        }
+    }
+
+    /// Make sure both current object and `to` are files, returns the
+    /// normalized `to`.
+    fn check_copy(&self, operation: &'static str, to: &str) -> Result<String> {
+        let to = normalize_path(to);
+
+        for path in [self.path(), to.as_str()] {
+            if !validate_path(path, ObjectMode::FILE) {
+                return Err(
+                    Error::new(ErrorKind::ObjectIsADirectory, "copy path is a directory")
+                        .with_operation(operation)
+                        .with_context("service", self.accessor().metadata().scheme().into_static())
+                        .with_context("from", self.path())
+                        .with_context("to", &to),
+                );
+            }
+        }
+
+        Ok(to)
+    }
+
+    /// Make sure versions are supported on current path.
+    fn check_versioning(&self, operation: &'static str, version: Option<&str>) -> Result<()> {
+        if !validate_path(self.path(), ObjectMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::ObjectIsADirectory, "version path is a directory")
+                    .with_operation(operation)
+                    .with_context("service", self.accessor().metadata().scheme().into_static())
+                    .with_context("path", self.path()),
+            );
+        }
+
+        if !self
+            .accessor()
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Versioning)
+        {
+            let mut err = Error::new(ErrorKind::Unsupported, "versioning is not supported")
+                .with_operation(operation)
+                .with_context("service", self.accessor().metadata().scheme().into_static())
+                .with_context("path", self.path());
+            if let Some(version) = version {
+                err = err.with_context("version", version);
+            }
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Make sure append is supported by current service.
+    fn check_append(&self, operation: &'static str, args: &OpWrite) -> Result<()> {
+        if args.append()
+            && !self
+                .accessor()
+                .metadata()
+                .capabilities()
+                .contains(AccessorCapability::Append)
+        {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "append is not supported")
+                    .with_operation(operation)
+                    .with_context("service", self.accessor().metadata().scheme().into_static())
+                    .with_context("path", self.path()),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Make sure expire is supported by current service.
+    fn check_expire(&self, operation: &'static str, args: &OpWrite) -> Result<()> {
+        if args.expire().is_some()
+            && !self
+                .accessor()
+                .metadata()
+                .capabilities()
+                .contains(AccessorCapability::Expire)
+        {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "expire is not supported")
+                    .with_operation(operation)
+                    .with_context("service", self.accessor().metadata().scheme().into_static())
+                    .with_context("path", self.path()),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn check_tagging(&self, operation: &'static str) -> Result<()> {
+        if !validate_path(self.path(), ObjectMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::ObjectIsADirectory, "tagging path is a directory")
+                    .with_operation(operation)
+                    .with_context("service", self.accessor().metadata().scheme().into_static())
+                    .with_context("path", self.path()),
+            );
+        }
+
+        if !self
+            .accessor()
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Tagging)
+        {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "tagging is not supported")
+                    .with_operation(operation)
+                    .with_context("service", self.accessor().metadata().scheme().into_static())
+                    .with_context("path", self.path()),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Validate tags against the common limits of services.
+    fn check_tags(&self, operation: &'static str, tags: &HashMap<String, String>) -> Result<()> {
+        let err = |msg: &'static str| {
+            Error::new(ErrorKind::Unexpected, msg)
+                .with_operation(operation)
+                .with_context("service", self.accessor().metadata().scheme().into_static())
+                .with_context("path", self.path())
+        };
+
+        if tags.len() > MAX_TAGS {
+            return Err(err("too many tags")
+                .with_context("count", tags.len().to_string())
+                .with_context("limit", MAX_TAGS.to_string()));
+        }
+
+        for (key, value) in tags {
+            let len = key.chars().count();
+            if len == 0 || len > MAX_TAG_KEY_LENGTH {
+                return Err(err("tag key length is out of range")
+                    .with_context("key", key)
+                    .with_context("length", len.to_string())
+                    .with_context("limit", MAX_TAG_KEY_LENGTH.to_string()));
+            }
+
+            let len = value.chars().count();
+            if len > MAX_TAG_VALUE_LENGTH {
+                return Err(err("tag value is too long")
+                    .with_context("key", key)
+                    .with_context("length", len.to_string())
+                    .with_context("limit", MAX_TAG_VALUE_LENGTH.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Make sure `start_after` is supported and is a direct child of
+    /// current dir, so that tokens from other dirs will be rejected.
+    fn check_start_after(&self, operation: &'static str, args: &OpList) -> Result<()> {
+        let start_after = match args.start_after() {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        if !self
+            .accessor()
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::ListStartAfter)
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "service doesn't support list with start_after",
+            )
+            .with_operation(operation)
             .with_context("service", self.accessor().metadata().scheme().into_static())
             .with_context("path", self.path()));
         }
 
-        let (_, pager) = self.acc.blocking_list(self.path(), OpList::new())?;
-        Ok(BlockingObjectLister::new(self.acc.clone(), pager))
-    }
-
-    /// metadata_ref is used to get object metadata with mutex guard.
-    ///
-    /// Called can decide to access or clone the content of object metadata.
-    /// But they can't pass the guard outside or across the await boundary.
-    ///
-    /// # Notes
-    ///
-    /// We return `MutexGuard<'_, ObjectMetadata>` here to make rustc 1.60 happy.
-    /// After MSRV bumped to higher version, we can elide this.
-    async fn metadata_ref(&self) -> Result<MutexGuard<'_, ObjectMetadata>> {
-        // Make sure the mutex guard has been dropped.
-        {
-            let guard = self.meta.lock().expect("lock must succeed");
-            if guard.is_complete() {
-                return Ok(guard);
-            }
+        let name = match self.path() {
+            "/" => Some(start_after),
+            p => start_after.strip_prefix(p),
+        };
+        let is_child = match name.map(|v| v.strip_suffix('/').unwrap_or(v)) {
+            Some(v) => !v.is_empty() && !v.contains('/'),
+            None => false,
+        };
+        if !is_child {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "start_after is not a direct child of the path trying to list",
+            )
+            .with_operation(operation)
+            .with_context("service", self.accessor().metadata().scheme().into_static())
+            .with_context("path", self.path())
+            .with_context("start_after", start_after));
         }
 
-        let rp = self.acc.stat(self.path(), OpStat::new()).await?;
-        let meta = rp.into_metadata();
+        Ok(())
+    }
 
-        let mut guard = self.meta.lock().expect("lock must succeed");
-        *guard = meta;
+    /// Make sure the length of returning content matches with the
+    /// `Content-Range` reported by services.
+    fn check_content_range(
+        &self,
+        operation: &'static str,
+        meta: &ObjectMetadata,
+        actual: u64,
+    ) -> Result<()> {
+        let expect = match meta.content_range().and_then(|v| v.len()) {
+            Some(v) => v,
+            None => return Ok(()),
+        };
 
-        Ok(guard)
+        if expect != actual {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "content length mismatch with content range",
+            )
+            .with_operation(operation)
+            .with_context("service", self.accessor().metadata().scheme().into_static())
+            .with_context("path", self.path())
+            .with_context("expect", expect.to_string())
+            .with_context("actual", actual.to_string()));
+        }
+
+        Ok(())
     }
 
-    fn blocking_metadata_ref(&self) -> Result<MutexGuard<'_, ObjectMetadata>> {
+    fn blocking_metadata_ref(
+        &self,
+        keys: impl Into<FlagSet<ObjectMetakey>>,
+    ) -> Result<MutexGuard<'_, ObjectMetadata>> {
+        let keys = keys.into();
+
         // Make sure the mutex guard has been dropped.
         {
             let guard = self.meta.lock().expect("lock must succeed");
-            if guard.is_complete() {
+            if guard.is_complete() || guard.metakeys().contains(keys) {
                 return Ok(guard);
             }
         }
 
         let rp = self.acc.blocking_stat(self.path(), OpStat::new())?;
-        let meta = rp.into_metadata();
+        let meta = rp.into_metadata().with_complete();
 
         let mut guard = self.meta.lock().expect("lock must succeed");
         *guard = meta;
@@ -1242,7 +2488,7 @@ impl Object {
     /// Use this function to detect the outside changes of object.
     pub async fn stat(&self) -> Result<ObjectMetadata> {
         let rp = self.acc.stat(self.path(), OpStat::new()).await?;
-        let meta = rp.into_metadata();
+        let meta = rp.into_metadata().with_complete();
 
         // Always write latest metadata into cache.
         {
@@ -1253,6 +2499,37 @@ impl Object {
         Ok(meta)
     }
 
+    /// Get current object's metadata **without cache** with extra options.
+    ///
+    /// # Notes
+    ///
+    /// Cached metadata is only updated while stating the latest version.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::OpStat;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::S3)?;
+    /// let args = OpStat::new().with_version("3HL4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY");
+    /// let meta = op.object("test").stat_with(args).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stat_with(&self, args: OpStat) -> Result<ObjectMetadata> {
+        if args.version().is_none() {
+            return self.stat().await;
+        }
+        self.check_versioning("Object::stat_with", args.version())?;
+
+        let rp = self.acc.stat(self.path(), args).await?;
+        Ok(rp.into_metadata())
+    }
+
     /// Get current object's metadata with cache.
     ///
     /// # Notes
@@ -1283,11 +2560,108 @@ impl Object {
     /// # }
     /// ```
     pub async fn metadata(&self) -> Result<ObjectMetadata> {
-        let guard = self.metadata_ref().await?;
+        let guard = self.metadata_ref(ObjectMetakey::Complete).await?;
+
+        Ok(guard.clone())
+    }
+
+    /// Get current object's metadata from cache, `stat` will be sent if
+    /// it's not cached yet or `refresh` is `true`.
+    ///
+    /// # Notes
+    ///
+    /// Metadata is cached in this object handle (and its clones) after the
+    /// first successful `stat`, and will be invalidated by `write`, `delete`
+    /// and friends called on the same handle. Changes made by other handles
+    /// or processes can only be observed with `refresh`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let o = op.object("test");
+    /// o.write("hello").await?;
+    /// // Only the first call sends `stat`.
+    /// let meta = o.metadata_cached(false).await?;
+    /// let meta = o.metadata_cached(false).await?;
+    /// // Force a `stat` to pick up outside changes.
+    /// let meta = o.metadata_cached(true).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn metadata_cached(&self, refresh: bool) -> Result<ObjectMetadata> {
+        if refresh {
+            return self.stat().await;
+        }
+
+        self.metadata().await
+    }
+
+    /// Get current object's metadata with cache, only send `stat` while
+    /// any of `keys` is missing in cached metadata.
+    ///
+    /// Objects returned by `list` carry the metadata provided by the
+    /// listing response. Use this function to access them without
+    /// sending another request for every object.
+    ///
+    /// # Notes
+    ///
+    /// Only fields in `keys` are guaranteed to be populated in returning
+    /// metadata unless it's complete.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::TryStreamExt;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// use opendal::ObjectMetakey;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let meta = op
+    ///     .object("test")
+    ///     .metadata_with(ObjectMetakey::Mode | ObjectMetakey::ContentLength)
+    ///     .await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn metadata_with(
+        &self,
+        keys: impl Into<FlagSet<ObjectMetakey>>,
+    ) -> Result<ObjectMetadata> {
+        let guard = self.metadata_ref(keys).await?;
+
+        Ok(guard.clone())
+    }
+
+    /// Get current object's metadata with cache in blocking way, only send
+    /// `stat` while any of `keys` is missing in cached metadata.
+    ///
+    /// See [`Object::metadata_with`] for more details.
+    pub fn blocking_metadata_with(
+        &self,
+        keys: impl Into<FlagSet<ObjectMetakey>>,
+    ) -> Result<ObjectMetadata> {
+        let guard = self.blocking_metadata_ref(keys)?;
 
         Ok(guard.clone())
     }
 
+    /// Metakeys of fields that have been cached in current object.
+    ///
+    /// This function doesn't send any request.
+    pub fn metakeys(&self) -> FlagSet<ObjectMetakey> {
+        self.meta.lock().expect("lock must succeed").metakeys()
+    }
+
     /// The size of `ObjectEntry`'s corresponding object
     ///
     /// `content_length` is a prefetched metadata field in `ObjectEntry`.
@@ -1302,7 +2676,7 @@ impl Object {
             }
         }
 
-        let guard = self.metadata_ref().await?;
+        let guard = self.metadata_ref(ObjectMetakey::Complete).await?;
         Ok(guard.content_length())
     }
 
@@ -1324,12 +2698,16 @@ impl Object {
             }
         }
 
-        let guard = self.metadata_ref().await?;
+        let guard = self.metadata_ref(ObjectMetakey::Complete).await?;
         Ok(guard.content_md5().map(|v| v.to_string()))
     }
 
     /// The last modified UTC datetime of `ObjectEntry`'s corresponding object
     ///
+    /// Both http-date (like `Last-Modified` header) and rfc3339 (like gcs's
+    /// `updated`) returned by services are converted into UTC, and values
+    /// that can't be parsed are treated as `None`.
+    ///
     /// `last_modified` is a prefetched metadata field in `ObjectEntry`
     ///
     /// It doesn't mean this metadata field of object doesn't exist if `last_modified` is `None`.
@@ -1346,7 +2724,7 @@ impl Object {
             }
         }
 
-        let guard = self.metadata_ref().await?;
+        let guard = self.metadata_ref(ObjectMetakey::Complete).await?;
         Ok(guard.last_modified())
     }
 
@@ -1404,7 +2782,7 @@ impl Object {
         }
 
         let rp = self.acc.blocking_stat(self.path(), OpStat::new())?;
-        let meta = rp.into_metadata();
+        let meta = rp.into_metadata().with_complete();
 
         {
             let mut guard = self.meta.lock().expect("lock must succeed");
@@ -1416,6 +2794,10 @@ impl Object {
 
     /// Check if this object exists or not.
     ///
+    /// Only [`ErrorKind::ObjectNotFound`] will be treated as `Ok(false)`,
+    /// other errors like [`ErrorKind::ObjectPermissionDenied`] will be
+    /// returned as is.
+    ///
     /// # Example
     ///
     /// ```
@@ -1434,7 +2816,7 @@ impl Object {
     /// }
     /// ```
     pub async fn is_exist(&self) -> Result<bool> {
-        let r = self.metadata_ref().await;
+        let r = self.metadata_ref(ObjectMetakey::Complete).await;
         match r {
             Ok(_) => Ok(true),
             Err(err) => match err.kind() {
@@ -1557,12 +2939,122 @@ impl Object {
     /// # }
     /// ```
     pub fn presign_write(&self, expire: Duration) -> Result<PresignedRequest> {
-        let op = OpPresign::new(OpWrite::new(0), expire);
+        self.presign_write_with(OpWrite::new(0), expire)
+    }
+
+    /// Presign an operation for write with extra options.
+    ///
+    /// Content type and length in `op` will be part of the signed headers
+    /// on services like s3, so uploads with different values will be
+    /// rejected. Length `0` means no limit on the content length.
+    ///
+    /// The required headers will be returned in
+    /// [`PresignedRequest::header`], callers should send them as is.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::OpWrite;
+    /// use opendal::Operator;
+    /// use time::Duration;
+    /// # use opendal::Scheme;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    /// #    let op = Operator::from_env(Scheme::Memory)?;
+    ///     let signed_req = op.object("test.png").presign_write_with(
+    ///         OpWrite::new(1024).with_content_type("image/png"),
+    ///         Duration::hours(1),
+    ///     )?;
+    ///     let mut req = http::Request::builder()
+    ///         .method(signed_req.method())
+    ///         .uri(signed_req.uri());
+    ///     for (k, v) in signed_req.header() {
+    ///         req = req.header(k, v);
+    ///     }
+    ///     let req = req.body(())?;
+    ///
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn presign_write_with(&self, op: OpWrite, expire: Duration) -> Result<PresignedRequest> {
+        let op = OpPresign::new(op, expire);
 
         let rp = self.acc.presign(self.path(), op)?;
         Ok(rp.into_presigned_request())
     }
 
+    /// Presign an operation for stat(head) which expires at `deadline`.
+    ///
+    /// It's the same as [`Object::presign_stat`] except the expire is
+    /// calculated from the absolute `deadline`, see
+    /// [`Object::presign_read_until`] for details.
+    pub fn presign_stat_until(&self, deadline: OffsetDateTime) -> Result<PresignedRequest> {
+        self.presign_stat(self.presign_expire_until(deadline)?)
+    }
+
+    /// Presign an operation for read which expires at `deadline`.
+    ///
+    /// The expire will be calculated against current time of the system,
+    /// clock skew between local and services is not taken into account.
+    ///
+    /// An error with [`ErrorKind::InvalidInput`] will be returned if
+    /// `deadline` has already passed, or it's beyond the maximum expire
+    /// of services (7 days for s3 and gcs). oss, obs and cos don't have a
+    /// maximum expire, the deadline will be signed as is.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    /// use time::Duration;
+    /// use time::OffsetDateTime;
+    /// # use opendal::Scheme;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    /// #    let op = Operator::from_env(Scheme::Memory)?;
+    ///     let deadline = OffsetDateTime::now_utc() + Duration::hours(1);
+    ///     let signed_req = op.object("test").presign_read_until(deadline)?;
+    ///     let req = http::Request::builder()
+    ///         .method(signed_req.method())
+    ///         .uri(signed_req.uri())
+    ///         .body(())?;
+    ///
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn presign_read_until(&self, deadline: OffsetDateTime) -> Result<PresignedRequest> {
+        self.presign_read(self.presign_expire_until(deadline)?)
+    }
+
+    /// Presign an operation for write which expires at `deadline`.
+    ///
+    /// It's the same as [`Object::presign_write`] except the expire is
+    /// calculated from the absolute `deadline`, see
+    /// [`Object::presign_read_until`] for details.
+    pub fn presign_write_until(&self, deadline: OffsetDateTime) -> Result<PresignedRequest> {
+        self.presign_write(self.presign_expire_until(deadline)?)
+    }
+
+    /// Calculate the expire of presign from absolute deadline.
+    fn presign_expire_until(&self, deadline: OffsetDateTime) -> Result<Duration> {
+        let expire = deadline - OffsetDateTime::now_utc();
+        if expire <= Duration::ZERO {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "presign deadline has already passed",
+            )
+            .with_operation("Object::presign")
+            .with_context("service", self.accessor().metadata().scheme().into_static())
+            .with_context("path", self.path())
+            .with_context("deadline", deadline.to_string()));
+        }
+        Ok(expire)
+    }
+
     /// Construct a multipart with existing upload id.
     pub fn to_multipart(&self, upload_id: &str) -> ObjectMultipart {
         ObjectMultipart::new(self.operator(), &self.path, upload_id)
@@ -1577,3 +3069,427 @@ impl Object {
         Ok(self.to_multipart(rp.upload_id()))
     }
 }
+
+/// Watch the bytes sent by write, returns the throughput in bytes per
+/// second once the average throughput within the latest `duration` is
+/// lower than `floor`.
+///
+/// Watching stops after all `size` bytes have been sent since the service
+/// is waiting for response then.
+async fn watch_throughput(
+    sent: Arc<AtomicU64>,
+    size: u64,
+    floor: u64,
+    duration: std::time::Duration,
+) -> u64 {
+    let interval = (duration / 8).clamp(
+        std::time::Duration::from_millis(1),
+        std::time::Duration::from_secs(1),
+    );
+
+    // Samples of `(time, sent bytes)` which cover the latest `duration`.
+    let mut samples = VecDeque::from([(Instant::now(), 0)]);
+    loop {
+        tokio::time::sleep(interval).await;
+
+        let (now, bytes) = (Instant::now(), sent.load(Ordering::Relaxed));
+        if bytes >= size {
+            return future::pending().await;
+        }
+
+        samples.push_back((now, bytes));
+        while samples.len() >= 2 && now - samples[1].0 >= duration {
+            samples.pop_front();
+        }
+
+        let (start, start_bytes) = samples[0];
+        let elapsed = now - start;
+        if elapsed < duration {
+            continue;
+        }
+
+        let rate = (bytes - start_bytes) as u128 * 1_000_000 / elapsed.as_micros().max(1);
+        if rate < floor as u128 {
+            return rate as u64;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use futures::AsyncReadExt;
+
+    use super::*;
+
+    /// Mock service that reads `4` bytes and then stalls for paths start
+    /// with `stall`.
+    #[derive(Debug, Default)]
+    struct MockService;
+
+    #[async_trait]
+    impl Accessor for MockService {
+        fn metadata(&self) -> AccessorMetadata {
+            let mut am = AccessorMetadata::default();
+            am.set_capabilities(AccessorCapability::Write);
+            am
+        }
+
+        async fn write(&self, path: &str, args: OpWrite, mut r: BytesReader) -> Result<RpWrite> {
+            let mut buf = vec![0; 4];
+            r.read_exact(&mut buf).await.expect("read must succeed");
+            if path.starts_with("stall") {
+                future::pending::<()>().await;
+            }
+
+            let mut bs = Vec::new();
+            r.read_to_end(&mut bs).await.expect("read must succeed");
+            Ok(RpWrite::new(args.size()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_with_min_throughput() {
+        let op = Operator::new(Arc::new(MockService));
+
+        let args = OpWrite::new(1024).with_min_throughput(1024, Duration::from_millis(100));
+        let res = op.object("stall").write_with(args, vec![0; 1024]).await;
+        let err = res.expect_err("stalled write must be aborted");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+
+        let args = OpWrite::new(1024).with_min_throughput(1024, Duration::from_millis(100));
+        op.object("normal")
+            .write_with(args, vec![0; 1024])
+            .await
+            .expect("write must succeed");
+    }
+
+    #[test]
+    fn test_blocking_unsupported() {
+        let op = Operator::new(Arc::new(MockService));
+        assert!(!op.metadata().can_blocking());
+
+        let o = op.object("normal");
+        let err = o.blocking_read().expect_err("blocking read must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = o
+            .blocking_write(vec![0; 1024])
+            .expect_err("blocking write must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = o.blocking_metadata().expect_err("blocking stat must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = o.blocking_delete().expect_err("blocking delete must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op
+            .object("dir/")
+            .blocking_list()
+            .err()
+            .expect("blocking list must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_read_unsupported() {
+        let op = Operator::from_env(Scheme::Memory).expect("build must succeed");
+        assert!(!op.metadata().can_conditional_read());
+
+        let o = op.object("file");
+        o.write("Hello, World!").await.expect("write must succeed");
+
+        for args in [
+            OpRead::new().with_if_match("etag"),
+            OpRead::new().with_if_none_match("etag"),
+        ] {
+            let err = o.read_with(args).await.expect_err("read must fail");
+            assert_eq!(err.kind(), ErrorKind::Unsupported);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_range_rejected() {
+        let op = Operator::from_env(Scheme::Memory).expect("build must succeed");
+        op.object("file")
+            .write("Hello, World!")
+            .await
+            .expect("write must succeed");
+
+        for br in [
+            BytesRange::new(Some(0), Some(0)),
+            BytesRange::new(Some(10), Some(0)),
+            BytesRange::new(None, Some(0)),
+        ] {
+            let err = op
+                .inner()
+                .read("file", OpRead::new().with_range(br))
+                .await
+                .err()
+                .expect("empty range must be rejected");
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{br:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_range_of_missing_object() {
+        let op = Operator::from_env(Scheme::Memory).expect("build must succeed");
+        let o = op.object("not_exist");
+
+        let err = o.range_read(0..0).await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+        let err = o.blocking_range_read(10..10).expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+        let err = o.range_reader(0..0).await.err().expect("read must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+
+        o.write("Hello, World!").await.expect("write must succeed");
+        assert!(o
+            .range_read(0..0)
+            .await
+            .expect("read must succeed")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_path_escapes_root() {
+        let op = Operator::from_env(Scheme::Memory).expect("build must succeed");
+
+        op.object("a/../b")
+            .write("Hello, World!")
+            .await
+            .expect("write must succeed");
+        op.object("./b")
+            .metadata()
+            .await
+            .expect("stat must succeed");
+        assert_eq!(op.object("x/y/..").path(), "x/");
+        assert_eq!(op.object("a/..").path(), "/");
+
+        for path in ["../../etc/passwd", "a/../../b"] {
+            let o = op.object(path);
+            let err = o
+                .write("Hello, World!")
+                .await
+                .expect_err("escaped path must be rejected");
+            assert_eq!(err.kind(), ErrorKind::InvalidPath, "{path}");
+
+            let err = o
+                .metadata()
+                .await
+                .expect_err("escaped path must be rejected");
+            assert_eq!(err.kind(), ErrorKind::InvalidPath, "{path}");
+        }
+
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let root = dir.join("root");
+        let mut builder = services::fs::Builder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder.build().expect("build must succeed"));
+        let err = op
+            .object("../escaped")
+            .write("Hello, World!")
+            .await
+            .expect_err("escaped path must be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidPath);
+        assert!(!dir.join("escaped").exists());
+    }
+
+    /// Mock service that keeps tags of objects in memory.
+    #[derive(Debug, Default)]
+    struct MockTaggingService {
+        tags: Mutex<HashMap<String, HashMap<String, String>>>,
+    }
+
+    #[async_trait]
+    impl Accessor for MockTaggingService {
+        fn metadata(&self) -> AccessorMetadata {
+            let mut am = AccessorMetadata::default();
+            am.set_capabilities(AccessorCapability::Tagging);
+            am
+        }
+
+        async fn get_tags(&self, path: &str, _: OpGetTags) -> Result<RpGetTags> {
+            let tags = self.tags.lock().unwrap();
+            Ok(RpGetTags::new(tags.get(path).cloned().unwrap_or_default()))
+        }
+
+        async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+            let mut tags = self.tags.lock().unwrap();
+            tags.insert(path.to_string(), args.tags().clone());
+            Ok(RpSetTags::default())
+        }
+
+        async fn delete_tags(&self, path: &str, _: OpDeleteTags) -> Result<RpDeleteTags> {
+            let mut tags = self.tags.lock().unwrap();
+            tags.remove(path);
+            Ok(RpDeleteTags::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tags() {
+        let op = Operator::new(Arc::new(MockTaggingService::default()));
+        let o = op.object("file");
+
+        assert!(o.tags().await.expect("get tags must succeed").is_empty());
+
+        let tags = HashMap::from([
+            ("project".to_string(), "ingestion".to_string()),
+            ("项目".to_string(), "数据湖 🚀".to_string()),
+            ("empty".to_string(), "".to_string()),
+        ]);
+        o.set_tags(tags.clone())
+            .await
+            .expect("set tags must succeed");
+        assert_eq!(o.tags().await.expect("get tags must succeed"), tags);
+
+        o.set_tags(HashMap::new())
+            .await
+            .expect("set empty tags must succeed");
+        assert!(o.tags().await.expect("get tags must succeed").is_empty());
+
+        o.set_tags(tags).await.expect("set tags must succeed");
+        o.delete_tags().await.expect("delete tags must succeed");
+        assert!(o.tags().await.expect("get tags must succeed").is_empty());
+
+        // Limits are counted in characters instead of bytes.
+        let tags = HashMap::from([("键".repeat(128), "值".repeat(256))]);
+        o.set_tags(tags).await.expect("set tags must succeed");
+
+        let cases = vec![
+            (
+                "too many tags",
+                (0..11).map(|i| (i.to_string(), "".to_string())).collect(),
+            ),
+            (
+                "empty key",
+                HashMap::from([("".to_string(), "value".to_string())]),
+            ),
+            (
+                "key too long",
+                HashMap::from([("键".repeat(129), "value".to_string())]),
+            ),
+            (
+                "value too long",
+                HashMap::from([("key".to_string(), "值".repeat(257))]),
+            ),
+        ];
+        for (name, tags) in cases {
+            let err = o.set_tags(tags).await.expect_err("set tags must fail");
+            assert_eq!(err.kind(), ErrorKind::Unexpected, "{name}");
+            assert!(err.to_string().contains("limit"), "{name}: {err}");
+        }
+
+        let err = op
+            .object("dir/")
+            .tags()
+            .await
+            .expect_err("get tags of dir must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectIsADirectory);
+
+        let op = Operator::new(Arc::new(MockService));
+        let err = op
+            .object("file")
+            .tags()
+            .await
+            .expect_err("get tags must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_append_unsupported() {
+        let op = Operator::from_env(Scheme::Memory).expect("build must succeed");
+        let o = op.object("file");
+        o.write("Hello, ").await.expect("write must succeed");
+
+        let err = o.append("World!").await.expect_err("append must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = o.blocking_append("World!").expect_err("append must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        // Existing content must not be overwritten.
+        assert_eq!(o.read().await.expect("read must succeed"), b"Hello, ");
+    }
+
+    #[tokio::test]
+    async fn test_expire_unsupported() {
+        let op = Operator::from_env(Scheme::Memory).expect("build must succeed");
+        let o = op.object("file");
+
+        let args = OpWrite::new(5).with_expire(Duration::from_secs(1));
+        let err = o
+            .write_with(args.clone(), "Hello")
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = o
+            .blocking_write_with(args, "Hello")
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        assert!(!o.is_exist().await.expect("stat must succeed"));
+    }
+
+    /// Mock service that counts `stat` calls.
+    #[derive(Debug, Default)]
+    struct MockStatService {
+        stats: AtomicU64,
+    }
+
+    #[async_trait]
+    impl Accessor for MockStatService {
+        fn metadata(&self) -> AccessorMetadata {
+            let mut am = AccessorMetadata::default();
+            am.set_capabilities(AccessorCapability::Read | AccessorCapability::Write);
+            am
+        }
+
+        async fn write(&self, _: &str, args: OpWrite, _: BytesReader) -> Result<RpWrite> {
+            Ok(RpWrite::new(args.size()))
+        }
+
+        async fn stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+            self.stats.fetch_add(1, Ordering::Relaxed);
+            Ok(RpStat::new(
+                ObjectMetadata::new(ObjectMode::FILE).with_content_length(4),
+            ))
+        }
+
+        async fn delete(&self, _: &str, _: OpDelete) -> Result<RpDelete> {
+            Ok(RpDelete::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metadata_cached() {
+        let acc = Arc::new(MockStatService::default());
+        let op = Operator::new(acc.clone());
+        let stats = || acc.stats.load(Ordering::Relaxed);
+
+        let o = op.object("file");
+        for _ in 0..3 {
+            let meta = o.metadata_cached(false).await.expect("stat must succeed");
+            assert_eq!(meta.content_length(), 4);
+        }
+        o.metadata().await.expect("stat must succeed");
+        o.content_length().await.expect("stat must succeed");
+        assert_eq!(stats(), 1);
+
+        o.metadata_cached(true).await.expect("stat must succeed");
+        assert_eq!(stats(), 2);
+        o.metadata_cached(false).await.expect("stat must succeed");
+        assert_eq!(stats(), 2);
+
+        // Cache will be invalidated by write and delete.
+        o.write_from(4, Cursor::new(vec![0; 4]))
+            .await
+            .expect("write must succeed");
+        o.metadata_cached(false).await.expect("stat must succeed");
+        assert_eq!(stats(), 3);
+
+        o.delete().await.expect("delete must succeed");
+        o.metadata_cached(false).await.expect("stat must succeed");
+        assert_eq!(stats(), 4);
+    }
+}
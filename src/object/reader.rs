@@ -12,45 +12,162 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::future::Future;
 use std::io;
+use std::io::Read;
+use std::io::SeekFrom;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 
+use futures::future::BoxFuture;
+use futures::ready;
 use futures::AsyncRead;
+use futures::AsyncSeek;
 use time::OffsetDateTime;
 
 use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
 use crate::ObjectMetadata;
+use crate::OpRead;
+use crate::OpStat;
+use crate::Result;
+
+/// Size of the internal buffer of [`ObjectReader`]: 64 KiB.
+///
+/// Backward seeks within the most recently read bytes will be served from
+/// this buffer without sending new requests.
+const BUFFER_SIZE: usize = 64 * 1024;
 
 /// ObjectReader is a bytes reader that carries it's related metadata.
 /// Users could fetch part of metadata that carried by read response.
+///
+/// # Seek
+///
+/// ObjectReader implements `AsyncSeek`:
+///
+/// - Seeking is a pure in memory operation, the next read after seeking
+///   will start a new ranged read on backend.
+/// - Recently read bytes are buffered, seeking back into them will not
+///   start new requests.
+/// - Seeking past the end is allowed, and the following read returns 0.
+/// - `SeekFrom::End` uses the content length of this reader, it will be
+///   fetched via `stat` if not known.
+///
+/// # Tokio
+///
+/// ObjectReader implements `tokio::io::AsyncRead` and `tokio::io::AsyncBufRead`
+/// too, so it can be used with `tokio::io::copy` without `tokio_util::compat`:
+///
+/// - `poll_read` with an empty buffer returns immediately without sending
+///   requests.
+/// - Polling after EOF keeps returning EOF.
+/// - `poll_fill_buf` serves from the internal buffer, no extra buffer is
+///   allocated.
 pub struct ObjectReader {
+    acc: Arc<dyn Accessor>,
+    path: String,
     meta: ObjectMetadata,
-    inner: BytesReader,
+
+    /// Absolute offset of this reader in the object.
+    offset: u64,
+    /// Size of this reader, `None` means not known yet.
+    size: Option<u64>,
+    /// Current position relative to `offset`.
+    pos: u64,
+
+    /// Recently read bytes which starts at `buf_start`.
+    buf: Vec<u8>,
+    buf_start: u64,
+    /// The bytes reader that is positioned at the end of `buf`.
+    inner: Option<BytesReader>,
+    state: State,
+}
+
+enum State {
+    Idle,
+    /// Sending a read request that starts at given position.
+    Sending(u64, BoxFuture<'static, Result<(RpRead, BytesReader)>>),
+    Stating(BoxFuture<'static, Result<RpStat>>),
 }
 
 impl ObjectReader {
     /// Create a new object reader.
-    pub fn new(meta: ObjectMetadata, inner: BytesReader) -> Self {
-        ObjectReader { meta, inner }
+    ///
+    /// `inner` is the reader which reads from `offset` of the object, and
+    /// `meta` is returned by the same read request.
+    pub(crate) fn new(
+        acc: Arc<dyn Accessor>,
+        path: &str,
+        offset: u64,
+        meta: ObjectMetadata,
+        inner: BytesReader,
+    ) -> Self {
+        ObjectReader {
+            acc,
+            path: path.to_string(),
+            offset,
+            size: meta.content_length_raw(),
+            meta,
+            pos: 0,
+
+            buf: Vec::new(),
+            buf_start: 0,
+            inner: Some(inner),
+            state: State::Idle,
+        }
+    }
+
+    /// Create a new lazy object reader which will not send read requests
+    /// until it's polled.
+    ///
+    /// `meta` must carry the content length of this reader.
+    pub(crate) fn lazy(
+        acc: Arc<dyn Accessor>,
+        path: &str,
+        offset: u64,
+        meta: ObjectMetadata,
+    ) -> Self {
+        ObjectReader {
+            acc,
+            path: path.to_string(),
+            offset,
+            size: meta.content_length_raw(),
+            meta,
+            pos: 0,
+
+            buf: Vec::new(),
+            buf_start: 0,
+            inner: None,
+            state: State::Idle,
+        }
     }
 
     /// Replace the bytes reader with new one.
+    ///
+    /// # Notes
+    ///
+    /// Readers created by following seeks will not be replaced.
     pub fn with_reader(mut self, inner: BytesReader) -> Self {
-        self.inner = inner;
+        self.inner = Some(inner);
         self
     }
 
     /// Replace the bytes reader with new one.
+    ///
+    /// # Notes
+    ///
+    /// Readers created by following seeks will not be mapped.
     pub fn map_reader(mut self, f: impl FnOnce(BytesReader) -> BytesReader) -> Self {
-        self.inner = f(self.inner);
+        self.inner = self.inner.map(f);
         self
     }
 
     /// Convert into a bytes reader to consume the reader.
     pub fn into_reader(self) -> BytesReader {
-        self.inner
+        self.into_parts().1
     }
 
     /// Convert into parts.
@@ -61,8 +178,18 @@ impl ObjectReader {
     ///
     /// The [`ObjectMetadata`] is **different** from the whole object's
     /// metadata. It just described the corresbonding reader's metadata.
-    pub fn into_parts(self) -> (ObjectMetadata, BytesReader) {
-        (self.meta, self.inner)
+    ///
+    /// Bytes that have been buffered for seeking will be lost.
+    ///
+    /// If no request has been sent yet (like readers created by
+    /// [`Object::reader_with_range`](crate::Object::reader_with_range)),
+    /// the whole reader will be returned as bytes reader so that it's
+    /// still lazy.
+    pub fn into_parts(mut self) -> (ObjectMetadata, BytesReader) {
+        match self.inner.take() {
+            Some(inner) => (self.meta, inner),
+            None => (self.meta.clone(), Box::new(self)),
+        }
     }
 
     /// Content length of this object reader.
@@ -95,12 +222,8 @@ impl ObjectReader {
     /// `ETag` is defined by [RFC 7232](https://httpwg.org/specs/rfc7232.html#header.etag)
     /// Refer to [MDN ETag](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/ETag) for more information.
     ///
-    /// OpenDAL will return this value AS-IS like the following:
-    ///
-    /// - `"33a64df551425fcc55e4d42a148795d9f25f89d4"`
-    /// - `W/"0815"`
-    ///
-    /// `"` is part of etag.
+    /// OpenDAL will return this value with surrounding quotes stripped, see
+    /// [`ObjectMetadata::etag`] for more details.
     ///
     /// # Notes
     ///
@@ -109,6 +232,76 @@ impl ObjectReader {
     pub fn etag(&self) -> Option<&str> {
         self.meta.etag()
     }
+
+    /// Content encoding of this object.
+    ///
+    /// `Content-Encoding` is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-encoding)
+    /// Refer to [MDN Content-Encoding](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Encoding) for more information.
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.meta.content_encoding()
+    }
+
+    fn buf_end(&self) -> u64 {
+        self.buf_start + self.buf.len() as u64
+    }
+
+    /// Append read bytes into buffer and drop the too old ones.
+    fn fill_buf(&mut self, bs: &[u8]) {
+        self.buf.extend_from_slice(bs);
+        if self.buf.len() > 2 * BUFFER_SIZE {
+            let n = self.buf.len() - BUFFER_SIZE;
+            self.buf.drain(..n);
+            self.buf_start += n as u64;
+        }
+    }
+
+    /// Make sure the read response starts at the requested position.
+    fn check_content_range(&self, pos: u64, meta: &ObjectMetadata) -> Result<()> {
+        let start = match meta.content_range().and_then(|v| v.range()) {
+            Some(v) => v.start,
+            None => return Ok(()),
+        };
+
+        if start != self.offset + pos {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "content range mismatch with requested position",
+            )
+            .with_operation("ObjectReader::read")
+            .with_context("service", self.acc.metadata().scheme().into_static())
+            .with_context("path", &self.path)
+            .with_context("expect", (self.offset + pos).to_string())
+            .with_context("actual", start.to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn poll_size(&mut self, cx: &mut Context<'_>) -> Poll<Result<u64>> {
+        if let Some(size) = self.size {
+            return Poll::Ready(Ok(size));
+        }
+
+        match &mut self.state {
+            State::Stating(fut) => {
+                let rp = ready!(Pin::new(fut).poll(cx));
+                self.state = State::Idle;
+
+                let total = rp?.into_metadata().content_length();
+                let size = total.saturating_sub(self.offset);
+                self.size = Some(size);
+                Poll::Ready(Ok(size))
+            }
+            _ => {
+                let acc = self.acc.clone();
+                let path = self.path.clone();
+                let fut = async move { acc.stat(&path, OpStat::new()).await };
+
+                self.state = State::Stating(Box::pin(fut));
+                self.poll_size(cx)
+            }
+        }
+    }
 }
 
 impl AsyncRead for ObjectReader {
@@ -117,6 +310,361 @@ impl AsyncRead for ObjectReader {
         cx: &mut Context<'_>,
         buf: &mut [u8],
     ) -> Poll<io::Result<usize>> {
-        Pin::new(&mut self.inner).poll_read(cx, buf)
+        // Serve from buffer if possible.
+        if self.pos >= self.buf_start && self.pos < self.buf_end() {
+            let start = (self.pos - self.buf_start) as usize;
+            let n = (self.buf.len() - start).min(buf.len());
+            buf[..n].copy_from_slice(&self.buf[start..start + n]);
+            self.pos += n as u64;
+            return Poll::Ready(Ok(n));
+        }
+
+        if self.pos == self.buf_end() {
+            if let Some(r) = self.inner.as_mut() {
+                let n = ready!(Pin::new(r).poll_read(cx, buf))?;
+                self.fill_buf(&buf[..n]);
+                self.pos += n as u64;
+                return Poll::Ready(Ok(n));
+            }
+        }
+
+        // Check size first so that we don't send requests past the end.
+        let size = ready!(self.poll_size(cx))?;
+        if self.pos >= size {
+            return Poll::Ready(Ok(0));
+        }
+
+        match &mut self.state {
+            State::Sending(pos, fut) => {
+                let pos = *pos;
+                let res = ready!(Pin::new(fut).poll(cx));
+                // Reset state before checking result so that the reader
+                // could be polled again after errors.
+                self.state = State::Idle;
+                let (rp, r) = res?;
+                // Position has been changed by seek, send a new request.
+                if pos != self.pos {
+                    return self.poll_read(cx, buf);
+                }
+                self.check_content_range(pos, &rp.into_metadata())?;
+
+                self.buf.clear();
+                self.buf_start = self.pos;
+                self.inner = Some(r);
+                self.poll_read(cx, buf)
+            }
+            _ => {
+                self.inner = None;
+
+                let acc = self.acc.clone();
+                let path = self.path.clone();
+                let op = OpRead::new().with_range(BytesRange::new(
+                    Some(self.offset + self.pos),
+                    Some(size - self.pos),
+                ));
+                let fut = async move { acc.read(&path, op).await };
+
+                self.state = State::Sending(self.pos, Box::pin(fut));
+                self.poll_read(cx, buf)
+            }
+        }
+    }
+}
+
+impl tokio::io::AsyncRead for ObjectReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let n = ready!(AsyncRead::poll_read(self, cx, buf.initialize_unfilled()))?;
+        buf.advance(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl tokio::io::AsyncBufRead for ObjectReader {
+    fn poll_fill_buf(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        if self.pos < self.buf_start || self.pos >= self.buf_end() {
+            // Bytes read from backend will be appended into our buffer,
+            // step back so that they are returned below.
+            let mut chunk = [0; 8 * 1024];
+            let n = ready!(AsyncRead::poll_read(self.as_mut(), cx, &mut chunk))?;
+            if n == 0 {
+                return Poll::Ready(Ok(&[]));
+            }
+            self.pos -= n as u64;
+        }
+
+        let this = self.get_mut();
+        let start = (this.pos - this.buf_start) as usize;
+        Poll::Ready(Ok(&this.buf[start..]))
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        self.pos += amt as u64;
+    }
+}
+
+impl AsyncSeek for ObjectReader {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let (base, off) = match pos {
+            SeekFrom::Start(off) => {
+                self.pos = off;
+                return Poll::Ready(Ok(off));
+            }
+            SeekFrom::Current(off) => (self.pos, off),
+            SeekFrom::End(off) => (ready!(self.poll_size(cx))?, off),
+        };
+
+        let pos = if off >= 0 {
+            base.checked_add(off as u64)
+        } else {
+            base.checked_sub(off.unsigned_abs())
+        };
+        match pos {
+            Some(n) => {
+                self.pos = n;
+                Poll::Ready(Ok(n))
+            }
+            None => Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ))),
+        }
+    }
+}
+
+/// BlockingObjectReader is a blocking bytes reader that carries it's
+/// related metadata.
+///
+/// BlockingObjectReader implements `std::io::Read`, no async runtime is
+/// required to use it.
+pub struct BlockingObjectReader {
+    meta: ObjectMetadata,
+    inner: BlockingBytesReader,
+}
+
+impl BlockingObjectReader {
+    /// Create a new blocking object reader.
+    pub(crate) fn new(meta: ObjectMetadata, inner: BlockingBytesReader) -> Self {
+        BlockingObjectReader { meta, inner }
+    }
+
+    /// Convert into a blocking bytes reader to consume the reader.
+    pub fn into_reader(self) -> BlockingBytesReader {
+        self.inner
+    }
+
+    /// Convert into parts.
+    ///
+    /// # Notes
+    ///
+    /// The [`ObjectMetadata`] is **different** from the whole object's
+    /// metadata. It just described the corresbonding reader's metadata.
+    pub fn into_parts(self) -> (ObjectMetadata, BlockingBytesReader) {
+        (self.meta, self.inner)
+    }
+
+    /// Content length of this object reader.
+    ///
+    /// # Notes
+    ///
+    /// The content length returned here is the length of this read request.
+    /// It's **different** from the object's content length.
+    pub fn content_length(&self) -> u64 {
+        self.meta
+            .content_length_raw()
+            .expect("object reader must have content length")
+    }
+
+    /// Last modified of this object.
+    ///
+    /// # Notes
+    ///
+    /// The last modified returned here is the last modified of this read request.
+    /// It's **different** from the object's last modified.
+    pub fn last_modified(&self) -> Option<OffsetDateTime> {
+        self.meta.last_modified()
+    }
+
+    /// ETag of this object.
+    ///
+    /// # Notes
+    ///
+    /// The etag returned here is the etag of this read request.
+    /// It's **different** from the object's etag.
+    pub fn etag(&self) -> Option<&str> {
+        self.meta.etag()
+    }
+}
+
+impl Read for BlockingObjectReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use async_trait::async_trait;
+    use futures::AsyncReadExt;
+    use futures::AsyncSeekExt;
+
+    use super::*;
+    use crate::layers::Layer;
+    use crate::services::memory;
+    use crate::Operator;
+
+    /// Count the read requests sent to inner accessor, the first `failures`
+    /// requests will fail with temporary errors.
+    #[derive(Debug, Default)]
+    struct ReadCounter {
+        inner: Option<Arc<dyn Accessor>>,
+        count: Arc<AtomicUsize>,
+        failures: Arc<AtomicUsize>,
+    }
+
+    impl Layer for &ReadCounter {
+        fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+            Arc::new(ReadCounter {
+                inner: Some(inner),
+                count: self.count.clone(),
+                failures: self.failures.clone(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Accessor for ReadCounter {
+        fn inner(&self) -> Option<Arc<dyn Accessor>> {
+            self.inner.clone()
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            if self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |v| v.checked_sub(1))
+                .is_ok()
+            {
+                return Err(Error::new(ErrorKind::Unexpected, "read failed").set_temporary());
+            }
+            self.inner.as_ref().unwrap().read(path, args).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_seek_within_buffer() {
+        let counter = ReadCounter::default();
+        let op = Operator::new(memory::Builder::default().build().unwrap()).layer(&counter);
+
+        let content: Vec<u8> = (0..1024 * 1024).map(|v| v as u8).collect();
+        let o = op.object("test");
+        o.write(content.clone()).await.unwrap();
+
+        let mut r = o.reader().await.unwrap();
+        assert_eq!(counter.count.load(Ordering::SeqCst), 1);
+
+        // Read the footer.
+        r.seek(SeekFrom::End(-8)).await.unwrap();
+        let mut bs = vec![0; 8];
+        r.read_exact(&mut bs).await.unwrap();
+        assert_eq!(bs, &content[content.len() - 8..]);
+        assert_eq!(counter.count.load(Ordering::SeqCst), 2);
+
+        // Seek back within buffer will not send new requests.
+        r.seek(SeekFrom::Current(-4)).await.unwrap();
+        let mut bs = vec![0; 4];
+        r.read_exact(&mut bs).await.unwrap();
+        assert_eq!(bs, &content[content.len() - 4..]);
+        assert_eq!(counter.count.load(Ordering::SeqCst), 2);
+
+        // Read past the end will not send new requests either.
+        r.seek(SeekFrom::End(8)).await.unwrap();
+        assert_eq!(r.read(&mut bs).await.unwrap(), 0);
+        assert_eq!(counter.count.load(Ordering::SeqCst), 2);
+
+        // Seek before the buffer will send a new request.
+        r.seek(SeekFrom::Start(0)).await.unwrap();
+        let mut bs = vec![0; 16];
+        r.read_exact(&mut bs).await.unwrap();
+        assert_eq!(bs, &content[..16]);
+        assert_eq!(counter.count.load(Ordering::SeqCst), 3);
+
+        // Negative position is invalid.
+        assert!(r.seek(SeekFrom::Current(-17)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lazy_reader_with_range() {
+        let counter = ReadCounter::default();
+        let op = Operator::new(memory::Builder::default().build().unwrap()).layer(&counter);
+
+        let content: Vec<u8> = (0..4096).map(|v| v as u8).collect();
+        let o = op.object("test");
+        o.write(content.clone()).await.unwrap();
+
+        let mut r = o.reader_with_range(1024..2048).await.unwrap();
+        assert_eq!(r.content_length(), 1024);
+        assert_eq!(counter.count.load(Ordering::SeqCst), 0, "must be lazy");
+
+        let mut bs = vec![0; 16];
+        r.read_exact(&mut bs).await.unwrap();
+        assert_eq!(bs, &content[1024..1040]);
+        assert_eq!(counter.count.load(Ordering::SeqCst), 1);
+
+        // Positions are relative to the start of range.
+        r.seek(SeekFrom::End(-16)).await.unwrap();
+        r.read_exact(&mut bs).await.unwrap();
+        assert_eq!(bs, &content[2032..2048]);
+        assert_eq!(counter.count.load(Ordering::SeqCst), 2);
+
+        // Seek past the end of range returns nothing.
+        assert_eq!(r.seek(SeekFrom::Start(2048)).await.unwrap(), 2048);
+        assert_eq!(r.read(&mut bs).await.unwrap(), 0);
+        assert_eq!(counter.count.load(Ordering::SeqCst), 2);
+
+        // Range will be clamped to the object's content length.
+        let r = o.reader_with_range(4000..8192).await.unwrap();
+        assert_eq!(r.content_length(), 96);
+        let mut bs = Vec::new();
+        r.into_reader().read_to_end(&mut bs).await.unwrap();
+        assert_eq!(bs, &content[4000..]);
+    }
+
+    #[tokio::test]
+    async fn test_read_again_after_error() {
+        let counter = ReadCounter::default();
+        let op = Operator::new(memory::Builder::default().build().unwrap()).layer(&counter);
+
+        let content: Vec<u8> = (0..4096).map(|v| v as u8).collect();
+        let o = op.object("test");
+        o.write(content.clone()).await.unwrap();
+
+        let mut r = o.reader_with_range(1024..2048).await.unwrap();
+        counter.failures.store(2, Ordering::SeqCst);
+
+        // Polling again after an error must return the next error
+        // instead of panicking.
+        let mut bs = vec![0; 16];
+        assert!(r.read(&mut bs).await.is_err());
+        assert!(r.read(&mut bs).await.is_err());
+        assert_eq!(counter.count.load(Ordering::SeqCst), 2);
+
+        r.read_exact(&mut bs).await.unwrap();
+        assert_eq!(bs, &content[1024..1040]);
+        assert_eq!(counter.count.load(Ordering::SeqCst), 3);
     }
 }
@@ -0,0 +1,116 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use time::OffsetDateTime;
+
+/// ObjectVersion is a version of object returned by [`Object::list_versions`].
+///
+/// [`Object::list_versions`]: crate::Object::list_versions
+#[derive(Debug, Clone, Default)]
+pub struct ObjectVersion {
+    path: String,
+    version_id: String,
+    is_latest: bool,
+    is_delete_marker: bool,
+    last_modified: Option<OffsetDateTime>,
+    content_length: Option<u64>,
+    etag: Option<String>,
+}
+
+impl ObjectVersion {
+    /// Create a new version.
+    pub fn new(path: &str, version_id: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            version_id: version_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Mark whether this version is the latest version of object.
+    pub fn with_is_latest(mut self, is_latest: bool) -> Self {
+        self.is_latest = is_latest;
+        self
+    }
+
+    /// Mark whether this version is a delete marker.
+    pub fn with_is_delete_marker(mut self, is_delete_marker: bool) -> Self {
+        self.is_delete_marker = is_delete_marker;
+        self
+    }
+
+    /// Set the last modified time of this version.
+    pub fn with_last_modified(mut self, last_modified: OffsetDateTime) -> Self {
+        self.last_modified = Some(last_modified);
+        self
+    }
+
+    /// Set the content length of this version.
+    pub fn with_content_length(mut self, content_length: u64) -> Self {
+        self.content_length = Some(content_length);
+        self
+    }
+
+    /// Set the etag of this version.
+    pub fn with_etag(mut self, etag: &str) -> Self {
+        self.etag = Some(etag.to_string());
+        self
+    }
+
+    /// Get path of this version.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Set path of this version.
+    pub fn set_path(&mut self, path: &str) -> &mut Self {
+        self.path = path.to_string();
+        self
+    }
+
+    /// Get version id of this version.
+    pub fn version_id(&self) -> &str {
+        &self.version_id
+    }
+
+    /// Check if this version is the latest version of object.
+    pub fn is_latest(&self) -> bool {
+        self.is_latest
+    }
+
+    /// Check if this version is a delete marker.
+    ///
+    /// Delete markers have no content, reading them will fail.
+    pub fn is_delete_marker(&self) -> bool {
+        self.is_delete_marker
+    }
+
+    /// Get the last modified time of this version.
+    pub fn last_modified(&self) -> Option<OffsetDateTime> {
+        self.last_modified
+    }
+
+    /// Get the content length of this version.
+    ///
+    /// `None` means the service doesn't return it, or this version is a
+    /// delete marker.
+    pub fn content_length(&self) -> Option<u64> {
+        self.content_length
+    }
+
+    /// Get the etag of this version.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+}
@@ -0,0 +1,837 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::mem;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use bytes::Bytes;
+use futures::future::poll_fn;
+use futures::future::BoxFuture;
+use futures::io::Cursor;
+use futures::ready;
+use futures::stream::FuturesUnordered;
+use futures::AsyncWrite;
+use futures::Future;
+use futures::StreamExt;
+use log::warn;
+
+use crate::raw::*;
+use crate::*;
+
+/// Default part size of [`ObjectWriter`]: 8 MiB.
+///
+/// It's larger than the minimal part size of multipart upload (5 MiB in s3).
+const DEFAULT_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// ObjectWriter will coalesce many small appends into fewer requests.
+///
+/// # Process
+///
+/// ```txt
+/// append
+///     -> flush (optional) to force a boundary
+///         -> close to finalize the object
+///         -> abort to cancel this writer
+/// ```
+///
+/// # Behavior
+///
+/// - Appended data will be buffered in memory until `part_size` or
+///   `flush_interval` has been reached.
+/// - For services that support multipart, every flush will upload buffered
+///   data as a new part. `close` will complete the multipart upload.
+/// - For services that don't support multipart, all data will be kept in
+///   memory and written in one request while `close`.
+/// - Data is always written in the same order as appended.
+/// - Up to `concurrency` parts will be uploaded at the same time, parts are
+///   committed in the order of their part numbers.
+/// - Flush errors will be returned by the `append`/`flush`/`close` call that
+///   triggered it, buffered data will be kept so users can retry.
+/// - Failed parts will be uploaded again with the same part number by the
+///   next `append`/`flush`/`close` call before the multipart upload is
+///   completed.
+/// - Writer dropped without `close` will abort the multipart upload in
+///   background (requires a tokio runtime), so that uploaded parts will
+///   not be left in services.
+///
+/// # AsyncWrite
+///
+/// ObjectWriter implements `AsyncWrite` so that it can be used with
+/// `futures::io::copy` and friends:
+///
+/// - `poll_write` will upload a part once `part_size` bytes are buffered,
+///   errors of the upload will be returned by following calls.
+/// - `poll_flush` only waits for in-flight parts, it will not force a
+///   boundary like [`ObjectWriter::flush`].
+/// - `poll_close` is the same as [`ObjectWriter::close`], and it's fine to
+///   call it again after succeeded.
+///
+/// `tokio::io::AsyncWrite` is implemented in the same way, `poll_shutdown`
+/// works like `poll_close`.
+///
+/// # Notes
+///
+/// Services like s3 require every part except the last one to be larger than
+/// 5 MiB. Please make sure `part_size` and the data size between explicit
+/// `flush` calls are large enough, or `close` will fail.
+///
+/// Cloudflare R2 also requires these parts to be in the same size, so
+/// `flush` should not be called explicitly while writing to it.
+pub struct ObjectWriter {
+    acc: Arc<dyn Accessor>,
+    path: String,
+    multipart: bool,
+
+    part_size: usize,
+    concurrency: usize,
+    flush_interval: Option<Duration>,
+    last_flush: Instant,
+
+    buf: Vec<u8>,
+    upload_id: Option<String>,
+    next_part_number: usize,
+    parts: Vec<ObjectPart>,
+    /// Parts that are uploading concurrently after the upload id is known.
+    uploads: FuturesUnordered<BoxFuture<'static, (usize, Bytes, Result<ObjectPart>)>>,
+    /// Parts that failed to upload, they will be uploaded again with the
+    /// same part number.
+    failed_parts: Vec<(usize, Bytes)>,
+    state: State,
+    closed: bool,
+}
+
+enum State {
+    Idle,
+    /// Creating the multipart upload and uploading the given bytes as the
+    /// first part.
+    ///
+    /// The upload id is returned even if failed so that it can be aborted.
+    Part(BoxFuture<'static, (usize, Bytes, Option<String>, Result<ObjectPart>)>),
+    /// Writing the given bytes in one request or completing the multipart.
+    Close(BoxFuture<'static, (Bytes, Result<()>)>),
+    Abort(BoxFuture<'static, Result<()>>),
+}
+
+impl ObjectWriter {
+    /// Create a new object writer.
+    pub(crate) fn new(acc: Arc<dyn Accessor>, path: &str) -> Self {
+        let multipart = acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Multipart);
+
+        Self {
+            acc,
+            path: path.to_string(),
+            multipart,
+
+            part_size: DEFAULT_PART_SIZE,
+            concurrency: 1,
+            flush_interval: None,
+            last_flush: Instant::now(),
+
+            buf: Vec::new(),
+            upload_id: None,
+            next_part_number: 1,
+            parts: Vec::new(),
+            uploads: FuturesUnordered::new(),
+            failed_parts: Vec::new(),
+            state: State::Idle,
+            closed: false,
+        }
+    }
+
+    /// Set the part size of writer, default to 8 MiB.
+    ///
+    /// Buffered data will be uploaded as a part while its size reaches
+    /// `part_size`, so every part except the last one will be exactly
+    /// `part_size` unless flushed explicitly.
+    pub fn with_part_size(mut self, part_size: usize) -> Self {
+        self.part_size = part_size.max(1);
+        self
+    }
+
+    /// Set the max number of parts that can be uploaded at the same time,
+    /// default to 1.
+    ///
+    /// The multipart upload is always created by the first part, following
+    /// parts will be uploaded concurrently. Up to `concurrency` parts will
+    /// be kept in memory.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set the flush interval of writer.
+    ///
+    /// Buffered data will be flushed by the first `append` after `interval`
+    /// since last flush.
+    pub fn with_flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = Some(interval);
+        self
+    }
+
+    /// Append bytes into writer.
+    pub async fn append(&mut self, bs: impl AsRef<[u8]>) -> Result<()> {
+        self.check_closed("ObjectWriter::append")?;
+
+        let mut bs = bs.as_ref();
+        loop {
+            let n = poll_fn(|cx| self.poll_append(cx, bs)).await?;
+            bs = &bs[n..];
+            if bs.is_empty() {
+                break;
+            }
+        }
+
+        poll_fn(|cx| self.poll_uploads(cx, false)).await
+    }
+
+    /// Flush buffered data to force a boundary.
+    ///
+    /// For services that don't support multipart, this is a no-op.
+    pub async fn flush(&mut self) -> Result<()> {
+        self.check_closed("ObjectWriter::flush")?;
+
+        poll_fn(|cx| self.poll_uploads(cx, true)).await?;
+
+        self.last_flush = Instant::now();
+        if !self.multipart || self.buf.is_empty() {
+            return Ok(());
+        }
+
+        self.start_part();
+        poll_fn(|cx| self.poll_uploads(cx, true)).await
+    }
+
+    /// Close the writer and make sure all data has been written.
+    pub async fn close(&mut self) -> Result<()> {
+        self.check_closed("ObjectWriter::close")?;
+
+        poll_fn(|cx| self.poll_close_inner(cx)).await
+    }
+
+    /// Abort the writer and drop all buffered data.
+    ///
+    /// Parts that already uploaded will be removed too.
+    pub async fn abort(&mut self) -> Result<()> {
+        self.check_closed("ObjectWriter::abort")?;
+
+        poll_fn(|cx| self.poll_abort(cx)).await
+    }
+
+    /// Poll the in-flight request until it's finished.
+    fn poll_state(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let res = match &mut self.state {
+            State::Idle => return Poll::Ready(Ok(())),
+            State::Part(fut) => {
+                let (part_number, bs, upload_id, res) = ready!(Pin::new(fut).poll(cx));
+                if upload_id.is_some() {
+                    self.upload_id = upload_id;
+                }
+                match res {
+                    Ok(part) => {
+                        self.parts.push(part);
+                        Ok(())
+                    }
+                    Err(err) => {
+                        // Nothing else is in-flight, so the part number can
+                        // be reused by restored data.
+                        self.next_part_number = part_number;
+                        self.restore(bs);
+                        Err(err)
+                    }
+                }
+            }
+            State::Close(fut) => {
+                let (bs, res) = ready!(Pin::new(fut).poll(cx));
+                match res {
+                    Ok(()) => {
+                        self.closed = true;
+                        Ok(())
+                    }
+                    Err(err) => {
+                        self.restore(bs);
+                        Err(err)
+                    }
+                }
+            }
+            State::Abort(fut) => {
+                let res = ready!(Pin::new(fut).poll(cx));
+                if res.is_ok() {
+                    self.buf.clear();
+                    self.closed = true;
+                }
+                res
+            }
+        };
+
+        self.state = State::Idle;
+        Poll::Ready(res)
+    }
+
+    /// Poll the in-flight requests until there is room for a new part, or
+    /// until all of them are finished if `drain` is true.
+    ///
+    /// Failed parts will be uploaded again before new parts.
+    fn poll_uploads(&mut self, cx: &mut Context<'_>, drain: bool) -> Poll<Result<()>> {
+        ready!(self.poll_state(cx))?;
+
+        loop {
+            while self.uploads.len() < self.concurrency {
+                match self.failed_parts.pop() {
+                    Some((part_number, bs)) => self.push_upload(part_number, bs),
+                    None => break,
+                }
+            }
+
+            let ready = if drain {
+                self.uploads.is_empty()
+            } else {
+                self.uploads.len() < self.concurrency
+            };
+            if ready {
+                return Poll::Ready(Ok(()));
+            }
+
+            match ready!(self.uploads.poll_next_unpin(cx)) {
+                Some((_, _, Ok(part))) => self.parts.push(part),
+                Some((part_number, bs, Err(err))) => {
+                    self.failed_parts.push((part_number, bs));
+                    return Poll::Ready(Err(err));
+                }
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+
+    fn poll_append(&mut self, cx: &mut Context<'_>, bs: &[u8]) -> Poll<Result<usize>> {
+        loop {
+            ready!(self.poll_uploads(cx, false))?;
+
+            if !self.multipart {
+                self.buf.extend_from_slice(bs);
+                return Poll::Ready(Ok(bs.len()));
+            }
+
+            // Buffered data could be larger than part size if it's restored
+            // from a failed upload.
+            if self.buf.len() >= self.part_size {
+                self.start_part();
+                continue;
+            }
+
+            let n = bs.len().min(self.part_size - self.buf.len());
+            self.buf.extend_from_slice(&bs[..n]);
+
+            let interval_reached = self
+                .flush_interval
+                .map(|v| self.last_flush.elapsed() >= v)
+                .unwrap_or_default();
+            if self.buf.len() >= self.part_size || (interval_reached && !self.buf.is_empty()) {
+                self.start_part();
+            }
+
+            return Poll::Ready(Ok(n));
+        }
+    }
+
+    fn poll_close_inner(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            ready!(self.poll_uploads(cx, true))?;
+            if self.closed {
+                return Poll::Ready(Ok(()));
+            }
+
+            // Buffered data must be uploaded as the last part before
+            // completing the multipart upload.
+            if self.upload_id.is_some() && !self.buf.is_empty() {
+                self.start_part();
+                continue;
+            }
+
+            self.start_close();
+        }
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            if let State::Abort(_) = self.state {
+                return self.poll_state(cx);
+            }
+
+            // Wait for in-flight requests so that we know the upload id,
+            // their errors don't matter anymore.
+            let _ = ready!(self.poll_state(cx));
+            while ready!(self.uploads.poll_next_unpin(cx)).is_some() {}
+            self.failed_parts.clear();
+            if self.closed {
+                return Poll::Ready(Ok(()));
+            }
+
+            let upload_id = match self.upload_id.clone() {
+                Some(upload_id) => upload_id,
+                None => {
+                    self.buf.clear();
+                    self.closed = true;
+                    return Poll::Ready(Ok(()));
+                }
+            };
+
+            let acc = self.acc.clone();
+            let path = self.path.clone();
+            self.state = State::Abort(Box::pin(async move {
+                acc.abort_multipart(&path, OpAbortMultipart::new(upload_id))
+                    .await
+                    .map(|_| ())
+            }));
+        }
+    }
+
+    /// Start uploading buffered data as a new part.
+    ///
+    /// The first part will create the multipart upload exclusively, following
+    /// parts will be uploaded concurrently.
+    fn start_part(&mut self) {
+        let part_number = self.next_part_number;
+        let bs = Bytes::from(mem::take(&mut self.buf));
+
+        self.next_part_number += 1;
+        self.last_flush = Instant::now();
+
+        if self.upload_id.is_some() {
+            self.push_upload(part_number, bs);
+            return;
+        }
+
+        let acc = self.acc.clone();
+        let path = self.path.clone();
+        self.state = State::Part(Box::pin(async move {
+            let upload_id = match acc.create_multipart(&path, OpCreateMultipart::new()).await {
+                Ok(rp) => rp.upload_id().to_string(),
+                Err(err) => return (part_number, bs, None, Err(err)),
+            };
+
+            let res = write_part(acc, path, upload_id.clone(), part_number, bs.clone()).await;
+            (part_number, bs, Some(upload_id), res)
+        }));
+    }
+
+    /// Push the upload of given part into in-flight requests.
+    fn push_upload(&mut self, part_number: usize, bs: Bytes) {
+        let acc = self.acc.clone();
+        let path = self.path.clone();
+        let upload_id = self
+            .upload_id
+            .clone()
+            .expect("upload id must be known before uploading parts concurrently");
+
+        self.uploads.push(Box::pin(async move {
+            let res = write_part(acc, path, upload_id, part_number, bs.clone()).await;
+            (part_number, bs, res)
+        }));
+    }
+
+    /// Start writing buffered data in one request or completing the
+    /// multipart upload.
+    fn start_close(&mut self) {
+        let acc = self.acc.clone();
+        let path = self.path.clone();
+
+        let fut: BoxFuture<'static, (Bytes, Result<()>)> = match self.upload_id.clone() {
+            None => {
+                let bs = Bytes::from(mem::take(&mut self.buf));
+                Box::pin(async move {
+                    let res = acc
+                        .write(
+                            &path,
+                            OpWrite::new(bs.len() as u64),
+                            Box::new(Cursor::new(bs.clone())),
+                        )
+                        .await
+                        .map(|_| ());
+                    (bs, res)
+                })
+            }
+            Some(upload_id) => {
+                let mut parts = self.parts.clone();
+                // Parts could be finished out of order while uploading
+                // concurrently.
+                parts.sort_by_key(|v| v.part_number());
+                Box::pin(async move {
+                    let res = acc
+                        .complete_multipart(&path, OpCompleteMultipart::new(upload_id, parts))
+                        .await
+                        .map(|_| ());
+                    (Bytes::new(), res)
+                })
+            }
+        };
+        self.state = State::Close(fut);
+    }
+
+    /// Put bytes of failed request back before buffered data.
+    fn restore(&mut self, bs: Bytes) {
+        let mut buf = bs.to_vec();
+        buf.append(&mut self.buf);
+        self.buf = buf;
+    }
+
+    fn check_closed(&self, operation: &'static str) -> Result<()> {
+        if self.closed {
+            return Err(Error::new(ErrorKind::Unexpected, "writer has been closed")
+                .with_operation(operation)
+                .with_context("service", self.acc.metadata().scheme().into_static())
+                .with_context("path", &self.path));
+        }
+
+        Ok(())
+    }
+}
+
+async fn write_part(
+    acc: Arc<dyn Accessor>,
+    path: String,
+    upload_id: String,
+    part_number: usize,
+    bs: Bytes,
+) -> Result<ObjectPart> {
+    let op = OpWriteMultipart::new(upload_id, part_number, bs.len() as u64);
+    acc.write_multipart(&path, op, Box::new(Cursor::new(bs)))
+        .await
+        .map(|rp| rp.into_object_part())
+}
+
+impl AsyncWrite for ObjectWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.check_closed("ObjectWriter::poll_write")?;
+
+        this.poll_append(cx, buf).map_err(io::Error::from)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.closed {
+            return Poll::Ready(Ok(()));
+        }
+
+        this.poll_uploads(cx, true).map_err(io::Error::from)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_close_inner(cx).map_err(io::Error::from)
+    }
+}
+
+impl tokio::io::AsyncWrite for ObjectWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write(self, cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(self, cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_close(self, cx)
+    }
+}
+
+/// Abort the multipart upload if writer is dropped without close.
+impl Drop for ObjectWriter {
+    fn drop(&mut self) {
+        if self.closed {
+            return;
+        }
+
+        let state = mem::replace(&mut self.state, State::Idle);
+        if self.upload_id.is_none() && !matches!(state, State::Part(_)) {
+            return;
+        }
+
+        let handle = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => handle,
+            Err(_) => {
+                warn!(
+                    "writer of {} dropped without close outside tokio runtime, multipart upload {:?} will not be aborted",
+                    self.path, self.upload_id
+                );
+                return;
+            }
+        };
+
+        let acc = self.acc.clone();
+        let path = self.path.clone();
+        let mut upload_id = self.upload_id.clone();
+        handle.spawn(async move {
+            match state {
+                State::Idle => {}
+                State::Part(fut) => {
+                    let (_, _, id, _) = fut.await;
+                    upload_id = upload_id.or(id);
+                }
+                State::Close(fut) => {
+                    if fut.await.1.is_ok() {
+                        return;
+                    }
+                }
+                State::Abort(fut) => {
+                    if fut.await.is_ok() {
+                        return;
+                    }
+                }
+            }
+
+            if let Some(upload_id) = upload_id {
+                if let Err(err) = acc
+                    .abort_multipart(&path, OpAbortMultipart::new(upload_id))
+                    .await
+                {
+                    warn!("abort multipart upload of {path} failed: {err:?}");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use futures::AsyncReadExt;
+    use futures::AsyncWriteExt;
+
+    use super::*;
+
+    /// Mock service that keeps uploaded parts in memory.
+    #[derive(Debug, Default)]
+    struct MockService {
+        parts: Mutex<HashMap<usize, Vec<u8>>>,
+        object: Mutex<Vec<u8>>,
+        aborted: Mutex<Vec<String>>,
+        /// Upload of this part number will fail.
+        failed_part: Option<usize>,
+        /// Parts that are uploading now.
+        inflight: AtomicUsize,
+        /// Max number of parts that are uploading at the same time.
+        max_inflight: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        fn metadata(&self) -> AccessorMetadata {
+            let mut am = AccessorMetadata::default();
+            am.set_capabilities(AccessorCapability::Write | AccessorCapability::Multipart);
+            am
+        }
+
+        async fn create_multipart(
+            &self,
+            _: &str,
+            _: OpCreateMultipart,
+        ) -> Result<RpCreateMultipart> {
+            Ok(RpCreateMultipart::new("upload"))
+        }
+
+        async fn write_multipart(
+            &self,
+            _: &str,
+            args: OpWriteMultipart,
+            mut r: BytesReader,
+        ) -> Result<RpWriteMultipart> {
+            let mut bs = Vec::new();
+            r.read_to_end(&mut bs)
+                .await
+                .map_err(|err| Error::new(ErrorKind::Unexpected, "read part").set_source(err))?;
+            assert_eq!(bs.len() as u64, args.size());
+
+            let inflight = self.inflight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_inflight.fetch_max(inflight, Ordering::SeqCst);
+            // Give other parts a chance to start.
+            for _ in 0..10 {
+                tokio::task::yield_now().await;
+            }
+            self.inflight.fetch_sub(1, Ordering::SeqCst);
+
+            if self.failed_part == Some(args.part_number()) {
+                return Err(Error::new(ErrorKind::Unexpected, "upload part failed"));
+            }
+
+            self.parts.lock().unwrap().insert(args.part_number(), bs);
+            Ok(RpWriteMultipart::new(
+                args.part_number(),
+                &args.part_number().to_string(),
+            ))
+        }
+
+        async fn complete_multipart(
+            &self,
+            _: &str,
+            args: OpCompleteMultipart,
+        ) -> Result<RpCompleteMultipart> {
+            let parts = self.parts.lock().unwrap();
+            let mut object = self.object.lock().unwrap();
+            for part in args.parts() {
+                object.extend_from_slice(&parts[&part.part_number()]);
+            }
+            Ok(RpCompleteMultipart::default())
+        }
+
+        async fn abort_multipart(
+            &self,
+            _: &str,
+            args: OpAbortMultipart,
+        ) -> Result<RpAbortMultipart> {
+            self.aborted
+                .lock()
+                .unwrap()
+                .push(args.upload_id().to_string());
+            Ok(RpAbortMultipart::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_writer_multipart() {
+        let acc = Arc::new(MockService::default());
+        let content: Vec<u8> = (0..20 * 1024 * 1024).map(|v| v as u8).collect();
+
+        let mut w = ObjectWriter::new(acc.clone(), "path/to/file");
+        w.write_all(&content).await.expect("write must succeed");
+        w.close().await.expect("close must succeed");
+
+        let sizes: Vec<_> = {
+            let parts = acc.parts.lock().unwrap();
+            (1..=parts.len()).map(|v| parts[&v].len()).collect()
+        };
+        assert_eq!(
+            sizes,
+            vec![DEFAULT_PART_SIZE, DEFAULT_PART_SIZE, 4 * 1024 * 1024]
+        );
+        assert!(*acc.object.lock().unwrap() == content);
+        assert!(acc.aborted.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_writer_concurrent_multipart() {
+        let acc = Arc::new(MockService::default());
+        let content: Vec<u8> = (0..1024).map(|v| v as u8).collect();
+
+        let mut w = ObjectWriter::new(acc.clone(), "path/to/file")
+            .with_part_size(100)
+            .with_concurrency(4);
+        w.write_all(&content).await.expect("write must succeed");
+        w.close().await.expect("close must succeed");
+
+        assert_eq!(acc.parts.lock().unwrap().len(), 11);
+        assert_eq!(acc.max_inflight.load(Ordering::SeqCst), 4);
+        assert!(*acc.object.lock().unwrap() == content);
+    }
+
+    #[tokio::test]
+    async fn test_writer_abort_on_drop() {
+        let acc = Arc::new(MockService::default());
+
+        let mut w = ObjectWriter::new(acc.clone(), "path/to/file").with_part_size(4);
+        w.write_all(b"Hello, World!")
+            .await
+            .expect("write must succeed");
+        drop(w);
+
+        for _ in 0..100 {
+            if !acc.aborted.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(*acc.aborted.lock().unwrap(), vec!["upload".to_string()]);
+        assert!(acc.object.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_writer_part_error() {
+        let acc = Arc::new(MockService {
+            failed_part: Some(2),
+            ..Default::default()
+        });
+
+        let mut w = ObjectWriter::new(acc.clone(), "path/to/file").with_part_size(4);
+        let err = w
+            .write_all(b"Hello, World!")
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert!(err.to_string().contains("upload part failed"), "{err}");
+
+        // Data of the failed part is kept so that the same error will be
+        // returned while closing.
+        let err = w.close().await.expect_err("close must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(acc.object.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_writer_memory_multipart() {
+        let op = Operator::new(
+            services::memory::Builder::default()
+                .build()
+                .expect("build must succeed"),
+        );
+        let o = op.object("path/to/file");
+
+        let mut w = o
+            .writer()
+            .await
+            .expect("writer must succeed")
+            .with_part_size(4);
+        w.write_all(b"Hello, World!")
+            .await
+            .expect("write must succeed");
+        // Object is not visible until closed.
+        assert!(!o.is_exist().await.expect("is_exist must succeed"));
+        let uploads = op
+            .list_multipart_uploads("path/")
+            .await
+            .expect("list must succeed");
+        assert_eq!(uploads.len(), 1);
+        assert_eq!(uploads[0].path(), "path/to/file");
+        w.close().await.expect("close must succeed");
+        assert!(op
+            .list_multipart_uploads("path/")
+            .await
+            .expect("list must succeed")
+            .is_empty());
+
+        let bs = o.read().await.expect("read must succeed");
+        assert_eq!(bs, b"Hello, World!");
+    }
+}
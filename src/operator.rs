@@ -12,20 +12,38 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::env;
 use std::sync::Arc;
 
+use anyhow::anyhow;
+use flagset::FlagSet;
+use futures::stream;
+use futures::AsyncReadExt;
 use futures::StreamExt;
 use futures::TryStreamExt;
+use percent_encoding::percent_decode_str;
+use time::OffsetDateTime;
 
 use crate::object::ObjectLister;
 use crate::raw::*;
 use crate::services;
+use crate::BatchOperations;
 use crate::Error;
 use crate::ErrorKind;
 use crate::Layer;
 use crate::Object;
+use crate::ObjectMetakey;
 use crate::ObjectMode;
+use crate::ObjectUpload;
+use crate::OpBatch;
+use crate::OpCreateMultipart;
+use crate::OpDelete;
+use crate::OpList;
+use crate::OpListMultipart;
+use crate::OpRead;
+use crate::OpStat;
+use crate::OpWrite;
 use crate::Result;
 use crate::Scheme;
 
@@ -117,6 +135,10 @@ impl Operator {
     /// - Boolean values will be checked by its existences and non-empty value.
     ///   `on`, `yes`, `true`, `off`, `no`, `false` will all be treated as `true`
     ///   To disable a flag, please set value to empty.
+    /// - Unknown keys will be ignored, use [`Operator::from_iter_strict`] to
+    ///   reject them instead.
+    /// - Missing or invalid required keys will return
+    ///   [`ErrorKind::ConfigInvalid`] with the key in context `field`.
     ///
     /// # Examples
     ///
@@ -145,8 +167,11 @@ impl Operator {
         it: impl Iterator<Item = (String, String)> + 'static,
     ) -> Result<Self> {
         let op = match scheme {
+            Scheme::Alluxio => services::alluxio::Builder::from_iter(it).build()?.into(),
             Scheme::Azblob => services::azblob::Builder::from_iter(it).build()?.into(),
             Scheme::Azdfs => services::azdfs::Builder::from_iter(it).build()?.into(),
+            Scheme::B2 => services::b2::Builder::from_iter(it).build()?.into(),
+            Scheme::Cos => services::cos::Builder::from_iter(it).build()?.into(),
             Scheme::Fs => services::fs::Builder::from_iter(it).build()?.into(),
             #[cfg(feature = "services-ftp")]
             Scheme::Ftp => services::ftp::Builder::from_iter(it).build()?.into(),
@@ -157,16 +182,22 @@ impl Operator {
             #[cfg(feature = "services-ipfs")]
             Scheme::Ipfs => services::ipfs::Builder::from_iter(it).build()?.into(),
             Scheme::Ipmfs => services::ipmfs::Builder::from_iter(it).build()?.into(),
+            #[cfg(feature = "services-memcached")]
+            Scheme::Memcached => services::memcached::Builder::from_iter(it).build()?.into(),
             Scheme::Memory => services::memory::Builder::default().build()?.into(),
             #[cfg(feature = "services-moka")]
             Scheme::Moka => services::moka::Builder::from_iter(it).build()?.into(),
             Scheme::Obs => services::obs::Builder::from_iter(it).build()?.into(),
             Scheme::Oss => services::oss::Builder::from_iter(it).build()?.into(),
+            #[cfg(feature = "services-postgresql")]
+            Scheme::Postgresql => services::postgresql::Builder::from_iter(it).build()?.into(),
             #[cfg(feature = "services-redis")]
             Scheme::Redis => services::redis::Builder::from_iter(it).build()?.into(),
             #[cfg(feature = "services-rocksdb")]
             Scheme::Rocksdb => services::rocksdb::Builder::from_iter(it).build()?.into(),
             Scheme::S3 => services::s3::Builder::from_iter(it).build()?.into(),
+            Scheme::Swift => services::swift::Builder::from_iter(it).build()?.into(),
+            Scheme::Webdav => services::webdav::Builder::from_iter(it).build()?.into(),
             Scheme::Custom(v) => {
                 return Err(
                     Error::new(ErrorKind::Unsupported, "custom service  is not supported")
@@ -178,6 +209,155 @@ impl Operator {
         Ok(op)
     }
 
+    /// Create a new operator from given iterator in static dispatch like
+    /// [`Operator::from_iter`], but return [`ErrorKind::ConfigInvalid`] with
+    /// the key in context `field` if any key is unknown to the service.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # use opendal::ErrorKind;
+    /// # fn main() -> Result<()> {
+    /// let err = Operator::from_iter_strict(
+    ///     Scheme::Fs,
+    ///     [("rot".to_string(), "/tmp".to_string())].into_iter(),
+    /// )
+    /// .expect_err("unknown key must be rejected");
+    /// assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    /// assert_eq!(err.context("field"), Some("rot"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_iter_strict(
+        scheme: Scheme,
+        it: impl Iterator<Item = (String, String)> + 'static,
+    ) -> Result<Self> {
+        let keys = match Self::config_keys(scheme) {
+            Some(keys) => keys,
+            None => return Self::from_iter(scheme, it),
+        };
+
+        let opts: Vec<(String, String)> = it.collect();
+        if let Some((k, _)) = opts.iter().find(|(k, _)| !keys.contains(&k.as_str())) {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "config key is unknown")
+                    .with_operation("Operator::from_iter_strict")
+                    .with_context("service", scheme)
+                    .with_context("field", k),
+            );
+        }
+
+        Self::from_iter(scheme, opts.into_iter())
+    }
+
+    /// Create a new operator from a config map like those loaded from TOML
+    /// files or env.
+    ///
+    /// Options will be applied to the builder of `scheme` like
+    /// [`Operator::from_iter_strict`], so unknown keys will be rejected with
+    /// [`ErrorKind::ConfigInvalid`] and the key in context `field`.
+    ///
+    /// `scheme` could be parsed from its name, see [`Scheme::enabled`] for
+    /// services that are compiled in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # fn main() -> Result<()> {
+    /// let scheme: Scheme = "fs".parse()?;
+    /// let map = HashMap::from([("root".to_string(), "/tmp".to_string())]);
+    /// let _ = Operator::from_map(scheme, map)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_map(scheme: Scheme, map: HashMap<String, String>) -> Result<Self> {
+        Self::from_iter_strict(scheme, map.into_iter())
+    }
+
+    /// Create a new operator from uri like `s3://bucket/path/to/root?region=us-east-1`.
+    ///
+    /// # Behavior
+    ///
+    /// - Scheme of uri will be parsed as [`Scheme`].
+    /// - Authority of uri will be used as bucket, container or endpoint
+    ///   depending on the service, for example:
+    ///   - `s3://bucket`, `gcs://bucket`, `oss://bucket`, `obs://bucket`,
+    ///     `b2://bucket`: `bucket`
+    ///   - `azblob://container`, `swift://container`: `container`
+    ///   - `azdfs://filesystem`: `filesystem`
+    ///   - `http://host:port`, `ipmfs://host:port`: `endpoint`
+    /// - Path of uri will be used as `root`, default to `/` if not set.
+    /// - Query parameters will be used as other config keys, unknown keys
+    ///   will be rejected like [`Operator::from_iter_strict`].
+    /// - Credentials in userinfo like `s3://ak:sk@bucket` will be rejected,
+    ///   please set them via env or query options instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # fn main() -> Result<()> {
+    /// let _ = Operator::from_uri("fs:///tmp/data")?;
+    /// let _ = Operator::from_uri("s3://bucket/prefix?region=us-east-1")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_uri(uri: &str) -> Result<Self> {
+        let (scheme, opts) = parse_uri(uri)?;
+
+        Self::from_iter_strict(scheme, opts.into_iter())
+    }
+
+    /// Keys accepted by the builder of given scheme.
+    ///
+    /// Returns `None` if the scheme can't be built from iterator.
+    fn config_keys(scheme: Scheme) -> Option<&'static [&'static str]> {
+        let keys = match scheme {
+            Scheme::Alluxio => services::alluxio::Builder::KEYS,
+            Scheme::Azblob => services::azblob::Builder::KEYS,
+            Scheme::Azdfs => services::azdfs::Builder::KEYS,
+            Scheme::B2 => services::b2::Builder::KEYS,
+            Scheme::Cos => services::cos::Builder::KEYS,
+            Scheme::Fs => services::fs::Builder::KEYS,
+            #[cfg(feature = "services-ftp")]
+            Scheme::Ftp => services::ftp::Builder::KEYS,
+            Scheme::Gcs => services::gcs::Builder::KEYS,
+            #[cfg(feature = "services-hdfs")]
+            Scheme::Hdfs => services::hdfs::Builder::KEYS,
+            Scheme::Http => services::http::Builder::KEYS,
+            #[cfg(feature = "services-ipfs")]
+            Scheme::Ipfs => services::ipfs::Builder::KEYS,
+            Scheme::Ipmfs => services::ipmfs::Builder::KEYS,
+            #[cfg(feature = "services-memcached")]
+            Scheme::Memcached => services::memcached::Builder::KEYS,
+            Scheme::Memory => &[],
+            #[cfg(feature = "services-moka")]
+            Scheme::Moka => services::moka::Builder::KEYS,
+            Scheme::Obs => services::obs::Builder::KEYS,
+            Scheme::Oss => services::oss::Builder::KEYS,
+            #[cfg(feature = "services-postgresql")]
+            Scheme::Postgresql => services::postgresql::Builder::KEYS,
+            #[cfg(feature = "services-redis")]
+            Scheme::Redis => services::redis::Builder::KEYS,
+            #[cfg(feature = "services-rocksdb")]
+            Scheme::Rocksdb => services::rocksdb::Builder::KEYS,
+            Scheme::S3 => services::s3::Builder::KEYS,
+            Scheme::Swift => services::swift::Builder::KEYS,
+            Scheme::Webdav => services::webdav::Builder::KEYS,
+            Scheme::Custom(_) => return None,
+        };
+
+        Some(keys)
+    }
+
     /// Create a new operator from env.
     ///
     /// # Behavior
@@ -316,9 +496,407 @@ impl Operator {
         Object::new(self.clone(), path)
     }
 
+    /// Scan all files under the dir `path` recursively.
+    ///
+    /// This is a shortcut of [`Object::scan`], refer to it for more details.
+    /// Use [`Operator::scan_with`] to return dirs as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::TryStreamExt;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let mut ds = op.scan("path/to/dir/").await?;
+    /// while let Some(o) = ds.try_next().await? {
+    ///     println!("{}", o.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scan(&self, path: &str) -> Result<ObjectLister> {
+        self.object(path).scan().await
+    }
+
+    /// Scan all objects under the dir `path` recursively with option.
+    ///
+    /// [`OpList::with_recursive`] is always set, dirs will be returned if
+    /// [`OpList::with_include_dirs`] is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::TryStreamExt;
+    /// # use opendal::OpList;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let mut ds = op
+    ///     .scan_with("path/to/dir/", OpList::new().with_include_dirs(true))
+    ///     .await?;
+    /// while let Some(o) = ds.try_next().await? {
+    ///     println!("{}", o.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scan_with(&self, path: &str, args: OpList) -> Result<ObjectLister> {
+        self.object(path).list_with(args.with_recursive(true)).await
+    }
+
+    /// Count files and sum up their sizes under the dir `prefix` recursively.
+    ///
+    /// Returns `(count, total_bytes)`. Files are visited via
+    /// [`Operator::scan`] one by one without buffering, and the metadata
+    /// carried by listing responses will be used so that services like
+    /// `s3` don't need to send a `stat` for every file.
+    ///
+    /// Scanning will stop once `max_entries` files are counted, callers
+    /// could compare `count` with `max_entries` to tell whether the
+    /// result has been truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let (count, bytes) = op.stat_prefix("path/to/dir/", Some(10000)).await?;
+    /// println!("{count} files with {bytes} bytes");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stat_prefix(
+        &self,
+        prefix: &str,
+        max_entries: Option<usize>,
+    ) -> Result<(usize, u64)> {
+        let mut count = 0;
+        let mut total_bytes = 0;
+        if max_entries == Some(0) {
+            return Ok((count, total_bytes));
+        }
+
+        let mut ds = self.scan(prefix).await?;
+        while let Some(o) = ds.try_next().await? {
+            let meta = o
+                .metadata_with(ObjectMetakey::Mode | ObjectMetakey::ContentLength)
+                .await?;
+            if meta.mode() != ObjectMode::FILE {
+                continue;
+            }
+
+            count += 1;
+            total_bytes += meta.content_length();
+            if max_entries == Some(count) {
+                break;
+            }
+        }
+
+        Ok((count, total_bytes))
+    }
+
+    /// List all ongoing multipart uploads whose path starts with `prefix`.
+    ///
+    /// Uploads that are never completed or aborted will be kept by services
+    /// like s3 and charged until they are aborted.
+    ///
+    /// # Notes
+    ///
+    /// Services that don't support multipart like `fs` will always return
+    /// an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// for upload in op.list_multipart_uploads("dir/").await? {
+    ///     println!("{} {}", upload.path(), upload.upload_id());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_multipart_uploads(&self, prefix: &str) -> Result<Vec<ObjectUpload>> {
+        if !self.metadata().can_multipart() {
+            return Ok(Vec::new());
+        }
+
+        let prefix = normalize_path(prefix);
+
+        let mut uploads = Vec::new();
+        let mut op = OpListMultipart::new();
+        loop {
+            let rp = self.accessor.list_multipart(&prefix, op).await?;
+
+            let next = match (rp.next_key_marker(), rp.next_upload_id_marker()) {
+                (Some(key), Some(upload_id)) => Some(
+                    OpListMultipart::new()
+                        .with_key_marker(key)
+                        .with_upload_id_marker(upload_id),
+                ),
+                _ => None,
+            };
+            uploads.extend(rp.into_uploads());
+
+            match next {
+                Some(v) => op = v,
+                None => return Ok(uploads),
+            }
+        }
+    }
+
+    /// Abort all ongoing multipart uploads that are initiated before `time`.
+    ///
+    /// The count of aborted uploads will be returned.
+    ///
+    /// # Notes
+    ///
+    /// Uploads without initiated time will be kept since we can't tell
+    /// whether they are stale.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// use time::Duration;
+    /// use time::OffsetDateTime;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let cutoff = OffsetDateTime::now_utc() - Duration::days(7);
+    /// let aborted = op.abort_multipart_uploads_before(cutoff).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn abort_multipart_uploads_before(&self, time: OffsetDateTime) -> Result<usize> {
+        let uploads = self.list_multipart_uploads("/").await?;
+
+        let mut aborted = 0;
+        for upload in uploads {
+            match upload.initiated() {
+                Some(initiated) if initiated < time => {
+                    self.object(upload.path())
+                        .to_multipart(upload.upload_id())
+                        .abort()
+                        .await?;
+                    aborted += 1;
+                }
+                _ => continue,
+            }
+        }
+
+        Ok(aborted)
+    }
+
+    /// Copy the object at `src_path` of `src_op` into `dst_path` of current
+    /// operator.
+    ///
+    /// Refer to [`Operator::copy_from_with_progress`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let src = Operator::from_env(Scheme::Memory)?;
+    /// # src.object("path/to/src").write("Hello, World!").await?;
+    /// let dst = Operator::from_env(Scheme::Memory)?;
+    /// dst.copy_from(&src, "path/to/src", "path/to/dst").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_from(&self, src_op: &Operator, src_path: &str, dst_path: &str) -> Result<()> {
+        self.copy_from_with_progress(src_op, src_path, dst_path, |_| {})
+            .await
+    }
+
+    /// Copy the object at `src_path` of `src_op` into `dst_path` of current
+    /// operator, `progress` will be called with the total bytes copied so
+    /// far.
+    ///
+    /// # Behavior
+    ///
+    /// - Content will be streamed from `src_op` to current operator, so the
+    ///   object doesn't need to fit in memory.
+    /// - If current operator supports multipart and the object is larger
+    ///   than 64 MiB, the object will be uploaded in 8 MiB parts. Only one
+    ///   part will be buffered in memory at the same time.
+    /// - Content type of the source object will be kept if known.
+    /// - If copy failed while uploading parts, the multipart upload will be
+    ///   aborted so that no parts will be left in services.
+    /// - An error will be returned if the source object yields fewer or more
+    ///   bytes than its content length.
+    ///
+    /// # Notes
+    ///
+    /// Services like `memory` will still hold the whole object while
+    /// writing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let src = Operator::from_env(Scheme::Memory)?;
+    /// # src.object("path/to/src").write("Hello, World!").await?;
+    /// let dst = Operator::from_env(Scheme::Memory)?;
+    /// dst.copy_from_with_progress(&src, "path/to/src", "path/to/dst", |n| {
+    ///     println!("copied {n} bytes");
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_from_with_progress(
+        &self,
+        src_op: &Operator,
+        src_path: &str,
+        dst_path: &str,
+        mut progress: impl FnMut(u64) + Send + 'static,
+    ) -> Result<()> {
+        let src = src_op.object(src_path);
+        let dst = self.object(dst_path);
+        for (op, o) in [(src_op, &src), (self, &dst)] {
+            if !validate_path(o.path(), ObjectMode::FILE) {
+                return Err(
+                    Error::new(ErrorKind::ObjectIsADirectory, "copy path is a directory")
+                        .with_operation("Operator::copy_from")
+                        .with_context("service", op.metadata().scheme().into_static())
+                        .with_context("path", o.path()),
+                );
+            }
+        }
+
+        let meta = src_op
+            .accessor
+            .stat(src.path(), OpStat::new())
+            .await?
+            .into_metadata();
+        let size = meta.content_length();
+        let (_, r) = src_op.accessor.read(src.path(), OpRead::new()).await?;
+        let r = verify_size_read(r, size);
+
+        if self.metadata().can_multipart() && size > COPY_MULTIPART_THRESHOLD {
+            return self
+                .copy_multipart(&dst, meta.content_type(), r, progress)
+                .await;
+        }
+
+        let mut args = OpWrite::new(size);
+        if let Some(v) = meta.content_type() {
+            args = args.with_content_type(v);
+        }
+
+        let mut copied = 0;
+        let r = Box::new(observe_read(Box::new(r), move |e| {
+            if let ReadEvent::Read(n) = e {
+                copied += n as u64;
+                progress(copied);
+            }
+        }));
+        let _ = self.accessor.write(dst.path(), args, r).await?;
+        Ok(())
+    }
+
+    /// Upload the content of `r` into `dst` part by part, the upload will be
+    /// aborted if any error happened.
+    async fn copy_multipart(
+        &self,
+        dst: &Object,
+        content_type: Option<&str>,
+        mut r: impl BytesRead,
+        mut progress: impl FnMut(u64),
+    ) -> Result<()> {
+        let mut args = OpCreateMultipart::new();
+        if let Some(v) = content_type {
+            args = args.with_content_type(v);
+        }
+        let rp = self.accessor.create_multipart(dst.path(), args).await?;
+        let mp = dst.to_multipart(rp.upload_id());
+
+        let upload = async {
+            let mut parts = Vec::new();
+            let mut copied = 0;
+            loop {
+                let mut bs = Vec::with_capacity(COPY_PART_SIZE);
+                (&mut r)
+                    .take(COPY_PART_SIZE as u64)
+                    .read_to_end(&mut bs)
+                    .await
+                    .map_err(|err| {
+                        Error::new(ErrorKind::Unexpected, "read from source failed")
+                            .with_operation("Operator::copy_from")
+                            .with_context("path", dst.path())
+                            .set_source(err)
+                    })?;
+                if bs.is_empty() {
+                    break;
+                }
+
+                let n = bs.len() as u64;
+                parts.push(mp.write(parts.len() + 1, bs).await?);
+                copied += n;
+                progress(copied);
+            }
+            mp.complete(parts).await
+        };
+
+        match upload.await {
+            Ok(_) => Ok(()),
+            Err(err) => match mp.abort().await {
+                Ok(()) => Err(err),
+                Err(e) => Err(err.with_context("abort", e.to_string())),
+            },
+        }
+    }
+
     /// Check if this operator can work correctly.
     ///
-    /// We will send a `list` request to path and return any errors we met.
+    /// A cheap probe will be sent to the service and any errors we met
+    /// will be returned, so that misconfigured services could be found at
+    /// startup.
+    ///
+    /// - For services that support `list` like `s3`, `gcs`, `azblob` and
+    ///   `fs`, we will list root with a page size of `1`.
+    /// - For other services like `redis` and `rocksdb`, we will stat a
+    ///   random path which is expected to be not found.
+    ///
+    /// Check never creates or modifies any objects.
+    ///
+    /// # Behavior
+    ///
+    /// - [`ErrorKind::ConfigInvalid`] will be returned if root (like
+    ///   the bucket of s3) is not found.
+    /// - [`ErrorKind::ObjectPermissionDenied`] will be returned if the
+    ///   credential doesn't have permission to access root.
+    /// - Other errors keep their kinds, and temporary errors like network
+    ///   failures are still temporary so that callers can retry them.
+    ///
+    /// The underlying service error will be carried in context `error`.
     ///
     /// ```
     /// # use std::sync::Arc;
@@ -336,15 +914,67 @@ impl Operator {
     /// # }
     /// ```
     pub async fn check(&self) -> Result<()> {
-        let mut ds = self.object("/").list().await?;
+        if !self.metadata().can_list() {
+            let path = format!(".opendal-check-{}", uuid::Uuid::new_v4());
+            return match self.object(&path).metadata().await {
+                Ok(_) => Ok(()),
+                Err(err) if err.kind() == ErrorKind::ObjectNotFound => Ok(()),
+                Err(err) => Err(self.check_error(err)),
+            };
+        }
+
+        let mut ds = self
+            .object("/")
+            .list_with(OpList::new().with_limit(1))
+            .await
+            .map_err(|err| self.check_error(err))?;
+        if let Some(Err(err)) = ds.next().await {
+            return Err(self.check_error(err));
+        }
+
+        Ok(())
+    }
+
+    fn check_error(&self, err: Error) -> Error {
+        let meta = self.metadata();
+        let (kind, message) = match err.kind() {
+            ErrorKind::ObjectNotFound => (ErrorKind::ConfigInvalid, "root of service is not found"),
+            ErrorKind::ObjectPermissionDenied => (
+                ErrorKind::ObjectPermissionDenied,
+                "permission denied while accessing root of service",
+            ),
+            kind => (kind, "service is not available"),
+        };
 
-        match ds.next().await {
-            Some(Err(e)) if e.kind() != ErrorKind::ObjectNotFound => Err(e),
-            _ => Ok(()),
+        let mut new = Error::new(kind, message)
+            .with_operation("Operator::check")
+            .with_context("service", meta.scheme().into_static())
+            .with_context("root", meta.root())
+            .with_context("error", err.to_string());
+        if err.is_temporary() {
+            new = new.set_temporary();
         }
+        new
     }
 }
 
+/// Objects larger than this size will be copied via multipart by
+/// [`Operator::copy_from`] if supported.
+const COPY_MULTIPART_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+/// Part size used by [`Operator::copy_from`] while copying via multipart.
+///
+/// It's larger than the minimal part size of multipart upload (5 MiB in s3).
+const COPY_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Default concurrent limit of [`BatchOperator`].
+const DEFAULT_BATCH_LIMIT: usize = 8;
+
+/// Max count of paths in one native batch request.
+///
+/// s3 allows at most 1000 keys in one `DeleteObjects` request.
+const MAX_BATCH_SIZE: usize = 1000;
+
 /// BatchOperator is used to take batch operations like walk_dir and remove_all, should
 /// be constructed by [`Operator::batch()`].
 ///
@@ -354,11 +984,24 @@ impl Operator {
 #[derive(Clone, Debug)]
 pub struct BatchOperator {
     src: Operator,
+    limit: usize,
 }
 
 impl BatchOperator {
     pub(crate) fn new(op: Operator) -> Self {
-        BatchOperator { src: op }
+        BatchOperator {
+            src: op,
+            limit: DEFAULT_BATCH_LIMIT,
+        }
+    }
+
+    /// Specify the concurrent limit of batch operations.
+    ///
+    /// At most `limit` requests will be sent at the same time. `0` will be
+    /// treated as `1`.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit.max(1);
+        self
     }
 
     /// Walk a dir in the best way that suitable for underlying storage.
@@ -407,6 +1050,111 @@ impl BatchOperator {
         let obs = self.walk_bottom_up(path)?;
         obs.try_for_each(|v| async move { v.delete().await }).await
     }
+
+    /// Remove given paths in the best way that suitable for underlying storage.
+    ///
+    /// - For services that support `batch` (s3 and gcs for now), paths will be
+    ///   removed by native batch requests like s3's `DeleteObjects`.
+    /// - For other services, paths will be removed one by one concurrently.
+    ///
+    /// At most `limit` requests will be sent at the same time, refer to
+    /// [`BatchOperator::with_limit`].
+    ///
+    /// # Notes
+    ///
+    /// Removing not existing paths will not return errors.
+    ///
+    /// The result of every path is returned in the same order as input, so
+    /// that users can check and retry the failed ones. If the whole native
+    /// batch request failed, all paths in it will carry an error with the
+    /// same kind and temporary status.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::from_env(Scheme::Memory)?;
+    /// let results = op
+    ///     .batch()
+    ///     .with_limit(16)
+    ///     .remove(vec!["a".to_string(), "b".to_string()])
+    ///     .await;
+    /// for (path, res) in results {
+    ///     if let Err(err) = res {
+    ///         println!("failed to remove {path}: {err}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn remove(&self, paths: Vec<String>) -> Vec<(String, Result<()>)> {
+        let acc = self.src.inner();
+        let scheme = self.src.metadata().scheme();
+        let paths: Vec<String> = paths.iter().map(|v| normalize_path(v)).collect();
+
+        if self.src.metadata().can_batch() {
+            let batches = paths
+                .chunks(MAX_BATCH_SIZE)
+                .map(|v| v.to_vec())
+                .collect::<Vec<_>>();
+
+            stream::iter(batches)
+                .map(|batch| {
+                    let acc = acc.clone();
+                    async move {
+                        let ops = batch
+                            .iter()
+                            .map(|path| (path.clone(), OpDelete::new()))
+                            .collect();
+
+                        match acc.batch(OpBatch::new(BatchOperations::Delete(ops))).await {
+                            Ok(rp) => match rp.into_results() {
+                                BatchedResults::Delete(results) => results
+                                    .into_iter()
+                                    .map(|(path, res)| (path, res.map(|_| ())))
+                                    .collect::<Vec<_>>(),
+                            },
+                            // The whole batch failed, mark all paths in it as failed.
+                            Err(err) => batch
+                                .into_iter()
+                                .map(|path| {
+                                    let mut e =
+                                        Error::new(err.kind(), "batch delete request failed")
+                                            .with_operation("BatchOperator::remove")
+                                            .with_context("service", scheme)
+                                            .with_context("path", &path)
+                                            .set_source(anyhow!(err.to_string()));
+                                    if err.is_temporary() {
+                                        e = e.set_temporary();
+                                    }
+                                    (path, Err(e))
+                                })
+                                .collect(),
+                        }
+                    }
+                })
+                .buffered(self.limit)
+                .flat_map(stream::iter)
+                .collect()
+                .await
+        } else {
+            stream::iter(paths)
+                .map(|path| {
+                    let acc = acc.clone();
+                    async move {
+                        let res = acc.delete(&path, OpDelete::new()).await.map(|_| ());
+                        (path, res)
+                    }
+                })
+                .buffered(self.limit)
+                .collect()
+                .await
+        }
+    }
 }
 
 /// Metadata for operator, users can use this metadata to get information of operator.
@@ -436,6 +1184,32 @@ impl OperatorMetadata {
         self.acc.name()
     }
 
+    /// Capabilities of current backend.
+    ///
+    /// Capabilities will be adjusted by layers, for example,
+    /// [`ImmutableIndexLayer`][crate::layers::ImmutableIndexLayer] adds
+    /// [`AccessorCapability::List`] to the backend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::Scheme;
+    /// use opendal::raw::AccessorCapability;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let op = Operator::from_env(Scheme::Memory)?;
+    /// let cap = op.metadata().capabilities();
+    /// assert!(cap.contains(AccessorCapability::Read | AccessorCapability::Blocking));
+    /// assert!(!cap.contains(AccessorCapability::Presign));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capabilities(&self) -> FlagSet<AccessorCapability> {
+        self.acc.capabilities()
+    }
+
     /// Check if current backend supports [`Accessor::read`] or not.
     pub fn can_read(&self) -> bool {
         self.acc.capabilities().contains(AccessorCapability::Read)
@@ -471,6 +1245,143 @@ impl OperatorMetadata {
             .capabilities()
             .contains(AccessorCapability::Blocking)
     }
+
+    /// Check if current backend supports batch operations or not.
+    pub fn can_batch(&self) -> bool {
+        self.acc.capabilities().contains(AccessorCapability::Batch)
+    }
+
+    /// Check if current backend supports conditional write or not.
+    pub fn can_conditional_write(&self) -> bool {
+        self.acc
+            .capabilities()
+            .contains(AccessorCapability::ConditionalWrite)
+    }
+
+    /// Check if current backend supports conditional read or not.
+    pub fn can_conditional_read(&self) -> bool {
+        self.acc
+            .capabilities()
+            .contains(AccessorCapability::ConditionalRead)
+    }
+
+    /// Check if current backend supports list with start_after or not.
+    pub fn can_list_start_after(&self) -> bool {
+        self.acc
+            .capabilities()
+            .contains(AccessorCapability::ListStartAfter)
+    }
+
+    /// Check if current backend supports list recursively without walking
+    /// dirs or not.
+    pub fn can_list_recursive(&self) -> bool {
+        self.acc
+            .capabilities()
+            .contains(AccessorCapability::ListRecursive)
+    }
+
+    /// Check if current backend supports copy inside service or not.
+    pub fn can_copy(&self) -> bool {
+        self.acc.capabilities().contains(AccessorCapability::Copy)
+    }
+
+    /// Check if current backend supports object versions or not.
+    pub fn can_versioning(&self) -> bool {
+        self.acc
+            .capabilities()
+            .contains(AccessorCapability::Versioning)
+    }
+
+    /// Check if current backend supports write with expire or not.
+    pub fn can_expire(&self) -> bool {
+        self.acc.capabilities().contains(AccessorCapability::Expire)
+    }
+}
+
+/// Parse uri into scheme and config keys.
+///
+/// The uri itself is never carried in errors since query parameters could
+/// contain credentials.
+fn parse_uri(uri: &str) -> Result<(Scheme, Vec<(String, String)>)> {
+    let invalid = |msg: &'static str| {
+        Error::new(ErrorKind::ConfigInvalid, msg).with_operation("Operator::from_uri")
+    };
+    let decode = |field: &str, v: &str| -> Result<String> {
+        percent_decode_str(v)
+            .decode_utf8()
+            .map(|v| v.to_string())
+            .map_err(|e| {
+                invalid("uri is not valid utf-8 after percent decoding")
+                    .with_context("field", field)
+                    .set_source(e)
+            })
+    };
+
+    let (raw_scheme, rest) = uri
+        .split_once("://")
+        .ok_or_else(|| invalid("uri must be in the form of `scheme://authority/path?query`"))?;
+    let raw_scheme = raw_scheme.to_lowercase();
+    let scheme: Scheme = raw_scheme.parse()?;
+
+    let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let (authority, path) = match rest.find('/') {
+        Some(idx) => rest.split_at(idx),
+        None => (rest, ""),
+    };
+    if authority.contains('@') {
+        return Err(invalid(
+            "credentials are not allowed in uri, please set them via env or options instead",
+        )
+        .with_context("service", scheme)
+        .with_context("field", "userinfo"));
+    }
+
+    let mut opts = Vec::new();
+    if !authority.is_empty() {
+        let (key, value) = match scheme {
+            Scheme::S3 | Scheme::Gcs | Scheme::Oss | Scheme::Obs | Scheme::B2 | Scheme::Cos => {
+                ("bucket", authority.to_string())
+            }
+            Scheme::Azblob | Scheme::Swift => ("container", authority.to_string()),
+            Scheme::Azdfs => ("filesystem", authority.to_string()),
+            #[cfg(feature = "services-hdfs")]
+            Scheme::Hdfs => ("name_node", format!("hdfs://{authority}")),
+            #[cfg(feature = "services-ftp")]
+            Scheme::Ftp => ("endpoint", format!("{raw_scheme}://{authority}")),
+            #[cfg(feature = "services-memcached")]
+            Scheme::Memcached => ("endpoint", format!("tcp://{authority}")),
+            #[cfg(feature = "services-redis")]
+            Scheme::Redis => ("endpoint", format!("redis://{authority}")),
+            Scheme::Http => ("endpoint", format!("{raw_scheme}://{authority}")),
+            #[cfg(feature = "services-ipfs")]
+            Scheme::Ipfs => ("endpoint", format!("https://{authority}")),
+            Scheme::Webdav => ("endpoint", format!("https://{authority}")),
+            Scheme::Ipmfs | Scheme::Alluxio => ("endpoint", format!("http://{authority}")),
+            _ => {
+                return Err(invalid("authority is not supported by service")
+                    .with_context("service", scheme)
+                    .with_context("field", "authority"))
+            }
+        };
+        opts.push((key.to_string(), decode(key, &value)?));
+    }
+    if !path.is_empty() {
+        opts.push(("root".to_string(), decode("root", path)?));
+    }
+
+    for pair in query.split('&').filter(|v| !v.is_empty()) {
+        let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+        let k = decode(k, k)?;
+        if opts.iter().any(|(key, _)| key == &k) {
+            return Err(invalid("config is set more than once in uri")
+                .with_context("service", scheme)
+                .with_context("field", k));
+        }
+        let v = decode(&k, v)?;
+        opts.push((k, v));
+    }
+
+    Ok((scheme, opts))
 }
 
 #[cfg(test)]
@@ -494,4 +1405,716 @@ mod tests {
         .join();
         assert!(op.is_poisoned());
     }
+    #[tokio::test]
+    async fn test_check() {
+        use async_trait::async_trait;
+
+        use crate::raw::*;
+        use crate::*;
+
+        #[derive(Debug)]
+        struct MockService(ErrorKind);
+
+        #[async_trait]
+        impl Accessor for MockService {
+            fn metadata(&self) -> AccessorMetadata {
+                let mut am = AccessorMetadata::default();
+                am.set_capabilities(AccessorCapability::List);
+                am
+            }
+
+            async fn list(&self, _: &str, _: OpList) -> Result<(RpList, ObjectPager)> {
+                Err(Error::new(self.0, "NoSuchBucket"))
+            }
+        }
+
+        let op = Operator::new(MockService(ErrorKind::ObjectNotFound));
+        let err = op.check().await.expect_err("check must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert!(err.to_string().contains("NoSuchBucket"));
+
+        let op = Operator::new(MockService(ErrorKind::ObjectPermissionDenied));
+        let err = op.check().await.expect_err("check must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectPermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_check_without_list() {
+        use async_trait::async_trait;
+
+        use crate::raw::*;
+        use crate::*;
+
+        #[derive(Debug)]
+        struct MockService(ErrorKind);
+
+        #[async_trait]
+        impl Accessor for MockService {
+            fn metadata(&self) -> AccessorMetadata {
+                let mut am = AccessorMetadata::default();
+                am.set_capabilities(AccessorCapability::Read);
+                am
+            }
+
+            async fn stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+                Err(Error::new(self.0, "stat failed"))
+            }
+        }
+
+        let op = Operator::new(MockService(ErrorKind::ObjectNotFound));
+        op.check().await.expect("check must succeed");
+
+        let op = Operator::new(MockService(ErrorKind::ObjectPermissionDenied));
+        let err = op.check().await.expect_err("check must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectPermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_check_does_not_modify() {
+        use std::env;
+
+        use futures::TryStreamExt;
+
+        use crate::services::fs;
+        use crate::Operator;
+        use crate::Scheme;
+
+        let op = Operator::from_env(Scheme::Memory).expect("build operator");
+        op.check().await.expect("check must succeed");
+        let ds = op.scan("/").await.expect("scan must succeed");
+        let paths: Vec<_> = ds.try_collect().await.expect("scan must succeed");
+        assert!(paths.is_empty(), "check must not create objects");
+
+        let root = env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let mut builder = fs::Builder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder.build().expect("build must succeed"));
+        op.check().await.expect("check must succeed");
+        let entries = std::fs::read_dir(&root)
+            .expect("root must be created")
+            .count();
+        assert_eq!(entries, 0, "check must not create objects");
+        std::fs::remove_dir_all(&root).expect("remove root must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_scan() {
+        use std::collections::HashSet;
+
+        use futures::TryStreamExt;
+
+        use crate::OpList;
+        use crate::Operator;
+        use crate::Scheme;
+
+        let op = Operator::from_env(Scheme::Memory).expect("build operator");
+        let files = [
+            "x/a", "x/b", "x/y/c", "x/y/z/d", "x/y/z/e", "x/w/f", "other/g",
+        ];
+        for path in files {
+            op.object(path)
+                .write("data")
+                .await
+                .expect("write must succeed");
+        }
+
+        let mut visited = HashSet::new();
+        let mut ds = op.scan("x/").await.expect("scan must succeed");
+        while let Some(o) = ds.try_next().await.expect("scan must succeed") {
+            assert!(
+                visited.insert(o.path().to_string()),
+                "duplicated path: {}",
+                o.path()
+            );
+        }
+        let expected: HashSet<String> = files[..6].iter().map(|v| v.to_string()).collect();
+        assert_eq!(visited, expected);
+
+        let ds = op
+            .scan_with("x/", OpList::new().with_include_dirs(true))
+            .await
+            .expect("scan must succeed");
+        let mut paths: Vec<String> = ds
+            .map_ok(|o| o.path().to_string())
+            .try_collect()
+            .await
+            .expect("scan must succeed");
+        paths.sort_unstable();
+        assert_eq!(
+            paths,
+            vec!["x/a", "x/b", "x/w/", "x/w/f", "x/y/", "x/y/c", "x/y/z/", "x/y/z/d", "x/y/z/e"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stat_prefix() {
+        use std::env;
+
+        use crate::services::fs;
+        use crate::Operator;
+
+        let root = env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let mut builder = fs::Builder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let files = [
+            ("x/a", "Hello"),
+            ("x/empty", ""),
+            ("x/y/b", "World!"),
+            ("x/y/z/empty", ""),
+            ("x/y/z/c", "abc"),
+            ("other/d", "ignored"),
+        ];
+        for (path, content) in files {
+            op.object(path)
+                .write(content)
+                .await
+                .expect("write must succeed");
+        }
+        op.object("x/w/")
+            .create()
+            .await
+            .expect("create dir must succeed");
+
+        let res = op
+            .stat_prefix("x/", None)
+            .await
+            .expect("stat prefix must succeed");
+        assert_eq!(res, (5, 14));
+
+        let (count, bytes) = op
+            .stat_prefix("x/", Some(2))
+            .await
+            .expect("stat prefix must succeed");
+        assert_eq!(count, 2);
+        assert!(bytes <= 14);
+
+        let res = op
+            .stat_prefix("not_exist/", None)
+            .await
+            .expect("stat prefix must succeed");
+        assert_eq!(res, (0, 0));
+
+        std::fs::remove_dir_all(&root).expect("remove root must succeed");
+    }
+
+    #[test]
+    fn test_from_iter_keys() {
+        use crate::services;
+
+        macro_rules! assert_keys {
+            ($builder:ty) => {
+                let default = format!("{:?}", <$builder>::default());
+                for key in <$builder>::KEYS {
+                    let value = match *key {
+                        "thread_pool_enabled" | "trust_accept_ranges" => "true",
+                        _ => "1",
+                    };
+                    let builder =
+                        <$builder>::from_iter([(key.to_string(), value.to_string())].into_iter());
+                    assert_ne!(
+                        format!("{builder:?}"),
+                        default,
+                        "key {key} of {} must be handled",
+                        stringify!($builder)
+                    );
+                }
+            };
+        }
+
+        assert_keys!(services::alluxio::Builder);
+        assert_keys!(services::azblob::Builder);
+        assert_keys!(services::azdfs::Builder);
+        assert_keys!(services::b2::Builder);
+        assert_keys!(services::fs::Builder);
+        #[cfg(feature = "services-ftp")]
+        assert_keys!(services::ftp::Builder);
+        assert_keys!(services::gcs::Builder);
+        #[cfg(feature = "services-hdfs")]
+        assert_keys!(services::hdfs::Builder);
+        assert_keys!(services::http::Builder);
+        #[cfg(feature = "services-ipfs")]
+        assert_keys!(services::ipfs::Builder);
+        assert_keys!(services::ipmfs::Builder);
+        #[cfg(feature = "services-memcached")]
+        assert_keys!(services::memcached::Builder);
+        #[cfg(feature = "services-moka")]
+        assert_keys!(services::moka::Builder);
+        assert_keys!(services::obs::Builder);
+        assert_keys!(services::oss::Builder);
+        #[cfg(feature = "services-postgresql")]
+        assert_keys!(services::postgresql::Builder);
+        #[cfg(feature = "services-redis")]
+        assert_keys!(services::redis::Builder);
+        #[cfg(feature = "services-rocksdb")]
+        assert_keys!(services::rocksdb::Builder);
+        assert_keys!(services::s3::Builder);
+        assert_keys!(services::swift::Builder);
+    }
+
+    #[test]
+    fn test_from_map() {
+        use std::collections::HashMap;
+        use std::env;
+
+        use crate::*;
+
+        let mut map = HashMap::from([
+            ("bucket".to_string(), "test".to_string()),
+            ("region".to_string(), "us-east-1".to_string()),
+            ("endpoint".to_string(), "http://127.0.0.1:9000".to_string()),
+        ]);
+        let op = Operator::from_map(Scheme::S3, map.clone()).expect("build must succeed");
+        assert_eq!(op.metadata().scheme(), Scheme::S3);
+        assert_eq!(op.metadata().name(), "test");
+
+        map.insert("unknown".to_string(), "value".to_string());
+        let err = Operator::from_map(Scheme::S3, map).expect_err("unknown key must be rejected");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert_eq!(err.context("field"), Some("unknown"));
+
+        let root = env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let map = HashMap::from([("root".to_string(), root.to_string_lossy().to_string())]);
+        let op = Operator::from_map(Scheme::Fs, map).expect("build must succeed");
+        assert_eq!(op.metadata().scheme(), Scheme::Fs);
+        assert_eq!(op.metadata().root(), format!("{}/", root.to_string_lossy()));
+    }
+
+    #[test]
+    fn test_from_iter_strict() {
+        use crate::*;
+
+        let opts = || {
+            [
+                ("bucket".to_string(), "test".to_string()),
+                ("region".to_string(), "us-east-1".to_string()),
+                ("endpoint".to_string(), "http://127.0.0.1:9000".to_string()),
+            ]
+            .into_iter()
+        };
+        Operator::from_iter_strict(Scheme::S3, opts()).expect("known keys must be accepted");
+
+        let err = Operator::from_iter_strict(
+            Scheme::S3,
+            opts().chain([("buckt".to_string(), "test".to_string())]),
+        )
+        .expect_err("unknown key must be rejected");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert_eq!(err.context("field"), Some("buckt"));
+
+        // Unknown keys are ignored if not strict.
+        Operator::from_iter(
+            Scheme::S3,
+            opts().chain([("buckt".to_string(), "test".to_string())]),
+        )
+        .expect("unknown key must be ignored");
+
+        let err = Operator::from_iter(
+            Scheme::S3,
+            [("region".to_string(), "us-east-1".to_string())].into_iter(),
+        )
+        .expect_err("missing bucket must be rejected");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert_eq!(err.context("field"), Some("bucket"));
+    }
+
+    #[test]
+    fn test_parse_uri() {
+        use super::parse_uri;
+        use crate::*;
+
+        let kvs = |v: &[(&str, &str)]| -> Vec<(String, String)> {
+            v.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+
+        #[allow(unused_mut)]
+        let mut cases = vec![
+            (
+                "s3://bucket/prefix?region=us-east-1",
+                Scheme::S3,
+                kvs(&[
+                    ("bucket", "bucket"),
+                    ("root", "/prefix"),
+                    ("region", "us-east-1"),
+                ]),
+            ),
+            ("s3://bucket", Scheme::S3, kvs(&[("bucket", "bucket")])),
+            (
+                "gcs://bucket/a%20b/",
+                Scheme::Gcs,
+                kvs(&[("bucket", "bucket"), ("root", "/a b/")]),
+            ),
+            (
+                "oss://bucket/?endpoint=https%3A%2F%2Foss.aliyuncs.com",
+                Scheme::Oss,
+                kvs(&[
+                    ("bucket", "bucket"),
+                    ("root", "/"),
+                    ("endpoint", "https://oss.aliyuncs.com"),
+                ]),
+            ),
+            ("obs://bucket", Scheme::Obs, kvs(&[("bucket", "bucket")])),
+            (
+                "cos://examplebucket-1250000000?region=ap-beijing",
+                Scheme::Cos,
+                kvs(&[
+                    ("bucket", "examplebucket-1250000000"),
+                    ("region", "ap-beijing"),
+                ]),
+            ),
+            (
+                "b2://bucket/root?bucket_id=bucket_id",
+                Scheme::B2,
+                kvs(&[
+                    ("bucket", "bucket"),
+                    ("root", "/root"),
+                    ("bucket_id", "bucket_id"),
+                ]),
+            ),
+            (
+                "azblob://container/root",
+                Scheme::Azblob,
+                kvs(&[("container", "container"), ("root", "/root")]),
+            ),
+            (
+                "azdfs://filesystem",
+                Scheme::Azdfs,
+                kvs(&[("filesystem", "filesystem")]),
+            ),
+            (
+                "swift://container?region=RegionOne",
+                Scheme::Swift,
+                kvs(&[("container", "container"), ("region", "RegionOne")]),
+            ),
+            ("fs:///tmp/data", Scheme::Fs, kvs(&[("root", "/tmp/data")])),
+            ("memory://", Scheme::Memory, kvs(&[])),
+            (
+                "https://example.com/dir/",
+                Scheme::Http,
+                kvs(&[("endpoint", "https://example.com"), ("root", "/dir/")]),
+            ),
+            (
+                "ipmfs://127.0.0.1:5001/root",
+                Scheme::Ipmfs,
+                kvs(&[("endpoint", "http://127.0.0.1:5001"), ("root", "/root")]),
+            ),
+            (
+                "alluxio://127.0.0.1:39999",
+                Scheme::Alluxio,
+                kvs(&[("endpoint", "http://127.0.0.1:39999")]),
+            ),
+            (
+                "webdav://dav.example.com/root",
+                Scheme::Webdav,
+                kvs(&[("endpoint", "https://dav.example.com"), ("root", "/root")]),
+            ),
+        ];
+        #[cfg(feature = "services-ftp")]
+        cases.push((
+            "ftps://ftp.example.com:2121/root",
+            Scheme::Ftp,
+            kvs(&[
+                ("endpoint", "ftps://ftp.example.com:2121"),
+                ("root", "/root"),
+            ]),
+        ));
+        #[cfg(feature = "services-redis")]
+        cases.push((
+            "redis://127.0.0.1:6379/root?db=1",
+            Scheme::Redis,
+            kvs(&[
+                ("endpoint", "redis://127.0.0.1:6379"),
+                ("root", "/root"),
+                ("db", "1"),
+            ]),
+        ));
+        #[cfg(feature = "services-ipfs")]
+        cases.push((
+            "ipfs://ipfs.io/ipfs/QmPpCt1aYGb9JWJRmXRUnmJtVgeFFTJGzWFYEEX7bo9zGJ",
+            Scheme::Ipfs,
+            kvs(&[
+                ("endpoint", "https://ipfs.io"),
+                (
+                    "root",
+                    "/ipfs/QmPpCt1aYGb9JWJRmXRUnmJtVgeFFTJGzWFYEEX7bo9zGJ",
+                ),
+            ]),
+        ));
+        #[cfg(feature = "services-hdfs")]
+        cases.push((
+            "hdfs://127.0.0.1:9000/root",
+            Scheme::Hdfs,
+            kvs(&[("name_node", "hdfs://127.0.0.1:9000"), ("root", "/root")]),
+        ));
+
+        for (uri, scheme, opts) in cases {
+            let (actual_scheme, actual_opts) = parse_uri(uri).expect("parse must succeed");
+            assert_eq!(actual_scheme, scheme, "{uri}");
+            assert_eq!(actual_opts, opts, "{uri}");
+        }
+    }
+
+    #[test]
+    fn test_from_uri() {
+        use std::env;
+
+        use crate::*;
+
+        let op = Operator::from_uri(
+            "s3://bucket/prefix?region=us-east-1&endpoint=http://127.0.0.1:9000",
+        )
+        .expect("build must succeed");
+        assert_eq!(op.metadata().scheme(), Scheme::S3);
+        assert_eq!(op.metadata().name(), "bucket");
+        assert_eq!(op.metadata().root(), "/prefix/");
+
+        let op = Operator::from_uri("s3://bucket?region=us-east-1&endpoint=http://127.0.0.1:9000")
+            .expect("build must succeed");
+        assert_eq!(op.metadata().root(), "/");
+
+        let dir = env::temp_dir();
+        let op = Operator::from_uri(&format!("fs://{}", dir.to_string_lossy()))
+            .expect("build must succeed");
+        assert_eq!(op.metadata().scheme(), Scheme::Fs);
+
+        let cases = vec![
+            ("unknown://bucket", ErrorKind::ConfigInvalid, None),
+            ("s3:/bucket", ErrorKind::ConfigInvalid, None),
+            (
+                "s3://ak:sk@bucket?region=us-east-1",
+                ErrorKind::ConfigInvalid,
+                Some("userinfo"),
+            ),
+            (
+                "s3://bucket?bucket=other&region=us-east-1",
+                ErrorKind::ConfigInvalid,
+                Some("bucket"),
+            ),
+            (
+                "s3://bucket?regoin=us-east-1",
+                ErrorKind::ConfigInvalid,
+                Some("regoin"),
+            ),
+            ("fs://host/tmp", ErrorKind::ConfigInvalid, Some("authority")),
+            (
+                "s3:///prefix?region=us-east-1",
+                ErrorKind::ConfigInvalid,
+                Some("bucket"),
+            ),
+        ];
+        for (uri, kind, field) in cases {
+            let err = Operator::from_uri(uri).expect_err("build must fail");
+            assert_eq!(err.kind(), kind, "{uri}");
+            assert_eq!(err.context("field"), field, "{uri}");
+            // Uri could contain credentials, it must not be carried by errors.
+            assert!(!format!("{err:?}").contains("ak:sk"), "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_copy_from() {
+        use std::env;
+        use std::sync::atomic::AtomicU64;
+        use std::sync::atomic::Ordering;
+        use std::sync::Arc;
+
+        use rand::RngCore;
+        use rand::SeedableRng;
+        use sha2::Digest;
+        use sha2::Sha256;
+
+        use crate::services::fs;
+        use crate::ErrorKind;
+        use crate::Operator;
+        use crate::Scheme;
+
+        let mut content = vec![0; 100 * 1024 * 1024];
+        rand::rngs::StdRng::seed_from_u64(278).fill_bytes(&mut content);
+        let expected = Sha256::digest(&content);
+
+        let memory = Operator::from_env(Scheme::Memory).expect("build operator");
+        let root = env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let mut builder = fs::Builder::default();
+        builder.root(&root.to_string_lossy());
+        let fs = Operator::new(builder.build().expect("build must succeed"));
+
+        memory
+            .object("src")
+            .write(content)
+            .await
+            .expect("write must succeed");
+
+        let copied = Arc::new(AtomicU64::new(0));
+        let counter = copied.clone();
+        fs.copy_from_with_progress(&memory, "src", "dir/dst", move |n| {
+            counter.store(n, Ordering::Relaxed)
+        })
+        .await
+        .expect("copy must succeed");
+        assert_eq!(copied.load(Ordering::Relaxed), 100 * 1024 * 1024);
+        let bs = std::fs::read(root.join("dir/dst")).expect("read must succeed");
+        assert_eq!(Sha256::digest(&bs), expected);
+
+        memory
+            .copy_from(&fs, "dir/dst", "dst")
+            .await
+            .expect("copy must succeed");
+        let bs = memory
+            .object("dst")
+            .read()
+            .await
+            .expect("read must succeed");
+        assert_eq!(Sha256::digest(&bs), expected);
+
+        let err = memory
+            .copy_from(&fs, "dir/", "dst")
+            .await
+            .expect_err("copy dir must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectIsADirectory);
+
+        std::fs::remove_dir_all(&root).expect("remove root must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_copy_from_multipart() {
+        use std::collections::HashMap;
+        use std::sync::Arc;
+        use std::sync::Mutex;
+
+        use async_trait::async_trait;
+        use futures::AsyncReadExt;
+
+        use super::COPY_MULTIPART_THRESHOLD;
+        use super::COPY_PART_SIZE;
+        use crate::raw::*;
+        use crate::*;
+
+        /// Mock service that serves `source` as a png at any path, and fails
+        /// to upload the part `fail_part`.
+        #[derive(Debug, Default)]
+        struct MockService {
+            source: Vec<u8>,
+            fail_part: Option<usize>,
+            content_type: Mutex<Option<String>>,
+            parts: Mutex<HashMap<usize, Vec<u8>>>,
+            object: Mutex<Vec<u8>>,
+            aborted: Mutex<bool>,
+        }
+
+        #[async_trait]
+        impl Accessor for MockService {
+            fn metadata(&self) -> AccessorMetadata {
+                let mut am = AccessorMetadata::default();
+                am.set_capabilities(
+                    AccessorCapability::Read
+                        | AccessorCapability::Write
+                        | AccessorCapability::Multipart,
+                );
+                am
+            }
+
+            async fn stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+                Ok(RpStat::new(
+                    ObjectMetadata::new(ObjectMode::FILE)
+                        .with_content_length(self.source.len() as u64)
+                        .with_content_type("image/png"),
+                ))
+            }
+
+            async fn read(&self, _: &str, _: OpRead) -> Result<(RpRead, BytesReader)> {
+                Ok((
+                    RpRead::new(self.source.len() as u64),
+                    Box::new(futures::io::Cursor::new(self.source.clone())),
+                ))
+            }
+
+            async fn create_multipart(
+                &self,
+                _: &str,
+                args: OpCreateMultipart,
+            ) -> Result<RpCreateMultipart> {
+                *self.content_type.lock().unwrap() = args.content_type().map(|v| v.to_string());
+                Ok(RpCreateMultipart::new("upload"))
+            }
+
+            async fn write_multipart(
+                &self,
+                _: &str,
+                args: OpWriteMultipart,
+                mut r: BytesReader,
+            ) -> Result<RpWriteMultipart> {
+                if self.fail_part == Some(args.part_number()) {
+                    return Err(Error::new(ErrorKind::Unexpected, "upload part failed"));
+                }
+
+                let mut bs = Vec::new();
+                r.read_to_end(&mut bs).await.map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "read part").set_source(err)
+                })?;
+                assert!(bs.len() <= COPY_PART_SIZE);
+                self.parts.lock().unwrap().insert(args.part_number(), bs);
+                Ok(RpWriteMultipart::new(
+                    args.part_number(),
+                    &args.part_number().to_string(),
+                ))
+            }
+
+            async fn complete_multipart(
+                &self,
+                _: &str,
+                args: OpCompleteMultipart,
+            ) -> Result<RpCompleteMultipart> {
+                let parts = self.parts.lock().unwrap();
+                let mut object = self.object.lock().unwrap();
+                for part in args.parts() {
+                    object.extend_from_slice(&parts[&part.part_number()]);
+                }
+                Ok(RpCompleteMultipart::default())
+            }
+
+            async fn abort_multipart(
+                &self,
+                _: &str,
+                _: OpAbortMultipart,
+            ) -> Result<RpAbortMultipart> {
+                *self.aborted.lock().unwrap() = true;
+                Ok(RpAbortMultipart::default())
+            }
+        }
+
+        let content: Vec<u8> = (0..COPY_MULTIPART_THRESHOLD + 1).map(|v| v as u8).collect();
+        let src = Operator::new(MockService {
+            source: content.clone(),
+            ..Default::default()
+        });
+
+        let acc = Arc::new(MockService::default());
+        let op = Operator::new(acc.clone());
+        op.copy_from(&src, "src", "dst")
+            .await
+            .expect("copy must succeed");
+        assert_eq!(acc.parts.lock().unwrap().len(), 9);
+        assert!(*acc.object.lock().unwrap() == content);
+        assert_eq!(
+            acc.content_type.lock().unwrap().as_deref(),
+            Some("image/png")
+        );
+        assert!(!*acc.aborted.lock().unwrap());
+
+        let acc = Arc::new(MockService {
+            fail_part: Some(2),
+            ..Default::default()
+        });
+        let op = Operator::new(acc.clone());
+        let err = op
+            .copy_from(&src, "src", "dst")
+            .await
+            .expect_err("copy must fail");
+        assert!(err.to_string().contains("upload part failed"));
+        assert!(acc.object.lock().unwrap().is_empty());
+        assert!(*acc.aborted.lock().unwrap());
+    }
 }
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use time::Duration;
+use time::OffsetDateTime;
 
 use crate::raw::*;
 use crate::*;
@@ -41,34 +44,249 @@ impl OpCreate {
 ///
 /// The path must be normalized.
 #[derive(Debug, Clone, Default)]
-pub struct OpDelete {}
+pub struct OpDelete {
+    version: Option<String>,
+}
 
 impl OpDelete {
     /// Create a new `OpDelete`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delete the given version of object instead of the latest one.
+    ///
+    /// Only services with [`AccessorCapability::Versioning`] support this.
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// Get version from option.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+}
+
+/// Args for `copy` operation.
+///
+/// Both source and target path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpCopy {}
+
+impl OpCopy {
+    /// Create a new `OpCopy`.
     pub fn new() -> Self {
         Self {}
     }
 }
 
+/// Args for `rename` operation.
+///
+/// Both source and target path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpRename {}
+
+impl OpRename {
+    /// Create a new `OpRename`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Args for `get_tags` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpGetTags {}
+
+impl OpGetTags {
+    /// Create a new `OpGetTags`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Args for `set_tags` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpSetTags {
+    tags: HashMap<String, String>,
+}
+
+impl OpSetTags {
+    /// Create a new `OpSetTags`.
+    ///
+    /// All existing tags of the object will be replaced by `tags`.
+    pub fn new(tags: HashMap<String, String>) -> Self {
+        Self { tags }
+    }
+
+    /// Get tags from option.
+    pub fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+}
+
+/// Args for `delete_tags` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpDeleteTags {}
+
+impl OpDeleteTags {
+    /// Create a new `OpDeleteTags`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Args for `batch` operation.
+#[derive(Debug, Clone)]
+pub struct OpBatch {
+    ops: BatchOperations,
+}
+
+impl OpBatch {
+    /// Create a new `OpBatch`.
+    pub fn new(ops: BatchOperations) -> Self {
+        Self { ops }
+    }
+
+    /// Get operations from op.
+    pub fn operation(&self) -> &BatchOperations {
+        &self.ops
+    }
+
+    /// Consume OpBatch into BatchOperations.
+    pub fn into_operation(self) -> BatchOperations {
+        self.ops
+    }
+}
+
+/// Batch operations used for batch.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum BatchOperations {
+    /// Batch delete operations.
+    Delete(Vec<(String, OpDelete)>),
+}
+
 /// Args for `list` operation.
 #[derive(Debug, Clone, Default)]
-pub struct OpList {}
+pub struct OpList {
+    limit: Option<usize>,
+    start_after: Option<String>,
+    recursive: bool,
+    include_dirs: bool,
+}
 
 impl OpList {
     /// Create a new `OpList`.
     pub fn new() -> Self {
-        Self {}
+        Self {
+            limit: None,
+            start_after: None,
+            recursive: false,
+            include_dirs: false,
+        }
+    }
+
+    /// Set the max count of objects returned in one page.
+    ///
+    /// This is a hint of page size: services will still return all objects
+    /// by sending more requests while users keep polling the lister.
+    /// `0` means using the default page size of services.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Get the page size limit of list.
+    pub fn limit(&self) -> Option<usize> {
+        self.limit.filter(|v| *v > 0)
+    }
+
+    /// Set the path that list should start after, the path itself will
+    /// not be returned.
+    ///
+    /// The path must be a direct child of the listing dir like
+    /// `path/to/dir/file`, which is usually the
+    /// [`continuation_token`][crate::ObjectLister::continuation_token] of a
+    /// previous lister. Only services with
+    /// [`AccessorCapability::ListStartAfter`] support this.
+    ///
+    /// # Notes
+    ///
+    /// - `s3` and `gcs` will return objects that are lexicographically
+    ///   after `start_after`.
+    /// - `fs` will skip entries until `start_after` has been found, so it
+    ///   must still exist.
+    pub fn with_start_after(mut self, path: &str) -> Self {
+        self.start_after = Some(path.to_string());
+        self
+    }
+
+    /// Get the path that list should start after.
+    pub fn start_after(&self) -> Option<&str> {
+        self.start_after.as_deref()
+    }
+
+    /// Set whether list should return all files under the dir recursively.
+    ///
+    /// Dirs will not be returned while listing recursively. Services with
+    /// [`AccessorCapability::ListRecursive`] will list without delimiter,
+    /// others will be walked dir by dir. Refer to
+    /// [`Object::scan`][crate::Object::scan] for more details.
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Check if list should return all files under the dir recursively.
+    pub fn recursive(&self) -> bool {
+        self.recursive
+    }
+
+    /// Set whether dirs should be returned while listing recursively.
+    ///
+    /// Dirs will be walked one by one even if services have
+    /// [`AccessorCapability::ListRecursive`], since listing without
+    /// delimiter doesn't return dirs. Non-recursive listing always returns
+    /// dirs.
+    pub fn with_include_dirs(mut self, include_dirs: bool) -> Self {
+        self.include_dirs = include_dirs;
+        self
+    }
+
+    /// Check if dirs should be returned while listing recursively.
+    pub fn include_dirs(&self) -> bool {
+        self.include_dirs
     }
 }
 
 /// Args for `create_multipart` operation.
 #[derive(Debug, Clone, Default)]
-pub struct OpCreateMultipart {}
+pub struct OpCreateMultipart {
+    content_type: Option<String>,
+}
 
 impl OpCreateMultipart {
     /// Create a new `OpCreateMultipart`.
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Set the content type of the object that will be completed.
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Get the content type from option
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
     }
 }
 
@@ -152,6 +370,84 @@ impl OpAbortMultipart {
     }
 }
 
+/// Args for `list_multipart` operation.
+///
+/// The path is the prefix of uploads and must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpListMultipart {
+    key_marker: Option<String>,
+    upload_id_marker: Option<String>,
+}
+
+impl OpListMultipart {
+    /// Create a new `OpListMultipart`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Change the key marker, uploads will be listed after this key.
+    pub fn with_key_marker(mut self, key_marker: &str) -> Self {
+        self.key_marker = Some(key_marker.to_string());
+        self
+    }
+
+    /// Get key_marker from option.
+    pub fn key_marker(&self) -> Option<&str> {
+        self.key_marker.as_deref()
+    }
+
+    /// Change the upload id marker, uploads of key marker will be listed
+    /// after this upload id.
+    pub fn with_upload_id_marker(mut self, upload_id_marker: &str) -> Self {
+        self.upload_id_marker = Some(upload_id_marker.to_string());
+        self
+    }
+
+    /// Get upload_id_marker from option.
+    pub fn upload_id_marker(&self) -> Option<&str> {
+        self.upload_id_marker.as_deref()
+    }
+}
+
+/// Args for `list_versions` operation.
+///
+/// The path is the prefix of versions and must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpListVersions {
+    key_marker: Option<String>,
+    version_id_marker: Option<String>,
+}
+
+impl OpListVersions {
+    /// Create a new `OpListVersions`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Change the key marker, versions will be listed after this key.
+    pub fn with_key_marker(mut self, key_marker: &str) -> Self {
+        self.key_marker = Some(key_marker.to_string());
+        self
+    }
+
+    /// Get key_marker from option.
+    pub fn key_marker(&self) -> Option<&str> {
+        self.key_marker.as_deref()
+    }
+
+    /// Change the version id marker, versions of key marker will be listed
+    /// after this version id.
+    pub fn with_version_id_marker(mut self, version_id_marker: &str) -> Self {
+        self.version_id_marker = Some(version_id_marker.to_string());
+        self
+    }
+
+    /// Get version_id_marker from option.
+    pub fn version_id_marker(&self) -> Option<&str> {
+        self.version_id_marker.as_deref()
+    }
+}
+
 /// Args for `presign` operation.
 ///
 /// The path must be normalized.
@@ -225,6 +521,10 @@ impl From<OpWriteMultipart> for PresignOperation {
 pub struct OpRead {
     br: BytesRange,
     total_size_hint: Option<u64>,
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    verify_content_md5: bool,
+    version: Option<String>,
 }
 
 impl OpRead {
@@ -255,16 +555,125 @@ impl OpRead {
     pub fn total_size_hint(&self) -> Option<u64> {
         self.total_size_hint
     }
+
+    /// Set the `If-Match` condition of OpRead.
+    ///
+    /// Read will return [`ErrorKind::ConditionNotMatch`] if object's etag
+    /// doesn't match with given etag. Services without
+    /// [`AccessorCapability::ConditionalRead`] will return
+    /// [`ErrorKind::Unsupported`].
+    ///
+    /// Etag returned by [`ObjectMetadata::etag`] can be used directly, it
+    /// will be quoted before sending to services.
+    pub fn with_if_match(mut self, etag: &str) -> Self {
+        self.if_match = Some(quote_etag(etag));
+        self
+    }
+
+    /// Get `If-Match` condition from OpRead.
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Set the `If-None-Match` condition of OpRead.
+    ///
+    /// Read will return [`ErrorKind::ConditionNotMatch`] if object's etag
+    /// matches with given etag. Refer to [`OpRead::with_if_match`] for the
+    /// supported services.
+    ///
+    /// Etag returned by [`ObjectMetadata::etag`] can be used directly, it
+    /// will be quoted before sending to services.
+    pub fn with_if_none_match(mut self, etag: &str) -> Self {
+        self.if_none_match = Some(quote_etag(etag));
+        self
+    }
+
+    /// Get `If-None-Match` condition from OpRead.
+    pub fn if_none_match(&self) -> Option<&str> {
+        self.if_none_match.as_deref()
+    }
+
+    /// Set whether to verify the content md5 of OpRead.
+    ///
+    /// Read will return [`ErrorKind::ChecksumMismatch`] if the md5 of read
+    /// content doesn't match with [`ObjectMetadata::content_md5`].
+    ///
+    /// # Notes
+    ///
+    /// - Only whole object reads can be verified.
+    /// - Verification will be skipped if services don't report content md5.
+    pub fn with_verify_content_md5(mut self, verify: bool) -> Self {
+        self.verify_content_md5 = verify;
+        self
+    }
+
+    /// Check if content md5 should be verified.
+    pub fn verify_content_md5(&self) -> bool {
+        self.verify_content_md5
+    }
+
+    /// Read the given version of object instead of the latest one.
+    ///
+    /// Only services with [`AccessorCapability::Versioning`] support this.
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// Get version from option.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+}
+
+/// Quote every entity tag in the etag list so that it could be used in
+/// `If-Match` and `If-None-Match` headers.
+///
+/// - `abc` => `"abc"`
+/// - `W/abc` => `W/"abc"`
+/// - `"abc"` and `*` will be kept as is.
+fn quote_etag(etag: &str) -> String {
+    etag.split(',')
+        .map(|v| {
+            let v = v.trim();
+            let (weak, tag) = match v.strip_prefix("W/") {
+                Some(tag) => ("W/", tag),
+                None => ("", v),
+            };
+
+            if tag == "*" || tag.starts_with('"') {
+                v.to_string()
+            } else {
+                format!("{weak}\"{tag}\"")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Args for `stat` operation.
 #[derive(Debug, Clone, Default)]
-pub struct OpStat {}
+pub struct OpStat {
+    version: Option<String>,
+}
 
 impl OpStat {
     /// Create a new `OpStat`.
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Stat the given version of object instead of the latest one.
+    ///
+    /// Only services with [`AccessorCapability::Versioning`] support this.
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// Get version from option.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
     }
 }
 
@@ -273,6 +682,15 @@ impl OpStat {
 pub struct OpWrite {
     size: u64,
     content_type: Option<String>,
+    content_disposition: Option<String>,
+    cache_control: Option<String>,
+    expires: Option<OffsetDateTime>,
+    if_not_exists: bool,
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    min_throughput: Option<(u64, std::time::Duration)>,
+    append: bool,
+    expire: Option<std::time::Duration>,
 }
 
 impl OpWrite {
@@ -283,17 +701,147 @@ impl OpWrite {
         Self {
             size,
             content_type: None,
+            content_disposition: None,
+            cache_control: None,
+            expires: None,
+            if_not_exists: false,
+            if_match: None,
+            if_none_match: None,
+            min_throughput: None,
+            append: false,
+            expire: None,
         }
     }
 
     /// Set the content type of option
+    ///
+    /// Empty content type will be ignored so that services will use their
+    /// default like `application/octet-stream`.
     pub fn with_content_type(self, content_type: &str) -> Self {
+        if content_type.is_empty() {
+            return self;
+        }
         Self {
-            size: self.size(),
             content_type: Some(content_type.to_string()),
+            ..self
         }
     }
 
+    /// Set the `Content-Disposition` header of object.
+    ///
+    /// `Content-Disposition` tells browsers how to present the object,
+    /// for example `attachment; filename="report.pdf"`, and will be
+    /// returned by [`ObjectMetadata::content_disposition`] while `stat`.
+    /// Services that don't support `Content-Disposition` will ignore this
+    /// option.
+    pub fn with_content_disposition(mut self, content_disposition: &str) -> Self {
+        self.content_disposition = Some(content_disposition.to_string());
+        self
+    }
+
+    /// Set the `Cache-Control` header of object.
+    ///
+    /// `Cache-Control` will be sent to CDN and browsers while reading the
+    /// object, for example `public, max-age=3600`, and will be returned by
+    /// [`ObjectMetadata::cache_control`] while `stat`. Services that don't
+    /// support `Cache-Control` will ignore this option.
+    pub fn with_cache_control(mut self, cache_control: &str) -> Self {
+        self.cache_control = Some(cache_control.to_string());
+        self
+    }
+
+    /// Set the `Expires` header of object.
+    ///
+    /// `Expires` is an HTTP caching hint for CDN and browsers which will be
+    /// returned by [`ObjectMetadata::expires`] while `stat`, it doesn't mean
+    /// the object will be deleted after that time. Services that don't
+    /// support `Expires` will ignore this option.
+    pub fn with_expires(mut self, expires: OffsetDateTime) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    /// Set whether the write should only succeed if object doesn't exist.
+    ///
+    /// Write will return [`ErrorKind::ConditionNotMatch`] if object already
+    /// exists. Only services with [`AccessorCapability::ConditionalWrite`]
+    /// respect this option.
+    pub fn with_if_not_exists(mut self, if_not_exists: bool) -> Self {
+        self.if_not_exists = if_not_exists;
+        self
+    }
+
+    /// Set the `If-Match` condition of write.
+    ///
+    /// Write will return [`ErrorKind::ConditionNotMatch`] if the etag of
+    /// existing object doesn't match, which could be used to implement
+    /// optimistic concurrency. Only services with
+    /// [`AccessorCapability::ConditionalWrite`] respect this option, and
+    /// services that can't check etag like `fs` will return
+    /// [`ErrorKind::Unsupported`].
+    pub fn with_if_match(mut self, etag: &str) -> Self {
+        self.if_match = Some(etag.to_string());
+        self
+    }
+
+    /// Set the `If-None-Match` condition of write.
+    ///
+    /// `*` means the write should only succeed if object doesn't exist,
+    /// which is the same as [`OpWrite::with_if_not_exists`]. Refer to
+    /// [`OpWrite::with_if_match`] for the supported services.
+    pub fn with_if_none_match(mut self, etag: &str) -> Self {
+        self.if_none_match = Some(etag.to_string());
+        self
+    }
+
+    /// Set the minimum throughput of write in bytes per second.
+    ///
+    /// Write will be aborted with a temporary error if the average
+    /// throughput within the latest `duration` stays below
+    /// `bytes_per_sec`, so that stalled connections could be detected
+    /// before the total timeout. Throughput is measured on the bytes
+    /// consumed from the request body.
+    ///
+    /// Only the async write operations in [`Object`] respect this option.
+    pub fn with_min_throughput(
+        mut self,
+        bytes_per_sec: u64,
+        duration: std::time::Duration,
+    ) -> Self {
+        self.min_throughput = Some((bytes_per_sec, duration));
+        self
+    }
+
+    /// Set whether the write should append to the end of existing object.
+    ///
+    /// Object will be created if it doesn't exist. Only services with
+    /// [`AccessorCapability::Append`] respect this option, others will
+    /// return [`ErrorKind::Unsupported`] instead of overwriting the
+    /// existing object.
+    pub fn with_append(mut self, append: bool) -> Self {
+        self.append = append;
+        self
+    }
+
+    /// Set the time to live of object.
+    ///
+    /// Unlike [`OpWrite::with_expires`], object will be removed by service
+    /// after `expire` and reads will return [`ErrorKind::ObjectNotFound`].
+    /// Object will be kept persistent if not set. Only services with
+    /// [`AccessorCapability::Expire`] respect this option, others will
+    /// return [`ErrorKind::Unsupported`].
+    pub fn with_expire(mut self, expire: std::time::Duration) -> Self {
+        self.expire = Some(expire);
+        self
+    }
+
+    /// Set the size of option, used by layers that change the content.
+    #[cfg(feature = "layers-compression")]
+    pub(crate) fn with_size(mut self, size: u64) -> Self {
+        self.size = size;
+        self
+    }
+
     /// Get size from option.
     pub fn size(&self) -> u64 {
         self.size
@@ -302,4 +850,56 @@ impl OpWrite {
     pub fn content_type(&self) -> Option<&str> {
         self.content_type.as_deref()
     }
+
+    /// Get the content disposition from option.
+    pub fn content_disposition(&self) -> Option<&str> {
+        self.content_disposition.as_deref()
+    }
+
+    /// Get the cache control from option.
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    /// Get the expires from option.
+    pub fn expires(&self) -> Option<OffsetDateTime> {
+        self.expires
+    }
+
+    /// Get the time to live from option.
+    pub fn expire(&self) -> Option<std::time::Duration> {
+        self.expire
+    }
+
+    /// Check if the write should only succeed if object doesn't exist.
+    pub fn if_not_exists(&self) -> bool {
+        self.if_not_exists || self.if_none_match.as_deref() == Some("*")
+    }
+
+    /// Get the `If-Match` condition from option.
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Get the `If-None-Match` condition from option.
+    ///
+    /// Returns `*` if the write should only succeed if object doesn't exist.
+    pub fn if_none_match(&self) -> Option<&str> {
+        match self.if_none_match.as_deref() {
+            Some(v) => Some(v),
+            None if self.if_not_exists => Some("*"),
+            None => None,
+        }
+    }
+
+    /// Get the minimum throughput in bytes per second and the duration
+    /// it's measured in from option.
+    pub fn min_throughput(&self) -> Option<(u64, std::time::Duration)> {
+        self.min_throughput
+    }
+
+    /// Check if the write should append to the end of existing object.
+    pub fn append(&self) -> bool {
+        self.append
+    }
 }
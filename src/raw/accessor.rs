@@ -44,6 +44,12 @@ use crate::*;
 /// | [`write_multipart`][Accessor::write_multipart] | `Multipart` |
 /// | [`complete_multipart`][Accessor::complete_multipart] | `Multipart` |
 /// | [`abort_multipart`][Accessor::abort_multipart] | `Multipart` |
+/// | [`list_multipart`][Accessor::list_multipart] | `Multipart` |
+/// | [`list_versions`][Accessor::list_versions] | `Versioning` |
+/// | [`batch`][Accessor::batch] | `Batch` |
+/// | [`get_tags`][Accessor::get_tags] | `Tagging` |
+/// | [`set_tags`][Accessor::set_tags] | `Tagging` |
+/// | [`delete_tags`][Accessor::delete_tags] | `Tagging` |
 /// | [`blocking_create`][Accessor::blocking_create] | `Blocking` |
 /// | [`blocking_read`][Accessor::blocking_read] | `Blocking` |
 /// | [`blocking_write`][Accessor::blocking_write] | `Blocking` |
@@ -61,7 +67,7 @@ use crate::*;
 ///   - Use `unimplemented!()` if not implemented or can't implement.
 /// - Operations with capability requirement like `presign` are optional operations.
 ///   - Services can implement them based on services capabilities.
-///   - The default implementation should return [`std::io::ErrorKind::Unsupported`].
+///   - The default implementation should return [`ErrorKind::Unsupported`].
 #[async_trait]
 pub trait Accessor: Send + Sync + Debug + 'static {
     /// Return the inner accessor if there is one.
@@ -94,10 +100,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
         match self.inner() {
             Some(inner) => inner.create(path, args).await,
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::Create)),
         }
     }
 
@@ -111,10 +114,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
         match self.inner() {
             Some(inner) => inner.read(path, args).await,
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::Read)),
         }
     }
 
@@ -127,10 +127,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
         match self.inner() {
             Some(inner) => inner.write(path, args, r).await,
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::Write)),
         }
     }
 
@@ -144,10 +141,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         match self.inner() {
             Some(inner) => inner.stat(path, args).await,
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::Stat)),
         }
     }
 
@@ -160,10 +154,41 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         match self.inner() {
             Some(inner) => inner.delete(path, args).await,
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::Delete)),
+        }
+    }
+
+    /// Invoke the `copy` operation from the specified path to another path
+    /// in the same service.
+    ///
+    /// # Behavior
+    ///
+    /// - Require capability: `Copy`
+    /// - Both `from` and `to` MUST be file paths, DON'T NEED to check object mode.
+    /// - Copy SHOULD be done inside service without transferring the content.
+    /// - Copy SHOULD return `ObjectNotFound` if `from` doesn't exist.
+    /// - Copy SHOULD overwrite `to` if it already exists.
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        match self.inner() {
+            Some(inner) => inner.copy(from, to, args).await,
+            None => Err(new_unsupported_error(self, Operation::Copy)),
+        }
+    }
+
+    /// Invoke the `rename` operation from the specified path to another
+    /// path in the same service.
+    ///
+    /// # Behavior
+    ///
+    /// - Require capability: `Rename`
+    /// - Both `from` and `to` MUST be file paths, DON'T NEED to check object mode.
+    /// - Rename SHOULD be done inside service without transferring the content.
+    /// - Rename SHOULD return `ObjectNotFound` if `from` doesn't exist.
+    /// - Rename SHOULD overwrite `to` if it already exists.
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        match self.inner() {
+            Some(inner) => inner.rename(from, to, args).await,
+            None => Err(new_unsupported_error(self, Operation::Rename)),
         }
     }
 
@@ -173,13 +198,12 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     ///
     /// - Input path MUST be dir path, DON'T NEED to check object mode.
     /// - List non-exist dir should return Empty.
+    /// - With `recursive`, all files under the dir SHOULD be returned
+    ///   without dirs, only required for services with `ListRecursive`.
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         match self.inner() {
             Some(inner) => inner.list(path, args).await,
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::List)),
         }
     }
 
@@ -192,10 +216,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         match self.inner() {
             Some(inner) => inner.presign(path, args),
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::Presign)),
         }
     }
 
@@ -212,10 +233,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     ) -> Result<RpCreateMultipart> {
         match self.inner() {
             Some(inner) => inner.create_multipart(path, args).await,
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::CreateMultipart)),
         }
     }
 
@@ -232,10 +250,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     ) -> Result<RpWriteMultipart> {
         match self.inner() {
             Some(inner) => inner.write_multipart(path, args, r).await,
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::WriteMultipart)),
         }
     }
 
@@ -251,10 +266,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     ) -> Result<RpCompleteMultipart> {
         match self.inner() {
             Some(inner) => inner.complete_multipart(path, args).await,
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::CompleteMultipart)),
         }
     }
 
@@ -270,10 +282,95 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     ) -> Result<RpAbortMultipart> {
         match self.inner() {
             Some(inner) => inner.abort_multipart(path, args).await,
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::AbortMultipart)),
+        }
+    }
+
+    /// Invoke the `list_multipart` operation on the specified prefix.
+    ///
+    /// # Behavior
+    ///
+    /// - Require capability: `Multipart`
+    /// - Only one page of uploads SHOULD be returned, callers will continue
+    ///   with the next markers in reply.
+    /// - Paths of returned uploads MUST be relative to root.
+    async fn list_multipart(&self, path: &str, args: OpListMultipart) -> Result<RpListMultipart> {
+        match self.inner() {
+            Some(inner) => inner.list_multipart(path, args).await,
+            None => Err(new_unsupported_error(self, Operation::ListMultipart)),
+        }
+    }
+
+    /// Invoke the `list_versions` operation on the specified prefix.
+    ///
+    /// # Behavior
+    ///
+    /// - Require capability: `Versioning`
+    /// - Only one page of versions SHOULD be returned, callers will continue
+    ///   with the next markers in reply.
+    /// - Versions of the same object SHOULD be returned from newest to oldest,
+    ///   delete markers included.
+    /// - Paths of returned versions MUST be relative to root.
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        match self.inner() {
+            Some(inner) => inner.list_versions(path, args).await,
+            None => Err(new_unsupported_error(self, Operation::ListVersions)),
+        }
+    }
+
+    /// Invoke the `get_tags` operation on the specified path.
+    ///
+    /// # Behavior
+    ///
+    /// - Require capability: `Tagging`
+    /// - Input path MUST be file path, DON'T NEED to check object mode.
+    /// - Object without tags SHOULD return an empty tag set.
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        match self.inner() {
+            Some(inner) => inner.get_tags(path, args).await,
+            None => Err(new_unsupported_error(self, Operation::GetTags)),
+        }
+    }
+
+    /// Invoke the `set_tags` operation on the specified path.
+    ///
+    /// # Behavior
+    ///
+    /// - Require capability: `Tagging`
+    /// - Input path MUST be file path, DON'T NEED to check object mode.
+    /// - All existing tags SHOULD be replaced by the given tags.
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        match self.inner() {
+            Some(inner) => inner.set_tags(path, args).await,
+            None => Err(new_unsupported_error(self, Operation::SetTags)),
+        }
+    }
+
+    /// Invoke the `delete_tags` operation on the specified path.
+    ///
+    /// # Behavior
+    ///
+    /// - Require capability: `Tagging`
+    /// - Input path MUST be file path, DON'T NEED to check object mode.
+    /// - All tags of the object SHOULD be removed.
+    async fn delete_tags(&self, path: &str, args: OpDeleteTags) -> Result<RpDeleteTags> {
+        match self.inner() {
+            Some(inner) => inner.delete_tags(path, args).await,
+            None => Err(new_unsupported_error(self, Operation::DeleteTags)),
+        }
+    }
+
+    /// Invoke the `batch` operations.
+    ///
+    /// # Behavior
+    ///
+    /// - Require capability: `Batch`
+    /// - Every input operation MUST have its own result, services SHOULD NOT
+    ///   return an error if only some of the operations failed.
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        match self.inner() {
+            Some(inner) => inner.batch(args).await,
+            None => Err(new_unsupported_error(self, Operation::Batch)),
         }
     }
 
@@ -287,10 +384,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
         match self.inner() {
             Some(inner) => inner.blocking_create(path, args),
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::BlockingCreate)),
         }
     }
 
@@ -304,10 +398,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, BlockingBytesReader)> {
         match self.inner() {
             Some(inner) => inner.blocking_read(path, args),
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::BlockingRead)),
         }
     }
 
@@ -321,10 +412,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     fn blocking_write(&self, path: &str, args: OpWrite, r: BlockingBytesReader) -> Result<RpWrite> {
         match self.inner() {
             Some(inner) => inner.blocking_write(path, args, r),
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::BlockingWrite)),
         }
     }
 
@@ -338,10 +426,7 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         match self.inner() {
             Some(inner) => inner.blocking_stat(path, args),
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::BlockingStat)),
         }
     }
 
@@ -355,10 +440,22 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         match self.inner() {
             Some(inner) => inner.blocking_delete(path, args),
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::BlockingDelete)),
+        }
+    }
+
+    /// Invoke the `blocking_copy` operation from the specified path to
+    /// another path in the same service.
+    ///
+    /// This operation is the blocking version of [`Accessor::copy`]
+    ///
+    /// # Behavior
+    ///
+    /// - Require capability: `Copy` and `Blocking`
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        match self.inner() {
+            Some(inner) => inner.blocking_copy(from, to, args),
+            None => Err(new_unsupported_error(self, Operation::BlockingCopy)),
         }
     }
 
@@ -373,14 +470,18 @@ pub trait Accessor: Send + Sync + Debug + 'static {
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
         match self.inner() {
             Some(inner) => inner.blocking_list(path, args),
-            None => Err(Error::new(
-                ErrorKind::Unsupported,
-                "operation is not supported",
-            )),
+            None => Err(new_unsupported_error(self, Operation::BlockingList)),
         }
     }
 }
 
+/// Build the error returned by operations that accessor can't perform.
+fn new_unsupported_error<A: Accessor + ?Sized>(acc: &A, op: Operation) -> Error {
+    Error::new(ErrorKind::Unsupported, "operation is not supported")
+        .with_operation(op.into_static())
+        .with_context("service", acc.metadata().scheme().into_static())
+}
+
 /// All functions in `Accessor` only requires `&self`, so it's safe to implement
 /// `Accessor` for `Arc<dyn Accessor>`.
 #[async_trait]
@@ -404,6 +505,12 @@ impl<T: Accessor> Accessor for Arc<T> {
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.as_ref().delete(path, args).await
     }
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.as_ref().copy(from, to, args).await
+    }
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.as_ref().rename(from, to, args).await
+    }
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         self.as_ref().list(path, args).await
     }
@@ -441,6 +548,24 @@ impl<T: Accessor> Accessor for Arc<T> {
     ) -> Result<RpAbortMultipart> {
         self.as_ref().abort_multipart(path, args).await
     }
+    async fn list_multipart(&self, path: &str, args: OpListMultipart) -> Result<RpListMultipart> {
+        self.as_ref().list_multipart(path, args).await
+    }
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        self.as_ref().list_versions(path, args).await
+    }
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.as_ref().batch(args).await
+    }
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.as_ref().get_tags(path, args).await
+    }
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        self.as_ref().set_tags(path, args).await
+    }
+    async fn delete_tags(&self, path: &str, args: OpDeleteTags) -> Result<RpDeleteTags> {
+        self.as_ref().delete_tags(path, args).await
+    }
 
     fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
         self.as_ref().blocking_create(path, args)
@@ -457,6 +582,9 @@ impl<T: Accessor> Accessor for Arc<T> {
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.as_ref().blocking_delete(path, args)
     }
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.as_ref().blocking_copy(from, to, args)
+    }
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
         self.as_ref().blocking_list(path, args)
     }
@@ -542,5 +670,31 @@ flags! {
         Multipart,
         /// Add this capability if service supports `blocking`
         Blocking,
+        /// Add this capability if service supports `batch`
+        Batch,
+        /// Add this capability if service supports `write` with `if_not_exists`,
+        /// `if_match` or `if_none_match`
+        ConditionalWrite,
+        /// Add this capability if service supports `list` with `start_after`
+        ListStartAfter,
+        /// Add this capability if service supports `list` with `recursive`
+        ListRecursive,
+        /// Add this capability if service supports `copy` inside service
+        Copy,
+        /// Add this capability if service supports `get_tags`, `set_tags`
+        /// and `delete_tags`
+        Tagging,
+        /// Add this capability if service supports `list_versions` and
+        /// `read`, `stat`, `delete` with `version`
+        Versioning,
+        /// Add this capability if service supports `write` with `append`
+        Append,
+        /// Add this capability if service supports `rename` inside service
+        Rename,
+        /// Add this capability if service supports `write` with `expire`
+        Expire,
+        /// Add this capability if service supports `read` with `if_match`
+        /// or `if_none_match`
+        ConditionalRead,
     }
 }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::fmt::Debug;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use flagset::FlagSet;
@@ -61,6 +62,30 @@ pub trait Adapter: Send + Sync + Debug + Clone + 'static {
         .with_operation("kv::Adapter::blocking_set"))
     }
 
+    /// Set a key into service which will be removed after `expire`.
+    ///
+    /// Services that implement this should set `Expire` capability.
+    async fn set_with_expire(&self, path: &str, value: &[u8], expire: Duration) -> Result<()> {
+        let _ = (path, value, expire);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::set_with_expire"))
+    }
+
+    /// The blocking version of set_with_expire.
+    fn blocking_set_with_expire(&self, path: &str, value: &[u8], expire: Duration) -> Result<()> {
+        let _ = (path, value, expire);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::blocking_set_with_expire"))
+    }
+
     /// Delete a key from service.
     ///
     /// - return `Ok(())` even if this key is not exist.
@@ -78,6 +103,30 @@ pub trait Adapter: Send + Sync + Debug + Clone + 'static {
         )
         .with_operation("kv::Adapter::blocking_delete"))
     }
+
+    /// Scan all keys that start with `path` from service.
+    ///
+    /// Services that implement scan should set `List` capability.
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        let _ = path;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::scan"))
+    }
+
+    /// The blocking version of scan.
+    fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
+        let _ = path;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::blocking_scan"))
+    }
 }
 
 /// Metadata for this key value accessor.
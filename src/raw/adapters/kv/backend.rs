@@ -12,9 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use futures::io::Cursor;
 use futures::AsyncReadExt;
+use parking_lot::Mutex;
+use time::OffsetDateTime;
+use uuid::Uuid;
 
 use super::Adapter;
 use crate::raw::*;
@@ -25,6 +33,19 @@ use crate::*;
 pub struct Backend<S: Adapter> {
     kv: S,
     root: String,
+    /// Parts of in-progress multipart uploads, keyed by upload id.
+    ///
+    /// Parts are kept in process memory and will be set into kv while
+    /// completing, so only adapters that declare `Multipart` use them.
+    uploads: Arc<Mutex<HashMap<String, Upload>>>,
+}
+
+/// In-progress multipart upload.
+#[derive(Debug)]
+struct Upload {
+    path: String,
+    initiated: OffsetDateTime,
+    parts: BTreeMap<usize, Vec<u8>>,
 }
 
 impl<S> Backend<S>
@@ -36,6 +57,7 @@ where
         Self {
             kv,
             root: "/".to_string(),
+            uploads: Arc::default(),
         }
     }
 
@@ -58,18 +80,16 @@ where
         am
     }
 
-    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        if args.mode() == ObjectMode::FILE {
-            self.kv.set(path, &[]).await?;
-        }
+    /// Dirs are stored as empty keys ending with `/` so that they can be
+    /// listed even when they are empty.
+    async fn create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
+        self.kv.set(path, &[]).await?;
 
         Ok(RpCreate::default())
     }
 
-    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        if args.mode() == ObjectMode::FILE {
-            self.kv.blocking_set(path, &[])?;
-        }
+    fn blocking_create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
+        self.kv.blocking_set(path, &[])?;
 
         Ok(RpCreate::default())
     }
@@ -115,7 +135,10 @@ where
             .await
             .map_err(|err| Error::new(ErrorKind::Unexpected, "read from source").set_source(err))?;
 
-        self.kv.set(path, &bs).await?;
+        match args.expire() {
+            Some(expire) => self.kv.set_with_expire(path, &bs, expire).await?,
+            None => self.kv.set(path, &bs).await?,
+        }
 
         Ok(RpWrite::new(args.size()))
     }
@@ -130,7 +153,10 @@ where
         r.read_to_end(&mut bs)
             .map_err(|err| Error::new(ErrorKind::Unexpected, "read from source").set_source(err))?;
 
-        self.kv.blocking_set(path, &bs)?;
+        match args.expire() {
+            Some(expire) => self.kv.blocking_set_with_expire(path, &bs, expire)?,
+            None => self.kv.blocking_set(path, &bs)?,
+        }
 
         Ok(RpWrite::new(args.size()))
     }
@@ -178,12 +204,145 @@ where
         self.kv.blocking_delete(path)?;
         Ok(RpDelete::default())
     }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
+        let keys = self.kv.scan(scan_prefix(path)).await?;
+        let entries = build_entries(path, keys, args.recursive());
+
+        Ok((RpList::default(), Box::new(KvPager::new(entries))))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
+        let keys = self.kv.blocking_scan(scan_prefix(path))?;
+        let entries = build_entries(path, keys, args.recursive());
+
+        Ok((RpList::default(), Box::new(KvPager::new(entries))))
+    }
+
+    async fn create_multipart(
+        &self,
+        path: &str,
+        _: OpCreateMultipart,
+    ) -> Result<RpCreateMultipart> {
+        self.check_multipart(Operation::CreateMultipart)?;
+
+        let upload_id = Uuid::new_v4().to_string();
+        self.uploads.lock().insert(
+            upload_id.clone(),
+            Upload {
+                path: path.to_string(),
+                initiated: OffsetDateTime::now_utc(),
+                parts: BTreeMap::default(),
+            },
+        );
+
+        Ok(RpCreateMultipart::new(&upload_id))
+    }
+
+    async fn write_multipart(
+        &self,
+        _: &str,
+        args: OpWriteMultipart,
+        mut r: BytesReader,
+    ) -> Result<RpWriteMultipart> {
+        self.check_multipart(Operation::WriteMultipart)?;
+
+        let mut bs = Vec::with_capacity(args.size() as usize);
+        r.read_to_end(&mut bs)
+            .await
+            .map_err(|err| Error::new(ErrorKind::Unexpected, "read from source").set_source(err))?;
+
+        match self.uploads.lock().get_mut(args.upload_id()) {
+            Some(upload) => upload.parts.insert(args.part_number(), bs),
+            None => return Err(upload_not_found(args.upload_id())),
+        };
+
+        // Part number is unique within an upload, use it as etag directly.
+        Ok(RpWriteMultipart::new(
+            args.part_number(),
+            &args.part_number().to_string(),
+        ))
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        self.check_multipart(Operation::CompleteMultipart)?;
+
+        let mut bs = Vec::new();
+        {
+            let uploads = self.uploads.lock();
+            let upload = uploads
+                .get(args.upload_id())
+                .ok_or_else(|| upload_not_found(args.upload_id()))?;
+            for part in args.parts() {
+                let part_bs = upload.parts.get(&part.part_number()).ok_or_else(|| {
+                    Error::new(ErrorKind::Unexpected, "part is not uploaded")
+                        .with_context("upload_id", args.upload_id())
+                        .with_context("part_number", part.part_number().to_string())
+                })?;
+                bs.extend_from_slice(part_bs);
+            }
+        }
+
+        self.kv.set(path, &bs).await?;
+        self.uploads.lock().remove(args.upload_id());
+
+        Ok(RpCompleteMultipart::default())
+    }
+
+    async fn abort_multipart(&self, _: &str, args: OpAbortMultipart) -> Result<RpAbortMultipart> {
+        self.check_multipart(Operation::AbortMultipart)?;
+
+        self.uploads.lock().remove(args.upload_id());
+
+        Ok(RpAbortMultipart::default())
+    }
+
+    /// All uploads will be returned in one page.
+    async fn list_multipart(&self, path: &str, _: OpListMultipart) -> Result<RpListMultipart> {
+        self.check_multipart(Operation::ListMultipart)?;
+
+        let prefix = scan_prefix(path);
+        let mut uploads: Vec<_> = self
+            .uploads
+            .lock()
+            .iter()
+            .filter(|(_, upload)| upload.path.starts_with(prefix))
+            .map(|(upload_id, upload)| {
+                ObjectUpload::new(&upload.path, upload_id).with_initiated(upload.initiated)
+            })
+            .collect();
+        uploads.sort_by(|a, b| (a.path(), a.upload_id()).cmp(&(b.path(), b.upload_id())));
+
+        Ok(RpListMultipart::new(uploads))
+    }
 }
 
 impl<S> Backend<S>
 where
     S: Adapter,
 {
+    fn check_multipart(&self, op: Operation) -> Result<()> {
+        if self
+            .kv
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Multipart)
+        {
+            return Ok(());
+        }
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support multipart",
+        )
+        .with_operation(op.into_static())
+        .with_context("service", self.kv.metadata().scheme().into_static()))
+    }
+
     fn apply_range(&self, mut bs: Vec<u8>, br: BytesRange) -> Vec<u8> {
         match (br.offset(), br.size()) {
             (Some(offset), Some(size)) => {
@@ -199,3 +358,77 @@ where
         }
     }
 }
+
+fn upload_not_found(upload_id: &str) -> Error {
+    Error::new(ErrorKind::ObjectNotFound, "multipart upload is not found")
+        .with_context("upload_id", upload_id)
+}
+
+/// The root dir `/` is stored without leading slash.
+fn scan_prefix(path: &str) -> &str {
+    if path == "/" {
+        ""
+    } else {
+        path
+    }
+}
+
+/// Build entries of dir `path` from scanned keys.
+///
+/// Dirs are built from both dir keys and the parents of keys. Only files
+/// will be returned if `recursive` is set.
+fn build_entries(path: &str, keys: Vec<String>, recursive: bool) -> Vec<ObjectEntry> {
+    let prefix = scan_prefix(path);
+
+    let mut dirs = BTreeSet::new();
+    let mut entries = Vec::with_capacity(keys.len());
+    for key in keys {
+        let rel = match key.strip_prefix(prefix) {
+            Some(rel) if !rel.is_empty() => rel,
+            _ => continue,
+        };
+
+        match rel.find('/') {
+            Some(idx) if !recursive => {
+                dirs.insert(format!("{}{}", prefix, &rel[..=idx]));
+            }
+            _ if key.ends_with('/') => continue,
+            _ => entries.push(ObjectEntry::new(
+                &key,
+                ObjectMetadata::new(ObjectMode::FILE),
+            )),
+        }
+    }
+
+    entries.extend(
+        dirs.into_iter()
+            .map(|dir| ObjectEntry::new(&dir, ObjectMetadata::new(ObjectMode::DIR))),
+    );
+    entries
+}
+
+/// KvPager returns all entries in one page.
+struct KvPager {
+    entries: Option<Vec<ObjectEntry>>,
+}
+
+impl KvPager {
+    fn new(entries: Vec<ObjectEntry>) -> Self {
+        Self {
+            entries: Some(entries).filter(|v| !v.is_empty()),
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectPage for KvPager {
+    async fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
+        Ok(self.entries.take())
+    }
+}
+
+impl BlockingObjectPage for KvPager {
+    fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
+        Ok(self.entries.take())
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use time::format_description::well_known::Rfc2822;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use time::UtcOffset;
+
+/// Parse datetime from rfc2822 string like `Sun, 06 Nov 1994 08:49:37 GMT`,
+/// which is used by http headers like `Last-Modified`.
+///
+/// Returned datetime will be converted into UTC. `None` will be returned if
+/// input is not a valid rfc2822 datetime.
+pub fn parse_datetime_from_rfc2822(s: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(s.trim(), &Rfc2822)
+        .ok()
+        .map(|v| v.to_offset(UtcOffset::UTC))
+}
+
+/// Parse datetime from rfc3339 string like `2022-08-15T11:33:34.866Z`,
+/// which is used by services like gcs in json responses.
+///
+/// Returned datetime will be converted into UTC. `None` will be returned if
+/// input is not a valid rfc3339 datetime.
+pub fn parse_datetime_from_rfc3339(s: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(s.trim(), &Rfc3339)
+        .ok()
+        .map(|v| v.to_offset(UtcOffset::UTC))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_datetime_from_rfc2822() {
+        let expected = OffsetDateTime::from_unix_timestamp(784111777).unwrap();
+
+        let cases = vec![
+            ("http date", "Sun, 06 Nov 1994 08:49:37 GMT", Some(expected)),
+            (
+                "utc offset",
+                "Sun, 06 Nov 1994 08:49:37 +0000",
+                Some(expected),
+            ),
+            (
+                "other offset",
+                "Sun, 06 Nov 1994 16:49:37 +0800",
+                Some(expected),
+            ),
+            ("rfc3339", "1994-11-06T08:49:37Z", None),
+            ("invalid", "0", None),
+            ("empty", "", None),
+        ];
+
+        for (name, input, expected) in cases {
+            let actual = parse_datetime_from_rfc2822(input);
+            assert_eq!(actual, expected, "{name}");
+            if let Some(v) = actual {
+                assert_eq!(v.offset(), UtcOffset::UTC, "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_datetime_from_rfc3339() {
+        let expected = OffsetDateTime::from_unix_timestamp(1660563214).unwrap()
+            + time::Duration::milliseconds(866);
+
+        let cases = vec![
+            ("utc", "2022-08-15T11:33:34.866Z", Some(expected)),
+            (
+                "other offset",
+                "2022-08-15T19:33:34.866+08:00",
+                Some(expected),
+            ),
+            ("http date", "Mon, 15 Aug 2022 11:33:34 GMT", None),
+            ("invalid", "2022-08-15", None),
+            ("empty", "", None),
+        ];
+
+        for (name, input, expected) in cases {
+            let actual = parse_datetime_from_rfc3339(input);
+            assert_eq!(actual, expected, "{name}");
+            if let Some(v) = actual {
+                assert_eq!(v.offset(), UtcOffset::UTC, "{name}");
+            }
+        }
+    }
+}
@@ -114,6 +114,9 @@ impl From<AsyncBody> for reqwest::Body {
 /// Client SHOULD NEVER construct this body.
 pub struct IncomingAsyncBody(BytesReader);
 
+/// Max size of response body that will be read while parsing errors.
+pub(crate) const ERROR_BODY_LIMIT: usize = 64 * 1024;
+
 impl IncomingAsyncBody {
     /// Construct a new incoming async body
     pub fn new(r: BytesReader) -> Self {
@@ -146,6 +149,30 @@ impl IncomingAsyncBody {
         Ok(Bytes::from(w))
     }
 
+    /// Consume at most `max` bytes of the response, returns the bytes and
+    /// whether they have been truncated.
+    ///
+    /// This is used while parsing errors so that a broken server can't make
+    /// us buffer a huge body in memory. The rest of the response will be
+    /// dropped without reading.
+    pub(crate) async fn bytes_with_limit(self, max: usize) -> Result<(Bytes, bool)> {
+        use futures::io;
+        use futures::AsyncReadExt;
+
+        let mut w = Vec::with_capacity(min(max, 1024));
+        io::copy(self.0.take(max as u64 + 1), &mut w)
+            .await
+            .map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "copy from resposne")
+                    .with_operation("http_util::IncomingAsyncBody::bytes_with_limit")
+                    .set_source(err)
+            })?;
+
+        let truncated = w.len() > max;
+        w.truncate(max);
+        Ok((Bytes::from(w), truncated))
+    }
+
     /// Consume the response to build a reader.
     pub fn reader(self) -> BytesReader {
         self.0
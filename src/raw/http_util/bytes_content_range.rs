@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt::Display;
+use std::fmt::Formatter;
 use std::ops::Range;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
@@ -35,7 +37,8 @@ use crate::Result;
 ///
 /// ## Usage of the default.
 ///
-/// `BytesContentRange::default` is not a valid content range.
+/// `BytesContentRange::default` is not a valid content range, it will be
+/// rendered as `bytes */*` which means both range and size are unknown.
 /// Please make sure their comes up with `with_range` or `with_size` call.
 ///
 /// ## Allow clippy::len_without_is_empty
@@ -118,8 +121,30 @@ impl BytesContentRange {
     pub fn to_bytes_range(self) -> Option<BytesRange> {
         match (self.0, self.1, self.2) {
             (Some(start), Some(end), _) => Some(BytesRange::from(start..=end)),
-            (None, None, Some(_)) => None,
-            _ => unreachable!("invalid bytes range: {:?}", self),
+            _ => None,
+        }
+    }
+
+    /// Convert bytes content range into Content-Range header value.
+    ///
+    /// The returning value is the exact inverse of `from_str`:
+    ///
+    /// - `bytes <range-start>-<range-end>/<size>`
+    /// - `bytes <range-start>-<range-end>/*`
+    /// - `bytes */<size>`
+    /// - `bytes */*` if neither range nor size is known
+    pub fn header_value(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for BytesContentRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.0, self.1, self.2) {
+            (Some(start), Some(end), Some(size)) => write!(f, "bytes {start}-{end}/{size}"),
+            (Some(start), Some(end), None) => write!(f, "bytes {start}-{end}/*"),
+            (_, _, Some(size)) => write!(f, "bytes */{size}"),
+            _ => write!(f, "bytes */*"),
         }
     }
 }
@@ -141,6 +166,9 @@ impl FromStr for BytesContentRange {
                 .set_source(e)
         };
 
+        if s == "*/*" {
+            return Ok(BytesContentRange::default());
+        }
         if let Some(size) = s.strip_prefix("*/") {
             return Ok(
                 BytesContentRange::default().with_size(size.parse().map_err(parse_int_error)?)
@@ -212,6 +240,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_bytes_content_range_header_value() -> Result<()> {
+        let cases = vec![
+            (
+                "range start with unknown size",
+                BytesContentRange::default().with_range(123, 456),
+                "bytes 123-456/*",
+            ),
+            (
+                "range start with known size",
+                BytesContentRange::default()
+                    .with_range(123, 456)
+                    .with_size(789),
+                "bytes 123-456/789",
+            ),
+            (
+                "only have size",
+                BytesContentRange::default().with_size(789),
+                "bytes */789",
+            ),
+            (
+                "neither range nor size",
+                BytesContentRange::default(),
+                "bytes */*",
+            ),
+        ];
+
+        for (name, input, expected) in cases {
+            let actual = input.header_value();
+            assert_eq!(expected, actual, "{name}");
+
+            let parsed: BytesContentRange = actual.parse()?;
+            assert_eq!(input, parsed, "{name}: round trip")
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_from_bytes_range() {
         let cases = vec![
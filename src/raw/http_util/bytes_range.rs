@@ -86,14 +86,77 @@ impl BytesRange {
         self.0.unwrap_or_default() == 0 && self.1.is_none()
     }
 
+    /// Check if this range is empty, a.k.a, the size is `Some(0)`.
+    ///
+    /// Empty range can't be represented by `Range` header, users should
+    /// not send it to services.
+    pub fn is_empty(&self) -> bool {
+        self.1 == Some(0)
+    }
+
+    /// Check if this range is a suffix range like `bytes=-1024`.
+    ///
+    /// Suffix range could be read without knowing the total size, the real
+    /// offset could be learnt from `Content-Range` of the response.
+    pub fn is_suffix(&self) -> bool {
+        self.0.is_none() && self.1.is_some()
+    }
+
+    /// Advance the range by `n` bytes that have been consumed.
+    ///
+    /// This is useful to resume a partial read: the returning range could be
+    /// used to build a new `Range` header for the rest of content.
+    ///
+    /// # Behavior
+    ///
+    /// - `offset` will be moved forward by `n` and `size` will be shrunk by `n`.
+    /// - For range without size, only `offset` will be moved.
+    /// - For suffix range (`bytes=-<size>`), only `size` will be shrunk.
+    /// - Advancing past the known size will clamp to an empty range (size
+    ///   `Some(0)`), users should check [`BytesRange::is_empty`] before
+    ///   sending new requests.
+    pub fn advance(&mut self, n: u64) {
+        let n = match self.1 {
+            Some(size) => {
+                let n = n.min(size);
+                self.1 = Some(size - n);
+                n
+            }
+            None => n,
+        };
+
+        match (self.0, self.1) {
+            (Some(offset), _) => self.0 = Some(offset + n),
+            // Range without offset and size means the whole content.
+            (None, None) => self.0 = Some(n),
+            // Suffix range only need to shrink it's size.
+            (None, Some(_)) => {}
+        }
+    }
+
     /// Convert bytes range into Range header.
     ///
+    /// Returns `None` if this range is full so that callers don't need to
+    /// send the `Range` header at all.
+    ///
     /// # NOTE
     ///
-    /// - `bytes=-1023` means get the suffix of the file.
+    /// - `bytes=-1024` means get the last 1024 bytes of the file.
     /// - `bytes=0-1023` means get the first 1024 bytes, we must set the end to 1023.
-    pub fn to_header(&self) -> String {
-        format!("bytes={self}")
+    pub fn to_header(&self) -> Option<String> {
+        if self.is_full() {
+            None
+        } else {
+            Some(format!("bytes={self}"))
+        }
+    }
+
+    /// Parse a `Range` header value like `bytes=0-1023` into BytesRange.
+    ///
+    /// This is the inverse of [`BytesRange::to_header`], the suffix form
+    /// `bytes=-500` will be parsed as the last 500 bytes.
+    pub fn parse_range_header(value: &str) -> Result<Self> {
+        value.parse()
     }
 
     /// Convert bytes range into rust range.
@@ -132,9 +195,12 @@ impl BytesRange {
     }
 }
 
+/// Empty range can't be represented in `Range` header, it will be
+/// displayed as `empty` instead.
 impl Display for BytesRange {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match (self.0, self.1) {
+            (_, Some(0)) => write!(f, "empty"),
             (Some(offset), None) => write!(f, "{}-", offset),
             (None, Some(size)) => write!(f, "-{}", size),
             (Some(offset), Some(size)) => write!(f, "{}-{}", offset, offset + size - 1),
@@ -183,12 +249,17 @@ impl FromStr for BytesRange {
             // -<suffix-length>
             Ok(BytesRange::new(
                 None,
-                Some(v[1].parse::<u64>().map_err(parse_int_error)? + 1),
+                Some(v[1].parse().map_err(parse_int_error)?),
             ))
         } else {
             // <range-start>-<range-end>
             let start: u64 = v[0].parse().map_err(parse_int_error)?;
             let end: u64 = v[1].parse().map_err(parse_int_error)?;
+            if end < start {
+                return Err(Error::new(ErrorKind::Unexpected, "header range is invalid")
+                    .with_operation("BytesRange::from_str")
+                    .with_context("value", value));
+            }
             Ok(BytesRange::new(Some(start), Some(end - start + 1)))
         }
     }
@@ -233,19 +304,90 @@ mod tests {
         assert_eq!(h.to_string(), "1024-2047");
     }
 
+    #[test]
+    fn test_bytes_range_empty_after_advance() {
+        for (name, input) in [
+            ("range from start", (Some(0), Some(100))),
+            ("range", (Some(10), Some(100))),
+            ("suffix", (None, Some(100))),
+        ] {
+            let mut br = BytesRange::new(input.0, input.1);
+            br.advance(200);
+
+            assert!(br.is_empty(), "{name}");
+            assert_eq!(br.to_string(), "empty", "{name}");
+        }
+
+        assert!(!BytesRange::new(Some(0), None).is_empty());
+        assert!(!BytesRange::new(None, None).is_empty());
+    }
+
     #[test]
     fn test_bytes_range_to_header() {
         let h = BytesRange::new(None, Some(1024));
-        assert_eq!(h.to_header(), "bytes=-1024");
+        assert_eq!(h.to_header().as_deref(), Some("bytes=-1024"));
 
         let h = BytesRange::new(Some(0), Some(1024));
-        assert_eq!(h.to_header(), "bytes=0-1023");
+        assert_eq!(h.to_header().as_deref(), Some("bytes=0-1023"));
 
         let h = BytesRange::new(Some(1024), None);
-        assert_eq!(h.to_header(), "bytes=1024-");
+        assert_eq!(h.to_header().as_deref(), Some("bytes=1024-"));
 
         let h = BytesRange::new(Some(1024), Some(1024));
-        assert_eq!(h.to_header(), "bytes=1024-2047");
+        assert_eq!(h.to_header().as_deref(), Some("bytes=1024-2047"));
+
+        assert_eq!(BytesRange::new(None, None).to_header(), None);
+        assert_eq!(BytesRange::new(Some(0), None).to_header(), None);
+    }
+
+    #[test]
+    fn test_bytes_range_is_suffix() {
+        assert!(BytesRange::new(None, Some(1024)).is_suffix());
+        assert!(BytesRange::from(..1024).is_suffix());
+        assert!(!BytesRange::new(Some(0), Some(1024)).is_suffix());
+        assert!(!BytesRange::new(None, None).is_suffix());
+    }
+
+    #[test]
+    fn test_bytes_range_header_round_trip() -> Result<()> {
+        let cases = vec![
+            (
+                "offset only",
+                BytesRange::new(Some(1024), None),
+                "bytes=1024-",
+            ),
+            (
+                "offset and size",
+                BytesRange::new(Some(1024), Some(1024)),
+                "bytes=1024-2047",
+            ),
+            ("one byte", BytesRange::new(Some(0), Some(1)), "bytes=0-0"),
+            ("suffix", BytesRange::new(None, Some(500)), "bytes=-500"),
+        ];
+
+        for (name, range, header) in cases {
+            assert_eq!(range.to_header().as_deref(), Some(header), "{name}");
+            assert_eq!(BytesRange::parse_range_header(header)?, range, "{name}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_range_header_invalid() {
+        for input in [
+            "",
+            "0-1",
+            "bytes=",
+            "bytes=a-",
+            "bytes=1-0",
+            "bytes=0-1,2-3",
+        ] {
+            assert!(
+                BytesRange::parse_range_header(input).is_err(),
+                "{input} must be invalid"
+            );
+        }
     }
 
     #[test]
@@ -272,7 +414,7 @@ mod tests {
                 "bytes=123-",
                 BytesRange::new(Some(123), None),
             ),
-            ("suffix", "bytes=-123", BytesRange::new(None, Some(124))),
+            ("suffix", "bytes=-123", BytesRange::new(None, Some(123))),
             (
                 "range",
                 "bytes=123-124",
@@ -295,6 +437,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_bytes_range_advance() {
+        let cases = vec![
+            ("full", (None, None), 10, (Some(10), None)),
+            ("offset only", (Some(10), None), 10, (Some(20), None)),
+            ("suffix", (None, Some(100)), 10, (None, Some(90))),
+            ("range", (Some(10), Some(100)), 10, (Some(20), Some(90))),
+            (
+                "range exactly",
+                (Some(10), Some(100)),
+                100,
+                (Some(110), Some(0)),
+            ),
+            (
+                "range overflow",
+                (Some(10), Some(100)),
+                200,
+                (Some(110), Some(0)),
+            ),
+            ("suffix overflow", (None, Some(100)), 200, (None, Some(0))),
+            ("zero", (Some(10), Some(100)), 0, (Some(10), Some(100))),
+        ];
+
+        for (name, input, n, expected) in cases {
+            let mut actual = BytesRange::new(input.0, input.1);
+            actual.advance(n);
+
+            assert_eq!(BytesRange::new(expected.0, expected.1), actual, "{name}");
+        }
+    }
+
     #[test]
     fn test_apply_on_bytes() -> Result<()> {
         let bs = Bytes::from_static("Hello, World!".as_bytes());
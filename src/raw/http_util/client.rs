@@ -17,6 +17,7 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::io;
 use std::str::FromStr;
+use std::time::Duration;
 
 use futures::TryStreamExt;
 use http::Request;
@@ -58,6 +59,19 @@ impl Debug for HttpClient {
 impl HttpClient {
     /// Create a new http client.
     pub fn new() -> Self {
+        Self::with_pool_options(None, None)
+    }
+
+    /// Create a new http client with connection pool options.
+    ///
+    /// - `pool_max_idle_per_host`: the max idle connections kept alive per host.
+    /// - `connect_timeout`: the timeout for establishing connections.
+    ///
+    /// Default values of clients will be used if not set.
+    pub fn with_pool_options(
+        pool_max_idle_per_host: Option<usize>,
+        connect_timeout: Option<Duration>,
+    ) -> Self {
         let async_client = {
             let mut builder = ClientBuilder::new();
 
@@ -70,6 +84,13 @@ impl HttpClient {
             // Redirect will be handled by ourselves.
             builder = builder.redirect(Policy::none());
 
+            if let Some(v) = pool_max_idle_per_host {
+                builder = builder.pool_max_idle_per_host(v);
+            }
+            if let Some(v) = connect_timeout {
+                builder = builder.connect_timeout(v);
+            }
+
             #[cfg(feature = "trust-dns")]
             // using trust-dns async resolver
             let builder = builder.trust_dns(true);
@@ -80,21 +101,7 @@ impl HttpClient {
             builder.build().expect("reqwest client must build succeed")
         };
 
-        let sync_client = {
-            let mut builder = ureq::AgentBuilder::new();
-
-            for key in ["http_proxy", "https_proxy", "HTTP_PROXY", "HTTPS_PROXY"] {
-                if let Ok(proxy) = env::var(key) {
-                    // Ignore proxy setting if proxy is invalid.
-                    if let Ok(proxy) = ureq::Proxy::new(proxy) {
-                        debug!("sync client: set proxy to {proxy:?}");
-                        builder = builder.proxy(proxy);
-                    }
-                }
-            }
-
-            builder.build()
-        };
+        let sync_client = new_sync_client(pool_max_idle_per_host, connect_timeout);
 
         HttpClient {
             async_client,
@@ -102,6 +109,21 @@ impl HttpClient {
         }
     }
 
+    /// Create a new http client with a user provided async client.
+    ///
+    /// The connection pool of `client` will be shared by all its clones, so
+    /// that keep-alive connections could be reused across operators.
+    ///
+    /// `client` should not enable auto decompress and redirect, otherwise
+    /// the content and length returned by services could be mismatched.
+    /// Blocking operations still use the default sync client.
+    pub fn with_client(client: reqwest::Client) -> Self {
+        HttpClient {
+            async_client: client,
+            sync_client: new_sync_client(None, None),
+        }
+    }
+
     /// Send a request in blocking way.
     pub fn send(&self, req: Request<Body>) -> Result<Response<Body>> {
         let (parts, body) = req.into_parts();
@@ -224,3 +246,29 @@ impl HttpClient {
         Ok(resp)
     }
 }
+
+fn new_sync_client(
+    pool_max_idle_per_host: Option<usize>,
+    connect_timeout: Option<Duration>,
+) -> ureq::Agent {
+    let mut builder = ureq::AgentBuilder::new();
+
+    for key in ["http_proxy", "https_proxy", "HTTP_PROXY", "HTTPS_PROXY"] {
+        if let Ok(proxy) = env::var(key) {
+            // Ignore proxy setting if proxy is invalid.
+            if let Ok(proxy) = ureq::Proxy::new(proxy) {
+                debug!("sync client: set proxy to {proxy:?}");
+                builder = builder.proxy(proxy);
+            }
+        }
+    }
+
+    if let Some(v) = pool_max_idle_per_host {
+        builder = builder.max_idle_connections_per_host(v);
+    }
+    if let Some(v) = connect_timeout {
+        builder = builder.timeout_connect(v);
+    }
+
+    builder.build()
+}
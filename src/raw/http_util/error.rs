@@ -22,6 +22,8 @@ use http::HeaderValue;
 use http::Response;
 use http::StatusCode;
 
+use super::parse_etag;
+use super::parse_version_id;
 use super::IncomingAsyncBody;
 use crate::Error;
 use crate::ErrorKind;
@@ -93,3 +95,63 @@ pub fn new_request_build_error(err: http::Error) -> Error {
 pub fn new_request_sign_error(err: anyhow::Error) -> Error {
     Error::new(ErrorKind::Unexpected, "signing request").set_source(err)
 }
+
+/// Add the current metadata of object carried by the error response into
+/// context if the error is [`ErrorKind::ConditionNotMatch`].
+///
+/// Services like s3 return `ETag`, `Last-Modified` and version id while
+/// conditions are not matched, users can take them from error's context
+/// (`etag`, `last_modified` and `version_id`) to retry conditional
+/// operations without a following `stat`.
+pub fn with_current_metadata(mut err: Error, headers: &HeaderMap) -> Error {
+    if err.kind() != ErrorKind::ConditionNotMatch {
+        return err;
+    }
+
+    if let Ok(Some(v)) = parse_etag(headers) {
+        err = err.with_context("etag", v);
+    }
+    if let Some(v) = headers
+        .get(http::header::LAST_MODIFIED)
+        .and_then(|v| v.to_str().ok())
+    {
+        err = err.with_context("last_modified", v);
+    }
+    if let Ok(Some(v)) = parse_version_id(headers) {
+        err = err.with_context("version_id", v);
+    }
+    err
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header::ETAG;
+    use http::header::LAST_MODIFIED;
+
+    use super::*;
+
+    #[test]
+    fn test_with_current_metadata() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        headers.insert("x-amz-version-id", HeaderValue::from_static("v1"));
+
+        let err = with_current_metadata(
+            Error::new(ErrorKind::ConditionNotMatch, "precondition failed"),
+            &headers,
+        );
+        assert_eq!(err.context("etag"), Some("abc"));
+        assert_eq!(
+            err.context("last_modified"),
+            Some("Wed, 21 Oct 2015 07:28:00 GMT")
+        );
+        assert_eq!(err.context("version_id"), Some("v1"));
+
+        let err = with_current_metadata(Error::new(ErrorKind::Unexpected, "other"), &headers);
+        assert_eq!(err.context("etag"), None);
+    }
+}
@@ -12,21 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
+
 use http::header::HeaderName;
+use http::header::CACHE_CONTROL;
+use http::header::CONTENT_DISPOSITION;
+use http::header::CONTENT_ENCODING;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_RANGE;
 use http::header::CONTENT_TYPE;
 use http::header::ETAG;
+use http::header::EXPIRES;
+use http::header::IF_MATCH;
+use http::header::IF_NONE_MATCH;
 use http::header::LAST_MODIFIED;
 use http::HeaderMap;
-use time::format_description::well_known::Rfc2822;
+use http::HeaderValue;
 use time::OffsetDateTime;
+use time::UtcOffset;
 
 use crate::raw::*;
 use crate::Error;
 use crate::ErrorKind;
 use crate::ObjectMetadata;
 use crate::ObjectMode;
+use crate::OpWrite;
 use crate::Result;
 
 /// Parse content length from header map.
@@ -54,18 +64,40 @@ pub fn parse_content_length(headers: &HeaderMap) -> Result<Option<u64>> {
 }
 
 /// Parse content md5 from header map.
+///
+/// `x-amz-meta-md5` (user metadata set by clients like `aws s3 cp`) and
+/// `x-goog-hash` (like `crc32c=n03x6A==,md5=Ojk9c3dhfxgoKVVHYwFbHQ==`) will
+/// be used if `Content-MD5` doesn't exist.
 pub fn parse_content_md5(headers: &HeaderMap) -> Result<Option<&str>> {
-    match headers.get(HeaderName::from_static("content-md5")) {
-        None => Ok(None),
-        Some(v) => Ok(Some(v.to_str().map_err(|e| {
+    fn to_str(v: &HeaderValue) -> Result<&str> {
+        v.to_str().map_err(|e| {
             Error::new(
                 ErrorKind::Unexpected,
                 "header value is not valid utf-8 string",
             )
             .with_operation("http_util::parse_content_md5")
             .set_source(e)
-        })?)),
+        })
+    }
+
+    if let Some(v) = headers.get(HeaderName::from_static("content-md5")) {
+        return Ok(Some(to_str(v)?));
+    }
+
+    if let Some(v) = headers.get(HeaderName::from_static("x-amz-meta-md5")) {
+        return Ok(Some(to_str(v)?));
+    }
+
+    for v in headers.get_all(HeaderName::from_static("x-goog-hash")) {
+        if let Some(md5) = to_str(v)?
+            .split(',')
+            .find_map(|v| v.trim().strip_prefix("md5="))
+        {
+            return Ok(Some(md5));
+        }
     }
+
+    Ok(None)
 }
 
 /// Parse content type from header map.
@@ -83,6 +115,46 @@ pub fn parse_content_type(headers: &HeaderMap) -> Result<Option<&str>> {
     }
 }
 
+/// Parse content disposition from header map.
+///
+/// Servers may return raw non-ASCII file names in this header, they will be
+/// decoded as utf-8 lossily instead of failing the whole request.
+pub fn parse_content_disposition(headers: &HeaderMap) -> Option<Cow<'_, str>> {
+    headers
+        .get(CONTENT_DISPOSITION)
+        .map(|v| String::from_utf8_lossy(v.as_bytes()))
+}
+
+/// Parse cache control from header map.
+pub fn parse_cache_control(headers: &HeaderMap) -> Result<Option<&str>> {
+    match headers.get(CACHE_CONTROL) {
+        None => Ok(None),
+        Some(v) => Ok(Some(v.to_str().map_err(|e| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "header value is not valid utf-8 string",
+            )
+            .with_operation("http_util::parse_cache_control")
+            .set_source(e)
+        })?)),
+    }
+}
+
+/// Parse content encoding from header map.
+pub fn parse_content_encoding(headers: &HeaderMap) -> Result<Option<&str>> {
+    match headers.get(CONTENT_ENCODING) {
+        None => Ok(None),
+        Some(v) => Ok(Some(v.to_str().map_err(|e| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "header value is not valid utf-8 string",
+            )
+            .with_operation("http_util::parse_content_encoding")
+            .set_source(e)
+        })?)),
+    }
+}
+
 /// Parse content range from header map.
 pub fn parse_content_range(headers: &HeaderMap) -> Result<Option<BytesContentRange>> {
     match headers.get(CONTENT_RANGE) {
@@ -103,6 +175,8 @@ pub fn parse_content_range(headers: &HeaderMap) -> Result<Option<BytesContentRan
 }
 
 /// Parse last modified from header map.
+///
+/// The value will be converted into UTC, and invalid values will be ignored.
 pub fn parse_last_modified(headers: &HeaderMap) -> Result<Option<OffsetDateTime>> {
     match headers.get(LAST_MODIFIED) {
         None => Ok(None),
@@ -115,35 +189,116 @@ pub fn parse_last_modified(headers: &HeaderMap) -> Result<Option<OffsetDateTime>
                 .with_operation("http_util::parse_last_modified")
                 .set_source(e)
             })?;
-            let t = OffsetDateTime::parse(v, &Rfc2822).map_err(|e| {
+
+            Ok(parse_datetime_from_rfc2822(v))
+        }
+    }
+}
+
+/// Parse expires from header map.
+///
+/// Invalid values like `0` which means "already expired" will be ignored.
+pub fn parse_expires(headers: &HeaderMap) -> Result<Option<OffsetDateTime>> {
+    match headers.get(EXPIRES) {
+        None => Ok(None),
+        Some(v) => {
+            let v = v.to_str().map_err(|e| {
                 Error::new(
                     ErrorKind::Unexpected,
-                    "header value is not valid rfc2822 time",
+                    "header value is not valid utf-8 string",
                 )
-                .with_operation("http_util::parse_last_modified")
+                .with_operation("http_util::parse_expires")
                 .set_source(e)
             })?;
 
-            Ok(Some(t))
+            Ok(parse_datetime_from_rfc2822(v))
         }
     }
 }
 
+/// Format time into HTTP-date like `Sun, 06 Nov 1994 08:49:37 GMT`.
+///
+/// HTTP-date is defined by [RFC 7231](https://httpwg.org/specs/rfc7231.html#http.date),
+/// time will be converted into UTC before formatting.
+pub fn format_http_date(t: OffsetDateTime) -> Result<String> {
+    let t = t.to_offset(UtcOffset::UTC);
+    if !(0..=9999).contains(&t.year()) {
+        return Err(
+            Error::new(ErrorKind::Unexpected, "year is out of range of http date")
+                .with_operation("http_util::format_http_date")
+                .with_context("time", t.to_string()),
+        );
+    }
+
+    // Weekday and month are displayed in full names, take the first three
+    // letters as required by IMF-fixdate.
+    Ok(format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+        &t.weekday().to_string()[..3],
+        t.day(),
+        &t.month().to_string()[..3],
+        t.year(),
+        t.hour(),
+        t.minute(),
+        t.second()
+    ))
+}
+
 /// Parse etag from header map.
-pub fn parse_etag(headers: &HeaderMap) -> Result<Option<&str>> {
+///
+/// Surrounding quotes will be stripped while the weak prefix `W/` is kept:
+///
+/// - `"33a64df551425fcc55e4d42a148795d9f25f89d4"` => `33a64df551425fcc55e4d42a148795d9f25f89d4`
+/// - `W/"0815"` => `W/0815`
+pub fn parse_etag(headers: &HeaderMap) -> Result<Option<String>> {
     match headers.get(ETAG) {
         None => Ok(None),
-        Some(v) => Ok(Some(v.to_str().map_err(|e| {
-            Error::new(
-                ErrorKind::Unexpected,
-                "header value is not valid utf-8 string",
-            )
-            .with_operation("http_util::parse_etag")
-            .set_source(e)
-        })?)),
+        Some(v) => {
+            let v = v.to_str().map_err(|e| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "header value is not valid utf-8 string",
+                )
+                .with_operation("http_util::parse_etag")
+                .set_source(e)
+            })?;
+
+            Ok(Some(normalize_etag(v)))
+        }
     }
 }
 
+/// Normalize etag by stripping surrounding quotes and keeping the weak
+/// prefix `W/`.
+pub fn normalize_etag(etag: &str) -> String {
+    match etag.strip_prefix("W/") {
+        Some(v) => format!("W/{}", v.trim_matches('"')),
+        None => etag.trim_matches('"').to_string(),
+    }
+}
+
+/// Parse version id from header map.
+///
+/// Both `x-amz-version-id` (s3) and `x-goog-generation` (gcs) are supported.
+pub fn parse_version_id(headers: &HeaderMap) -> Result<Option<&str>> {
+    let v = match headers
+        .get(HeaderName::from_static("x-amz-version-id"))
+        .or_else(|| headers.get(HeaderName::from_static("x-goog-generation")))
+    {
+        None => return Ok(None),
+        Some(v) => v,
+    };
+
+    Ok(Some(v.to_str().map_err(|e| {
+        Error::new(
+            ErrorKind::Unexpected,
+            "header value is not valid utf-8 string",
+        )
+        .with_operation("http_util::parse_version_id")
+        .set_source(e)
+    })?))
+}
+
 /// parse_into_object_metadata will parse standards http headers into ObjectMetadata.
 ///
 /// # Notes
@@ -167,12 +322,28 @@ pub fn parse_into_object_metadata(path: &str, headers: &HeaderMap) -> Result<Obj
         m.set_content_type(v);
     }
 
+    if let Some(v) = parse_content_disposition(headers) {
+        m.set_content_disposition(&v);
+    }
+
+    if let Some(v) = parse_cache_control(headers)? {
+        m.set_cache_control(v);
+    }
+
+    if let Some(v) = parse_content_encoding(headers)? {
+        m.set_content_encoding(v);
+    }
+
     if let Some(v) = parse_content_range(headers)? {
         m.set_content_range(v);
     }
 
     if let Some(v) = parse_etag(headers)? {
-        m.set_etag(v);
+        m.set_etag(&v);
+    }
+
+    if let Some(v) = parse_version_id(headers)? {
+        m.set_version_id(v);
     }
 
     if let Some(v) = parse_content_md5(headers)? {
@@ -183,5 +354,299 @@ pub fn parse_into_object_metadata(path: &str, headers: &HeaderMap) -> Result<Obj
         m.set_last_modified(v);
     }
 
+    if let Some(v) = parse_expires(headers)? {
+        m.set_expires(v);
+    }
+
     Ok(m)
 }
+
+/// Insert `Content-Disposition` header for write.
+pub fn insert_content_disposition(headers: &mut HeaderMap, args: &OpWrite) -> Result<()> {
+    if let Some(v) = args.content_disposition() {
+        let value = HeaderValue::from_str(v).map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "header value is invalid")
+                .with_operation("http_util::insert_content_disposition")
+                .with_context("value", v)
+                .set_source(e)
+        })?;
+        headers.insert(CONTENT_DISPOSITION, value);
+    }
+
+    Ok(())
+}
+
+/// Insert `Cache-Control` header for write.
+pub fn insert_cache_control(headers: &mut HeaderMap, args: &OpWrite) -> Result<()> {
+    if let Some(v) = args.cache_control() {
+        let value = HeaderValue::from_str(v).map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "header value is invalid")
+                .with_operation("http_util::insert_cache_control")
+                .with_context("value", v)
+                .set_source(e)
+        })?;
+        headers.insert(CACHE_CONTROL, value);
+    }
+
+    Ok(())
+}
+
+/// Insert `If-Match` and `If-None-Match` headers for conditional write.
+///
+/// `if_not_exists` will be sent as `If-None-Match: *`.
+pub fn insert_write_conditions(headers: &mut HeaderMap, args: &OpWrite) -> Result<()> {
+    let conditions = [
+        (IF_MATCH, args.if_match()),
+        (IF_NONE_MATCH, args.if_none_match()),
+    ];
+
+    for (key, value) in conditions {
+        if let Some(v) = value {
+            let value = HeaderValue::from_str(v).map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "header value is invalid")
+                    .with_operation("http_util::insert_write_conditions")
+                    .with_context("header", key.as_str())
+                    .with_context("value", v)
+                    .set_source(e)
+            })?;
+            headers.insert(key, value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Insert `Expires` header for write.
+pub fn insert_expires(headers: &mut HeaderMap, args: &OpWrite) -> Result<()> {
+    if let Some(expires) = args.expires() {
+        let v = format_http_date(expires)?;
+        let value = HeaderValue::from_str(&v).map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "header value is invalid")
+                .with_operation("http_util::insert_expires")
+                .with_context("value", &v)
+                .set_source(e)
+        })?;
+        headers.insert(EXPIRES, value);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_last_modified() {
+        let cases = vec![
+            (
+                "http date",
+                "Sun, 06 Nov 1994 08:49:37 GMT",
+                Some(OffsetDateTime::from_unix_timestamp(784111777).unwrap()),
+            ),
+            ("invalid", "yesterday", None),
+        ];
+
+        for (name, input, expected) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(LAST_MODIFIED, HeaderValue::from_static(input));
+            let actual = parse_last_modified(&headers).expect("parse must succeed");
+            assert_eq!(actual, expected, "{name}");
+        }
+        assert_eq!(parse_last_modified(&HeaderMap::new()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_etag() {
+        let cases = vec![
+            ("strong etag", "\"33a64df551425fcc\"", "33a64df551425fcc"),
+            ("weak etag", "W/\"0815\"", "W/0815"),
+            ("not quoted etag", "CKWasoTgyPkCEAE=", "CKWasoTgyPkCEAE="),
+        ];
+
+        for (name, input, expected) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(ETAG, HeaderValue::from_str(input).unwrap());
+
+            let actual = parse_etag(&headers).expect("parse must succeed");
+            assert_eq!(actual.as_deref(), Some(expected), "{name}");
+        }
+
+        assert_eq!(parse_etag(&HeaderMap::new()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_content_md5() {
+        let cases = vec![
+            (
+                "content-md5",
+                "content-md5",
+                "Ojk9c3dhfxgoKVVHYwFbHQ==",
+                Some("Ojk9c3dhfxgoKVVHYwFbHQ=="),
+            ),
+            (
+                "x-amz-meta-md5",
+                "x-amz-meta-md5",
+                "3a393d7377617f182829554763015b1d",
+                Some("3a393d7377617f182829554763015b1d"),
+            ),
+            (
+                "x-goog-hash",
+                "x-goog-hash",
+                "crc32c=n03x6A==,md5=Ojk9c3dhfxgoKVVHYwFbHQ==",
+                Some("Ojk9c3dhfxgoKVVHYwFbHQ=="),
+            ),
+            (
+                "x-goog-hash without md5",
+                "x-goog-hash",
+                "crc32c=n03x6A==",
+                None,
+            ),
+        ];
+
+        for (name, key, value, expected) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                HeaderName::from_static(key),
+                HeaderValue::from_str(value).unwrap(),
+            );
+
+            let actual = parse_content_md5(&headers).expect("parse must succeed");
+            assert_eq!(actual, expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_insert_write_conditions() {
+        let cases = vec![
+            ("no condition", OpWrite::new(0), None, None),
+            (
+                "if match",
+                OpWrite::new(0).with_if_match("\"etag\""),
+                Some("\"etag\""),
+                None,
+            ),
+            (
+                "if none match",
+                OpWrite::new(0).with_if_none_match("\"etag\""),
+                None,
+                Some("\"etag\""),
+            ),
+            (
+                "if not exists",
+                OpWrite::new(0).with_if_not_exists(true),
+                None,
+                Some("*"),
+            ),
+        ];
+
+        for (name, args, if_match, if_none_match) in cases {
+            let mut headers = HeaderMap::new();
+            insert_write_conditions(&mut headers, &args).expect("insert must succeed");
+
+            let get = |key| headers.get(key).map(|v: &HeaderValue| v.to_str().unwrap());
+            assert_eq!(get(IF_MATCH), if_match, "{name}");
+            assert_eq!(get(IF_NONE_MATCH), if_none_match, "{name}");
+        }
+
+        let mut headers = HeaderMap::new();
+        let args = OpWrite::new(0).with_if_match("invalid\netag");
+        assert!(insert_write_conditions(&mut headers, &args).is_err());
+    }
+
+    #[test]
+    fn test_format_http_date() {
+        let t = OffsetDateTime::from_unix_timestamp(784111777).unwrap();
+        assert_eq!(
+            format_http_date(t).unwrap(),
+            "Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+
+        // Time will be converted into UTC.
+        let t = t.to_offset(UtcOffset::from_hms(8, 0, 0).unwrap());
+        assert_eq!(
+            format_http_date(t).unwrap(),
+            "Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+
+        let t = OffsetDateTime::from_unix_timestamp(-62_198_755_200).unwrap();
+        assert!(format_http_date(t).is_err(), "negative year");
+    }
+
+    #[test]
+    fn test_parse_expires() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_expires(&headers).unwrap(), None);
+
+        let t = OffsetDateTime::from_unix_timestamp(784111777).unwrap();
+        insert_expires(&mut headers, &OpWrite::new(0).with_expires(t)).unwrap();
+        assert_eq!(
+            headers.get(EXPIRES).unwrap(),
+            "Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+        assert_eq!(parse_expires(&headers).unwrap(), Some(t));
+
+        headers.insert(EXPIRES, HeaderValue::from_static("0"));
+        assert_eq!(parse_expires(&headers).unwrap(), None, "invalid expires");
+    }
+
+    #[test]
+    fn test_parse_version_id() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_version_id(&headers).unwrap(), None);
+
+        headers.insert(
+            HeaderName::from_static("x-goog-generation"),
+            HeaderValue::from_static("1660563214863653"),
+        );
+        assert_eq!(
+            parse_version_id(&headers).unwrap(),
+            Some("1660563214863653")
+        );
+
+        headers.insert(
+            HeaderName::from_static("x-amz-version-id"),
+            HeaderValue::from_static("3HL4kqtJlcpXroDTDmJ"),
+        );
+        assert_eq!(
+            parse_version_id(&headers).unwrap(),
+            Some("3HL4kqtJlcpXroDTDmJ")
+        );
+    }
+
+    #[test]
+    fn test_parse_content_disposition() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_content_disposition(&headers), None);
+
+        headers.insert(
+            CONTENT_DISPOSITION,
+            HeaderValue::from_static("attachment; filename=\"hello.txt\""),
+        );
+        assert_eq!(
+            parse_content_disposition(&headers).as_deref(),
+            Some("attachment; filename=\"hello.txt\"")
+        );
+
+        // Non-ASCII file names should be decoded instead of failing.
+        headers.insert(
+            CONTENT_DISPOSITION,
+            HeaderValue::from_bytes("attachment; filename=\"你好.txt\"".as_bytes()).unwrap(),
+        );
+        assert_eq!(
+            parse_content_disposition(&headers).as_deref(),
+            Some("attachment; filename=\"你好.txt\"")
+        );
+
+        headers.insert(
+            CONTENT_DISPOSITION,
+            HeaderValue::from_bytes(b"attachment; filename=\"\xff.txt\"").unwrap(),
+        );
+        assert_eq!(
+            parse_content_disposition(&headers).as_deref(),
+            Some("attachment; filename=\"\u{fffd}.txt\"")
+        );
+    }
+}
@@ -26,15 +26,27 @@ mod body;
 pub use body::AsyncBody;
 pub use body::Body;
 pub use body::IncomingAsyncBody;
+pub(crate) use body::ERROR_BODY_LIMIT;
 
 mod header;
+pub use header::format_http_date;
+pub use header::insert_cache_control;
+pub use header::insert_content_disposition;
+pub use header::insert_expires;
+pub use header::insert_write_conditions;
+pub use header::normalize_etag;
+pub use header::parse_cache_control;
+pub use header::parse_content_disposition;
+pub use header::parse_content_encoding;
 pub use header::parse_content_length;
 pub use header::parse_content_md5;
 pub use header::parse_content_range;
 pub use header::parse_content_type;
 pub use header::parse_etag;
+pub use header::parse_expires;
 pub use header::parse_into_object_metadata;
 pub use header::parse_last_modified;
+pub use header::parse_version_id;
 
 mod uri;
 pub use uri::percent_encode_path;
@@ -43,6 +55,7 @@ mod error;
 pub use error::new_request_build_error;
 pub use error::new_request_sign_error;
 pub use error::parse_error_response;
+pub use error::with_current_metadata;
 pub use error::ErrorResponse;
 
 mod bytes_range;
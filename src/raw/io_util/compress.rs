@@ -14,6 +14,8 @@
 
 //! This mod provides compress support for BytesWrite and decompress support for BytesRead.
 
+use std::io::Error;
+use std::io::ErrorKind;
 use std::io::Result;
 use std::path::PathBuf;
 use std::pin::Pin;
@@ -112,6 +114,20 @@ impl CompressAlgorithm {
 
         CompressAlgorithm::from_extension(&ext)
     }
+
+    /// Create CompressAlgorithm from the value of `Content-Encoding`.
+    ///
+    /// If the content encoding is not supported, `None` will be return instead.
+    pub fn from_content_encoding(encoding: &str) -> Option<CompressAlgorithm> {
+        match encoding.trim().to_ascii_lowercase().as_str() {
+            "br" => Some(CompressAlgorithm::Brotli),
+            // `deflate` in HTTP means zlib wrapped deflate stream.
+            "deflate" => Some(CompressAlgorithm::Zlib),
+            "gzip" | "x-gzip" => Some(CompressAlgorithm::Gzip),
+            "zstd" => Some(CompressAlgorithm::Zstd),
+            _ => None,
+        }
+    }
 }
 
 impl From<CompressAlgorithm> for DecompressCodec {
@@ -303,6 +319,9 @@ pub struct DecompressDecoder {
     decoder: DecompressCodec,
     state: DecompressState,
     multiple_members: bool,
+    /// Whether the decoder has reached the end of a complete stream, so
+    /// that the underlying reader is allowed to return EOF.
+    stream_end: bool,
 }
 
 impl DecompressDecoder {
@@ -313,6 +332,7 @@ impl DecompressDecoder {
             decoder: algo.into(),
             state: DecompressState::Reading,
             multiple_members: false,
+            stream_end: false,
         }
     }
 
@@ -345,12 +365,21 @@ impl DecompressDecoder {
 
     /// Decode data into output.
     /// Returns the data that has been written.
+    ///
+    /// An `UnexpectedEof` error will be returned if the input reaches EOF
+    /// before a complete stream has been decoded.
     pub fn decode(&mut self, output: &mut [u8]) -> Result<usize> {
         debug_assert_eq!(self.state, DecompressState::Decoding);
 
         // If input is empty, inner reader must reach EOF, return directly.
         if self.buf.is_empty() {
             trace!("input is empty, return directly");
+            if !self.stream_end {
+                return Err(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "compressed stream ends before it's complete",
+                ));
+            }
             // Avoid attempting to reinitialise the decoder if the reader
             // has returned EOF.
             self.multiple_members = false;
@@ -365,6 +394,12 @@ impl DecompressDecoder {
         let written_len = output.written().len();
         self.buf.advance(read_len);
 
+        if done {
+            self.stream_end = true;
+        } else if read_len != 0 {
+            self.stream_end = false;
+        }
+
         if done {
             self.state = DecompressState::Flushing;
         } else if self.buf.is_empty() {
@@ -766,4 +801,36 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_decompress_reader_truncated() -> Result<()> {
+        let _ = env_logger::try_init();
+
+        for algo in [CompressAlgorithm::Gzip, CompressAlgorithm::Zstd] {
+            let compressed_content = fs::read(format!(
+                "{}/testdata/ontime.csv.{}",
+                env::current_dir()?.to_string_lossy(),
+                if algo == CompressAlgorithm::Zstd {
+                    "zst"
+                } else {
+                    algo.extension()
+                }
+            ))?;
+
+            let size = compressed_content.len();
+            for len in [0, 5, 10, size / 2, size - 4] {
+                let mut cr =
+                    DecompressReader::new(Cursor::new(compressed_content[..len].to_vec()), algo);
+
+                let mut result = vec![];
+                let err = cr
+                    .read_to_end(&mut result)
+                    .await
+                    .expect_err("truncated stream must fail");
+                assert_eq!(err.kind(), ErrorKind::UnexpectedEof, "{algo:?} {len}");
+            }
+        }
+
+        Ok(())
+    }
 }
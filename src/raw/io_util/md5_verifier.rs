@@ -0,0 +1,126 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::AsyncRead;
+use md5::Digest;
+use md5::Md5;
+
+use crate::raw::*;
+use crate::*;
+
+/// Create a reader that verifies the md5 of all content read from
+/// BytesReader.
+///
+/// `expected` could be either hex encoded (like s3's ETag) or base64
+/// encoded (like `Content-MD5`). [`ErrorKind::ChecksumMismatch`] will be
+/// returned while reaching EOF if the checksum doesn't match.
+///
+/// # Example
+///
+/// ```rust
+/// use opendal::raw::verify_md5_read;
+/// # use std::io::Result;
+/// # use futures::io;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let r = Box::new(io::Cursor::new("Hello, World!"));
+/// let s = verify_md5_read(r, "65a8e27d8879283831b664bd8b7f0ad4");
+/// io::copy(s, &mut io::sink()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify_md5_read(s: BytesReader, expected: &str) -> Md5Verifier {
+    Md5Verifier {
+        s,
+        expected: expected.to_string(),
+        hasher: Some(Md5::new()),
+    }
+}
+
+/// Verifier that created via [`verify_md5_read`].
+pub struct Md5Verifier {
+    s: BytesReader,
+    expected: String,
+    /// The hasher will be taken out after checksum has been verified.
+    hasher: Option<Md5>,
+}
+
+impl AsyncRead for Md5Verifier {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        match Pin::new(&mut self.s).poll_read(cx, buf) {
+            Poll::Ready(Ok(0)) => match self.hasher.take() {
+                Some(hasher) => {
+                    let digest = hasher.finalize();
+                    let hex = format!("{digest:x}");
+                    let b64 = base64::encode(digest.as_slice());
+                    if self.expected.eq_ignore_ascii_case(&hex) || self.expected == b64 {
+                        Poll::Ready(Ok(0))
+                    } else {
+                        Poll::Ready(Err(Error::new(
+                            ErrorKind::ChecksumMismatch,
+                            "content md5 doesn't match",
+                        )
+                        .with_operation("Md5Verifier::poll_read")
+                        .with_context("expected", &self.expected)
+                        .with_context("actual", &hex)
+                        .into()))
+                    }
+                }
+                None => Poll::Ready(Ok(0)),
+            },
+            Poll::Ready(Ok(n)) => {
+                if let Some(hasher) = self.hasher.as_mut() {
+                    hasher.update(&buf[..n]);
+                }
+                Poll::Ready(Ok(n))
+            }
+            v => v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::io;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_md5_verifier() {
+        let cases = vec![
+            ("hex", "65a8e27d8879283831b664bd8b7f0ad4", true),
+            ("upper hex", "65A8E27D8879283831B664BD8B7F0AD4", true),
+            ("base64", "ZajifYh5KDgxtmS9i38K1A==", true),
+            ("mismatch", "ZajifYh5KDgxtmS9i38K1B==", false),
+        ];
+
+        for (name, expected, ok) in cases {
+            let r = Box::new(io::Cursor::new("Hello, World!"));
+            let s = verify_md5_read(r, expected);
+
+            let res = io::copy(s, &mut io::sink()).await;
+            assert_eq!(res.is_ok(), ok, "{name}");
+        }
+    }
+}
@@ -41,6 +41,14 @@ pub use write_observer::observe_write;
 pub use write_observer::WriteEvent;
 pub use write_observer::WriteObserver;
 
+mod md5_verifier;
+pub use md5_verifier::verify_md5_read;
+pub use md5_verifier::Md5Verifier;
+
+mod size_verifier;
+pub use size_verifier::verify_size_read;
+pub use size_verifier::SizeVerifier;
+
 mod seekable_reader;
 pub use seekable_reader::seekable_read;
 pub use seekable_reader::SeekableReader;
@@ -60,4 +68,5 @@ pub use compress::DecompressState;
 
 mod walk;
 pub use walk::BottomUpWalker;
+pub use walk::FlatWalker;
 pub use walk::TopDownWalker;
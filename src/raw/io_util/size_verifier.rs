@@ -0,0 +1,166 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::io::Read;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::ready;
+use futures::AsyncRead;
+
+use crate::*;
+
+/// Create a reader that makes sure exactly `size` bytes will be read from
+/// BytesReader.
+///
+/// An error will be returned if the inner reader reaches EOF before `size`
+/// or yields more bytes than `size`, so that services won't produce
+/// truncated objects.
+///
+/// # Example
+///
+/// ```rust
+/// use opendal::raw::verify_size_read;
+/// # use std::io::Result;
+/// # use futures::io;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let r = Box::new(io::Cursor::new("Hello, World!"));
+/// let s = verify_size_read(r, 13);
+/// io::copy(s, &mut io::sink()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify_size_read<R>(r: R, size: u64) -> SizeVerifier<R> {
+    SizeVerifier {
+        inner: r,
+        size,
+        read: 0,
+    }
+}
+
+/// Verifier that created via [`verify_size_read`].
+pub struct SizeVerifier<R> {
+    inner: R,
+    size: u64,
+    read: u64,
+}
+
+impl<R> SizeVerifier<R> {
+    /// Check the result of reading from inner reader.
+    ///
+    /// `probe` means we have read `size` bytes and only polled inner reader
+    /// to make sure it has reached EOF.
+    fn check(&mut self, n: usize, probe: bool) -> io::Result<usize> {
+        if probe {
+            return match n {
+                0 => Ok(0),
+                _ => Err(self.error(io::ErrorKind::InvalidData, self.size + n as u64)),
+            };
+        }
+
+        if n == 0 {
+            return Err(self.error(io::ErrorKind::UnexpectedEof, self.read));
+        }
+
+        self.read += n as u64;
+        if self.read > self.size {
+            return Err(self.error(io::ErrorKind::InvalidData, self.read));
+        }
+        Ok(n)
+    }
+
+    fn error(&self, kind: io::ErrorKind, actual: u64) -> io::Error {
+        let msg = match kind {
+            io::ErrorKind::UnexpectedEof => "reader returns fewer bytes than expected size",
+            _ => "reader returns more bytes than expected size",
+        };
+
+        io::Error::new(
+            kind,
+            Error::new(ErrorKind::Unexpected, msg)
+                .with_operation("SizeVerifier::read")
+                .with_context("expect", self.size.to_string())
+                .with_context("actual", actual.to_string()),
+        )
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for SizeVerifier<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        if self.read >= self.size {
+            let mut probe = [0; 1];
+            let n = ready!(Pin::new(&mut self.inner).poll_read(cx, &mut probe))?;
+            return Poll::Ready(self.check(n, true));
+        }
+
+        let n = ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        Poll::Ready(self.check(n, false))
+    }
+}
+
+impl<R: Read> Read for SizeVerifier<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.read >= self.size {
+            let mut probe = [0; 1];
+            let n = self.inner.read(&mut probe)?;
+            return self.check(n, true);
+        }
+
+        let n = self.inner.read(buf)?;
+        self.check(n, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::io;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_size_verifier() {
+        let cases = vec![
+            ("exact", 13, true),
+            ("fewer", 14, false),
+            ("more", 12, false),
+            ("empty", 0, false),
+        ];
+
+        for (name, size, ok) in cases {
+            let r = io::Cursor::new("Hello, World!");
+            let s = verify_size_read(r, size);
+
+            let res = io::copy(s, &mut io::sink()).await;
+            assert_eq!(res.is_ok(), ok, "{name}");
+
+            let mut s = verify_size_read(std::io::Cursor::new("Hello, World!"), size);
+            let res = std::io::copy(&mut s, &mut std::io::sink());
+            assert_eq!(res.is_ok(), ok, "blocking {name}");
+        }
+    }
+}
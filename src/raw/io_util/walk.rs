@@ -242,6 +242,115 @@ impl ObjectPage for BottomUpWalker {
     }
 }
 
+/// FlatWalker will walk dir in depth first way and only output files,
+/// dirs will also be output if [`FlatWalker::with_include_dirs`] is set.
+///
+/// Given the following file tree:
+///
+/// ```txt
+/// .
+/// ├── dir_x/
+/// │   ├── dir_y/
+/// │   │   ├── dir_z/
+/// │   │   └── file_c
+/// │   └── file_b
+/// └── file_a
+/// ```
+///
+/// FlatWalker will output entries like:
+///
+/// ```txt
+/// dir_x/file_a
+/// dir_x/dir_y/file_b
+/// dir_x/dir_y/dir_z/file_c
+/// ```
+///
+/// This is used for services that can't list recursively in one listing.
+///
+/// # Note
+///
+/// There is no guarantee about the order of files. Only paths of pending
+/// dirs will be kept in memory, and pages are fetched while polling.
+pub struct FlatWalker {
+    acc: Arc<dyn Accessor>,
+    limit: Option<usize>,
+    include_dirs: bool,
+    dirs: Vec<String>,
+    pager: Option<(String, ObjectPager)>,
+}
+
+impl FlatWalker {
+    /// Create a new [`FlatWalker`]
+    pub fn new(acc: Arc<dyn Accessor>, path: &str, limit: Option<usize>) -> Self {
+        FlatWalker {
+            acc,
+            limit,
+            include_dirs: false,
+            dirs: vec![path.to_string()],
+            pager: None,
+        }
+    }
+
+    /// Output dirs along with files, the walking dir itself will not be
+    /// output.
+    pub fn with_include_dirs(mut self, include_dirs: bool) -> Self {
+        self.include_dirs = include_dirs;
+        self
+    }
+}
+
+#[async_trait]
+impl ObjectPage for FlatWalker {
+    async fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
+        loop {
+            let (dir, pager) = match &mut self.pager {
+                Some((dir, pager)) => (dir, pager),
+                None => {
+                    let dir = match self.dirs.pop() {
+                        Some(dir) => dir,
+                        None => return Ok(None),
+                    };
+
+                    let mut args = OpList::new();
+                    if let Some(limit) = self.limit {
+                        args = args.with_limit(limit);
+                    }
+                    let (_, pager) = self.acc.list(&dir, args).await?;
+                    let (dir, pager) = self.pager.insert((dir, pager));
+                    (dir, pager)
+                }
+            };
+
+            let entries = match pager.next_page().await? {
+                Some(entries) => entries,
+                None => {
+                    self.pager = None;
+                    continue;
+                }
+            };
+
+            let mut output = Vec::with_capacity(entries.len());
+            for de in entries {
+                if de.mode().is_dir() {
+                    // Some services could return the dir itself.
+                    if de.path() != dir.as_str() {
+                        self.dirs.push(de.path().to_string());
+                        if self.include_dirs {
+                            output.push(de);
+                        }
+                    }
+                } else {
+                    output.push(de);
+                }
+            }
+
+            if !output.is_empty() {
+                return Ok(Some(output));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
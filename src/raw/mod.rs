@@ -46,8 +46,13 @@ pub use path::get_basename;
 pub use path::get_parent;
 pub use path::normalize_path;
 pub use path::normalize_root;
+pub use path::path_escapes_root;
 pub use path::validate_path;
 
+mod datetime;
+pub use datetime::parse_datetime_from_rfc2822;
+pub use datetime::parse_datetime_from_rfc3339;
+
 mod wrappers;
 pub use wrappers::apply_wrapper;
 
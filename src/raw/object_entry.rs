@@ -59,6 +59,11 @@ impl ObjectEntry {
         self.meta.mode()
     }
 
+    /// Get the metadata of object entry.
+    pub fn metadata(&self) -> &ObjectMetadata {
+        &self.meta
+    }
+
     /// Consume to convert into an object.
     pub fn into_object(self, op: Operator) -> Object {
         Object::with(op, &self.path, self.meta)
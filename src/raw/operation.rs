@@ -31,6 +31,10 @@ pub enum Operation {
     Stat,
     /// Operation for [`crate::raw::Accessor::delete`]
     Delete,
+    /// Operation for [`crate::raw::Accessor::copy`]
+    Copy,
+    /// Operation for [`crate::raw::Accessor::rename`]
+    Rename,
     /// Operation for [`crate::raw::Accessor::list`]
     List,
     /// Operation for [`crate::raw::Accessor::presign`]
@@ -43,6 +47,18 @@ pub enum Operation {
     CompleteMultipart,
     /// Operation for [`crate::raw::Accessor::abort_multipart`]
     AbortMultipart,
+    /// Operation for [`crate::raw::Accessor::list_multipart`]
+    ListMultipart,
+    /// Operation for [`crate::raw::Accessor::list_versions`]
+    ListVersions,
+    /// Operation for [`crate::raw::Accessor::batch`]
+    Batch,
+    /// Operation for [`crate::raw::Accessor::get_tags`]
+    GetTags,
+    /// Operation for [`crate::raw::Accessor::set_tags`]
+    SetTags,
+    /// Operation for [`crate::raw::Accessor::delete_tags`]
+    DeleteTags,
     /// Operation for [`crate::raw::Accessor::blocking_create`]
     BlockingCreate,
     /// Operation for [`crate::raw::Accessor::blocking_read`]
@@ -53,6 +69,8 @@ pub enum Operation {
     BlockingStat,
     /// Operation for [`crate::raw::Accessor::blocking_delete`]
     BlockingDelete,
+    /// Operation for [`crate::raw::Accessor::blocking_copy`]
+    BlockingCopy,
     /// Operation for [`crate::raw::Accessor::blocking_list`]
     BlockingList,
 }
@@ -85,17 +103,26 @@ impl From<Operation> for &'static str {
             Operation::Write => "write",
             Operation::Stat => "stat",
             Operation::Delete => "delete",
+            Operation::Copy => "copy",
+            Operation::Rename => "rename",
             Operation::List => "list",
             Operation::Presign => "presign",
             Operation::CreateMultipart => "create_multipart",
             Operation::WriteMultipart => "write_multipart",
             Operation::CompleteMultipart => "complete_multipart",
             Operation::AbortMultipart => "abort_multipart",
+            Operation::ListMultipart => "list_multipart",
+            Operation::ListVersions => "list_versions",
+            Operation::Batch => "batch",
+            Operation::GetTags => "get_tags",
+            Operation::SetTags => "set_tags",
+            Operation::DeleteTags => "delete_tags",
             Operation::BlockingCreate => "blocking_create",
             Operation::BlockingRead => "blocking_read",
             Operation::BlockingWrite => "blocking_write",
             Operation::BlockingStat => "blocking_stat",
             Operation::BlockingDelete => "blocking_delete",
+            Operation::BlockingCopy => "blocking_copy",
             Operation::BlockingList => "blocking_list",
         }
     }
@@ -94,6 +94,12 @@ pub fn build_rel_path(root: &str, path: &str) -> String {
 /// - All leading / will be trimmed: `///abc` => `abc`
 /// - Internal // will be replaced by /: `abc///def` => `abc/def`
 /// - Empty path will be `/`: `` => `/`
+/// - `.` will be removed: `./abc/./def` => `abc/def`
+/// - `..` will be resolved lexically: `abc/../def` => `def`
+/// - Path ends with `.` or `..` is a dir path: `abc/def/..` => `abc/`
+///
+/// `..` that goes beyond root will be kept like `abc/../../def` =>
+/// `../def`, use [`path_escapes_root`] to check it.
 pub fn normalize_path(path: &str) -> String {
     // - all whitespace has been trimmed.
     // - all leading `/` has been trimmed.
@@ -104,15 +110,34 @@ pub fn normalize_path(path: &str) -> String {
         return "/".to_string();
     }
 
-    let has_trailing = path.ends_with('/');
+    let has_trailing = path.ends_with('/')
+        || path.ends_with("/.")
+        || path.ends_with("/..")
+        || path == "."
+        || path == "..";
+
+    let mut segments: Vec<&str> = Vec::new();
+    for v in path.split('/') {
+        match v {
+            "" | "." => {}
+            ".." => match segments.last() {
+                Some(&last) if last != ".." => {
+                    segments.pop();
+                }
+                _ => segments.push(".."),
+            },
+            v => segments.push(v),
+        }
+    }
 
-    let mut p = path
-        .split('/')
-        .filter(|v| !v.is_empty())
-        .collect::<Vec<&str>>()
-        .join("/");
+    // Path has been resolved to root.
+    if segments.is_empty() {
+        return "/".to_string();
+    }
 
-    // Append trailing back if input path is endswith `/`.
+    let mut p = segments.join("/");
+
+    // Append trailing back if input path is a dir path.
     if has_trailing {
         p.push('/');
     }
@@ -120,6 +145,14 @@ pub fn normalize_path(path: &str) -> String {
     p
 }
 
+/// Check if normalized path goes beyond root like `../abc`.
+///
+/// Services MUST NOT access these paths, otherwise users may access data
+/// outside of configured root.
+pub fn path_escapes_root(path: &str) -> bool {
+    path.split('/').any(|v| v == "..")
+}
+
 /// Make sure root is normalized to style like `/abc/def/`.
 ///
 /// # Normalize Rules
@@ -242,6 +275,16 @@ mod tests {
             ("file path contains ///", "abc///def", "abc/def"),
             ("dir path contains ///", "abc///def///", "abc/def/"),
             ("file with whitespace", "abc/def   ", "abc/def"),
+            ("file path with .", "./abc/./def", "abc/def"),
+            ("dir path ends with .", "abc/.", "abc/"),
+            ("file path with ..", "abc/../def", "def"),
+            ("dir path ends with ..", "abc/def/..", "abc/"),
+            ("path resolved to root", "abc/..", "/"),
+            ("path resolved to root with .", "./", "/"),
+            ("escaped file path", "abc/../../def", "../def"),
+            ("escaped dir path", "abc/../..", "../"),
+            ("escaped abs path", "/../../etc/passwd", "../../etc/passwd"),
+            ("file name contains ..", "abc/..def", "abc/..def"),
         ];
 
         for (name, input, expect) in cases {
@@ -249,6 +292,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_path_escapes_root() {
+        let cases = vec![
+            ("file path", "abc/def", false),
+            ("root path", "/", false),
+            ("file name contains ..", "abc/..def", false),
+            ("escaped file path", "../def", true),
+            ("escaped dir path", "../", true),
+            ("escaped inner path", "abc/../../def", true),
+        ];
+
+        for (name, input, expect) in cases {
+            assert_eq!(path_escapes_root(input), expect, "{}", name)
+        }
+    }
+
     #[test]
     fn test_normalize_root() {
         let cases = vec![
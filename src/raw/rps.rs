@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use http::Request;
 
 use crate::*;
@@ -24,6 +26,77 @@ pub struct RpCreate {}
 #[derive(Debug, Clone, Default)]
 pub struct RpDelete {}
 
+/// Reply for `copy` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpCopy {}
+
+/// Reply for `rename` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpRename {}
+
+/// Reply for `get_tags` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpGetTags {
+    tags: HashMap<String, String>,
+}
+
+impl RpGetTags {
+    /// Create a new reply for `get_tags`.
+    pub fn new(tags: HashMap<String, String>) -> Self {
+        Self { tags }
+    }
+
+    /// Get the tags from reply.
+    pub fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    /// Consume reply to get the tags.
+    pub fn into_tags(self) -> HashMap<String, String> {
+        self.tags
+    }
+}
+
+/// Reply for `set_tags` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpSetTags {}
+
+/// Reply for `delete_tags` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpDeleteTags {}
+
+/// Reply for `batch` operation.
+#[derive(Debug)]
+pub struct RpBatch {
+    results: BatchedResults,
+}
+
+impl RpBatch {
+    /// Create a new RpBatch.
+    pub fn new(results: BatchedResults) -> Self {
+        Self { results }
+    }
+
+    /// Get the results from RpBatch.
+    pub fn results(&self) -> &BatchedResults {
+        &self.results
+    }
+
+    /// Consume RpBatch into BatchedResults.
+    pub fn into_results(self) -> BatchedResults {
+        self.results
+    }
+}
+
+/// Batch results of [`BatchOperations`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BatchedResults {
+    /// Results of batch delete operations, every path will have its own
+    /// result.
+    Delete(Vec<(String, Result<RpDelete>)>),
+}
+
 /// Reply for `list` operation.
 #[derive(Debug, Clone, Default)]
 pub struct RpList {}
@@ -88,6 +161,106 @@ pub struct RpCompleteMultipart {}
 #[derive(Debug, Clone, Default)]
 pub struct RpAbortMultipart {}
 
+/// Reply for `list_multipart` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpListMultipart {
+    uploads: Vec<ObjectUpload>,
+    next_key_marker: Option<String>,
+    next_upload_id_marker: Option<String>,
+}
+
+impl RpListMultipart {
+    /// Create a new reply for `list_multipart`.
+    pub fn new(uploads: Vec<ObjectUpload>) -> Self {
+        Self {
+            uploads,
+            ..Default::default()
+        }
+    }
+
+    /// Set the markers of next page, `None` means there is no more page.
+    pub fn with_next_marker(mut self, key_marker: &str, upload_id_marker: &str) -> Self {
+        self.next_key_marker = Some(key_marker.to_string());
+        self.next_upload_id_marker = Some(upload_id_marker.to_string());
+        self
+    }
+
+    /// Get the uploads from reply.
+    pub fn uploads(&self) -> &[ObjectUpload] {
+        &self.uploads
+    }
+
+    /// Get the mutable uploads from reply.
+    pub fn uploads_mut(&mut self) -> &mut Vec<ObjectUpload> {
+        &mut self.uploads
+    }
+
+    /// Get the key marker of next page.
+    pub fn next_key_marker(&self) -> Option<&str> {
+        self.next_key_marker.as_deref()
+    }
+
+    /// Get the upload id marker of next page.
+    pub fn next_upload_id_marker(&self) -> Option<&str> {
+        self.next_upload_id_marker.as_deref()
+    }
+
+    /// Consume reply to get the uploads.
+    pub fn into_uploads(self) -> Vec<ObjectUpload> {
+        self.uploads
+    }
+}
+
+/// Reply for `list_versions` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpListVersions {
+    versions: Vec<ObjectVersion>,
+    next_key_marker: Option<String>,
+    next_version_id_marker: Option<String>,
+}
+
+impl RpListVersions {
+    /// Create a new reply for `list_versions`.
+    pub fn new(versions: Vec<ObjectVersion>) -> Self {
+        Self {
+            versions,
+            ..Default::default()
+        }
+    }
+
+    /// Set the markers of next page, `None` means there is no more page.
+    pub fn with_next_marker(mut self, key_marker: &str, version_id_marker: &str) -> Self {
+        self.next_key_marker = Some(key_marker.to_string());
+        self.next_version_id_marker = Some(version_id_marker.to_string());
+        self
+    }
+
+    /// Get the versions from reply.
+    pub fn versions(&self) -> &[ObjectVersion] {
+        &self.versions
+    }
+
+    /// Get the mutable versions from reply.
+    pub fn versions_mut(&mut self) -> &mut Vec<ObjectVersion> {
+        &mut self.versions
+    }
+
+    /// Get the key marker of next page.
+    pub fn next_key_marker(&self) -> Option<&str> {
+        self.next_key_marker.as_deref()
+    }
+
+    /// Get the version id marker of next page.
+    pub fn next_version_id_marker(&self) -> Option<&str> {
+        self.next_version_id_marker.as_deref()
+    }
+
+    /// Consume reply to get the versions.
+    pub fn into_versions(self) -> Vec<ObjectVersion> {
+        self.versions
+    }
+}
+
 /// Reply for `presign` operation.
 #[derive(Debug, Clone)]
 pub struct RpPresign {
@@ -200,18 +373,38 @@ impl RpStat {
 #[derive(Debug, Clone, Default)]
 pub struct RpWrite {
     written: u64,
+    etag: Option<String>,
 }
 
 impl RpWrite {
     /// Create a new reply for write.
     pub fn new(written: u64) -> Self {
-        Self { written }
+        Self {
+            written,
+            etag: None,
+        }
     }
 
     /// Get the written size (in bytes) of write operation.
     pub fn written(&self) -> u64 {
         self.written
     }
+
+    /// Set the etag of written object.
+    ///
+    /// Surrounding quotes should be stripped, see [`ObjectMetadata::etag`]
+    /// for more details.
+    pub fn with_etag(mut self, etag: &str) -> Self {
+        self.etag = Some(etag.to_string());
+        self
+    }
+
+    /// Get the etag of written object.
+    ///
+    /// `None` means the service doesn't return etag in write response.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
 }
 
 #[cfg(test)]
@@ -39,6 +39,35 @@ impl<T: Accessor + 'static> ErrorContextWrapper<T> {
         let meta = inner.metadata();
         Self { meta, inner }
     }
+
+    /// Reject paths that go beyond root so that services never access
+    /// data outside of root.
+    fn check_path(&self, op: Operation, path: &str) -> Result<()> {
+        if path_escapes_root(path) {
+            return Err(
+                Error::new(ErrorKind::InvalidPath, "path goes beyond root of service")
+                    .with_operation(op.into_static())
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Empty range can't be sent as `Range` header, reject it before
+    /// reaching services.
+    fn check_range(&self, op: Operation, path: &str, br: BytesRange) -> Result<()> {
+        if br.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "read range is empty")
+                .with_operation(op.into_static())
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+                .with_context("range", br.to_string()));
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -48,6 +77,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
     }
 
     async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.check_path(Operation::Create, path)?;
         self.inner.create(path, args).await.map_err(|err| {
             err.with_operation(Operation::Create.into_static())
                 .with_context("service", self.meta.scheme())
@@ -56,7 +86,9 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
+        self.check_path(Operation::Read, path)?;
         let br = args.range();
+        self.check_range(Operation::Read, path, br)?;
         self.inner.read(path, args).await.map_err(|err| {
             err.with_operation(Operation::Read.into_static())
                 .with_context("service", self.meta.scheme())
@@ -66,6 +98,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
     }
 
     async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
+        self.check_path(Operation::Write, path)?;
         self.inner.write(path, args, r).await.map_err(|err| {
             err.with_operation(Operation::Write.into_static())
                 .with_context("service", self.meta.scheme())
@@ -74,6 +107,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.check_path(Operation::Stat, path)?;
         self.inner.stat(path, args).await.map_err(|err| {
             err.with_operation(Operation::Stat.into_static())
                 .with_context("service", self.meta.scheme())
@@ -82,6 +116,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.check_path(Operation::Delete, path)?;
         self.inner.delete(path, args).await.map_err(|err| {
             err.with_operation(Operation::Delete.into_static())
                 .with_context("service", self.meta.scheme())
@@ -89,7 +124,30 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
         })
     }
 
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.check_path(Operation::Copy, from)?;
+        self.check_path(Operation::Copy, to)?;
+        self.inner.copy(from, to, args).await.map_err(|err| {
+            err.with_operation(Operation::Copy.into_static())
+                .with_context("service", self.meta.scheme())
+                .with_context("from", from)
+                .with_context("to", to)
+        })
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.check_path(Operation::Rename, from)?;
+        self.check_path(Operation::Rename, to)?;
+        self.inner.rename(from, to, args).await.map_err(|err| {
+            err.with_operation(Operation::Rename.into_static())
+                .with_context("service", self.meta.scheme())
+                .with_context("from", from)
+                .with_context("to", to)
+        })
+    }
+
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
+        self.check_path(Operation::List, path)?;
         self.inner
             .list(path, args)
             .await
@@ -117,6 +175,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
     }
 
     fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.check_path(Operation::Presign, path)?;
         self.inner.presign(path, args).map_err(|err| {
             err.with_operation(Operation::Presign.into_static())
                 .with_context("service", self.meta.scheme())
@@ -129,6 +188,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
         path: &str,
         args: OpCreateMultipart,
     ) -> Result<RpCreateMultipart> {
+        self.check_path(Operation::CreateMultipart, path)?;
         self.inner
             .create_multipart(path, args)
             .await
@@ -145,6 +205,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
         args: OpWriteMultipart,
         r: BytesReader,
     ) -> Result<RpWriteMultipart> {
+        self.check_path(Operation::WriteMultipart, path)?;
         self.inner
             .write_multipart(path, args, r)
             .await
@@ -160,6 +221,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
         path: &str,
         args: OpCompleteMultipart,
     ) -> Result<RpCompleteMultipart> {
+        self.check_path(Operation::CompleteMultipart, path)?;
         self.inner
             .complete_multipart(path, args)
             .await
@@ -175,6 +237,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
         path: &str,
         args: OpAbortMultipart,
     ) -> Result<RpAbortMultipart> {
+        self.check_path(Operation::AbortMultipart, path)?;
         self.inner.abort_multipart(path, args).await.map_err(|err| {
             err.with_operation(Operation::AbortMultipart.into_static())
                 .with_context("service", self.meta.scheme())
@@ -182,7 +245,68 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
         })
     }
 
+    async fn list_multipart(&self, path: &str, args: OpListMultipart) -> Result<RpListMultipart> {
+        self.check_path(Operation::ListMultipart, path)?;
+        self.inner.list_multipart(path, args).await.map_err(|err| {
+            err.with_operation(Operation::ListMultipart.into_static())
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        self.check_path(Operation::ListVersions, path)?;
+        self.inner.list_versions(path, args).await.map_err(|err| {
+            err.with_operation(Operation::ListVersions.into_static())
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.check_path(Operation::GetTags, path)?;
+        self.inner.get_tags(path, args).await.map_err(|err| {
+            err.with_operation(Operation::GetTags.into_static())
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        self.check_path(Operation::SetTags, path)?;
+        self.inner.set_tags(path, args).await.map_err(|err| {
+            err.with_operation(Operation::SetTags.into_static())
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn delete_tags(&self, path: &str, args: OpDeleteTags) -> Result<RpDeleteTags> {
+        self.check_path(Operation::DeleteTags, path)?;
+        self.inner.delete_tags(path, args).await.map_err(|err| {
+            err.with_operation(Operation::DeleteTags.into_static())
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        match args.operation() {
+            BatchOperations::Delete(ops) => {
+                for (path, _) in ops {
+                    self.check_path(Operation::Batch, path)?;
+                }
+            }
+        }
+
+        self.inner.batch(args).await.map_err(|err| {
+            err.with_operation(Operation::Batch.into_static())
+                .with_context("service", self.meta.scheme())
+        })
+    }
+
     fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.check_path(Operation::BlockingCreate, path)?;
         self.inner.blocking_create(path, args).map_err(|err| {
             err.with_operation(Operation::BlockingCreate.into_static())
                 .with_context("service", self.meta.scheme())
@@ -191,6 +315,8 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, BlockingBytesReader)> {
+        self.check_path(Operation::BlockingRead, path)?;
+        self.check_range(Operation::BlockingRead, path, args.range())?;
         self.inner.blocking_read(path, args).map_err(|err| {
             err.with_operation(Operation::BlockingRead.into_static())
                 .with_context("service", self.meta.scheme())
@@ -199,6 +325,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite, r: BlockingBytesReader) -> Result<RpWrite> {
+        self.check_path(Operation::BlockingWrite, path)?;
         self.inner.blocking_write(path, args, r).map_err(|err| {
             err.with_operation(Operation::BlockingWrite.into_static())
                 .with_context("service", self.meta.scheme())
@@ -207,6 +334,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.check_path(Operation::BlockingStat, path)?;
         self.inner.blocking_stat(path, args).map_err(|err| {
             err.with_operation(Operation::BlockingStat.into_static())
                 .with_context("service", self.meta.scheme())
@@ -215,6 +343,7 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.check_path(Operation::BlockingDelete, path)?;
         self.inner.blocking_delete(path, args).map_err(|err| {
             err.with_operation(Operation::BlockingDelete.into_static())
                 .with_context("service", self.meta.scheme())
@@ -222,7 +351,19 @@ impl<T: Accessor + 'static> Accessor for ErrorContextWrapper<T> {
         })
     }
 
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.check_path(Operation::BlockingCopy, from)?;
+        self.check_path(Operation::BlockingCopy, to)?;
+        self.inner.blocking_copy(from, to, args).map_err(|err| {
+            err.with_operation(Operation::BlockingCopy.into_static())
+                .with_context("service", self.meta.scheme())
+                .with_context("from", from)
+                .with_context("to", to)
+        })
+    }
+
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
+        self.check_path(Operation::BlockingList, path)?;
         self.inner.blocking_list(path, args).map_err(|err| {
             err.with_operation(Operation::BlockingList.into_static())
                 .with_context("service", self.meta.scheme())
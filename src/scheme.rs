@@ -17,6 +17,22 @@ use std::fmt::Formatter;
 use std::str::FromStr;
 
 use crate::Error;
+use crate::ErrorKind;
+
+/// Services that are only available with given feature enabled.
+const OPTIONAL_SERVICES: &[(&str, &str)] = &[
+    ("ftp", "services-ftp"),
+    ("ftps", "services-ftp"),
+    ("hdfs", "services-hdfs"),
+    ("ipfs", "services-ipfs"),
+    ("ipns", "services-ipfs"),
+    ("memcached", "services-memcached"),
+    ("moka", "services-moka"),
+    ("postgres", "services-postgresql"),
+    ("postgresql", "services-postgresql"),
+    ("redis", "services-redis"),
+    ("rocksdb", "services-rocksdb"),
+];
 
 /// Services that OpenDAL supports
 ///
@@ -28,10 +44,16 @@ use crate::Error;
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum Scheme {
+    /// [alluxio][crate::services::alluxio]: Alluxio services.
+    Alluxio,
     /// [azblob][crate::services::azblob]: Azure Storage Blob services.
     Azblob,
     /// [azdfs][crate::services::azdfs]: Azure Data Lake Storage Gen2.
     Azdfs,
+    /// [b2][crate::services::b2]: Backblaze B2 services.
+    B2,
+    /// [cos][crate::services::cos]: Tencent Cloud COS services.
+    Cos,
     /// [fs][crate::services::fs]: POSIX alike file system.
     Fs,
     /// [gcs][crate::services::gcs]: Google Cloud Storage backend.
@@ -49,6 +71,9 @@ pub enum Scheme {
     Ipfs,
     /// [ipmfs][crate::services::ipmfs]: IPFS mutable file system
     Ipmfs,
+    /// [memcached][crate::services::memcached]: Memcached services.
+    #[cfg(feature = "services-memcached")]
+    Memcached,
     /// [memory][crate::services::memory]: In memory backend support.
     Memory,
     /// [moka][crate::services::moka]: moka backend support.
@@ -56,6 +81,9 @@ pub enum Scheme {
     Moka,
     /// [obs][crate::services::obs]: Huawei Cloud OBS services.
     Obs,
+    /// [postgresql][crate::services::postgresql]: PostgreSQL services
+    #[cfg(feature = "services-postgresql")]
+    Postgresql,
     /// [redis][crate::services::redis]: Redis services
     #[cfg(feature = "services-redis")]
     Redis,
@@ -64,8 +92,12 @@ pub enum Scheme {
     Rocksdb,
     /// [s3][crate::services::s3]: AWS S3 alike services.
     S3,
+    /// [swift][crate::services::swift]: OpenStack Swift services.
+    Swift,
     /// [oss][crate::services::oss]: Aliyun Object Storage Services
     Oss,
+    /// [webdav][crate::services::webdav]: WebDAV services.
+    Webdav,
     /// Custom that allow users to implement services outside of OpenDAL.
     ///
     /// # NOTE
@@ -80,6 +112,46 @@ impl Scheme {
     pub fn into_static(self) -> &'static str {
         self.into()
     }
+
+    /// Returns all services that have been compiled in.
+    ///
+    /// Services behind features like `services-rocksdb` are only included
+    /// while the feature is enabled.
+    pub fn enabled() -> &'static [Scheme] {
+        &[
+            Scheme::Alluxio,
+            Scheme::Azblob,
+            Scheme::Azdfs,
+            Scheme::B2,
+            Scheme::Cos,
+            Scheme::Fs,
+            #[cfg(feature = "services-ftp")]
+            Scheme::Ftp,
+            Scheme::Gcs,
+            #[cfg(feature = "services-hdfs")]
+            Scheme::Hdfs,
+            Scheme::Http,
+            #[cfg(feature = "services-ipfs")]
+            Scheme::Ipfs,
+            Scheme::Ipmfs,
+            #[cfg(feature = "services-memcached")]
+            Scheme::Memcached,
+            Scheme::Memory,
+            #[cfg(feature = "services-moka")]
+            Scheme::Moka,
+            Scheme::Obs,
+            Scheme::Oss,
+            #[cfg(feature = "services-postgresql")]
+            Scheme::Postgresql,
+            #[cfg(feature = "services-redis")]
+            Scheme::Redis,
+            #[cfg(feature = "services-rocksdb")]
+            Scheme::Rocksdb,
+            Scheme::S3,
+            Scheme::Swift,
+            Scheme::Webdav,
+        ]
+    }
 }
 
 impl Default for Scheme {
@@ -91,8 +163,11 @@ impl Default for Scheme {
 impl Display for Scheme {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
+            Scheme::Alluxio => write!(f, "alluxio"),
             Scheme::Azblob => write!(f, "azblob"),
             Scheme::Azdfs => write!(f, "azdfs"),
+            Scheme::B2 => write!(f, "b2"),
+            Scheme::Cos => write!(f, "cos"),
             Scheme::Fs => write!(f, "fs"),
             #[cfg(feature = "services-hdfs")]
             Scheme::Hdfs => write!(f, "hdfs"),
@@ -103,29 +178,43 @@ impl Display for Scheme {
             #[cfg(feature = "services-ipfs")]
             Scheme::Ipfs => write!(f, "ipfs"),
             Scheme::Ipmfs => write!(f, "ipmfs"),
+            #[cfg(feature = "services-memcached")]
+            Scheme::Memcached => write!(f, "memcached"),
             Scheme::Memory => write!(f, "memory"),
             #[cfg(feature = "services-moka")]
             Scheme::Moka => write!(f, "moka"),
             Scheme::Obs => write!(f, "obs"),
+            #[cfg(feature = "services-postgresql")]
+            Scheme::Postgresql => write!(f, "postgresql"),
             #[cfg(feature = "services-redis")]
             Scheme::Redis => write!(f, "redis"),
             #[cfg(feature = "services-rocksdb")]
             Scheme::Rocksdb => write!(f, "rocksdb"),
             Scheme::S3 => write!(f, "s3"),
+            Scheme::Swift => write!(f, "swift"),
             Scheme::Oss => write!(f, "oss"),
+            Scheme::Webdav => write!(f, "webdav"),
             Scheme::Custom(v) => write!(f, "{v}"),
         }
     }
 }
 
+/// Parse scheme from its name, case-insensitive.
+///
+/// Unknown services or services whose feature is not enabled will return
+/// [`ErrorKind::ConfigInvalid`]. [`Scheme::Custom`] can't be parsed and
+/// should be constructed directly.
 impl FromStr for Scheme {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s.to_lowercase();
         match s.as_str() {
+            "alluxio" => Ok(Scheme::Alluxio),
             "azblob" => Ok(Scheme::Azblob),
             "azdfs" => Ok(Scheme::Azdfs),
+            "b2" => Ok(Scheme::B2),
+            "cos" => Ok(Scheme::Cos),
             "fs" => Ok(Scheme::Fs),
             "gcs" => Ok(Scheme::Gcs),
             #[cfg(feature = "services-hdfs")]
@@ -136,17 +225,33 @@ impl FromStr for Scheme {
             #[cfg(feature = "services-ipfs")]
             "ipfs" | "ipns" => Ok(Scheme::Ipfs),
             "ipmfs" => Ok(Scheme::Ipmfs),
+            #[cfg(feature = "services-memcached")]
+            "memcached" => Ok(Scheme::Memcached),
             "memory" => Ok(Scheme::Memory),
             #[cfg(feature = "services-moka")]
             "moka" => Ok(Scheme::Moka),
             "obs" => Ok(Scheme::Obs),
+            #[cfg(feature = "services-postgresql")]
+            "postgresql" | "postgres" => Ok(Scheme::Postgresql),
             #[cfg(feature = "services-redis")]
             "redis" => Ok(Scheme::Redis),
             #[cfg(feature = "services-rocksdb")]
             "rocksdb" => Ok(Scheme::Rocksdb),
             "s3" => Ok(Scheme::S3),
+            "swift" => Ok(Scheme::Swift),
             "oss" => Ok(Scheme::Oss),
-            _ => Ok(Scheme::Custom(Box::leak(s.into_boxed_str()))),
+            "webdav" => Ok(Scheme::Webdav),
+            v => match OPTIONAL_SERVICES.iter().find(|(name, _)| *name == v) {
+                Some((_, feature)) => Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    &format!("service is not enabled, please enable feature {feature}"),
+                )
+                .with_operation("Scheme::from_str")
+                .with_context("service", v)),
+                None => Err(Error::new(ErrorKind::ConfigInvalid, "service is unknown")
+                    .with_operation("Scheme::from_str")
+                    .with_context("service", v)),
+            },
         }
     }
 }
@@ -154,8 +259,11 @@ impl FromStr for Scheme {
 impl From<Scheme> for &'static str {
     fn from(v: Scheme) -> Self {
         match v {
+            Scheme::Alluxio => "alluxio",
             Scheme::Azblob => "azblob",
             Scheme::Azdfs => "azdfs",
+            Scheme::B2 => "b2",
+            Scheme::Cos => "cos",
             Scheme::Fs => "fs",
             Scheme::Gcs => "gcs",
             #[cfg(feature = "services-hdfs")]
@@ -166,16 +274,22 @@ impl From<Scheme> for &'static str {
             #[cfg(feature = "services-ipfs")]
             Scheme::Ipfs => "ipfs",
             Scheme::Ipmfs => "ipmfs",
+            #[cfg(feature = "services-memcached")]
+            Scheme::Memcached => "memcached",
             Scheme::Memory => "memory",
             #[cfg(feature = "services-moka")]
             Scheme::Moka => "moka",
             Scheme::Obs => "obs",
+            #[cfg(feature = "services-postgresql")]
+            Scheme::Postgresql => "postgresql",
             #[cfg(feature = "services-redis")]
             Scheme::Redis => "redis",
             #[cfg(feature = "services-rocksdb")]
-            Scheme::Rocksdb => "service-rocksdb",
+            Scheme::Rocksdb => "rocksdb",
             Scheme::S3 => "s3",
+            Scheme::Swift => "swift",
             Scheme::Oss => "oss",
+            Scheme::Webdav => "webdav",
             Scheme::Custom(v) => v,
         }
     }
@@ -186,3 +300,39 @@ impl From<Scheme> for String {
         v.into_static().to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for scheme in Scheme::enabled() {
+            let name = scheme.to_string();
+            assert_eq!(name, scheme.into_static());
+            assert_eq!(&name.parse::<Scheme>().expect("must succeed"), scheme);
+            assert_eq!(
+                &name.to_uppercase().parse::<Scheme>().expect("must succeed"),
+                scheme
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        let err = "unknown".parse::<Scheme>().expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert_eq!(err.context("service"), Some("unknown"));
+
+        // Optional services are either enabled or rejected with the feature.
+        for (name, feature) in OPTIONAL_SERVICES {
+            match name.parse::<Scheme>() {
+                Ok(v) => assert!(Scheme::enabled().contains(&v), "{name}"),
+                Err(err) => {
+                    assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+                    assert!(err.to_string().contains(feature), "{err}");
+                }
+            }
+        }
+    }
+}
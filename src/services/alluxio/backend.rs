@@ -0,0 +1,531 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::io;
+use futures::AsyncReadExt;
+use http::header::CONTENT_LENGTH;
+use http::header::CONTENT_TYPE;
+use http::header::RANGE;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use time::OffsetDateTime;
+
+use super::dir_stream::DirStream;
+use super::error::parse_error;
+use super::error::parse_json_deserialize_error;
+use super::error::parse_json_serialize_error;
+use crate::raw::*;
+use crate::*;
+
+/// Backend for alluxio service
+#[derive(Clone)]
+pub struct Backend {
+    root: String,
+    endpoint: String,
+    client: HttpClient,
+}
+
+impl fmt::Debug for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Backend")
+            .field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .finish()
+    }
+}
+
+impl Backend {
+    pub(crate) fn new(root: String, client: HttpClient, endpoint: String) -> Self {
+        Self {
+            root,
+            client,
+            endpoint,
+        }
+    }
+}
+
+#[async_trait]
+impl Accessor for Backend {
+    fn metadata(&self) -> AccessorMetadata {
+        let mut am = AccessorMetadata::default();
+        am.set_scheme(Scheme::Alluxio)
+            .set_root(&self.root)
+            .set_capabilities(
+                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::List,
+            );
+
+        am
+    }
+
+    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        match args.mode() {
+            ObjectMode::DIR => {
+                let resp = self.alluxio_create_directory(path).await?;
+
+                match resp.status() {
+                    StatusCode::OK | StatusCode::NO_CONTENT => {
+                        resp.into_body().consume().await?;
+                        Ok(RpCreate::default())
+                    }
+                    _ => Err(parse_error(resp).await?),
+                }
+            }
+            ObjectMode::FILE => {
+                let id = self.create_file(path).await?;
+                self.close_stream(id).await?;
+                Ok(RpCreate::default())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
+        let meta = self.stat(path, OpStat::new()).await?.into_metadata();
+        if meta.mode() != ObjectMode::FILE {
+            return Err(
+                Error::new(ErrorKind::ObjectIsADirectory, "read path is not a file")
+                    .with_operation("alluxio::read")
+                    .with_context("path", path),
+            );
+        }
+
+        let total = meta.content_length();
+        let range = args.range();
+        let (offset, size) = match (range.offset(), range.size()) {
+            (Some(offset), size) => {
+                let offset = offset.min(total);
+                let size = size.unwrap_or(total).min(total - offset);
+                (offset, size)
+            }
+            (None, Some(size)) => {
+                let size = size.min(total);
+                (total - size, size)
+            }
+            (None, None) => (0, total),
+        };
+
+        let meta = meta.with_content_length(size);
+        if size == 0 {
+            return Ok((
+                RpRead::with_metadata(meta),
+                Box::new(io::Cursor::new(vec![])),
+            ));
+        }
+
+        let range = if size == total {
+            BytesRange::default()
+        } else {
+            BytesRange::new(Some(offset), Some(size))
+        };
+
+        let id = self.open_file(path).await?;
+        let resp = self.alluxio_stream_read(id, range).await?;
+
+        match resp.status() {
+            StatusCode::PARTIAL_CONTENT => {
+                Ok((RpRead::with_metadata(meta), resp.into_body().reader()))
+            }
+            StatusCode::OK => {
+                let mut r = resp.into_body().reader();
+
+                // The proxy returns the whole file, skip to the offset by ourselves.
+                if offset > 0 {
+                    let n = io::copy((&mut r).take(offset), &mut io::sink())
+                        .await
+                        .map_err(|err| {
+                            Error::new(ErrorKind::Unexpected, "skip to range offset")
+                                .with_operation("alluxio::read")
+                                .with_context("path", path)
+                                .set_source(err)
+                        })?;
+                    if n != offset {
+                        return Err(Error::new(
+                            ErrorKind::Unexpected,
+                            "stream is shorter than range offset",
+                        )
+                        .with_operation("alluxio::read")
+                        .with_context("path", path)
+                        .with_context("expect", offset.to_string())
+                        .with_context("actual", n.to_string()));
+                    }
+                }
+
+                Ok((RpRead::with_metadata(meta), Box::new(r.take(size))))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
+        let id = self.create_file(path).await?;
+
+        let resp = self
+            .alluxio_stream_write(id, args.size(), AsyncBody::Reader(r))
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let written: u64 =
+                    serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+                if written != args.size() {
+                    return Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "written bytes mismatch with write size",
+                    )
+                    .with_operation("alluxio::write")
+                    .with_context("path", path)
+                    .with_context("expect", args.size().to_string())
+                    .with_context("actual", written.to_string()));
+                }
+            }
+            _ => return Err(parse_error(resp).await?),
+        }
+
+        self.close_stream(id).await?;
+        Ok(RpWrite::new(args.size()))
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(ObjectMetadata::new(ObjectMode::DIR)));
+        }
+
+        let resp = self.alluxio_get_status(path).await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let info: FileInfo =
+                    serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+                Ok(RpStat::new(info.metadata()?))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let resp = self.alluxio_delete(path).await?;
+
+        match resp.status() {
+            StatusCode::OK | StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            _ => {
+                let err = parse_error(resp).await?;
+                match err.kind() {
+                    ErrorKind::ObjectNotFound => Ok(RpDelete::default()),
+                    _ => Err(err),
+                }
+            }
+        }
+    }
+
+    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, ObjectPager)> {
+        Ok((
+            RpList::default(),
+            Box::new(DirStream::new(Arc::new(self.clone()), &self.root, path)),
+        ))
+    }
+}
+
+impl Backend {
+    /// Create file and returns the id of output stream.
+    ///
+    /// Alluxio doesn't allow creating an existing file, so we will delete
+    /// it and create again.
+    async fn create_file(&self, path: &str) -> Result<u64> {
+        let mut resp = self.alluxio_create_file(path).await?;
+
+        if resp.status() != StatusCode::OK {
+            let err = parse_error(resp).await?;
+            if err.context("status_code") != Some("ALREADY_EXISTS") {
+                return Err(err.with_operation("alluxio::create_file"));
+            }
+
+            self.delete(path, OpDelete::new()).await?;
+            resp = self.alluxio_create_file(path).await?;
+        }
+
+        match resp.status() {
+            StatusCode::OK => parse_stream_id(resp).await,
+            _ => Err(parse_error(resp)
+                .await?
+                .with_operation("alluxio::create_file")),
+        }
+    }
+
+    /// Open file and returns the id of input stream.
+    async fn open_file(&self, path: &str) -> Result<u64> {
+        let resp = self.alluxio_open_file(path).await?;
+
+        match resp.status() {
+            StatusCode::OK => parse_stream_id(resp).await,
+            _ => Err(parse_error(resp)
+                .await?
+                .with_operation("alluxio::open_file")),
+        }
+    }
+
+    /// Close the stream, the file will be completed if it's an output stream.
+    async fn close_stream(&self, id: u64) -> Result<()> {
+        let resp = self.alluxio_stream_close(id).await?;
+
+        match resp.status() {
+            StatusCode::OK | StatusCode::NO_CONTENT => resp.into_body().consume().await,
+            _ => Err(parse_error(resp)
+                .await?
+                .with_operation("alluxio::close_stream")),
+        }
+    }
+
+    /// Build url like `http://127.0.0.1:39999/api/v1/paths//path/to/file/get-status`.
+    fn path_url(&self, path: &str, action: &str) -> String {
+        let p = build_rooted_abs_path(&self.root, path);
+        // Alluxio takes `/path/to/dir/` and `/path/to/dir` as the same path.
+        let p = match p.trim_end_matches('/') {
+            "" => "/",
+            v => v,
+        };
+
+        format!(
+            "{}/api/v1/paths/{}/{}",
+            self.endpoint,
+            percent_encode_path(p),
+            action
+        )
+    }
+
+    async fn send_with_options(
+        &self,
+        url: &str,
+        options: serde_json::Value,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let bs = serde_json::to_vec(&options).map_err(parse_json_serialize_error)?;
+
+        let req = Request::post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(AsyncBody::Bytes(bs.into()))
+            .map_err(new_request_build_error)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn alluxio_get_status(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = self.path_url(path, "get-status");
+
+        self.send_with_options(&url, json!({})).await
+    }
+
+    pub(crate) async fn alluxio_list_status(
+        &self,
+        path: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = self.path_url(path, "list-status");
+
+        self.send_with_options(&url, json!({})).await
+    }
+
+    async fn alluxio_create_directory(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = self.path_url(path, "create-directory");
+
+        self.send_with_options(&url, json!({"recursive": true, "allowExists": true}))
+            .await
+    }
+
+    async fn alluxio_create_file(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = self.path_url(path, "create-file");
+
+        self.send_with_options(&url, json!({"recursive": true}))
+            .await
+    }
+
+    async fn alluxio_open_file(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = self.path_url(path, "open-file");
+
+        self.send_with_options(&url, json!({})).await
+    }
+
+    async fn alluxio_delete(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let url = self.path_url(path, "delete");
+
+        self.send_with_options(&url, json!({"recursive": false}))
+            .await
+    }
+
+    async fn alluxio_stream_read(
+        &self,
+        id: u64,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{}/api/v1/streams/{}/read", self.endpoint, id);
+
+        let mut req = Request::post(&url);
+        if let Some(range_header) = range.to_header() {
+            req = req.header(RANGE, range_header);
+        }
+
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn alluxio_stream_write(
+        &self,
+        id: u64,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{}/api/v1/streams/{}/write", self.endpoint, id);
+
+        let req = Request::post(&url)
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .header(CONTENT_LENGTH, size)
+            .body(body)
+            .map_err(new_request_build_error)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn alluxio_stream_close(&self, id: u64) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{}/api/v1/streams/{}/close", self.endpoint, id);
+
+        let req = Request::post(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.client.send_async(req).await
+    }
+}
+
+/// Alluxio returns the id of created stream as a plain number.
+async fn parse_stream_id(resp: Response<IncomingAsyncBody>) -> Result<u64> {
+    let bs = resp.into_body().bytes().await?;
+    serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)
+}
+
+/// Status of file or directory returned by `get-status` and `list-status`.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct FileInfo {
+    pub path: String,
+    pub length: u64,
+    pub folder: bool,
+    pub last_modification_time_ms: i64,
+}
+
+impl FileInfo {
+    pub fn metadata(&self) -> Result<ObjectMetadata> {
+        let mode = if self.folder {
+            ObjectMode::DIR
+        } else {
+            ObjectMode::FILE
+        };
+
+        let mut meta = ObjectMetadata::new(mode);
+        if !self.folder {
+            meta.set_content_length(self.length);
+        }
+        if self.last_modification_time_ms > 0 {
+            let t = OffsetDateTime::from_unix_timestamp_nanos(
+                self.last_modification_time_ms as i128 * 1_000_000,
+            )
+            .map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "parse last modification time")
+                    .with_context("value", self.last_modification_time_ms.to_string())
+                    .set_source(e)
+            })?;
+            meta.set_last_modified(t);
+        }
+
+        Ok(meta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_info() {
+        let bs = r#"{
+  "fileId": 33554431,
+  "name": "file",
+  "path": "/dir/file",
+  "length": 14,
+  "blockSizeBytes": 67108864,
+  "creationTimeMs": 1672531200000,
+  "completed": true,
+  "folder": false,
+  "lastModificationTimeMs": 1672531200000
+}"#;
+
+        let info: FileInfo = serde_json::from_str(bs).expect("must success");
+        assert_eq!(info.path, "/dir/file");
+
+        let meta = info.metadata().expect("must success");
+        assert_eq!(meta.mode(), ObjectMode::FILE);
+        assert_eq!(meta.content_length(), 14);
+        assert_eq!(
+            meta.last_modified().map(|v| v.unix_timestamp()),
+            Some(1672531200)
+        );
+
+        let bs = r#"{"path": "/dir", "length": 2, "folder": true}"#;
+        let info: FileInfo = serde_json::from_str(bs).expect("must success");
+        let meta = info.metadata().expect("must success");
+        assert_eq!(meta.mode(), ObjectMode::DIR);
+        assert_eq!(meta.last_modified(), None);
+    }
+
+    #[test]
+    fn test_path_url() {
+        let backend = Backend::new(
+            "/root/".to_string(),
+            HttpClient::new(),
+            "http://127.0.0.1:39999".to_string(),
+        );
+
+        assert_eq!(
+            backend.path_url("dir/file", "get-status"),
+            "http://127.0.0.1:39999/api/v1/paths//root/dir/file/get-status"
+        );
+        assert_eq!(
+            backend.path_url("dir/", "list-status"),
+            "http://127.0.0.1:39999/api/v1/paths//root/dir/list-status"
+        );
+
+        let backend = Backend::new(
+            "/".to_string(),
+            HttpClient::new(),
+            "http://127.0.0.1:39999".to_string(),
+        );
+        assert_eq!(
+            backend.path_url("/", "list-status"),
+            "http://127.0.0.1:39999/api/v1/paths///list-status"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use super::backend::Backend;
+use super::backend::FileInfo;
+use super::error::parse_error;
+use super::error::parse_json_deserialize_error;
+use crate::raw::*;
+use crate::ErrorKind;
+use crate::Result;
+
+pub struct DirStream {
+    backend: Arc<Backend>,
+    root: String,
+    path: String,
+    consumed: bool,
+}
+
+impl DirStream {
+    pub fn new(backend: Arc<Backend>, root: &str, path: &str) -> Self {
+        Self {
+            backend,
+            root: root.to_string(),
+            path: path.to_string(),
+            consumed: false,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectPage for DirStream {
+    async fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
+        if self.consumed {
+            return Ok(None);
+        }
+
+        let resp = self.backend.alluxio_list_status(&self.path).await?;
+
+        if resp.status() != StatusCode::OK {
+            let err = parse_error(resp).await?;
+            // List a not exist dir should return empty.
+            return match err.kind() {
+                ErrorKind::ObjectNotFound => {
+                    self.consumed = true;
+                    Ok(None)
+                }
+                _ => Err(err),
+            };
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let infos: Vec<FileInfo> =
+            serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+        // Alluxio returns all entries in one response.
+        self.consumed = true;
+
+        let mut entries = Vec::with_capacity(infos.len());
+        for info in infos {
+            let mut path = build_rel_path(&self.root, &info.path);
+            if info.folder {
+                path.push('/');
+            }
+
+            entries.push(ObjectEntry::new(&path, info.metadata()?.with_complete()));
+        }
+
+        Ok(Some(entries))
+    }
+}
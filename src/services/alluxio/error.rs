@@ -0,0 +1,164 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::de;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Error returned by alluxio proxy like:
+///
+/// ```json
+/// {"statusCode":"NOT_FOUND","message":"Path \"/abc\" does not exist."}
+/// ```
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+struct AlluxioError {
+    #[serde(rename = "statusCode")]
+    status_code: String,
+    message: String,
+}
+
+/// Parse error response into Error.
+///
+/// Alluxio proxy returns `500 Internal Server Error` for most errors, so
+/// we will use the `statusCode` in body to decide the error kind. The
+/// `statusCode` will be carried in context too.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    let alluxio_error = de::from_slice::<AlluxioError>(&bs)
+        .ok()
+        .filter(|v| !v.status_code.is_empty());
+
+    let (kind, retryable) = match &alluxio_error {
+        Some(ae) => parse_status_code(&ae.status_code),
+        None => match parts.status {
+            StatusCode::NOT_FOUND => (ErrorKind::ObjectNotFound, false),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                (ErrorKind::ObjectPermissionDenied, false)
+            }
+            StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+            _ => (ErrorKind::Unexpected, false),
+        },
+    };
+
+    let message = match &alluxio_error {
+        Some(ae) => ae.message.clone(),
+        None => String::from_utf8_lossy(&bs).into_owned(),
+    };
+
+    let mut err = Error::new(kind, &message).with_context("response", format!("{:?}", parts));
+    if let Some(ae) = alluxio_error {
+        err = err.with_context("status_code", ae.status_code);
+    }
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+/// Map alluxio's status code into error kind and whether it's retryable.
+fn parse_status_code(code: &str) -> (ErrorKind, bool) {
+    match code {
+        "NOT_FOUND" => (ErrorKind::ObjectNotFound, false),
+        "PERMISSION_DENIED" | "UNAUTHENTICATED" => (ErrorKind::ObjectPermissionDenied, false),
+        "UNIMPLEMENTED" => (ErrorKind::Unsupported, false),
+        "UNAVAILABLE" | "DEADLINE_EXCEEDED" | "RESOURCE_EXHAUSTED" | "ABORTED" => {
+            (ErrorKind::Unexpected, true)
+        }
+        _ => (ErrorKind::Unexpected, false),
+    }
+}
+
+pub fn parse_json_deserialize_error(e: serde_json::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "deserialize json").set_source(e)
+}
+
+pub fn parse_json_serialize_error(e: serde_json::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "serialize json").set_source(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::io::Cursor;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parse_error() {
+        let cases = vec![
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                r#"{"statusCode":"NOT_FOUND","message":"Path \"/abc\" does not exist."}"#,
+                ErrorKind::ObjectNotFound,
+                false,
+            ),
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                r#"{"statusCode":"PERMISSION_DENIED","message":"denied"}"#,
+                ErrorKind::ObjectPermissionDenied,
+                false,
+            ),
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                r#"{"statusCode":"UNAVAILABLE","message":"master is not ready"}"#,
+                ErrorKind::Unexpected,
+                true,
+            ),
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                r#"{"statusCode":"ALREADY_EXISTS","message":"exists"}"#,
+                ErrorKind::Unexpected,
+                false,
+            ),
+            (
+                StatusCode::NOT_FOUND,
+                "<html>Not Found</html>",
+                ErrorKind::ObjectNotFound,
+                false,
+            ),
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "",
+                ErrorKind::Unexpected,
+                true,
+            ),
+        ];
+
+        for (status, body, kind, temporary) in cases {
+            let resp = Response::builder()
+                .status(status)
+                .body(IncomingAsyncBody::new(Box::new(Cursor::new(
+                    body.as_bytes(),
+                ))))
+                .expect("must success");
+
+            let err = parse_error(resp).await.expect("must success");
+            assert_eq!(err.kind(), kind, "{body}");
+            assert_eq!(err.is_temporary(), temporary, "{body}");
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Alluxio services support based on [Alluxio REST API](https://docs.alluxio.io/os/user/stable/en/api/REST-API.html).
+//!
+//! Everything will be read and written via the REST API of Alluxio proxy,
+//! so that OpenDAL based tools can take advantage of Alluxio's cache.
+//!
+//! # Configuration
+//!
+//! - `root`: Set the work directory for backend
+//! - `endpoint`: Set the endpoint of Alluxio proxy, default to `http://127.0.0.1:39999`
+//!
+//! You can refer to [`Builder`]'s docs for more information
+//!
+//! # Notes
+//!
+//! Range read will be sent as `Range` header while reading the stream. If
+//! the proxy doesn't respect it, we will skip the leading bytes locally.
+//!
+//! # Environment
+//!
+//! - `OPENDAL_ALLUXIO_ROOT`    optional
+//! - `OPENDAL_ALLUXIO_ENDPOINT`  optional
+//!
+//! # Example
+//!
+//! ## Initiate via environment variables
+//!
+//! Set environment correctly:
+//!
+//! ```shell
+//! export OPENDAL_ALLUXIO_ROOT=/path/to/root
+//! export OPENDAL_ALLUXIO_ENDPOINT=http://127.0.0.1:39999
+//! ```
+//!
+//! ```no_run
+//! use anyhow::Result;
+//! use opendal::Object;
+//! use opendal::Operator;
+//! use opendal::Scheme;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let op: Operator = Operator::from_env(Scheme::Alluxio)?;
+//!
+//!     // create an object handler to start operation on it.
+//!     let _op: Object = op.object("test_file");
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ## Via Builder
+//!
+//! ```no_run
+//! use anyhow::Result;
+//! use opendal::services::alluxio;
+//! use opendal::Object;
+//! use opendal::Operator;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     // create backend builder
+//!     let mut builder = alluxio::Builder::default();
+//!
+//!     // set the endpoint of alluxio proxy for OpenDAL
+//!     builder.endpoint("http://127.0.0.1:39999");
+//!     builder.root("/path/to/dir");
+//!
+//!     let op: Operator = Operator::new(builder.build()?);
+//!
+//!     // Create an object handle to start operation on object.
+//!     let _: Object = op.object("test_file");
+//!
+//!     Ok(())
+//! }
+//! ```
+
+mod backend;
+mod builder;
+pub use builder::Builder;
+
+mod dir_stream;
+pub(super) mod error;
@@ -20,14 +20,22 @@ use std::mem;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use bytes::Buf;
+use bytes::Bytes;
 use http::header::HeaderName;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::HeaderValue;
 use http::Request;
 use http::Response;
 use http::StatusCode;
 use log::debug;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
 use reqsign::AzureStorageSigner;
+use serde::Deserialize;
+use serde::Serialize;
+use uuid::Uuid;
 
 use super::dir_stream::DirStream;
 use super::error::parse_error;
@@ -36,6 +44,8 @@ use crate::raw::*;
 use crate::*;
 
 const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
+const X_MS_BLOB_CONTENT_DISPOSITION: &str = "x-ms-blob-content-disposition";
+const X_MS_BLOB_CACHE_CONTROL: &str = "x-ms-blob-cache-control";
 
 /// Builder for azblob services
 #[derive(Default, Clone)]
@@ -45,6 +55,10 @@ pub struct Builder {
     endpoint: Option<String>,
     account_name: Option<String>,
     account_key: Option<String>,
+
+    http_client: Option<reqwest::Client>,
+    pool_max_idle_per_host: Option<usize>,
+    connect_timeout: Option<std::time::Duration>,
 }
 
 impl Debug for Builder {
@@ -62,6 +76,11 @@ impl Debug for Builder {
             ds.field("account_key", &"<redacted>");
         }
 
+        ds.field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field("connect_timeout", &self.connect_timeout);
+        if self.http_client.is_some() {
+            ds.field("http_client", &"<custom>");
+        }
         ds.finish()
     }
 }
@@ -124,6 +143,16 @@ impl Builder {
         self
     }
 
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "container",
+        "endpoint",
+        "account_name",
+        "account_key",
+        "pool_max_idle_per_host",
+        "connect_timeout",
+    ];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
 
@@ -135,6 +164,14 @@ impl Builder {
                 "endpoint" => builder.endpoint(v),
                 "account_name" => builder.account_name(v),
                 "account_key" => builder.account_key(v),
+                "pool_max_idle_per_host" => match v.parse::<usize>() {
+                    Ok(v) => builder.pool_max_idle_per_host(v),
+                    _ => continue,
+                },
+                "connect_timeout" => match v.parse::<u64>() {
+                    Ok(v) => builder.connect_timeout(std::time::Duration::from_secs(v)),
+                    _ => continue,
+                },
                 _ => continue,
             };
         }
@@ -186,17 +223,19 @@ impl Builder {
 
         let account_name = conn_map.get("AccountName").ok_or_else(|| {
             Error::new(
-                ErrorKind::BackendConfigInvalid,
+                ErrorKind::ConfigInvalid,
                 "connection string must have AccountName",
             )
+            .with_context("field", "account_name")
             .with_operation("Builder::from_connection_string")
         })?;
         builder.account_name(account_name);
         let account_key = conn_map.get("AccountKey").ok_or_else(|| {
             Error::new(
-                ErrorKind::BackendConfigInvalid,
+                ErrorKind::ConfigInvalid,
                 "connection string must have AccountKey",
             )
+            .with_context("field", "account_key")
             .with_operation("Builder::from_connection_string")
         })?;
         builder.account_key(account_key);
@@ -211,6 +250,32 @@ impl Builder {
         Ok(builder)
     }
 
+    /// Set the http client used by this backend.
+    ///
+    /// Connections will be reused across operators that share clones of
+    /// the same `client`. Passing a custom client overrides
+    /// [`Builder::pool_max_idle_per_host`] and [`Builder::connect_timeout`].
+    pub fn http_client(&mut self, client: reqwest::Client) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Set the max idle connections kept alive per host.
+    ///
+    /// This will be ignored if [`Builder::http_client`] is set.
+    pub fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set the timeout for establishing connections.
+    ///
+    /// This will be ignored if [`Builder::http_client`] is set.
+    pub fn connect_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
     /// Consume builder to build an azblob backend.
     pub fn build(&mut self) -> Result<impl Accessor> {
         debug!("backend build started: {:?}", &self);
@@ -221,25 +286,28 @@ impl Builder {
         // Handle endpoint, region and container name.
         let container = match self.container.is_empty() {
             false => Ok(&self.container),
-            true => Err(
-                Error::new(ErrorKind::BackendConfigInvalid, "container is empty")
-                    .with_operation("Builder::build")
-                    .with_context("service", Scheme::Azblob),
-            ),
+            true => Err(Error::new(ErrorKind::ConfigInvalid, "container is empty")
+                .with_context("field", "container")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Azblob)),
         }?;
         debug!("backend use container {}", &container);
 
         let endpoint = match &self.endpoint {
             Some(endpoint) => Ok(endpoint.clone()),
-            None => Err(
-                Error::new(ErrorKind::BackendConfigInvalid, "endpoint is empty")
-                    .with_operation("Builder::build")
-                    .with_context("service", Scheme::Azblob),
-            ),
+            None => Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_context("field", "endpoint")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Azblob)),
         }?;
         debug!("backend use endpoint {}", &container);
 
-        let client = HttpClient::new();
+        let client = match self.http_client.take() {
+            Some(client) => HttpClient::with_client(client),
+            None => {
+                HttpClient::with_pool_options(self.pool_max_idle_per_host, self.connect_timeout)
+            }
+        };
 
         let mut signer_builder = AzureStorageSigner::builder();
         if let (Some(name), Some(key)) = (&self.account_name, &self.account_key) {
@@ -247,7 +315,8 @@ impl Builder {
         }
 
         let signer = signer_builder.build().map_err(|e| {
-            Error::new(ErrorKind::BackendConfigInvalid, "build AzureStorageSigner")
+            Error::new(ErrorKind::ConfigInvalid, "build AzureStorageSigner")
+                .with_context("field", "account_key")
                 .with_operation("Builder::build")
                 .with_context("service", Scheme::Azblob)
                 .with_context("endpoint", &endpoint)
@@ -286,7 +355,14 @@ impl Accessor for Backend {
             .set_root(&self.root)
             .set_name(&self.container)
             .set_capabilities(
-                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::List,
+                AccessorCapability::Read
+                    | AccessorCapability::Write
+                    | AccessorCapability::List
+                    | AccessorCapability::ConditionalWrite
+                    | AccessorCapability::ConditionalRead
+                    | AccessorCapability::ListRecursive
+                    | AccessorCapability::Multipart
+                    | AccessorCapability::Tagging,
             );
 
         am
@@ -311,7 +387,7 @@ impl Accessor for Backend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
-        let resp = self.azblob_get_blob(path, args.range()).await?;
+        let resp = self.azblob_get_blob(path, &args).await?;
 
         let status = resp.status();
 
@@ -331,6 +407,25 @@ impl Accessor for Backend {
             args.content_type(),
             AsyncBody::Reader(r),
         )?;
+        // Blob properties are set via `x-ms-blob-*` headers, and will be
+        // returned as standard headers while reading.
+        for (key, value) in [
+            (X_MS_BLOB_CONTENT_DISPOSITION, args.content_disposition()),
+            (X_MS_BLOB_CACHE_CONTROL, args.cache_control()),
+        ] {
+            if let Some(v) = value {
+                let value = HeaderValue::from_str(v).map_err(|e| {
+                    Error::new(ErrorKind::Unexpected, "header value is invalid")
+                        .with_operation(Operation::Write.into_static())
+                        .with_context("header", key)
+                        .with_context("value", v)
+                        .set_source(e)
+                })?;
+                req.headers_mut()
+                    .insert(HeaderName::from_static(key), value);
+            }
+        }
+        insert_write_conditions(req.headers_mut(), &args)?;
 
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
 
@@ -340,8 +435,14 @@ impl Accessor for Backend {
 
         match status {
             StatusCode::CREATED | StatusCode::OK => {
+                let etag = parse_etag(resp.headers())?;
                 resp.into_body().consume().await?;
-                Ok(RpWrite::new(args.size()))
+
+                let rp = RpWrite::new(args.size());
+                Ok(match etag {
+                    Some(v) => rp.with_etag(&v),
+                    None => rp,
+                })
             }
             _ => Err(parse_error(resp).await?),
         }
@@ -377,22 +478,143 @@ impl Accessor for Backend {
         }
     }
 
-    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, ObjectPager)> {
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         let op = Box::new(DirStream::new(
             Arc::new(self.clone()),
             self.root.clone(),
             path.to_string(),
+            if args.recursive() { "" } else { "/" },
+            args.limit(),
         ));
 
         Ok((RpList::default(), op))
     }
+
+    /// Block blobs don't need to be initiated, the upload id is only used
+    /// to build block ids of parts.
+    async fn create_multipart(&self, _: &str, _: OpCreateMultipart) -> Result<RpCreateMultipart> {
+        Ok(RpCreateMultipart::new(&Uuid::new_v4().to_string()))
+    }
+
+    async fn write_multipart(
+        &self,
+        path: &str,
+        args: OpWriteMultipart,
+        r: BytesReader,
+    ) -> Result<RpWriteMultipart> {
+        let block_id = build_block_id(args.upload_id(), args.part_number());
+
+        let mut req =
+            self.azblob_put_block_request(path, &block_id, args.size(), AsyncBody::Reader(r))?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        let resp = self.client.send_async(req).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED => {
+                resp.into_body().consume().await?;
+
+                // Block id will be used to commit this block while completing.
+                Ok(RpWriteMultipart::new(args.part_number(), &block_id))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        let resp = self.azblob_put_block_list(path, args.parts()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED => {
+                resp.into_body().consume().await?;
+
+                Ok(RpCompleteMultipart::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Azblob doesn't support removing uncommitted blocks, they will be
+    /// garbage collected by azblob after one week.
+    async fn abort_multipart(&self, _: &str, _: OpAbortMultipart) -> Result<RpAbortMultipart> {
+        Ok(RpAbortMultipart::default())
+    }
+
+    /// Uncommitted blocks can't be listed without knowing the blob, so
+    /// there are no uploads to be returned.
+    async fn list_multipart(&self, _: &str, _: OpListMultipart) -> Result<RpListMultipart> {
+        Ok(RpListMultipart::new(Vec::new()))
+    }
+
+    async fn get_tags(&self, path: &str, _: OpGetTags) -> Result<RpGetTags> {
+        let resp = self.azblob_get_blob_tags(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let tags: Tags = quick_xml::de::from_reader(bs.reader()).map_err(|e| {
+                    Error::new(ErrorKind::Unexpected, "deserialize xml from response").set_source(e)
+                })?;
+
+                Ok(RpGetTags::new(
+                    tags.tag_set
+                        .tag
+                        .into_iter()
+                        .map(|v| (v.key, v.value))
+                        .collect(),
+                ))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        let resp = self.azblob_set_blob_tags(path, args.tags()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::NO_CONTENT | StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpSetTags::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete_tags(&self, path: &str, _: OpDeleteTags) -> Result<RpDeleteTags> {
+        // azblob doesn't have a delete api for tags, set an empty tag set instead.
+        let resp = self.azblob_set_blob_tags(path, &HashMap::new()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::NO_CONTENT | StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpDeleteTags::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
 }
 
 impl Backend {
     async fn azblob_get_blob(
         &self,
         path: &str,
-        range: BytesRange,
+        args: &OpRead,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
@@ -405,7 +627,8 @@ impl Backend {
 
         let mut req = Request::get(&url);
 
-        if !range.is_full() {
+        let range = args.range();
+        if let Some(range_header) = range.to_header() {
             // azblob doesn't support read with suffix range.
             //
             // ref: https://learn.microsoft.com/en-us/rest/api/storageservices/specifying-the-range-header-for-blob-service-operations
@@ -416,7 +639,14 @@ impl Backend {
                 ));
             }
 
-            req = req.header(http::header::RANGE, range.to_header());
+            req = req.header(http::header::RANGE, range_header);
+        }
+
+        if let Some(if_match) = args.if_match() {
+            req = req.header(http::header::IF_MATCH, if_match);
+        }
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(http::header::IF_NONE_MATCH, if_none_match);
         }
 
         let mut req = req
@@ -462,6 +692,67 @@ impl Backend {
         Ok(req)
     }
 
+    fn azblob_put_block_request(
+        &self,
+        path: &str,
+        block_id: &str,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=block&blockid={}",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p),
+            utf8_percent_encode(block_id, NON_ALPHANUMERIC)
+        );
+
+        let req = Request::put(&url).header(CONTENT_LENGTH, size);
+
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    async fn azblob_put_block_list(
+        &self,
+        path: &str,
+        parts: &[ObjectPart],
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=blocklist",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let content = quick_xml::se::to_string(&BlockList {
+            latest: parts
+                .iter()
+                .map(|v| BlockListLatest {
+                    block_id: v.etag().to_string(),
+                })
+                .collect(),
+        })
+        .map_err(|e| Error::new(ErrorKind::Unexpected, "serialize xml").set_source(e))?;
+
+        let req = Request::put(&url)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/xml");
+
+        let mut req = req
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        self.client.send_async(req).await
+    }
+
     async fn azblob_get_blob_properties(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
@@ -504,21 +795,91 @@ impl Backend {
         self.client.send_async(req).await
     }
 
+    async fn azblob_get_blob_tags(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=tags",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn azblob_set_blob_tags(
+        &self,
+        path: &str,
+        tags: &HashMap<String, String>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=tags",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let mut tag = tags
+            .iter()
+            .map(|(key, value)| Tag {
+                key: key.to_string(),
+                value: value.to_string(),
+            })
+            .collect::<Vec<_>>();
+        // Sort tags to make the request body stable.
+        tag.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let content = quick_xml::se::to_string(&Tags {
+            tag_set: TagSet { tag },
+        })
+        .map_err(|e| Error::new(ErrorKind::Unexpected, "serialize xml").set_source(e))?;
+
+        let req = Request::put(&url)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/xml");
+
+        let mut req = req
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        self.client.send_async(req).await
+    }
+
     pub(crate) async fn azblob_list_blobs(
         &self,
         path: &str,
+        delimiter: &str,
         next_marker: &str,
+        limit: Option<usize>,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let mut url = format!(
-            "{}/{}?restype=container&comp=list&delimiter=/",
+            "{}/{}?restype=container&comp=list",
             self.endpoint, self.container
         );
+        // Empty delimiter means list all objects under prefix recursively.
+        if !delimiter.is_empty() {
+            write!(url, "&delimiter={delimiter}").expect("write into string must succeed");
+        }
         if !p.is_empty() {
             write!(url, "&prefix={}", percent_encode_path(&p))
                 .expect("write into string must succeed");
         }
+        if let Some(limit) = limit {
+            write!(url, "&maxresults={limit}").expect("write into string must succeed");
+        }
         if !next_marker.is_empty() {
             write!(url, "&marker={next_marker}").expect("write into string must succeed");
         }
@@ -533,6 +894,49 @@ impl Backend {
     }
 }
 
+/// Build block id for the given part.
+///
+/// Block ids within a blob must be base64 encoded and have the same length,
+/// so part number is padded.
+fn build_block_id(upload_id: &str, part_number: usize) -> String {
+    base64::encode(format!("{upload_id}-{part_number:05}"))
+}
+
+/// Request of Put Block List.
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename = "BlockList", rename_all = "PascalCase")]
+struct BlockList {
+    latest: Vec<BlockListLatest>,
+}
+
+#[derive(Default, Debug, Serialize)]
+struct BlockListLatest {
+    #[serde(rename = "$value")]
+    block_id: String,
+}
+
+/// Request and result of Set Blob Tags and Get Blob Tags.
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename = "Tags", rename_all = "PascalCase")]
+struct Tags {
+    tag_set: TagSet,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct TagSet {
+    tag: Vec<Tag>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct Tag {
+    #[serde(rename = "$unflatten=Key")]
+    key: String,
+    #[serde(rename = "$unflatten=Value")]
+    value: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::Builder;
@@ -574,4 +978,464 @@ EndpointSuffix=core.chinacloudapi.cn;
         assert_eq!(builder.account_name.unwrap(), "storagesample");
         assert_eq!(builder.account_key.unwrap(), "account-key")
     }
+
+    #[tokio::test]
+    async fn test_conditional_write() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::raw::Accessor;
+        use crate::ErrorKind;
+        use crate::OpWrite;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(header("if-match", "\"etag\""))
+            .respond_with(ResponseTemplate::new(412))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(header("if-none-match", "\"etag\""))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .container("test")
+            .account_name("devstoreaccount1")
+            .account_key("Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==");
+        let acc = builder.build().expect("build must succeed");
+
+        let err = acc
+            .write(
+                "file",
+                OpWrite::new(5).with_if_match("\"etag\""),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+        acc.write(
+            "file",
+            OpWrite::new(5).with_if_none_match("\"etag\""),
+            Box::new(futures::io::Cursor::new("hello")),
+        )
+        .await
+        .expect("write must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_write_with_cache_control() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::headers;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::raw::Accessor;
+        use crate::OpStat;
+        use crate::OpWrite;
+
+        let cache_control = "public, max-age=3600";
+        let disposition = r#"attachment; filename="hello.txt""#;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            // Values of request headers will be split by `,` in wiremock.
+            .and(headers(
+                super::X_MS_BLOB_CACHE_CONTROL,
+                vec!["public", "max-age=3600"],
+            ))
+            .and(header(super::X_MS_BLOB_CONTENT_DISPOSITION, disposition))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/test/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "5")
+                    .insert_header("cache-control", cache_control)
+                    .insert_header("content-disposition", disposition),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .container("test")
+            .account_name("devstoreaccount1")
+            .account_key("Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==");
+        let acc = builder.build().expect("build must succeed");
+
+        acc.write(
+            "file",
+            OpWrite::new(5)
+                .with_cache_control(cache_control)
+                .with_content_disposition(disposition),
+            Box::new(futures::io::Cursor::new("hello")),
+        )
+        .await
+        .expect("write must succeed");
+
+        let meta = acc
+            .stat("file", OpStat::new())
+            .await
+            .expect("stat must succeed")
+            .into_metadata();
+        assert_eq!(meta.cache_control(), Some(cache_control));
+        assert_eq!(meta.content_disposition(), Some(disposition));
+    }
+
+    #[tokio::test]
+    async fn test_etag() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::raw::Accessor;
+        use crate::OpStat;
+        use crate::OpWrite;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "5")
+                    .insert_header("etag", "\"0x8CB171BA9E94B0B\""),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .respond_with(ResponseTemplate::new(201).insert_header("etag", "\"0x8CB171BA9E94B0B\""))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .container("test")
+            .account_name("devstoreaccount1")
+            .account_key("Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==");
+        let acc = builder.build().expect("build must succeed");
+
+        let rp = acc
+            .write(
+                "file",
+                OpWrite::new(5),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+            .await
+            .expect("write must succeed");
+        assert_eq!(rp.etag(), Some("0x8CB171BA9E94B0B"));
+
+        let meta = acc
+            .stat("file", OpStat::new())
+            .await
+            .expect("stat must succeed")
+            .into_metadata();
+        assert_eq!(meta.etag(), Some("0x8CB171BA9E94B0B"));
+    }
+
+    #[tokio::test]
+    async fn test_tagging() {
+        use std::collections::HashMap;
+
+        use wiremock::matchers::body_string;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::raw::Accessor;
+        use crate::OpDeleteTags;
+        use crate::OpGetTags;
+        use crate::OpSetTags;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("comp", "tags"))
+            .and(body_string(
+                "<Tags><TagSet><Tag><Key>项目</Key><Value>数据湖 🚀</Value></Tag></TagSet></Tags>",
+            ))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("comp", "tags"))
+            .and(body_string("<Tags><TagSet/></Tags>"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .and(query_param("comp", "tags"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+<Tags>
+  <TagSet>
+    <Tag><Key>项目</Key><Value>数据湖 🚀</Value></Tag>
+  </TagSet>
+</Tags>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test/empty"))
+            .and(query_param("comp", "tags"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(
+                    r#"<?xml version="1.0" encoding="utf-8"?><Tags><TagSet/></Tags>"#,
+                ),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .container("test")
+            .account_name("devstoreaccount1")
+            .account_key("Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==");
+        let acc = builder.build().expect("build must succeed");
+
+        let tags = HashMap::from([("项目".to_string(), "数据湖 🚀".to_string())]);
+        acc.set_tags("file", OpSetTags::new(tags.clone()))
+            .await
+            .expect("set tags must succeed");
+
+        let rp = acc
+            .get_tags("file", OpGetTags::new())
+            .await
+            .expect("get tags must succeed");
+        assert_eq!(rp.into_tags(), tags);
+
+        let rp = acc
+            .get_tags("empty", OpGetTags::new())
+            .await
+            .expect("get tags must succeed");
+        assert!(rp.tags().is_empty());
+
+        // Delete tags is done by setting an empty tag set.
+        acc.delete_tags("file", OpDeleteTags::new())
+            .await
+            .expect("delete tags must succeed");
+    }
+
+    #[test]
+    fn test_serialize_block_list() {
+        let content = quick_xml::se::to_string(&super::BlockList {
+            latest: vec![
+                super::BlockListLatest {
+                    block_id: "MDAwMDE=".to_string(),
+                },
+                super::BlockListLatest {
+                    block_id: "MDAwMDI=".to_string(),
+                },
+            ],
+        })
+        .expect("serialize must succeed");
+
+        assert_eq!(
+            content,
+            "<BlockList><Latest>MDAwMDE=</Latest><Latest>MDAwMDI=</Latest></BlockList>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multipart() {
+        use futures::AsyncWriteExt;
+        use wiremock::matchers::body_string_contains;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::Operator;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("comp", "block"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(4)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("comp", "blocklist"))
+            .and(body_string_contains("<Latest>"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .container("test")
+            .account_name("devstoreaccount1")
+            .account_key("Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let mut w = op
+            .object("file")
+            .writer()
+            .await
+            .expect("writer must succeed")
+            .with_part_size(4);
+        w.write_all(b"Hello, World!")
+            .await
+            .expect("write must succeed");
+        w.close().await.expect("close must succeed");
+
+        let requests = mock_server
+            .received_requests()
+            .await
+            .expect("requests must be recorded");
+        let blocklist = String::from_utf8_lossy(&requests[4].body).to_string();
+        let block_ids: Vec<_> = requests[..4]
+            .iter()
+            .map(|req| {
+                req.url
+                    .query_pairs()
+                    .find(|(k, _)| k == "blockid")
+                    .expect("blockid must exist")
+                    .1
+                    .to_string()
+            })
+            .collect();
+        for block_id in &block_ids {
+            assert_eq!(block_id.len(), block_ids[0].len());
+            assert!(blocklist.contains(&format!("<Latest>{block_id}</Latest>")));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multipart_concurrent() {
+        use std::collections::HashMap;
+        use std::time::Duration;
+
+        use wiremock::matchers::body_bytes;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::Operator;
+
+        let content: Vec<u8> = (0..62).map(|v| b'0' + v).collect();
+
+        let mock_server = MockServer::start().await;
+        // The last block fails once, it should be staged again by the next
+        // close without committing.
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("comp", "block"))
+            .and(body_bytes(content[60..].to_vec()))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("comp", "block"))
+            .respond_with(ResponseTemplate::new(201).set_delay(Duration::from_millis(10)))
+            .expect(16)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("comp", "blocklist"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .container("test")
+            .account_name("devstoreaccount1")
+            .account_key("Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let mut w = op
+            .object("file")
+            .writer()
+            .await
+            .expect("writer must succeed")
+            .with_part_size(4)
+            .with_concurrency(4);
+        w.append(&content).await.expect("append must succeed");
+        w.close().await.expect_err("close must fail");
+        w.close().await.expect("close must succeed");
+
+        let requests = mock_server
+            .received_requests()
+            .await
+            .expect("requests must be recorded");
+        let block_id = |req: &wiremock::Request| {
+            req.url
+                .query_pairs()
+                .find(|(k, _)| k == "blockid")
+                .expect("blockid must exist")
+                .1
+                .to_string()
+        };
+        let blocks: HashMap<_, _> = requests
+            .iter()
+            .filter(|req| req.url.query().unwrap_or_default().contains("comp=block&"))
+            .map(|req| (block_id(req), req.body.clone()))
+            .collect();
+        assert_eq!(blocks.len(), 16);
+
+        // Rebuild the committed content in the order of block list.
+        let blocklist = String::from_utf8_lossy(&requests[requests.len() - 1].body).to_string();
+        let committed: Vec<u8> = blocklist
+            .split("<Latest>")
+            .skip(1)
+            .flat_map(|v| {
+                let id = v.split("</Latest>").next().expect("block id must exist");
+                blocks[id].clone()
+            })
+            .collect();
+        assert_eq!(committed, content);
+    }
 }
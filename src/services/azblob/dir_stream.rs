@@ -17,8 +17,6 @@ use async_trait::async_trait;
 use bytes::Buf;
 use quick_xml::de;
 use serde::Deserialize;
-use time::format_description::well_known::Rfc2822;
-use time::OffsetDateTime;
 
 use super::backend::Backend;
 use super::error::parse_error;
@@ -29,18 +27,28 @@ pub struct DirStream {
     backend: Arc<Backend>,
     root: String,
     path: String,
+    delimiter: &'static str,
 
+    limit: Option<usize>,
     next_marker: String,
     done: bool,
 }
 
 impl DirStream {
-    pub fn new(backend: Arc<Backend>, root: String, path: String) -> Self {
+    pub fn new(
+        backend: Arc<Backend>,
+        root: String,
+        path: String,
+        delimiter: &'static str,
+        limit: Option<usize>,
+    ) -> Self {
         Self {
             backend,
             root,
             path,
+            delimiter,
 
+            limit,
             next_marker: "".to_string(),
             done: false,
         }
@@ -56,7 +64,7 @@ impl ObjectPage for DirStream {
 
         let resp = self
             .backend
-            .azblob_list_blobs(&self.path, &self.next_marker)
+            .azblob_list_blobs(&self.path, self.delimiter, &self.next_marker, self.limit)
             .await?;
 
         if resp.status() != http::StatusCode::OK {
@@ -97,23 +105,16 @@ impl ObjectPage for DirStream {
                 continue;
             }
 
-            let meta = ObjectMetadata::new(ObjectMode::FILE)
+            let mut meta = ObjectMetadata::new(ObjectMode::FILE)
                 // Keep fit with ETag header.
-                .with_etag(&format!("\"{}\"", object.properties.etag.as_str()))
+                .with_etag(object.properties.etag.as_str())
                 .with_content_length(object.properties.content_length)
                 .with_content_md5(object.properties.content_md5.as_str())
                 .with_content_type(&object.properties.content_type)
-                .with_last_modified(
-                    OffsetDateTime::parse(object.properties.last_modified.as_str(), &Rfc2822)
-                        .map_err(|e| {
-                            Error::new(
-                                ErrorKind::Unexpected,
-                                "parse last modified RFC2822 datetime",
-                            )
-                            .set_source(e)
-                        })?,
-                )
                 .with_complete();
+            if let Some(dt) = parse_datetime_from_rfc2822(&object.properties.last_modified) {
+                meta.set_last_modified(dt);
+            }
 
             let de = ObjectEntry::new(&build_rel_path(&self.root, &object.name), meta);
 
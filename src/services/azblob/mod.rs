@@ -24,6 +24,17 @@
 //!
 //! Refer to [`Builder`]'s public API docs for more information.
 //!
+//! # Block Blobs
+//!
+//! Multipart uploads are backed by block blobs: every part is staged by
+//! `Put Block` with a generated block id, and blocks are committed by
+//! `Put Block List` in the order of part numbers while completing. Staged
+//! blocks are not visible until committed, so a failed block can be staged
+//! again safely.
+//!
+//! Use [`ObjectWriter::with_concurrency`][crate::ObjectWriter::with_concurrency]
+//! to stage blocks in parallel.
+//!
 //! # Environment
 //!
 //! - `OPENDAL_AZBLOB_ROOT`
@@ -123,4 +134,4 @@ mod backend;
 pub use backend::Builder;
 
 mod dir_stream;
-mod error;
+pub(super) mod error;
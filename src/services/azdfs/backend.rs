@@ -33,6 +33,8 @@ use crate::object::ObjectMetadata;
 use crate::raw::*;
 use crate::*;
 
+const X_MS_RENAME_SOURCE: &str = "x-ms-rename-source";
+
 /// Builder for azblob services
 #[derive(Default, Clone)]
 pub struct Builder {
@@ -63,6 +65,14 @@ impl Debug for Builder {
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "filesystem",
+        "endpoint",
+        "account_name",
+        "account_key",
+    ];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
 
@@ -148,21 +158,19 @@ impl Builder {
         // Handle endpoint, region and container name.
         let filesystem = match self.filesystem.is_empty() {
             false => Ok(&self.filesystem),
-            true => Err(
-                Error::new(ErrorKind::BackendConfigInvalid, "filesystem is empty")
-                    .with_operation("Builder::build")
-                    .with_context("service", Scheme::Azblob),
-            ),
+            true => Err(Error::new(ErrorKind::ConfigInvalid, "filesystem is empty")
+                .with_context("field", "filesystem")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Azblob)),
         }?;
         debug!("backend use filesystem {}", &filesystem);
 
         let endpoint = match &self.endpoint {
             Some(endpoint) => Ok(endpoint.clone()),
-            None => Err(
-                Error::new(ErrorKind::BackendConfigInvalid, "endpoint is empty")
-                    .with_operation("Builder::build")
-                    .with_context("service", Scheme::Azblob),
-            ),
+            None => Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_context("field", "endpoint")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Azblob)),
         }?;
         debug!("backend use endpoint {}", &filesystem);
 
@@ -174,7 +182,8 @@ impl Builder {
         }
 
         let signer = signer_builder.build().map_err(|e| {
-            Error::new(ErrorKind::BackendConfigInvalid, "build AzureStorageSigner")
+            Error::new(ErrorKind::ConfigInvalid, "build AzureStorageSigner")
+                .with_context("field", "account_key")
                 .with_operation("Builder::build")
                 .with_context("service", Scheme::Azdfs)
                 .with_context("endpoint", &endpoint)
@@ -213,7 +222,10 @@ impl Accessor for Backend {
             .set_root(&self.root)
             .set_name(&self.filesystem)
             .set_capabilities(
-                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::List,
+                AccessorCapability::Read
+                    | AccessorCapability::Write
+                    | AccessorCapability::List
+                    | AccessorCapability::Rename,
             );
 
         am
@@ -277,7 +289,28 @@ impl Accessor for Backend {
             }
         }
 
-        let mut req = self.azdfs_update_request(path, Some(args.size()), AsyncBody::Reader(r))?;
+        // Append with empty body is not allowed, flush the created file directly.
+        if args.size() > 0 {
+            let mut req = self.azdfs_append_request(path, args.size(), AsyncBody::Reader(r))?;
+
+            self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+            let resp = self.client.send_async(req).await?;
+
+            let status = resp.status();
+            match status {
+                StatusCode::OK | StatusCode::ACCEPTED => {
+                    resp.into_body().consume().await?;
+                }
+                _ => {
+                    return Err(parse_error(resp)
+                        .await?
+                        .with_operation("Backend::azdfs_append_request"));
+                }
+            }
+        }
+
+        let mut req = self.azdfs_flush_request(path, args.size())?;
 
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
 
@@ -291,7 +324,42 @@ impl Accessor for Backend {
             }
             _ => Err(parse_error(resp)
                 .await?
-                .with_operation("Backend::azdfs_update_request")),
+                .with_operation("Backend::azdfs_flush_request")),
+        }
+    }
+
+    async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        // Rename requires the parent of target exists.
+        let parent = get_parent(to);
+        if parent != "/" {
+            let mut req = self.azdfs_create_request(parent, "directory", None, AsyncBody::Empty)?;
+
+            self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+            let resp = self.client.send_async(req).await?;
+
+            let status = resp.status();
+            match status {
+                StatusCode::CREATED | StatusCode::OK => {
+                    resp.into_body().consume().await?;
+                }
+                _ => {
+                    return Err(parse_error(resp)
+                        .await?
+                        .with_operation("Backend::azdfs_create_request"));
+                }
+            }
+        }
+
+        let resp = self.azdfs_rename(from, to).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpRename::default())
+            }
+            _ => Err(parse_error(resp).await?),
         }
     }
 
@@ -353,7 +421,7 @@ impl Backend {
 
         let mut req = Request::get(&url);
 
-        if !range.is_full() {
+        if let Some(range_header) = range.to_header() {
             // azblob doesn't support read with suffix range.
             //
             // ref: https://learn.microsoft.com/en-us/rest/api/storageservices/specifying-the-range-header-for-blob-service-operations
@@ -364,7 +432,7 @@ impl Backend {
                 ));
             }
 
-            req = req.header(http::header::RANGE, range.to_header());
+            req = req.header(http::header::RANGE, range_header);
         }
 
         let mut req = req
@@ -412,29 +480,26 @@ impl Backend {
         Ok(req)
     }
 
+    /// Upload data to the start of created file, data is not readable
+    /// until flushed.
+    ///
     /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/datalakestoragegen2/path/update
-    fn azdfs_update_request(
+    fn azdfs_append_request(
         &self,
         path: &str,
-        size: Option<u64>,
+        size: u64,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        // - close: Make this is the final action to this file.
-        // - flush: Flush the file directly.
         let url = format!(
-            "{}/{}/{}?action=append&close=true&flush=true&position=0",
+            "{}/{}/{}?action=append&position=0",
             self.endpoint,
             self.filesystem,
             percent_encode_path(&p)
         );
 
-        let mut req = Request::patch(&url);
-
-        if let Some(size) = size {
-            req = req.header(CONTENT_LENGTH, size)
-        }
+        let req = Request::patch(&url).header(CONTENT_LENGTH, size);
 
         // Set body
         let req = req.body(body).map_err(new_request_build_error)?;
@@ -442,6 +507,58 @@ impl Backend {
         Ok(req)
     }
 
+    /// Commit appended data, `position` must be the length of file after
+    /// all data appended.
+    ///
+    /// - close: Make this is the final action to this file.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/datalakestoragegen2/path/update
+    fn azdfs_flush_request(&self, path: &str, position: u64) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?action=flush&close=true&position={position}",
+            self.endpoint,
+            self.filesystem,
+            percent_encode_path(&p)
+        );
+
+        let req = Request::patch(&url)
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    /// Rename `from` to `to` by creating `to` with `x-ms-rename-source`.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/datalakestoragegen2/path/create
+    async fn azdfs_rename(&self, from: &str, to: &str) -> Result<Response<IncomingAsyncBody>> {
+        let source = build_abs_path(&self.root, from);
+        let target = build_abs_path(&self.root, to);
+
+        let url = format!(
+            "{}/{}/{}",
+            self.endpoint,
+            self.filesystem,
+            percent_encode_path(&target)
+        );
+
+        let mut req = Request::put(&url)
+            .header(
+                X_MS_RENAME_SOURCE,
+                format!("/{}/{}", self.filesystem, percent_encode_path(&source)),
+            )
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        self.client.send_async(req).await
+    }
+
     async fn azdfs_get_properties(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path)
             .trim_end_matches('/')
@@ -518,3 +635,120 @@ impl Backend {
         self.client.send_async(req).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::matchers::query_param;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    fn new_backend(endpoint: &str) -> impl Accessor {
+        let mut builder = Builder::default();
+        builder
+            .endpoint(endpoint)
+            .filesystem("test")
+            .account_name("devstoreaccount1")
+            .account_key("Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==");
+        builder.build().expect("build must succeed")
+    }
+
+    #[tokio::test]
+    async fn test_write() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("resource", "file"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/test/file"))
+            .and(query_param("action", "append"))
+            .and(query_param("position", "0"))
+            .and(header("content-length", "5"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/test/file"))
+            .and(query_param("action", "flush"))
+            .and(query_param("position", "5"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/test/file"))
+            .and(query_param("action", "flush"))
+            .and(query_param("position", "0"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let acc = new_backend(&mock_server.uri());
+        let rp = acc
+            .write(
+                "file",
+                OpWrite::new(5),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+            .await
+            .expect("write must succeed");
+        assert_eq!(rp.written(), 5);
+
+        // Empty file will be flushed without append.
+        acc.write(
+            "file",
+            OpWrite::new(0),
+            Box::new(futures::io::Cursor::new("")),
+        )
+        .await
+        .expect("write must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_rename() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/dir"))
+            .and(query_param("resource", "directory"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/dir/to"))
+            .and(header(X_MS_RENAME_SOURCE, "/test/from"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/to"))
+            .and(header(X_MS_RENAME_SOURCE, "/test/not_exist"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let acc = new_backend(&mock_server.uri());
+        acc.rename("from", "dir/to", OpRename::new())
+            .await
+            .expect("rename must succeed");
+
+        let err = acc
+            .rename("not_exist", "to", OpRename::new())
+            .await
+            .expect_err("rename must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+    }
+}
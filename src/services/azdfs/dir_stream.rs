@@ -97,7 +97,7 @@ impl ObjectPage for DirStream {
 
             let meta = ObjectMetadata::new(mode)
                 // Keep fit with ETag header.
-                .with_etag(&format!("\"{}\"", &object.etag))
+                .with_etag(&object.etag)
                 .with_content_length(object.content_length.parse().map_err(|err| {
                     Error::new(ErrorKind::Unexpected, "content length is not valid integer")
                         .set_source(err)
@@ -117,4 +117,4 @@ mod backend;
 pub use backend::Builder;
 
 mod dir_stream;
-mod error;
+pub(super) mod error;
@@ -0,0 +1,351 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use http::header::AUTHORIZATION;
+use http::header::CONTENT_TYPE;
+use http::Request;
+use http::StatusCode;
+use log::debug;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde_json::json;
+use time::Duration;
+use time::OffsetDateTime;
+
+use super::error::parse_error;
+use super::error::parse_json_deserialize_error;
+use super::error::parse_json_serialize_error;
+use crate::raw::*;
+use crate::*;
+
+/// Tokens will be refreshed before they are really expired.
+const TOKEN_REFRESH_AHEAD: Duration = Duration::minutes(2);
+/// Authorization tokens of b2 are valid for at most 24 hours.
+const TOKEN_LIFETIME: Duration = Duration::hours(24);
+
+/// Token returned by `b2_authorize_account`, along with the urls and the
+/// bucket id to use.
+#[derive(Clone)]
+pub struct Token {
+    pub token: String,
+    pub api_url: String,
+    pub download_url: String,
+    pub bucket_id: String,
+    pub expires_at: OffsetDateTime,
+}
+
+impl Debug for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Token")
+            .field("token", &"<redacted>")
+            .field("api_url", &self.api_url)
+            .field("download_url", &self.download_url)
+            .field("bucket_id", &self.bucket_id)
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+impl Token {
+    fn is_valid(&self) -> bool {
+        self.expires_at - TOKEN_REFRESH_AHEAD > OffsetDateTime::now_utc()
+    }
+}
+
+/// Upload url returned by `b2_get_upload_url`.
+///
+/// An upload url can only be used by one upload at the same time.
+#[derive(Clone)]
+pub struct UploadUrl {
+    pub upload_url: String,
+    pub token: String,
+}
+
+impl Debug for UploadUrl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UploadUrl")
+            .field("upload_url", &self.upload_url)
+            .field("token", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Config to authenticate via `b2_authorize_account`.
+#[derive(Clone)]
+pub struct Credential {
+    pub endpoint: String,
+    pub application_key_id: String,
+    pub application_key: String,
+    pub bucket: String,
+    /// Use this bucket id instead of looking it up by bucket name.
+    pub bucket_id: Option<String>,
+}
+
+impl Debug for Credential {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credential")
+            .field("endpoint", &self.endpoint)
+            .field("application_key_id", &self.application_key_id)
+            .field("application_key", &"<redacted>")
+            .field("bucket", &self.bucket)
+            .field("bucket_id", &self.bucket_id)
+            .finish()
+    }
+}
+
+/// Authenticator will cache the token and authenticate again while the
+/// token is expired or invalidated.
+///
+/// Upload urls are cached too, so that they could be reused by later
+/// uploads.
+pub struct Authenticator {
+    client: HttpClient,
+    credential: Credential,
+    token: Mutex<Option<Token>>,
+    upload_urls: Mutex<Vec<UploadUrl>>,
+}
+
+impl Debug for Authenticator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Authenticator")
+            .field("credential", &self.credential)
+            .field("token", &self.token.lock())
+            .field("upload_urls", &self.upload_urls.lock().len())
+            .finish()
+    }
+}
+
+impl Authenticator {
+    /// Create an authenticator that authenticate via `b2_authorize_account`.
+    pub fn new(client: HttpClient, credential: Credential) -> Self {
+        Self {
+            client,
+            credential,
+            token: Mutex::new(None),
+            upload_urls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Get a valid token, authenticate if needed.
+    pub async fn token(&self) -> Result<Token> {
+        if let Some(token) = self.token.lock().as_ref() {
+            if token.is_valid() {
+                return Ok(token.clone());
+            }
+        }
+
+        let token = self.authorize().await?;
+        *self.token.lock() = Some(token.clone());
+        Ok(token)
+    }
+
+    /// Invalidate the cached token so that next [`Authenticator::token`]
+    /// will authenticate again.
+    pub fn invalidate(&self) {
+        *self.token.lock() = None;
+    }
+
+    /// Take a cached upload url out, returns `None` if there are no
+    /// idle upload urls.
+    pub fn take_upload_url(&self) -> Option<UploadUrl> {
+        self.upload_urls.lock().pop()
+    }
+
+    /// Put the upload url back so that it could be reused.
+    ///
+    /// Only upload urls that succeeded should be put back, b2 requires
+    /// clients to get a new one after failures.
+    pub fn put_upload_url(&self, upload_url: UploadUrl) {
+        self.upload_urls.lock().push(upload_url)
+    }
+
+    async fn authorize(&self) -> Result<Token> {
+        debug!("b2 authorize account: {:?}", self.credential);
+
+        let url = format!("{}/b2api/v2/b2_authorize_account", self.credential.endpoint);
+        let auth = format!(
+            "Basic {}",
+            base64::encode(format!(
+                "{}:{}",
+                self.credential.application_key_id, self.credential.application_key
+            ))
+        );
+        let req = Request::get(&url)
+            .header(AUTHORIZATION, auth)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send_async(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp)
+                .await?
+                .with_operation("b2::authorize_account"));
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: AuthorizeAccountOutput =
+            serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+        let bucket_id = match &self.credential.bucket_id {
+            Some(v) => v.clone(),
+            None => match output.allowed.bucket_id.as_deref() {
+                // Keys restricted to the bucket don't have permission to
+                // list buckets.
+                Some(v)
+                    if output.allowed.bucket_name.as_deref() == Some(&self.credential.bucket) =>
+                {
+                    v.to_string()
+                }
+                _ => self.find_bucket_id(&output).await?,
+            },
+        };
+
+        Ok(Token {
+            token: output.authorization_token,
+            api_url: output.api_url.trim_end_matches('/').to_string(),
+            download_url: output.download_url.trim_end_matches('/').to_string(),
+            bucket_id,
+            expires_at: OffsetDateTime::now_utc() + TOKEN_LIFETIME,
+        })
+    }
+
+    /// Find the bucket id by bucket name via `b2_list_buckets`.
+    async fn find_bucket_id(&self, output: &AuthorizeAccountOutput) -> Result<String> {
+        let body = json!({
+            "accountId": output.account_id,
+            "bucketName": self.credential.bucket,
+        });
+        let body = serde_json::to_vec(&body).map_err(parse_json_serialize_error)?;
+
+        let url = format!(
+            "{}/b2api/v2/b2_list_buckets",
+            output.api_url.trim_end_matches('/')
+        );
+        let req = Request::post(&url)
+            .header(AUTHORIZATION, &output.authorization_token)
+            .header(CONTENT_TYPE, "application/json")
+            .body(AsyncBody::Bytes(body.into()))
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send_async(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?.with_operation("b2::list_buckets"));
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: ListBucketsOutput =
+            serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+        output
+            .buckets
+            .into_iter()
+            .find(|v| v.bucket_name == self.credential.bucket)
+            .map(|v| v.bucket_id)
+            .ok_or_else(|| {
+                Error::new(ErrorKind::ConfigInvalid, "bucket not found")
+                    .with_operation("b2::list_buckets")
+                    .with_context("bucket", &self.credential.bucket)
+            })
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct AuthorizeAccountOutput {
+    account_id: String,
+    authorization_token: String,
+    api_url: String,
+    download_url: String,
+    allowed: Allowed,
+}
+
+/// Capabilities and restrictions of the application key.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct Allowed {
+    bucket_id: Option<String>,
+    bucket_name: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct ListBucketsOutput {
+    buckets: Vec<BucketOutput>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct BucketOutput {
+    bucket_id: String,
+    bucket_name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// This example is from https://www.backblaze.com/b2/docs/b2_authorize_account.html
+    #[test]
+    fn test_parse_authorize_account_output() {
+        let bs = r#"{
+  "absoluteMinimumPartSize": 5000000,
+  "accountId": "YOUR_ACCOUNT_ID",
+  "allowed": {
+    "bucketId": "BUCKET_ID",
+    "bucketName": "BUCKET_NAME",
+    "capabilities": ["listBuckets", "listFiles", "readFiles", "writeFiles", "deleteFiles"],
+    "namePrefix": null
+  },
+  "apiUrl": "https://apiNNN.backblazeb2.com",
+  "authorizationToken": "4_0022623512fc8f80000000001_0186e431_d18d02_acct_tH7VW03boebOXayIc43-sxptpfA=",
+  "downloadUrl": "https://f002.backblazeb2.com",
+  "recommendedPartSize": 100000000,
+  "s3ApiUrl": "https://s3.us-west-NNN.backblazeb2.com"
+}"#;
+
+        let out: AuthorizeAccountOutput = serde_json::from_str(bs).expect("must success");
+        assert_eq!(out.account_id, "YOUR_ACCOUNT_ID");
+        assert_eq!(out.api_url, "https://apiNNN.backblazeb2.com");
+        assert_eq!(out.download_url, "https://f002.backblazeb2.com");
+        assert!(out.authorization_token.starts_with("4_0022623512fc8f8"));
+        assert_eq!(out.allowed.bucket_id.as_deref(), Some("BUCKET_ID"));
+        assert_eq!(out.allowed.bucket_name.as_deref(), Some("BUCKET_NAME"));
+
+        // Keys without bucket restriction return null.
+        let bs = r#"{"accountId": "id", "allowed": {"bucketId": null, "bucketName": null}}"#;
+        let out: AuthorizeAccountOutput = serde_json::from_str(bs).expect("must success");
+        assert_eq!(out.allowed.bucket_id, None);
+    }
+
+    #[test]
+    fn test_token_is_valid() {
+        let token = Token {
+            token: "token".to_string(),
+            api_url: "https://api000.backblazeb2.com".to_string(),
+            download_url: "https://f000.backblazeb2.com".to_string(),
+            bucket_id: "bucket_id".to_string(),
+            expires_at: OffsetDateTime::now_utc() + Duration::minutes(1),
+        };
+        assert!(!token.is_valid(), "token is about to expire");
+
+        let token = Token {
+            expires_at: OffsetDateTime::now_utc() + TOKEN_LIFETIME,
+            ..token
+        };
+        assert!(token.is_valid());
+    }
+}
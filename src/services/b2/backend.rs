@@ -0,0 +1,971 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::header::AUTHORIZATION;
+use http::header::CONTENT_LENGTH;
+use http::header::CONTENT_TYPE;
+use http::HeaderMap;
+use http::Method;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use log::debug;
+use serde::Deserialize;
+use serde_json::json;
+use time::OffsetDateTime;
+
+use super::auth::Authenticator;
+use super::auth::Credential;
+use super::auth::Token;
+use super::auth::UploadUrl;
+use super::checksum::Sha1Reader;
+use super::checksum::SHA1_HEX_LEN;
+use super::dir_stream::DirStream;
+use super::error::parse_error;
+use super::error::parse_json_deserialize_error;
+use super::error::parse_json_serialize_error;
+use crate::raw::*;
+use crate::*;
+
+/// Default endpoint to authorize account.
+const DEFAULT_ENDPOINT: &str = "https://api.backblazeb2.com";
+
+/// Let b2 detect the content type by file name.
+const AUTO_CONTENT_TYPE: &str = "b2/x-auto";
+
+const X_BZ_FILE_NAME: &str = "X-Bz-File-Name";
+const X_BZ_FILE_ID: &str = "X-Bz-File-Id";
+const X_BZ_CONTENT_SHA1: &str = "X-Bz-Content-Sha1";
+const X_BZ_UPLOAD_TIMESTAMP: &str = "X-Bz-Upload-Timestamp";
+const X_BZ_PART_NUMBER: &str = "X-Bz-Part-Number";
+
+/// Builder for Backblaze B2 services
+#[derive(Default, Clone)]
+pub struct Builder {
+    root: Option<String>,
+    bucket: Option<String>,
+    bucket_id: Option<String>,
+    endpoint: Option<String>,
+    application_key_id: Option<String>,
+    application_key: Option<String>,
+}
+
+impl Debug for Builder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut d = f.debug_struct("Builder");
+        d.field("root", &self.root)
+            .field("bucket", &self.bucket)
+            .field("bucket_id", &self.bucket_id)
+            .field("endpoint", &self.endpoint)
+            .field("application_key_id", &self.application_key_id);
+        if self.application_key.is_some() {
+            d.field("application_key", &"<redacted>");
+        }
+        d.finish()
+    }
+}
+
+impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "bucket",
+        "bucket_id",
+        "endpoint",
+        "application_key_id",
+        "application_key",
+    ];
+
+    pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
+        let mut builder = Builder::default();
+
+        for (k, v) in it {
+            let v = v.as_str();
+            match k.as_ref() {
+                "root" => builder.root(v),
+                "bucket" => builder.bucket(v),
+                "bucket_id" => builder.bucket_id(v),
+                "endpoint" => builder.endpoint(v),
+                "application_key_id" => builder.application_key_id(v),
+                "application_key" => builder.application_key(v),
+                _ => continue,
+            };
+        }
+
+        builder
+    }
+
+    /// Set root of this backend.
+    ///
+    /// All operations will happen under this root.
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+
+        self
+    }
+
+    /// Set bucket name of this backend.
+    /// The param is required.
+    pub fn bucket(&mut self, bucket: &str) -> &mut Self {
+        if !bucket.is_empty() {
+            self.bucket = Some(bucket.to_string());
+        }
+
+        self
+    }
+
+    /// Set bucket id of this backend.
+    ///
+    /// If not set, we will use the bucket that application key is
+    /// restricted to, or look it up via `b2_list_buckets` which requires
+    /// the `listBuckets` capability.
+    pub fn bucket_id(&mut self, bucket_id: &str) -> &mut Self {
+        if !bucket_id.is_empty() {
+            self.bucket_id = Some(bucket_id.to_string());
+        }
+
+        self
+    }
+
+    /// Set endpoint to authorize account, default to
+    /// `https://api.backblazeb2.com`.
+    ///
+    /// Urls to call other apis are returned by `b2_authorize_account`.
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        }
+
+        self
+    }
+
+    /// Set application key id of this backend.
+    /// The param is required.
+    pub fn application_key_id(&mut self, application_key_id: &str) -> &mut Self {
+        if !application_key_id.is_empty() {
+            self.application_key_id = Some(application_key_id.to_string());
+        }
+
+        self
+    }
+
+    /// Set application key of this backend.
+    /// The param is required.
+    pub fn application_key(&mut self, application_key: &str) -> &mut Self {
+        if !application_key.is_empty() {
+            self.application_key = Some(application_key.to_string());
+        }
+
+        self
+    }
+
+    /// Consume builder to build a b2 backend.
+    pub fn build(&mut self) -> Result<impl Accessor> {
+        debug!("backend build started: {:?}", &self);
+
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let required = |v: &Option<String>, name: &str| {
+            v.clone().ok_or_else(|| {
+                Error::new(ErrorKind::ConfigInvalid, &format!("{name} is empty"))
+                    .with_context("field", name)
+                    .with_context("service", Scheme::B2)
+            })
+        };
+
+        let bucket = required(&self.bucket, "bucket")?;
+        debug!("backend use bucket {}", &bucket);
+
+        let client = HttpClient::new();
+
+        let auth = Authenticator::new(
+            client.clone(),
+            Credential {
+                endpoint: self
+                    .endpoint
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string()),
+                application_key_id: required(&self.application_key_id, "application_key_id")?,
+                application_key: required(&self.application_key, "application_key")?,
+                bucket: bucket.clone(),
+                bucket_id: self.bucket_id.clone(),
+            },
+        );
+
+        debug!("backend build finished: {:?}", &self);
+        Ok(apply_wrapper(Backend {
+            client,
+            root,
+            bucket,
+            auth: Arc::new(auth),
+        }))
+    }
+}
+
+/// Backend for Backblaze B2 services.
+#[derive(Debug, Clone)]
+pub struct Backend {
+    client: HttpClient,
+    root: String,
+    bucket: String,
+    auth: Arc<Authenticator>,
+}
+
+#[async_trait]
+impl Accessor for Backend {
+    fn metadata(&self) -> AccessorMetadata {
+        let mut am = AccessorMetadata::default();
+        am.set_scheme(Scheme::B2)
+            .set_root(&self.root)
+            .set_name(&self.bucket)
+            .set_capabilities(
+                AccessorCapability::Read
+                    | AccessorCapability::Write
+                    | AccessorCapability::List
+                    | AccessorCapability::ListRecursive
+                    | AccessorCapability::Multipart,
+            );
+
+        am
+    }
+
+    async fn create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
+        self.b2_upload_file(path, 0, None, Box::new(futures::io::empty()))
+            .await?;
+
+        Ok(RpCreate::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
+        let resp = self
+            .b2_download_file_by_name(path, Method::GET, args.range())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_file_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body().reader()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
+        self.b2_upload_file(path, args.size(), args.content_type(), r)
+            .await?;
+
+        Ok(RpWrite::new(args.size()))
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(ObjectMetadata::new(ObjectMode::DIR)));
+        }
+
+        let resp = self
+            .b2_download_file_by_name(path, Method::HEAD, BytesRange::default())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => parse_file_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::NOT_FOUND if path.ends_with('/') => {
+                Ok(RpStat::new(ObjectMetadata::new(ObjectMode::DIR)))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let file_name = build_abs_path(&self.root, path);
+
+        // Every upload creates a new version in b2, we need to delete all
+        // of them, otherwise the previous version will be visible again.
+        loop {
+            let resp = self
+                .b2_download_file_by_name(path, Method::HEAD, BytesRange::default())
+                .await?;
+
+            let file_id = match resp.status() {
+                StatusCode::OK => resp
+                    .headers()
+                    .get(X_BZ_FILE_ID)
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| v.to_string())
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::Unexpected,
+                            "X-Bz-File-Id not present in returning response",
+                        )
+                        .with_operation("b2::delete")
+                    })?,
+                StatusCode::NOT_FOUND => return Ok(RpDelete::default()),
+                _ => return Err(parse_error(resp).await?),
+            };
+            resp.into_body().consume().await?;
+
+            let resp = self
+                .b2_api("b2_delete_file_version", |_| {
+                    json!({
+                        "fileName": file_name,
+                        "fileId": file_id,
+                    })
+                })
+                .await?;
+
+            match resp.status() {
+                StatusCode::OK => resp.into_body().consume().await?,
+                _ => {
+                    let err = parse_error(resp).await?;
+                    // The version has been deleted by others.
+                    if err.kind() != ErrorKind::ObjectNotFound {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
+        Ok((
+            RpList::default(),
+            Box::new(DirStream::new(
+                Arc::new(self.clone()),
+                &self.root,
+                path,
+                args.recursive(),
+            )),
+        ))
+    }
+
+    async fn create_multipart(
+        &self,
+        path: &str,
+        args: OpCreateMultipart,
+    ) -> Result<RpCreateMultipart> {
+        let file_name = build_abs_path(&self.root, path);
+        let content_type = args.content_type().unwrap_or(AUTO_CONTENT_TYPE);
+
+        let resp = self
+            .b2_api("b2_start_large_file", |token| {
+                json!({
+                    "bucketId": token.bucket_id,
+                    "fileName": file_name,
+                    "contentType": content_type,
+                })
+            })
+            .await?;
+
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: LargeFileOutput =
+            serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+        Ok(RpCreateMultipart::new(&output.file_id))
+    }
+
+    async fn write_multipart(
+        &self,
+        _: &str,
+        args: OpWriteMultipart,
+        r: BytesReader,
+    ) -> Result<RpWriteMultipart> {
+        let resp = self
+            .b2_api(
+                "b2_get_upload_part_url",
+                |_| json!({ "fileId": args.upload_id() }),
+            )
+            .await?;
+        let upload_url = parse_upload_url(resp).await?;
+
+        let req = Request::post(&upload_url.upload_url)
+            .header(AUTHORIZATION, &upload_url.token)
+            .header(X_BZ_PART_NUMBER, args.part_number())
+            .header(CONTENT_LENGTH, args.size() + SHA1_HEX_LEN)
+            .header(X_BZ_CONTENT_SHA1, "hex_digits_at_end")
+            .body(AsyncBody::Reader(Box::new(Sha1Reader::new(r))))
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send_async(req).await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                let output: UploadPartOutput =
+                    serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+                // The sha1 of parts are required to finish the large file.
+                Ok(RpWriteMultipart::new(
+                    args.part_number(),
+                    &output.content_sha1,
+                ))
+            }
+            // Streaming uploads can't be sent again, users can retry with
+            // a new upload part url.
+            StatusCode::UNAUTHORIZED => Err(parse_error(resp)
+                .await?
+                .with_context("service", Scheme::B2)
+                .set_temporary()),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn complete_multipart(
+        &self,
+        _: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        let mut parts = args.parts().to_vec();
+        parts.sort_by_key(|v| v.part_number());
+        let part_sha1_array = parts.iter().map(|v| v.etag()).collect::<Vec<_>>();
+
+        let resp = self
+            .b2_api("b2_finish_large_file", |_| {
+                json!({
+                    "fileId": args.upload_id(),
+                    "partSha1Array": part_sha1_array,
+                })
+            })
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCompleteMultipart::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn abort_multipart(&self, _: &str, args: OpAbortMultipart) -> Result<RpAbortMultipart> {
+        let resp = self
+            .b2_api(
+                "b2_cancel_large_file",
+                |_| json!({ "fileId": args.upload_id() }),
+            )
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpAbortMultipart::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+impl Backend {
+    /// Send request built by `f`, we will authenticate again and resend
+    /// it once if the token has been expired.
+    async fn send(
+        &self,
+        f: impl Fn(&Token) -> Result<Request<AsyncBody>>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let token = self.auth.token().await?;
+        let resp = self.client.send_async(f(&token)?).await?;
+
+        if resp.status() != StatusCode::UNAUTHORIZED {
+            return Ok(resp);
+        }
+        resp.into_body().consume().await?;
+
+        debug!("b2 token is expired, authenticate again");
+        self.auth.invalidate();
+        let token = self.auth.token().await?;
+        self.client.send_async(f(&token)?).await
+    }
+
+    /// Call b2 native api with json body built by `f`.
+    async fn b2_api(
+        &self,
+        api: &str,
+        f: impl Fn(&Token) -> serde_json::Value,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        self.send(|token| {
+            let url = format!("{}/b2api/v2/{}", token.api_url, api);
+            let body = serde_json::to_vec(&f(token)).map_err(parse_json_serialize_error)?;
+
+            Request::post(&url)
+                .header(AUTHORIZATION, &token.token)
+                .header(CONTENT_TYPE, "application/json")
+                .header(CONTENT_LENGTH, body.len())
+                .body(AsyncBody::Bytes(body.into()))
+                .map_err(new_request_build_error)
+        })
+        .await
+    }
+
+    async fn b2_download_file_by_name(
+        &self,
+        path: &str,
+        method: Method,
+        range: BytesRange,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        self.send(|token| {
+            let url = format!(
+                "{}/file/{}/{}",
+                token.download_url,
+                percent_encode_path(&self.bucket),
+                percent_encode_path(&p)
+            );
+
+            let mut req = Request::builder()
+                .method(method.clone())
+                .uri(&url)
+                .header(AUTHORIZATION, &token.token);
+            if let Some(range_header) = range.to_header() {
+                req = req.header(http::header::RANGE, range_header);
+            }
+
+            req.body(AsyncBody::Empty).map_err(new_request_build_error)
+        })
+        .await
+    }
+
+    /// Get an upload url, cached upload urls will be reused.
+    async fn upload_url(&self) -> Result<UploadUrl> {
+        if let Some(upload_url) = self.auth.take_upload_url() {
+            return Ok(upload_url);
+        }
+
+        let resp = self
+            .b2_api(
+                "b2_get_upload_url",
+                |token| json!({ "bucketId": token.bucket_id }),
+            )
+            .await?;
+        parse_upload_url(resp).await
+    }
+
+    /// Upload file via `b2_upload_file`.
+    ///
+    /// The sha1 of content will be appended at the end of body.
+    async fn b2_upload_file(
+        &self,
+        path: &str,
+        size: u64,
+        content_type: Option<&str>,
+        r: BytesReader,
+    ) -> Result<()> {
+        let p = build_abs_path(&self.root, path);
+        let upload_url = self.upload_url().await?;
+
+        let req = Request::post(&upload_url.upload_url)
+            .header(AUTHORIZATION, &upload_url.token)
+            .header(X_BZ_FILE_NAME, percent_encode_path(&p))
+            .header(CONTENT_TYPE, content_type.unwrap_or(AUTO_CONTENT_TYPE))
+            .header(CONTENT_LENGTH, size + SHA1_HEX_LEN)
+            .header(X_BZ_CONTENT_SHA1, "hex_digits_at_end")
+            .body(AsyncBody::Reader(Box::new(Sha1Reader::new(r))))
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send_async(req).await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                self.auth.put_upload_url(upload_url);
+                Ok(())
+            }
+            // Streaming uploads can't be sent again, the expired upload
+            // url has been dropped so that users can retry with a new one.
+            StatusCode::UNAUTHORIZED => Err(parse_error(resp)
+                .await?
+                .with_context("service", Scheme::B2)
+                .set_temporary()),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// List files in bucket with given prefix.
+    ///
+    /// `prefix` is the absolute path.
+    pub(crate) async fn b2_list_file_names(
+        &self,
+        prefix: &str,
+        delimiter: Option<&str>,
+        start_file_name: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        self.b2_api("b2_list_file_names", |token| {
+            let mut body = json!({
+                "bucketId": token.bucket_id,
+                "prefix": prefix,
+                "maxFileCount": DirStream::LIMIT,
+            });
+            if let Some(delimiter) = delimiter {
+                body["delimiter"] = delimiter.into();
+            }
+            if let Some(start_file_name) = start_file_name {
+                body["startFileName"] = start_file_name.into();
+            }
+            body
+        })
+        .await
+    }
+}
+
+/// Parse metadata of file from the headers of `b2_download_file_by_name`.
+///
+/// B2 doesn't return `Last-Modified`, we will use the upload timestamp
+/// instead.
+fn parse_file_metadata(path: &str, headers: &HeaderMap) -> Result<ObjectMetadata> {
+    let mut meta = parse_into_object_metadata(path, headers)?;
+
+    if meta.last_modified().is_none() {
+        if let Some(v) = headers.get(X_BZ_UPLOAD_TIMESTAMP) {
+            let ts = v
+                .to_str()
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "header value is not valid upload timestamp",
+                    )
+                    .with_context("value", format!("{:?}", v))
+                })?;
+            meta.set_last_modified(parse_upload_timestamp(ts)?);
+        }
+    }
+
+    Ok(meta)
+}
+
+/// B2 returns upload timestamp in milliseconds since epoch.
+pub(crate) fn parse_upload_timestamp(v: i64) -> Result<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp_nanos(v as i128 * 1_000_000).map_err(|e| {
+        Error::new(ErrorKind::Unexpected, "parse upload timestamp for b2")
+            .with_context("value", v.to_string())
+            .set_source(e)
+    })
+}
+
+async fn parse_upload_url(resp: Response<IncomingAsyncBody>) -> Result<UploadUrl> {
+    if resp.status() != StatusCode::OK {
+        return Err(parse_error(resp).await?);
+    }
+
+    let bs = resp.into_body().bytes().await?;
+    let output: UploadUrlOutput =
+        serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+    Ok(UploadUrl {
+        upload_url: output.upload_url,
+        token: output.authorization_token,
+    })
+}
+
+/// Output of `b2_get_upload_url` and `b2_get_upload_part_url`.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct UploadUrlOutput {
+    upload_url: String,
+    authorization_token: String,
+}
+
+/// Output of `b2_start_large_file`.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct LargeFileOutput {
+    file_id: String,
+}
+
+/// Output of `b2_upload_part`.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct UploadPartOutput {
+    content_sha1: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_file_metadata() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, "46".parse().unwrap());
+        headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+        headers.insert(X_BZ_UPLOAD_TIMESTAMP, "1439083733000".parse().unwrap());
+
+        let meta = parse_file_metadata("dir/file", &headers).expect("must success");
+        assert_eq!(meta.mode(), ObjectMode::FILE);
+        assert_eq!(meta.content_length(), 46);
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(
+            meta.last_modified().map(|v| v.unix_timestamp()),
+            Some(1439083733)
+        );
+    }
+
+    async fn mock_authorize(server: &MockServer, token: &str) {
+        Mock::given(method("GET"))
+            .and(path("/b2api/v2/b2_authorize_account"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "accountId": "account",
+                "authorizationToken": token,
+                "apiUrl": server.uri(),
+                "downloadUrl": server.uri(),
+                "allowed": {"bucketId": "bucket_id", "bucketName": "bucket"},
+            })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_refresh_on_unauthorized() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        mock_authorize(&mock_server, "first_token").await;
+        mock_authorize(&mock_server, "second_token").await;
+        Mock::given(method("HEAD"))
+            .and(path("/file/bucket/file"))
+            .and(header("authorization", "first_token"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/file/bucket/file"))
+            .and(header("authorization", "second_token"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(X_BZ_UPLOAD_TIMESTAMP, "1439083733000")
+                    .set_body_bytes(b"Hello, World!".to_vec()),
+            )
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("bucket")
+            .application_key_id("key_id")
+            .application_key("key");
+        let acc = builder.build().expect("build must succeed");
+
+        // The token will be refreshed only once.
+        for _ in 0..2 {
+            let meta = acc
+                .stat("file", OpStat::new())
+                .await
+                .expect("stat must succeed")
+                .into_metadata();
+            assert_eq!(meta.content_length(), 13);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_url_reuse() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        mock_authorize(&mock_server, "token").await;
+        Mock::given(method("POST"))
+            .and(path("/b2api/v2/b2_get_upload_url"))
+            .and(header("authorization", "token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "bucketId": "bucket_id",
+                "uploadUrl": format!("{}/upload", mock_server.uri()),
+                "authorizationToken": "upload_token",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/upload"))
+            .and(header("authorization", "upload_token"))
+            .and(header("x-bz-file-name", "dir/file"))
+            .and(header("x-bz-content-sha1", "hex_digits_at_end"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("bucket")
+            .application_key_id("key_id")
+            .application_key("key");
+        let acc = builder.build().expect("build must succeed");
+
+        for _ in 0..2 {
+            acc.write(
+                "dir/file",
+                OpWrite::new(13),
+                Box::new(futures::io::Cursor::new(b"Hello, World!".to_vec())),
+            )
+            .await
+            .expect("write must succeed");
+        }
+
+        let bodies = mock_server
+            .received_requests()
+            .await
+            .expect("requests must be recorded")
+            .into_iter()
+            .filter(|req| req.url.path() == "/upload")
+            .map(|req| String::from_utf8_lossy(&req.body).into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            bodies,
+            vec!["Hello, World!0a0a9f2a6772942557ab5355d76af442f8f65e01"; 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_large_file() {
+        use wiremock::matchers::body_json;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        mock_authorize(&mock_server, "token").await;
+        Mock::given(method("POST"))
+            .and(path("/b2api/v2/b2_start_large_file"))
+            .and(body_json(json!({
+                "bucketId": "bucket_id",
+                "fileName": "dir/file",
+                "contentType": AUTO_CONTENT_TYPE,
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "fileId": "file_id",
+                "fileName": "dir/file",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/b2api/v2/b2_get_upload_part_url"))
+            .and(body_json(json!({ "fileId": "file_id" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "fileId": "file_id",
+                "uploadUrl": format!("{}/upload_part", mock_server.uri()),
+                "authorizationToken": "upload_token",
+            })))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        for (part_number, sha1) in [
+            ("1", "0a0a9f2a6772942557ab5355d76af442f8f65e01"),
+            ("2", "b1d5781111d84f7b3fe45a0852e59758cd7a87e5"),
+        ] {
+            Mock::given(method("POST"))
+                .and(path("/upload_part"))
+                .and(header("authorization", "upload_token"))
+                .and(header("x-bz-part-number", part_number))
+                .and(header("x-bz-content-sha1", "hex_digits_at_end"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "fileId": "file_id",
+                    "partNumber": part_number.parse::<usize>().unwrap(),
+                    "contentSha1": sha1,
+                })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+        Mock::given(method("POST"))
+            .and(path("/b2api/v2/b2_finish_large_file"))
+            .and(body_json(json!({
+                "fileId": "file_id",
+                "partSha1Array": [
+                    "0a0a9f2a6772942557ab5355d76af442f8f65e01",
+                    "b1d5781111d84f7b3fe45a0852e59758cd7a87e5",
+                ],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/b2api/v2/b2_cancel_large_file"))
+            .and(body_json(json!({ "fileId": "file_id" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("bucket")
+            .application_key_id("key_id")
+            .application_key("key");
+        let acc = builder.build().expect("build must succeed");
+        assert!(acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Multipart));
+
+        let upload_id = acc
+            .create_multipart("dir/file", OpCreateMultipart::new())
+            .await
+            .expect("create multipart must succeed")
+            .upload_id()
+            .to_string();
+        assert_eq!(upload_id, "file_id");
+
+        let mut parts = Vec::new();
+        // Upload parts in reverse order, they will be sorted while finishing.
+        for (part_number, content) in [(2, "10"), (1, "Hello, World!")] {
+            let rp = acc
+                .write_multipart(
+                    "dir/file",
+                    OpWriteMultipart::new(upload_id.clone(), part_number, content.len() as u64),
+                    Box::new(futures::io::Cursor::new(content.as_bytes().to_vec())),
+                )
+                .await
+                .expect("write multipart must succeed");
+            parts.push(rp.into_object_part());
+        }
+
+        acc.complete_multipart(
+            "dir/file",
+            OpCompleteMultipart::new(upload_id.clone(), parts),
+        )
+        .await
+        .expect("complete multipart must succeed");
+        acc.abort_multipart("dir/file", OpAbortMultipart::new(upload_id))
+            .await
+            .expect("abort multipart must succeed");
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::min;
+use std::io;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::ready;
+use futures::AsyncRead;
+use sha1::Digest;
+use sha1::Sha1;
+
+use crate::raw::*;
+
+/// Length of hex encoded sha1 that will be appended to the content.
+pub const SHA1_HEX_LEN: u64 = 40;
+
+/// Sha1Reader appends the hex encoded sha1 of content at the end.
+///
+/// B2 requires the sha1 of every upload, we can't know it before reading
+/// the whole content. With `X-Bz-Content-Sha1: hex_digits_at_end`, b2
+/// will take the last 40 bytes of the body as the sha1, so the total
+/// length should be `size + SHA1_HEX_LEN`.
+pub struct Sha1Reader {
+    inner: BytesReader,
+    hasher: Option<Sha1>,
+    digest: Vec<u8>,
+    pos: usize,
+}
+
+impl Sha1Reader {
+    pub fn new(inner: BytesReader) -> Self {
+        Self {
+            inner,
+            hasher: Some(Sha1::new()),
+            digest: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl AsyncRead for Sha1Reader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let this = self.get_mut();
+
+        if let Some(hasher) = this.hasher.as_mut() {
+            let n = ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+            if n > 0 {
+                hasher.update(&buf[..n]);
+                return Poll::Ready(Ok(n));
+            }
+
+            let hasher = this.hasher.take().expect("hasher must be valid");
+            this.digest = format!("{:x}", hasher.finalize()).into_bytes();
+        }
+
+        let n = min(buf.len(), this.digest.len() - this.pos);
+        buf[..n].copy_from_slice(&this.digest[this.pos..this.pos + n]);
+        this.pos += n;
+        Poll::Ready(Ok(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::io::Cursor;
+    use futures::AsyncReadExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sha1_reader() {
+        let mut r = Sha1Reader::new(Box::new(Cursor::new(b"Hello, World!".to_vec())));
+
+        let mut bs = Vec::new();
+        r.read_to_end(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs.len() as u64, 13 + SHA1_HEX_LEN);
+        assert_eq!(
+            String::from_utf8_lossy(&bs),
+            "Hello, World!0a0a9f2a6772942557ab5355d76af442f8f65e01"
+        );
+
+        let mut r = Sha1Reader::new(Box::new(Cursor::new(vec![])));
+        let mut bs = Vec::new();
+        r.read_to_end(&mut bs).await.expect("read must succeed");
+        assert_eq!(
+            String::from_utf8_lossy(&bs),
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709"
+        );
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::backend::parse_upload_timestamp;
+use super::backend::Backend;
+use super::error::parse_error;
+use super::error::parse_json_deserialize_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct DirStream {
+    backend: Arc<Backend>,
+    root: String,
+    path: String,
+    recursive: bool,
+
+    start_file_name: Option<String>,
+    done: bool,
+}
+
+impl DirStream {
+    /// Max files returned in one page.
+    pub const LIMIT: usize = 1000;
+
+    pub fn new(backend: Arc<Backend>, root: &str, path: &str, recursive: bool) -> Self {
+        Self {
+            backend,
+            root: root.to_string(),
+            path: path.to_string(),
+            recursive,
+            start_file_name: None,
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectPage for DirStream {
+    async fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let prefix = build_abs_path(&self.root, &self.path);
+        let resp = self
+            .backend
+            .b2_list_file_names(
+                &prefix,
+                if self.recursive { None } else { Some("/") },
+                self.start_file_name.as_deref(),
+            )
+            .await?;
+
+        if resp.status() != http::StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: ListOutput =
+            serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+        // `nextFileName` will be null if there are no more files.
+        self.done = output.next_file_name.is_none();
+        self.start_file_name = output.next_file_name;
+
+        let mut entries = Vec::with_capacity(output.files.len());
+        for file in output.files {
+            // Skip the dir itself, and dir markers while listing recursively.
+            if file.file_name == prefix || (self.recursive && file.file_name.ends_with('/')) {
+                continue;
+            }
+
+            let de = match file.action.as_str() {
+                "folder" => ObjectEntry::new(
+                    &build_rel_path(&self.root, &file.file_name),
+                    ObjectMetadata::new(ObjectMode::DIR).with_complete(),
+                ),
+                "upload" => {
+                    let mode = if file.file_name.ends_with('/') {
+                        ObjectMode::DIR
+                    } else {
+                        ObjectMode::FILE
+                    };
+
+                    let mut meta = ObjectMetadata::new(mode);
+                    meta.set_content_length(file.content_length);
+                    if let Some(v) = &file.content_type {
+                        meta.set_content_type(v);
+                    }
+                    meta.set_last_modified(parse_upload_timestamp(file.upload_timestamp)?);
+
+                    ObjectEntry::new(&build_rel_path(&self.root, &file.file_name), meta)
+                }
+                // Unfinished large files (`start`) and hidden files (`hide`)
+                // are not visible to users.
+                _ => continue,
+            };
+
+            entries.push(de);
+        }
+
+        Ok(Some(entries))
+    }
+}
+
+/// Output of `b2_list_file_names`.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ListOutput {
+    files: Vec<FileOutput>,
+    next_file_name: Option<String>,
+}
+
+/// File returned by `b2_list_file_names`, `action` could be `upload`,
+/// `folder`, `start` or `hide`.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct FileOutput {
+    file_name: String,
+    action: String,
+    content_length: u64,
+    content_type: Option<String>,
+    upload_timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// This example is from https://www.backblaze.com/b2/docs/b2_list_file_names.html
+    #[test]
+    fn test_parse_list_output() {
+        let bs = r#"{
+  "files": [
+    {
+      "accountId": "ACCOUNT_ID",
+      "action": "upload",
+      "bucketId": "BUCKET_ID",
+      "contentLength": 7,
+      "contentSha1": "dc724af18fbdd4e59189f5fe768a5f8311527050",
+      "contentType": "text/plain",
+      "fileId": "4_z27c88f1d182b150646ff0b16_f1004ba650fe24e6b_d20150809_m012853_c100_v0009990_t0000",
+      "fileInfo": {"src_last_modified_millis": "1439083733000"},
+      "fileName": "files/hello.txt",
+      "uploadTimestamp": 1439083733000
+    },
+    {
+      "action": "folder",
+      "contentLength": 0,
+      "contentSha1": null,
+      "contentType": null,
+      "fileId": null,
+      "fileName": "files/sub/",
+      "uploadTimestamp": 0
+    }
+  ],
+  "nextFileName": "files/world.txt"
+}"#;
+
+        let out: ListOutput = serde_json::from_str(bs).expect("must success");
+        assert_eq!(out.next_file_name.as_deref(), Some("files/world.txt"));
+        assert_eq!(out.files.len(), 2);
+        assert_eq!(out.files[0].file_name, "files/hello.txt");
+        assert_eq!(out.files[0].action, "upload");
+        assert_eq!(out.files[0].content_length, 7);
+        assert_eq!(out.files[0].content_type.as_deref(), Some("text/plain"));
+        assert_eq!(out.files[0].upload_timestamp, 1439083733000);
+        assert_eq!(out.files[1].file_name, "files/sub/");
+        assert_eq!(out.files[1].action, "folder");
+
+        let out: ListOutput =
+            serde_json::from_str(r#"{"files": [], "nextFileName": null}"#).expect("must success");
+        assert!(out.files.is_empty());
+        assert_eq!(out.next_file_name, None);
+    }
+}
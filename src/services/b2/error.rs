@@ -0,0 +1,162 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use http::Response;
+use http::StatusCode;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// B2Error is the error returned by b2 native api.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct B2Error {
+    status: u16,
+    code: String,
+    message: String,
+}
+
+/// Parse error respons into Error.
+///
+/// B2 returns errors in json like `{"status": 401, "code":
+/// "bad_auth_token", "message": "..."}`, responses of `HEAD` don't have
+/// body so we will fallback to the status code.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    let (kind, retryable, message) = match serde_json::from_slice::<B2Error>(&bs) {
+        Ok(b2_error) => {
+            let (kind, retryable) = parse_error_code(parts.status, &b2_error.code);
+            (kind, retryable, format!("{:?}", b2_error))
+        }
+        Err(_) => {
+            let (kind, retryable) = parse_error_code(parts.status, "");
+            (kind, retryable, String::from_utf8_lossy(&bs).into_owned())
+        }
+    };
+
+    let mut err = Error::new(kind, &message).with_context("response", format!("{:?}", parts));
+    err = with_current_metadata(err, &parts.headers);
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+/// Map the error code of b2 into error kind and whether it's retryable.
+///
+/// Codes are documented at <https://www.backblaze.com/b2/docs/calling.html#error_handling>.
+fn parse_error_code(status: StatusCode, code: &str) -> (ErrorKind, bool) {
+    match code {
+        "not_found" | "file_not_present" | "no_such_file" => (ErrorKind::ObjectNotFound, false),
+        "bad_auth_token" | "expired_auth_token" | "unauthorized" | "access_denied" => {
+            (ErrorKind::ObjectPermissionDenied, false)
+        }
+        // Caps on storage, download or transactions won't be lifted until
+        // users change them, retry won't help.
+        "cap_exceeded"
+        | "storage_cap_exceeded"
+        | "download_cap_exceeded"
+        | "transaction_cap_exceeded" => (ErrorKind::ObjectPermissionDenied, false),
+        "too_many_requests" | "request_timeout" | "service_unavailable" | "internal_error" => {
+            (ErrorKind::Unexpected, true)
+        }
+        _ => match status {
+            StatusCode::NOT_FOUND => (ErrorKind::ObjectNotFound, false),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                (ErrorKind::ObjectPermissionDenied, false)
+            }
+            StatusCode::NOT_MODIFIED | StatusCode::PRECONDITION_FAILED => {
+                (ErrorKind::ConditionNotMatch, false)
+            }
+            StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+            _ => (ErrorKind::Unexpected, false),
+        },
+    }
+}
+
+pub fn parse_json_deserialize_error(e: serde_json::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "deserialize json").set_source(e)
+}
+
+pub fn parse_json_serialize_error(e: serde_json::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "serialize json").set_source(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::io::Cursor;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parse_error() {
+        for (status, body, kind, temporary) in [
+            (
+                StatusCode::UNAUTHORIZED,
+                r#"{"code": "bad_auth_token", "message": "Invalid authorization token", "status": 401}"#,
+                ErrorKind::ObjectPermissionDenied,
+                false,
+            ),
+            (
+                StatusCode::FORBIDDEN,
+                r#"{"code": "cap_exceeded", "message": "Cannot upload files, storage cap exceeded", "status": 403}"#,
+                ErrorKind::ObjectPermissionDenied,
+                false,
+            ),
+            (
+                StatusCode::BAD_REQUEST,
+                r#"{"code": "file_not_present", "message": "File not present: dir/file", "status": 400}"#,
+                ErrorKind::ObjectNotFound,
+                false,
+            ),
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                r#"{"code": "service_unavailable", "message": "no tomes available", "status": 503}"#,
+                ErrorKind::Unexpected,
+                true,
+            ),
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                r#"{"code": "too_many_requests", "message": "", "status": 429}"#,
+                ErrorKind::Unexpected,
+                true,
+            ),
+            // Response of `HEAD` doesn't have body.
+            (StatusCode::NOT_FOUND, "", ErrorKind::ObjectNotFound, false),
+        ] {
+            let resp = Response::builder()
+                .status(status)
+                .body(IncomingAsyncBody::new(Box::new(Cursor::new(
+                    body.as_bytes().to_vec(),
+                ))))
+                .expect("must success");
+
+            let err = parse_error(resp).await.expect("must success");
+            assert_eq!(err.kind(), kind, "{status}");
+            assert_eq!(err.is_temporary(), temporary, "{status}");
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Backblaze B2 services support via native API.
+//!
+//! # Configuration
+//!
+//! - `root`: Set the work directory for backend
+//! - `bucket`: Set the bucket name for backend
+//! - `bucket_id`: Set the bucket id, will be looked up by bucket name if not set
+//! - `endpoint`: Set the endpoint to authorize account, default to `https://api.backblazeb2.com`
+//! - `application_key_id`: Set the application key id for backend
+//! - `application_key`: Set the application key for backend
+//!
+//! You can refer to [`Builder`]'s docs for more information
+//!
+//! # Authentication
+//!
+//! The token returned by `b2_authorize_account` will be cached and
+//! refreshed before it has been expired. If b2 returns `401 Unauthorized`,
+//! we will authenticate again and resend the request.
+//!
+//! Upload urls returned by `b2_get_upload_url` are cached and reused by
+//! later uploads. Streaming uploads can't be resent, the failed upload url
+//! will be dropped and a temporary error will be returned instead so that
+//! users can retry the upload.
+//!
+//! # Checksum
+//!
+//! B2 requires sha1 of every upload. OpenDAL calculates it while sending
+//! the content and appends it at the end of body via
+//! `X-Bz-Content-Sha1: hex_digits_at_end`.
+//!
+//! # Large Files
+//!
+//! Multipart uploads are backed by large files: `b2_start_large_file`
+//! creates the upload, every part is uploaded by `b2_upload_part` via a new
+//! url from `b2_get_upload_part_url`, and the sha1 of parts are sent to
+//! `b2_finish_large_file` while completing. B2 requires every part except
+//! the last one to be at least 5 MB.
+//!
+//! # Delete
+//!
+//! Every upload creates a new version of file in b2, deleting a file will
+//! delete all of its versions via `b2_delete_file_version`.
+//!
+//! # Environment
+//!
+//! - `OPENDAL_B2_BUCKET`    required
+//! - `OPENDAL_B2_APPLICATION_KEY_ID`  required
+//! - `OPENDAL_B2_APPLICATION_KEY`  required
+//! - `OPENDAL_B2_BUCKET_ID`  optional
+//! - `OPENDAL_B2_ENDPOINT`  optional
+//!
+//! # Example
+//!
+//! ## Initiate via environment variables
+//!
+//! Set environment correctly:
+//!
+//! ```shell
+//! export OPENDAL_B2_BUCKET=test
+//! export OPENDAL_B2_ROOT=/path/to/dir/
+//! export OPENDAL_B2_APPLICATION_KEY_ID=application_key_id
+//! export OPENDAL_B2_APPLICATION_KEY=application_key
+//! ```
+//! ```no_run
+//! use anyhow::Result;
+//! use opendal::Object;
+//! use opendal::Operator;
+//! use opendal::Scheme;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let op: Operator = Operator::from_env(Scheme::B2)?;
+//!
+//!     // create an object handler to start operation on it.
+//!     let _op: Object = op.object("test_file");
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ## Via Builder
+//!
+//! ```no_run
+//! use anyhow::Result;
+//! use opendal::services::b2;
+//! use opendal::Object;
+//! use opendal::Operator;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     // create backend builder
+//!     let mut builder = b2::Builder::default();
+//!
+//!     // set the storage bucket for OpenDAL
+//!     builder.bucket("test");
+//!     // Set the application key.
+//!     builder.application_key_id("application_key_id");
+//!     builder.application_key("application_key");
+//!
+//!     let op: Operator = Operator::new(builder.build()?);
+//!
+//!     // Create an object handle to start operation on object.
+//!     let _: Object = op.object("test_file");
+//!
+//!     Ok(())
+//! }
+//! ```
+
+mod backend;
+pub use backend::Builder;
+
+mod auth;
+mod checksum;
+mod dir_stream;
+pub(super) mod error;
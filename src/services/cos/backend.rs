@@ -0,0 +1,795 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::header::CONTENT_LENGTH;
+use http::header::CONTENT_TYPE;
+use http::header::HOST;
+use http::header::IF_MATCH;
+use http::header::IF_NONE_MATCH;
+use http::header::RANGE;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use http::Uri;
+use log::debug;
+
+use super::dir_stream::DirStream;
+use super::error::parse_error;
+use super::signer::Signer;
+use crate::raw::*;
+use crate::*;
+
+/// Builder for Tencent Cloud COS services
+#[derive(Default, Clone)]
+pub struct Builder {
+    root: Option<String>,
+    bucket: Option<String>,
+    region: Option<String>,
+    endpoint: Option<String>,
+    secret_id: Option<String>,
+    secret_key: Option<String>,
+    security_token: Option<String>,
+}
+
+impl Debug for Builder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut d = f.debug_struct("Builder");
+        d.field("root", &self.root)
+            .field("bucket", &self.bucket)
+            .field("region", &self.region)
+            .field("endpoint", &self.endpoint);
+        if self.secret_id.is_some() {
+            d.field("secret_id", &"<redacted>");
+        }
+        if self.secret_key.is_some() {
+            d.field("secret_key", &"<redacted>");
+        }
+        if self.security_token.is_some() {
+            d.field("security_token", &"<redacted>");
+        }
+        d.finish()
+    }
+}
+
+impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "bucket",
+        "region",
+        "endpoint",
+        "secret_id",
+        "secret_key",
+        "security_token",
+    ];
+
+    pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
+        let mut builder = Builder::default();
+
+        for (k, v) in it {
+            let v = v.as_str();
+            match k.as_ref() {
+                "root" => builder.root(v),
+                "bucket" => builder.bucket(v),
+                "region" => builder.region(v),
+                "endpoint" => builder.endpoint(v),
+                "secret_id" => builder.secret_id(v),
+                "secret_key" => builder.secret_key(v),
+                "security_token" => builder.security_token(v),
+                _ => continue,
+            };
+        }
+
+        builder
+    }
+
+    /// Set root of this backend.
+    ///
+    /// All operations will happen under this root.
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+
+        self
+    }
+
+    /// Set bucket of this backend.
+    ///
+    /// The bucket name of COS contains the APPID, like `examplebucket-1250000000`.
+    pub fn bucket(&mut self, bucket: &str) -> &mut Self {
+        if !bucket.is_empty() {
+            self.bucket = Some(bucket.to_string());
+        }
+
+        self
+    }
+
+    /// Set region of this backend, like `ap-beijing`.
+    ///
+    /// The endpoint will be `https://<bucket>.cos.<region>.myqcloud.com`
+    /// if endpoint is not set.
+    pub fn region(&mut self, region: &str) -> &mut Self {
+        if !region.is_empty() {
+            self.region = Some(region.to_string());
+        }
+
+        self
+    }
+
+    /// Set endpoint of this backend.
+    ///
+    /// Both COS default domain and custom domain endpoints are allowed.
+    /// Please DO NOT add the bucket name to the default domain endpoint.
+    ///
+    /// - `https://cos.ap-beijing.myqcloud.com`
+    /// - `cos.ap-beijing.myqcloud.com` (https by default)
+    /// - `https://custom.example.com` (bound to the bucket)
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        }
+
+        self
+    }
+
+    /// Set secret_id of this backend.
+    pub fn secret_id(&mut self, secret_id: &str) -> &mut Self {
+        if !secret_id.is_empty() {
+            self.secret_id = Some(secret_id.to_string());
+        }
+
+        self
+    }
+
+    /// Set secret_key of this backend.
+    pub fn secret_key(&mut self, secret_key: &str) -> &mut Self {
+        if !secret_key.is_empty() {
+            self.secret_key = Some(secret_key.to_string());
+        }
+
+        self
+    }
+
+    /// Set security_token of this backend, which is required by
+    /// temporary credentials.
+    pub fn security_token(&mut self, security_token: &str) -> &mut Self {
+        if !security_token.is_empty() {
+            self.security_token = Some(security_token.to_string());
+        }
+
+        self
+    }
+
+    /// Consume builder to build a COS backend.
+    pub fn build(&mut self) -> Result<impl Accessor> {
+        debug!("backend build started: {:?}", &self);
+
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let bucket = match &self.bucket {
+            Some(bucket) => Ok(bucket.to_string()),
+            None => Err(Error::new(ErrorKind::ConfigInvalid, "bucket is empty")
+                .with_context("field", "bucket")
+                .with_context("service", Scheme::Cos)),
+        }?;
+        debug!("backend use bucket {}", &bucket);
+
+        let endpoint = match (&self.endpoint, &self.region) {
+            (Some(endpoint), _) => {
+                let uri = endpoint.parse::<Uri>().map_err(|err| {
+                    Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+                        .with_context("field", "endpoint")
+                        .with_context("service", Scheme::Cos)
+                        .set_source(err)
+                })?;
+                let scheme = uri.scheme_str().unwrap_or("https");
+                let host = uri.host().unwrap_or_default();
+                let host = if host.starts_with("cos.") && host.ends_with(".myqcloud.com") {
+                    format!("{bucket}.{host}")
+                } else {
+                    host.to_string()
+                };
+                match uri.port_u16() {
+                    Some(port) => format!("{scheme}://{host}:{port}"),
+                    None => format!("{scheme}://{host}"),
+                }
+            }
+            (None, Some(region)) => format!("https://{bucket}.cos.{region}.myqcloud.com"),
+            (None, None) => {
+                return Err(
+                    Error::new(ErrorKind::ConfigInvalid, "endpoint or region is required")
+                        .with_context("field", "region")
+                        .with_context("service", Scheme::Cos),
+                )
+            }
+        };
+        debug!("backend use endpoint {}", &endpoint);
+
+        let host = endpoint
+            .split_once("://")
+            .map(|(_, v)| v.to_string())
+            .unwrap_or_default();
+
+        let signer = match (&self.secret_id, &self.secret_key) {
+            (Some(secret_id), Some(secret_key)) => {
+                let mut signer = Signer::new(secret_id, secret_key);
+                if let Some(token) = &self.security_token {
+                    signer = signer.with_security_token(token);
+                }
+                Some(Arc::new(signer))
+            }
+            (None, None) => None,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "secret_id and secret_key must be set together",
+                )
+                .with_context("field", "secret_key")
+                .with_context("service", Scheme::Cos))
+            }
+        };
+
+        debug!("backend build finished: {:?}", &self);
+        Ok(apply_wrapper(Backend {
+            client: HttpClient::new(),
+            root,
+            endpoint,
+            host,
+            bucket,
+            signer,
+        }))
+    }
+}
+
+/// Backend for Tencent Cloud COS services.
+#[derive(Debug, Clone)]
+pub struct Backend {
+    client: HttpClient,
+    root: String,
+    endpoint: String,
+    /// Authority of endpoint, will be signed in `Host` header.
+    host: String,
+    bucket: String,
+    /// `None` means accessing COS anonymously.
+    signer: Option<Arc<Signer>>,
+}
+
+#[async_trait]
+impl Accessor for Backend {
+    fn metadata(&self) -> AccessorMetadata {
+        let mut capabilities = AccessorCapability::Read
+            | AccessorCapability::Write
+            | AccessorCapability::List
+            | AccessorCapability::ConditionalRead
+            | AccessorCapability::ListRecursive;
+        if self.signer.is_some() {
+            capabilities |= AccessorCapability::Presign;
+        }
+
+        let mut am = AccessorMetadata::default();
+        am.set_scheme(Scheme::Cos)
+            .set_root(&self.root)
+            .set_name(&self.bucket)
+            .set_capabilities(capabilities);
+
+        am
+    }
+
+    async fn create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
+        let mut req = self.cos_put_object_request(path, Some(0), None, AsyncBody::Empty)?;
+
+        self.sign(&mut req)?;
+
+        let resp = self.client.send_async(req).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCreate::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
+        let mut req = self.cos_get_object_request(path, &args)?;
+
+        self.sign(&mut req)?;
+
+        let resp = self.client.send_async(req).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_object_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body().reader()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
+        let mut req = self.cos_put_object_request(
+            path,
+            Some(args.size()),
+            args.content_type(),
+            AsyncBody::Reader(r),
+        )?;
+        insert_content_disposition(req.headers_mut(), &args)?;
+        insert_cache_control(req.headers_mut(), &args)?;
+
+        self.sign(&mut req)?;
+
+        let resp = self.client.send_async(req).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                let etag = parse_etag(resp.headers())?;
+                resp.into_body().consume().await?;
+
+                let rp = RpWrite::new(args.size());
+                Ok(match etag {
+                    Some(v) => rp.with_etag(&v),
+                    None => rp,
+                })
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(ObjectMetadata::new(ObjectMode::DIR)));
+        }
+
+        let mut req = self.cos_head_object_request(path)?;
+
+        self.sign(&mut req)?;
+
+        let resp = self.client.send_async(req).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => parse_into_object_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::NOT_FOUND if path.ends_with('/') => {
+                Ok(RpStat::new(ObjectMetadata::new(ObjectMode::DIR)))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        let resp = self.cos_delete_object(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::NO_CONTENT | StatusCode::ACCEPTED | StatusCode::NOT_FOUND => {
+                Ok(RpDelete::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
+        Ok((
+            RpList::default(),
+            Box::new(DirStream::new(
+                Arc::new(self.clone()),
+                &self.root,
+                path,
+                if args.recursive() { "" } else { "/" },
+            )),
+        ))
+    }
+
+    fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let signer = self.signer.as_ref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unsupported,
+                "presign requires credentials, please set secret_id and secret_key",
+            )
+            .with_operation(Operation::Presign.into_static())
+        })?;
+
+        // We will not send this request out, just for signing.
+        let mut req = match args.operation() {
+            PresignOperation::Stat(_) => self.cos_head_object_request(path)?,
+            PresignOperation::Read(v) => self.cos_get_object_request(path, v)?,
+            // Content length will be returned in headers so that callers
+            // know what to send.
+            PresignOperation::Write(v) => self.cos_put_object_request(
+                path,
+                (v.size() > 0).then(|| v.size()),
+                v.content_type(),
+                AsyncBody::Empty,
+            )?,
+            PresignOperation::WriteMultipart(_) => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "presign write multipart is not supported by cos",
+                )
+                .with_operation(Operation::Presign.into_static()))
+            }
+        };
+
+        signer.sign_query(&mut req, args.expire())?;
+
+        // We don't need this request anymore, consume it directly.
+        let (parts, _) = req.into_parts();
+
+        Ok(RpPresign::new(PresignedRequest::new(
+            parts.method,
+            parts.uri,
+            parts.headers,
+        )))
+    }
+}
+
+impl Backend {
+    fn sign(&self, req: &mut Request<AsyncBody>) -> Result<()> {
+        match &self.signer {
+            Some(signer) => signer.sign(req),
+            None => Ok(()),
+        }
+    }
+
+    fn cos_get_object_request(&self, path: &str, args: &OpRead) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::get(&url).header(HOST, &self.host);
+
+        if let Some(range_header) = args.range().to_header() {
+            req = req.header(RANGE, range_header)
+        }
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, if_none_match);
+        }
+
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    fn cos_put_object_request(
+        &self,
+        path: &str,
+        size: Option<u64>,
+        content_type: Option<&str>,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::put(&url).header(HOST, &self.host);
+
+        if let Some(size) = size {
+            req = req.header(CONTENT_LENGTH, size)
+        }
+        if let Some(mime) = content_type {
+            req = req.header(CONTENT_TYPE, mime)
+        }
+
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    fn cos_head_object_request(&self, path: &str) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+
+        let req = Request::head(&url)
+            .header(HOST, &self.host)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    async fn cos_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::delete(&url)
+            .header(HOST, &self.host)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    pub(crate) async fn cos_list_objects(
+        &self,
+        path: &str,
+        delimiter: &str,
+        next_marker: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut queries = vec![];
+        // Empty delimiter means list all objects under prefix recursively.
+        if !delimiter.is_empty() {
+            queries.push(format!("delimiter={delimiter}"));
+        }
+        if !p.is_empty() {
+            queries.push(format!("prefix={}", percent_encode_path(&p)));
+        }
+        if !next_marker.is_empty() {
+            queries.push(format!("marker={}", percent_encode_path(next_marker)));
+        }
+
+        let mut url = format!("{}/", self.endpoint);
+        if !queries.is_empty() {
+            write!(url, "?{}", queries.join("&")).expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .header(HOST, &self.host)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::matchers::query_param;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    #[test]
+    fn test_build_endpoint() {
+        let endpoint = |ep: Option<&str>, region: Option<&str>| {
+            let mut builder = Builder::default();
+            builder.bucket("examplebucket-1250000000");
+            if let Some(ep) = ep {
+                builder.endpoint(ep);
+            }
+            if let Some(region) = region {
+                builder.region(region);
+            }
+            builder
+                .build()
+                .map(|acc| format!("{:?}", acc))
+                .map_err(|err| err.kind())
+        };
+
+        for (ep, region, expected) in [
+            (
+                Some("https://cos.ap-beijing.myqcloud.com"),
+                None,
+                "https://examplebucket-1250000000.cos.ap-beijing.myqcloud.com",
+            ),
+            (
+                Some("cos.ap-beijing.myqcloud.com"),
+                None,
+                "https://examplebucket-1250000000.cos.ap-beijing.myqcloud.com",
+            ),
+            (
+                None,
+                Some("ap-guangzhou"),
+                "https://examplebucket-1250000000.cos.ap-guangzhou.myqcloud.com",
+            ),
+            (
+                Some("http://127.0.0.1:9000"),
+                Some("ap-guangzhou"),
+                "http://127.0.0.1:9000",
+            ),
+        ] {
+            let debug = endpoint(ep, region).expect("build must succeed");
+            assert!(
+                debug.contains(&format!("endpoint: \"{expected}\"")),
+                "{debug}"
+            );
+        }
+
+        assert_eq!(endpoint(None, None).unwrap_err(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    fn test_presign() {
+        let mut builder = Builder::default();
+        builder
+            .bucket("examplebucket-1250000000")
+            .region("ap-beijing")
+            .secret_id("secret_id")
+            .secret_key("secret_key")
+            .security_token("token");
+        let acc = builder.build().expect("build must succeed");
+
+        let req = acc
+            .presign(
+                "file",
+                OpPresign::new(OpRead::new(), time::Duration::hours(1)),
+            )
+            .expect("presign must succeed")
+            .into_presigned_request();
+        assert_eq!(req.method(), http::Method::GET);
+        assert_eq!(
+            req.uri().host(),
+            Some("examplebucket-1250000000.cos.ap-beijing.myqcloud.com")
+        );
+        let query = req.uri().query().expect("query must exist");
+        for key in [
+            "q-sign-algorithm=sha1",
+            "q-ak=secret_id",
+            "q-header-list=host",
+            "q-signature=",
+            "x-cos-security-token=token",
+        ] {
+            assert!(query.contains(key), "{query}");
+        }
+
+        let req = acc
+            .presign(
+                "file",
+                OpPresign::new(
+                    OpWrite::new(5).with_content_type("text/plain"),
+                    time::Duration::hours(1),
+                ),
+            )
+            .expect("presign must succeed")
+            .into_presigned_request();
+        assert_eq!(req.method(), http::Method::PUT);
+        let query = req.uri().query().expect("query must exist");
+        assert!(
+            query.contains("q-header-list=content-length;content-type;host"),
+            "{query}"
+        );
+        assert_eq!(
+            req.header().get(CONTENT_TYPE).and_then(|v| v.to_str().ok()),
+            Some("text/plain")
+        );
+    }
+
+    #[test]
+    fn test_presign_without_credential() {
+        let mut builder = Builder::default();
+        builder
+            .bucket("examplebucket-1250000000")
+            .region("ap-beijing");
+        let acc = builder.build().expect("build must succeed");
+
+        assert!(!acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Presign));
+        let err = acc
+            .presign(
+                "file",
+                OpPresign::new(OpRead::new(), time::Duration::hours(1)),
+            )
+            .expect_err("presign must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_read_with_range() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/file"))
+            .and(wiremock::matchers::header("range", "bytes=1-3"))
+            .and(|req: &wiremock::Request| {
+                req.headers.iter().any(|(k, v)| {
+                    k.as_str() == "authorization"
+                        && v.last().as_str().starts_with("q-sign-algorithm=sha1&")
+                })
+            })
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", "bytes 1-3/5")
+                    .set_body_string("ell"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("examplebucket-1250000000")
+            .secret_id("secret_id")
+            .secret_key("secret_key");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let bs = op
+            .object("file")
+            .range_read(1..4)
+            .await
+            .expect("read must succeed");
+        assert_eq!(bs, b"ell");
+    }
+
+    #[tokio::test]
+    async fn test_list_with_marker() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(query_param("prefix", "dir/"))
+            .and(|req: &wiremock::Request| !req.url.query_pairs().any(|(k, _)| k == "marker"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListBucketResult>
+    <IsTruncated>true</IsTruncated>
+    <NextMarker>dir/a</NextMarker>
+    <Contents><Key>dir/a</Key><Size>1</Size></Contents>
+</ListBucketResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(query_param("prefix", "dir/"))
+            .and(query_param("marker", "dir/a"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListBucketResult>
+    <IsTruncated>false</IsTruncated>
+    <Contents><Key>dir/b</Key><Size>2</Size></Contents>
+</ListBucketResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("examplebucket-1250000000");
+        let acc = builder.build().expect("build must succeed");
+
+        let (_, mut pager) = acc
+            .list("dir/", OpList::new())
+            .await
+            .expect("list must succeed");
+        let mut paths = Vec::new();
+        while let Some(entries) = pager.next_page().await.expect("next page must succeed") {
+            paths.extend(entries.into_iter().map(|v| v.path().to_string()));
+        }
+        assert_eq!(paths, ["dir/a", "dir/b"]);
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Buf;
+use quick_xml::de;
+use serde::Deserialize;
+
+use super::backend::Backend;
+use super::error::parse_error;
+use super::error::parse_xml_deserialize_error;
+use crate::raw::*;
+use crate::ObjectMetadata;
+use crate::ObjectMode;
+use crate::Result;
+
+pub struct DirStream {
+    backend: Arc<Backend>,
+    root: String,
+    path: String,
+    delimiter: &'static str,
+
+    next_marker: String,
+    done: bool,
+}
+
+impl DirStream {
+    pub fn new(backend: Arc<Backend>, root: &str, path: &str, delimiter: &'static str) -> Self {
+        Self {
+            backend,
+            root: root.to_string(),
+            path: path.to_string(),
+            delimiter,
+            next_marker: "".to_string(),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectPage for DirStream {
+    async fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let resp = self
+            .backend
+            .cos_list_objects(&self.path, self.delimiter, &self.next_marker)
+            .await?;
+
+        if resp.status() != http::StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+
+        let output: Output = de::from_reader(bs.reader()).map_err(parse_xml_deserialize_error)?;
+
+        // `NextMarker` is only returned while delimiter is set, fallback to
+        // the last key otherwise.
+        self.done = !output.is_truncated;
+        self.next_marker = match output.next_marker {
+            Some(v) if !v.is_empty() => v,
+            _ => output
+                .contents
+                .last()
+                .map(|v| v.key.clone())
+                .unwrap_or_default(),
+        };
+        if self.next_marker.is_empty() {
+            self.done = true;
+        }
+
+        let mut entries = Vec::with_capacity(output.common_prefixes.len() + output.contents.len());
+
+        for prefix in output.common_prefixes {
+            let de = ObjectEntry::new(
+                &build_rel_path(&self.root, &prefix.prefix),
+                ObjectMetadata::new(ObjectMode::DIR).with_complete(),
+            );
+
+            entries.push(de);
+        }
+
+        for object in output.contents {
+            if object.key.ends_with('/') {
+                continue;
+            }
+
+            let meta = ObjectMetadata::new(ObjectMode::FILE).with_content_length(object.size);
+
+            let de = ObjectEntry::new(&build_rel_path(&self.root, &object.key), meta);
+
+            entries.push(de);
+        }
+
+        Ok(Some(entries))
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct Output {
+    name: String,
+    prefix: String,
+    contents: Vec<Content>,
+    common_prefixes: Vec<CommonPrefix>,
+    is_truncated: bool,
+    next_marker: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct CommonPrefix {
+    prefix: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct Content {
+    key: String,
+    size: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Buf;
+
+    use super::*;
+
+    /// Example is from [GET Bucket (List Objects)](https://www.tencentcloud.com/document/product/436/30614)
+    #[test]
+    fn test_parse_xml() {
+        let bs = bytes::Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult>
+    <Name>examplebucket-1250000000</Name>
+    <Prefix>example/</Prefix>
+    <Marker/>
+    <MaxKeys>1000</MaxKeys>
+    <Delimiter>/</Delimiter>
+    <IsTruncated>true</IsTruncated>
+    <NextMarker>example/file.jpg</NextMarker>
+    <CommonPrefixes>
+        <Prefix>example/dir/</Prefix>
+    </CommonPrefixes>
+    <Contents>
+        <Key>example/</Key>
+        <LastModified>2019-08-16T10:45:53.000Z</LastModified>
+        <ETag>"d41d8cd98f00b204e9800998ecf8427e"</ETag>
+        <Size>0</Size>
+        <Owner>
+            <ID>1250000000</ID>
+            <DisplayName>1250000000</DisplayName>
+        </Owner>
+        <StorageClass>STANDARD</StorageClass>
+    </Contents>
+    <Contents>
+        <Key>example/file.jpg</Key>
+        <LastModified>2019-08-16T10:45:53.000Z</LastModified>
+        <ETag>"c9d28698978bb6fef6c1ed1c439a17d3"</ETag>
+        <Size>37</Size>
+        <Owner>
+            <ID>1250000000</ID>
+            <DisplayName>1250000000</DisplayName>
+        </Owner>
+        <StorageClass>STANDARD</StorageClass>
+    </Contents>
+</ListBucketResult>"#,
+        );
+        let out: Output = de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(out.name, "examplebucket-1250000000");
+        assert_eq!(out.prefix, "example/");
+        assert!(out.is_truncated);
+        assert_eq!(out.next_marker, Some("example/file.jpg".to_string()));
+        assert_eq!(
+            out.contents
+                .iter()
+                .map(|v| (v.key.as_str(), v.size))
+                .collect::<Vec<_>>(),
+            [("example/", 0), ("example/file.jpg", 37)],
+        );
+        assert_eq!(
+            out.common_prefixes
+                .iter()
+                .map(|v| v.prefix.as_str())
+                .collect::<Vec<_>>(),
+            ["example/dir/"],
+        );
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Buf;
+use http::Response;
+use http::StatusCode;
+use quick_xml::de;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// CosError is the error returned by cos service.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct CosError {
+    code: String,
+    message: String,
+    resource: String,
+    request_id: String,
+    trace_id: String,
+}
+
+/// Parse error respons into Error.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    let (kind, retryable, message) = match de::from_reader::<_, CosError>(bs.clone().reader()) {
+        Ok(cos_error) => {
+            let (kind, retryable) = parse_error_code(parts.status, &cos_error.code);
+            (kind, retryable, format!("{:?}", cos_error))
+        }
+        Err(_) => {
+            let (kind, retryable) = parse_error_code(parts.status, "");
+            (kind, retryable, String::from_utf8_lossy(&bs).into_owned())
+        }
+    };
+
+    let mut err = Error::new(kind, &message).with_context("response", format!("{:?}", parts));
+    err = with_current_metadata(err, &parts.headers);
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+/// Map the error code of cos into error kind and whether it's retryable.
+///
+/// Error codes could be found at <https://www.tencentcloud.com/document/product/436/7730>
+fn parse_error_code(status: StatusCode, code: &str) -> (ErrorKind, bool) {
+    match code {
+        "NoSuchKey" | "NoSuchBucket" => (ErrorKind::ObjectNotFound, false),
+        "AccessDenied" => (ErrorKind::ObjectPermissionDenied, false),
+        "RequestTimeTooSkewed" | "SlowDown" | "ServiceUnavailable" => (ErrorKind::Unexpected, true),
+        _ => match status {
+            StatusCode::NOT_FOUND => (ErrorKind::ObjectNotFound, false),
+            StatusCode::FORBIDDEN => (ErrorKind::ObjectPermissionDenied, false),
+            StatusCode::NOT_MODIFIED | StatusCode::PRECONDITION_FAILED => {
+                (ErrorKind::ConditionNotMatch, false)
+            }
+            StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+            _ => (ErrorKind::Unexpected, false),
+        },
+    }
+}
+
+pub fn parse_xml_deserialize_error(e: quick_xml::DeError) -> Error {
+    Error::new(ErrorKind::Unexpected, "deserialize xml").set_source(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let bs = bytes::Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>NoSuchKey</Code>
+  <Message>The specified key does not exist.</Message>
+  <Resource>examplebucket-1250000000.cos.ap-beijing.myqcloud.com/exampleobject</Resource>
+  <RequestId>NTg3NzRiMjVfYmRjMzVfMTViMl8yNzM2****</RequestId>
+  <TraceId>OGVmYzZiMmQzYjA2OWNhODk0NTRkMTBiOWVmMDAxODc0OWRkZjk0ZDM1NmI1M2E2MTRlY2MzZDhmNmI5MWI1OTBjYzE2MjAxN2M1MzJiOTdkZjMxMDVlYTZjN2FiMmI0NTk3NWFiNjAyMzdlM2RlMmVmOGNiNWIxYjYwNDFhYmQ=</TraceId>
+</Error>"#,
+        );
+
+        let out: CosError = de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(out.code, "NoSuchKey");
+        assert_eq!(out.message, "The specified key does not exist.");
+        assert_eq!(
+            out.resource,
+            "examplebucket-1250000000.cos.ap-beijing.myqcloud.com/exampleobject"
+        );
+        assert_eq!(out.request_id, "NTg3NzRiMjVfYmRjMzVfMTViMl8yNzM2****");
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_code() {
+        for (status, code, kind, retryable) in [
+            (
+                StatusCode::NOT_FOUND,
+                "NoSuchKey",
+                ErrorKind::ObjectNotFound,
+                false,
+            ),
+            (
+                StatusCode::FORBIDDEN,
+                "AccessDenied",
+                ErrorKind::ObjectPermissionDenied,
+                false,
+            ),
+            (
+                StatusCode::FORBIDDEN,
+                "RequestTimeTooSkewed",
+                ErrorKind::Unexpected,
+                true,
+            ),
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "",
+                ErrorKind::Unexpected,
+                true,
+            ),
+        ] {
+            let bs = format!("<Error><Code>{code}</Code></Error>");
+            let body = IncomingAsyncBody::new(Box::new(Cursor::new(bs.into_bytes())));
+            let resp = Response::builder()
+                .status(status)
+                .body(body)
+                .expect("response must be valid");
+
+            let err = parse_error(resp).await.expect("parse must succeed");
+            assert_eq!(err.kind(), kind, "{code}");
+            assert_eq!(err.is_temporary(), retryable, "{code}");
+        }
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tencent Cloud COS services support.
+//!
+//! Requests are signed with COS native signature, so presigned urls work
+//! with both permanent and temporary credentials.
+//!
+//! # Configuration
+//!
+//! - `root`: Set the work directory for backend
+//! - `bucket`: Set the bucket name with APPID for backend, like `examplebucket-1250000000`
+//! - `region`: Set the region for backend, like `ap-beijing`
+//! - `endpoint`: Customizable endpoint setting, will be built from `region` if not set
+//! - `secret_id`: Set the secret_id for backend.
+//! - `secret_key`: Set the secret_key for backend.
+//! - `security_token`: Set the security_token of temporary credentials for backend.
+//!
+//! Requests will be sent anonymously if `secret_id` and `secret_key` are not set.
+//!
+//! You can refer to [`Builder`]'s docs for more information
+//!
+//! # Environment
+//!
+//! - `OPENDAL_COS_ROOT`
+//! - `OPENDAL_COS_BUCKET`
+//! - `OPENDAL_COS_REGION`
+//! - `OPENDAL_COS_ENDPOINT`
+//! - `OPENDAL_COS_SECRET_ID`
+//! - `OPENDAL_COS_SECRET_KEY`
+//! - `OPENDAL_COS_SECURITY_TOKEN`
+//!
+//! # Example
+//!
+//! ## Initiate via environment variables
+//!
+//! Set environment correctly:
+//!
+//! ```shell
+//! export OPENDAL_COS_BUCKET=examplebucket-1250000000
+//! export OPENDAL_COS_REGION=ap-beijing
+//! export OPENDAL_COS_ROOT=/path/to/dir/
+//! export OPENDAL_COS_SECRET_ID=secret_id
+//! export OPENDAL_COS_SECRET_KEY=secret_key
+//! ```
+//! ```no_run
+//! use anyhow::Result;
+//! use opendal::Object;
+//! use opendal::Operator;
+//! use opendal::Scheme;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let op: Operator = Operator::from_env(Scheme::Cos)?;
+//!
+//!     // create an object handler to start operation on it.
+//!     let _op: Object = op.object("test_file");
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ## Via Builder
+//!
+//! ```no_run
+//! use anyhow::Result;
+//! use opendal::services::cos;
+//! use opendal::Object;
+//! use opendal::Operator;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     // create backend builder
+//!     let mut builder = cos::Builder::default();
+//!
+//!     // set the storage bucket for OpenDAL
+//!     builder.bucket("examplebucket-1250000000");
+//!     // set the region, endpoint will be `https://examplebucket-1250000000.cos.ap-beijing.myqcloud.com`
+//!     builder.region("ap-beijing");
+//!     // Set the secret_id and secret_key.
+//!     builder.secret_id("secret_id");
+//!     builder.secret_key("secret_key");
+//!
+//!     let op: Operator = Operator::new(builder.build()?);
+//!
+//!     // Create an object handle to start operation on object.
+//!     let _: Object = op.object("test_file");
+//!
+//!     Ok(())
+//! }
+//! ```
+
+mod backend;
+pub use backend::Builder;
+
+mod dir_stream;
+mod error;
+mod signer;
@@ -0,0 +1,274 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use hmac::Hmac;
+use hmac::Mac;
+use http::header::AUTHORIZATION;
+use http::HeaderValue;
+use http::Request;
+use percent_encoding::percent_decode_str;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::AsciiSet;
+use percent_encoding::NON_ALPHANUMERIC;
+use sha1::Digest;
+use sha1::Sha1;
+use time::Duration;
+use time::OffsetDateTime;
+
+use crate::*;
+
+/// Header to carry the token of temporary credentials.
+pub const X_COS_SECURITY_TOKEN: &str = "x-cos-security-token";
+
+/// Default expire duration of authorization in headers.
+const DEFAULT_EXPIRE: Duration = Duration::hours(1);
+
+/// COS requires all chars except `A-Z a-z 0-9 - _ . ~` to be encoded.
+static COS_ENCODE_SET: AsciiSet = NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Signer for Tencent COS.
+///
+/// Reference: [Request Signature](https://www.tencentcloud.com/document/product/436/7778)
+#[derive(Clone)]
+pub struct Signer {
+    secret_id: String,
+    secret_key: String,
+    security_token: Option<String>,
+}
+
+impl Debug for Signer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Signer")
+            .field("secret_id", &"<redacted>")
+            .field("secret_key", &"<redacted>")
+            .finish_non_exhaustive()
+    }
+}
+
+impl Signer {
+    /// Create a new signer with secret id and key.
+    pub fn new(secret_id: &str, secret_key: &str) -> Self {
+        Self {
+            secret_id: secret_id.to_string(),
+            secret_key: secret_key.to_string(),
+            security_token: None,
+        }
+    }
+
+    /// Set the token of temporary credentials.
+    pub fn with_security_token(mut self, token: &str) -> Self {
+        self.security_token = Some(token.to_string());
+        self
+    }
+
+    /// Sign request with `Authorization` header.
+    pub fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        if let Some(token) = &self.security_token {
+            req.headers_mut()
+                .insert(X_COS_SECURITY_TOKEN, parse_header_value(token)?);
+        }
+
+        let now = OffsetDateTime::now_utc();
+        let auth = self.authorization(req, now, now + DEFAULT_EXPIRE);
+        req.headers_mut()
+            .insert(AUTHORIZATION, parse_header_value(&auth)?);
+
+        Ok(())
+    }
+
+    /// Sign request in query, the signed request will be valid in `expire`.
+    pub fn sign_query<T>(&self, req: &mut Request<T>, expire: Duration) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+        let mut query = self.authorization(req, now, now + expire);
+        // Token is not a part of signature.
+        if let Some(token) = &self.security_token {
+            query.push_str(&format!(
+                "&{}={}",
+                X_COS_SECURITY_TOKEN,
+                utf8_percent_encode(token, &COS_ENCODE_SET)
+            ));
+        }
+
+        let uri = req.uri().to_string();
+        let uri = format!(
+            "{}{}{}",
+            uri,
+            if uri.contains('?') { "&" } else { "?" },
+            query
+        );
+        *req.uri_mut() = uri.parse().map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "presigned uri is invalid")
+                .with_context("uri", &uri)
+                .set_source(e)
+        })?;
+
+        Ok(())
+    }
+
+    /// Build the authorization string which is in the form of:
+    ///
+    /// ```text
+    /// q-sign-algorithm=sha1&q-ak=<SecretId>&q-sign-time=<KeyTime>&q-key-time=<KeyTime>
+    /// &q-header-list=<HeaderList>&q-url-param-list=<UrlParamList>&q-signature=<Signature>
+    /// ```
+    fn authorization<T>(
+        &self,
+        req: &Request<T>,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> String {
+        let key_time = format!("{};{}", start.unix_timestamp(), end.unix_timestamp());
+        let sign_key = hex_hmac_sha1(self.secret_key.as_bytes(), key_time.as_bytes());
+
+        let (param_list, params) = canonicalize(
+            req.uri()
+                .query()
+                .unwrap_or_default()
+                .split('&')
+                .filter(|v| !v.is_empty())
+                .map(|v| {
+                    let (k, v) = v.split_once('=').unwrap_or((v, ""));
+                    (decode(k), decode(v))
+                }),
+        );
+        let (header_list, headers) = canonicalize(
+            req.headers()
+                .iter()
+                .filter(|(k, _)| *k != AUTHORIZATION)
+                .map(|(k, v)| {
+                    (
+                        k.as_str().to_string(),
+                        String::from_utf8_lossy(v.as_bytes()).to_string(),
+                    )
+                }),
+        );
+
+        let http_string = format!(
+            "{}\n{}\n{}\n{}\n",
+            req.method().as_str().to_lowercase(),
+            decode(req.uri().path()),
+            params,
+            headers
+        );
+        let string_to_sign = format!(
+            "sha1\n{}\n{:x}\n",
+            key_time,
+            Sha1::digest(http_string.as_bytes())
+        );
+        let signature = hex_hmac_sha1(sign_key.as_bytes(), string_to_sign.as_bytes());
+
+        format!(
+            "q-sign-algorithm=sha1&q-ak={}&q-sign-time={key_time}&q-key-time={key_time}&q-header-list={}&q-url-param-list={}&q-signature={}",
+            self.secret_id, header_list, param_list, signature
+        )
+    }
+}
+
+/// Canonicalize key-value pairs into `(key_list, key_values)`:
+///
+/// - `key_list`: encoded lowercase keys sorted and joined by `;`
+/// - `key_values`: `key=value` with both encoded, sorted by key and joined by `&`
+fn canonicalize(it: impl Iterator<Item = (String, String)>) -> (String, String) {
+    let mut pairs: Vec<(String, String)> = it
+        .map(|(k, v)| {
+            (
+                utf8_percent_encode(&k.to_lowercase(), &COS_ENCODE_SET).to_string(),
+                utf8_percent_encode(&v, &COS_ENCODE_SET).to_string(),
+            )
+        })
+        .collect();
+    pairs.sort();
+
+    let list = pairs
+        .iter()
+        .map(|(k, _)| k.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    let kvs = pairs
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join("&");
+    (list, kvs)
+}
+
+fn decode(v: &str) -> String {
+    percent_decode_str(v).decode_utf8_lossy().to_string()
+}
+
+fn hex_hmac_sha1(key: &[u8], content: &[u8]) -> String {
+    let mut h = Hmac::<Sha1>::new_from_slice(key).expect("hmac accepts key of any size");
+    h.update(content);
+    format!("{:x}", h.finalize().into_bytes())
+}
+
+fn parse_header_value(v: &str) -> Result<HeaderValue> {
+    v.parse().map_err(|e| {
+        Error::new(ErrorKind::Unexpected, "header value is invalid")
+            .with_operation("cos::Signer::sign")
+            .set_source(e)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header::CONTENT_LENGTH;
+    use http::header::CONTENT_TYPE;
+    use http::header::DATE;
+    use http::header::HOST;
+
+    use super::*;
+
+    /// Example is from [Request Signature](https://www.tencentcloud.com/document/product/436/7778)
+    #[test]
+    fn test_authorization() {
+        let signer = Signer::new(
+            "AKIDQjz3ltompVjBni5LitkWHFlFpwkn9U5q",
+            "BQYIM75p8x0iWVFSIgqEKwFprpRSVHlz",
+        );
+        let req = Request::put(
+            "https://examplebucket-1250000000.cos.ap-beijing.myqcloud.com/exampleobject(%E8%85%BE%E8%AE%AF%E4%BA%91)",
+        )
+        .header(DATE, "Thu, 16 May 2019 06:45:51 GMT")
+        .header(HOST, "examplebucket-1250000000.cos.ap-beijing.myqcloud.com")
+        .header(CONTENT_TYPE, "text/plain")
+        .header(CONTENT_LENGTH, "13")
+        .header("content-md5", "mQ/fVh815F3k6TAUm8m0eg==")
+        .header("x-cos-acl", "private")
+        .header("x-cos-grant-read", "uin=\"100000000011\"")
+        .body(())
+        .expect("request must be valid");
+
+        let auth = signer.authorization(
+            &req,
+            OffsetDateTime::from_unix_timestamp(1557989151).unwrap(),
+            OffsetDateTime::from_unix_timestamp(1557996351).unwrap(),
+        );
+
+        assert_eq!(
+            auth,
+            "q-sign-algorithm=sha1&q-ak=AKIDQjz3ltompVjBni5LitkWHFlFpwkn9U5q\
+             &q-sign-time=1557989151;1557996351&q-key-time=1557989151;1557996351\
+             &q-header-list=content-length;content-md5;content-type;date;host;x-cos-acl;x-cos-grant-read\
+             &q-url-param-list=&q-signature=3b8851a11a569213c17ba8fa7dcf2abec6935172"
+        );
+    }
+}
@@ -16,7 +16,9 @@ use std::cmp::min;
 use std::io;
 use std::io::Read;
 use std::io::SeekFrom;
+use std::path::Path;
 use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
 
 use async_compat::Compat;
 use async_trait::async_trait;
@@ -39,9 +41,13 @@ use crate::*;
 pub struct Builder {
     root: Option<String>,
     atomic_write_dir: Option<String>,
+    disable_atomic_write: bool,
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] =
+        &["root", "atomic_write_dir", "disable_atomic_write"];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
 
@@ -50,6 +56,7 @@ impl Builder {
             match k.as_ref() {
                 "root" => builder.root(v),
                 "atomic_write_dir" => builder.atomic_write_dir(v),
+                "disable_atomic_write" if !v.is_empty() => builder.disable_atomic_write(),
                 _ => continue,
             };
         }
@@ -69,6 +76,9 @@ impl Builder {
     }
 
     /// Set temp dir for atomic write.
+    ///
+    /// By default, temp files are created next to the target file. The dir
+    /// must be on the same filesystem with root, otherwise rename will fail.
     pub fn atomic_write_dir(&mut self, dir: &str) -> &mut Self {
         self.atomic_write_dir = if dir.is_empty() {
             None
@@ -79,12 +89,34 @@ impl Builder {
         self
     }
 
+    /// Disable atomic write so that data will be written to the target
+    /// file directly.
+    ///
+    /// By default, data will be written into a temp file like
+    /// `.<name>.tmp.<uuid>` and renamed to the target file after finished,
+    /// so that readers never see a partial file. Temp files are skipped
+    /// while listing. Disable it for filesystems that don't support rename
+    /// well.
+    pub fn disable_atomic_write(&mut self) -> &mut Self {
+        self.disable_atomic_write = true;
+        self
+    }
+
     /// Consume current builder to build a fs backend.
     pub fn build(&mut self) -> Result<impl Accessor> {
         debug!("backend build started: {:?}", &self);
 
         let root = normalize_root(&self.root.take().unwrap_or_default());
         let atomic_write_dir = self.atomic_write_dir.as_deref().map(normalize_root);
+        if self.disable_atomic_write && atomic_write_dir.is_some() {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "atomic_write_dir can't be set while atomic write is disabled",
+            )
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::Fs)
+            .with_context("field", "disable_atomic_write"));
+        }
 
         // If root dir is not exist, we must create it.
         if let Err(e) = std::fs::metadata(&root) {
@@ -115,6 +147,7 @@ impl Builder {
         debug!("backend build finished: {:?}", &self);
         Ok(apply_wrapper(Backend {
             root,
+            atomic_write: !self.disable_atomic_write,
             atomic_write_dir,
         }))
     }
@@ -124,6 +157,7 @@ impl Builder {
 #[derive(Debug, Clone)]
 pub struct Backend {
     root: String,
+    atomic_write: bool,
     atomic_write_dir: Option<String>,
 }
 
@@ -132,7 +166,129 @@ fn tmp_file_of(path: &str) -> String {
     let name = get_basename(path);
     let uuid = Uuid::new_v4().to_string();
 
-    format!("{name}.{uuid}")
+    format!(".{name}.tmp.{uuid}")
+}
+
+/// Check if given file name is a temp file built by [`tmp_file_of`], they
+/// should never be returned while listing.
+pub(super) fn is_tmp_file(name: &str) -> bool {
+    match name.strip_prefix('.').and_then(|v| v.rsplit_once(".tmp.")) {
+        Some((name, uuid)) => !name.is_empty() && Uuid::parse_str(uuid).is_ok(),
+        None => false,
+    }
+}
+
+/// Default count of entries returned in one page of list.
+const DEFAULT_PAGE_SIZE: usize = 256;
+
+/// Build etag of file via its last modified time and content length.
+///
+/// fs doesn't have etag, we synthesize it like nginx: `<mtime>-<length>`.
+fn fs_etag(meta: &std::fs::Metadata) -> Result<String> {
+    let mtime = meta
+        .modified()
+        .map_err(parse_io_error)?
+        .duration_since(UNIX_EPOCH)
+        .map(|v| v.as_nanos())
+        .unwrap_or_default();
+
+    Ok(format!("{:x}-{:x}", mtime, meta.len()))
+}
+
+/// Build object metadata from fs metadata, shared by `stat` and `list`.
+pub(super) fn parse_fs_metadata(meta: &std::fs::Metadata) -> Result<ObjectMetadata> {
+    let mode = if meta.is_dir() {
+        ObjectMode::DIR
+    } else if meta.is_file() {
+        ObjectMode::FILE
+    } else {
+        ObjectMode::Unknown
+    };
+
+    Ok(ObjectMetadata::new(mode)
+        .with_content_length(meta.len())
+        .with_last_modified(
+            meta.modified()
+                .map(OffsetDateTime::from)
+                .map_err(parse_io_error)?,
+        )
+        .with_etag(&fs_etag(meta)?))
+}
+
+/// Add the etag of existing file into context while conditional write
+/// failed, so that users don't need to stat it again.
+fn with_current_etag(err: Error, p: impl AsRef<Path>) -> Error {
+    if err.kind() != ErrorKind::ConditionNotMatch {
+        return err;
+    }
+
+    match std::fs::metadata(p)
+        .map_err(parse_io_error)
+        .and_then(|v| fs_etag(&v))
+    {
+        Ok(etag) => err.with_context("etag", etag),
+        Err(_) => err,
+    }
+}
+
+/// Build error for `start_after` that can't be found while listing.
+fn start_after_not_found(err: io::Error, start_after: &str) -> Error {
+    let err = parse_io_error(err);
+    if err.kind() != ErrorKind::ObjectNotFound {
+        return err;
+    }
+
+    Error::new(
+        ErrorKind::ObjectNotFound,
+        "start_after is not found, list can't be resumed",
+    )
+    .with_operation("fs::list")
+    .with_context("start_after", start_after)
+}
+
+/// fs can't check etag while writing, only `if_not_exists` is supported.
+fn check_write_condition(args: &OpWrite) -> Result<()> {
+    if args.if_match().is_some() || args.if_none_match().map_or(false, |v| v != "*") {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "write with etag condition is not supported",
+        )
+        .with_context("service", Scheme::Fs));
+    }
+
+    Ok(())
+}
+
+/// Check the `if_match` and `if_none_match` conditions of read against etag.
+fn check_read_condition(etag: &str, args: &OpRead) -> Result<()> {
+    let matches = |cond: &str| {
+        cond.split(',')
+            .map(|v| v.trim())
+            .any(|v| v == "*" || normalize_etag(v).trim_start_matches("W/") == etag)
+    };
+
+    if let Some(cond) = args.if_match() {
+        if !matches(cond) {
+            return Err(
+                Error::new(ErrorKind::ConditionNotMatch, "if-match condition not match")
+                    .with_context("etag", etag)
+                    .with_context("if_match", cond),
+            );
+        }
+    }
+
+    if let Some(cond) = args.if_none_match() {
+        if matches(cond) {
+            return Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "if-none-match condition not match",
+            )
+            .with_context("etag", etag)
+            .with_context("if_none_match", cond));
+        }
+    }
+
+    Ok(())
 }
 
 impl Backend {
@@ -225,6 +381,59 @@ impl Backend {
 
         Ok(p)
     }
+
+    /// Build temp path for atomic write of `target`.
+    ///
+    /// Temp file will be placed next to the target file unless
+    /// `atomic_write_dir` is set, so that rename will not cross devices.
+    async fn ensure_tmp_path(&self, path: &str, target: &str) -> Result<PathBuf> {
+        match &self.atomic_write_dir {
+            Some(dir) => Ok(Self::ensure_write_abs_path(dir, &tmp_file_of(path))
+                .await?
+                .into()),
+            None => Ok(Path::new(target).with_file_name(tmp_file_of(path))),
+        }
+    }
+
+    /// Synchronously build temp path for atomic write of `target`.
+    fn blocking_ensure_tmp_path(&self, path: &str, target: &str) -> Result<PathBuf> {
+        match &self.atomic_write_dir {
+            Some(dir) => Ok(Self::blocking_ensure_write_abs_path(dir, &tmp_file_of(path))?.into()),
+            None => Ok(Path::new(target).with_file_name(tmp_file_of(path))),
+        }
+    }
+
+    /// Write all data into temp file and sync it to disk.
+    async fn write_tmp_file(p: &Path, r: BytesReader) -> Result<u64> {
+        let f = fs::OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(p)
+            .await
+            .map_err(parse_io_error)?;
+
+        let mut f = Compat::new(f);
+        let size = futures::io::copy(r, &mut f).await.map_err(parse_io_error)?;
+        f.into_inner().sync_all().await.map_err(parse_io_error)?;
+
+        Ok(size)
+    }
+
+    /// Synchronously write all data into temp file and sync it to disk.
+    fn blocking_write_tmp_file(p: &Path, mut r: BlockingBytesReader) -> Result<u64> {
+        let mut f = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(p)
+            .map_err(parse_io_error)?;
+
+        let size = io::copy(&mut r, &mut f).map_err(parse_io_error)?;
+        f.sync_all().map_err(parse_io_error)?;
+
+        Ok(size)
+    }
 }
 
 #[async_trait]
@@ -237,7 +446,12 @@ impl Accessor for Backend {
                 AccessorCapability::Read
                     | AccessorCapability::Write
                     | AccessorCapability::List
-                    | AccessorCapability::Blocking,
+                    | AccessorCapability::Blocking
+                    | AccessorCapability::ConditionalWrite
+                    | AccessorCapability::ConditionalRead
+                    | AccessorCapability::ListStartAfter
+                    | AccessorCapability::Copy
+                    | AccessorCapability::Append,
             );
 
         am
@@ -291,6 +505,7 @@ impl Accessor for Backend {
                 "given path is a directoty",
             ));
         }
+        check_read_condition(&fs_etag(&meta)?, &args)?;
 
         let f = fs::OpenOptions::new()
             .read(true)
@@ -344,43 +559,64 @@ impl Accessor for Backend {
         Ok((RpRead::new(size), Box::new(r) as BytesReader))
     }
 
-    async fn write(&self, path: &str, _: OpWrite, r: BytesReader) -> Result<RpWrite> {
-        if let Some(atomic_write_dir) = &self.atomic_write_dir {
-            let temp_path =
-                Self::ensure_write_abs_path(atomic_write_dir, &tmp_file_of(path)).await?;
-            let target_path = Self::ensure_write_abs_path(&self.root, path).await?;
-            let f = fs::OpenOptions::new()
-                .create(true)
-                .truncate(true)
-                .write(true)
-                .open(&temp_path)
-                .await
-                .map_err(parse_io_error)?;
+    async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
+        check_write_condition(&args)?;
 
-            let size = {
-                // Implicitly flush and close temp file
-                let mut f = Compat::new(f);
-                futures::io::copy(r, &mut f).await.map_err(parse_io_error)?
+        // Append must write into the target file directly.
+        if self.atomic_write && !args.append() {
+            let target_path = Self::ensure_write_abs_path(&self.root, path).await?;
+            let temp_path = self.ensure_tmp_path(path, &target_path).await?;
+
+            // Don't leave the temp file if the reader failed.
+            let size = match Self::write_tmp_file(&temp_path, r).await {
+                Ok(size) => size,
+                Err(err) => {
+                    let _ = fs::remove_file(&temp_path).await;
+                    return Err(err);
+                }
             };
-            fs::rename(&temp_path, &target_path)
-                .await
-                .map_err(parse_io_error)?;
+            if args.if_not_exists() {
+                // hard_link will fail if target already exists.
+                let res = fs::hard_link(&temp_path, &target_path).await;
+                fs::remove_file(&temp_path).await.map_err(parse_io_error)?;
+                res.map_err(|err| with_current_etag(parse_io_error(err), &target_path))?;
+            } else if let Err(err) = fs::rename(&temp_path, &target_path).await {
+                let _ = fs::remove_file(&temp_path).await;
+                return Err(parse_io_error(err));
+            }
 
             Ok(RpWrite::new(size))
         } else {
             let p = Self::ensure_write_abs_path(&self.root, path).await?;
 
-            let f = fs::OpenOptions::new()
-                .create(true)
-                .truncate(true)
-                .write(true)
+            let mut opts = fs::OpenOptions::new();
+            opts.write(true);
+            if args.if_not_exists() {
+                opts.create_new(true);
+            } else if args.append() {
+                opts.create(true).append(true);
+            } else {
+                opts.create(true).truncate(true);
+            }
+            let f = opts
                 .open(&p)
                 .await
-                .map_err(parse_io_error)?;
+                .map_err(|err| with_current_etag(parse_io_error(err), &p))?;
 
             let mut f = Compat::new(f);
 
-            let size = futures::io::copy(r, &mut f).await.map_err(parse_io_error)?;
+            // Don't leave a partial file if the reader failed, existing
+            // content must be kept while appending.
+            let size = match futures::io::copy(r, &mut f).await {
+                Ok(size) => size,
+                Err(err) => {
+                    drop(f);
+                    if !args.append() {
+                        let _ = fs::remove_file(&p).await;
+                    }
+                    return Err(parse_io_error(err));
+                }
+            };
 
             Ok(RpWrite::new(size))
         }
@@ -391,22 +627,7 @@ impl Accessor for Backend {
 
         let meta = Self::fs_metadata(&p).await?;
 
-        let mode = if meta.is_dir() {
-            ObjectMode::DIR
-        } else if meta.is_file() {
-            ObjectMode::FILE
-        } else {
-            ObjectMode::Unknown
-        };
-        let m = ObjectMetadata::new(mode)
-            .with_content_length(meta.len() as u64)
-            .with_last_modified(
-                meta.modified()
-                    .map(OffsetDateTime::from)
-                    .map_err(parse_io_error)?,
-            );
-
-        Ok(RpStat::new(m))
+        Ok(RpStat::new(parse_fs_metadata(&meta)?))
     }
 
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
@@ -437,7 +658,30 @@ impl Accessor for Backend {
         Ok(RpDelete::default())
     }
 
-    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, ObjectPager)> {
+    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        let from = build_rooted_abs_path(&self.root, from);
+        let to = Self::ensure_write_abs_path(&self.root, to).await?;
+
+        // Copy into temp file first to make sure readers never see a
+        // partial file.
+        if self.atomic_write {
+            let temp_path = self.ensure_tmp_path(&to, &to).await?;
+            let res = match fs::copy(&from, &temp_path).await {
+                Ok(_) => fs::rename(&temp_path, &to).await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = res {
+                let _ = fs::remove_file(&temp_path).await;
+                return Err(parse_io_error(err));
+            }
+        } else {
+            fs::copy(&from, &to).await.map_err(parse_io_error)?;
+        }
+
+        Ok(RpCopy::default())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         let p = build_rooted_abs_path(&self.root, path);
 
         let f = match tokio::fs::read_dir(&p).await {
@@ -451,7 +695,21 @@ impl Accessor for Backend {
             }
         };
 
-        let rd = DirPager::new(&self.root, f);
+        // We will skip entries until `start_after`, make sure it exists so
+        // that we won't skip all of them.
+        if let Some(start_after) = args.start_after() {
+            let sp = build_rooted_abs_path(&self.root, start_after);
+            fs::metadata(&sp)
+                .await
+                .map_err(|err| start_after_not_found(err, start_after))?;
+        }
+
+        let rd = DirPager::new(
+            &self.root,
+            f,
+            args.limit().unwrap_or(DEFAULT_PAGE_SIZE),
+            args.start_after(),
+        );
 
         Ok((RpList::default(), Box::new(rd)))
     }
@@ -498,6 +756,7 @@ impl Accessor for Backend {
 
         // Validate if input path is a valid file.
         let meta = Self::blocking_fs_metadata(&p)?;
+        check_read_condition(&fs_etag(&meta)?, &args)?;
 
         let mut f = std::fs::OpenOptions::new()
             .read(true)
@@ -546,37 +805,63 @@ impl Accessor for Backend {
     fn blocking_write(
         &self,
         path: &str,
-        _: OpWrite,
+        args: OpWrite,
         mut r: BlockingBytesReader,
     ) -> Result<RpWrite> {
-        if let Some(atomic_write_dir) = &self.atomic_write_dir {
-            let temp_path =
-                Self::blocking_ensure_write_abs_path(atomic_write_dir, &tmp_file_of(path))?;
-            let target_path = Self::blocking_ensure_write_abs_path(&self.root, path)?;
+        check_write_condition(&args)?;
 
-            let size = {
-                // Implicitly flush and close temp file
-                let mut f = std::fs::OpenOptions::new()
-                    .create(true)
-                    .write(true)
-                    .open(&temp_path)
-                    .map_err(parse_io_error)?;
-
-                std::io::copy(&mut r, &mut f).map_err(parse_io_error)?
+        // Append must write into the target file directly.
+        if self.atomic_write && !args.append() {
+            let target_path = Self::blocking_ensure_write_abs_path(&self.root, path)?;
+            let temp_path = self.blocking_ensure_tmp_path(path, &target_path)?;
+
+            // Don't leave the temp file if the reader failed.
+            let size = match Self::blocking_write_tmp_file(&temp_path, r) {
+                Ok(size) => size,
+                Err(err) => {
+                    let _ = std::fs::remove_file(&temp_path);
+                    return Err(err);
+                }
             };
-            std::fs::rename(&temp_path, &target_path).map_err(parse_io_error)?;
+            if args.if_not_exists() {
+                // hard_link will fail if target already exists.
+                let res = std::fs::hard_link(&temp_path, &target_path);
+                std::fs::remove_file(&temp_path).map_err(parse_io_error)?;
+                res.map_err(|err| with_current_etag(parse_io_error(err), &target_path))?;
+            } else if let Err(err) = std::fs::rename(&temp_path, &target_path) {
+                let _ = std::fs::remove_file(&temp_path);
+                return Err(parse_io_error(err));
+            }
 
             Ok(RpWrite::new(size))
         } else {
             let p = Self::blocking_ensure_write_abs_path(&self.root, path)?;
 
-            let mut f = std::fs::OpenOptions::new()
-                .create(true)
-                .write(true)
+            let mut opts = std::fs::OpenOptions::new();
+            opts.write(true);
+            if args.if_not_exists() {
+                opts.create_new(true);
+            } else if args.append() {
+                opts.create(true).append(true);
+            } else {
+                opts.create(true).truncate(true);
+            }
+            let mut f = opts
                 .open(&p)
-                .map_err(parse_io_error)?;
-
-            let size = std::io::copy(&mut r, &mut f).map_err(parse_io_error)?;
+                .map_err(|err| with_current_etag(parse_io_error(err), &p))?;
+
+            // Don't leave a partial file if the reader failed, existing
+            // content must be kept while appending.
+            let size = match std::io::copy(&mut r, &mut f) {
+                Ok(size) => size,
+                Err(err) => {
+                    drop(f);
+                    if !args.append() {
+                        let _ = std::fs::remove_file(&p);
+                    }
+                    return Err(parse_io_error(err));
+                }
+            };
 
             Ok(RpWrite::new(size))
         }
@@ -587,22 +872,7 @@ impl Accessor for Backend {
 
         let meta = Self::blocking_fs_metadata(&p)?;
 
-        let mode = if meta.is_dir() {
-            ObjectMode::DIR
-        } else if meta.is_file() {
-            ObjectMode::FILE
-        } else {
-            ObjectMode::Unknown
-        };
-        let m = ObjectMetadata::new(mode)
-            .with_content_length(meta.len() as u64)
-            .with_last_modified(
-                meta.modified()
-                    .map(OffsetDateTime::from)
-                    .map_err(parse_io_error)?,
-            );
-
-        Ok(RpStat::new(m))
+        Ok(RpStat::new(parse_fs_metadata(&meta)?))
     }
 
     fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
@@ -633,7 +903,28 @@ impl Accessor for Backend {
         Ok(RpDelete::default())
     }
 
-    fn blocking_list(&self, path: &str, _: OpList) -> Result<(RpList, BlockingObjectPager)> {
+    fn blocking_copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        let from = build_rooted_abs_path(&self.root, from);
+        let to = Self::blocking_ensure_write_abs_path(&self.root, to)?;
+
+        // Copy into temp file first to make sure readers never see a
+        // partial file.
+        if self.atomic_write {
+            let temp_path = self.blocking_ensure_tmp_path(&to, &to)?;
+            let res =
+                std::fs::copy(&from, &temp_path).and_then(|_| std::fs::rename(&temp_path, &to));
+            if let Err(err) = res {
+                let _ = std::fs::remove_file(&temp_path);
+                return Err(parse_io_error(err));
+            }
+        } else {
+            std::fs::copy(&from, &to).map_err(parse_io_error)?;
+        }
+
+        Ok(RpCopy::default())
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, BlockingObjectPager)> {
         let p = build_rooted_abs_path(&self.root, path);
 
         let f = match std::fs::read_dir(&p) {
@@ -650,7 +941,19 @@ impl Accessor for Backend {
             }
         };
 
-        let rd = BlockingDirPager::new(&self.root, f);
+        // We will skip entries until `start_after`, make sure it exists so
+        // that we won't skip all of them.
+        if let Some(start_after) = args.start_after() {
+            let sp = build_rooted_abs_path(&self.root, start_after);
+            std::fs::metadata(sp).map_err(|err| start_after_not_found(err, start_after))?;
+        }
+
+        let rd = BlockingDirPager::new(
+            &self.root,
+            f,
+            args.limit().unwrap_or(DEFAULT_PAGE_SIZE),
+            args.start_after(),
+        );
 
         Ok((RpList::default(), Box::new(rd)))
     }
@@ -663,9 +966,9 @@ mod tests {
     #[test]
     fn test_tmp_file_of() {
         let cases = vec![
-            ("hello.txt", "hello.txt"),
-            ("/tmp/opendal.log", "opendal.log"),
-            ("/abc/def/hello.parquet", "hello.parquet"),
+            ("hello.txt", ".hello.txt.tmp."),
+            ("/tmp/opendal.log", ".opendal.log.tmp."),
+            ("/abc/def/hello.parquet", ".hello.parquet.tmp."),
         ];
 
         for (path, expected_prefix) in cases {
@@ -674,4 +977,307 @@ mod tests {
             assert!(tmp_file.starts_with(expected_prefix));
         }
     }
+
+    #[test]
+    fn test_is_tmp_file() {
+        let cases = vec![
+            (tmp_file_of("hello.txt"), true),
+            (tmp_file_of("/abc/def/.hidden"), true),
+            (".hello.txt.tmp.abc".to_string(), false),
+            (
+                ".tmp.1e4d9a4e-9c39-4d4a-8d9b-2b6c2f3d7c10".to_string(),
+                false,
+            ),
+            (
+                "hello.txt.tmp.1e4d9a4e-9c39-4d4a-8d9b-2b6c2f3d7c10".to_string(),
+                false,
+            ),
+            (".hello.txt".to_string(), false),
+        ];
+
+        for (name, expected) in cases {
+            assert_eq!(is_tmp_file(&name), expected, "{}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_during_write() {
+        use std::env;
+        use std::sync::mpsc;
+        use std::sync::Arc;
+        use std::thread;
+        use std::time::Duration;
+
+        /// Reader that yields some data and then blocks until notified.
+        struct PendingReader(Option<mpsc::Receiver<()>>);
+
+        impl Read for PendingReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                match self.0.take() {
+                    Some(rx) => {
+                        rx.recv().expect("notify must succeed");
+                        buf[..3].copy_from_slice(b"new");
+                        Ok(3)
+                    }
+                    None => Ok(0),
+                }
+            }
+        }
+
+        let root = env::temp_dir().join(Uuid::new_v4().to_string());
+        let mut builder = Builder::default();
+        builder.root(&root.to_string_lossy());
+        let acc = Arc::new(builder.build().expect("build must succeed"));
+
+        let (tx, rx) = mpsc::channel();
+        let handle = {
+            let acc = acc.clone();
+            thread::spawn(move || {
+                acc.blocking_write("file", OpWrite::new(3), Box::new(PendingReader(Some(rx))))
+            })
+        };
+
+        // Wait until the temp file has been created.
+        let mut created = false;
+        for _ in 0..100 {
+            created = std::fs::read_dir(&root)
+                .map(|rd| rd.flatten().any(|v| v.file_name() != "file"))
+                .unwrap_or_default();
+            if created {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(created, "temp file must be created");
+
+        let (_, mut pager) = acc
+            .list("/", OpList::new())
+            .await
+            .expect("list must succeed");
+        let mut paths = Vec::new();
+        while let Some(page) = pager.next_page().await.expect("list must succeed") {
+            paths.extend(page.into_iter().map(|v| v.path().to_string()));
+        }
+        assert!(paths.is_empty(), "temp files must be skipped: {:?}", paths);
+
+        let (_, mut pager) = acc
+            .blocking_list("/", OpList::new())
+            .expect("list must succeed");
+        let mut paths = Vec::new();
+        while let Some(page) = pager.next_page().expect("list must succeed") {
+            paths.extend(page.into_iter().map(|v| v.path().to_string()));
+        }
+        assert!(paths.is_empty(), "temp files must be skipped: {:?}", paths);
+
+        tx.send(()).expect("notify must succeed");
+        handle
+            .join()
+            .expect("join must succeed")
+            .expect("write must succeed");
+
+        let (_, mut pager) = acc
+            .list("/", OpList::new())
+            .await
+            .expect("list must succeed");
+        let mut paths = Vec::new();
+        while let Some(page) = pager.next_page().await.expect("list must succeed") {
+            paths.extend(page.into_iter().map(|v| v.path().to_string()));
+        }
+        assert_eq!(paths, vec!["file"]);
+
+        std::fs::remove_dir_all(&root).expect("remove dir must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_atomic_write() {
+        use std::env;
+
+        use futures::TryStreamExt;
+
+        /// Reader that yields some data and then fails.
+        struct BrokenReader(bool);
+
+        impl Read for BrokenReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0 {
+                    return Err(io::Error::new(io::ErrorKind::Other, "broken"));
+                }
+                self.0 = true;
+                buf[..3].copy_from_slice(b"new");
+                Ok(3)
+            }
+        }
+
+        let root = env::temp_dir().join(Uuid::new_v4().to_string());
+        let mut builder = Builder::default();
+        builder.root(&root.to_string_lossy());
+        let acc = builder.build().expect("build must succeed");
+
+        acc.blocking_write("file", OpWrite::new(3), Box::new(io::Cursor::new("old")))
+            .expect("write must succeed");
+
+        let err = acc
+            .blocking_write("file", OpWrite::new(6), Box::new(BrokenReader(false)))
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        let r = futures::stream::iter(vec![
+            Ok(bytes::Bytes::from("new")),
+            Err(io::Error::new(io::ErrorKind::Other, "broken")),
+        ])
+        .into_async_read();
+        let err = acc
+            .write("file", OpWrite::new(6), Box::new(r))
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        // The old content is kept and temp files are cleaned up.
+        assert_eq!(
+            std::fs::read_to_string(root.join("file")).expect("read must succeed"),
+            "old"
+        );
+        let entries: Vec<_> = std::fs::read_dir(&root)
+            .expect("read dir must succeed")
+            .map(|v| v.expect("entry must be valid").file_name())
+            .collect();
+        assert_eq!(entries, vec!["file"]);
+
+        std::fs::remove_dir_all(&root).expect("remove dir must succeed");
+    }
+
+    #[test]
+    fn test_disable_atomic_write() {
+        use std::env;
+
+        let root = env::temp_dir().join(Uuid::new_v4().to_string());
+        let mut builder = Builder::default();
+        builder
+            .root(&root.to_string_lossy())
+            .atomic_write_dir(&root.join("tmp").to_string_lossy())
+            .disable_atomic_write();
+        let err = builder.build().expect_err("build must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let mut builder = Builder::default();
+        builder.root(&root.to_string_lossy()).disable_atomic_write();
+        let acc = builder.build().expect("build must succeed");
+        acc.blocking_write("file", OpWrite::new(5), Box::new(io::Cursor::new("hello")))
+            .expect("write must succeed");
+        assert_eq!(
+            std::fs::read_to_string(root.join("file")).expect("read must succeed"),
+            "hello"
+        );
+
+        std::fs::remove_dir_all(&root).expect("remove dir must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_append() {
+        use std::env;
+
+        let root = env::temp_dir().join(Uuid::new_v4().to_string());
+        let mut builder = Builder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let o = op.object("file");
+        o.append("Hello, ").await.expect("append must succeed");
+        o.append("World!").await.expect("append must succeed");
+        assert_eq!(o.read().await.expect("read must succeed"), b"Hello, World!");
+        assert_eq!(
+            o.metadata()
+                .await
+                .expect("stat must succeed")
+                .content_length(),
+            13
+        );
+
+        o.blocking_append(" Bye!").expect("append must succeed");
+        assert_eq!(
+            o.blocking_read().expect("read must succeed"),
+            b"Hello, World! Bye!"
+        );
+
+        std::fs::remove_dir_all(&root).expect("remove dir must succeed");
+    }
+
+    #[test]
+    fn test_check_read_condition() {
+        let etag = "17a2b3c4-400";
+
+        let cases = vec![
+            ("no condition", OpRead::new(), true),
+            ("if-match", OpRead::new().with_if_match(etag), true),
+            ("if-match any", OpRead::new().with_if_match("*"), true),
+            (
+                "if-match in list",
+                OpRead::new().with_if_match(r#""abc", "17a2b3c4-400""#),
+                true,
+            ),
+            (
+                "if-match not match",
+                OpRead::new().with_if_match(r#""abc""#),
+                false,
+            ),
+            (
+                "if-none-match",
+                OpRead::new().with_if_none_match(r#""abc""#),
+                true,
+            ),
+            (
+                "if-none-match weak",
+                OpRead::new().with_if_none_match(r#"W/"17a2b3c4-400""#),
+                false,
+            ),
+            (
+                "if-none-match not match",
+                OpRead::new().with_if_none_match(etag),
+                false,
+            ),
+        ];
+
+        for (name, args, expected) in cases {
+            let actual = check_read_condition(etag, &args);
+            assert_eq!(expected, actual.is_ok(), "{name}");
+            if let Err(err) = actual {
+                assert_eq!(ErrorKind::ConditionNotMatch, err.kind(), "{name}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capabilities() {
+        use std::env;
+
+        let mut builder = Builder::default();
+        builder.root(&env::temp_dir().to_string_lossy());
+        let op = Operator::new(builder.build().expect("build must succeed"));
+        assert_eq!(
+            op.metadata().capabilities(),
+            AccessorCapability::Read
+                | AccessorCapability::Write
+                | AccessorCapability::List
+                | AccessorCapability::Blocking
+                | AccessorCapability::ConditionalWrite
+                | AccessorCapability::ConditionalRead
+                | AccessorCapability::ListStartAfter
+                | AccessorCapability::Copy
+                | AccessorCapability::Append
+        );
+
+        let o = op.object(&uuid::Uuid::new_v4().to_string());
+        let err = o
+            .presign_read(time::Duration::hours(1))
+            .expect_err("presign must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert_eq!(err.context("service"), Some("fs"));
+        let err = o
+            .create_multipart()
+            .await
+            .err()
+            .expect("create multipart must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert_eq!(err.context("service"), Some("fs"));
+    }
 }
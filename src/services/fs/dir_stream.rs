@@ -14,6 +14,8 @@
 
 use async_trait::async_trait;
 
+use super::backend::is_tmp_file;
+use super::backend::parse_fs_metadata;
 use super::error::parse_io_error;
 use crate::raw::*;
 use crate::ObjectMetadata;
@@ -24,15 +26,17 @@ pub struct DirPager {
     root: String,
 
     size: usize,
+    /// Entries will be skipped until we have found this path.
+    start_after: Option<String>,
     rd: tokio::fs::ReadDir,
 }
 
 impl DirPager {
-    pub fn new(root: &str, rd: tokio::fs::ReadDir) -> Self {
+    pub fn new(root: &str, rd: tokio::fs::ReadDir, size: usize, start_after: Option<&str>) -> Self {
         Self {
             root: root.to_string(),
-            // TODO: make this a configurable value.
-            size: 256,
+            size,
+            start_after: start_after.map(|v| v.trim_end_matches('/').to_string()),
             rd,
         }
     }
@@ -43,13 +47,24 @@ impl ObjectPage for DirPager {
     async fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
         let mut oes: Vec<ObjectEntry> = Vec::with_capacity(self.size);
 
-        for _ in 0..self.size {
+        while oes.len() < self.size {
             let de = match self.rd.next_entry().await.map_err(parse_io_error)? {
                 Some(de) => de,
                 None => break,
             };
 
+            // Skip temp files of in-progress atomic writes.
+            if is_tmp_file(&de.file_name().to_string_lossy()) {
+                continue;
+            }
+
             let path = build_rel_path(&self.root, &de.path().to_string_lossy());
+            if let Some(start_after) = &self.start_after {
+                if start_after == &path {
+                    self.start_after = None;
+                }
+                continue;
+            }
 
             // On Windows and most Unix platforms this function is free
             // (no extra system calls needed), but some Unix platforms may
@@ -58,7 +73,10 @@ impl ObjectPage for DirPager {
             let file_type = de.file_type().await.map_err(parse_io_error)?;
 
             let d = if file_type.is_file() {
-                ObjectEntry::new(&path, ObjectMetadata::new(ObjectMode::FILE))
+                // Files' metadata is the same as `stat`, so we can return
+                // it as complete to avoid extra `stat` for every file.
+                let meta = de.metadata().await.map_err(parse_io_error)?;
+                ObjectEntry::new(&path, parse_fs_metadata(&meta)?.with_complete())
             } else if file_type.is_dir() {
                 // Make sure we are returning the correct path.
                 ObjectEntry::new(
@@ -80,15 +98,17 @@ pub struct BlockingDirPager {
     root: String,
 
     size: usize,
+    /// Entries will be skipped until we have found this path.
+    start_after: Option<String>,
     rd: std::fs::ReadDir,
 }
 
 impl BlockingDirPager {
-    pub fn new(root: &str, rd: std::fs::ReadDir) -> Self {
+    pub fn new(root: &str, rd: std::fs::ReadDir, size: usize, start_after: Option<&str>) -> Self {
         Self {
             root: root.to_string(),
-            // TODO: make this a configurable value.
-            size: 256,
+            size,
+            start_after: start_after.map(|v| v.trim_end_matches('/').to_string()),
             rd,
         }
     }
@@ -98,13 +118,24 @@ impl BlockingObjectPage for BlockingDirPager {
     fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
         let mut oes: Vec<ObjectEntry> = Vec::with_capacity(self.size);
 
-        for _ in 0..self.size {
+        while oes.len() < self.size {
             let de = match self.rd.next() {
                 Some(de) => de.map_err(parse_io_error)?,
                 None => break,
             };
 
+            // Skip temp files of in-progress atomic writes.
+            if is_tmp_file(&de.file_name().to_string_lossy()) {
+                continue;
+            }
+
             let path = build_rel_path(&self.root, &de.path().to_string_lossy());
+            if let Some(start_after) = &self.start_after {
+                if start_after == &path {
+                    self.start_after = None;
+                }
+                continue;
+            }
 
             // On Windows and most Unix platforms this function is free
             // (no extra system calls needed), but some Unix platforms may
@@ -113,7 +144,10 @@ impl BlockingObjectPage for BlockingDirPager {
             let file_type = de.file_type().map_err(parse_io_error)?;
 
             let d = if file_type.is_file() {
-                ObjectEntry::new(&path, ObjectMetadata::new(ObjectMode::FILE))
+                // Files' metadata is the same as `stat`, so we can return
+                // it as complete to avoid extra `stat` for every file.
+                let meta = de.metadata().map_err(parse_io_error)?;
+                ObjectEntry::new(&path, parse_fs_metadata(&meta)?.with_complete())
             } else if file_type.is_dir() {
                 // Make sure we are returning the correct path.
                 ObjectEntry::new(
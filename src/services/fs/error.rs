@@ -24,6 +24,7 @@ pub fn parse_io_error(err: io::Error) -> Error {
     let (kind, retryable) = match err.kind() {
         NotFound => (ErrorKind::ObjectNotFound, false),
         PermissionDenied => (ErrorKind::ObjectPermissionDenied, false),
+        AlreadyExists => (ErrorKind::ConditionNotMatch, false),
         Interrupted | UnexpectedEof | TimedOut | WouldBlock => (ErrorKind::Unexpected, true),
         _ => (ErrorKind::Unexpected, true),
     };
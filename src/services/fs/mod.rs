@@ -17,6 +17,8 @@
 //! # Configuration
 //!
 //! - `root`: Set the work dir for backend.
+//! - `atomic_write_dir`: Set the temp dir for atomic write, default to the dir of target file.
+//! - `disable_atomic_write`: Write to target file directly instead of temp file + rename.
 //!
 //! Refer to [`Builder`]'s public API docs for more information.
 //!
@@ -15,8 +15,9 @@
 use std::cmp::min;
 use std::fmt::Debug;
 use std::fmt::Formatter;
-use std::str;
-use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use bb8::PooledConnection;
@@ -26,17 +27,17 @@ use futures::AsyncReadExt;
 use http::Uri;
 use log::debug;
 use suppaftp::async_native_tls::TlsConnector;
-use suppaftp::list::File;
 use suppaftp::types::FileType;
 use suppaftp::types::Response;
 use suppaftp::FtpError;
 use suppaftp::FtpStream;
 use suppaftp::Status;
-use time::OffsetDateTime;
 use tokio::sync::OnceCell;
 
 use super::dir_stream::DirStream;
+use super::dir_stream::Entry;
 use super::dir_stream::ReadDir;
+use super::util::mlsd;
 use super::util::FtpReader;
 use crate::raw::*;
 use crate::*;
@@ -48,18 +49,38 @@ pub struct Builder {
     root: Option<String>,
     user: Option<String>,
     password: Option<String>,
+    enable_secure: bool,
+    danger_accept_invalid_certs: bool,
 }
 
 impl Debug for Builder {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Builder")
-            .field("endpoint", &self.endpoint)
+        let mut d = f.debug_struct("Builder");
+        d.field("endpoint", &self.endpoint)
             .field("root", &self.root)
-            .finish()
+            .field("user", &self.user)
+            .field("enable_secure", &self.enable_secure)
+            .field(
+                "danger_accept_invalid_certs",
+                &self.danger_accept_invalid_certs,
+            );
+        if self.password.is_some() {
+            d.field("password", &"<redacted>");
+        }
+        d.finish()
     }
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "endpoint",
+        "user",
+        "password",
+        "enable_secure",
+        "danger_accept_invalid_certs",
+    ];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
 
@@ -70,6 +91,10 @@ impl Builder {
                 "endpoint" => builder.endpoint(v),
                 "user" => builder.user(v),
                 "password" => builder.password(v),
+                "enable_secure" if !v.is_empty() => builder.enable_secure(),
+                "danger_accept_invalid_certs" if !v.is_empty() => {
+                    builder.danger_accept_invalid_certs()
+                }
                 _ => continue,
             };
         }
@@ -121,26 +146,43 @@ impl Builder {
         self
     }
 
+    /// Enable explicit FTPS (AUTH TLS) for ftp backend.
+    ///
+    /// Both control and data connections will be protected. It's the same
+    /// as using the `ftps://` scheme in endpoint.
+    pub fn enable_secure(&mut self) -> &mut Self {
+        self.enable_secure = true;
+        self
+    }
+
+    /// Accept invalid certificates while FTPS is enabled.
+    ///
+    /// # Warning
+    ///
+    /// Any certificate including self-signed and expired ones will be
+    /// trusted, which should only be used for test servers.
+    pub fn danger_accept_invalid_certs(&mut self) -> &mut Self {
+        self.danger_accept_invalid_certs = true;
+        self
+    }
+
     /// Build a ftp backend.
     pub fn build(&mut self) -> Result<impl Accessor> {
         debug!("ftp backend build started: {:?}", &self);
         let endpoint = match &self.endpoint {
             None => {
-                return Err(Error::new(
-                    ErrorKind::BackendConfigInvalid,
-                    "endpoint is empty",
-                ))
+                return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                    .with_context("field", "endpoint"))
             }
             Some(v) => v,
         };
 
         let endpoint_uri = match endpoint.parse::<Uri>() {
             Err(e) => {
-                return Err(
-                    Error::new(ErrorKind::BackendConfigInvalid, "endpoint is invalid")
-                        .with_context("endpoint", endpoint)
-                        .set_source(e),
-                );
+                return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+                    .with_context("field", "endpoint")
+                    .with_context("endpoint", endpoint)
+                    .set_source(e));
             }
             Ok(uri) => uri,
         };
@@ -150,20 +192,22 @@ impl Builder {
 
         let endpoint = format!("{}:{}", host, port);
 
-        let enable_secure = match endpoint_uri.scheme_str() {
-            Some("ftp") => false,
-            // if the user forgot to add a scheme prefix
-            // treat it as using secured scheme
-            Some("ftps") | None => true,
-
-            Some(s) => {
-                return Err(Error::new(
-                    ErrorKind::BackendConfigInvalid,
-                    "endpoint is unsupported or invalid",
-                )
-                .with_context("endpoint", s));
-            }
-        };
+        let enable_secure = self.enable_secure
+            || match endpoint_uri.scheme_str() {
+                Some("ftp") => false,
+                // if the user forgot to add a scheme prefix
+                // treat it as using secured scheme
+                Some("ftps") | None => true,
+
+                Some(s) => {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "endpoint is unsupported or invalid",
+                    )
+                    .with_context("field", "endpoint")
+                    .with_context("endpoint", s));
+                }
+            };
 
         let root = normalize_root(&self.root.take().unwrap_or_default());
 
@@ -181,21 +225,27 @@ impl Builder {
 
         Ok(apply_wrapper(Backend {
             endpoint,
+            domain: host.to_string(),
             root,
             user,
             password,
             enable_secure,
+            danger_accept_invalid_certs: self.danger_accept_invalid_certs,
             pool: OnceCell::new(),
+            mlsd_unsupported: Arc::new(AtomicBool::new(false)),
         }))
     }
 }
 
 pub struct Manager {
     endpoint: String,
+    /// Domain used to verify the certificate of server.
+    domain: String,
     root: String,
     user: String,
     password: String,
     enable_secure: bool,
+    danger_accept_invalid_certs: bool,
 }
 
 #[async_trait]
@@ -207,10 +257,13 @@ impl bb8::ManageConnection for Manager {
         let stream = FtpStream::connect(&self.endpoint).await?;
 
         // switch to secure mode if ssl/tls is on.
+        //
+        // `into_secure` will also send `PBSZ 0` and `PROT P` so that the
+        // data connections are protected too.
         let mut ftp_stream = if self.enable_secure {
-            stream
-                .into_secure(TlsConnector::new(), &self.endpoint)
-                .await?
+            let connector =
+                TlsConnector::new().danger_accept_invalid_certs(self.danger_accept_invalid_certs);
+            stream.into_secure(connector, &self.domain).await?
         } else {
             stream
         };
@@ -252,11 +305,16 @@ impl bb8::ManageConnection for Manager {
 #[derive(Clone)]
 pub struct Backend {
     endpoint: String,
+    domain: String,
     root: String,
     user: String,
     password: String,
     enable_secure: bool,
+    danger_accept_invalid_certs: bool,
     pool: OnceCell<bb8::Pool<Manager>>,
+    /// Set if server doesn't support `MLSD`, so that we can fall back to
+    /// `LIST` directly.
+    mlsd_unsupported: Arc<AtomicBool>,
 }
 
 impl Debug for Backend {
@@ -319,23 +377,23 @@ impl Accessor for Backend {
             (Some(offset), Some(size)) => {
                 ftp_stream.resume_transfer(offset as usize).await?;
                 let ds = ftp_stream.retr_as_stream(path).await?.take(size);
-                (Box::new(ds), min(size, meta.size() as u64 - offset))
+                (Box::new(ds), min(size, meta.size() - offset))
             }
             (Some(offset), None) => {
                 ftp_stream.resume_transfer(offset as usize).await?;
                 let ds = ftp_stream.retr_as_stream(path).await?;
-                (Box::new(ds), meta.size() as u64 - offset)
+                (Box::new(ds), meta.size() - offset)
             }
             (None, Some(size)) => {
                 ftp_stream
-                    .resume_transfer((meta.size() as u64 - size) as usize)
+                    .resume_transfer((meta.size() - size) as usize)
                     .await?;
                 let ds = ftp_stream.retr_as_stream(path).await?;
                 (Box::new(ds), size)
             }
             (None, None) => {
                 let ds = ftp_stream.retr_as_stream(path).await?;
-                (Box::new(ds), meta.size() as u64)
+                (Box::new(ds), meta.size())
             }
         };
 
@@ -365,18 +423,13 @@ impl Accessor for Backend {
             return Ok(RpStat::new(ObjectMetadata::new(ObjectMode::DIR)));
         }
 
-        let file = self.ftp_stat(path).await?;
+        let entry = self.ftp_stat(path).await?;
 
-        let mode = if file.is_file() {
-            ObjectMode::FILE
-        } else if file.is_directory() {
-            ObjectMode::DIR
-        } else {
-            ObjectMode::Unknown
-        };
-        let mut meta = ObjectMetadata::new(mode);
-        meta.set_content_length(file.size() as u64);
-        meta.set_last_modified(OffsetDateTime::from(file.modified()));
+        let mut meta = ObjectMetadata::new(entry.mode());
+        meta.set_content_length(entry.size());
+        if let Some(v) = entry.last_modified() {
+            meta.set_last_modified(v);
+        }
 
         Ok(RpStat::new(meta))
     }
@@ -408,9 +461,7 @@ impl Accessor for Backend {
         let mut ftp_stream = self.ftp_connect(Operation::List).await?;
 
         let pathname = if path == "/" { None } else { Some(path) };
-        let files = ftp_stream.list(pathname).await?;
-
-        let rd = ReadDir::new(files);
+        let rd = self.ftp_list(&mut ftp_stream, pathname).await?;
 
         Ok((
             RpList::default(),
@@ -428,10 +479,12 @@ impl Backend {
                     .max_size(64)
                     .build(Manager {
                         endpoint: self.endpoint.to_string(),
+                        domain: self.domain.to_string(),
                         root: self.root.to_string(),
                         user: self.user.to_string(),
                         password: self.password.to_string(),
                         enable_secure: self.enable_secure,
+                        danger_accept_invalid_certs: self.danger_accept_invalid_certs,
                     })
                     .await
             })
@@ -445,29 +498,54 @@ impl Backend {
         })
     }
 
-    async fn ftp_stat(&self, path: &str) -> Result<File> {
+    /// List entries of given dir via `MLSD`, fall back to `LIST` if `MLSD`
+    /// is not supported by server.
+    ///
+    /// `MLSD` is only used on plain connections, check [`mlsd`] for details.
+    async fn ftp_list(
+        &self,
+        ftp_stream: &mut FtpStream,
+        pathname: Option<&str>,
+    ) -> Result<ReadDir> {
+        if !self.enable_secure && !self.mlsd_unsupported.load(Ordering::Relaxed) {
+            match mlsd(ftp_stream, pathname).await {
+                Ok(lines) => return Ok(ReadDir::from_mlsd(lines)),
+                Err(FtpError::UnexpectedResponse(Response {
+                    status:
+                        Status::CommandNotImplemented | Status::BadCommand | Status::NotImplemented,
+                    ..
+                })) => {
+                    debug!("ftp server doesn't support MLSD, fall back to LIST");
+                    self.mlsd_unsupported.store(true, Ordering::Relaxed);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let lines = ftp_stream.list(pathname).await?;
+        Ok(ReadDir::from_list(lines))
+    }
+
+    async fn ftp_stat(&self, path: &str) -> Result<Entry> {
         let mut ftp_stream = self.ftp_connect(Operation::Stat).await?;
 
         let (parent, basename) = (get_parent(path), get_basename(path));
 
         let pathname = if parent == "/" { None } else { Some(parent) };
 
-        let resp = ftp_stream.list(pathname).await?;
+        let rd = self.ftp_list(&mut ftp_stream, pathname).await?;
 
         // Get stat of file.
-        let mut files = resp
-            .into_iter()
-            .filter_map(|file| File::from_str(file.as_str()).ok())
-            .filter(|f| f.name() == basename.trim_end_matches('/'))
-            .collect::<Vec<File>>();
-
-        if files.is_empty() {
-            Err(Error::new(
+        let entry = rd
+            .filter_map(|entry| entry.ok())
+            .find(|entry| entry.name() == basename.trim_end_matches('/'));
+
+        match entry {
+            Some(entry) => Ok(entry),
+            None => Err(Error::new(
                 ErrorKind::ObjectNotFound,
                 "file is not found during list",
-            ))
-        } else {
-            Ok(files.remove(0))
+            )),
         }
     }
 }
@@ -491,7 +569,7 @@ mod build_test {
         let b = builder.build();
         assert!(b.is_ok());
 
-        // no scheme
+        // no scheme, will be seen as ftps
         let mut builder = Builder::default();
         builder.endpoint("ftp_server.local:8765");
         let b = builder.build();
@@ -503,6 +581,17 @@ mod build_test {
         let b = builder.build();
         assert!(b.is_err());
         let e = b.unwrap_err();
-        assert_eq!(e.kind(), ErrorKind::BackendConfigInvalid);
+        assert_eq!(e.kind(), ErrorKind::ConfigInvalid);
+        assert_eq!(e.context("field"), Some("endpoint"));
+    }
+
+    #[test]
+    fn test_build_secure() {
+        let mut builder = Builder::default();
+        builder
+            .endpoint("ftp://ftp_server.local")
+            .enable_secure()
+            .danger_accept_invalid_certs();
+        assert!(builder.build().is_ok());
     }
 }
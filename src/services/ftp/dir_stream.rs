@@ -12,43 +12,197 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::str;
 use std::str::FromStr;
 
 use async_trait::async_trait;
 use suppaftp::list::File;
+use time::Date;
+use time::Month;
 use time::OffsetDateTime;
+use time::Time;
 
 use crate::raw::*;
 use crate::*;
 
+/// Entry parsed from a line returned by `MLSD` or `LIST`.
+pub struct Entry {
+    name: String,
+    mode: ObjectMode,
+    size: u64,
+    last_modified: Option<OffsetDateTime>,
+}
+
+impl Entry {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn mode(&self) -> ObjectMode {
+        self.mode
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn last_modified(&self) -> Option<OffsetDateTime> {
+        self.last_modified
+    }
+
+    /// Parse a line returned by `LIST`, both Unix and DOS styled lines are
+    /// supported.
+    ///
+    /// Returns `None` if this line should be skipped.
+    pub fn from_list_line(line: &str) -> Result<Option<Entry>> {
+        // Some servers prepend `total <blocks>` like `ls -l` does.
+        if line.starts_with("total ") {
+            return Ok(None);
+        }
+
+        let f = File::from_str(line).map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "parse file from response")
+                .with_context("line", line)
+                .set_source(e)
+        })?;
+
+        // Skip the current and parent dirs returned by some servers.
+        if f.name() == "." || f.name() == ".." {
+            return Ok(None);
+        }
+
+        let mode = if f.is_file() {
+            ObjectMode::FILE
+        } else if f.is_directory() {
+            ObjectMode::DIR
+        } else {
+            ObjectMode::Unknown
+        };
+
+        Ok(Some(Entry {
+            name: f.name().to_string(),
+            mode,
+            size: f.size() as u64,
+            last_modified: Some(OffsetDateTime::from(f.modified())),
+        }))
+    }
+
+    /// Parse a line returned by `MLSD` like `type=file;size=13;modify=20210104123000; a.txt`.
+    ///
+    /// Returns `None` if this line should be skipped.
+    ///
+    /// Check [RFC 3659](https://www.rfc-editor.org/rfc/rfc3659#section-7) for details.
+    pub fn from_mlsd_line(line: &str) -> Result<Option<Entry>> {
+        // Facts are ended by a space, the rest is the name which could
+        // contain spaces too.
+        let (facts, name) = match line.split_once(' ') {
+            Some((facts, name)) if !name.is_empty() => (facts, name),
+            _ => {
+                return Err(
+                    Error::new(ErrorKind::Unexpected, "parse file from response")
+                        .with_context("line", line),
+                )
+            }
+        };
+
+        let mut entry = Entry {
+            name: name.to_string(),
+            mode: ObjectMode::Unknown,
+            size: 0,
+            last_modified: None,
+        };
+
+        for (k, v) in facts.split(';').filter_map(|v| v.split_once('=')) {
+            match k.to_ascii_lowercase().as_str() {
+                "type" => match v.to_ascii_lowercase().as_str() {
+                    "file" => entry.mode = ObjectMode::FILE,
+                    "dir" => entry.mode = ObjectMode::DIR,
+                    // Skip the current and parent dirs.
+                    "cdir" | "pdir" => return Ok(None),
+                    _ => entry.mode = ObjectMode::Unknown,
+                },
+                "size" => entry.size = v.parse().unwrap_or_default(),
+                "modify" => entry.last_modified = parse_mlsd_time(v),
+                _ => {}
+            }
+        }
+
+        Ok(Some(entry))
+    }
+}
+
+/// Parse time value of `MLSD` facts like `20210104123000` or
+/// `20210104123000.123`, which is always in UTC.
+fn parse_mlsd_time(s: &str) -> Option<OffsetDateTime> {
+    let s = s.split('.').next()?;
+    if s.len() != 14 || !s.bytes().all(|v| v.is_ascii_digit()) {
+        return None;
+    }
+
+    let date = Date::from_calendar_date(
+        s[0..4].parse().ok()?,
+        Month::try_from(s[4..6].parse::<u8>().ok()?).ok()?,
+        s[6..8].parse().ok()?,
+    )
+    .ok()?;
+    let time = Time::from_hms(
+        s[8..10].parse().ok()?,
+        s[10..12].parse().ok()?,
+        s[12..14].parse().ok()?,
+    )
+    .ok()?;
+
+    Some(date.with_time(time).assume_utc())
+}
+
 pub struct ReadDir {
-    files: Vec<String>,
+    lines: Vec<String>,
     index: usize,
+    mlsd: bool,
 }
 
 impl Iterator for ReadDir {
-    type Item = Result<File>;
+    type Item = Result<Entry>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.files.len() {
-            return None;
-        }
-        self.index += 1;
-        let result = match File::from_str(self.files[self.index - 1].as_str()) {
-            Ok(f) => Ok(f),
-            Err(e) => {
-                Err(Error::new(ErrorKind::Unexpected, "parse file from response").set_source(e))
+        loop {
+            if self.index >= self.lines.len() {
+                return None;
             }
-        };
+            self.index += 1;
+            let line = self.lines[self.index - 1].as_str();
 
-        Some(result)
+            let result = if self.mlsd {
+                Entry::from_mlsd_line(line)
+            } else {
+                Entry::from_list_line(line)
+            };
+
+            match result {
+                Ok(None) => continue,
+                Ok(Some(entry)) => return Some(Ok(entry)),
+                Err(err) => return Some(Err(err)),
+            }
+        }
     }
 }
 
 impl ReadDir {
-    pub fn new(files: Vec<String>) -> ReadDir {
-        ReadDir { files, index: 0 }
+    /// Create a ReadDir from lines returned by `LIST`.
+    pub fn from_list(lines: Vec<String>) -> ReadDir {
+        ReadDir {
+            lines,
+            index: 0,
+            mlsd: false,
+        }
+    }
+
+    /// Create a ReadDir from lines returned by `MLSD`.
+    pub fn from_mlsd(lines: Vec<String>) -> ReadDir {
+        ReadDir {
+            lines,
+            index: 0,
+            mlsd: true,
+        }
     }
 }
 
@@ -82,24 +236,24 @@ impl ObjectPage for DirStream {
 
             let path = self.path.to_string() + de.name();
 
-            let d = if de.is_file() {
-                ObjectEntry::new(
-                    &path,
-                    ObjectMetadata::new(ObjectMode::FILE)
-                        .with_content_length(de.size() as u64)
-                        .with_last_modified(OffsetDateTime::from(de.modified()))
-                        .with_complete(),
-                )
-            } else if de.is_directory() {
-                ObjectEntry::new(
+            let d = match de.mode() {
+                ObjectMode::FILE => {
+                    let mut meta = ObjectMetadata::new(ObjectMode::FILE)
+                        .with_content_length(de.size())
+                        .with_complete();
+                    if let Some(v) = de.last_modified() {
+                        meta.set_last_modified(v);
+                    }
+                    ObjectEntry::new(&path, meta)
+                }
+                ObjectMode::DIR => ObjectEntry::new(
                     &format!("{}/", &path),
                     ObjectMetadata::new(ObjectMode::DIR).with_complete(),
-                )
-            } else {
-                ObjectEntry::new(
+                ),
+                ObjectMode::Unknown => ObjectEntry::new(
                     &path,
                     ObjectMetadata::new(ObjectMode::Unknown).with_complete(),
-                )
+                ),
             };
 
             oes.push(d)
@@ -108,3 +262,141 @@ impl ObjectPage for DirStream {
         Ok(if oes.is_empty() { None } else { Some(oes) })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn list(lines: &[&str]) -> Vec<ObjectEntry> {
+        let rd = ReadDir::from_list(lines.iter().map(|v| v.to_string()).collect());
+        let mut ds = DirStream::new("dir/", rd);
+
+        let mut entries = Vec::new();
+        while let Some(page) = ds.next_page().await.expect("list must succeed") {
+            entries.extend(page);
+        }
+        entries
+    }
+
+    #[tokio::test]
+    async fn test_list_posix() {
+        let entries = list(&[
+            "total 12",
+            "drwxr-xr-x    2 1000     1000         4096 Jan 04 12:30 .",
+            "drwxr-xr-x    3 1000     1000         4096 Jan 04 12:30 ..",
+            "-rw-r--r--    1 1000     1000           13 Jan 04  2021 hello world.txt",
+            "drwxr-xr-x    2 1000     1000         4096 Jan 04  2021 my  dir",
+        ])
+        .await;
+
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].path(), "dir/hello world.txt");
+        assert_eq!(entries[0].mode(), ObjectMode::FILE);
+        assert_eq!(entries[0].metadata().content_length(), 13);
+        assert_eq!(
+            entries[0].metadata().last_modified(),
+            Some(OffsetDateTime::from_unix_timestamp(1609718400).unwrap())
+        );
+
+        assert_eq!(entries[1].path(), "dir/my  dir/");
+        assert_eq!(entries[1].mode(), ObjectMode::DIR);
+    }
+
+    #[tokio::test]
+    async fn test_list_dos() {
+        let entries = list(&[
+            "04-01-21  12:30PM                   13 hello world.txt",
+            "04-01-21  12:30PM       <DIR>          my dir",
+        ])
+        .await;
+
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].path(), "dir/hello world.txt");
+        assert_eq!(entries[0].mode(), ObjectMode::FILE);
+        assert_eq!(entries[0].metadata().content_length(), 13);
+        assert_eq!(
+            entries[0].metadata().last_modified(),
+            Some(OffsetDateTime::from_unix_timestamp(1609763400).unwrap())
+        );
+
+        assert_eq!(entries[1].path(), "dir/my dir/");
+        assert_eq!(entries[1].mode(), ObjectMode::DIR);
+    }
+
+    #[tokio::test]
+    async fn test_list_mlsd() {
+        let rd = ReadDir::from_mlsd(
+            [
+                "type=cdir;modify=20210104123000; .",
+                "type=pdir;modify=20210104123000; ..",
+                "type=file;size=13;modify=20210104000000.123;UNIX.mode=0644; hello world.txt",
+                "Type=dir;Modify=20210104000000; my dir",
+                "type=OS.unix=slink:/tmp;size=4; link",
+            ]
+            .iter()
+            .map(|v| v.to_string())
+            .collect(),
+        );
+        let mut ds = DirStream::new("dir/", rd);
+
+        let mut entries = Vec::new();
+        while let Some(page) = ds.next_page().await.expect("list must succeed") {
+            entries.extend(page);
+        }
+
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[0].path(), "dir/hello world.txt");
+        assert_eq!(entries[0].mode(), ObjectMode::FILE);
+        assert_eq!(entries[0].metadata().content_length(), 13);
+        assert_eq!(
+            entries[0].metadata().last_modified(),
+            Some(OffsetDateTime::from_unix_timestamp(1609718400).unwrap())
+        );
+
+        assert_eq!(entries[1].path(), "dir/my dir/");
+        assert_eq!(entries[1].mode(), ObjectMode::DIR);
+
+        assert_eq!(entries[2].path(), "dir/link");
+        assert_eq!(entries[2].mode(), ObjectMode::Unknown);
+    }
+
+    #[test]
+    fn test_parse_mlsd_line_invalid() {
+        let err = Entry::from_mlsd_line("type=file;size=13;")
+            .err()
+            .expect("parse must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    #[test]
+    fn test_parse_mlsd_time() {
+        let cases = vec![
+            ("20210104123000", Some(1609763400)),
+            ("20210104123000.123", Some(1609763400)),
+            ("2021010412300", None),
+            ("20211304123000", None),
+            ("invalid", None),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                parse_mlsd_time(input),
+                expected.map(|v| OffsetDateTime::from_unix_timestamp(v).unwrap()),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_invalid_line() {
+        let rd = ReadDir::from_list(vec!["invalid line".to_string()]);
+        let mut ds = DirStream::new("dir/", rd);
+
+        let err = ds.next_page().await.expect_err("list must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+}
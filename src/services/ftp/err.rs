@@ -30,6 +30,9 @@ impl From<FtpError> for Error {
             FtpError::UnexpectedResponse(ref resp) if resp.status == Status::FileUnavailable => {
                 (ErrorKind::ObjectNotFound, false)
             }
+            FtpError::UnexpectedResponse(ref resp) if resp.status == Status::NotLoggedIn => {
+                (ErrorKind::ObjectPermissionDenied, false)
+            }
             // Allow retry bad response.
             FtpError::BadResponse => (ErrorKind::Unexpected, true),
             _ => (ErrorKind::Unexpected, false),
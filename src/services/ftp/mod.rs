@@ -20,16 +20,37 @@
 //! - `port` : set the port for connection
 //! - `root`: Set the work directory for backend
 //! - `credential`:  login credentials
-//! - `tls`: tls mode
+//! - `enable_secure`: use explicit FTPS (AUTH TLS)
+//! - `danger_accept_invalid_certs`: skip certificate verification for FTPS
 //!
 //! You can refer to [`Builder`]'s docs for more information
 //!
+//! # FTPS
+//!
+//! Explicit FTPS will be used if endpoint starts with `ftps://` (or has no
+//! scheme) or [`Builder::enable_secure`] is called. Both the control and
+//! data connections (`PROT P`) will be protected, so reads, writes and
+//! listings never go over plaintext.
+//!
+//! # List
+//!
+//! Entries are listed via `MLSD` whose machine readable facts are used to
+//! fill `content_length` and `last_modified`. If the server doesn't support
+//! `MLSD`, we will fall back to `LIST` for this backend: both Unix (`ls -l`)
+//! and DOS styled lines are parsed. The `total` line and `.`/`..` entries
+//! returned by some servers are skipped.
+//!
+//! Our ftp client can't send `MLSD` over TLS, so `LIST` is always used
+//! with FTPS.
+//!
 //! # Environment
 //!
 //! - `OPENDAL_FTP_ENDPOINT`    optional
 //! - `OPENDAL_FTP_ROOT`    required
 //! - `OPENDAL_FTP_USER`  optional
 //! - `OPENDAL_FTP_PASSWORD`    optional
+//! - `OPENDAL_FTP_ENABLE_SECURE`    optional
+//! - `OPENDAL_FTP_DANGER_ACCEPT_INVALID_CERTS`    optional
 //!
 //! # Example
 //!
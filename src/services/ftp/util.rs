@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use std::io;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
@@ -21,8 +23,14 @@ use bb8::PooledConnection;
 use futures::future::BoxFuture;
 use futures::ready;
 use futures::AsyncRead;
+use futures::AsyncWriteExt;
 use futures::FutureExt;
+use suppaftp::types::FtpResult;
+use suppaftp::FtpError;
+use suppaftp::FtpStream;
 use suppaftp::Status;
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpStream;
 
 use super::backend::Manager;
 use crate::raw::*;
@@ -35,7 +43,7 @@ pub struct FtpReader {
 }
 
 pub enum State {
-    Reading(Option<PooledConnection<'static, Manager>>),
+    Reading(Option<Box<PooledConnection<'static, Manager>>>),
     Finalize(BoxFuture<'static, Result<()>>),
 }
 
@@ -44,7 +52,7 @@ impl FtpReader {
     pub fn new(r: BytesReader, c: PooledConnection<'static, Manager>) -> Self {
         Self {
             reader: r,
-            state: State::Reading(Some(c)),
+            state: State::Reading(Some(Box::new(c))),
         }
     }
 }
@@ -96,3 +104,190 @@ impl AsyncRead for FtpReader {
         }
     }
 }
+
+/// Execute `MLSD` and return the lines it returned.
+///
+/// suppaftp doesn't support `MLSD` or sending custom commands, so we write
+/// commands to the control connection directly and read responses via
+/// `read_response_in`. The control connection is wrapped by TLS in secure
+/// mode, so this MUST only be used on plain connections.
+pub async fn mlsd(ftp_stream: &mut FtpStream, pathname: Option<&str>) -> FtpResult<Vec<String>> {
+    send_command(ftp_stream, "PASV").await?;
+    let resp = ftp_stream.read_response(Status::PassiveMode).await?;
+    let addr = match parse_pasv_addr(&resp.body) {
+        Some(addr) => addr,
+        None => return Err(FtpError::UnexpectedResponse(resp)),
+    };
+
+    let cmd = match pathname {
+        Some(p) => format!("MLSD {}", p),
+        None => "MLSD".to_string(),
+    };
+    send_command(ftp_stream, &cmd).await?;
+
+    // Some servers only reply after the data connection established, so we
+    // must connect before reading the response. The response should be read
+    // even if connect failed, otherwise it will be left in the connection.
+    let data_stream = TcpStream::connect(addr).await;
+    ftp_stream
+        .read_response_in(&[Status::AboutToSend, Status::AlreadyOpen])
+        .await?;
+    let mut data_stream = data_stream.map_err(FtpError::ConnectionError)?;
+
+    let mut bs = Vec::new();
+    let res = data_stream.read_to_end(&mut bs).await;
+    // Data connection must be closed before reading the final response.
+    drop(data_stream);
+    ftp_stream
+        .read_response_in(&[Status::ClosingDataConnection, Status::RequestedFileActionOk])
+        .await?;
+    res.map_err(|_| FtpError::BadResponse)?;
+
+    Ok(String::from_utf8_lossy(&bs)
+        .lines()
+        .map(|v| v.trim_end_matches('\r'))
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string())
+        .collect())
+}
+
+async fn send_command(ftp_stream: &FtpStream, cmd: &str) -> FtpResult<()> {
+    let mut w = ftp_stream.get_ref().await;
+    w.write_all(format!("{}\r\n", cmd).as_bytes())
+        .await
+        .map_err(FtpError::ConnectionError)
+}
+
+/// Parse address from response of `PASV` like `227 Entering Passive Mode (h1,h2,h3,h4,p1,p2).`
+fn parse_pasv_addr(body: &str) -> Option<SocketAddr> {
+    let start = body.find('(')?;
+    let end = start + body[start..].find(')')?;
+
+    let vs = body[start + 1..end]
+        .split(',')
+        .map(|v| v.trim().parse::<u8>())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .ok()?;
+    if vs.len() != 6 {
+        return None;
+    }
+
+    let ip = Ipv4Addr::new(vs[0], vs[1], vs[2], vs[3]);
+    let port = (u16::from(vs[4]) << 8) | u16::from(vs[5]);
+    Some(SocketAddr::new(ip.into(), port))
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncBufReadExt;
+    use tokio::io::AsyncWriteExt;
+    use tokio::io::BufReader;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Serve a single ftp connection which sends `lines` back for `MLSD`,
+    /// or rejects it if `reject` is true.
+    async fn serve(listener: TcpListener, lines: &'static [&'static str], reject: bool) {
+        let (conn, _) = listener.accept().await.unwrap();
+        let (r, mut w) = conn.into_split();
+        let mut r = BufReader::new(r).lines();
+
+        w.write_all(b"220 ready\r\n").await.unwrap();
+
+        let data_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = data_listener.local_addr().unwrap().port();
+
+        assert_eq!(r.next_line().await.unwrap().unwrap(), "PASV");
+        w.write_all(
+            format!(
+                "227 Entering Passive Mode (127,0,0,1,{},{}).\r\n",
+                port >> 8,
+                port & 0xff
+            )
+            .as_bytes(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(r.next_line().await.unwrap().unwrap(), "MLSD dir");
+        let (mut data, _) = data_listener.accept().await.unwrap();
+        if reject {
+            w.write_all(b"500 Unknown command.\r\n").await.unwrap();
+            return;
+        }
+
+        w.write_all(b"150 Here comes the directory listing.\r\n")
+            .await
+            .unwrap();
+        for line in lines {
+            data.write_all(format!("{}\r\n", line).as_bytes())
+                .await
+                .unwrap();
+        }
+        drop(data);
+        w.write_all(b"226 Directory send OK.\r\n").await.unwrap();
+
+        // Keep the control connection open until client closed it.
+        let _ = r.next_line().await;
+    }
+
+    #[tokio::test]
+    async fn test_mlsd() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve(
+            listener,
+            &[
+                "type=cdir; .",
+                "type=file;size=13;modify=20210104123000; hello world.txt",
+            ],
+            false,
+        ));
+
+        let mut ftp_stream = FtpStream::connect(addr).await.unwrap();
+        let lines = mlsd(&mut ftp_stream, Some("dir"))
+            .await
+            .expect("mlsd must succeed");
+        assert_eq!(
+            lines,
+            vec![
+                "type=cdir; .",
+                "type=file;size=13;modify=20210104123000; hello world.txt"
+            ]
+        );
+
+        drop(ftp_stream);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mlsd_unsupported() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve(listener, &[], true));
+
+        let mut ftp_stream = FtpStream::connect(addr).await.unwrap();
+        let err = mlsd(&mut ftp_stream, Some("dir"))
+            .await
+            .expect_err("mlsd must fail");
+        assert!(
+            matches!(&err, FtpError::UnexpectedResponse(resp) if resp.status == Status::BadCommand),
+            "{:?}",
+            err
+        );
+
+        server.await.unwrap();
+    }
+
+    #[test]
+    fn test_parse_pasv_addr() {
+        assert_eq!(
+            parse_pasv_addr("227 Entering Passive Mode (192,168,1,2,4,1)."),
+            Some("192.168.1.2:1025".parse().unwrap())
+        );
+        assert_eq!(parse_pasv_addr("227 Entering Passive Mode."), None);
+        assert_eq!(parse_pasv_addr("227 (192,168,1,2,4)"), None);
+        assert_eq!(parse_pasv_addr("227 (192,168,1,256,4,1)"), None);
+    }
+}
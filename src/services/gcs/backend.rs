@@ -12,33 +12,46 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Write;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use bytes::Buf;
+use bytes::Bytes;
+use futures::AsyncReadExt;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::Request;
 use http::Response;
 use http::StatusCode;
 use log::debug;
+use reqsign::AwsV4Signer;
 use reqsign::GoogleSigner;
 use serde::Deserialize;
+use serde::Serialize;
 use serde_json;
-use time::format_description::well_known::Rfc3339;
-use time::OffsetDateTime;
+use uuid::Uuid;
 
+use super::batch::build_batch_body;
+use super::batch::parse_batch_response;
+use super::batch::BATCH_MAX_SIZE;
 use super::dir_stream::DirStream;
 use super::error::parse_error;
 use super::error::parse_json_deserialize_error;
+use super::error::parse_json_serialize_error;
+use super::error::parse_xml_deserialize_error;
 use super::uri::percent_encode_path;
+use crate::raw::percent_encode_path as percent_encode_xml_path;
 use crate::raw::*;
 use crate::*;
 
 const DEFAULT_GCS_ENDPOINT: &str = "https://storage.googleapis.com";
 const DEFAULT_GCS_SCOPE: &str = "https://www.googleapis.com/auth/devstorage.read_write";
+/// The max expire of presigned requests allowed by V4 signing.
+const PRESIGN_MAX_EXPIRE: time::Duration = time::Duration::days(7);
 
 // TODO: Server side encryption support
 
@@ -61,9 +74,35 @@ pub struct Builder {
     credential: Option<String>,
     /// credential path for GCS service.
     credential_path: Option<String>,
+
+    /// HMAC key for presigning.
+    hmac_access_key_id: Option<String>,
+    hmac_secret: Option<String>,
+
+    /// Send requests without signing.
+    allow_anonymous: bool,
+
+    http_client: Option<reqwest::Client>,
+    pool_max_idle_per_host: Option<usize>,
+    connect_timeout: Option<std::time::Duration>,
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "bucket",
+        "endpoint",
+        "credential",
+        "credential_path",
+        "scope",
+        "service_account",
+        "hmac_access_key_id",
+        "hmac_secret",
+        "allow_anonymous",
+        "pool_max_idle_per_host",
+        "connect_timeout",
+    ];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
         for (k, v) in it {
@@ -73,7 +112,20 @@ impl Builder {
                 "bucket" => builder.bucket(v),
                 "endpoint" => builder.endpoint(v),
                 "credential" => builder.credential(v),
+                "credential_path" => builder.credential_path(v),
                 "scope" => builder.scope(v),
+                "service_account" => builder.service_account(v),
+                "hmac_access_key_id" => builder.hmac_access_key_id(v),
+                "hmac_secret" => builder.hmac_secret(v),
+                "allow_anonymous" if !v.is_empty() => builder.allow_anonymous(),
+                "pool_max_idle_per_host" => match v.parse::<usize>() {
+                    Ok(v) => builder.pool_max_idle_per_host(v),
+                    _ => continue,
+                },
+                "connect_timeout" => match v.parse::<u64>() {
+                    Ok(v) => builder.connect_timeout(std::time::Duration::from_secs(v)),
+                    _ => continue,
+                },
                 _ => continue,
             };
         }
@@ -148,6 +200,62 @@ impl Builder {
         self
     }
 
+    /// Set the access id of HMAC key which is used to presign requests.
+    ///
+    /// Presigning requires static credentials, OAuth2 tokens can't be
+    /// used. Please refer to [HMAC keys](https://cloud.google.com/storage/docs/authentication/hmackeys)
+    /// to create one.
+    pub fn hmac_access_key_id(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.hmac_access_key_id = Some(v.to_string())
+        };
+        self
+    }
+
+    /// Set the secret of HMAC key which is used to presign requests.
+    pub fn hmac_secret(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.hmac_secret = Some(v.to_string())
+        };
+        self
+    }
+
+    /// Allow anonymous access so that opendal will send requests without
+    /// signing them. This is useful for public-read buckets.
+    ///
+    /// OAuth2 tokens will not be loaded. Operations that require permission
+    /// like write will be rejected by the server with `PermissionDenied`.
+    pub fn allow_anonymous(&mut self) -> &mut Self {
+        self.allow_anonymous = true;
+        self
+    }
+
+    /// Set the http client used by this backend.
+    ///
+    /// Connections will be reused across operators that share clones of
+    /// the same `client`. Passing a custom client overrides
+    /// [`Builder::pool_max_idle_per_host`] and [`Builder::connect_timeout`].
+    pub fn http_client(&mut self, client: reqwest::Client) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Set the max idle connections kept alive per host.
+    ///
+    /// This will be ignored if [`Builder::http_client`] is set.
+    pub fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set the timeout for establishing connections.
+    ///
+    /// This will be ignored if [`Builder::http_client`] is set.
+    pub fn connect_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
     /// Establish connection to GCS and finish making GCS backend
     pub fn build(&mut self) -> Result<impl Accessor> {
         debug!("backend build started: {:?}", self);
@@ -158,17 +266,21 @@ impl Builder {
         // Handle endpoint and bucket name
         let bucket = match self.bucket.is_empty() {
             false => Ok(&self.bucket),
-            true => Err(
-                Error::new(ErrorKind::BackendConfigInvalid, "bucket is empty")
-                    .with_operation("Builder::build")
-                    .with_context("service", Scheme::Gcs),
-            ),
+            true => Err(Error::new(ErrorKind::ConfigInvalid, "bucket is empty")
+                .with_context("field", "bucket")
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Gcs)),
         }?;
 
         // TODO: server side encryption
 
         // build http client
-        let client = HttpClient::new();
+        let client = match self.http_client.take() {
+            Some(client) => HttpClient::with_client(client),
+            None => {
+                HttpClient::with_pool_options(self.pool_max_idle_per_host, self.connect_timeout)
+            }
+        };
         let endpoint = self
             .endpoint
             .clone()
@@ -193,7 +305,8 @@ impl Builder {
             signer_builder.credential_path(cred);
         }
         let signer = signer_builder.build().map_err(|e| {
-            Error::new(ErrorKind::BackendConfigInvalid, "build GoogleSigner")
+            Error::new(ErrorKind::ConfigInvalid, "build GoogleSigner")
+                .with_context("field", "credential")
                 .with_operation("Builder::build")
                 .with_context("service", Scheme::Gcs)
                 .with_context("bucket", bucket)
@@ -202,11 +315,40 @@ impl Builder {
         })?;
         let signer = Arc::new(signer);
 
+        // GCS accepts V4 signatures of HMAC keys in XML API which is the
+        // same as s3.
+        let presign_signer = match (&self.hmac_access_key_id, &self.hmac_secret) {
+            (Some(ak), Some(sk)) => {
+                let signer = AwsV4Signer::builder()
+                    .service("s3")
+                    .region("auto")
+                    .access_key(ak)
+                    .secret_key(sk)
+                    .disable_load_from_env()
+                    .disable_load_from_profile()
+                    .disable_load_from_imds_v2()
+                    .disable_load_from_assume_role()
+                    .disable_load_from_assume_role_with_web_identity()
+                    .build()
+                    .map_err(|e| {
+                        Error::new(ErrorKind::ConfigInvalid, "build AwsV4Signer")
+                            .with_context("field", "hmac_access_key_id")
+                            .with_operation("Builder::build")
+                            .with_context("service", Scheme::Gcs)
+                            .set_source(e)
+                    })?;
+                Some(Arc::new(signer))
+            }
+            _ => None,
+        };
+
         let backend = Backend {
             root,
             endpoint,
             bucket: bucket.clone(),
             signer,
+            presign_signer,
+            allow_anonymous: self.allow_anonymous,
             client,
         };
 
@@ -220,10 +362,25 @@ impl Debug for Builder {
 
         ds.field("root", &self.root)
             .field("bucket", &self.bucket)
-            .field("endpoint", &self.endpoint);
+            .field("endpoint", &self.endpoint)
+            .field("scope", &self.scope)
+            .field("service_account", &self.service_account)
+            .field("credential_path", &self.credential_path)
+            .field("allow_anonymous", &self.allow_anonymous);
         if self.credential.is_some() {
             ds.field("credentials", &"<redacted>");
         }
+        if self.hmac_access_key_id.is_some() {
+            ds.field("hmac_access_key_id", &"<redacted>");
+        }
+        if self.hmac_secret.is_some() {
+            ds.field("hmac_secret", &"<redacted>");
+        }
+        ds.field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field("connect_timeout", &self.connect_timeout);
+        if self.http_client.is_some() {
+            ds.field("http_client", &"<custom>");
+        }
         ds.finish()
     }
 }
@@ -238,6 +395,8 @@ pub struct Backend {
 
     client: HttpClient,
     signer: Arc<GoogleSigner>,
+    presign_signer: Option<Arc<AwsV4Signer>>,
+    allow_anonymous: bool,
 }
 
 impl Debug for Backend {
@@ -248,6 +407,7 @@ impl Debug for Backend {
             .field("root", &self.root)
             .field("client", &self.client)
             .field("signer", &"<redacted>")
+            .field("allow_anonymous", &self.allow_anonymous)
             .finish()
     }
 }
@@ -255,20 +415,34 @@ impl Debug for Backend {
 #[async_trait]
 impl Accessor for Backend {
     fn metadata(&self) -> AccessorMetadata {
+        let mut capabilities = AccessorCapability::Read
+            | AccessorCapability::Write
+            | AccessorCapability::List
+            | AccessorCapability::ConditionalWrite
+            | AccessorCapability::ConditionalRead
+            | AccessorCapability::ListStartAfter
+            | AccessorCapability::ListRecursive
+            | AccessorCapability::Copy
+            | AccessorCapability::Multipart
+            | AccessorCapability::Versioning
+            | AccessorCapability::Batch;
+        if self.presign_signer.is_some() {
+            capabilities |= AccessorCapability::Presign;
+        }
+
         let mut am = AccessorMetadata::default();
         am.set_scheme(Scheme::Gcs)
             .set_root(&self.root)
             .set_name(&self.bucket)
-            .set_capabilities(
-                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::List,
-            );
+            .set_capabilities(capabilities);
         am
     }
 
     async fn create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
-        let mut req = self.gcs_insert_object_request(path, Some(0), None, AsyncBody::Empty)?;
+        let mut req =
+            self.gcs_insert_object_request(path, Some(0), None, None, AsyncBody::Empty)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         let resp = self.client.send_async(req).await?;
 
@@ -281,7 +455,7 @@ impl Accessor for Backend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
-        let resp = self.gcs_get_object(path, args.range()).await?;
+        let resp = self.gcs_get_object(path, &args).await?;
 
         if resp.status().is_success() {
             let meta = parse_into_object_metadata(path, resp.headers())?;
@@ -292,32 +466,54 @@ impl Accessor for Backend {
     }
 
     async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
-        let mut req = self.gcs_insert_object_request(
-            path,
-            Some(args.size()),
-            args.content_type(),
-            AsyncBody::Reader(r),
-        )?;
+        // GCS checks object data preconditions via generation, `0` means
+        // the object must not exist.
+        let if_generation_match = if args.if_not_exists() { Some(0) } else { None };
+        let mut req = match (args.content_disposition(), args.cache_control()) {
+            (None, None) => self.gcs_insert_object_request(
+                path,
+                Some(args.size()),
+                args.content_type(),
+                if_generation_match,
+                AsyncBody::Reader(r),
+            )?,
+            // Media upload can't carry metadata other than content type,
+            // use multipart upload instead.
+            _ => self.gcs_insert_object_multipart_request(path, &args, if_generation_match, r)?,
+        };
+        insert_write_conditions(req.headers_mut(), &args)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         let resp = self.client.send_async(req).await?;
 
         if (200..300).contains(&resp.status().as_u16()) {
-            resp.into_body().consume().await?;
-            Ok(RpWrite::new(args.size()))
+            // GCS returns the object resource of written object.
+            let slc = resp.into_body().bytes().await?;
+            let rp = RpWrite::new(args.size());
+            if slc.is_empty() {
+                return Ok(rp);
+            }
+
+            let meta: GetObjectJsonResponse =
+                serde_json::from_slice(&slc).map_err(parse_json_deserialize_error)?;
+            Ok(if meta.etag.is_empty() {
+                rp
+            } else {
+                rp.with_etag(&meta.etag)
+            })
         } else {
             Err(parse_error(resp).await?)
         }
     }
 
-    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         // Stat root always returns a DIR.
         if path == "/" {
             return Ok(RpStat::new(ObjectMetadata::new(ObjectMode::DIR)));
         }
 
-        let resp = self.gcs_get_object_metadata(path).await?;
+        let resp = self.gcs_get_object_metadata(path, args.version()).await?;
 
         if resp.status().is_success() {
             // read http response body
@@ -332,8 +528,13 @@ impl Accessor for Backend {
             };
             let mut m = ObjectMetadata::new(mode);
 
-            m.set_etag(&meta.etag);
+            if !meta.etag.is_empty() {
+                m.set_etag(&meta.etag);
+            }
             m.set_content_md5(&meta.md5_hash);
+            if !meta.generation.is_empty() {
+                m.set_version_id(&meta.generation);
+            }
 
             let size = meta
                 .size
@@ -343,11 +544,16 @@ impl Accessor for Backend {
             if !meta.content_type.is_empty() {
                 m.set_content_type(&meta.content_type);
             }
+            if !meta.content_disposition.is_empty() {
+                m.set_content_disposition(&meta.content_disposition);
+            }
+            if !meta.cache_control.is_empty() {
+                m.set_cache_control(&meta.cache_control);
+            }
 
-            let datetime = OffsetDateTime::parse(&meta.updated, &Rfc3339).map_err(|e| {
-                Error::new(ErrorKind::Unexpected, "parse date time with rfc 3339").set_source(e)
-            })?;
-            m.set_last_modified(datetime);
+            if let Some(v) = parse_datetime_from_rfc3339(&meta.updated) {
+                m.set_last_modified(v);
+            }
 
             Ok(RpStat::new(m))
         } else if resp.status() == StatusCode::NOT_FOUND && path.ends_with('/') {
@@ -357,8 +563,8 @@ impl Accessor for Backend {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.gcs_delete_object(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self.gcs_delete_object(path, args.version()).await?;
 
         // deleting not existing objects is ok
         if resp.status().is_success() || resp.status() == StatusCode::NOT_FOUND {
@@ -368,29 +574,309 @@ impl Accessor for Backend {
         }
     }
 
-    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, ObjectPager)> {
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let BatchOperations::Delete(ops) = args.into_operation();
+
+        // GCS allows at most 100 calls in one batch request, split them
+        // into chunks and send them one by one.
+        let mut results = Vec::with_capacity(ops.len());
+        for chunk in ops.chunks(BATCH_MAX_SIZE) {
+            match self.gcs_batch_delete(chunk).await {
+                Ok(v) => results.extend(v),
+                // The whole chunk failed, mark all paths in it as failed.
+                Err(err) => results.extend(chunk.iter().map(|(path, _)| {
+                    let mut e = Error::new(err.kind(), "batch delete request failed")
+                        .with_operation(Operation::Delete.into_static())
+                        .with_context("path", path)
+                        .set_source(anyhow::anyhow!(err.to_string()));
+                    if err.is_temporary() {
+                        e = e.set_temporary();
+                    }
+                    (path.clone(), Err(e))
+                })),
+            }
+        }
+
+        Ok(RpBatch::new(BatchedResults::Delete(results)))
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        // GCS pages versions with a single page token, which is carried
+        // by key marker.
+        let resp = self
+            .gcs_list_object_versions(path, args.key_marker())
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let output: ListVersionsResponse =
+            serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+        let mut versions = Vec::with_capacity(output.items.len());
+        for item in output.items {
+            // Noncurrent versions will carry the time they are deleted.
+            let mut version =
+                ObjectVersion::new(&build_rel_path(&self.root, &item.name), &item.generation)
+                    .with_is_latest(item.time_deleted.is_empty());
+            if let Some(v) = parse_datetime_from_rfc3339(&item.updated) {
+                version = version.with_last_modified(v);
+            }
+            if let Ok(size) = item.size.parse::<u64>() {
+                version = version.with_content_length(size);
+            }
+            if !item.etag.is_empty() {
+                version = version.with_etag(&item.etag);
+            }
+            versions.push(version);
+        }
+        // GCS returns versions from oldest to newest, generations are
+        // increasing numbers.
+        versions.sort_by(|a, b| {
+            a.path().cmp(b.path()).then_with(|| {
+                let generation = |v: &ObjectVersion| v.version_id().parse::<u64>().ok();
+                generation(b).cmp(&generation(a))
+            })
+        });
+
+        let rp = RpListVersions::new(versions);
+        Ok(match output.next_page_token {
+            Some(token) if !token.is_empty() => rp.with_next_marker(&token, ""),
+            _ => rp,
+        })
+    }
+
+    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        // Large objects may not be rewritten in one call, we need to keep
+        // calling with the returned token until it's done.
+        let mut token: Option<String> = None;
+
+        loop {
+            let resp = self.gcs_rewrite_object(from, to, token.as_deref()).await?;
+
+            if !resp.status().is_success() {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let result: RewriteObjectJsonResponse =
+                serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+            if result.done {
+                return Ok(RpCopy::default());
+            }
+            if result.rewrite_token.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "rewrite is not done but no rewrite token returned",
+                ));
+            }
+            token = Some(result.rewrite_token);
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         Ok((
             RpList::default(),
-            Box::new(DirStream::new(Arc::new(self.clone()), &self.root, path)),
+            Box::new(DirStream::new(
+                Arc::new(self.clone()),
+                &self.root,
+                path,
+                if args.recursive() { "" } else { "/" },
+                args.limit(),
+                args.start_after(),
+            )),
         ))
     }
+
+    fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let signer = self.presign_signer.as_ref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unsupported,
+                "presign requires static credentials, please set hmac_access_key_id and hmac_secret",
+            )
+            .with_operation(Operation::Presign.into_static())
+        })?;
+        if args.expire() > PRESIGN_MAX_EXPIRE {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "presign expire is longer than the maximum 7 days allowed by V4 signing",
+            )
+            .with_operation(Operation::Presign.into_static())
+            .with_context("expire", args.expire().to_string()));
+        }
+
+        // We will not send this request out, just for signing.
+        let url = self.gcs_xml_object_url(path);
+        let mut req = match args.operation() {
+            PresignOperation::Stat(_) => Request::head(&url)
+                .body(AsyncBody::Empty)
+                .map_err(new_request_build_error)?,
+            PresignOperation::Read(v) => {
+                let mut req = Request::get(&url);
+                if let Some(range_header) = v.range().to_header() {
+                    req = req.header(http::header::RANGE, range_header);
+                }
+                req.body(AsyncBody::Empty)
+                    .map_err(new_request_build_error)?
+            }
+            // Content length and type will be part of the signed headers.
+            PresignOperation::Write(v) => {
+                let mut req = Request::put(&url);
+                if v.size() > 0 {
+                    req = req.header(CONTENT_LENGTH, v.size());
+                }
+                if let Some(mime) = v.content_type() {
+                    req = req.header(CONTENT_TYPE, mime);
+                }
+                req.body(AsyncBody::Empty)
+                    .map_err(new_request_build_error)?
+            }
+            PresignOperation::WriteMultipart(v) => self.gcs_upload_part_request(
+                path,
+                v.upload_id(),
+                v.part_number(),
+                None,
+                AsyncBody::Empty,
+            )?,
+        };
+
+        signer
+            .sign_query(&mut req, args.expire())
+            .map_err(new_request_sign_error)?;
+
+        // We don't need this request anymore, consume it directly.
+        let (parts, _) = req.into_parts();
+
+        Ok(RpPresign::new(PresignedRequest::new(
+            parts.method,
+            parts.uri,
+            parts.headers,
+        )))
+    }
+
+    async fn create_multipart(
+        &self,
+        path: &str,
+        _: OpCreateMultipart,
+    ) -> Result<RpCreateMultipart> {
+        let resp = self.gcs_initiate_multipart_upload(path).await?;
+
+        if !resp.status().is_success() {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let result: InitiateMultipartUploadResult =
+            quick_xml::de::from_reader(bs.reader()).map_err(parse_xml_deserialize_error)?;
+
+        Ok(RpCreateMultipart::new(&result.upload_id))
+    }
+
+    async fn write_multipart(
+        &self,
+        path: &str,
+        args: OpWriteMultipart,
+        r: BytesReader,
+    ) -> Result<RpWriteMultipart> {
+        let resp = self
+            .gcs_upload_part(
+                path,
+                args.upload_id(),
+                args.part_number(),
+                args.size(),
+                AsyncBody::Reader(r),
+            )
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(parse_error(resp).await?);
+        }
+
+        let etag = parse_etag(resp.headers())?.ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "ETag not present in returning response",
+            )
+        })?;
+        resp.into_body().consume().await?;
+
+        Ok(RpWriteMultipart::new(args.part_number(), &etag))
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        let resp = self
+            .gcs_complete_multipart_upload(path, args.upload_id(), args.parts())
+            .await?;
+
+        if resp.status().is_success() {
+            resp.into_body().consume().await?;
+            Ok(RpCompleteMultipart::default())
+        } else {
+            Err(parse_error(resp).await?)
+        }
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        let resp = self
+            .gcs_abort_multipart_upload(path, args.upload_id())
+            .await?;
+
+        if resp.status().is_success() {
+            resp.into_body().consume().await?;
+            Ok(RpAbortMultipart::default())
+        } else {
+            Err(parse_error(resp).await?)
+        }
+    }
 }
 
 impl Backend {
-    fn gcs_get_object_request(&self, path: &str, range: BytesRange) -> Result<Request<AsyncBody>> {
+    /// Sign the request unless anonymous access is allowed.
+    fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        if self.allow_anonymous {
+            return Ok(());
+        }
+
+        self.signer.sign(req).map_err(new_request_sign_error)
+    }
+
+    fn gcs_get_object_request(&self, path: &str, args: &OpRead) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
+        let mut url = format!(
             "{}/storage/v1/b/{}/o/{}?alt=media",
             self.endpoint,
             self.bucket,
             percent_encode_path(&p)
         );
+        if let Some(generation) = args.version() {
+            write!(url, "&generation={}", percent_encode_path(generation))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::get(&url);
 
-        if !range.is_full() {
-            req = req.header(http::header::RANGE, range.to_header());
+        let range = args.range();
+        if let Some(range_header) = range.to_header() {
+            req = req.header(http::header::RANGE, range_header);
+        }
+
+        if let Some(if_match) = args.if_match() {
+            req = req.header(http::header::IF_MATCH, if_match);
+        }
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(http::header::IF_NONE_MATCH, if_none_match);
         }
 
         let req = req
@@ -403,11 +889,11 @@ impl Backend {
     async fn gcs_get_object(
         &self,
         path: &str,
-        range: BytesRange,
+        args: &OpRead,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.gcs_get_object_request(path, range)?;
+        let mut req = self.gcs_get_object_request(path, args)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         self.client.send_async(req).await
     }
@@ -417,16 +903,20 @@ impl Backend {
         path: &str,
         size: Option<u64>,
         content_type: Option<&str>,
+        if_generation_match: Option<u64>,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
+        let mut url = format!(
             "{}/upload/storage/v1/b/{}/o?uploadType=media&name={}",
             self.endpoint,
             self.bucket,
             percent_encode_path(&p)
         );
+        if let Some(generation) = if_generation_match {
+            write!(url, "&ifGenerationMatch={generation}").expect("write into string must succeed");
+        }
 
         let mut req = Request::post(&url);
 
@@ -444,15 +934,81 @@ impl Backend {
         Ok(req)
     }
 
-    async fn gcs_get_object_metadata(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+    /// Build a multipart upload request which carries object metadata
+    /// alongside the content.
+    ///
+    /// Refer to [Perform a multipart upload](https://cloud.google.com/storage/docs/uploading-objects#uploading-an-object)
+    /// for more information.
+    fn gcs_insert_object_multipart_request(
+        &self,
+        path: &str,
+        args: &OpWrite,
+        if_generation_match: Option<u64>,
+        r: BytesReader,
+    ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
+        let mut url = format!(
+            "{}/upload/storage/v1/b/{}/o?uploadType=multipart",
+            self.endpoint, self.bucket,
+        );
+        if let Some(generation) = if_generation_match {
+            write!(url, "&ifGenerationMatch={generation}").expect("write into string must succeed");
+        }
+
+        let metadata = serde_json::to_string(&InsertObjectMetadata {
+            name: &p,
+            content_type: args.content_type(),
+            content_disposition: args.content_disposition(),
+            cache_control: args.cache_control(),
+        })
+        .map_err(parse_json_serialize_error)?;
+
+        let boundary = format!("opendal-{}", Uuid::new_v4());
+        let head = format!(
+            "--{boundary}\r\n\
+             Content-Type: application/json; charset=UTF-8\r\n\r\n\
+             {metadata}\r\n\
+             --{boundary}\r\n\
+             Content-Type: {}\r\n\r\n",
+            args.content_type().unwrap_or("application/octet-stream")
+        );
+        let tail = format!("\r\n--{boundary}--\r\n");
+        let length = head.len() as u64 + args.size() + tail.len() as u64;
+
+        let body = futures::io::Cursor::new(head.into_bytes())
+            .chain(r)
+            .chain(futures::io::Cursor::new(tail.into_bytes()));
+
+        let req = Request::post(&url)
+            .header(CONTENT_LENGTH, length)
+            .header(
+                CONTENT_TYPE,
+                format!("multipart/related; boundary={boundary}"),
+            )
+            .body(AsyncBody::Reader(Box::new(body)))
+            .map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    async fn gcs_get_object_metadata(
+        &self,
+        path: &str,
+        generation: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
             "{}/storage/v1/b/{}/o/{}",
             self.endpoint,
             self.bucket,
             percent_encode_path(&p)
         );
+        if let Some(generation) = generation {
+            write!(url, "?generation={}", percent_encode_path(generation))
+                .expect("write into string must succeed");
+        }
 
         let req = Request::get(&url);
 
@@ -460,26 +1016,304 @@ impl Backend {
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         self.client.send_async(req).await
     }
 
-    async fn gcs_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+    async fn gcs_delete_object(
+        &self,
+        path: &str,
+        generation: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
+        let mut url = format!(
             "{}/storage/v1/b/{}/o/{}",
             self.endpoint,
             self.bucket,
             percent_encode_path(&p)
         );
+        if let Some(generation) = generation {
+            write!(url, "?generation={}", percent_encode_path(generation))
+                .expect("write into string must succeed");
+        }
+
+        let mut req = Request::delete(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    /// Delete objects via a batch request, results are returned in the
+    /// same order as input.
+    async fn gcs_batch_delete(
+        &self,
+        ops: &[(String, OpDelete)],
+    ) -> Result<Vec<(String, Result<RpDelete>)>> {
+        let calls = ops
+            .iter()
+            .map(|(path, args)| {
+                let p = build_abs_path(&self.root, path);
+                let mut uri = format!(
+                    "/storage/v1/b/{}/o/{}",
+                    self.bucket,
+                    percent_encode_path(&p)
+                );
+                if let Some(generation) = args.version() {
+                    write!(uri, "?generation={}", percent_encode_path(generation))
+                        .expect("write into string must succeed");
+                }
+                ("DELETE", uri)
+            })
+            .collect::<Vec<_>>();
+
+        let boundary = format!("opendal-{}", Uuid::new_v4());
+        let body = build_batch_body(&boundary, &calls);
+
+        let url = format!("{}/batch/storage/v1", self.endpoint);
+        let mut req = Request::post(&url)
+            .header(CONTENT_LENGTH, body.len())
+            .header(
+                CONTENT_TYPE,
+                format!("multipart/mixed; boundary={boundary}"),
+            )
+            .body(AsyncBody::Bytes(Bytes::from(body)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        let resp = self.client.send_async(req).await?;
+        if !resp.status().is_success() {
+            return Err(parse_error(resp).await?);
+        }
+
+        let content_type = parse_content_type(resp.headers())?
+            .unwrap_or_default()
+            .to_string();
+        let bs = resp.into_body().bytes().await?;
+        let mut resps = parse_batch_response(&content_type, &bs)?
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        let mut results = Vec::with_capacity(ops.len());
+        for (idx, (path, _)) in ops.iter().enumerate() {
+            let res = match resps.remove(&idx) {
+                // deleting not existing objects is ok
+                Some(resp)
+                    if resp.status().is_success() || resp.status() == StatusCode::NOT_FOUND =>
+                {
+                    Ok(RpDelete::default())
+                }
+                Some(resp) => {
+                    let (parts, body) = resp.into_parts();
+                    let resp = Response::from_parts(
+                        parts,
+                        IncomingAsyncBody::new(Box::new(futures::io::Cursor::new(body))),
+                    );
+                    Err(parse_error(resp)
+                        .await?
+                        .with_operation(Operation::Delete.into_static())
+                        .with_context("path", path))
+                }
+                None => Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "response of batch delete is missing",
+                )
+                .with_operation(Operation::Delete.into_static())
+                .with_context("path", path)),
+            };
+            results.push((path.clone(), res));
+        }
+
+        Ok(results)
+    }
+
+    async fn gcs_rewrite_object(
+        &self,
+        from: &str,
+        to: &str,
+        rewrite_token: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let source = build_abs_path(&self.root, from);
+        let target = build_abs_path(&self.root, to);
+
+        let mut url = format!(
+            "{}/storage/v1/b/{}/o/{}/rewriteTo/b/{}/o/{}",
+            self.endpoint,
+            self.bucket,
+            percent_encode_path(&source),
+            self.bucket,
+            percent_encode_path(&target)
+        );
+        if let Some(token) = rewrite_token {
+            write!(url, "?rewriteToken={}", percent_encode_path(token))
+                .expect("write into string must succeed");
+        }
+
+        // Make sure content length has been set to avoid post with chunked encoding.
+        let mut req = Request::post(&url)
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    /// Multipart upload is only available in the XML API, object path
+    /// should keep `/` as is there.
+    fn gcs_xml_object_url(&self, path: &str) -> String {
+        let p = build_abs_path(&self.root, path);
+
+        format!(
+            "{}/{}/{}",
+            self.endpoint,
+            self.bucket,
+            percent_encode_xml_path(&p)
+        )
+    }
+
+    async fn gcs_initiate_multipart_upload(
+        &self,
+        path: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{}?uploads", self.gcs_xml_object_url(path));
+
+        // Make sure content length has been set to avoid post with chunked encoding.
+        let mut req = Request::post(&url)
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    fn gcs_upload_part_request(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        size: Option<u64>,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        let url = format!(
+            "{}?partNumber={}&uploadId={}",
+            self.gcs_xml_object_url(path),
+            part_number,
+            percent_encode_path(upload_id)
+        );
+
+        let mut req = Request::put(&url);
+
+        if let Some(size) = size {
+            req = req.header(CONTENT_LENGTH, size);
+        }
+
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    async fn gcs_upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req =
+            self.gcs_upload_part_request(path, upload_id, part_number, Some(size), body)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn gcs_complete_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: &[ObjectPart],
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}?uploadId={}",
+            self.gcs_xml_object_url(path),
+            percent_encode_path(upload_id)
+        );
+
+        let content = quick_xml::se::to_string(&CompleteMultipartUploadRequest {
+            part: parts
+                .iter()
+                .map(|v| CompleteMultipartUploadRequestPart {
+                    part_number: v.part_number(),
+                    etag: v.etag().to_string(),
+                })
+                .collect(),
+        })
+        .map_err(parse_xml_deserialize_error)?;
+
+        let mut req = Request::post(&url)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/xml")
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn gcs_abort_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!(
+            "{}?uploadId={}",
+            self.gcs_xml_object_url(path),
+            percent_encode_path(upload_id)
+        );
 
         let mut req = Request::delete(&url)
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn gcs_list_object_versions(
+        &self,
+        path: &str,
+        page_token: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}/storage/v1/b/{}/o?prefix={}&versions=true",
+            self.endpoint,
+            self.bucket,
+            percent_encode_path(&p)
+        );
+        if let Some(page_token) = page_token {
+            write!(url, "&pageToken={}", percent_encode_path(page_token))
+                .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
 
         self.client.send_async(req).await
     }
@@ -487,16 +1321,26 @@ impl Backend {
     pub(crate) async fn gcs_list_objects(
         &self,
         path: &str,
+        delimiter: &str,
         page_token: &str,
+        limit: Option<usize>,
+        start_after: Option<&str>,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let mut url = format!(
-            "{}/storage/v1/b/{}/o?delimiter=/&prefix={}",
+            "{}/storage/v1/b/{}/o?prefix={}",
             self.endpoint,
             self.bucket,
             percent_encode_path(&p)
         );
+        // Empty delimiter means list all objects under prefix recursively.
+        if !delimiter.is_empty() {
+            write!(url, "&delimiter={delimiter}").expect("write into string must succeed");
+        }
+        if let Some(limit) = limit {
+            write!(url, "&maxResults={limit}").expect("write into string must succeed");
+        }
         if !page_token.is_empty() {
             // NOTE:
             //
@@ -507,12 +1351,22 @@ impl Backend {
             write!(url, "&pageToken={}", percent_encode_path(page_token))
                 .expect("write into string must succeed");
         }
+        if let Some(start_after) = start_after {
+            // `startOffset` is inclusive, the entry itself will be filtered
+            // out while listing.
+            write!(
+                url,
+                "&startOffset={}",
+                percent_encode_path(&build_abs_path(&self.root, start_after))
+            )
+            .expect("write into string must succeed");
+        }
 
         let mut req = Request::get(&url)
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         self.client.send_async(req).await
     }
@@ -530,6 +1384,10 @@ struct GetObjectJsonResponse {
     ///
     /// For example: `"etag": "CKWasoTgyPkCEAE="`
     etag: String,
+    /// Generation of this object, used as version id.
+    ///
+    /// For example: `"generation": "1660563214863653"`
+    generation: String,
     /// RFC3339 styled datetime string.
     ///
     /// For example: `"updated": "2022-08-15T11:33:34.866Z"`
@@ -542,12 +1400,116 @@ struct GetObjectJsonResponse {
     ///
     /// For examlpe: `"contentType": "image/png",`
     content_type: String,
+    /// Content disposition of this object.
+    ///
+    /// For example: `"contentDisposition": "attachment; filename=\"a.png\""`
+    content_disposition: String,
+    /// Cache control of this object.
+    ///
+    /// For example: `"cacheControl": "public, max-age=3600"`
+    cache_control: String,
+}
+
+/// Response of list objects with `versions=true`, only the fields we need
+/// are listed.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ListVersionsResponse {
+    next_page_token: Option<String>,
+    items: Vec<ListVersionsResponseItem>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ListVersionsResponseItem {
+    name: String,
+    generation: String,
+    size: String,
+    etag: String,
+    updated: String,
+    /// Only noncurrent versions have this field.
+    time_deleted: String,
+}
+
+/// Metadata part of multipart upload request.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InsertObjectMetadata<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_type: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_disposition: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<&'a str>,
+}
+
+/// Response of rewrite object, only the fields we need are listed.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct RewriteObjectJsonResponse {
+    /// Whether the rewrite is done.
+    done: bool,
+    /// Token to continue the rewrite, only returned while not done.
+    rewrite_token: String,
+}
+
+/// Result of initiating multipart upload in XML API.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct InitiateMultipartUploadResult {
+    upload_id: String,
+}
+
+/// Request of completing multipart upload in XML API.
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
+struct CompleteMultipartUploadRequest {
+    part: Vec<CompleteMultipartUploadRequestPart>,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct CompleteMultipartUploadRequestPart {
+    #[serde(rename = "$unflatten=PartNumber")]
+    part_number: usize,
+    #[serde(rename = "$unflatten=ETag")]
+    etag: String,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_deserialize_rewrite_object_json_response() {
+        let content = r#"{
+  "kind": "storage#rewriteResponse",
+  "totalBytesRewritten": "1048576",
+  "objectSize": "10000000",
+  "done": false,
+  "rewriteToken": "TOKEN"
+}"#;
+
+        let resp: RewriteObjectJsonResponse =
+            serde_json::from_str(content).expect("json Deserialize must succeed");
+        assert!(!resp.done);
+        assert_eq!(resp.rewrite_token, "TOKEN");
+
+        let content = r#"{
+  "kind": "storage#rewriteResponse",
+  "totalBytesRewritten": "10000000",
+  "objectSize": "10000000",
+  "done": true,
+  "resource": {"name": "target"}
+}"#;
+
+        let resp: RewriteObjectJsonResponse =
+            serde_json::from_str(content).expect("json Deserialize must succeed");
+        assert!(resp.done);
+        assert!(resp.rewrite_token.is_empty());
+    }
+
     #[test]
     fn test_deserialize_get_object_json_response() {
         let content = r#"{
@@ -577,6 +1539,484 @@ mod tests {
         assert_eq!(meta.updated, "2022-08-15T11:33:34.866Z");
         assert_eq!(meta.md5_hash, "fHcEH1vPwA6eTPqxuasXcg==");
         assert_eq!(meta.etag, "CKWasoTgyPkCEAE=");
+        assert_eq!(meta.generation, "1660563214863653");
         assert_eq!(meta.content_type, "image/png");
     }
+
+    #[test]
+    fn test_serialize_insert_object_metadata() {
+        let metadata = InsertObjectMetadata {
+            name: "dir/file",
+            content_type: Some("text/plain"),
+            content_disposition: Some(r#"attachment; filename="hello.txt""#),
+            cache_control: Some("public, max-age=3600"),
+        };
+        assert_eq!(
+            serde_json::to_string(&metadata).expect("must succeed"),
+            r#"{"name":"dir/file","contentType":"text/plain","contentDisposition":"attachment; filename=\"hello.txt\"","cacheControl":"public, max-age=3600"}"#
+        );
+
+        let metadata = InsertObjectMetadata {
+            name: "file",
+            content_type: None,
+            content_disposition: None,
+            cache_control: Some("no-cache"),
+        };
+        assert_eq!(
+            serde_json::to_string(&metadata).expect("must succeed"),
+            r#"{"name":"file","cacheControl":"no-cache"}"#
+        );
+    }
+
+    /// This example is from https://cloud.google.com/storage/docs/xml-api/post-object-multipart
+    #[test]
+    fn test_deserialize_initiate_multipart_upload_result() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <InitiateMultipartUploadResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+              <Bucket>travel-maps</Bucket>
+              <Key>paris.jpg</Key>
+              <UploadId>VXBsb2FkIElEIGZvciBlbHZpbmcncyBteS1tb3ZpZS5tMnRzIHVwbG9hZA</UploadId>
+            </InitiateMultipartUploadResult>"#,
+        );
+
+        let out: InitiateMultipartUploadResult =
+            quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(
+            out.upload_id,
+            "VXBsb2FkIElEIGZvciBlbHZpbmcncyBteS1tb3ZpZS5tMnRzIHVwbG9hZA"
+        );
+    }
+
+    #[test]
+    fn test_serialize_complete_multipart_upload_request() {
+        let req = CompleteMultipartUploadRequest {
+            part: vec![CompleteMultipartUploadRequestPart {
+                part_number: 1,
+                etag: "7778aef83f66abc1fa1e8477f296d394".to_string(),
+            }],
+        };
+
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+
+        assert_eq!(
+            actual,
+            "<CompleteMultipartUpload>\
+             <Part><PartNumber>1</PartNumber><ETag>7778aef83f66abc1fa1e8477f296d394</ETag></Part>\
+             </CompleteMultipartUpload>"
+        );
+    }
+
+    #[test]
+    fn test_presign() {
+        let mut builder = Builder::default();
+        builder
+            .bucket("test")
+            .credential("")
+            .hmac_access_key_id("access_key_id")
+            .hmac_secret("secret");
+        let acc = builder.build().expect("build must succeed");
+        assert!(acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Presign));
+
+        let req = acc
+            .presign(
+                "file",
+                OpPresign::new(
+                    OpWrite::new(1024).with_content_type("image/png"),
+                    time::Duration::hours(1),
+                ),
+            )
+            .expect("presign must succeed")
+            .into_presigned_request();
+
+        assert_eq!(req.method(), http::Method::PUT);
+        assert_eq!(req.uri().path(), "/test/file");
+        assert_eq!(req.header()[CONTENT_TYPE], "image/png");
+        let query = req.uri().query().expect("query must exist");
+        assert!(query.contains("X-Amz-Signature="), "{query}");
+        assert!(
+            query.contains("X-Amz-SignedHeaders=content-length%3Bcontent-type%3Bhost"),
+            "{query}"
+        );
+    }
+
+    #[test]
+    fn test_presign_expire_too_long() {
+        let mut builder = Builder::default();
+        builder
+            .bucket("test")
+            .credential("")
+            .hmac_access_key_id("access_key_id")
+            .hmac_secret("secret");
+        let acc = builder.build().expect("build must succeed");
+
+        acc.presign(
+            "file",
+            OpPresign::new(OpStat::new(), time::Duration::days(7)),
+        )
+        .expect("presign with 7 days must succeed");
+
+        let err = acc
+            .presign(
+                "file",
+                OpPresign::new(OpStat::new(), time::Duration::days(8)),
+            )
+            .expect_err("presign must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_presign_without_hmac_key() {
+        let mut builder = Builder::default();
+        builder.bucket("test").credential("");
+        let acc = builder.build().expect("build must succeed");
+        assert!(!acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Presign));
+
+        let err = acc
+            .presign(
+                "file",
+                OpPresign::new(OpStat::new(), time::Duration::hours(1)),
+            )
+            .expect_err("presign must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_allow_anonymous() {
+        use futures::AsyncReadExt;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/b/test/o/file"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/upload/storage/v1/b/test/o"))
+            .respond_with(ResponseTemplate::new(403).set_body_string(
+                r#"{"error":{"code":403,"message":"Anonymous caller does not have storage.objects.create access."}}"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .allow_anonymous();
+        let acc = builder.build().expect("build must succeed");
+
+        let (_, mut r) = acc
+            .read("file", OpRead::new())
+            .await
+            .expect("read must succeed");
+        let mut bs = String::new();
+        r.read_to_string(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, "hello");
+
+        let err = acc
+            .write(
+                "file",
+                OpWrite::new(5),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectPermissionDenied);
+
+        let requests = mock_server
+            .received_requests()
+            .await
+            .expect("requests must be recorded");
+        assert_eq!(requests.len(), 2);
+        for req in requests {
+            assert!(
+                !req.headers.contains_key(&"authorization".into()),
+                "{} {} must not be signed",
+                req.method,
+                req.url
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_delete() {
+        use wiremock::matchers::body_string_contains;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let body = "--batch_abc\r\n\
+             Content-Type: application/http\r\n\
+             Content-ID: <response-0>\r\n\
+             \r\n\
+             HTTP/1.1 204 No Content\r\n\
+             \r\n\
+             \r\n\
+             --batch_abc\r\n\
+             Content-Type: application/http\r\n\
+             Content-ID: <response-1>\r\n\
+             \r\n\
+             HTTP/1.1 404 Not Found\r\n\
+             Content-Type: application/json\r\n\
+             \r\n\
+             {\"error\": {\"code\": 404, \"message\": \"No such object: test/b\"}}\r\n\
+             \r\n\
+             --batch_abc\r\n\
+             Content-Type: application/http\r\n\
+             Content-ID: <response-2>\r\n\
+             \r\n\
+             HTTP/1.1 403 Forbidden\r\n\
+             Content-Type: application/json\r\n\
+             \r\n\
+             {\"error\": {\"code\": 403, \"message\": \"Access denied.\"}}\r\n\
+             \r\n\
+             --batch_abc--\r\n";
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/batch/storage/v1"))
+            .and(body_string_contains(
+                "DELETE /storage/v1/b/test/o/a HTTP/1.1",
+            ))
+            .and(body_string_contains(
+                "DELETE /storage/v1/b/test/o/c?generation=1 HTTP/1.1",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                body.as_bytes().to_vec(),
+                "multipart/mixed; boundary=batch_abc",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .allow_anonymous();
+        let acc = builder.build().expect("build must succeed");
+        assert!(acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Batch));
+
+        let rp = acc
+            .batch(OpBatch::new(BatchOperations::Delete(vec![
+                ("a".to_string(), OpDelete::new()),
+                ("b".to_string(), OpDelete::new()),
+                ("c".to_string(), OpDelete::new().with_version("1")),
+            ])))
+            .await
+            .expect("batch must succeed");
+        let BatchedResults::Delete(results) = rp.into_results();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, "a");
+        assert!(results[0].1.is_ok());
+        // Deleting not existing objects is ok.
+        assert_eq!(results[1].0, "b");
+        assert!(results[1].1.is_ok());
+        assert_eq!(results[2].0, "c");
+        assert_eq!(
+            results[2].1.as_ref().expect_err("delete must fail").kind(),
+            ErrorKind::ObjectPermissionDenied
+        );
+    }
+
+    #[tokio::test]
+    async fn test_etag() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/upload/storage/v1/b/test/o"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(
+                    r#"{"name": "file", "size": "5", "etag": "CKWasoTgyPkCEAE="}"#,
+                ),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/b/test/o/file"))
+            .and(query_param("alt", "media"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"CKWasoTgyPkCEAE=\"")
+                    .set_body_string("hello"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/b/test/o/file"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"size": "5", "etag": "CKWasoTgyPkCEAE=", "updated": "2022-08-15T11:33:34.866Z"}"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/b/test/o/no_etag"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"size": "5", "updated": "invalid"}"#),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .allow_anonymous();
+        let acc = builder.build().expect("build must succeed");
+
+        let rp = acc
+            .write(
+                "file",
+                OpWrite::new(5),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+            .await
+            .expect("write must succeed");
+        assert_eq!(rp.etag(), Some("CKWasoTgyPkCEAE="));
+
+        let (rp, _) = acc
+            .read("file", OpRead::new())
+            .await
+            .expect("read must succeed");
+        assert_eq!(rp.into_metadata().etag(), Some("CKWasoTgyPkCEAE="));
+
+        let meta = acc
+            .stat("file", OpStat::new())
+            .await
+            .expect("stat must succeed")
+            .into_metadata();
+        assert_eq!(meta.etag(), Some("CKWasoTgyPkCEAE="));
+        assert_eq!(
+            meta.last_modified(),
+            parse_datetime_from_rfc3339("2022-08-15T11:33:34.866Z")
+        );
+
+        let meta = acc
+            .stat("no_etag", OpStat::new())
+            .await
+            .expect("stat must succeed")
+            .into_metadata();
+        assert_eq!(meta.etag(), None);
+        // Invalid datetime will be ignored.
+        assert_eq!(meta.last_modified(), None);
+    }
+
+    #[tokio::test]
+    async fn test_versions() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::matchers::query_param_is_missing;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/b/test/o"))
+            .and(query_param("versions", "true"))
+            .and(query_param("prefix", "file"))
+            .and(query_param_is_missing("pageToken"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+  "nextPageToken": "token",
+  "items": [
+    {"name": "file", "generation": "9", "size": "1", "updated": "2022-08-15T11:33:34.866Z", "timeDeleted": "2022-08-16T11:33:34.866Z"},
+    {"name": "file", "generation": "10", "size": "2", "etag": "CKWasoTgyPkCEAE=", "updated": "2022-08-16T11:33:34.866Z"}
+  ]
+}"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/storage/v1/b/test/o/file"))
+            .and(query_param("generation", "9"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .allow_anonymous();
+        let acc = builder.build().expect("build must succeed");
+        assert!(acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Versioning));
+
+        let rp = acc
+            .list_versions("file", OpListVersions::new())
+            .await
+            .expect("list must succeed");
+        assert_eq!(rp.next_key_marker(), Some("token"));
+        let versions: Vec<_> = rp
+            .versions()
+            .iter()
+            .map(|v| {
+                (
+                    v.path(),
+                    v.version_id(),
+                    v.is_latest(),
+                    v.is_delete_marker(),
+                    v.content_length(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("file", "10", true, false, Some(2)),
+                ("file", "9", false, false, Some(1)),
+            ]
+        );
+        assert_eq!(rp.versions()[0].etag(), Some("CKWasoTgyPkCEAE="));
+
+        acc.delete("file", OpDelete::new().with_version("9"))
+            .await
+            .expect("delete must succeed");
+    }
 }
@@ -0,0 +1,231 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for GCS [batch requests](https://cloud.google.com/storage/docs/batch).
+
+use std::fmt::Write;
+
+use bytes::Bytes;
+use http::header::HeaderName;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Response;
+use http::StatusCode;
+
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// GCS allows at most 100 calls in one batch request.
+pub(super) const BATCH_MAX_SIZE: usize = 100;
+
+/// Build the `multipart/mixed` body of a batch request.
+///
+/// `calls` are `(method, path_and_query)` of every call, their index will
+/// be used as the `Content-ID` so that responses can be matched back.
+pub(super) fn build_batch_body(boundary: &str, calls: &[(&str, String)]) -> String {
+    let mut body = String::new();
+    for (idx, (method, uri)) in calls.iter().enumerate() {
+        write!(
+            body,
+            "--{}\r\n\
+             Content-Type: application/http\r\n\
+             Content-ID: <{}>\r\n\
+             \r\n\
+             {} {} HTTP/1.1\r\n\
+             \r\n",
+            boundary, idx, method, uri
+        )
+        .expect("write into string must succeed");
+    }
+    write!(body, "--{}--\r\n", boundary).expect("write into string must succeed");
+    body
+}
+
+/// Parse the `multipart/mixed` body of a batch response.
+///
+/// Returns the index of call and its response. Calls that don't have a
+/// response will be absent.
+pub(super) fn parse_batch_response(
+    content_type: &str,
+    bs: &[u8],
+) -> Result<Vec<(usize, Response<Bytes>)>> {
+    let boundary = content_type
+        .split(';')
+        .filter_map(|v| v.trim().strip_prefix("boundary="))
+        .map(|v| v.trim_matches('"'))
+        .next()
+        .ok_or_else(|| {
+            new_batch_response_error("boundary is missing in content type")
+                .with_context("content_type", content_type)
+        })?;
+
+    let body = String::from_utf8_lossy(bs);
+    let delimiter = format!("--{}", boundary);
+
+    let mut responses = Vec::new();
+    // The first part is the preamble before the first delimiter.
+    for part in body.split(delimiter.as_str()).skip(1) {
+        // `--` after the delimiter marks the end of body.
+        if part.starts_with("--") {
+            break;
+        }
+
+        let (head, http) = split_head(part.trim_start())
+            .ok_or_else(|| new_batch_response_error("part header is invalid"))?;
+        let idx = head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(k, _)| k.trim().eq_ignore_ascii_case("content-id"))
+            .and_then(|(_, v)| {
+                v.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .strip_prefix("response-")
+                    .and_then(|v| v.parse::<usize>().ok())
+            })
+            .ok_or_else(|| new_batch_response_error("content id of part is invalid"))?;
+
+        responses.push((idx, parse_http_response(http)?));
+    }
+
+    Ok(responses)
+}
+
+/// Parse a http response embedded in a part like:
+///
+/// ```text
+/// HTTP/1.1 404 Not Found
+/// Content-Type: application/json
+///
+/// {"error": {"code": 404, "message": "No such object"}}
+/// ```
+fn parse_http_response(s: &str) -> Result<Response<Bytes>> {
+    let (head, body) = split_head(s).unwrap_or((s, ""));
+    let mut lines = head.lines();
+
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|v| v.parse::<u16>().ok())
+        .and_then(|v| StatusCode::from_u16(v).ok())
+        .ok_or_else(|| new_batch_response_error("status line of part is invalid"))?;
+
+    let mut headers = HeaderMap::new();
+    for (k, v) in lines.filter_map(|line| line.split_once(':')) {
+        // Invalid headers are not used by us, it's safe to skip them.
+        if let (Ok(k), Ok(v)) = (
+            HeaderName::from_bytes(k.trim().as_bytes()),
+            HeaderValue::from_str(v.trim()),
+        ) {
+            headers.append(k, v);
+        }
+    }
+
+    let mut resp = Response::new(Bytes::from(body.trim_end().to_string()));
+    *resp.status_mut() = status;
+    *resp.headers_mut() = headers;
+    Ok(resp)
+}
+
+/// Split header and body by the first empty line.
+fn split_head(s: &str) -> Option<(&str, &str)> {
+    s.split_once("\r\n\r\n").or_else(|| s.split_once("\n\n"))
+}
+
+fn new_batch_response_error(msg: &'static str) -> Error {
+    Error::new(ErrorKind::Unexpected, msg).with_operation("gcs::parse_batch_response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_batch_body() {
+        let body = build_batch_body(
+            "opendal",
+            &[
+                ("DELETE", "/storage/v1/b/test/o/a".to_string()),
+                ("DELETE", "/storage/v1/b/test/o/b?generation=1".to_string()),
+            ],
+        );
+
+        assert_eq!(
+            body,
+            "--opendal\r\n\
+             Content-Type: application/http\r\n\
+             Content-ID: <0>\r\n\
+             \r\n\
+             DELETE /storage/v1/b/test/o/a HTTP/1.1\r\n\
+             \r\n\
+             --opendal\r\n\
+             Content-Type: application/http\r\n\
+             Content-ID: <1>\r\n\
+             \r\n\
+             DELETE /storage/v1/b/test/o/b?generation=1 HTTP/1.1\r\n\
+             \r\n\
+             --opendal--\r\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_batch_response() {
+        let body = "--batch_abc\r\n\
+             Content-Type: application/http\r\n\
+             Content-ID: <response-1>\r\n\
+             \r\n\
+             HTTP/1.1 404 Not Found\r\n\
+             Content-Type: application/json; charset=UTF-8\r\n\
+             \r\n\
+             {\"error\": {\"code\": 404, \"message\": \"No such object: test/b\"}}\r\n\
+             \r\n\
+             --batch_abc\r\n\
+             Content-Type: application/http\r\n\
+             Content-ID: <response-0>\r\n\
+             \r\n\
+             HTTP/1.1 204 No Content\r\n\
+             Content-Length: 0\r\n\
+             \r\n\
+             \r\n\
+             --batch_abc--\r\n";
+
+        let resps = parse_batch_response("multipart/mixed; boundary=batch_abc", body.as_bytes())
+            .expect("parse must succeed");
+        assert_eq!(resps.len(), 2);
+
+        let (idx, resp) = &resps[0];
+        assert_eq!(*idx, 1);
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "application/json; charset=UTF-8"
+        );
+        assert_eq!(
+            resp.body().as_ref(),
+            b"{\"error\": {\"code\": 404, \"message\": \"No such object: test/b\"}}"
+        );
+
+        let (idx, resp) = &resps[1];
+        assert_eq!(*idx, 0);
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(resp.body().is_empty());
+    }
+
+    #[test]
+    fn test_parse_batch_response_without_boundary() {
+        let err = parse_batch_response("multipart/mixed", b"").expect_err("parse must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+}
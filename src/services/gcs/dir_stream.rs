@@ -17,8 +17,6 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json;
-use time::format_description::well_known::Rfc3339;
-use time::OffsetDateTime;
 
 use super::backend::Backend;
 use super::error::parse_error;
@@ -32,6 +30,9 @@ pub struct DirStream {
     backend: Arc<Backend>,
     root: String,
     path: String,
+    delimiter: &'static str,
+    limit: Option<usize>,
+    start_after: Option<String>,
     page_token: String,
 
     done: bool,
@@ -39,11 +40,21 @@ pub struct DirStream {
 
 impl DirStream {
     /// Generate a new directory walker
-    pub fn new(backend: Arc<Backend>, root: &str, path: &str) -> Self {
+    pub fn new(
+        backend: Arc<Backend>,
+        root: &str,
+        path: &str,
+        delimiter: &'static str,
+        limit: Option<usize>,
+        start_after: Option<&str>,
+    ) -> Self {
         Self {
             backend,
             root: root.to_string(),
             path: path.to_string(),
+            delimiter,
+            limit,
+            start_after: start_after.map(|v| v.to_string()),
             page_token: "".to_string(),
 
             done: false,
@@ -60,7 +71,13 @@ impl ObjectPage for DirStream {
 
         let resp = self
             .backend
-            .gcs_list_objects(&self.path, &self.page_token)
+            .gcs_list_objects(
+                &self.path,
+                self.delimiter,
+                &self.page_token,
+                self.limit,
+                self.start_after.as_deref(),
+            )
             .await?;
 
         if !resp.status().is_success() {
@@ -97,7 +114,9 @@ impl ObjectPage for DirStream {
 
             // set metadata fields
             meta.set_content_md5(object.md5_hash.as_str());
-            meta.set_etag(object.etag.as_str());
+            if !object.etag.is_empty() {
+                meta.set_etag(object.etag.as_str());
+            }
 
             let size = object.size.parse().map_err(|e| {
                 Error::new(ErrorKind::Unexpected, "parse u64 from list response").set_source(e)
@@ -107,10 +126,9 @@ impl ObjectPage for DirStream {
                 meta.set_content_type(&object.content_type);
             }
 
-            let dt = OffsetDateTime::parse(object.updated.as_str(), &Rfc3339).map_err(|e| {
-                Error::new(ErrorKind::Unexpected, "parse last modified as rfc3339").set_source(e)
-            })?;
-            meta.set_last_modified(dt);
+            if let Some(dt) = parse_datetime_from_rfc3339(&object.updated) {
+                meta.set_last_modified(dt);
+            }
             meta.set_complete();
 
             let de = ObjectEntry::new(&build_rel_path(&self.root, &object.name), meta);
@@ -118,6 +136,14 @@ impl ObjectPage for DirStream {
             entries.push(de);
         }
 
+        // Keep entries in lexicographic order, so that the path of last
+        // returned entry can be used as `start_after` to resume listing.
+        entries.sort_by(|a, b| a.path().cmp(b.path()));
+        // `startOffset` is inclusive, skip the `start_after` itself.
+        if let Some(start_after) = &self.start_after {
+            entries.retain(|v| v.path() != start_after);
+        }
+
         Ok(Some(entries))
     }
 }
@@ -49,24 +49,46 @@ struct GcsErrorDetail {
 /// Parse error respons into Error.
 pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (parts, body) = resp.into_parts();
-    let bs = body.bytes().await?;
+    let (bs, truncated) = body.bytes_with_limit(ERROR_BODY_LIMIT).await?;
+
+    let gcs_err = de::from_slice::<GcsErrorResponse>(&bs).ok();
 
     let (kind, retryable) = match parts.status {
+        // GCS returns `403` with reason `userRateLimitExceeded` while
+        // throttling, they should be retried instead of permission denied.
+        _ if gcs_err.as_ref().map_or(false, is_rate_limited) => (ErrorKind::Unexpected, true),
         StatusCode::NOT_FOUND => (ErrorKind::ObjectNotFound, false),
         StatusCode::FORBIDDEN => (ErrorKind::ObjectPermissionDenied, false),
-        StatusCode::INTERNAL_SERVER_ERROR
+        StatusCode::NOT_MODIFIED | StatusCode::PRECONDITION_FAILED => {
+            (ErrorKind::ConditionNotMatch, false)
+        }
+        StatusCode::TOO_MANY_REQUESTS
+        | StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
         | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
         _ => (ErrorKind::Unexpected, false),
     };
 
-    let message = match de::from_slice::<GcsErrorResponse>(&bs) {
-        Ok(gcs_err) => format!("{:?}", gcs_err),
-        Err(_) => String::from_utf8_lossy(&bs).into_owned(),
+    let message = match &gcs_err {
+        Some(gcs_err) => gcs_err.error.message.clone(),
+        None if truncated => format!(
+            "{}... (truncated at {} bytes)",
+            String::from_utf8_lossy(&bs),
+            bs.len()
+        ),
+        None => String::from_utf8_lossy(&bs).into_owned(),
     };
 
     let mut err = Error::new(kind, &message).with_context("response", format!("{:?}", parts));
+    err = with_current_metadata(err, &parts.headers);
+
+    if let Some(gcs_err) = gcs_err {
+        err = err.with_context("gcs_code", gcs_err.error.code.to_string());
+        if let Some(detail) = gcs_err.error.errors.first() {
+            err = err.with_context("gcs_reason", &detail.reason);
+        }
+    }
 
     if retryable {
         err = err.set_temporary();
@@ -75,12 +97,34 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     Ok(err)
 }
 
+/// Check whether the error is caused by rate limits.
+///
+/// Reasons are documented at <https://cloud.google.com/storage/docs/json_api/v1/status-codes>.
+fn is_rate_limited(gcs_err: &GcsErrorResponse) -> bool {
+    gcs_err.error.errors.iter().any(|v| {
+        matches!(
+            v.reason.as_str(),
+            "rateLimitExceeded" | "userRateLimitExceeded"
+        )
+    })
+}
+
 pub fn parse_json_deserialize_error(e: serde_json::Error) -> Error {
     Error::new(ErrorKind::Unexpected, "deserialize json").set_source(e)
 }
 
+pub fn parse_json_serialize_error(e: serde_json::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "serialize json").set_source(e)
+}
+
+pub fn parse_xml_deserialize_error(e: quick_xml::DeError) -> Error {
+    Error::new(ErrorKind::Unexpected, "deserialize xml").set_source(e)
+}
+
 #[cfg(test)]
 mod tests {
+    use futures::io::Cursor;
+
     use super::*;
 
     #[test]
@@ -116,4 +160,79 @@ mod tests {
         assert_eq!(out.error.errors[0].location_type, "header");
         assert_eq!(out.error.errors[0].location, "Authorization");
     }
+
+    #[tokio::test]
+    async fn test_parse_error_rate_limited() {
+        for (status, body) in [
+            (
+                StatusCode::FORBIDDEN,
+                r#"{"error": {"errors": [{"domain": "usageLimits", "reason": "userRateLimitExceeded", "message": "The user has exceeded their rate limit"}], "code": 403, "message": "The user has exceeded their rate limit"}}"#,
+            ),
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                r#"{"error": {"errors": [{"domain": "usageLimits", "reason": "rateLimitExceeded", "message": "Rate limit exceeded"}], "code": 429, "message": "Rate limit exceeded"}}"#,
+            ),
+            (StatusCode::TOO_MANY_REQUESTS, ""),
+        ] {
+            let resp = Response::builder()
+                .status(status)
+                .body(IncomingAsyncBody::new(Box::new(Cursor::new(
+                    body.as_bytes().to_vec(),
+                ))))
+                .expect("must success");
+
+            let err = parse_error(resp).await.expect("must success");
+            assert_eq!(err.kind(), ErrorKind::Unexpected, "{status}");
+            assert!(err.is_temporary(), "{status}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_truncated() {
+        let bs = vec![b'x'; 2 * ERROR_BODY_LIMIT];
+        let body = IncomingAsyncBody::new(Box::new(Cursor::new(bs)));
+        let resp = Response::builder()
+            .status(StatusCode::BAD_GATEWAY)
+            .body(body)
+            .expect("response must be valid");
+
+        let err = parse_error(resp).await.expect("parse must succeed");
+        assert!(err.is_temporary());
+        let s = err.to_string();
+        assert!(
+            s.ends_with(&format!("... (truncated at {ERROR_BODY_LIMIT} bytes)")),
+            "{s}"
+        );
+        assert!(s.len() < 2 * ERROR_BODY_LIMIT, "{}", s.len());
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_with_context() {
+        let bs = r#"{"error": {"errors": [{"domain": "global", "reason": "required", "message": "Login Required"}], "code": 401, "message": "Login Required"}}"#;
+        let body = IncomingAsyncBody::new(Box::new(Cursor::new(bs.as_bytes())));
+        let resp = Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(body)
+            .expect("response must be valid");
+
+        let err = parse_error(resp).await.expect("parse must succeed");
+        let s = err.to_string();
+        assert!(s.contains("gcs_code: 401"), "{s}");
+        assert!(s.contains("gcs_reason: required"), "{s}");
+        assert!(s.ends_with("=> Login Required"), "{s}");
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_precondition_failed() {
+        let bs = r#"{"error": {"errors": [{"domain": "global", "reason": "conditionNotMet", "message": "Precondition Failed"}], "code": 412, "message": "Precondition Failed"}}"#;
+        let body = IncomingAsyncBody::new(Box::new(Cursor::new(bs.as_bytes())));
+        let resp = Response::builder()
+            .status(StatusCode::PRECONDITION_FAILED)
+            .body(body)
+            .expect("response must be valid");
+
+        let err = parse_error(resp).await.expect("parse must succeed");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        assert!(!err.is_temporary());
+    }
 }
@@ -20,6 +20,11 @@
 //! - `bucket`: Set the container name for backend
 //! - `endpoint`: Customizable endpoint setting
 //! - `credentials`: Credential string for GCS OAuth2
+//! - `credential_path`: Path to the credential file for GCS OAuth2
+//! - `scope`: Scope for GCS OAuth2
+//! - `service_account`: Service account used to fetch token from vm metadata
+//! - `hmac_access_key_id` / `hmac_secret`: HMAC key used to presign requests
+//! - `allow_anonymous`: Send requests without signing, used to access public buckets.
 //!
 //! You can refer to [`Builder`]'s docs for more information
 //!
@@ -29,6 +34,18 @@
 //! - `OPENDAL_GCS_BUCKET`  required
 //! - `OPENDAL_GCS_ROOT`    optional
 //! - `OPENDAL_GCS_CREDENTIAL`  required
+//! - `OPENDAL_GCS_CREDENTIAL_PATH`  optional
+//! - `OPENDAL_GCS_SCOPE`  optional
+//! - `OPENDAL_GCS_SERVICE_ACCOUNT`  optional
+//! - `OPENDAL_GCS_HMAC_ACCESS_KEY_ID`  optional
+//! - `OPENDAL_GCS_HMAC_SECRET`  optional
+//! - `OPENDAL_GCS_ALLOW_ANONYMOUS`  optional
+//!
+//! # Batch
+//!
+//! [`BatchOperator::remove`](crate::BatchOperator::remove) deletes objects via GCS's
+//! [batch API](https://cloud.google.com/storage/docs/batch). GCS allows at most 100
+//! calls in one batch request, larger batches will be split and sent one by one.
 //!
 //! # Example
 //!
@@ -89,6 +106,7 @@
 mod backend;
 pub use backend::Builder;
 
+mod batch;
 mod dir_stream;
-mod error;
+pub(super) mod error;
 mod uri;
@@ -38,6 +38,8 @@ pub struct Builder {
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &["root", "name_node"];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
 
@@ -88,10 +90,9 @@ impl Builder {
         let name_node = match &self.name_node {
             Some(v) => v,
             None => {
-                return Err(
-                    Error::new(ErrorKind::BackendConfigInvalid, "name node is empty")
-                        .with_context("service", Scheme::Hdfs),
-                )
+                return Err(Error::new(ErrorKind::ConfigInvalid, "name node is empty")
+                    .with_context("field", "name_node")
+                    .with_context("service", Scheme::Hdfs))
             }
         };
 
@@ -135,7 +136,10 @@ impl Accessor for Backend {
         am.set_scheme(Scheme::Hdfs)
             .set_root(&self.root)
             .set_capabilities(
-                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::List,
+                AccessorCapability::Read
+                    | AccessorCapability::Write
+                    | AccessorCapability::List
+                    | AccessorCapability::Append,
             );
 
         am
@@ -216,7 +220,7 @@ impl Accessor for Backend {
         Ok((RpRead::new(size), r))
     }
 
-    async fn write(&self, path: &str, _: OpWrite, r: BytesReader) -> Result<RpWrite> {
+    async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
         let p = build_rooted_abs_path(&self.root, path);
 
         let parent = PathBuf::from(&p)
@@ -234,13 +238,21 @@ impl Accessor for Backend {
             .create_dir(&parent.to_string_lossy())
             .map_err(parse_io_error)?;
 
-        let mut f = self
-            .client
-            .open_file()
-            .create(true)
-            .write(true)
-            .open(&p)
-            .map_err(parse_io_error)?;
+        // hdfs can't open a not exist file for append, create it instead.
+        let append = args.append()
+            && match self.client.metadata(&p) {
+                Ok(_) => true,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+                Err(err) => return Err(parse_io_error(err)),
+            };
+
+        let mut opts = self.client.open_file();
+        if append {
+            opts.append(true);
+        } else {
+            opts.create(true).write(true);
+        }
+        let mut f = opts.open(&p).map_err(parse_io_error)?;
 
         let n = futures::io::copy(r, &mut f).await.map_err(parse_io_error)?;
 
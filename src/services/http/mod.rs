@@ -14,20 +14,33 @@
 
 //! HTTP Read-only backend support.
 //!
-//! Although HTTP backend supports `write`, `delete` and `create`, but they should
-//! only be used for testing purpose. As there are no standards for HTTP services
-//! to handle them.
+//! HTTP backend is read-only, `write`, `delete` and other operations will
+//! return `Unsupported`.
 //!
 //! HTTP Method mapping:
 //!
-//! - `create`: HTTP PUT (test only)
-//! - `read`: HTTP GET
-//! - `write`: HTTP PUT  (test only)
-//! - `stat`: HTTP HEAD
-//! - `delete`: HTTP DELETE  (test only)
-//! - `list`: List internal index.
+//! - `read`: HTTP GET with `Range` header
+//! - `stat`: HTTP HEAD, fallback to HTTP GET with `Range: bytes=0-0` if
+//!   `HEAD` is blocked by servers.
+//!
+//! # Configuration
+//!
+//! - `root`: Set the work dir for backend.
+//! - `endpoint`: Set the endpoint for backend.
+//! - `username`: Set the username for basic authentication.
+//! - `password`: Set the password for basic authentication.
+//! - `token`: Set the bearer token for authentication.
+//! - `trust_accept_ranges`: Set to `false` to fetch the whole content and
+//!   skip to the range locally for servers that don't handle ranges well.
+//!
+//! Refer to [`Builder`]'s public API docs for more information.
+//!
+//! # Notes
+//!
+//! Servers could return chunked responses without `Content-Length`, they
+//! will be streamed without a known size.
 
 mod backend;
 pub use backend::Builder;
 
-mod error;
+pub(super) mod error;
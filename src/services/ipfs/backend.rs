@@ -36,6 +36,8 @@ pub struct Builder {
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &["root", "endpoint"];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
 
@@ -93,9 +95,10 @@ impl Builder {
         let root = normalize_root(&self.root.take().unwrap_or_default());
         if !root.starts_with("/ipfs/") && !root.starts_with("/ipns/") {
             return Err(Error::new(
-                ErrorKind::BackendConfigInvalid,
+                ErrorKind::ConfigInvalid,
                 "root must start with /ipfs/ or /ipns/",
             )
+            .with_context("field", "root")
             .with_context("service", Scheme::Ipfs)
             .with_context("root", &root));
         }
@@ -103,11 +106,10 @@ impl Builder {
 
         let endpoint = match &self.endpoint {
             Some(endpoint) => Ok(endpoint.clone()),
-            None => Err(
-                Error::new(ErrorKind::BackendConfigInvalid, "endpoint is empty")
-                    .with_context("service", Scheme::Ipfs)
-                    .with_context("root", &root),
-            ),
+            None => Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_context("field", "endpoint")
+                .with_context("service", Scheme::Ipfs)
+                .with_context("root", &root)),
         }?;
         debug!("backend use endpoint {}", &endpoint);
 
@@ -288,9 +290,9 @@ impl Accessor for Backend {
                 }
 
                 if let Some(v) = parse_etag(resp.headers())? {
-                    m.set_etag(v);
+                    m.set_etag(&v);
 
-                    if v.starts_with("\"DirIndex") {
+                    if v.starts_with("DirIndex") {
                         m.set_mode(ObjectMode::DIR);
                     } else {
                         m.set_mode(ObjectMode::FILE);
@@ -326,8 +328,8 @@ impl Backend {
 
         let mut req = Request::get(&url);
 
-        if !range.is_full() {
-            req = req.header(http::header::RANGE, range.to_header());
+        if let Some(range_header) = range.to_header() {
+            req = req.header(http::header::RANGE, range_header);
         }
 
         let req = req
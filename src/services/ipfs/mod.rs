@@ -84,5 +84,5 @@
 mod backend;
 pub use backend::Builder;
 
-mod error;
+pub(super) mod error;
 mod ipld;
@@ -134,16 +134,7 @@ impl Accessor for Backend {
                 let res: IpfsStatResponse =
                     serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
 
-                let mode = match res.file_type.as_str() {
-                    "file" => ObjectMode::FILE,
-                    "directory" => ObjectMode::DIR,
-                    _ => ObjectMode::Unknown,
-                };
-
-                let mut meta = ObjectMetadata::new(mode);
-                meta.set_content_length(res.size);
-
-                Ok(RpStat::new(meta))
+                Ok(RpStat::new(res.into_metadata()))
             }
             _ => Err(parse_error(resp).await?),
         }
@@ -159,7 +150,14 @@ impl Accessor for Backend {
                 resp.into_body().consume().await?;
                 Ok(RpDelete::default())
             }
-            _ => Err(parse_error(resp).await?),
+            _ => {
+                let err = parse_error(resp).await?;
+                // ipmfs returns `file does not exist` for not exist path.
+                match err.kind() {
+                    ErrorKind::ObjectNotFound => Ok(RpDelete::default()),
+                    _ => Err(err),
+                }
+            }
         }
     }
 
@@ -293,8 +291,74 @@ impl Backend {
 #[derive(Deserialize, Default, Debug)]
 #[serde(default)]
 struct IpfsStatResponse {
+    #[serde(rename = "Hash")]
+    hash: String,
     #[serde(rename = "Size")]
     size: u64,
     #[serde(rename = "Type")]
     file_type: String,
 }
+
+impl IpfsStatResponse {
+    /// The CID of object will be returned as etag.
+    fn into_metadata(self) -> ObjectMetadata {
+        let mode = match self.file_type.as_str() {
+            "file" => ObjectMode::FILE,
+            "directory" => ObjectMode::DIR,
+            _ => ObjectMode::Unknown,
+        };
+
+        let mut meta = ObjectMetadata::new(mode);
+        meta.set_content_length(self.size);
+        if !self.hash.is_empty() {
+            meta.set_etag(&self.hash);
+        }
+
+        meta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Response of `ipfs files stat /test/file`.
+    #[test]
+    fn test_parse_stat_response() {
+        let bs = r#"{
+  "Blocks": 0,
+  "CumulativeSize": 21,
+  "Hash": "bafkreigp5eadxj7ndo4iuinmmhwqj6vuidoxq3oq4p4ewfw4bdmrfuhw5y",
+  "Local": true,
+  "Size": 13,
+  "SizeLocal": 21,
+  "Type": "file",
+  "WithLocality": false
+}"#;
+        let res: IpfsStatResponse = serde_json::from_str(bs).expect("must succeed");
+        let meta = res.into_metadata();
+
+        assert_eq!(meta.mode(), ObjectMode::FILE);
+        assert_eq!(meta.content_length(), 13);
+        assert_eq!(
+            meta.etag(),
+            Some("bafkreigp5eadxj7ndo4iuinmmhwqj6vuidoxq3oq4p4ewfw4bdmrfuhw5y")
+        );
+
+        let bs = r#"{
+  "Blocks": 2,
+  "CumulativeSize": 163,
+  "Hash": "QmUNLLsPACCz1vLxQVkXqqLX5R1X345qqfHbsf67hvA3Nn",
+  "Size": 0,
+  "Type": "directory"
+}"#;
+        let res: IpfsStatResponse = serde_json::from_str(bs).expect("must succeed");
+        let meta = res.into_metadata();
+
+        assert_eq!(meta.mode(), ObjectMode::DIR);
+        assert_eq!(
+            meta.etag(),
+            Some("QmUNLLsPACCz1vLxQVkXqqLX5R1X345qqfHbsf67hvA3Nn")
+        );
+    }
+}
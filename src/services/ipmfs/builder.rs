@@ -26,6 +26,8 @@ pub struct Builder {
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &["root", "endpoint"];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
 
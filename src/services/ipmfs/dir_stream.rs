@@ -82,12 +82,15 @@ impl ObjectPage for DirStream {
 
                     let path = build_rel_path(&self.root, &path);
 
-                    ObjectEntry::new(
-                        &path,
-                        ObjectMetadata::new(object.mode())
-                            .with_content_length(object.size)
-                            .with_complete(),
-                    )
+                    let mut meta = ObjectMetadata::new(object.mode())
+                        .with_content_length(object.size)
+                        .with_complete();
+                    // The CID of object will be returned as etag.
+                    if !object.hash.is_empty() {
+                        meta.set_etag(&object.hash);
+                    }
+
+                    ObjectEntry::new(&path, meta)
                 })
                 .collect(),
         ))
@@ -103,6 +106,8 @@ struct IpfsLsResponseEntry {
     file_type: i64,
     #[serde(rename = "Size")]
     size: u64,
+    #[serde(rename = "Hash")]
+    hash: String,
 }
 
 impl IpfsLsResponseEntry {
@@ -133,3 +138,57 @@ struct IpfsLsResponse {
     #[serde(rename = "Entries")]
     entries: Option<Vec<IpfsLsResponseEntry>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Response of `ipfs files ls --long /test/`.
+    #[test]
+    fn test_parse_ls_response() {
+        let bs = r#"{
+  "Entries": [
+    {
+      "Name": "dir",
+      "Type": 1,
+      "Size": 0,
+      "Hash": "QmUNLLsPACCz1vLxQVkXqqLX5R1X345qqfHbsf67hvA3Nn"
+    },
+    {
+      "Name": "file",
+      "Type": 0,
+      "Size": 13,
+      "Hash": "bafkreigp5eadxj7ndo4iuinmmhwqj6vuidoxq3oq4p4ewfw4bdmrfuhw5y"
+    }
+  ]
+}"#;
+        let res: IpfsLsResponse = serde_json::from_str(bs).expect("must succeed");
+        let entries = res.entries.expect("entries must exist");
+
+        assert_eq!(
+            entries
+                .iter()
+                .map(|v| (v.name.as_str(), v.mode(), v.size, v.hash.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    "dir",
+                    ObjectMode::DIR,
+                    0,
+                    "QmUNLLsPACCz1vLxQVkXqqLX5R1X345qqfHbsf67hvA3Nn"
+                ),
+                (
+                    "file",
+                    ObjectMode::FILE,
+                    13,
+                    "bafkreigp5eadxj7ndo4iuinmmhwqj6vuidoxq3oq4p4ewfw4bdmrfuhw5y"
+                ),
+            ]
+        );
+
+        // Empty dirs return `null` entries.
+        let res: IpfsLsResponse =
+            serde_json::from_str(r#"{"Entries": null}"#).expect("must succeed");
+        assert!(res.entries.is_none());
+    }
+}
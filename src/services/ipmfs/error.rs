@@ -59,6 +59,8 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
                 (ErrorKind::Unexpected, false)
             }
         }
+        StatusCode::NOT_FOUND => (ErrorKind::ObjectNotFound, false),
+        StatusCode::FORBIDDEN => (ErrorKind::ObjectPermissionDenied, false),
         StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT => {
             (ErrorKind::Unexpected, true)
         }
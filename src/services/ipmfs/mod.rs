@@ -21,6 +21,20 @@
 //!
 //! You can refer to [`Builder`]'s docs for more information
 //!
+//! # API Mapping
+//!
+//! ipmfs talks to the Files API (`/api/v0/files/*`) of local IPFS node:
+//!
+//! - `write`: `files/write?create=true&truncate=true` with body encoded in
+//!   `multipart/form-data`
+//! - `read`: `files/read`
+//! - `stat`: `files/stat`
+//! - `delete`: `files/rm`
+//! - `list`: `files/ls?long=true`
+//!
+//! CIDs returned by `files/stat` and `files/ls` will be exposed as
+//! [`ObjectMetadata::etag`][crate::ObjectMetadata::etag].
+//!
 //! # Environment
 //!
 //! - `OPENDAL_IPMFS_ROOT`    optional
@@ -84,4 +98,4 @@ mod builder;
 pub use builder::Builder;
 
 mod dir_stream;
-mod error;
+pub(super) mod error;
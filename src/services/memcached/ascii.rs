@@ -0,0 +1,384 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Connection to memcached which talks in the text protocol.
+///
+/// Reference: [protocol.txt](https://github.com/memcached/memcached/blob/master/doc/protocol.txt)
+pub struct Connection<S> {
+    io: BufReader<S>,
+    /// Whether this connection is in the middle of a request.
+    ///
+    /// It's set before sending a request and cleared after a complete
+    /// reply has been read, so connections left by io errors or cancelled
+    /// futures will not be reused.
+    broken: bool,
+}
+
+impl<S> Connection<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    pub fn new(io: S) -> Self {
+        Self {
+            io: BufReader::new(io),
+            broken: false,
+        }
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.broken
+    }
+
+    /// `get <key>\r\n`
+    ///
+    /// Returns `VALUE <key> <flags> <bytes>\r\n<data>\r\nEND\r\n` if found,
+    /// or `END\r\n` if not.
+    pub async fn get(&mut self, key: &str) -> Result<Option<Vec<u8>>> {
+        self.broken = true;
+        self.write_all(format!("get {key}\r\n").as_bytes()).await?;
+        self.flush().await?;
+
+        let line = self.read_line().await?;
+        if line == "END" {
+            self.broken = false;
+            return Ok(None);
+        }
+        let size = match parse_value_line(&line, key) {
+            Some(size) => size,
+            None => {
+                let err = parse_error_line(&line);
+                self.broken = !is_error_line(&line);
+                return Err(err);
+            }
+        };
+
+        let mut buf = vec![0; size + 2];
+        self.io.read_exact(&mut buf).await.map_err(parse_io_error)?;
+        if !buf.ends_with(b"\r\n") {
+            return Err(unexpected_reply("data block is not terminated by CRLF"));
+        }
+        buf.truncate(size);
+
+        let line = self.read_line().await?;
+        if line != "END" {
+            return Err(unexpected_reply(&line));
+        }
+
+        self.broken = false;
+        Ok(Some(buf))
+    }
+
+    /// `set <key> <flags> <exptime> <bytes>\r\n<data>\r\n`
+    ///
+    /// Returns `STORED\r\n` if succeeded.
+    pub async fn set(&mut self, key: &str, value: &[u8], exptime: u32) -> Result<()> {
+        self.broken = true;
+        self.write_all(format!("set {key} 0 {exptime} {}\r\n", value.len()).as_bytes())
+            .await?;
+        self.write_all(value).await?;
+        self.write_all(b"\r\n").await?;
+        self.flush().await?;
+
+        let line = self.read_line().await?;
+        self.finish(line, &["STORED"])
+    }
+
+    /// `delete <key>\r\n`
+    ///
+    /// Returns `DELETED\r\n` or `NOT_FOUND\r\n`, both are fine for us.
+    pub async fn delete(&mut self, key: &str) -> Result<()> {
+        self.broken = true;
+        self.write_all(format!("delete {key}\r\n").as_bytes())
+            .await?;
+        self.flush().await?;
+
+        let line = self.read_line().await?;
+        self.finish(line, &["DELETED", "NOT_FOUND"])
+    }
+
+    /// `version\r\n`
+    ///
+    /// Returns `VERSION <version>\r\n`.
+    pub async fn version(&mut self) -> Result<String> {
+        self.broken = true;
+        self.write_all(b"version\r\n").await?;
+        self.flush().await?;
+
+        let line = self.read_line().await?;
+        match line.strip_prefix("VERSION ") {
+            Some(v) => {
+                self.broken = false;
+                Ok(v.to_string())
+            }
+            None => {
+                let err = parse_error_line(&line);
+                self.broken = !is_error_line(&line);
+                Err(err)
+            }
+        }
+    }
+
+    /// Check the reply line of storage and deletion commands.
+    fn finish(&mut self, line: String, expected: &[&str]) -> Result<()> {
+        if expected.contains(&line.as_str()) {
+            self.broken = false;
+            return Ok(());
+        }
+
+        self.broken = !is_error_line(&line);
+        Err(parse_error_line(&line))
+    }
+
+    async fn write_all(&mut self, bs: &[u8]) -> Result<()> {
+        self.io
+            .get_mut()
+            .write_all(bs)
+            .await
+            .map_err(parse_io_error)
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.io.get_mut().flush().await.map_err(parse_io_error)
+    }
+
+    /// Read a line terminated by CRLF, and return it without CRLF.
+    async fn read_line(&mut self) -> Result<String> {
+        let mut buf = Vec::new();
+        let n = self
+            .io
+            .read_until(b'\n', &mut buf)
+            .await
+            .map_err(parse_io_error)?;
+        if n == 0 {
+            return Err(
+                Error::new(ErrorKind::Unexpected, "connection closed by memcached").set_temporary(),
+            );
+        }
+        if !buf.ends_with(b"\r\n") {
+            return Err(unexpected_reply(&String::from_utf8_lossy(&buf)));
+        }
+        buf.truncate(buf.len() - 2);
+
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+}
+
+/// Parse `VALUE <key> <flags> <bytes> [<cas unique>]` into the data size.
+fn parse_value_line(line: &str, key: &str) -> Option<usize> {
+    let mut parts = line.split(' ');
+    if parts.next()? != "VALUE" || parts.next()? != key {
+        return None;
+    }
+    parts.next()?.parse::<u32>().ok()?;
+    parts.next()?.parse::<usize>().ok()
+}
+
+/// Whether the line is an error reply which leaves the connection usable.
+fn is_error_line(line: &str) -> bool {
+    line == "ERROR" || line.starts_with("CLIENT_ERROR ") || line.starts_with("SERVER_ERROR ")
+}
+
+/// Parse error replies from memcached:
+///
+/// - `ERROR`: the command is unknown.
+/// - `CLIENT_ERROR <error>`: the input doesn't conform to the protocol.
+/// - `SERVER_ERROR <error>`: the server failed to serve the request, like
+///   out of memory.
+fn parse_error_line(line: &str) -> Error {
+    if line == "ERROR" {
+        return Error::new(
+            ErrorKind::Unexpected,
+            "command is not supported by memcached",
+        )
+        .with_context("response", line);
+    }
+    if let Some(msg) = line.strip_prefix("CLIENT_ERROR ") {
+        return Error::new(ErrorKind::Unexpected, msg).with_context("response", line);
+    }
+    if let Some(msg) = line.strip_prefix("SERVER_ERROR ") {
+        return Error::new(ErrorKind::Unexpected, msg)
+            .with_context("response", line)
+            .set_temporary();
+    }
+    unexpected_reply(line)
+}
+
+fn unexpected_reply(line: &str) -> Error {
+    Error::new(ErrorKind::Unexpected, "unexpected reply from memcached")
+        .with_context("response", line)
+}
+
+/// All io errors of the connection are temporary.
+pub fn parse_io_error(err: io::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, &err.kind().to_string())
+        .set_source(err)
+        .set_temporary()
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::duplex;
+    use tokio::io::DuplexStream;
+
+    use super::*;
+
+    /// Prepare a connection with the reply of server, and return the server
+    /// side to check the request.
+    async fn connect(reply: &[u8]) -> (Connection<DuplexStream>, DuplexStream) {
+        let (client, mut server) = duplex(4096);
+        server.write_all(reply).await.expect("write must succeed");
+        (Connection::new(client), server)
+    }
+
+    async fn request(mut server: DuplexStream) -> Vec<u8> {
+        let mut buf = Vec::new();
+        server.shutdown().await.expect("shutdown must succeed");
+        server
+            .read_to_end(&mut buf)
+            .await
+            .expect("read must succeed");
+        buf
+    }
+
+    #[tokio::test]
+    async fn test_get() {
+        let (mut conn, server) = connect(b"VALUE key 0 12\r\nHello\r\nWorld\r\nEND\r\n").await;
+
+        let bs = conn.get("key").await.expect("get must succeed");
+        assert_eq!(bs.as_deref(), Some(b"Hello\r\nWorld".as_slice()));
+        assert!(!conn.is_broken());
+        drop(conn);
+
+        assert_eq!(request(server).await, b"get key\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_get_with_cas() {
+        let (mut conn, _server) = connect(b"VALUE key 0 0 42\r\n\r\nEND\r\n").await;
+
+        let bs = conn.get("key").await.expect("get must succeed");
+        assert_eq!(bs.as_deref(), Some(b"".as_slice()));
+        assert!(!conn.is_broken());
+    }
+
+    #[tokio::test]
+    async fn test_get_not_found() {
+        let (mut conn, _server) = connect(b"END\r\n").await;
+
+        let bs = conn.get("key").await.expect("get must succeed");
+        assert_eq!(bs, None);
+        assert!(!conn.is_broken());
+    }
+
+    #[tokio::test]
+    async fn test_get_truncated() {
+        let (mut conn, mut server) = connect(b"VALUE key 0 12\r\nHello").await;
+        server.shutdown().await.expect("shutdown must succeed");
+
+        let err = conn.get("key").await.expect_err("get must fail");
+        assert!(err.is_temporary());
+        assert!(conn.is_broken());
+    }
+
+    #[tokio::test]
+    async fn test_set() {
+        let (mut conn, server) = connect(b"STORED\r\n").await;
+
+        conn.set("key", b"Hello, World!", 60)
+            .await
+            .expect("set must succeed");
+        assert!(!conn.is_broken());
+        drop(conn);
+
+        assert_eq!(
+            request(server).await,
+            b"set key 0 60 13\r\nHello, World!\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_server_error() {
+        let (mut conn, _server) = connect(b"SERVER_ERROR object too large for cache\r\n").await;
+
+        let err = conn
+            .set("key", b"Hello, World!", 0)
+            .await
+            .expect_err("set must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+        assert!(err.to_string().contains("object too large for cache"));
+        assert!(!conn.is_broken());
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        for reply in ["DELETED\r\n", "NOT_FOUND\r\n"] {
+            let (mut conn, server) = connect(reply.as_bytes()).await;
+
+            conn.delete("key").await.expect("delete must succeed");
+            assert!(!conn.is_broken());
+            drop(conn);
+
+            assert_eq!(request(server).await, b"delete key\r\n");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_version() {
+        let (mut conn, server) = connect(b"VERSION 1.6.17\r\n").await;
+
+        let v = conn.version().await.expect("version must succeed");
+        assert_eq!(v, "1.6.17");
+        drop(conn);
+
+        assert_eq!(request(server).await, b"version\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_unexpected_reply() {
+        let (mut conn, _server) = connect(b"EXISTS\r\n").await;
+
+        let err = conn.delete("key").await.expect_err("delete must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(!err.is_temporary());
+        assert!(conn.is_broken());
+    }
+
+    #[test]
+    fn test_parse_value_line() {
+        for (line, expected) in [
+            ("VALUE key 0 5", Some(5)),
+            ("VALUE key 1 5 1024", Some(5)),
+            ("VALUE other 0 5", None),
+            ("VALUE key 0", None),
+            ("VALUE key x 5", None),
+            ("END", None),
+        ] {
+            assert_eq!(parse_value_line(line, "key"), expected, "{line}");
+        }
+    }
+}
@@ -0,0 +1,503 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use async_trait::async_trait;
+use bb8::PooledConnection;
+use bb8::RunError;
+use http::Uri;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::AsciiSet;
+use percent_encoding::CONTROLS;
+use tokio::net::TcpStream;
+use tokio::sync::OnceCell;
+
+use super::ascii::parse_io_error;
+use super::ascii::Connection;
+use crate::raw::adapters::kv;
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+use crate::Scheme;
+
+const DEFAULT_MEMCACHED_ENDPOINT: &str = "tcp://127.0.0.1:11211";
+const DEFAULT_MEMCACHED_PORT: u16 = 11211;
+/// The default max item size of memcached, which could be changed by `-I`.
+const DEFAULT_MAX_VALUE_SIZE: usize = 1024 * 1024;
+/// Keys longer than this will be rejected by memcached.
+const MAX_KEY_LENGTH: usize = 250;
+/// Expiration times larger than this will be treated as unix timestamps.
+const MAX_RELATIVE_EXPTIME: u64 = 30 * 24 * 60 * 60;
+
+/// Keys of memcached can't contain control characters or whitespace, we
+/// encode them as well as `%` itself.
+static KEY_ENCODE_SET: AsciiSet = CONTROLS.add(b' ').add(b'%');
+
+/// Memcached backend builder
+#[derive(Clone, Default)]
+pub struct Builder {
+    /// network addresses of the memcached servers, separated by `,`.
+    ///
+    /// default is "tcp://127.0.0.1:11211"
+    endpoint: Option<String>,
+    /// the working directory of the service. Can be "/path/to/dir"
+    ///
+    /// default is "/"
+    root: Option<String>,
+    /// The default ttl for put operations.
+    default_ttl: Option<Duration>,
+    /// The max number of connections to every server.
+    connection_pool_max_size: Option<u32>,
+    /// The max size of values, should match `-I` of memcached.
+    max_value_size: Option<usize>,
+}
+
+impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "endpoint",
+        "default_ttl",
+        "connection_pool_max_size",
+        "max_value_size",
+    ];
+
+    pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
+        let mut builder = Builder::default();
+        for (k, v) in it {
+            let v = v.as_str();
+            match k.as_ref() {
+                "root" => builder.root(v),
+                "endpoint" => builder.endpoint(v),
+                "default_ttl" => match v.parse::<u64>() {
+                    Ok(v) => builder.default_ttl(Duration::from_secs(v)),
+                    _ => continue,
+                },
+                "connection_pool_max_size" => match v.parse::<u32>() {
+                    Ok(v) => builder.connection_pool_max_size(v),
+                    _ => continue,
+                },
+                "max_value_size" => match v.parse::<usize>() {
+                    Ok(v) => builder.max_value_size(v),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+        }
+        builder
+    }
+
+    /// set the network address of memcached servers.
+    ///
+    /// Multiple servers can be separated by `,` like
+    /// `tcp://10.0.0.1:11211,tcp://10.0.0.2:11211`, keys will be
+    /// distributed among them by hash.
+    ///
+    /// currently supported schemes:
+    /// - no scheme: will be seen as "tcp"
+    /// - "tcp" or "memcached": unsecured connections
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.to_owned());
+        }
+        self
+    }
+
+    /// set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_owned());
+        }
+        self
+    }
+
+    /// Set the default ttl for memcached services.
+    ///
+    /// If set, written keys will be removed by memcached after ttl.
+    pub fn default_ttl(&mut self, ttl: Duration) -> &mut Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    /// Set the max number of connections to every server.
+    ///
+    /// default: 10
+    pub fn connection_pool_max_size(&mut self, size: u32) -> &mut Self {
+        if size > 0 {
+            self.connection_pool_max_size = Some(size);
+        }
+        self
+    }
+
+    /// Set the max size of values that can be written.
+    ///
+    /// Memcached rejects items larger than 1MiB by default, please set this
+    /// along with `-I` of memcached if it has been changed.
+    ///
+    /// default: 1MiB
+    pub fn max_value_size(&mut self, size: usize) -> &mut Self {
+        if size > 0 {
+            self.max_value_size = Some(size);
+        }
+        self
+    }
+
+    /// Finish making memcached endpoint.
+    ///
+    /// Connections will be established lazily by the first request.
+    pub fn build(&mut self) -> Result<impl Accessor> {
+        let endpoint = self
+            .endpoint
+            .clone()
+            .unwrap_or_else(|| DEFAULT_MEMCACHED_ENDPOINT.to_string());
+
+        let mut addresses = Vec::new();
+        for ep in endpoint
+            .split(',')
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+        {
+            addresses.push(parse_address(ep)?);
+        }
+        if addresses.is_empty() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_context("field", "endpoint")
+                .with_context("service", Scheme::Memcached));
+        }
+
+        let root = normalize_root(
+            self.root
+                .clone()
+                .unwrap_or_else(|| "/".to_string())
+                .as_str(),
+        );
+
+        let pool_max_size = self.connection_pool_max_size.unwrap_or(10);
+        let servers = addresses
+            .into_iter()
+            .map(|address| Server {
+                address,
+                pool_max_size,
+                pool: OnceCell::new(),
+            })
+            .collect();
+
+        Ok(apply_wrapper(
+            Backend::new(Adapter {
+                servers,
+                default_ttl: self.default_ttl,
+                max_value_size: self.max_value_size.unwrap_or(DEFAULT_MAX_VALUE_SIZE),
+            })
+            .with_root(&root),
+        ))
+    }
+}
+
+impl Debug for Builder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("Builder");
+        ds.field("root", &self.root);
+        if let Some(endpoint) = self.endpoint.clone() {
+            ds.field("endpoint", &endpoint);
+        }
+        if let Some(default_ttl) = self.default_ttl {
+            ds.field("default_ttl", &default_ttl);
+        }
+        if let Some(size) = self.connection_pool_max_size {
+            ds.field("connection_pool_max_size", &size);
+        }
+        if let Some(size) = self.max_value_size {
+            ds.field("max_value_size", &size);
+        }
+        ds.finish()
+    }
+}
+
+/// Parse endpoint like `tcp://127.0.0.1:11211` into `127.0.0.1:11211`.
+fn parse_address(endpoint: &str) -> Result<String> {
+    let ep_url = endpoint.parse::<Uri>().map_err(|e| {
+        Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+            .with_context("field", "endpoint")
+            .with_context("service", Scheme::Memcached)
+            .with_context("endpoint", endpoint)
+            .set_source(e)
+    })?;
+
+    match ep_url.scheme_str() {
+        Some("tcp") | Some("memcached") | None => {}
+        Some(s) => {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "invalid or unsupported scheme")
+                    .with_context("field", "endpoint")
+                    .with_context("service", Scheme::Memcached)
+                    .with_context("scheme", s),
+            )
+        }
+    }
+
+    let host = ep_url
+        .host()
+        .map(|h| h.to_string())
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+    let port = ep_url.port_u16().unwrap_or(DEFAULT_MEMCACHED_PORT);
+    Ok(format!("{host}:{port}"))
+}
+
+/// Backend for memcached services.
+pub type Backend = kv::Backend<Adapter>;
+
+#[derive(Clone, Debug)]
+pub struct Adapter {
+    servers: Vec<Server>,
+
+    default_ttl: Option<Duration>,
+    max_value_size: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Server {
+    address: String,
+    pool_max_size: u32,
+    pool: OnceCell<bb8::Pool<Manager>>,
+}
+
+impl Adapter {
+    /// Pick the server of this key and get a connection to it.
+    async fn conn(&self, key: &str) -> Result<PooledConnection<'_, Manager>> {
+        let server = &self.servers[server_index(key, self.servers.len())];
+
+        let pool = server
+            .pool
+            .get_or_try_init(|| async {
+                bb8::Pool::builder()
+                    .max_size(server.pool_max_size)
+                    .build(Manager {
+                        address: server.address.clone(),
+                    })
+                    .await
+            })
+            .await?;
+
+        pool.get().await.map_err(|err| match err {
+            RunError::User(err) => err,
+            RunError::TimedOut => {
+                Error::new(ErrorKind::Unexpected, "connection request: timeout").set_temporary()
+            }
+        })
+    }
+
+    fn check_value_size(&self, value: &[u8]) -> Result<()> {
+        if value.len() > self.max_value_size {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "value is larger than the max item size of memcached, please increase it by `-I` and `max_value_size`",
+            )
+            .with_operation("kv::Adapter::set")
+            .with_context("service", Scheme::Memcached)
+            .with_context("size", value.len().to_string())
+            .with_context("max_value_size", self.max_value_size.to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl kv::Adapter for Adapter {
+    fn metadata(&self) -> kv::Metadata {
+        kv::Metadata::new(
+            Scheme::Memcached,
+            &self
+                .servers
+                .iter()
+                .map(|v| v.address.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+            AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::Expire,
+        )
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let key = encode_key(key)?;
+        let mut conn = self.conn(&key).await?;
+        conn.get(&key).await
+    }
+
+    async fn set(&self, key: &str, value: &[u8]) -> Result<()> {
+        self.check_value_size(value)?;
+
+        let key = encode_key(key)?;
+        let exptime = self.default_ttl.map(to_exptime).unwrap_or_default();
+        let mut conn = self.conn(&key).await?;
+        conn.set(&key, value, exptime).await
+    }
+
+    /// Memcached only supports expire in whole seconds, so `expire` will be
+    /// rounded up.
+    async fn set_with_expire(&self, key: &str, value: &[u8], expire: Duration) -> Result<()> {
+        self.check_value_size(value)?;
+
+        let key = encode_key(key)?;
+        let mut conn = self.conn(&key).await?;
+        conn.set(&key, value, to_exptime(expire)).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let key = encode_key(key)?;
+        let mut conn = self.conn(&key).await?;
+        conn.delete(&key).await
+    }
+}
+
+/// Encode path into a valid memcached key.
+fn encode_key(path: &str) -> Result<String> {
+    let key = utf8_percent_encode(path, &KEY_ENCODE_SET).to_string();
+    if key.len() > MAX_KEY_LENGTH {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "path is too long to be used as memcached key",
+        )
+        .with_context("service", Scheme::Memcached)
+        .with_context("path", path)
+        .with_context("max_key_length", MAX_KEY_LENGTH.to_string()));
+    }
+    Ok(key)
+}
+
+/// Convert expire into the exptime of memcached.
+///
+/// - Expire is rounded up to whole seconds, and at least 1s.
+/// - Expire larger than 30 days will be converted into unix timestamp.
+fn to_exptime(expire: Duration) -> u32 {
+    let mut secs = expire.as_secs();
+    if expire.subsec_nanos() > 0 || secs == 0 {
+        secs += 1;
+    }
+
+    if secs > MAX_RELATIVE_EXPTIME {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time must be later than unix epoch")
+            .as_secs();
+        secs += now;
+    }
+
+    secs.min(u32::MAX as u64) as u32
+}
+
+/// Pick a server for the key by FNV-1a hash.
+fn server_index(key: &str, n: usize) -> usize {
+    if n == 1 {
+        return 0;
+    }
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in key.bytes() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    (hash % n as u64) as usize
+}
+
+#[derive(Clone, Debug)]
+struct Manager {
+    address: String,
+}
+
+#[async_trait]
+impl bb8::ManageConnection for Manager {
+    type Connection = Connection<TcpStream>;
+    type Error = Error;
+
+    async fn connect(&self) -> std::result::Result<Self::Connection, Self::Error> {
+        let stream = TcpStream::connect(&self.address)
+            .await
+            .map_err(|err| parse_io_error(err).with_context("address", &self.address))?;
+        Ok(Connection::new(stream))
+    }
+
+    async fn is_valid(&self, conn: &mut Self::Connection) -> std::result::Result<(), Self::Error> {
+        conn.version().await.map(|_| ())
+    }
+
+    fn has_broken(&self, conn: &mut Self::Connection) -> bool {
+        conn.is_broken()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_address() {
+        for (endpoint, expected) in [
+            ("tcp://127.0.0.1:11211", "127.0.0.1:11211"),
+            ("memcached://example.com", "example.com:11211"),
+            ("10.0.0.1:12345", "10.0.0.1:12345"),
+        ] {
+            assert_eq!(
+                parse_address(endpoint).expect("parse must succeed"),
+                expected
+            );
+        }
+
+        let err = parse_address("unix:///tmp/memcached.sock").expect_err("parse must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    fn test_encode_key() {
+        assert_eq!(
+            encode_key("/path/to/a b%c\n.txt").expect("encode must succeed"),
+            "/path/to/a%20b%25c%0A.txt"
+        );
+        assert_eq!(
+            encode_key("/中文").expect("encode must succeed"),
+            "/%E4%B8%AD%E6%96%87"
+        );
+
+        let err = encode_key(&"a".repeat(MAX_KEY_LENGTH + 1)).expect_err("encode must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_to_exptime() {
+        assert_eq!(to_exptime(Duration::from_secs(10)), 10);
+        assert_eq!(to_exptime(Duration::from_millis(1500)), 2);
+        assert_eq!(to_exptime(Duration::ZERO), 1);
+        assert!(to_exptime(Duration::from_secs(MAX_RELATIVE_EXPTIME + 1)) > 1_600_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_value_too_large() {
+        let adapter = Adapter {
+            servers: vec![],
+            default_ttl: None,
+            max_value_size: 4,
+        };
+
+        let err = kv::Adapter::set(&adapter, "key", b"hello")
+            .await
+            .expect_err("set must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(err.to_string().contains("max item size"));
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memcached support for OpenDAL
+//!
+//! # Note
+//!
+//! The storage format for this service is not **stable** yet.
+//!
+//! PLEASE DON'T USE THIS SERVICE FOR PERSIST DATA.
+//!
+//! # Capabilities
+//!
+//! This service can be used to:
+//!
+//! - [x] read
+//! - [x] write
+//! - [ ] list
+//! - [x] expire
+//!
+//! Memcached can't iterate over keys, so list is not supported.
+//!
+//! # Configuration
+//!
+//! - `root`: Set the working directory of `OpenDAL`
+//! - `endpoint`: Set the network addresses of memcached servers, separated by `,`
+//! - `default_ttl`: Set the default ttl in seconds for written keys
+//! - `connection_pool_max_size`: Set the max number of connections to every server
+//! - `max_value_size`: Set the max size of values in bytes, default to 1MiB
+//!
+//! You can refer to [`Builder`]'s docs for more information
+//!
+//! # Value Size
+//!
+//! Memcached rejects items larger than its max item size, which is 1MiB
+//! by default. Writing values larger than `max_value_size` will return
+//! an [`Unsupported`][crate::ErrorKind::Unsupported] error before sending
+//! anything to memcached. Please start memcached with larger `-I` and set
+//! `max_value_size` accordingly to store larger values.
+//!
+//! # Keys
+//!
+//! Control characters, whitespace and `%` in paths will be percent-encoded
+//! while building memcached keys. Paths whose keys are longer than 250
+//! bytes are not supported.
+//!
+//! # Environment
+//!
+//! - `OPENDAL_MEMCACHED_ROOT` optional
+//! - `OPENDAL_MEMCACHED_ENDPOINT` optional
+//! - `OPENDAL_MEMCACHED_DEFAULT_TTL` optional
+//! - `OPENDAL_MEMCACHED_CONNECTION_POOL_MAX_SIZE` optional
+//! - `OPENDAL_MEMCACHED_MAX_VALUE_SIZE` optional
+//!
+//! # Example
+//!
+//! ## Initiate via environment variables:
+//!
+//! Set environment correctly:
+//!
+//! ```shell
+//! export OPENDAL_MEMCACHED_ENDPOINT=tcp://127.0.0.1:11211
+//! export OPENDAL_MEMCACHED_ROOT=/path/to/dir
+//! ```
+//! ```no_run
+//! use anyhow::Result;
+//! use opendal::Object;
+//! use opendal::Operator;
+//! use opendal::Scheme;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let op = Operator::from_env(Scheme::Memcached)?;
+//!
+//!     let _op: Object = op.object("hello_memcached!");
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ## Via Builder
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use anyhow::Result;
+//! use opendal::services::memcached;
+//! use opendal::Object;
+//! use opendal::Operator;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let mut builder = memcached::Builder::default();
+//!
+//!     builder
+//!         .endpoint("tcp://127.0.0.1:11211,tcp://127.0.0.1:11212")
+//!         .default_ttl(Duration::from_secs(3600))
+//!         .connection_pool_max_size(16);
+//!
+//!     let op: Operator = Operator::new(builder.build()?);
+//!     let _: Object = op.object("test_file");
+//!     Ok(())
+//! }
+//! ```
+
+mod ascii;
+mod backend;
+pub use backend::Builder;
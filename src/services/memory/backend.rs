@@ -52,7 +52,12 @@ impl kv::Adapter for Adapter {
         kv::Metadata::new(
             Scheme::Memory,
             &format!("{:?}", &self.inner as *const _),
-            AccessorCapability::Read | AccessorCapability::Write,
+            AccessorCapability::Read
+                | AccessorCapability::Write
+                | AccessorCapability::List
+                | AccessorCapability::ListRecursive
+                | AccessorCapability::Multipart
+                | AccessorCapability::Blocking,
         )
     }
 
@@ -86,11 +91,61 @@ impl kv::Adapter for Adapter {
 
         Ok(())
     }
+
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        self.blocking_scan(path)
+    }
+
+    fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
+        let inner = self.inner.lock();
+        let keys = inner
+            .range(path.to_string()..)
+            .map(|(k, _)| k)
+            .take_while(|k| k.starts_with(path))
+            .cloned()
+            .collect();
+
+        Ok(keys)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use async_trait::async_trait;
+
     use super::*;
+    use crate::*;
+
+    /// Report the md5 of `content` while stat, no matter what's stored in
+    /// memory.
+    #[derive(Debug)]
+    struct ContentMd5 {
+        inner: Option<Arc<dyn Accessor>>,
+        content_md5: String,
+    }
+
+    impl Layer for &ContentMd5 {
+        fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+            Arc::new(ContentMd5 {
+                inner: Some(inner),
+                content_md5: self.content_md5.clone(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Accessor for ContentMd5 {
+        fn inner(&self) -> Option<Arc<dyn Accessor>> {
+            self.inner.clone()
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            let rp = self.inner.as_ref().unwrap().stat(path, args).await?;
+            Ok(RpStat::new(
+                rp.into_metadata().with_content_md5(&self.content_md5),
+            ))
+        }
+    }
 
     #[test]
     fn test_accessor_metadata_name() {
@@ -100,4 +155,87 @@ mod tests {
         let b2 = Builder::default().build().unwrap();
         assert_ne!(b1.metadata().name(), b2.metadata().name())
     }
+
+    #[tokio::test]
+    async fn test_read_verify_content_md5() {
+        // md5 of "Hello, World!"
+        let layer = ContentMd5 {
+            inner: None,
+            content_md5: "ZajifYh5KDgxtmS9i38K1A==".to_string(),
+        };
+        let op = Operator::new(Builder::default().build().unwrap()).layer(&layer);
+
+        let o = op.object("hello");
+        o.write("Hello, World!").await.unwrap();
+        let bs = o
+            .read_with(OpRead::new().with_verify_content_md5(true))
+            .await
+            .expect("read must succeed");
+        assert_eq!(bs, b"Hello, World!");
+
+        // Inject a corrupted body.
+        let o = op.object("corrupted");
+        o.write("Hello, World?").await.unwrap();
+        let err = o
+            .read_with(OpRead::new().with_verify_content_md5(true))
+            .await
+            .expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::ChecksumMismatch);
+
+        // Verification is opt-in.
+        let o = op.object("corrupted");
+        assert!(o.read().await.is_ok());
+
+        // Ranged reads can't be verified.
+        let err = o
+            .read_with(
+                OpRead::new()
+                    .with_range((0..5).into())
+                    .with_verify_content_md5(true),
+            )
+            .await
+            .expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_read_verify_content_md5_skipped() {
+        let op = Operator::new(Builder::default().build().unwrap());
+
+        // memory doesn't report content md5, verification will be skipped.
+        let o = op.object("hello");
+        o.write("Hello, World!").await.unwrap();
+        let bs = o
+            .read_with(OpRead::new().with_verify_content_md5(true))
+            .await
+            .expect("read must succeed");
+        assert_eq!(bs, b"Hello, World!");
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let op = Operator::from_env(Scheme::Memory).expect("build operator");
+        assert_eq!(
+            op.metadata().capabilities(),
+            AccessorCapability::Read
+                | AccessorCapability::Write
+                | AccessorCapability::List
+                | AccessorCapability::ListRecursive
+                | AccessorCapability::Multipart
+                | AccessorCapability::Blocking
+        );
+
+        let o = op.object("file");
+        o.blocking_write("Hello, World!")
+            .expect("blocking write must succeed");
+        assert_eq!(
+            o.blocking_read().expect("blocking read must succeed"),
+            b"Hello, World!"
+        );
+        let err = o
+            .presign_read(time::Duration::hours(1))
+            .expect_err("presign must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert_eq!(err.context("service"), Some("memory"));
+    }
 }
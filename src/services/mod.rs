@@ -19,8 +19,11 @@
 //! - Builder: responsible for building the service backend.
 //! - Backend: the service backend which implements the [`Accessor`][crate::raw::Accessor] trait.
 
+pub mod alluxio;
 pub mod azblob;
 pub mod azdfs;
+pub mod b2;
+pub mod cos;
 pub mod fs;
 #[cfg(feature = "services-ftp")]
 pub mod ftp;
@@ -31,13 +34,84 @@ pub mod http;
 #[cfg(feature = "services-ipfs")]
 pub mod ipfs;
 pub mod ipmfs;
+#[cfg(feature = "services-memcached")]
+pub mod memcached;
 pub mod memory;
 #[cfg(feature = "services-moka")]
 pub mod moka;
 pub mod obs;
 pub mod oss;
+#[cfg(feature = "services-postgresql")]
+pub mod postgresql;
 #[cfg(feature = "services-redis")]
 pub mod redis;
 #[cfg(feature = "services-rocksdb")]
 pub mod rocksdb;
 pub mod s3;
+pub mod swift;
+pub mod webdav;
+
+#[cfg(test)]
+mod tests {
+    use futures::future::BoxFuture;
+    use futures::io::Cursor;
+    use http::Response;
+    use http::StatusCode;
+
+    use crate::raw::*;
+    use crate::Error;
+    use crate::ErrorKind;
+    use crate::Result;
+
+    type ParseError = fn(Response<IncomingAsyncBody>) -> BoxFuture<'static, Result<Error>>;
+
+    macro_rules! parsers {
+        ($($(#[$meta:meta])* $service:ident),* $(,)?) => {
+            vec![$(
+                $(#[$meta])*
+                (
+                    stringify!($service),
+                    (|resp| Box::pin(super::$service::error::parse_error(resp))) as ParseError,
+                ),
+            )*]
+        };
+    }
+
+    /// Responses of `HEAD` carry no body, so services must map errors
+    /// from the status code alone.
+    #[tokio::test]
+    async fn test_parse_error_status() {
+        let parsers: Vec<(&str, ParseError)> = parsers!(
+            alluxio,
+            azblob,
+            azdfs,
+            b2,
+            gcs,
+            http,
+            #[cfg(feature = "services-ipfs")]
+            ipfs,
+            ipmfs,
+            obs,
+            oss,
+            s3,
+            swift,
+        );
+
+        for (service, parse_error) in parsers {
+            for (status, kind) in [
+                (StatusCode::NOT_FOUND, ErrorKind::ObjectNotFound),
+                (StatusCode::FORBIDDEN, ErrorKind::ObjectPermissionDenied),
+            ] {
+                let body = IncomingAsyncBody::new(Box::new(Cursor::new(Vec::new())));
+                let resp = Response::builder()
+                    .status(status)
+                    .body(body)
+                    .expect("response must be valid");
+
+                let err = parse_error(resp).await.expect("parse must succeed");
+                assert_eq!(err.kind(), kind, "{service}: {status}");
+                assert!(!err.is_temporary(), "{service}: {status}");
+            }
+        }
+    }
+}
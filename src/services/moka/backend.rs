@@ -53,6 +53,15 @@ pub struct Builder {
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "name",
+        "max_capacity",
+        "time_to_live",
+        "time_to_idle",
+        "num_segments",
+        "thread_pool_enabled",
+    ];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
         for (k, v) in it {
@@ -192,7 +201,7 @@ impl kv::Adapter for Adapter {
         kv::Metadata::new(
             Scheme::Moka,
             self.inner.name().unwrap_or("moka"),
-            AccessorCapability::Read | AccessorCapability::Write,
+            AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::Blocking,
         )
     }
 
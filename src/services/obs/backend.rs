@@ -17,17 +17,24 @@ use std::fmt::Write;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use bytes::Buf;
+use bytes::Bytes;
+use http::header::AUTHORIZATION;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::header::DATE;
 use http::Request;
 use http::Response;
 use http::StatusCode;
 use http::Uri;
 use log::debug;
 use reqsign::HuaweicloudObsSigner;
+use serde::Deserialize;
+use serde::Serialize;
 
 use super::dir_stream::DirStream;
 use super::error::parse_error;
+use super::error::parse_xml_deserialize_error;
 use crate::raw::*;
 use crate::*;
 
@@ -39,21 +46,44 @@ pub struct Builder {
     access_key_id: Option<String>,
     secret_access_key: Option<String>,
     bucket: Option<String>,
+
+    http_client: Option<reqwest::Client>,
+    pool_max_idle_per_host: Option<usize>,
+    connect_timeout: Option<std::time::Duration>,
 }
 
 impl Debug for Builder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Builder")
-            .field("root", &self.root)
+        let mut d = f.debug_struct("Builder");
+        d.field("root", &self.root)
             .field("endpoint", &self.endpoint)
-            .field("access_key_id", &"<redacted>")
-            .field("secret_access_key", &"<redacted>")
-            .field("bucket", &self.bucket)
-            .finish()
+            .field("bucket", &self.bucket);
+        if self.access_key_id.is_some() {
+            d.field("access_key_id", &"<redacted>");
+        }
+        if self.secret_access_key.is_some() {
+            d.field("secret_access_key", &"<redacted>");
+        }
+        d.field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field("connect_timeout", &self.connect_timeout);
+        if self.http_client.is_some() {
+            d.field("http_client", &"<custom>");
+        }
+        d.finish()
     }
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "bucket",
+        "endpoint",
+        "access_key_id",
+        "secret_access_key",
+        "pool_max_idle_per_host",
+        "connect_timeout",
+    ];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
 
@@ -65,6 +95,14 @@ impl Builder {
                 "endpoint" => builder.endpoint(v),
                 "access_key_id" => builder.access_key_id(v),
                 "secret_access_key" => builder.secret_access_key(v),
+                "pool_max_idle_per_host" => match v.parse::<usize>() {
+                    Ok(v) => builder.pool_max_idle_per_host(v),
+                    _ => continue,
+                },
+                "connect_timeout" => match v.parse::<u64>() {
+                    Ok(v) => builder.connect_timeout(std::time::Duration::from_secs(v)),
+                    _ => continue,
+                },
                 _ => continue,
             };
         }
@@ -131,6 +169,32 @@ impl Builder {
         self
     }
 
+    /// Set the http client used by this backend.
+    ///
+    /// Connections will be reused across operators that share clones of
+    /// the same `client`. Passing a custom client overrides
+    /// [`Builder::pool_max_idle_per_host`] and [`Builder::connect_timeout`].
+    pub fn http_client(&mut self, client: reqwest::Client) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Set the max idle connections kept alive per host.
+    ///
+    /// This will be ignored if [`Builder::http_client`] is set.
+    pub fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set the timeout for establishing connections.
+    ///
+    /// This will be ignored if [`Builder::http_client`] is set.
+    pub fn connect_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
     /// Consume builder to build an OBS backend.
     pub fn build(&mut self) -> Result<impl Accessor> {
         debug!("backend build started: {:?}", &self);
@@ -140,23 +204,22 @@ impl Builder {
 
         let bucket = match &self.bucket {
             Some(bucket) => Ok(bucket.to_string()),
-            None => Err(
-                Error::new(ErrorKind::BackendConfigInvalid, "bucket is empty")
-                    .with_context("service", Scheme::Obs),
-            ),
+            None => Err(Error::new(ErrorKind::ConfigInvalid, "bucket is empty")
+                .with_context("field", "bucket")
+                .with_context("service", Scheme::Obs)),
         }?;
         debug!("backend use bucket {}", &bucket);
 
         let uri = match &self.endpoint {
             Some(endpoint) => endpoint.parse::<Uri>().map_err(|err| {
-                Error::new(ErrorKind::BackendConfigInvalid, "endpoint is invalid")
+                Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+                    .with_context("field", "endpoint")
                     .with_context("service", Scheme::Obs)
                     .set_source(err)
             }),
-            None => Err(
-                Error::new(ErrorKind::BackendConfigInvalid, "endpoint is empty")
-                    .with_context("service", Scheme::Obs),
-            ),
+            None => Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_context("field", "endpoint")
+                .with_context("service", Scheme::Obs)),
         }?;
 
         let scheme = match uri.scheme_str() {
@@ -173,9 +236,21 @@ impl Builder {
             }
         };
 
-        debug!("backend use endpoint {}", &endpoint);
+        // Keep the port of user domain in request url, but not in
+        // CanonicalizedResource.
+        let authority = match uri.port_u16() {
+            Some(port) if !is_obs_default => format!("{}:{}", endpoint, port),
+            _ => endpoint.clone(),
+        };
+
+        debug!("backend use endpoint {}", &authority);
 
-        let client = HttpClient::new();
+        let client = match self.http_client.take() {
+            Some(client) => HttpClient::with_client(client),
+            None => {
+                HttpClient::with_pool_options(self.pool_max_idle_per_host, self.connect_timeout)
+            }
+        };
 
         let mut signer_builder = HuaweicloudObsSigner::builder();
         if let (Some(access_key_id), Some(secret_access_key)) =
@@ -199,9 +274,13 @@ impl Builder {
             signer_builder.bucket(&endpoint);
         }
 
+        // Presigned requests must be signed by static credentials.
+        let static_credential = self.access_key_id.is_some() && self.secret_access_key.is_some();
+
         let signer = signer_builder.build().map_err(|e| {
-            Error::new(ErrorKind::Unexpected, "build HuaweicloudObsSigner")
+            Error::new(ErrorKind::ConfigInvalid, "build HuaweicloudObsSigner")
                 .with_context("service", Scheme::Obs)
+                .with_context("field", "access_key_id")
                 .set_source(e)
         })?;
 
@@ -209,9 +288,10 @@ impl Builder {
         Ok(apply_wrapper(Backend {
             client,
             root,
-            endpoint: format!("{}://{}", &scheme, &endpoint),
+            endpoint: format!("{}://{}", &scheme, &authority),
             signer: Arc::new(signer),
             bucket,
+            static_credential,
         }))
     }
 }
@@ -224,18 +304,29 @@ pub struct Backend {
     endpoint: String,
     signer: Arc<HuaweicloudObsSigner>,
     bucket: String,
+    static_credential: bool,
 }
 
 #[async_trait]
 impl Accessor for Backend {
     fn metadata(&self) -> AccessorMetadata {
+        let mut capabilities = AccessorCapability::Read
+            | AccessorCapability::Write
+            | AccessorCapability::List
+            | AccessorCapability::ConditionalWrite
+            | AccessorCapability::ConditionalRead
+            | AccessorCapability::ListRecursive
+            | AccessorCapability::Copy
+            | AccessorCapability::Multipart;
+        if self.static_credential {
+            capabilities |= AccessorCapability::Presign;
+        }
+
         let mut am = AccessorMetadata::default();
         am.set_scheme(Scheme::Obs)
             .set_root(&self.root)
             .set_name(&self.bucket)
-            .set_capabilities(
-                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::List,
-            );
+            .set_capabilities(capabilities);
 
         am
     }
@@ -259,7 +350,7 @@ impl Accessor for Backend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
-        let resp = self.obs_get_object(path, args.range()).await?;
+        let resp = self.obs_get_object(path, &args).await?;
 
         let status = resp.status();
 
@@ -279,6 +370,10 @@ impl Accessor for Backend {
             args.content_type(),
             AsyncBody::Reader(r),
         )?;
+        insert_content_disposition(req.headers_mut(), &args)?;
+        insert_cache_control(req.headers_mut(), &args)?;
+        insert_expires(req.headers_mut(), &args)?;
+        insert_write_conditions(req.headers_mut(), &args)?;
 
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
 
@@ -288,8 +383,14 @@ impl Accessor for Backend {
 
         match status {
             StatusCode::CREATED | StatusCode::OK => {
+                let etag = parse_etag(resp.headers())?;
                 resp.into_body().consume().await?;
-                Ok(RpWrite::new(args.size()))
+
+                let rp = RpWrite::new(args.size());
+                Ok(match etag {
+                    Some(v) => rp.with_etag(&v),
+                    None => rp,
+                })
             }
             _ => Err(parse_error(resp).await?),
         }
@@ -328,34 +429,259 @@ impl Accessor for Backend {
         }
     }
 
-    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, ObjectPager)> {
+    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        let resp = self.obs_copy_object(from, to).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCopy::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         Ok((
             RpList::default(),
-            Box::new(DirStream::new(Arc::new(self.clone()), &self.root, path)),
+            Box::new(DirStream::new(
+                Arc::new(self.clone()),
+                &self.root,
+                path,
+                if args.recursive() { "" } else { "/" },
+            )),
         ))
     }
-}
 
-impl Backend {
-    async fn obs_get_object(
+    fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        if !self.static_credential {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "presign requires static credentials, please set access_key_id and secret_access_key",
+            )
+            .with_operation(Operation::Presign.into_static()));
+        }
+
+        // We will not send this request out, just for signing.
+        let mut req = match args.operation() {
+            PresignOperation::Stat(_) => self.obs_head_object_request(path)?,
+            PresignOperation::Read(v) => self.obs_get_object_request(path, v)?,
+            // OBS only signs content type.
+            PresignOperation::Write(v) => {
+                self.obs_put_object_request(path, None, v.content_type(), AsyncBody::Empty)?
+            }
+            PresignOperation::WriteMultipart(v) => self.obs_upload_part_request(
+                path,
+                v.upload_id(),
+                v.part_number(),
+                None,
+                AsyncBody::Empty,
+            )?,
+        };
+
+        self.obs_sign_query(&mut req, args.expire())?;
+
+        // We don't need this request anymore, consume it directly.
+        let (parts, _) = req.into_parts();
+
+        Ok(RpPresign::new(PresignedRequest::new(
+            parts.method,
+            parts.uri,
+            parts.headers,
+        )))
+    }
+
+    async fn create_multipart(
         &self,
         path: &str,
-        range: BytesRange,
-    ) -> Result<Response<IncomingAsyncBody>> {
+        _: OpCreateMultipart,
+    ) -> Result<RpCreateMultipart> {
+        let resp = self.obs_initiate_multipart_upload(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let result: InitiateMultipartUploadResult =
+                    quick_xml::de::from_reader(bs.reader()).map_err(parse_xml_deserialize_error)?;
+
+                Ok(RpCreateMultipart::new(&result.upload_id))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write_multipart(
+        &self,
+        path: &str,
+        args: OpWriteMultipart,
+        r: BytesReader,
+    ) -> Result<RpWriteMultipart> {
+        let resp = self
+            .obs_upload_part(
+                path,
+                args.upload_id(),
+                args.part_number(),
+                args.size(),
+                AsyncBody::Reader(r),
+            )
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let etag = parse_etag(resp.headers())?.ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "ETag not present in returning response",
+                    )
+                })?;
+
+                resp.into_body().consume().await?;
+
+                Ok(RpWriteMultipart::new(args.part_number(), &etag))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        let resp = self
+            .obs_complete_multipart_upload(path, args.upload_id(), args.parts())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+
+                Ok(RpCompleteMultipart::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        let resp = self
+            .obs_abort_multipart_upload(path, args.upload_id())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+
+                Ok(RpAbortMultipart::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+impl Backend {
+    /// Sign request in the query string.
+    ///
+    /// The string to sign of query authentication is the same as header
+    /// authentication except that `Date` is replaced by `Expires`. So we
+    /// sign the request with `Date` set to `Expires`, and move the signature
+    /// into query.
+    ///
+    /// Reference: [Authentication of Signature in a URL](https://support.huaweicloud.com/intl/en-us/api-obs/obs_04_0011.html)
+    fn obs_sign_query(&self, req: &mut Request<AsyncBody>, expire: time::Duration) -> Result<()> {
+        let expires = (time::OffsetDateTime::now_utc() + expire).unix_timestamp();
+
+        req.headers_mut().insert(
+            DATE,
+            expires.to_string().parse().map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "header value is invalid").set_source(e)
+            })?,
+        );
+        self.signer.sign(req).map_err(new_request_sign_error)?;
+
+        let auth = req
+            .headers_mut()
+            .remove(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok().map(|v| v.to_string()))
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Unexpected, "signed request has no authorization")
+            })?;
+        req.headers_mut().remove(DATE);
+
+        // Authorization is in the format of `OBS ak:signature`.
+        let (ak, signature) = auth
+            .strip_prefix("OBS ")
+            .and_then(|v| v.split_once(':'))
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Unexpected, "authorization is invalid")
+                    .with_context("authorization", &auth)
+            })?;
+
+        let uri = req.uri().to_string();
+        let uri = format!(
+            "{}{}AccessKeyId={}&Expires={}&Signature={}",
+            uri,
+            if uri.contains('?') { "&" } else { "?" },
+            percent_encode_path(ak),
+            expires,
+            percent_encode_path(signature)
+        );
+        *req.uri_mut() = uri.parse().map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "presigned uri is invalid")
+                .with_context("uri", &uri)
+                .set_source(e)
+        })?;
+
+        Ok(())
+    }
+
+    fn obs_get_object_request(&self, path: &str, args: &OpRead) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
 
         let mut req = Request::get(&url);
 
-        if !range.is_full() {
-            req = req.header(http::header::RANGE, range.to_header())
+        let range = args.range();
+        if let Some(range_header) = range.to_header() {
+            req = req.header(http::header::RANGE, range_header)
         }
 
-        let mut req = req
+        if let Some(if_match) = args.if_match() {
+            req = req.header(http::header::IF_MATCH, if_match);
+        }
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(http::header::IF_NONE_MATCH, if_none_match);
+        }
+
+        let req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
+        Ok(req)
+    }
+
+    async fn obs_get_object(
+        &self,
+        path: &str,
+        args: &OpRead,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self.obs_get_object_request(path, args)?;
+
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
 
         self.client.send_async(req).await
@@ -387,7 +713,7 @@ impl Backend {
         Ok(req)
     }
 
-    async fn obs_get_head_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+    fn obs_head_object_request(&self, path: &str) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
@@ -397,10 +723,16 @@ impl Backend {
 
         let req = Request::head(&url);
 
-        let mut req = req
+        let req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
+        Ok(req)
+    }
+
+    async fn obs_get_head_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self.obs_head_object_request(path)?;
+
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
 
         self.client.send_async(req).await
@@ -422,20 +754,46 @@ impl Backend {
         self.client.send_async(req).await
     }
 
+    async fn obs_copy_object(&self, from: &str, to: &str) -> Result<Response<IncomingAsyncBody>> {
+        let source = build_abs_path(&self.root, from);
+        let target = build_abs_path(&self.root, to);
+
+        let source = format!("/{}/{}", self.bucket, percent_encode_path(&source));
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&target));
+
+        let mut req = Request::put(&url)
+            .header("x-obs-copy-source", source)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        self.client.send_async(req).await
+    }
+
     pub(crate) async fn obs_list_objects(
         &self,
         path: &str,
+        delimiter: &str,
         next_marker: &str,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let mut url = format!("{}?delimiter=/", self.endpoint);
+        let mut queries = vec![];
+        // Empty delimiter means list all objects under prefix recursively.
+        if !delimiter.is_empty() {
+            queries.push(format!("delimiter={delimiter}"));
+        }
         if !path.is_empty() {
-            write!(url, "&prefix={}", percent_encode_path(&p))
-                .expect("write into string must succeed");
+            queries.push(format!("prefix={}", percent_encode_path(&p)));
         }
         if !next_marker.is_empty() {
-            write!(url, "&marker={next_marker}").expect("write into string must succeed");
+            queries.push(format!("marker={next_marker}"));
+        }
+
+        let mut url = self.endpoint.clone();
+        if !queries.is_empty() {
+            write!(url, "?{}", queries.join("&")).expect("write into string must succeed");
         }
 
         let mut req = Request::get(&url)
@@ -446,4 +804,326 @@ impl Backend {
 
         self.client.send_async(req).await
     }
+
+    async fn obs_initiate_multipart_upload(
+        &self,
+        path: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?uploads", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::post(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        self.client.send_async(req).await
+    }
+
+    fn obs_upload_part_request(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        size: Option<u64>,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?partNumber={}&uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            part_number,
+            upload_id
+        );
+
+        let mut req = Request::put(&url);
+
+        if let Some(size) = size {
+            req = req.header(CONTENT_LENGTH, size);
+        }
+
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    async fn obs_upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req =
+            self.obs_upload_part_request(path, upload_id, part_number, Some(size), body)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn obs_complete_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: &[ObjectPart],
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            upload_id
+        );
+
+        let content = quick_xml::se::to_string(&CompleteMultipartUploadRequest {
+            part: parts
+                .iter()
+                .map(|v| CompleteMultipartUploadRequestPart {
+                    part_number: v.part_number(),
+                    etag: v.etag().to_string(),
+                })
+                .collect(),
+        })
+        .map_err(parse_xml_deserialize_error)?;
+
+        // Make sure content length has been set to avoid post with chunked encoding.
+        let mut req = Request::post(&url)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/xml")
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn obs_abort_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            upload_id
+        );
+
+        let mut req = Request::delete(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        self.client.send_async(req).await
+    }
+}
+
+/// Result of InitiateMultipartUpload
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct InitiateMultipartUploadResult {
+    upload_id: String,
+}
+
+/// Request of CompleteMultipartUpload
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
+struct CompleteMultipartUploadRequest {
+    part: Vec<CompleteMultipartUploadRequestPart>,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct CompleteMultipartUploadRequestPart {
+    #[serde(rename = "$unflatten=PartNumber")]
+    part_number: usize,
+    #[serde(rename = "$unflatten=ETag")]
+    etag: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// This example is from https://support.huaweicloud.com/intl/en-us/api-obs/obs_04_0098.html
+    #[test]
+    fn test_deserialize_initiate_multipart_upload_result() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <InitiateMultipartUploadResult xmlns="http://obs.myhwclouds.com/doc/2015-06-30/">
+              <Bucket>bucketname</Bucket>
+              <Key>objectkey</Key>
+              <UploadId>DCD2FC98B4F70000013DF578ACA318E7</UploadId>
+            </InitiateMultipartUploadResult>"#,
+        );
+
+        let out: InitiateMultipartUploadResult =
+            quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(out.upload_id, "DCD2FC98B4F70000013DF578ACA318E7");
+    }
+
+    #[test]
+    fn test_serialize_complete_multipart_upload_request() {
+        let req = CompleteMultipartUploadRequest {
+            part: vec![
+                CompleteMultipartUploadRequestPart {
+                    part_number: 1,
+                    etag: "a54357aff0632cce46d942af68356b38".to_string(),
+                },
+                CompleteMultipartUploadRequestPart {
+                    part_number: 2,
+                    etag: "0c78aef83f66abc1fa1e8477f296d394".to_string(),
+                },
+            ],
+        };
+
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+
+        assert_eq!(
+            actual,
+            "<CompleteMultipartUpload>\
+             <Part><PartNumber>1</PartNumber><ETag>a54357aff0632cce46d942af68356b38</ETag></Part>\
+             <Part><PartNumber>2</PartNumber><ETag>0c78aef83f66abc1fa1e8477f296d394</ETag></Part>\
+             </CompleteMultipartUpload>"
+        );
+    }
+
+    #[test]
+    fn test_presign() {
+        let mut builder = Builder::default();
+        builder
+            .endpoint("https://obs.cn-north-4.myhuaweicloud.com")
+            .bucket("test")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+        assert!(acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Presign));
+
+        let req = acc
+            .presign(
+                "file",
+                OpPresign::new(OpRead::new(), time::Duration::hours(1)),
+            )
+            .expect("presign must succeed")
+            .into_presigned_request();
+
+        assert_eq!(req.method(), http::Method::GET);
+        assert!(!req.header().contains_key(AUTHORIZATION));
+        assert!(!req.header().contains_key(DATE));
+        let query = req.uri().query().expect("query must exist");
+        assert!(
+            query.starts_with("AccessKeyId=access_key_id&Expires="),
+            "{query}"
+        );
+        assert!(query.contains("&Signature="), "{query}");
+
+        let req = acc
+            .presign(
+                "file",
+                OpPresign::new(
+                    OpWriteMultipart::new("upload_id".to_string(), 1, 0),
+                    time::Duration::hours(1),
+                ),
+            )
+            .expect("presign must succeed")
+            .into_presigned_request();
+        let query = req.uri().query().expect("query must exist");
+        assert!(
+            query.starts_with("partNumber=1&uploadId=upload_id&AccessKeyId="),
+            "{query}"
+        );
+    }
+
+    #[test]
+    fn test_presign_without_static_credential() {
+        let mut builder = Builder::default();
+        builder
+            .endpoint("https://obs.cn-north-4.myhuaweicloud.com")
+            .bucket("test");
+        let acc = builder.build().expect("build must succeed");
+        assert!(!acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Presign));
+
+        let err = acc
+            .presign(
+                "file",
+                OpPresign::new(OpStat::new(), time::Duration::hours(1)),
+            )
+            .expect_err("presign must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_etag() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "5")
+                    .insert_header("etag", "\"5d41402abc4b2a76b9719d911017c592\""),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"5d41402abc4b2a76b9719d911017c592\""),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+
+        let rp = acc
+            .write(
+                "file",
+                OpWrite::new(5),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+            .await
+            .expect("write must succeed");
+        assert_eq!(rp.etag(), Some("5d41402abc4b2a76b9719d911017c592"));
+
+        let meta = acc
+            .stat("file", OpStat::new())
+            .await
+            .expect("stat must succeed")
+            .into_metadata();
+        assert_eq!(meta.etag(), Some("5d41402abc4b2a76b9719d911017c592"));
+    }
 }
@@ -32,17 +32,19 @@ pub struct DirStream {
     backend: Arc<Backend>,
     root: String,
     path: String,
+    delimiter: &'static str,
 
     next_marker: String,
     done: bool,
 }
 
 impl DirStream {
-    pub fn new(backend: Arc<Backend>, root: &str, path: &str) -> Self {
+    pub fn new(backend: Arc<Backend>, root: &str, path: &str, delimiter: &'static str) -> Self {
         Self {
             backend,
             root: root.to_string(),
             path: path.to_string(),
+            delimiter,
             next_marker: "".to_string(),
             done: false,
         }
@@ -58,7 +60,7 @@ impl ObjectPage for DirStream {
 
         let resp = self
             .backend
-            .obs_list_objects(&self.path, &self.next_marker)
+            .obs_list_objects(&self.path, self.delimiter, &self.next_marker)
             .await?;
 
         if resp.status() != http::StatusCode::OK {
@@ -37,27 +37,25 @@ struct ObsError {
 /// Parse error respons into Error.
 pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (parts, body) = resp.into_parts();
-    let bs = body.bytes().await?;
-
-    let (kind, retryable) = match parts.status {
-        StatusCode::NOT_FOUND => (ErrorKind::ObjectNotFound, false),
-        StatusCode::FORBIDDEN => (ErrorKind::ObjectPermissionDenied, false),
-        StatusCode::INTERNAL_SERVER_ERROR
-        | StatusCode::BAD_GATEWAY
-        | StatusCode::SERVICE_UNAVAILABLE
-        | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
-        // OBS could return `520 Origin Error` errors which should be retried.
-        v if v.as_u16() == 520 => (ErrorKind::Unexpected, true),
-
-        _ => (ErrorKind::Unexpected, false),
-    };
+    let (bs, truncated) = body.bytes_with_limit(ERROR_BODY_LIMIT).await?;
 
-    let message = match de::from_reader::<_, ObsError>(bs.clone().reader()) {
-        Ok(obs_error) => format!("{:?}", obs_error),
-        Err(_) => String::from_utf8_lossy(&bs).into_owned(),
+    let (kind, retryable, message) = match de::from_reader::<_, ObsError>(bs.clone().reader()) {
+        Ok(obs_error) => {
+            let (kind, retryable) = parse_error_code(parts.status, &obs_error.code);
+            (kind, retryable, format!("{:?}", obs_error))
+        }
+        Err(_) => {
+            let (kind, retryable) = parse_error_code(parts.status, "");
+            let mut message = String::from_utf8_lossy(&bs).into_owned();
+            if truncated {
+                message.push_str(&format!("... (truncated at {} bytes)", bs.len()));
+            }
+            (kind, retryable, message)
+        }
     };
 
     let mut err = Error::new(kind, &message).with_context("response", format!("{:?}", parts));
+    err = with_current_metadata(err, &parts.headers);
 
     if retryable {
         err = err.set_temporary();
@@ -66,8 +64,41 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     Ok(err)
 }
 
+/// Map the error code of obs into error kind and whether it's retryable.
+///
+/// Codes take precedence over the status code, since proxies could return
+/// error bodies with ambiguous status like `200 OK`.
+fn parse_error_code(status: StatusCode, code: &str) -> (ErrorKind, bool) {
+    match code {
+        "NoSuchKey" | "NoSuchBucket" => (ErrorKind::ObjectNotFound, false),
+        "AccessDenied" => (ErrorKind::ObjectPermissionDenied, false),
+        "SlowDown" | "ServiceUnavailable" => (ErrorKind::Unexpected, true),
+        _ => match status {
+            StatusCode::NOT_FOUND => (ErrorKind::ObjectNotFound, false),
+            StatusCode::FORBIDDEN => (ErrorKind::ObjectPermissionDenied, false),
+            StatusCode::NOT_MODIFIED | StatusCode::PRECONDITION_FAILED => {
+                (ErrorKind::ConditionNotMatch, false)
+            }
+            StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+            // OBS could return `520 Origin Error` errors which should be retried.
+            v if v.as_u16() == 520 => (ErrorKind::Unexpected, true),
+
+            _ => (ErrorKind::Unexpected, false),
+        },
+    }
+}
+
+pub fn parse_xml_deserialize_error(e: quick_xml::DeError) -> Error {
+    Error::new(ErrorKind::Unexpected, "deserialize xml").set_source(e)
+}
+
 #[cfg(test)]
 mod tests {
+    use futures::io::Cursor;
+
     use super::*;
 
     #[test]
@@ -97,4 +128,118 @@ mod tests {
             "RkRCRDJENDc5MzdGQkQ4OUY3MTI4NTQ3NDk2Mjg0M0FBQUFBQUFBYmJiYmJiYmJD"
         );
     }
+
+    #[tokio::test]
+    async fn test_parse_error_truncated() {
+        let mut bs = b"<Error><Code>NoSuchKey</Code><Message>".to_vec();
+        bs.extend(vec![b'x'; 2 * ERROR_BODY_LIMIT]);
+        bs.extend(b"</Message></Error>");
+        let body = IncomingAsyncBody::new(Box::new(Cursor::new(bs)));
+        let resp = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(body)
+            .expect("response must be valid");
+
+        let err = parse_error(resp).await.expect("parse must succeed");
+        assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+        let s = err.to_string();
+        assert!(
+            s.ends_with(&format!("... (truncated at {ERROR_BODY_LIMIT} bytes)")),
+            "{s}"
+        );
+        assert!(s.len() < 2 * ERROR_BODY_LIMIT, "{}", s.len());
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_precondition_failed() {
+        let bs = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+<Code>PreconditionFailed</Code>
+<Message>At least one of the pre-conditions you specified did not hold</Message>
+</Error>"#;
+        let body = IncomingAsyncBody::new(Box::new(Cursor::new(bs.as_bytes())));
+        let resp = Response::builder()
+            .status(StatusCode::PRECONDITION_FAILED)
+            .header("etag", "\"current\"")
+            .body(body)
+            .expect("response must be valid");
+
+        let err = parse_error(resp).await.expect("parse must succeed");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        assert!(err.to_string().contains("PreconditionFailed"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_code() {
+        for (status, code, kind, temporary) in [
+            (
+                StatusCode::NOT_FOUND,
+                "NoSuchKey",
+                ErrorKind::ObjectNotFound,
+                false,
+            ),
+            (
+                StatusCode::NOT_FOUND,
+                "NoSuchBucket",
+                ErrorKind::ObjectNotFound,
+                false,
+            ),
+            (
+                StatusCode::FORBIDDEN,
+                "AccessDenied",
+                ErrorKind::ObjectPermissionDenied,
+                false,
+            ),
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "SlowDown",
+                ErrorKind::Unexpected,
+                true,
+            ),
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "ServiceUnavailable",
+                ErrorKind::Unexpected,
+                true,
+            ),
+            // Proxies could return error bodies with ambiguous status.
+            (
+                StatusCode::OK,
+                "NoSuchKey",
+                ErrorKind::ObjectNotFound,
+                false,
+            ),
+            (
+                StatusCode::OK,
+                "AccessDenied",
+                ErrorKind::ObjectPermissionDenied,
+                false,
+            ),
+            (StatusCode::OK, "SlowDown", ErrorKind::Unexpected, true),
+            // Unknown codes fallback to the status code.
+            (
+                StatusCode::FORBIDDEN,
+                "InvalidAccessKeyId",
+                ErrorKind::ObjectPermissionDenied,
+                false,
+            ),
+        ] {
+            let bs = format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+<Code>{code}</Code>
+<Message>message</Message>
+</Error>"#
+            );
+            let body = IncomingAsyncBody::new(Box::new(Cursor::new(bs.into_bytes())));
+            let resp = Response::builder()
+                .status(status)
+                .body(body)
+                .expect("response must be valid");
+
+            let err = parse_error(resp).await.expect("parse must succeed");
+            assert_eq!(err.kind(), kind, "{status} {code}");
+            assert_eq!(err.is_temporary(), temporary, "{status} {code}");
+        }
+    }
 }
@@ -96,4 +96,4 @@ mod backend;
 pub use backend::Builder;
 
 mod dir_stream;
-mod error;
+pub(super) mod error;
@@ -17,9 +17,13 @@ use std::fmt::Formatter;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use bytes::Buf;
+use bytes::Bytes;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::header::HOST;
+use http::header::IF_MATCH;
+use http::header::IF_NONE_MATCH;
 use http::header::RANGE;
 use http::Request;
 use http::Response;
@@ -28,9 +32,12 @@ use http::Uri;
 use log::debug;
 use reqsign::AliyunOssBuilder;
 use reqsign::AliyunOssSigner;
+use serde::Deserialize;
+use serde::Serialize;
 
 use super::dir_stream::DirStream;
 use super::error::parse_error;
+use super::error::parse_xml_deserialize_error;
 use crate::raw::*;
 use crate::*;
 
@@ -40,6 +47,7 @@ pub struct Builder {
     root: Option<String>,
 
     endpoint: Option<String>,
+    internal_endpoint: Option<String>,
     bucket: String,
 
     // authenticate options
@@ -55,6 +63,7 @@ impl Debug for Builder {
         d.field("root", &self.root)
             .field("bucket", &self.bucket)
             .field("endpoint", &self.endpoint)
+            .field("internal_endpoint", &self.internal_endpoint)
             .field("allow_anonymous", &self.allow_anonymous);
 
         if self.access_key_id.is_some() {
@@ -70,6 +79,16 @@ impl Debug for Builder {
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "bucket",
+        "endpoint",
+        "internal_endpoint",
+        "access_key_id",
+        "access_key_secret",
+        "allow_anonymous",
+    ];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
         for (k, v) in it {
@@ -78,6 +97,7 @@ impl Builder {
                 "root" => builder.root(v),
                 "bucket" => builder.bucket(v),
                 "endpoint" => builder.endpoint(v),
+                "internal_endpoint" => builder.internal_endpoint(v),
 
                 "access_key_id" => builder.access_key_id(v),
                 "access_key_secret" => builder.access_key_secret(v),
@@ -118,6 +138,22 @@ impl Builder {
         self
     }
 
+    /// Set internal endpoint of this backend.
+    ///
+    /// OSS provides internal endpoints like `https://oss-cn-beijing-internal.aliyuncs.com`
+    /// which are free of traffic charges when accessed from ECS in the same region.
+    ///
+    /// If set, all requests will be sent to the internal endpoint, while
+    /// presigned requests still use [`Builder::endpoint`] so that they
+    /// can be accessed from outside.
+    pub fn internal_endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.internal_endpoint = Some(endpoint.trim_end_matches('/').to_string())
+        }
+
+        self
+    }
+
     /// Set access_key_id of this backend.
     ///
     /// - If access_key_id is set, we will take user's input first.
@@ -158,37 +194,25 @@ impl Builder {
         // Handle endpoint, region and bucket name.
         let bucket = match self.bucket.is_empty() {
             false => Ok(&self.bucket),
-            true => Err(
-                Error::new(ErrorKind::BackendConfigInvalid, "bucket is empty")
-                    .with_context("service", Scheme::Oss),
-            ),
+            true => Err(Error::new(ErrorKind::ConfigInvalid, "bucket is empty")
+                .with_context("field", "bucket")
+                .with_context("service", Scheme::Oss)),
         }?;
         debug!("backend use bucket {}", &bucket);
 
-        let (endpoint, host) = match self.endpoint.clone() {
-            Some(ep) => {
-                let uri = ep.parse::<Uri>().map_err(|err| {
-                    Error::new(ErrorKind::BackendConfigInvalid, "endpoint is invalid")
-                        .with_context("service", Scheme::Oss)
-                        .with_context("endpoint", &ep)
-                        .set_source(err)
-                })?;
-                let host = uri.host().ok_or_else(|| {
-                    Error::new(ErrorKind::BackendConfigInvalid, "endpoint host is empty")
-                        .with_context("service", Scheme::Oss)
-                        .with_context("endpoint", &ep)
-                })?;
-                let full_host = format!("{}.{}", bucket, host);
-                let endpoint = format!("https://{}", full_host);
-                (endpoint, full_host)
-            }
+        let (presign_endpoint, presign_host) = match &self.endpoint {
+            Some(ep) => parse_endpoint(ep, bucket, "endpoint")?,
             None => {
-                return Err(
-                    Error::new(ErrorKind::BackendConfigInvalid, "endpoint is empty")
-                        .with_context("service", Scheme::Oss),
-                );
+                return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                    .with_context("field", "endpoint")
+                    .with_context("service", Scheme::Oss));
             }
         };
+        let (endpoint, host) = match &self.internal_endpoint {
+            Some(ep) => parse_endpoint(ep, bucket, "internal_endpoint")?,
+            None => (presign_endpoint.clone(), presign_host.clone()),
+        };
+        debug!("backend use endpoint {}", &endpoint);
 
         let mut signer_builder = AliyunOssBuilder::default();
 
@@ -204,7 +228,8 @@ impl Builder {
         }
 
         let signer = signer_builder.build().map_err(|e| {
-            Error::new(ErrorKind::BackendConfigInvalid, "build AliyunOssSigner")
+            Error::new(ErrorKind::ConfigInvalid, "build AliyunOssSigner")
+                .with_context("field", "access_key_id")
                 .with_context("service", Scheme::Oss)
                 .with_context("endpoint", &endpoint)
                 .with_context("bucket", bucket)
@@ -217,6 +242,8 @@ impl Builder {
             root,
             endpoint,
             host,
+            presign_endpoint,
+            presign_host,
             client: HttpClient::new(),
             bucket: self.bucket.clone(),
             signer: Arc::new(signer),
@@ -236,9 +263,33 @@ pub struct Backend {
     /// format: <bucket-name>.<endpoint-domain-name>
     host: String,
     endpoint: String,
+    /// Public endpoint and host used by presigned requests, which differ
+    /// from `endpoint` and `host` only if internal endpoint is set.
+    presign_host: String,
+    presign_endpoint: String,
     signer: Arc<AliyunOssSigner>,
 }
 
+/// Parse endpoint into `(endpoint, host)` with bucket name prepended to host.
+fn parse_endpoint(ep: &str, bucket: &str, field: &'static str) -> Result<(String, String)> {
+    let uri = ep.parse::<Uri>().map_err(|err| {
+        Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+            .with_context("field", field)
+            .with_context("service", Scheme::Oss)
+            .with_context("endpoint", ep)
+            .set_source(err)
+    })?;
+    let host = uri.host().ok_or_else(|| {
+        Error::new(ErrorKind::ConfigInvalid, "endpoint host is empty")
+            .with_context("field", field)
+            .with_context("service", Scheme::Oss)
+            .with_context("endpoint", ep)
+    })?;
+    let full_host = format!("{}.{}", bucket, host);
+    let endpoint = format!("https://{}", full_host);
+    Ok((endpoint, full_host))
+}
+
 impl Debug for Backend {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Backend")
@@ -246,6 +297,7 @@ impl Debug for Backend {
             .field("bucket", &self.bucket)
             .field("endpoint", &self.endpoint)
             .field("host", &self.host)
+            .field("presign_endpoint", &self.presign_endpoint)
             .finish()
     }
 }
@@ -261,7 +313,10 @@ impl Accessor for Backend {
                 AccessorCapability::Read
                     | AccessorCapability::Write
                     | AccessorCapability::List
-                    | AccessorCapability::Presign,
+                    | AccessorCapability::Presign
+                    | AccessorCapability::ConditionalRead
+                    | AccessorCapability::ListRecursive
+                    | AccessorCapability::Multipart,
             );
         am
     }
@@ -282,7 +337,7 @@ impl Accessor for Backend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
-        let resp = self.oss_get_object(path, args.range()).await?;
+        let resp = self.oss_get_object(path, &args).await?;
 
         let status = resp.status();
 
@@ -296,14 +351,16 @@ impl Accessor for Backend {
     }
 
     async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
-        let resp = self
-            .oss_put_object(
-                path,
-                Some(args.size()),
-                args.content_type(),
-                AsyncBody::Reader(r),
-            )
-            .await?;
+        let mut req = self.oss_put_object_request(
+            path,
+            Some(args.size()),
+            args.content_type(),
+            AsyncBody::Reader(r),
+        )?;
+        insert_expires(req.headers_mut(), &args)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        let resp = self.client.send_async(req).await?;
 
         let status = resp.status();
         match status {
@@ -348,29 +405,160 @@ impl Accessor for Backend {
         }
     }
 
-    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, ObjectPager)> {
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         Ok((
             RpList::default(),
-            Box::new(DirStream::new(Arc::new(self.clone()), &self.root, path)),
+            Box::new(DirStream::new(
+                Arc::new(self.clone()),
+                &self.root,
+                path,
+                if args.recursive() { "" } else { "/" },
+            )),
         ))
     }
 
+    async fn create_multipart(
+        &self,
+        path: &str,
+        _: OpCreateMultipart,
+    ) -> Result<RpCreateMultipart> {
+        let mut req = self.oss_initiate_multipart_upload_request(path)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        let resp = self.client.send_async(req).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let result: InitiateMultipartUploadResult =
+                    quick_xml::de::from_reader(bs.reader()).map_err(parse_xml_deserialize_error)?;
+
+                Ok(RpCreateMultipart::new(&result.upload_id))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write_multipart(
+        &self,
+        path: &str,
+        args: OpWriteMultipart,
+        r: BytesReader,
+    ) -> Result<RpWriteMultipart> {
+        let mut req = self.oss_upload_part_request(
+            path,
+            args.upload_id(),
+            args.part_number(),
+            Some(args.size()),
+            AsyncBody::Reader(r),
+        )?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        let resp = self.client.send_async(req).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                let etag = parse_etag(resp.headers())?.ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "ETag not present in returning response",
+                    )
+                })?;
+
+                resp.into_body().consume().await?;
+
+                Ok(RpWriteMultipart::new(args.part_number(), &etag))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        let mut req =
+            self.oss_complete_multipart_upload_request(path, args.upload_id(), args.parts())?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        let resp = self.client.send_async(req).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCompleteMultipart::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        let mut req = self.oss_abort_multipart_upload_request(path, args.upload_id())?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        let resp = self.client.send_async(req).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+                Ok(RpAbortMultipart::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
     fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         // We will not send this request out, just for signing.
         let mut req = match args.operation() {
             PresignOperation::Stat(_) => self.oss_head_object_request(path)?,
-            PresignOperation::Read(v) => self.oss_get_object_request(path, v.range())?,
-            PresignOperation::Write(_) => {
-                self.oss_put_object_request(path, None, None, AsyncBody::Empty)?
-            }
-            _ => {
-                return Err(Error::new(
-                    ErrorKind::Unsupported,
-                    "oss doesn't support multipart now",
-                ))
-            }
+            PresignOperation::Read(v) => self.oss_get_object_request(path, v)?,
+            // OSS only signs content type, content length will be returned in
+            // headers so that callers know what to send.
+            PresignOperation::Write(v) => self.oss_put_object_request(
+                path,
+                (v.size() > 0).then(|| v.size()),
+                v.content_type(),
+                AsyncBody::Empty,
+            )?,
+            PresignOperation::WriteMultipart(v) => self.oss_upload_part_request(
+                path,
+                v.upload_id(),
+                v.part_number(),
+                None,
+                AsyncBody::Empty,
+            )?,
         };
 
+        // Presigned requests could be sent from outside, use the public endpoint instead.
+        if self.presign_endpoint != self.endpoint {
+            let path_and_query = req
+                .uri()
+                .path_and_query()
+                .map(|v| v.as_str())
+                .unwrap_or("/");
+            *req.uri_mut() = format!("{}{}", self.presign_endpoint, path_and_query)
+                .parse()
+                .map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "presign uri is invalid").set_source(err)
+                })?;
+            req.headers_mut().insert(
+                HOST,
+                self.presign_host.parse().map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "presign host is invalid").set_source(err)
+                })?,
+            );
+        }
+
         self.signer
             .sign_query(&mut req, args.expire())
             .map_err(new_request_sign_error)?;
@@ -412,7 +600,104 @@ impl Backend {
         Ok(req)
     }
 
-    fn oss_get_object_request(&self, path: &str, range: BytesRange) -> Result<Request<AsyncBody>> {
+    fn oss_initiate_multipart_upload_request(&self, path: &str) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?uploads", self.endpoint, percent_encode_path(&p));
+
+        let req = Request::post(&url)
+            .header(HOST, &self.host)
+            .header(CONTENT_LENGTH, 0)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        Ok(req)
+    }
+
+    fn oss_upload_part_request(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        size: Option<u64>,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?partNumber={}&uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            part_number,
+            upload_id
+        );
+
+        let mut req = Request::put(&url).header(HOST, &self.host);
+
+        if let Some(size) = size {
+            req = req.header(CONTENT_LENGTH, size);
+        }
+
+        let req = req.body(body).map_err(new_request_build_error)?;
+        Ok(req)
+    }
+
+    fn oss_complete_multipart_upload_request(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: &[ObjectPart],
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            upload_id
+        );
+
+        let content = quick_xml::se::to_string(&CompleteMultipartUploadRequest {
+            part: parts
+                .iter()
+                .map(|v| CompleteMultipartUploadRequestPart {
+                    part_number: v.part_number(),
+                    etag: v.etag().to_string(),
+                })
+                .collect(),
+        })
+        .map_err(parse_xml_deserialize_error)?;
+
+        let req = Request::post(&url)
+            .header(HOST, &self.host)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/xml")
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+        Ok(req)
+    }
+
+    fn oss_abort_multipart_upload_request(
+        &self,
+        path: &str,
+        upload_id: &str,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            upload_id
+        );
+
+        let req = Request::delete(&url)
+            .header(HOST, &self.host)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        Ok(req)
+    }
+
+    fn oss_get_object_request(&self, path: &str, args: &OpRead) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
@@ -422,13 +707,21 @@ impl Backend {
             .header(HOST, &self.host)
             .header(CONTENT_TYPE, "application/octet-stream");
 
-        if !range.is_full() {
-            req = req.header(RANGE, range.to_header());
+        let range = args.range();
+        if let Some(range_header) = range.to_header() {
+            req = req.header(RANGE, range_header);
             // Adding `x-oss-range-behavior` header to use standard behavior.
             // ref: https://help.aliyun.com/document_detail/39571.html
             req = req.header("x-oss-range-behavior", "standard");
         }
 
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, if_none_match);
+        }
+
         let req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
@@ -469,13 +762,20 @@ impl Backend {
     fn oss_list_object_request(
         &self,
         path: &str,
+        delimiter: &str,
         token: Option<String>,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
-            "{}/?list-type=2&delimiter=/&prefix={}{}",
+            "{}/?list-type=2{}&prefix={}{}",
             self.endpoint,
+            // Empty delimiter means list all objects under prefix recursively.
+            if delimiter.is_empty() {
+                "".to_string()
+            } else {
+                format!("&delimiter={delimiter}")
+            },
             percent_encode_path(&p),
             token
                 .map(|t| format!("&continuation-token={}", percent_encode_path(&t)))
@@ -492,9 +792,9 @@ impl Backend {
     async fn oss_get_object(
         &self,
         path: &str,
-        range: BytesRange,
+        args: &OpRead,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.oss_get_object_request(path, range)?;
+        let mut req = self.oss_get_object_request(path, args)?;
 
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
         self.client.send_async(req).await
@@ -523,9 +823,10 @@ impl Backend {
     pub(super) async fn oss_list_object(
         &self,
         path: &str,
+        delimiter: &str,
         token: Option<String>,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.oss_list_object_request(path, token)?;
+        let mut req = self.oss_list_object_request(path, delimiter, token)?;
 
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
         self.client.send_async(req).await
@@ -537,3 +838,130 @@ impl Backend {
         self.client.send_async(req).await
     }
 }
+
+/// Result of InitiateMultipartUpload
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct InitiateMultipartUploadResult {
+    upload_id: String,
+}
+
+/// Request of CompleteMultipartUpload
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
+struct CompleteMultipartUploadRequest {
+    part: Vec<CompleteMultipartUploadRequestPart>,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct CompleteMultipartUploadRequestPart {
+    #[serde(rename = "$unflatten=PartNumber")]
+    part_number: usize,
+    #[serde(rename = "$unflatten=ETag")]
+    etag: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_complete_multipart_upload_request() {
+        let req = CompleteMultipartUploadRequest {
+            part: vec![CompleteMultipartUploadRequestPart {
+                part_number: 1,
+                etag: "3349DC700140D7F86A0784842780****".to_string(),
+            }],
+        };
+
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+
+        assert_eq!(
+            actual,
+            "<CompleteMultipartUpload>\
+             <Part><PartNumber>1</PartNumber><ETag>3349DC700140D7F86A0784842780****</ETag></Part>\
+             </CompleteMultipartUpload>"
+        );
+    }
+
+    #[test]
+    fn test_presign_write_multipart() {
+        let mut builder = Builder::default();
+        builder
+            .endpoint("http://oss-cn-hangzhou.aliyuncs.com")
+            .bucket("test")
+            .access_key_id("access_key_id")
+            .access_key_secret("access_key_secret");
+        let acc = builder.build().expect("build must succeed");
+
+        let presign = |part_number| {
+            let op = OpPresign::new(
+                OpWriteMultipart::new("upload_id".to_string(), part_number, 0),
+                time::Duration::hours(1),
+            );
+            acc.presign("file", op)
+                .expect("presign must succeed")
+                .into_presigned_request()
+        };
+        let signature = |req: &PresignedRequest| {
+            req.uri()
+                .query()
+                .expect("query must exist")
+                .split('&')
+                .find_map(|v| v.strip_prefix("Signature="))
+                .expect("signature must exist")
+                .to_string()
+        };
+
+        let req = presign(1);
+        assert_eq!(req.method(), http::Method::PUT);
+        let query = req.uri().query().expect("query must exist");
+        assert!(query.contains("partNumber=1"), "{query}");
+        assert!(query.contains("uploadId=upload_id"), "{query}");
+
+        // `partNumber` and `uploadId` are sub-resources which are signed.
+        assert_ne!(signature(&req), signature(&presign(2)));
+    }
+
+    #[test]
+    fn test_presign_with_internal_endpoint() {
+        let mut builder = Builder::default();
+        builder
+            .endpoint("https://oss-cn-hangzhou.aliyuncs.com")
+            .internal_endpoint("https://oss-cn-hangzhou-internal.aliyuncs.com")
+            .bucket("test")
+            .access_key_id("access_key_id")
+            .access_key_secret("access_key_secret");
+        let acc = builder.build().expect("build must succeed");
+
+        let req = acc
+            .presign(
+                "file",
+                OpPresign::new(OpRead::new(), time::Duration::hours(1)),
+            )
+            .expect("presign must succeed")
+            .into_presigned_request();
+        assert_eq!(
+            req.uri().host(),
+            Some("test.oss-cn-hangzhou.aliyuncs.com"),
+            "presign must use public endpoint"
+        );
+        assert_eq!(req.uri().path(), "/file");
+        assert_eq!(
+            req.header().get(HOST).and_then(|v| v.to_str().ok()),
+            Some("test.oss-cn-hangzhou.aliyuncs.com")
+        );
+    }
+
+    #[test]
+    fn test_build_with_invalid_internal_endpoint() {
+        let mut builder = Builder::default();
+        builder
+            .endpoint("https://oss-cn-hangzhou.aliyuncs.com")
+            .internal_endpoint("/no-host")
+            .bucket("test");
+        let err = builder.build().expect_err("build must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+}
@@ -35,6 +35,7 @@ pub struct DirStream {
     backend: Arc<Backend>,
     root: String,
     path: String,
+    delimiter: &'static str,
 
     token: Option<String>,
 
@@ -42,11 +43,12 @@ pub struct DirStream {
 }
 
 impl DirStream {
-    pub fn new(backend: Arc<Backend>, root: &str, path: &str) -> Self {
+    pub fn new(backend: Arc<Backend>, root: &str, path: &str, delimiter: &'static str) -> Self {
         Self {
             backend,
             root: root.to_string(),
             path: path.to_string(),
+            delimiter,
 
             token: None,
 
@@ -64,7 +66,7 @@ impl ObjectPage for DirStream {
 
         let resp = self
             .backend
-            .oss_list_object(&self.path, self.token.clone())
+            .oss_list_object(&self.path, self.delimiter, self.token.clone())
             .await?;
 
         if resp.status() != http::StatusCode::OK {
@@ -95,7 +97,7 @@ impl ObjectPage for DirStream {
             }
             let mut meta = ObjectMetadata::new(ObjectMode::FILE);
 
-            meta.set_etag(&object.etag);
+            meta.set_etag(&normalize_etag(&object.etag));
             meta.set_content_length(object.size);
             let dt = OffsetDateTime::parse(object.last_modified.as_str(), &Rfc3339)
                 .map(|v| {
@@ -38,22 +38,19 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (parts, body) = resp.into_parts();
     let bs = body.bytes().await?;
 
-    let (kind, retryable) = match parts.status {
-        StatusCode::NOT_FOUND => (ErrorKind::ObjectNotFound, false),
-        StatusCode::FORBIDDEN => (ErrorKind::ObjectPermissionDenied, false),
-        StatusCode::INTERNAL_SERVER_ERROR
-        | StatusCode::BAD_GATEWAY
-        | StatusCode::SERVICE_UNAVAILABLE
-        | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
-        _ => (ErrorKind::Unexpected, false),
-    };
-
-    let message = match de::from_reader::<_, OssError>(bs.clone().reader()) {
-        Ok(oss_err) => format!("{:?}", oss_err),
-        Err(_) => String::from_utf8_lossy(&bs).into_owned(),
+    let (kind, retryable, message) = match de::from_reader::<_, OssError>(bs.clone().reader()) {
+        Ok(oss_error) => {
+            let (kind, retryable) = parse_error_code(parts.status, &oss_error.code);
+            (kind, retryable, format!("{:?}", oss_error))
+        }
+        Err(_) => {
+            let (kind, retryable) = parse_error_code(parts.status, "");
+            (kind, retryable, String::from_utf8_lossy(&bs).into_owned())
+        }
     };
 
     let mut err = Error::new(kind, &message).with_context("response", format!("{:?}", parts));
+    err = with_current_metadata(err, &parts.headers);
 
     if retryable {
         err = err.set_temporary();
@@ -62,8 +59,39 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     Ok(err)
 }
 
+/// Map the error code of oss into error kind and whether it's retryable.
+///
+/// Error codes could be found at <https://www.alibabacloud.com/help/en/object-storage-service/latest/error-responses>
+fn parse_error_code(status: StatusCode, code: &str) -> (ErrorKind, bool) {
+    match code {
+        "NoSuchKey" | "NoSuchBucket" => (ErrorKind::ObjectNotFound, false),
+        "AccessDenied" => (ErrorKind::ObjectPermissionDenied, false),
+        // OSS returns `403 RequestTimeTooSkewed` if the local clock drifts
+        // too much, the request could succeed after the time is synced.
+        "RequestTimeTooSkewed" => (ErrorKind::Unexpected, true),
+        _ => match status {
+            StatusCode::NOT_FOUND => (ErrorKind::ObjectNotFound, false),
+            StatusCode::FORBIDDEN => (ErrorKind::ObjectPermissionDenied, false),
+            StatusCode::NOT_MODIFIED | StatusCode::PRECONDITION_FAILED => {
+                (ErrorKind::ConditionNotMatch, false)
+            }
+            StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+            _ => (ErrorKind::Unexpected, false),
+        },
+    }
+}
+
+pub fn parse_xml_deserialize_error(e: quick_xml::DeError) -> Error {
+    Error::new(ErrorKind::Unexpected, "deserialize xml").set_source(e)
+}
+
 #[cfg(test)]
 mod tests {
+    use futures::io::Cursor;
+
     use super::*;
 
     /// Error response example is from https://www.alibabacloud.com/help/en/object-storage-service/latest/error-responses
@@ -97,4 +125,47 @@ mod tests {
         assert_eq!(out.request_id, "1D842BC54255****");
         assert_eq!(out.host_id, "oss-cn-hangzhou.aliyuncs.com");
     }
+
+    #[tokio::test]
+    async fn test_parse_error_code() {
+        for (status, code, kind, retryable) in [
+            (
+                StatusCode::NOT_FOUND,
+                "NoSuchKey",
+                ErrorKind::ObjectNotFound,
+                false,
+            ),
+            (
+                StatusCode::FORBIDDEN,
+                "AccessDenied",
+                ErrorKind::ObjectPermissionDenied,
+                false,
+            ),
+            (
+                StatusCode::FORBIDDEN,
+                "RequestTimeTooSkewed",
+                ErrorKind::Unexpected,
+                true,
+            ),
+        ] {
+            let bs = format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>{code}</Code>
+  <Message>error message</Message>
+  <RequestId>1D842BC54255****</RequestId>
+  <HostId>test.oss-cn-hangzhou.aliyuncs.com</HostId>
+</Error>"#
+            );
+            let body = IncomingAsyncBody::new(Box::new(Cursor::new(bs.into_bytes())));
+            let resp = Response::builder()
+                .status(status)
+                .body(body)
+                .expect("response must be valid");
+
+            let err = parse_error(resp).await.expect("parse must succeed");
+            assert_eq!(err.kind(), kind, "{code}");
+            assert_eq!(err.is_temporary(), retryable, "{code}");
+        }
+    }
 }
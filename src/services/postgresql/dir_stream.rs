@@ -0,0 +1,120 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::backend::Backend;
+use crate::raw::*;
+use crate::ObjectMetadata;
+use crate::ObjectMode;
+use crate::Result;
+
+/// Max count of records returned in one page.
+const PAGE_SIZE: i64 = 1000;
+
+/// DirStream lists records by `LIKE prefix%` and groups them by `/`.
+///
+/// Records are returned in byte order of key, so we can use the last key
+/// as the cursor of next page.
+pub struct DirStream {
+    backend: Arc<Backend>,
+    root: String,
+    path: String,
+
+    /// The cursor of next page.
+    start_after: String,
+    /// The last dir we have returned, used to skip the nested records.
+    last_dir: Option<String>,
+    done: bool,
+}
+
+impl DirStream {
+    pub fn new(backend: Arc<Backend>, root: &str, path: &str) -> Self {
+        Self {
+            backend,
+            root: root.to_string(),
+            path: path.to_string(),
+
+            start_after: "".to_string(),
+            last_dir: None,
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectPage for DirStream {
+    async fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let prefix = build_abs_path(&self.root, &self.path);
+
+        let records = self
+            .backend
+            .postgresql_list(&prefix, &self.start_after, PAGE_SIZE)
+            .await?;
+        if (records.len() as i64) < PAGE_SIZE {
+            self.done = true;
+        }
+
+        let mut entries = Vec::with_capacity(records.len());
+        for (key, meta) in records {
+            self.start_after = key.clone();
+
+            // Skip the dir itself.
+            if key == prefix {
+                continue;
+            }
+
+            let rest = &key[prefix.len()..];
+            match rest.find('/') {
+                // Files or dirs directly under this dir.
+                None => entries.push(ObjectEntry::new(
+                    &build_rel_path(&self.root, &key),
+                    meta.with_complete(),
+                )),
+                Some(idx) => {
+                    let dir = format!("{}{}", prefix, &rest[..=idx]);
+                    if self.last_dir.as_deref() == Some(dir.as_str()) {
+                        continue;
+                    }
+
+                    entries.push(ObjectEntry::new(
+                        &build_rel_path(&self.root, &dir),
+                        ObjectMetadata::new(ObjectMode::DIR),
+                    ));
+                    self.last_dir = Some(dir);
+                }
+            }
+        }
+
+        // Skip all nested records of the last dir, `0` is the next char of `/`.
+        if let Some(dir) = &self.last_dir {
+            let skip_to = format!("{}0", dir.trim_end_matches('/'));
+            if skip_to > self.start_after {
+                self.start_after = skip_to;
+            }
+        }
+
+        Ok(if entries.is_empty() && self.done {
+            None
+        } else {
+            Some(entries)
+        })
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bb8::RunError;
+
+use crate::Error;
+use crate::ErrorKind;
+
+impl From<tokio_postgres::Error> for Error {
+    fn from(e: tokio_postgres::Error) -> Self {
+        // Allow retry for closed connections and io errors.
+        let retryable = e.is_closed()
+            || std::error::Error::source(&e)
+                .map(|v| v.is::<std::io::Error>())
+                .unwrap_or_default();
+
+        let mut err = Error::new(ErrorKind::Unexpected, "postgresql error").set_source(e);
+
+        if retryable {
+            err = err.set_temporary();
+        }
+
+        err
+    }
+}
+
+/// Errors returned by connection pool are always temporary.
+pub fn parse_pool_error(e: RunError<tokio_postgres::Error>) -> Error {
+    match e {
+        RunError::User(err) => Error::new(ErrorKind::Unexpected, "get connection from pool")
+            .set_source(err)
+            .set_temporary(),
+        RunError::TimedOut => {
+            Error::new(ErrorKind::Unexpected, "get connection from pool: timeout").set_temporary()
+        }
+    }
+}
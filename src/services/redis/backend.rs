@@ -19,15 +19,25 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use http::Uri;
+use redis::aio::ConnectionLike;
 use redis::aio::ConnectionManager;
 use redis::AsyncCommands;
 use redis::Client;
+use redis::Cmd;
 use redis::ConnectionAddr;
 use redis::ConnectionInfo;
+use redis::Pipeline;
 use redis::RedisConnectionInfo;
 use redis::RedisError;
+use redis::RedisFuture;
+use redis::Value;
 use tokio::sync::OnceCell;
 
+use super::cluster::ClusterConnection;
+#[cfg(feature = "services-redis-tls")]
+use super::tls::TlsClient;
+#[cfg(feature = "services-redis-tls")]
+use super::tls::TlsConnection;
 use crate::raw::adapters::kv;
 use crate::raw::*;
 use crate::Error;
@@ -45,6 +55,10 @@ pub struct Builder {
     ///
     /// default is "tcp://127.0.0.1:6379"
     endpoint: Option<String>,
+    /// network addresses of the Redis cluster nodes, separated by `,`.
+    ///
+    /// default is None
+    cluster_endpoints: Option<String>,
     /// the username to connect redis service.
     ///
     /// default is None
@@ -63,9 +77,29 @@ pub struct Builder {
     db: i64,
     /// The default ttl for put operations.
     default_ttl: Option<Duration>,
+    /// whether to connect redis over TLS.
+    ///
+    /// default is false
+    enable_tls: bool,
+    /// path to the PEM encoded CA certificate used to verify redis server.
+    ///
+    /// default is None
+    ca_cert: Option<String>,
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "endpoint",
+        "cluster_endpoints",
+        "username",
+        "password",
+        "db",
+        "default_ttl",
+        "enable_tls",
+        "ca_cert",
+    ];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
         for (k, v) in it {
@@ -73,12 +107,19 @@ impl Builder {
             match k.as_ref() {
                 "root" => builder.root(v),
                 "endpoint" => builder.endpoint(v),
+                "cluster_endpoints" => builder.cluster_endpoints(v),
                 "username" => builder.username(v),
                 "password" => builder.password(v),
                 "db" => match v.parse::<i64>() {
                     Ok(num) => builder.db(num),
                     _ => continue,
                 },
+                "default_ttl" => match v.parse::<u64>() {
+                    Ok(v) => builder.default_ttl(Duration::from_secs(v)),
+                    _ => continue,
+                },
+                "enable_tls" if !v.is_empty() => builder.enable_tls(),
+                "ca_cert" => builder.ca_cert(v),
                 _ => continue,
             };
         }
@@ -90,6 +131,7 @@ impl Builder {
     /// currently supported schemes:
     /// - no scheme: will be seen as "tcp"
     /// - "tcp" or "redis": unsecured redis connections
+    /// - "rediss": redis connections over TLS, see [`Builder::enable_tls`]
     /// - "unix" or "redis+unix": unix socket connection
     pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
         if !endpoint.is_empty() {
@@ -98,8 +140,50 @@ impl Builder {
         self
     }
 
+    /// set the network addresses of redis cluster nodes, separated by `,`
+    /// like `tcp://127.0.0.1:7000,tcp://127.0.0.1:7001`.
+    ///
+    /// Nodes are only used to discover the slots of cluster, so it's fine
+    /// to set part of them. Keys will be sent to the node that serves their
+    /// slots, and `MOVED` and `ASK` redirects will be followed.
+    ///
+    /// Only "tcp" and "redis" schemes are supported, and it can't be set
+    /// along with `endpoint`.
+    pub fn cluster_endpoints(&mut self, endpoints: &str) -> &mut Self {
+        if !endpoints.is_empty() {
+            self.cluster_endpoints = Some(endpoints.to_owned());
+        }
+        self
+    }
+
+    /// Connect redis over TLS, which is required by most managed redis
+    /// services.
+    ///
+    /// Requires the `services-redis-tls` feature, otherwise `build` will
+    /// return an error instead of falling back to plaintext. Endpoint with
+    /// "rediss" scheme enables TLS too.
+    pub fn enable_tls(&mut self) -> &mut Self {
+        self.enable_tls = true;
+        self
+    }
+
+    /// set the path of PEM encoded CA certificate which will be trusted
+    /// while connecting over TLS, besides the system roots.
+    ///
+    /// default: only system roots are trusted
+    pub fn ca_cert(&mut self, path: &str) -> &mut Self {
+        if !path.is_empty() {
+            self.ca_cert = Some(path.to_owned());
+        }
+        self
+    }
+
     /// set the username for redis
     ///
+    /// Username is required by redis 6+ with ACL enabled, `AUTH <username>
+    /// <password>` will be sent. Leave it empty for redis 5 and earlier,
+    /// which only support `AUTH <password>`.
+    ///
     /// default: no username
     pub fn username(&mut self, username: &str) -> &mut Self {
         if !username.is_empty() {
@@ -146,64 +230,12 @@ impl Builder {
 
     /// Establish connection to Redis and finish making Redis endpoint
     pub fn build(&mut self) -> Result<impl Accessor> {
-        let endpoint = self
-            .endpoint
-            .clone()
-            .unwrap_or_else(|| DEFAULT_REDIS_ENDPOINT.to_string());
-
-        let ep_url = endpoint.parse::<Uri>().map_err(|e| {
-            Error::new(ErrorKind::BackendConfigInvalid, "endpoint is invalid")
-                .with_context("service", Scheme::Redis)
-                .with_context("endpoint", endpoint)
-                .set_source(e)
-        })?;
-
-        let con_addr = match ep_url.scheme_str() {
-            Some("tcp") | Some("redis") | None => {
-                let host = ep_url
-                    .host()
-                    .map(|h| h.to_string())
-                    .unwrap_or_else(|| "127.0.0.1".to_string());
-                let port = ep_url.port_u16().unwrap_or(DEFAULT_REDIS_PORT);
-                ConnectionAddr::Tcp(host, port)
-            }
-            // TODO: wait for upstream to support `rustls` based TLS connection.
-            Some("unix") | Some("redis+unix") => {
-                let path = PathBuf::from(ep_url.path());
-                ConnectionAddr::Unix(path)
-            }
-            Some(s) => {
-                return Err(Error::new(
-                    ErrorKind::BackendConfigInvalid,
-                    "invalid or unsupported scheme",
-                )
-                .with_context("service", Scheme::Redis)
-                .with_context("scheme", s))
-            }
-        };
-
         let redis_info = RedisConnectionInfo {
             db: self.db,
             username: self.username.clone(),
             password: self.password.clone(),
         };
 
-        let con_info = ConnectionInfo {
-            addr: con_addr,
-            redis: redis_info,
-        };
-
-        let client = Client::open(con_info).map_err(|e| {
-            Error::new(
-                ErrorKind::BackendConfigInvalid,
-                "invalid or unsupported scheme",
-            )
-            .with_context("service", Scheme::Redis)
-            .with_context("endpoint", self.endpoint.as_ref().unwrap())
-            .with_context("db", self.db.to_string())
-            .set_source(e)
-        })?;
-
         let root = normalize_root(
             self.root
                 .clone()
@@ -211,16 +243,208 @@ impl Builder {
                 .as_str(),
         );
 
-        let conn = OnceCell::new();
+        let client = match (&self.endpoint, &self.cluster_endpoints) {
+            (_, Some(_)) if self.enable_tls => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "redis cluster over tls is not supported yet",
+                )
+                .with_context("field", "enable_tls")
+                .with_context("service", Scheme::Redis))
+            }
+            (Some(_), Some(_)) => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "endpoint and cluster_endpoints can't be set at the same time",
+                )
+                .with_context("field", "cluster_endpoints")
+                .with_context("service", Scheme::Redis))
+            }
+            (_, Some(endpoints)) => {
+                if self.db != 0 {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "redis cluster only supports db 0",
+                    )
+                    .with_context("field", "db")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("db", self.db.to_string()));
+                }
+
+                RedisClient::Cluster(ClusterConnection::new(
+                    parse_cluster_endpoints(endpoints)?,
+                    redis_info,
+                ))
+            }
+            (endpoint, None) => {
+                let endpoint = endpoint
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_REDIS_ENDPOINT.to_string());
+
+                match parse_endpoint(&endpoint)? {
+                    ConnectionAddr::Tcp(host, port) if self.enable_tls => {
+                        self.build_tls_client(&host, port, redis_info)?
+                    }
+                    ConnectionAddr::TcpTls { host, port, .. } => {
+                        self.build_tls_client(&host, port, redis_info)?
+                    }
+                    _ if self.enable_tls => {
+                        return Err(Error::new(
+                            ErrorKind::ConfigInvalid,
+                            "tls is only supported for tcp connections",
+                        )
+                        .with_context("field", "enable_tls")
+                        .with_context("service", Scheme::Redis)
+                        .with_context("endpoint", &endpoint))
+                    }
+                    addr => {
+                        let con_info = ConnectionInfo {
+                            addr,
+                            redis: redis_info,
+                        };
+
+                        let client = Client::open(con_info).map_err(|e| {
+                            Error::new(ErrorKind::ConfigInvalid, "invalid or unsupported scheme")
+                                .with_context("field", "endpoint")
+                                .with_context("service", Scheme::Redis)
+                                .with_context("endpoint", &endpoint)
+                                .with_context("db", self.db.to_string())
+                                .set_source(e)
+                        })?;
+                        RedisClient::Single(client)
+                    }
+                }
+            }
+        };
+
         Ok(apply_wrapper(
             Backend::new(Adapter {
                 client,
-                conn,
+                conn: OnceCell::new(),
                 default_ttl: self.default_ttl,
             })
             .with_root(&root),
         ))
     }
+
+    #[cfg(feature = "services-redis-tls")]
+    fn build_tls_client(
+        &self,
+        host: &str,
+        port: u16,
+        redis: RedisConnectionInfo,
+    ) -> Result<RedisClient> {
+        let ca_cert = match &self.ca_cert {
+            Some(path) => Some(std::fs::read(path).map_err(|e| {
+                Error::new(ErrorKind::ConfigInvalid, "ca cert can't be read")
+                    .with_context("field", "ca_cert")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("path", path)
+                    .set_source(e)
+            })?),
+            None => None,
+        };
+
+        Ok(RedisClient::Tls(TlsClient::new(
+            host,
+            port,
+            redis,
+            ca_cert.as_deref(),
+        )?))
+    }
+
+    /// Never fall back to plaintext if tls is requested.
+    #[cfg(not(feature = "services-redis-tls"))]
+    fn build_tls_client(&self, _: &str, _: u16, _: RedisConnectionInfo) -> Result<RedisClient> {
+        Err(Error::new(
+            ErrorKind::ConfigInvalid,
+            "tls is requested but feature services-redis-tls is not enabled",
+        )
+        .with_context("field", "enable_tls")
+        .with_context("service", Scheme::Redis))
+    }
+}
+
+/// Parse endpoint like `tcp://127.0.0.1:6379` into connection address.
+fn parse_endpoint(endpoint: &str) -> Result<ConnectionAddr> {
+    let ep_url = endpoint.parse::<Uri>().map_err(|e| {
+        Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+            .with_context("field", "endpoint")
+            .with_context("service", Scheme::Redis)
+            .with_context("endpoint", endpoint)
+            .set_source(e)
+    })?;
+
+    let con_addr = match ep_url.scheme_str() {
+        Some("tcp") | Some("redis") | None => {
+            let host = ep_url
+                .host()
+                .map(|h| h.to_string())
+                .unwrap_or_else(|| "127.0.0.1".to_string());
+            let port = ep_url.port_u16().unwrap_or(DEFAULT_REDIS_PORT);
+            ConnectionAddr::Tcp(host, port)
+        }
+        Some("rediss") => {
+            let host = ep_url
+                .host()
+                .map(|h| h.to_string())
+                .unwrap_or_else(|| "127.0.0.1".to_string());
+            let port = ep_url.port_u16().unwrap_or(DEFAULT_REDIS_PORT);
+            ConnectionAddr::TcpTls {
+                host,
+                port,
+                insecure: false,
+            }
+        }
+        Some("unix") | Some("redis+unix") => {
+            let path = PathBuf::from(ep_url.path());
+            ConnectionAddr::Unix(path)
+        }
+        Some(s) => {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "invalid or unsupported scheme")
+                    .with_context("field", "endpoint")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("scheme", s),
+            )
+        }
+    };
+
+    Ok(con_addr)
+}
+
+/// Parse endpoints of cluster nodes separated by `,`, only tcp connections
+/// are supported.
+fn parse_cluster_endpoints(endpoints: &str) -> Result<Vec<ConnectionAddr>> {
+    let mut addrs = Vec::new();
+    for endpoint in endpoints
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+    {
+        match parse_endpoint(endpoint)? {
+            addr @ ConnectionAddr::Tcp(_, _) => addrs.push(addr),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "redis cluster only supports tcp connections",
+                )
+                .with_context("field", "cluster_endpoints")
+                .with_context("service", Scheme::Redis)
+                .with_context("endpoint", endpoint))
+            }
+        }
+    }
+
+    if addrs.is_empty() {
+        return Err(
+            Error::new(ErrorKind::ConfigInvalid, "cluster_endpoints is empty")
+                .with_context("field", "cluster_endpoints")
+                .with_context("service", Scheme::Redis),
+        );
+    }
+
+    Ok(addrs)
 }
 
 impl Debug for Builder {
@@ -231,12 +455,24 @@ impl Debug for Builder {
         if let Some(endpoint) = self.endpoint.clone() {
             ds.field("endpoint", &endpoint);
         }
+        if let Some(endpoints) = self.cluster_endpoints.clone() {
+            ds.field("cluster_endpoints", &endpoints);
+        }
+        if self.enable_tls {
+            ds.field("enable_tls", &self.enable_tls);
+        }
+        if let Some(ca_cert) = self.ca_cert.clone() {
+            ds.field("ca_cert", &ca_cert);
+        }
         if let Some(username) = self.username.clone() {
             ds.field("username", &username);
         }
         if self.password.is_some() {
             ds.field("password", &"<redacted>");
         }
+        if let Some(default_ttl) = self.default_ttl {
+            ds.field("default_ttl", &default_ttl);
+        }
         ds.finish()
     }
 }
@@ -246,32 +482,119 @@ pub type Backend = kv::Backend<Adapter>;
 
 #[derive(Clone)]
 pub struct Adapter {
-    client: Client,
+    client: RedisClient,
     conn: OnceCell<ConnectionManager>,
 
     default_ttl: Option<Duration>,
 }
 
+#[derive(Clone)]
+enum RedisClient {
+    Single(Client),
+    #[cfg(feature = "services-redis-tls")]
+    Tls(TlsClient),
+    Cluster(ClusterConnection),
+}
+
+impl RedisClient {
+    /// Addresses of the server, or seed nodes of the cluster.
+    fn addr(&self) -> String {
+        match self {
+            RedisClient::Single(client) => client.get_connection_info().addr.to_string(),
+            #[cfg(feature = "services-redis-tls")]
+            RedisClient::Tls(client) => client.addr(),
+            RedisClient::Cluster(cluster) => cluster
+                .seeds()
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        }
+    }
+}
+
 // implement `Debug` manually, or password may be leaked.
 impl Debug for Adapter {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut ds = f.debug_struct("Adapter");
 
-        let info = self.client.get_connection_info();
-        ds.field("addr", &info.addr);
-        ds.field("db", &info.redis.db);
-        ds.field("user", &info.redis.username);
+        match &self.client {
+            RedisClient::Single(client) => {
+                let info = client.get_connection_info();
+                ds.field("addr", &info.addr);
+                ds.field("db", &info.redis.db);
+                ds.field("user", &info.redis.username);
+            }
+            #[cfg(feature = "services-redis-tls")]
+            RedisClient::Tls(client) => {
+                ds.field("addr", &client.addr());
+                ds.field("db", &client.db());
+                ds.field("user", &client.username());
+                ds.field("tls", &true);
+            }
+            RedisClient::Cluster(cluster) => {
+                ds.field("cluster", &cluster.seeds());
+            }
+        }
         ds.finish()
     }
 }
 
 impl Adapter {
-    async fn conn(&self) -> Result<ConnectionManager> {
-        Ok(self
-            .conn
-            .get_or_try_init(|| async { ConnectionManager::new(self.client.clone()).await })
-            .await?
-            .clone())
+    async fn conn(&self) -> Result<RedisConnection> {
+        match &self.client {
+            RedisClient::Single(client) => Ok(RedisConnection::Single(
+                self.conn
+                    .get_or_try_init(|| async { ConnectionManager::new(client.clone()).await })
+                    .await?
+                    .clone(),
+            )),
+            #[cfg(feature = "services-redis-tls")]
+            RedisClient::Tls(client) => Ok(RedisConnection::Tls(client.get_connection().await?)),
+            RedisClient::Cluster(cluster) => Ok(RedisConnection::Cluster(cluster.clone())),
+        }
+    }
+}
+
+/// Connection to a single redis server or a redis cluster.
+enum RedisConnection {
+    Single(ConnectionManager),
+    #[cfg(feature = "services-redis-tls")]
+    Tls(TlsConnection),
+    Cluster(ClusterConnection),
+}
+
+impl ConnectionLike for RedisConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        match self {
+            RedisConnection::Single(conn) => conn.req_packed_command(cmd),
+            #[cfg(feature = "services-redis-tls")]
+            RedisConnection::Tls(conn) => conn.req_packed_command(cmd),
+            RedisConnection::Cluster(conn) => conn.req_packed_command(cmd),
+        }
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        match self {
+            RedisConnection::Single(conn) => conn.req_packed_commands(cmd, offset, count),
+            #[cfg(feature = "services-redis-tls")]
+            RedisConnection::Tls(conn) => conn.req_packed_commands(cmd, offset, count),
+            RedisConnection::Cluster(conn) => conn.req_packed_commands(cmd, offset, count),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        match self {
+            RedisConnection::Single(conn) => conn.get_db(),
+            #[cfg(feature = "services-redis-tls")]
+            RedisConnection::Tls(conn) => conn.get_db(),
+            RedisConnection::Cluster(conn) => conn.get_db(),
+        }
     }
 }
 
@@ -280,8 +603,8 @@ impl kv::Adapter for Adapter {
     fn metadata(&self) -> kv::Metadata {
         kv::Metadata::new(
             Scheme::Redis,
-            &self.client.get_connection_info().addr.to_string(),
-            AccessorCapability::Read | AccessorCapability::Write,
+            &self.client.addr(),
+            AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::Expire,
         )
     }
 
@@ -300,6 +623,28 @@ impl kv::Adapter for Adapter {
         Ok(())
     }
 
+    /// Use `SETEX` for expire in whole seconds, and `PSETEX` for expire
+    /// with sub-second precision.
+    async fn set_with_expire(&self, key: &str, value: &[u8], expire: Duration) -> Result<()> {
+        if expire.as_millis() == 0 {
+            return Err(
+                Error::new(ErrorKind::Unexpected, "expire must be at least 1ms")
+                    .with_operation("kv::Adapter::set_with_expire")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("expire", format!("{expire:?}")),
+            );
+        }
+
+        let mut conn = self.conn().await?;
+        if expire.subsec_nanos() == 0 {
+            conn.set_ex(key, value, expire.as_secs() as usize).await?
+        } else {
+            conn.pset_ex(key, value, expire.as_millis() as usize)
+                .await?
+        }
+        Ok(())
+    }
+
     async fn delete(&self, key: &str) -> Result<()> {
         let mut conn = self.conn().await?;
         let _: () = conn.del(key).await?;
@@ -312,3 +657,101 @@ impl From<RedisError> for Error {
         Error::new(ErrorKind::Unexpected, e.category()).set_source(e)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_endpoint() {
+        for (endpoint, expected) in [
+            (
+                "tcp://127.0.0.1:6379",
+                ConnectionAddr::Tcp("127.0.0.1".to_string(), 6379),
+            ),
+            (
+                "redis://example.com",
+                ConnectionAddr::Tcp("example.com".to_string(), DEFAULT_REDIS_PORT),
+            ),
+            (
+                "127.0.0.1:7000",
+                ConnectionAddr::Tcp("127.0.0.1".to_string(), 7000),
+            ),
+            (
+                "rediss://example.com:6380",
+                ConnectionAddr::TcpTls {
+                    host: "example.com".to_string(),
+                    port: 6380,
+                    insecure: false,
+                },
+            ),
+        ] {
+            let addr = parse_endpoint(endpoint).expect("parse must succeed");
+            assert_eq!(addr, expected, "{endpoint}");
+        }
+
+        let err = parse_endpoint("http://127.0.0.1:6379").expect_err("parse must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    fn test_parse_cluster_endpoints() {
+        let addrs = parse_cluster_endpoints("tcp://127.0.0.1:7000, redis://127.0.0.1:7001,")
+            .expect("parse must succeed");
+        assert_eq!(
+            addrs,
+            vec![
+                ConnectionAddr::Tcp("127.0.0.1".to_string(), 7000),
+                ConnectionAddr::Tcp("127.0.0.1".to_string(), 7001),
+            ]
+        );
+
+        for endpoints in [",", "tcp://127.0.0.1:7000,http://127.0.0.1:7001"] {
+            let err = parse_cluster_endpoints(endpoints).expect_err("parse must fail");
+            assert_eq!(err.kind(), ErrorKind::ConfigInvalid, "{endpoints}");
+        }
+    }
+
+    #[test]
+    fn test_build_cluster() {
+        let mut builder = Builder::default();
+        builder.cluster_endpoints("tcp://127.0.0.1:7000,tcp://127.0.0.1:7001");
+        assert!(builder.build().is_ok());
+
+        builder.endpoint("tcp://127.0.0.1:6379");
+        let err = builder.build().expect_err("build must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let mut builder = Builder::default();
+        builder.cluster_endpoints("tcp://127.0.0.1:7000").db(1);
+        let err = builder.build().expect_err("build must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    fn test_build_tls() {
+        for endpoint in ["tcp://127.0.0.1:6379", "rediss://127.0.0.1:6379"] {
+            let mut builder = Builder::default();
+            builder.endpoint(endpoint).enable_tls();
+            assert_eq!(
+                builder.build().is_ok(),
+                cfg!(feature = "services-redis-tls"),
+                "{endpoint}"
+            );
+        }
+
+        let mut builder = Builder::default();
+        builder
+            .cluster_endpoints("tcp://127.0.0.1:7000")
+            .enable_tls();
+        let err = builder.build().expect_err("build must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint("rediss://127.0.0.1:6379")
+            .ca_cert("/path/to/not_exist/ca.pem");
+        let err = builder.build().expect_err("build must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+}
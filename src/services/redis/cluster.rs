@@ -0,0 +1,515 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use redis::aio::ConnectionLike;
+use redis::aio::ConnectionManager;
+use redis::Arg;
+use redis::Client;
+use redis::Cmd;
+use redis::ConnectionAddr;
+use redis::ConnectionInfo;
+use redis::ErrorKind;
+use redis::Pipeline;
+use redis::RedisConnectionInfo;
+use redis::RedisError;
+use redis::RedisFuture;
+use redis::RedisResult;
+use redis::Value;
+use tokio::sync::Mutex;
+
+/// Number of hash slots of redis cluster.
+const SLOT_SIZE: u16 = 16384;
+/// Max attempts to follow `MOVED` and `ASK` redirects for one request.
+const MAX_REDIRECTS: usize = 16;
+
+/// ClusterConnection routes commands to the node that serves the slot of
+/// their keys, and follows `MOVED` and `ASK` redirects transparently.
+///
+/// Reference: [Redis cluster specification](https://redis.io/docs/reference/cluster-spec/)
+#[derive(Clone)]
+pub struct ClusterConnection {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    /// Nodes used to discover the slots of cluster.
+    seeds: Vec<ConnectionAddr>,
+    redis: RedisConnectionInfo,
+
+    slots: RwLock<Vec<SlotRange>>,
+    conns: Mutex<HashMap<String, ConnectionManager>>,
+}
+
+/// Slots from `start` to `end` (inclusive) are served by `addr`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SlotRange {
+    start: u16,
+    end: u16,
+    addr: String,
+}
+
+impl ClusterConnection {
+    /// Create a new cluster connection, the slots will be fetched lazily
+    /// by the first request.
+    pub fn new(seeds: Vec<ConnectionAddr>, redis: RedisConnectionInfo) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                seeds,
+                redis,
+                slots: RwLock::new(Vec::new()),
+                conns: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Addresses of seed nodes.
+    pub fn seeds(&self) -> &[ConnectionAddr] {
+        &self.inner.seeds
+    }
+
+    fn client(&self, addr: &str) -> RedisResult<Client> {
+        Client::open(ConnectionInfo {
+            addr: parse_node_addr(addr)?,
+            redis: self.inner.redis.clone(),
+        })
+    }
+
+    async fn conn(&self, addr: &str) -> RedisResult<ConnectionManager> {
+        let mut conns = self.inner.conns.lock().await;
+        if let Some(conn) = conns.get(addr) {
+            return Ok(conn.clone());
+        }
+
+        let conn = ConnectionManager::new(self.client(addr)?).await?;
+        conns.insert(addr.to_string(), conn.clone());
+        Ok(conn)
+    }
+
+    /// Fetch slots via `CLUSTER SLOTS` from the first available node.
+    async fn refresh_slots(&self) -> RedisResult<()> {
+        let mut addrs: Vec<String> = self
+            .inner
+            .slots
+            .read()
+            .iter()
+            .map(|v| v.addr.clone())
+            .collect();
+        for seed in &self.inner.seeds {
+            let seed = seed.to_string();
+            if !addrs.contains(&seed) {
+                addrs.push(seed);
+            }
+        }
+
+        let mut last_err = None;
+        for addr in addrs {
+            let mut conn = match self.conn(&addr).await {
+                Ok(conn) => conn,
+                Err(err) => {
+                    last_err = Some(err);
+                    continue;
+                }
+            };
+            let value = match redis::cmd("CLUSTER")
+                .arg("SLOTS")
+                .query_async(&mut conn)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => {
+                    last_err = Some(err);
+                    continue;
+                }
+            };
+
+            let slots = parse_slots(&value, host_of(&addr))?;
+            *self.inner.slots.write() = slots;
+            return Ok(());
+        }
+
+        Err(last_err.unwrap_or_else(|| {
+            RedisError::from((ErrorKind::ClusterDown, "no available node in cluster"))
+        }))
+    }
+
+    /// Find the address of node which serves the given slot.
+    async fn node_of(&self, slot: u16) -> RedisResult<String> {
+        if self.inner.slots.read().is_empty() {
+            self.refresh_slots().await?;
+        }
+
+        self.inner
+            .slots
+            .read()
+            .iter()
+            .find(|v| v.start <= slot && slot <= v.end)
+            .map(|v| v.addr.clone())
+            .ok_or_else(|| {
+                RedisError::from((
+                    ErrorKind::ClusterDown,
+                    "slot is not served by any node",
+                    slot.to_string(),
+                ))
+            })
+    }
+
+    /// Send request to the node of `slot` and follow redirects.
+    ///
+    /// - `MOVED`: slots have been changed, refresh slots and send to the
+    ///   new node.
+    /// - `ASK`: slot is being migrated, send `ASKING` along with request
+    ///   to the given node via a new connection, so that the `ASKING`
+    ///   flag will not be consumed by concurrent requests.
+    async fn route<'a, T>(&'a self, slot: Option<u16>, req: Request<'a>) -> RedisResult<T>
+    where
+        T: FromValues,
+    {
+        let mut addr = match slot {
+            Some(slot) => self.node_of(slot).await?,
+            None => self.any_node().await?,
+        };
+        let mut asking = false;
+
+        for _ in 0..MAX_REDIRECTS {
+            let res = if asking {
+                asking = false;
+                let mut conn = self.client(&addr)?.get_async_connection().await?;
+                redis::cmd("ASKING").query_async::<_, ()>(&mut conn).await?;
+                req.send(&mut conn).await
+            } else {
+                let mut conn = self.conn(&addr).await?;
+                req.send(&mut conn).await
+            };
+
+            let err = match res {
+                Ok(v) => return T::from_values(v),
+                Err(err) => err,
+            };
+            let (node, _) = match err.redirect_node() {
+                Some(v) => v,
+                None => return Err(err),
+            };
+            let node = match node.strip_prefix(':') {
+                // Empty host means the same host as the current node.
+                Some(port) => format!("{}:{port}", host_of(&addr)),
+                None => node.to_string(),
+            };
+
+            match err.kind() {
+                ErrorKind::Moved => {
+                    // Slots will be refreshed by next request if failed.
+                    if self.refresh_slots().await.is_err() {
+                        self.inner.slots.write().clear();
+                    }
+                }
+                _ => asking = true,
+            }
+            addr = node;
+        }
+
+        Err(RedisError::from((
+            ErrorKind::ClientError,
+            "too many redirects",
+            addr,
+        )))
+    }
+
+    async fn any_node(&self) -> RedisResult<String> {
+        self.node_of(0).await
+    }
+}
+
+/// Requests could be sent to cluster.
+#[derive(Clone, Copy)]
+enum Request<'a> {
+    Cmd(&'a Cmd),
+    Pipeline(&'a Pipeline, usize, usize),
+}
+
+impl<'a> Request<'a> {
+    async fn send<C: ConnectionLike>(&self, conn: &mut C) -> RedisResult<Vec<Value>> {
+        match self {
+            Request::Cmd(cmd) => Ok(vec![conn.req_packed_command(cmd).await?]),
+            Request::Pipeline(pipeline, offset, count) => {
+                conn.req_packed_commands(pipeline, *offset, *count).await
+            }
+        }
+    }
+}
+
+trait FromValues: Sized {
+    fn from_values(v: Vec<Value>) -> RedisResult<Self>;
+}
+
+impl FromValues for Value {
+    fn from_values(v: Vec<Value>) -> RedisResult<Self> {
+        v.into_iter()
+            .next()
+            .ok_or_else(|| RedisError::from((ErrorKind::ResponseError, "response is empty")))
+    }
+}
+
+impl FromValues for Vec<Value> {
+    fn from_values(v: Vec<Value>) -> RedisResult<Self> {
+        Ok(v)
+    }
+}
+
+impl ConnectionLike for ClusterConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        Box::pin(async move {
+            let slot = key_of(cmd).map(key_slot);
+            self.route(slot, Request::Cmd(cmd)).await
+        })
+    }
+
+    /// All keys in the pipeline must be in the same slot, so that they can
+    /// be sent to the same node.
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        pipeline: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        Box::pin(async move {
+            let mut slot = None;
+            for key in pipeline.cmd_iter().filter_map(key_of) {
+                let s = key_slot(key);
+                if slot.map(|v| v != s).unwrap_or_default() {
+                    return Err(RedisError::from((
+                        ErrorKind::CrossSlot,
+                        "keys in pipeline must be in the same slot",
+                    )));
+                }
+                slot = Some(s);
+            }
+
+            self.route(slot, Request::Pipeline(pipeline, offset, count))
+                .await
+        })
+    }
+
+    /// Redis cluster only supports db 0.
+    fn get_db(&self) -> i64 {
+        0
+    }
+}
+
+/// Get the key of command, which is the first argument for all commands we
+/// used.
+fn key_of(cmd: &Cmd) -> Option<&[u8]> {
+    match cmd.args_iter().nth(1)? {
+        Arg::Simple(key) => Some(key),
+        Arg::Cursor => None,
+    }
+}
+
+/// Calculate the hash slot of key.
+///
+/// Only the hash tag will be hashed if key contains a non-empty one like
+/// `{user1000}.following`.
+fn key_slot(key: &[u8]) -> u16 {
+    let key = match key.iter().position(|v| *v == b'{') {
+        Some(open) => match key[open + 1..].iter().position(|v| *v == b'}') {
+            Some(close) if close > 0 => &key[open + 1..open + 1 + close],
+            _ => key,
+        },
+        None => key,
+    };
+
+    crc16(key) % SLOT_SIZE
+}
+
+/// CRC16 of XMODEM, which is used by redis cluster.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for b in data {
+        crc ^= (*b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Parse response of `CLUSTER SLOTS` like:
+///
+/// ```text
+/// 1) 1) (integer) 0
+///    2) (integer) 5460
+///    3) 1) "127.0.0.1"
+///       2) (integer) 30001
+///       3) "09dbe9720cda62f7865eabc5fd8857c5d2678366"
+///    4) ...replicas
+/// ```
+///
+/// Empty host means the same host as the node we are talking to.
+fn parse_slots(value: &Value, current_host: &str) -> RedisResult<Vec<SlotRange>> {
+    let invalid = || RedisError::from((ErrorKind::TypeError, "invalid response of cluster slots"));
+
+    let items = match value {
+        Value::Bulk(items) => items,
+        _ => return Err(invalid()),
+    };
+
+    let mut slots = Vec::with_capacity(items.len());
+    for item in items {
+        let (start, end, master) = match item {
+            Value::Bulk(v) if v.len() >= 3 => (&v[0], &v[1], &v[2]),
+            _ => return Err(invalid()),
+        };
+        let (start, end) = match (start, end) {
+            (Value::Int(start), Value::Int(end)) => (*start as u16, *end as u16),
+            _ => return Err(invalid()),
+        };
+        let (host, port) = match master {
+            Value::Bulk(v) if v.len() >= 2 => match (&v[0], &v[1]) {
+                (Value::Data(host), Value::Int(port)) => (String::from_utf8_lossy(host), *port),
+                _ => return Err(invalid()),
+            },
+            _ => return Err(invalid()),
+        };
+        let host = if host.is_empty() || host == "?" {
+            current_host.to_string()
+        } else {
+            host.to_string()
+        };
+
+        slots.push(SlotRange {
+            start,
+            end,
+            addr: format!("{host}:{port}"),
+        });
+    }
+
+    Ok(slots)
+}
+
+/// Parse node address like `127.0.0.1:7000` returned by cluster.
+fn parse_node_addr(addr: &str) -> RedisResult<ConnectionAddr> {
+    let invalid = || {
+        RedisError::from((
+            ErrorKind::InvalidClientConfig,
+            "invalid node address",
+            addr.to_string(),
+        ))
+    };
+
+    let (host, port) = addr.rsplit_once(':').ok_or_else(invalid)?;
+    let port = port.parse::<u16>().map_err(|_| invalid())?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    Ok(ConnectionAddr::Tcp(host.to_string(), port))
+}
+
+fn host_of(addr: &str) -> &str {
+    addr.rsplit_once(':').map(|(host, _)| host).unwrap_or(addr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc16() {
+        assert_eq!(crc16(b"123456789"), 0x31c3);
+    }
+
+    #[test]
+    fn test_key_slot() {
+        assert_eq!(key_slot(b"foo"), 12182);
+        assert_eq!(
+            key_slot(b"/path/to/file"),
+            crc16(b"/path/to/file") % SLOT_SIZE
+        );
+
+        for (key, tag) in [
+            ("{user1000}.following", "user1000"),
+            ("foo{bar}{zap}", "bar"),
+            ("foo{{bar}}zap", "{bar"),
+        ] {
+            assert_eq!(key_slot(key.as_bytes()), key_slot(tag.as_bytes()), "{key}");
+        }
+        // Empty hash tag will not be used.
+        assert_eq!(key_slot(b"foo{}{bar}"), crc16(b"foo{}{bar}") % SLOT_SIZE);
+    }
+
+    #[test]
+    fn test_key_of() {
+        assert_eq!(
+            key_of(redis::cmd("SET").arg("key").arg("value")),
+            Some(b"key".as_slice())
+        );
+        assert_eq!(key_of(&redis::cmd("PING")), None);
+    }
+
+    #[test]
+    fn test_parse_slots() {
+        let node = |host: &str, port: i64| {
+            Value::Bulk(vec![
+                Value::Data(host.as_bytes().to_vec()),
+                Value::Int(port),
+                Value::Data(b"09dbe9720cda62f7865eabc5fd8857c5d2678366".to_vec()),
+            ])
+        };
+        let value = Value::Bulk(vec![
+            Value::Bulk(vec![
+                Value::Int(0),
+                Value::Int(5460),
+                node("127.0.0.1", 30001),
+                node("127.0.0.1", 30004),
+            ]),
+            Value::Bulk(vec![Value::Int(5461), Value::Int(10922), node("", 30002)]),
+        ]);
+
+        let slots = parse_slots(&value, "10.0.0.1").expect("parse must succeed");
+        assert_eq!(
+            slots,
+            vec![
+                SlotRange {
+                    start: 0,
+                    end: 5460,
+                    addr: "127.0.0.1:30001".to_string(),
+                },
+                SlotRange {
+                    start: 5461,
+                    end: 10922,
+                    addr: "10.0.0.1:30002".to_string(),
+                },
+            ]
+        );
+
+        assert!(parse_slots(&Value::Int(1), "").is_err());
+    }
+
+    #[test]
+    fn test_parse_node_addr() {
+        assert_eq!(
+            parse_node_addr("127.0.0.1:7000").expect("parse must succeed"),
+            ConnectionAddr::Tcp("127.0.0.1".to_string(), 7000)
+        );
+        assert_eq!(
+            parse_node_addr("[::1]:7000").expect("parse must succeed"),
+            ConnectionAddr::Tcp("::1".to_string(), 7000)
+        );
+        assert!(parse_node_addr("127.0.0.1").is_err());
+    }
+}
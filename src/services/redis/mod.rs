@@ -24,19 +24,62 @@
 //!
 //! - `root`: Set the working directory of `OpenDAL`
 //! - `endpoint`: Set the network address of redis server
+//! - `cluster_endpoints`: Set the network addresses of redis cluster nodes, separated by `,`
 //! - `username`: Set the username of Redis
 //! - `password`: Set the password for authentication
 //! - `db`: Set the DB of redis
+//! - `default_ttl`: Set the default ttl in seconds for written keys
+//! - `enable_tls`: Connect redis over TLS
+//! - `ca_cert`: Set the path of CA certificate used to verify redis server
 //!
 //! You can refer to [`Builder`]'s docs for more information
 //!
+//! # Expire
+//!
+//! Keys written with [`OpWrite::with_expire`][crate::OpWrite::with_expire]
+//! will be removed by redis after expire, which overrides `default_ttl`.
+//! Expire with sub-second precision will be sent via `PSETEX`.
+//!
+//! # TLS
+//!
+//! TLS will be used if endpoint starts with `rediss://` or
+//! [`Builder::enable_tls`] is called, which is required by managed redis
+//! like ElastiCache and Azure Cache for Redis. Server certificates are
+//! verified against system roots and the CA set by [`Builder::ca_cert`].
+//!
+//! TLS requires the `services-redis-tls` feature, building a TLS backend
+//! without it will return [`ErrorKind::ConfigInvalid`][crate::ErrorKind::ConfigInvalid]
+//! instead of falling back to plaintext. Failed TLS handshakes like
+//! connecting to a plaintext server are reported as `ConfigInvalid` too,
+//! so they won't be retried.
+//!
+//! # Authentication
+//!
+//! `AUTH <username> <password>` will be sent if both `username` and
+//! `password` are set, which is required by redis 6+ with ACL enabled.
+//! Set `password` only to authenticate with redis 5 and earlier.
+//!
+//! # Cluster
+//!
+//! Set `cluster_endpoints` instead of `endpoint` to access a redis cluster.
+//! Slots of the cluster are discovered from given nodes, and every key will
+//! be sent to the node serving its slot. `MOVED` and `ASK` redirects caused
+//! by resharding are followed transparently.
+//!
+//! Redis cluster only supports db `0`, and `endpoint` can't be set along
+//! with `cluster_endpoints`.
+//!
 //! # Environment
 //!
 //! - `OPENDAL_REDIS_ROOT` optional
 //! - `OPENDAL_REDIS_ENDPOINT` optional
+//! - `OPENDAL_REDIS_CLUSTER_ENDPOINTS` optional
 //! - `OPENDAL_REDIS_USERNAME` optional
 //! - `OPENDAL_REDIS_PASSWORD` optional
 //! - `OPENDAL_REDIS_DB` optional
+//! - `OPENDAL_REDIS_DEFAULT_TTL` optional
+//! - `OPENDAL_REDIS_ENABLE_TLS` optional
+//! - `OPENDAL_REDIS_CA_CERT` optional
 //!
 //! # Example
 //!
@@ -58,7 +101,7 @@
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<()> {
-//!     let op = Operator::from_env(Scheme::Redis);
+//!     let op = Operator::from_env(Scheme::Redis)?;
 //!
 //!     // create an object handler to start operation on redis!
 //!
@@ -81,11 +124,20 @@
 //!     let mut builder = redis::Builder::default();
 //!
 //!     // this will build a Operator accessing Redis which runs on tcp://localhost:6379
-//!     let op: Operator = Operator::new(builder.build());
+//!     let op: Operator = Operator::new(builder.build()?);
+//!     let _: Object = op.object("test_file");
+//!
+//!     // this will build a Operator accessing Redis Cluster which has nodes on port 7000-7002
+//!     let mut builder = redis::Builder::default();
+//!     builder.cluster_endpoints("tcp://127.0.0.1:7000,tcp://127.0.0.1:7001,tcp://127.0.0.1:7002");
+//!     let op: Operator = Operator::new(builder.build()?);
 //!     let _: Object = op.object("test_file");
 //!     Ok(())
 //! }
 //! ```
 
 mod backend;
+mod cluster;
+#[cfg(feature = "services-redis-tls")]
+mod tls;
 pub use backend::Builder;
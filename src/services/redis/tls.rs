@@ -0,0 +1,239 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use redis::aio::ConnectionLike;
+use redis::aio::MultiplexedConnection;
+use redis::Cmd;
+use redis::Pipeline;
+use redis::RedisConnectionInfo;
+use redis::RedisFuture;
+use redis::Value;
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio_native_tls::native_tls;
+use tokio_native_tls::TlsConnector;
+
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+use crate::Scheme;
+
+/// Client of redis over TLS.
+///
+/// Connection will be established lazily and shared by all clones, it
+/// will be established again after the previous one is dropped.
+#[derive(Clone)]
+pub struct TlsClient {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    host: String,
+    port: u16,
+    redis: RedisConnectionInfo,
+    connector: TlsConnector,
+    conn: Mutex<Option<MultiplexedConnection>>,
+}
+
+impl TlsClient {
+    /// Create a new client, `ca_cert` is the PEM encoded certificate which
+    /// will be trusted besides system roots.
+    pub fn new(
+        host: &str,
+        port: u16,
+        redis: RedisConnectionInfo,
+        ca_cert: Option<&[u8]>,
+    ) -> Result<Self> {
+        let mut builder = native_tls::TlsConnector::builder();
+        if let Some(pem) = ca_cert {
+            let cert = native_tls::Certificate::from_pem(pem).map_err(|e| {
+                Error::new(ErrorKind::ConfigInvalid, "ca cert is invalid")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("field", "ca_cert")
+                    .set_source(e)
+            })?;
+            builder.add_root_certificate(cert);
+        }
+        let connector = builder.build().map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "build tls connector")
+                .with_context("service", Scheme::Redis)
+                .set_source(e)
+        })?;
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                host: host.to_string(),
+                port,
+                redis,
+                connector: TlsConnector::from(connector),
+                conn: Mutex::new(None),
+            }),
+        })
+    }
+
+    pub fn addr(&self) -> String {
+        format!("{}:{}", self.inner.host, self.inner.port)
+    }
+
+    pub fn db(&self) -> i64 {
+        self.inner.redis.db
+    }
+
+    pub fn username(&self) -> Option<&str> {
+        self.inner.redis.username.as_deref()
+    }
+
+    /// Get the shared connection, or connect to redis if not connected.
+    pub async fn get_connection(&self) -> Result<TlsConnection> {
+        let mut guard = self.inner.conn.lock().await;
+        let conn = match guard.as_ref() {
+            Some(conn) => conn.clone(),
+            None => {
+                let conn = self.connect().await?;
+                *guard = Some(conn.clone());
+                conn
+            }
+        };
+
+        Ok(TlsConnection {
+            conn,
+            client: self.clone(),
+        })
+    }
+
+    async fn connect(&self) -> Result<MultiplexedConnection> {
+        let addr = self.addr();
+        let tcp = TcpStream::connect((self.inner.host.as_str(), self.inner.port))
+            .await
+            .map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "connect to redis")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("addr", &addr)
+                    .set_source(e)
+                    .set_temporary()
+            })?;
+
+        // Handshake failures are caused by mismatched tls settings like
+        // connecting to a plaintext server or untrusted certs, retrying
+        // won't help.
+        let tls = self
+            .inner
+            .connector
+            .connect(&self.inner.host, tcp)
+            .await
+            .map_err(|e| {
+                Error::new(ErrorKind::ConfigInvalid, "tls handshake with redis failed")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("addr", &addr)
+                    .set_source(e)
+            })?;
+
+        let (conn, driver) = MultiplexedConnection::new(&self.inner.redis, tls).await?;
+        tokio::spawn(driver);
+
+        Ok(conn)
+    }
+
+    /// Drop the shared connection so that the next request will connect
+    /// again.
+    async fn reset(&self) {
+        self.inner.conn.lock().await.take();
+    }
+}
+
+/// Connection to redis over TLS which is acquired from [`TlsClient`].
+pub struct TlsConnection {
+    conn: MultiplexedConnection,
+    client: TlsClient,
+}
+
+impl ConnectionLike for TlsConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        Box::pin(async move {
+            let res = self.conn.req_packed_command(cmd).await;
+            if matches!(&res, Err(e) if e.is_io_error() || e.is_connection_dropped()) {
+                self.client.reset().await;
+            }
+            res
+        })
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        Box::pin(async move {
+            let res = self.conn.req_packed_commands(cmd, offset, count).await;
+            if matches!(&res, Err(e) if e.is_io_error() || e.is_connection_dropped()) {
+                self.client.reset().await;
+            }
+            res
+        })
+    }
+
+    fn get_db(&self) -> i64 {
+        self.conn.get_db()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    fn redis_info() -> RedisConnectionInfo {
+        RedisConnectionInfo {
+            db: 0,
+            username: None,
+            password: None,
+        }
+    }
+
+    #[test]
+    fn test_invalid_ca_cert() {
+        let err = TlsClient::new("127.0.0.1", 6379, redis_info(), Some(b"not a cert"))
+            .err()
+            .expect("new must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_tls_mismatch() {
+        // A plaintext server which replies redis errors.
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind must succeed");
+        let port = listener.local_addr().expect("addr must exist").port();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept must succeed");
+            let _ = stream.write_all(b"-ERR unknown command\r\n").await;
+        });
+
+        let client =
+            TlsClient::new("127.0.0.1", port, redis_info(), None).expect("new must succeed");
+        let err = client
+            .get_connection()
+            .await
+            .err()
+            .expect("connect must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert!(!err.is_temporary());
+    }
+}
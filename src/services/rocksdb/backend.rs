@@ -17,6 +17,8 @@ use std::fmt::Formatter;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use rocksdb::Direction;
+use rocksdb::IteratorMode;
 use rocksdb::TransactionDB;
 
 use crate::raw::adapters::kv;
@@ -36,11 +38,14 @@ pub struct Builder {
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &["root", "datadir"];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
         for (k, v) in it {
             let v = v.as_str();
             match k.as_ref() {
+                "root" => builder.root(v),
                 "datadir" => builder.datadir(v),
                 _ => continue,
             };
@@ -67,20 +72,16 @@ impl Builder {
     /// Consumes the builder and returns a `Rocksdb` instance.
     pub fn build(&mut self) -> Result<impl Accessor> {
         let path = self.datadir.take().ok_or_else(|| {
-            Error::new(
-                ErrorKind::BackendConfigInvalid,
-                "datadir is required but not set",
-            )
-            .with_context("service", Scheme::Rocksdb)
+            Error::new(ErrorKind::ConfigInvalid, "datadir is required but not set")
+                .with_context("field", "datadir")
+                .with_context("service", Scheme::Rocksdb)
         })?;
         let db = TransactionDB::open_default(&path).map_err(|e| {
-            Error::new(
-                ErrorKind::BackendConfigInvalid,
-                "open default transaction db",
-            )
-            .with_context("service", Scheme::Rocksdb)
-            .with_context("datadir", path)
-            .set_source(e)
+            Error::new(ErrorKind::ConfigInvalid, "open default transaction db")
+                .with_context("field", "datadir")
+                .with_context("service", Scheme::Rocksdb)
+                .with_context("datadir", path)
+                .set_source(e)
         })?;
 
         Ok(apply_wrapper(Backend::new(Adapter { db: Arc::new(db) })))
@@ -109,7 +110,11 @@ impl kv::Adapter for Adapter {
         kv::Metadata::new(
             Scheme::Rocksdb,
             &self.db.path().to_string_lossy(),
-            AccessorCapability::Read | AccessorCapability::Write,
+            AccessorCapability::Read
+                | AccessorCapability::Write
+                | AccessorCapability::List
+                | AccessorCapability::ListRecursive
+                | AccessorCapability::Blocking,
         )
     }
 
@@ -136,6 +141,33 @@ impl kv::Adapter for Adapter {
     fn blocking_delete(&self, path: &str) -> Result<()> {
         Ok(self.db.delete(path)?)
     }
+
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        self.blocking_scan(path)
+    }
+
+    fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
+        let it = self
+            .db
+            .iterator(IteratorMode::From(path.as_bytes(), Direction::Forward));
+
+        let mut keys = Vec::new();
+        for kv in it {
+            let (key, _) = kv?;
+            if !key.starts_with(path.as_bytes()) {
+                break;
+            }
+
+            let key = String::from_utf8(key.into_vec()).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "rocksdb key is not valid utf-8")
+                    .with_operation("kv::Adapter::blocking_scan")
+                    .set_source(err)
+            })?;
+            keys.push(key);
+        }
+
+        Ok(keys)
+    }
 }
 
 impl From<rocksdb::Error> for Error {
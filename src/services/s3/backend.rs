@@ -24,21 +24,28 @@ use bytes::Bytes;
 use http::header::HeaderName;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::HeaderMap;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
 use http::StatusCode;
 use log::debug;
 use log::error;
+use log::warn;
 use md5::Digest;
 use md5::Md5;
 use once_cell::sync::Lazy;
 use reqsign::AwsV4Signer;
 use serde::Deserialize;
 use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
+use super::credential::AssumeRoleLoader;
+use super::credential::DEFAULT_STS_ENDPOINT;
 use super::dir_stream::DirStream;
 use super::error::parse_error;
+use super::error::parse_error_code;
 use super::error::parse_xml_deserialize_error;
 use crate::raw::*;
 use crate::*;
@@ -54,6 +61,14 @@ static ENDPOINT_TEMPLATES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new
     m
 });
 
+/// The max expire of presigned requests allowed by SigV4.
+const PRESIGN_MAX_EXPIRE: time::Duration = time::Duration::days(7);
+
+/// The host suffix of Cloudflare R2 endpoints.
+const R2_ENDPOINT_SUFFIX: &str = ".r2.cloudflarestorage.com";
+/// Region used by R2 for all buckets.
+const R2_REGION: &str = "auto";
+
 mod constants {
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION: &str = "x-amz-server-side-encryption";
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
@@ -65,6 +80,13 @@ mod constants {
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID: &str =
         "x-amz-server-side-encryption-aws-kms-key-id";
     pub const X_AMZ_BUCKET_REGION: &str = "x-amz-bucket-region";
+    pub const X_AMZ_COPY_SOURCE: &str = "x-amz-copy-source";
+    pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
+        "x-amz-copy-source-server-side-encryption-customer-algorithm";
+    pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY: &str =
+        "x-amz-copy-source-server-side-encryption-customer-key";
+    pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5: &str =
+        "x-amz-copy-source-server-side-encryption-customer-key-md5";
 }
 
 /// Builder for s3 services
@@ -76,7 +98,10 @@ pub struct Builder {
     endpoint: Option<String>,
     region: Option<String>,
     role_arn: Option<String>,
+    role_session_name: Option<String>,
     external_id: Option<String>,
+    web_identity_token_file: Option<String>,
+    sts_endpoint: Option<String>,
     access_key_id: Option<String>,
     secret_access_key: Option<String>,
     server_side_encryption: Option<String>,
@@ -90,6 +115,12 @@ pub struct Builder {
 
     disable_credential_loader: bool,
     enable_virtual_host_style: bool,
+    allow_anonymous: bool,
+    enable_r2_compatible: bool,
+
+    http_client: Option<reqwest::Client>,
+    pool_max_idle_per_host: Option<usize>,
+    connect_timeout: Option<std::time::Duration>,
 }
 
 impl Debug for Builder {
@@ -101,9 +132,14 @@ impl Debug for Builder {
             .field("endpoint", &self.endpoint)
             .field("region", &self.region)
             .field("role_arn", &self.role_arn)
+            .field("role_session_name", &self.role_session_name)
             .field("external_id", &self.external_id)
+            .field("web_identity_token_file", &self.web_identity_token_file)
+            .field("sts_endpoint", &self.sts_endpoint)
             .field("disable_credential_loader", &self.disable_credential_loader)
-            .field("enable_virtual_host_style", &self.disable_credential_loader);
+            .field("enable_virtual_host_style", &self.enable_virtual_host_style)
+            .field("enable_r2_compatible", &self.enable_r2_compatible)
+            .field("allow_anonymous", &self.allow_anonymous);
 
         if self.access_key_id.is_some() {
             d.field("access_key_id", &"<redacted>");
@@ -131,11 +167,42 @@ impl Debug for Builder {
             d.field("security_token", &"<redacted>");
         }
 
+        d.field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field("connect_timeout", &self.connect_timeout);
+        if self.http_client.is_some() {
+            d.field("http_client", &"<custom>");
+        }
         d.finish()
     }
 }
 
 impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "bucket",
+        "endpoint",
+        "region",
+        "access_key_id",
+        "secret_access_key",
+        "security_token",
+        "role_arn",
+        "role_session_name",
+        "external_id",
+        "web_identity_token_file",
+        "sts_endpoint",
+        "server_side_encryption",
+        "server_side_encryption_aws_kms_key_id",
+        "server_side_encryption_customer_algorithm",
+        "server_side_encryption_customer_key",
+        "server_side_encryption_customer_key_md5",
+        "disable_credential_loader",
+        "enable_virtual_host_style",
+        "allow_anonymous",
+        "enable_r2_compatible",
+        "pool_max_idle_per_host",
+        "connect_timeout",
+    ];
+
     pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
         let mut builder = Builder::default();
 
@@ -150,7 +217,10 @@ impl Builder {
                 "secret_access_key" => builder.secret_access_key(v),
                 "security_token" => builder.security_token(v),
                 "role_arn" => builder.role_arn(v),
+                "role_session_name" => builder.role_session_name(v),
                 "external_id" => builder.external_id(v),
+                "web_identity_token_file" => builder.web_identity_token_file(v),
+                "sts_endpoint" => builder.sts_endpoint(v),
                 "server_side_encryption" => builder.server_side_encryption(v),
                 "server_side_encryption_aws_kms_key_id" => {
                     builder.server_side_encryption_aws_kms_key_id(v)
@@ -166,6 +236,16 @@ impl Builder {
                 }
                 "disable_credential_loader" if !v.is_empty() => builder.disable_credential_loader(),
                 "enable_virtual_host_style" if !v.is_empty() => builder.enable_virtual_host_style(),
+                "allow_anonymous" if !v.is_empty() => builder.allow_anonymous(),
+                "enable_r2_compatible" if !v.is_empty() => builder.enable_r2_compatible(),
+                "pool_max_idle_per_host" => match v.parse::<usize>() {
+                    Ok(v) => builder.pool_max_idle_per_host(v),
+                    _ => continue,
+                },
+                "connect_timeout" => match v.parse::<u64>() {
+                    Ok(v) => builder.connect_timeout(std::time::Duration::from_secs(v)),
+                    _ => continue,
+                },
                 _ => continue,
             };
         }
@@ -252,6 +332,18 @@ impl Builder {
     }
 
     /// Set role_arn for this backend.
+    ///
+    /// If set, opendal will call AWS STS to assume this role and sign
+    /// requests with the temporary credentials:
+    ///
+    /// - If `web_identity_token_file` is set, `AssumeRoleWithWebIdentity`
+    ///   will be called with the token in it.
+    /// - Otherwise, `AssumeRole` will be called with source credentials,
+    ///   which are `access_key_id` and `secret_access_key` if set, or loaded
+    ///   from environment.
+    ///
+    /// Temporary credentials will be cached and refreshed by the first
+    /// request 2 minutes before they expire.
     pub fn role_arn(&mut self, v: &str) -> &mut Self {
         if !v.is_empty() {
             self.role_arn = Some(v.to_string())
@@ -260,6 +352,15 @@ impl Builder {
         self
     }
 
+    /// Set role_session_name for this backend, default to `opendal`.
+    pub fn role_session_name(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.role_session_name = Some(v.to_string())
+        }
+
+        self
+    }
+
     /// Set external_id for this backend.
     pub fn external_id(&mut self, v: &str) -> &mut Self {
         if !v.is_empty() {
@@ -269,6 +370,33 @@ impl Builder {
         self
     }
 
+    /// Set web_identity_token_file for this backend.
+    ///
+    /// The file will be read every time the credentials are refreshed, so
+    /// that rotated tokens (like in EKS) can be used.
+    ///
+    /// Requires `role_arn` to be set.
+    pub fn web_identity_token_file(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.web_identity_token_file = Some(v.to_string())
+        }
+
+        self
+    }
+
+    /// Set sts_endpoint for this backend.
+    ///
+    /// - If not set, `https://sts.amazonaws.com` will be used and requests
+    ///   will be signed with region `us-east-1`.
+    /// - If set, requests will be signed with the region of this backend.
+    pub fn sts_endpoint(&mut self, v: &str) -> &mut Self {
+        if !v.is_empty() {
+            self.sts_endpoint = Some(v.to_string())
+        }
+
+        self
+    }
+
     /// Set server_side_encryption for this backend.
     ///
     /// Available values: `AES256`, `aws:kms`.
@@ -451,6 +579,92 @@ impl Builder {
         self
     }
 
+    /// Allow anonymous access so that opendal will send requests without
+    /// signing them. This is useful for public-read buckets.
+    ///
+    /// Credentials will not be loaded, and presign will not be supported.
+    /// Operations that require permission like write will be rejected by
+    /// the server with `PermissionDenied`.
+    pub fn allow_anonymous(&mut self) -> &mut Self {
+        self.allow_anonymous = true;
+        self
+    }
+
+    /// Enable Cloudflare R2 compatible mode.
+    ///
+    /// This mode will be enabled automatically if endpoint is like
+    /// `https://<account_id>.r2.cloudflarestorage.com`.
+    ///
+    /// - Region will be `auto` if not set.
+    /// - Requests will always be sent in path style, `enable_virtual_host_style`
+    ///   will be ignored.
+    /// - `server_side_encryption` with `AES256` will not be sent since R2
+    ///   encrypts all objects at rest, `aws:kms` is not supported.
+    /// - Tagging and versioning will not be supported.
+    ///
+    /// R2 requires all parts except the last one of a multipart upload to
+    /// be in the same size, please don't `flush` [`ObjectWriter`] explicitly.
+    ///
+    /// [`ObjectWriter`]: crate::ObjectWriter
+    pub fn enable_r2_compatible(&mut self) -> &mut Self {
+        self.enable_r2_compatible = true;
+        self
+    }
+
+    /// Build the loader to load credentials of `role_arn` via AWS STS.
+    fn build_assume_role_loader(
+        &self,
+        client: &HttpClient,
+        region: &str,
+        role_arn: &str,
+    ) -> Result<AssumeRoleLoader> {
+        let (sts_endpoint, sts_region) = match &self.sts_endpoint {
+            Some(v) if v.starts_with("http") => (v.to_string(), region),
+            Some(v) => (format!("https://{v}"), region),
+            None => (DEFAULT_STS_ENDPOINT.to_string(), "us-east-1"),
+        };
+
+        // Source credentials are used to sign `AssumeRole` requests.
+        let mut source_builder = AwsV4Signer::builder();
+        source_builder.service("sts");
+        source_builder.region(sts_region);
+        source_builder.disable_load_from_assume_role();
+        source_builder.disable_load_from_assume_role_with_web_identity();
+        if self.disable_credential_loader {
+            source_builder.disable_load_from_env();
+            source_builder.disable_load_from_profile();
+            source_builder.disable_load_from_imds_v2();
+        }
+        if let Some(v) = &self.security_token {
+            source_builder.security_token(v);
+        }
+        if let (Some(ak), Some(sk)) = (&self.access_key_id, &self.secret_access_key) {
+            source_builder.access_key(ak);
+            source_builder.secret_key(sk);
+        }
+        let source = source_builder.build().map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "build AwsV4Signer for sts")
+                .with_context("service", Scheme::S3)
+                .with_context("field", "sts_endpoint")
+                .set_source(e)
+        })?;
+
+        let mut loader =
+            AssumeRoleLoader::new(client.clone(), &sts_endpoint, role_arn).with_source(source);
+        if let Some(v) = &self.role_session_name {
+            loader = loader.with_role_session_name(v);
+        }
+        if let Some(v) = &self.external_id {
+            loader = loader.with_external_id(v);
+        }
+        if let Some(v) = &self.web_identity_token_file {
+            loader = loader.with_web_identity_token_file(v);
+        }
+        debug!("backend use assume role loader: {loader:?}");
+
+        Ok(loader)
+    }
+
     /// Read RFC-0057: Auto Region for detailed behavior.
     ///
     /// - If region is already known, the region will be returned directly.
@@ -491,8 +705,9 @@ impl Builder {
 
         let req = http::Request::head(&url).body(Body::Empty).map_err(|e| {
             error!("backend detect_region {}: {:?}", url, e);
-            Error::new(ErrorKind::Unexpected, "build request for head")
+            Error::new(ErrorKind::ConfigInvalid, "build request for head")
                 .with_context("service", Scheme::S3)
+                .with_context("field", "endpoint")
                 .with_context("url", &url)
                 .set_source(e)
         })?;
@@ -534,9 +749,10 @@ impl Builder {
                     .to_string();
                 let template = ENDPOINT_TEMPLATES.get(endpoint.as_str()).ok_or_else(|| {
                     Error::new(
-                        ErrorKind::Unexpected,
+                        ErrorKind::ConfigInvalid,
                         "can't detect region automatically, no valid endpoint template",
                     )
+                    .with_context("field", "region")
                 })?;
 
                 let endpoint = template.replace("{region}", &region);
@@ -552,6 +768,32 @@ impl Builder {
         }
     }
 
+    /// Set the http client used by this backend.
+    ///
+    /// Connections will be reused across operators that share clones of
+    /// the same `client`. Passing a custom client overrides
+    /// [`Builder::pool_max_idle_per_host`] and [`Builder::connect_timeout`].
+    pub fn http_client(&mut self, client: reqwest::Client) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Set the max idle connections kept alive per host.
+    ///
+    /// This will be ignored if [`Builder::http_client`] is set.
+    pub fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set the timeout for establishing connections.
+    ///
+    /// This will be ignored if [`Builder::http_client`] is set.
+    pub fn connect_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
     /// Finish the build process and create a new accessor.
     pub fn build(&mut self) -> Result<impl Accessor> {
         debug!("backend build started: {:?}", &self);
@@ -562,20 +804,44 @@ impl Builder {
         // Handle endpoint, region and bucket name.
         let bucket = match self.bucket.is_empty() {
             false => Ok(&self.bucket),
-            true => Err(Error::new(
-                ErrorKind::BackendConfigInvalid,
-                "bucket is empty",
-            )),
+            true => Err(Error::new(ErrorKind::ConfigInvalid, "bucket is empty")
+                .with_context("service", Scheme::S3)
+                .with_context("field", "bucket")),
         }?;
         debug!("backend use bucket {}", &bucket);
 
+        let r2_compatible =
+            self.enable_r2_compatible || self.endpoint.as_deref().map_or(false, is_r2_endpoint);
+        if r2_compatible {
+            debug!("backend use r2 compatible mode");
+            if self.region.is_none() {
+                self.region = Some(R2_REGION.to_string());
+            }
+
+            if self.server_side_encryption_aws_kms_key_id.is_some()
+                || self.server_side_encryption.as_deref() == Some("aws:kms")
+            {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "server_side_encryption with aws:kms is not supported by r2",
+                )
+                .with_context("service", Scheme::S3)
+                .with_context("field", "server_side_encryption"));
+            }
+            // R2 encrypts all objects at rest and rejects this header.
+            if self.server_side_encryption.take().is_some() {
+                warn!("backend ignores server_side_encryption in r2 compatible mode");
+            }
+        }
+
         let server_side_encryption = match &self.server_side_encryption {
             None => None,
             Some(v) => Some(v.parse().map_err(|e| {
                 Error::new(
-                    ErrorKind::BackendConfigInvalid,
+                    ErrorKind::ConfigInvalid,
                     "server_side_encryption value is invalid",
                 )
+                .with_context("field", "server_side_encryption")
                 .with_context("value", v)
                 .set_source(e)
             })?),
@@ -586,9 +852,10 @@ impl Builder {
                 None => None,
                 Some(v) => Some(v.parse().map_err(|e| {
                     Error::new(
-                        ErrorKind::BackendConfigInvalid,
+                        ErrorKind::ConfigInvalid,
                         "server_side_encryption_aws_kms_key_id value is invalid",
                     )
+                    .with_context("field", "server_side_encryption_aws_kms_key_id")
                     .with_context("value", v)
                     .set_source(e)
                 })?),
@@ -599,9 +866,10 @@ impl Builder {
                 None => None,
                 Some(v) => Some(v.parse().map_err(|e| {
                     Error::new(
-                        ErrorKind::BackendConfigInvalid,
+                        ErrorKind::ConfigInvalid,
                         "server_side_encryption_customer_algorithm value is invalid",
                     )
+                    .with_context("field", "server_side_encryption_customer_algorithm")
                     .with_context("value", v)
                     .set_source(e)
                 })?),
@@ -611,9 +879,10 @@ impl Builder {
             None => None,
             Some(v) => Some(v.parse().map_err(|e| {
                 Error::new(
-                    ErrorKind::BackendConfigInvalid,
+                    ErrorKind::ConfigInvalid,
                     "server_side_encryption_customer_key value is invalid",
                 )
+                .with_context("field", "server_side_encryption_customer_key")
                 .with_context("value", v)
                 .set_source(e)
             })?),
@@ -623,38 +892,43 @@ impl Builder {
                 None => None,
                 Some(v) => Some(v.parse().map_err(|e| {
                     Error::new(
-                        ErrorKind::BackendConfigInvalid,
+                        ErrorKind::ConfigInvalid,
                         "server_side_encryption_customer_key_md5 value is invalid",
                     )
+                    .with_context("field", "server_side_encryption_customer_key_md5")
                     .with_context("value", v)
                     .set_source(e)
                 })?),
             };
 
-        let client = HttpClient::new();
+        let client = match self.http_client.take() {
+            Some(client) => HttpClient::with_client(client),
+            None => {
+                HttpClient::with_pool_options(self.pool_max_idle_per_host, self.connect_timeout)
+            }
+        };
 
         let (mut endpoint, region) = self.detect_region(&client, bucket)?;
         // Construct endpoint which contains bucket name.
-        if self.enable_virtual_host_style {
+        if self.enable_virtual_host_style && !r2_compatible {
             endpoint = endpoint.replace("//", &format!("//{bucket}."))
         } else {
             write!(endpoint, "/{bucket}").expect("write into string must succeed");
         }
         debug!("backend use endpoint: {}, region: {}", &endpoint, &region);
 
+        if self.web_identity_token_file.is_some() && self.role_arn.is_none() {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "web_identity_token_file requires role_arn to be set",
+            )
+            .with_context("service", Scheme::S3)
+            .with_context("field", "role_arn"));
+        }
+
         let mut signer_builder = AwsV4Signer::builder();
         signer_builder.service("s3");
         signer_builder.region(&region);
-        signer_builder.allow_anonymous();
-        if let Some(v) = &self.security_token {
-            signer_builder.security_token(v);
-        }
-        if let Some(v) = &self.role_arn {
-            signer_builder.role_arn(v);
-        }
-        if let Some(v) = &self.external_id {
-            signer_builder.external_id(v);
-        }
         if self.disable_credential_loader {
             signer_builder.disable_load_from_env();
             signer_builder.disable_load_from_profile();
@@ -663,20 +937,51 @@ impl Builder {
             signer_builder.disable_load_from_assume_role_with_web_identity();
         }
 
-        if let (Some(ak), Some(sk)) = (&self.access_key_id, &self.secret_access_key) {
-            signer_builder.access_key(ak);
-            signer_builder.secret_key(sk);
+        // Presigned requests must be signed by static credentials, tokens
+        // loaded from instance metadata will expire before the requests.
+        let static_credential = !self.allow_anonymous
+            && self.role_arn.is_none()
+            && self.access_key_id.is_some()
+            && self.secret_access_key.is_some();
+
+        match &self.role_arn {
+            Some(role_arn) if !self.allow_anonymous => {
+                let loader = self.build_assume_role_loader(&client, &region, role_arn)?;
+                signer_builder.customed_credential_loader(loader);
+                // Never fallback to other credentials if assume role failed.
+                signer_builder.disable_load_from_env();
+                signer_builder.disable_load_from_profile();
+                signer_builder.disable_load_from_imds_v2();
+                signer_builder.disable_load_from_assume_role();
+                signer_builder.disable_load_from_assume_role_with_web_identity();
+            }
+            _ => {
+                signer_builder.allow_anonymous();
+                if let Some(v) = &self.security_token {
+                    signer_builder.security_token(v);
+                }
+                if let (Some(ak), Some(sk)) = (&self.access_key_id, &self.secret_access_key) {
+                    signer_builder.access_key(ak);
+                    signer_builder.secret_key(sk);
+                }
+            }
         }
 
-        let signer = signer_builder
-            .build()
-            .map_err(|e| Error::new(ErrorKind::Unexpected, "build AwsV4Signer").set_source(e))?;
+        let signer = signer_builder.build().map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "build AwsV4Signer")
+                .with_context("service", Scheme::S3)
+                .with_context("field", "region")
+                .set_source(e)
+        })?;
 
         debug!("backend build finished: {:?}", &self);
         Ok(apply_wrapper(Backend {
             root,
             endpoint,
             signer: Arc::new(signer),
+            static_credential,
+            allow_anonymous: self.allow_anonymous,
+            r2_compatible,
             bucket: self.bucket.clone(),
             client,
 
@@ -695,6 +1000,9 @@ pub struct Backend {
     bucket: String,
     endpoint: String,
     signer: Arc<AwsV4Signer>,
+    static_credential: bool,
+    allow_anonymous: bool,
+    r2_compatible: bool,
     client: HttpClient,
     // root will be "/" or "/abc/"
     root: String,
@@ -707,10 +1015,19 @@ pub struct Backend {
 }
 
 impl Backend {
+    /// Sign the request unless anonymous access is allowed.
+    fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        if self.allow_anonymous {
+            return Ok(());
+        }
+
+        self.signer.sign(req).map_err(new_request_sign_error)
+    }
+
     /// # Note
     ///
     /// header like X_AMZ_SERVER_SIDE_ENCRYPTION doesn't need to set while
-    //  get or stat.
+    /// get or stat, and is not allowed while uploading parts.
     pub(crate) fn insert_sse_headers(
         &self,
         mut req: http::request::Builder,
@@ -772,17 +1089,29 @@ impl Backend {
 #[async_trait]
 impl Accessor for Backend {
     fn metadata(&self) -> AccessorMetadata {
+        let mut capabilities = AccessorCapability::Read
+            | AccessorCapability::Write
+            | AccessorCapability::List
+            | AccessorCapability::Multipart
+            | AccessorCapability::Batch
+            | AccessorCapability::ConditionalWrite
+            | AccessorCapability::ConditionalRead
+            | AccessorCapability::ListStartAfter
+            | AccessorCapability::ListRecursive
+            | AccessorCapability::Copy;
+        // R2 doesn't support object tagging and versioning.
+        if !self.r2_compatible {
+            capabilities |= AccessorCapability::Tagging | AccessorCapability::Versioning;
+        }
+        if self.static_credential {
+            capabilities |= AccessorCapability::Presign;
+        }
+
         let mut am = AccessorMetadata::default();
         am.set_scheme(Scheme::S3)
             .set_root(&self.root)
             .set_name(&self.bucket)
-            .set_capabilities(
-                AccessorCapability::Read
-                    | AccessorCapability::Write
-                    | AccessorCapability::List
-                    | AccessorCapability::Presign
-                    | AccessorCapability::Multipart,
-            );
+            .set_capabilities(capabilities);
 
         am
     }
@@ -790,7 +1119,7 @@ impl Accessor for Backend {
     async fn create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
         let mut req = self.s3_put_object_request(path, Some(0), None, AsyncBody::Empty)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         let resp = self.client.send_async(req).await?;
 
@@ -806,13 +1135,14 @@ impl Accessor for Backend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
-        let resp = self.s3_get_object(path, args.range()).await?;
+        let resp = self.s3_get_object(path, &args).await?;
 
         let status = resp.status();
 
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                let meta = parse_into_object_metadata(path, resp.headers())?;
+                let mut meta = parse_into_object_metadata(path, resp.headers())?;
+                fill_content_md5(&mut meta, resp.headers());
                 Ok((RpRead::with_metadata(meta), resp.into_body().reader()))
             }
             _ => Err(parse_error(resp).await?),
@@ -826,8 +1156,13 @@ impl Accessor for Backend {
             args.content_type(),
             AsyncBody::Reader(r),
         )?;
+        insert_content_disposition(req.headers_mut(), &args)?;
+        insert_cache_control(req.headers_mut(), &args)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        insert_expires(req.headers_mut(), &args)?;
+        insert_write_conditions(req.headers_mut(), &args)?;
+
+        self.sign(&mut req)?;
 
         let resp = self.client.send_async(req).await?;
 
@@ -835,25 +1170,35 @@ impl Accessor for Backend {
 
         match status {
             StatusCode::CREATED | StatusCode::OK => {
+                let etag = parse_etag(resp.headers())?;
                 resp.into_body().consume().await?;
-                Ok(RpWrite::new(args.size()))
+
+                let rp = RpWrite::new(args.size());
+                Ok(match etag {
+                    Some(v) => rp.with_etag(&v),
+                    None => rp,
+                })
             }
             _ => Err(parse_error(resp).await?),
         }
     }
 
-    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         // Stat root always returns a DIR.
         if path == "/" {
             return Ok(RpStat::new(ObjectMetadata::new(ObjectMode::DIR)));
         }
 
-        let resp = self.s3_head_object(path).await?;
+        let resp = self.s3_head_object(path, args.version()).await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::OK => parse_into_object_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::OK => {
+                let mut meta = parse_into_object_metadata(path, resp.headers())?;
+                fill_content_md5(&mut meta, resp.headers());
+                Ok(RpStat::new(meta))
+            }
             StatusCode::NOT_FOUND if path.ends_with('/') => {
                 Ok(RpStat::new(ObjectMetadata::new(ObjectMode::DIR)))
             }
@@ -861,32 +1206,86 @@ impl Accessor for Backend {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.s3_delete_object(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self.s3_delete_object(path, args.version()).await?;
+
+        let status = resp.status();
+
+        match status {
+            // Some s3 compatible services will return 404 for not exist objects.
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Ok(RpDelete::default()),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        let resp = self.s3_copy_object(from, to).await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::NO_CONTENT => Ok(RpDelete::default()),
+            StatusCode::OK => {
+                // Copy could fail after `200 OK` has been sent, s3 will
+                // return the error in body instead.
+                let (parts, body) = resp.into_parts();
+                let bs = body.bytes().await?;
+                if String::from_utf8_lossy(&bs).contains("<Error>") {
+                    return Err(
+                        Error::new(ErrorKind::Unexpected, &String::from_utf8_lossy(&bs))
+                            .with_context("response", format!("{:?}", parts))
+                            .set_temporary(),
+                    );
+                }
+
+                Ok(RpCopy::default())
+            }
             _ => Err(parse_error(resp).await?),
         }
     }
 
-    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, ObjectPager)> {
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
         Ok((
             RpList::default(),
-            Box::new(DirStream::new(Arc::new(self.clone()), &self.root, path)),
+            Box::new(DirStream::new(
+                Arc::new(self.clone()),
+                &self.root,
+                path,
+                if args.recursive() { "" } else { "/" },
+                args.limit(),
+                args.start_after(),
+            )),
         ))
     }
 
     fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        if !self.static_credential {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "presign requires static credentials, please set access_key_id and secret_access_key",
+            )
+            .with_operation(Operation::Presign.into_static()));
+        }
+        if args.expire() > PRESIGN_MAX_EXPIRE {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "presign expire is longer than the maximum 7 days allowed by SigV4",
+            )
+            .with_operation(Operation::Presign.into_static())
+            .with_context("expire", args.expire().to_string()));
+        }
+
         // We will not send this request out, just for signing.
         let mut req = match args.operation() {
-            PresignOperation::Stat(_) => self.s3_head_object_request(path)?,
-            PresignOperation::Read(v) => self.s3_get_object_request(path, v.range())?,
-            PresignOperation::Write(_) => {
-                self.s3_put_object_request(path, None, None, AsyncBody::Empty)?
-            }
+            PresignOperation::Stat(v) => self.s3_head_object_request(path, v.version())?,
+            PresignOperation::Read(v) => self.s3_get_object_request(path, v)?,
+            // Content length and type will be part of the signed headers, so
+            // that uploads with different values will be rejected by s3.
+            PresignOperation::Write(v) => self.s3_put_object_request(
+                path,
+                (v.size() > 0).then(|| v.size()),
+                v.content_type(),
+                AsyncBody::Empty,
+            )?,
             PresignOperation::WriteMultipart(v) => self.s3_upload_part_request(
                 path,
                 v.upload_id(),
@@ -913,9 +1312,11 @@ impl Accessor for Backend {
     async fn create_multipart(
         &self,
         path: &str,
-        _: OpCreateMultipart,
+        args: OpCreateMultipart,
     ) -> Result<RpCreateMultipart> {
-        let resp = self.s3_initiate_multipart_upload(path).await?;
+        let resp = self
+            .s3_initiate_multipart_upload(path, args.content_type())
+            .await?;
 
         let status = resp.status();
 
@@ -946,7 +1347,7 @@ impl Accessor for Backend {
             AsyncBody::Reader(r),
         )?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         let resp = self.client.send_async(req).await?;
 
@@ -954,14 +1355,12 @@ impl Accessor for Backend {
 
         match status {
             StatusCode::OK => {
-                let etag = parse_etag(resp.headers())?
-                    .ok_or_else(|| {
-                        Error::new(
-                            ErrorKind::Unexpected,
-                            "ETag not present in returning response",
-                        )
-                    })?
-                    .to_string();
+                let etag = parse_etag(resp.headers())?.ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "ETag not present in returning response",
+                    )
+                })?;
 
                 resp.into_body().consume().await?;
 
@@ -1012,13 +1411,237 @@ impl Accessor for Backend {
             _ => Err(parse_error(resp).await?),
         }
     }
+
+    async fn list_multipart(&self, path: &str, args: OpListMultipart) -> Result<RpListMultipart> {
+        let resp = self
+            .s3_list_multipart_uploads(path, args.key_marker(), args.upload_id_marker())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let result: ListMultipartUploadsResult =
+                    quick_xml::de::from_reader(bs.reader()).map_err(parse_xml_deserialize_error)?;
+
+                let mut uploads = Vec::with_capacity(result.upload.len());
+                for upload in result.upload {
+                    let initiated =
+                        OffsetDateTime::parse(&upload.initiated, &Rfc3339).map_err(|e| {
+                            Error::new(ErrorKind::Unexpected, "parse initiated RFC3339 datetime")
+                                .set_source(e)
+                        })?;
+
+                    uploads.push(
+                        ObjectUpload::new(
+                            &build_rel_path(&self.root, &upload.key),
+                            &upload.upload_id,
+                        )
+                        .with_initiated(initiated),
+                    );
+                }
+
+                let mut rp = RpListMultipart::new(uploads);
+                // Markers are returned as is so that we can continue with
+                // exactly the same position.
+                if result.is_truncated && !result.next_key_marker.is_empty() {
+                    rp =
+                        rp.with_next_marker(&result.next_key_marker, &result.next_upload_id_marker);
+                }
+                Ok(rp)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        let resp = self
+            .s3_list_object_versions(path, args.key_marker(), args.version_id_marker())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let result: ListVersionsResult =
+                    quick_xml::de::from_reader(bs.reader()).map_err(parse_xml_deserialize_error)?;
+
+                let mut entries = result
+                    .version
+                    .into_iter()
+                    .map(|v| (v, false))
+                    .chain(result.delete_marker.into_iter().map(|v| (v, true)))
+                    .collect::<Vec<_>>();
+                // Restore the order of response: keys in ascending order,
+                // versions of the same key from newest to oldest.
+                entries.sort_by(|(a, _), (b, _)| {
+                    a.key
+                        .cmp(&b.key)
+                        .then_with(|| b.last_modified.cmp(&a.last_modified))
+                });
+
+                let mut versions = Vec::with_capacity(entries.len());
+                for (v, is_delete_marker) in entries {
+                    let mut version =
+                        ObjectVersion::new(&build_rel_path(&self.root, &v.key), &v.version_id)
+                            .with_is_latest(v.is_latest)
+                            .with_is_delete_marker(is_delete_marker);
+                    if !v.last_modified.is_empty() {
+                        let dt =
+                            OffsetDateTime::parse(&v.last_modified, &Rfc3339).map_err(|e| {
+                                Error::new(
+                                    ErrorKind::Unexpected,
+                                    "parse last modified RFC3339 datetime",
+                                )
+                                .set_source(e)
+                            })?;
+                        version = version.with_last_modified(dt);
+                    }
+                    if !is_delete_marker {
+                        version = version.with_content_length(v.size);
+                        if !v.etag.is_empty() {
+                            version = version.with_etag(&v.etag);
+                        }
+                    }
+                    versions.push(version);
+                }
+
+                let mut rp = RpListVersions::new(versions);
+                // Markers are returned as is so that we can continue with
+                // exactly the same position.
+                if result.is_truncated && !result.next_key_marker.is_empty() {
+                    rp = rp
+                        .with_next_marker(&result.next_key_marker, &result.next_version_id_marker);
+                }
+                Ok(rp)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let BatchOperations::Delete(ops) = args.into_operation();
+        if ops.len() > 1000 {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "s3 allows at most 1000 keys in one batch delete",
+            )
+            .with_operation(Operation::Batch.into_static())
+            .with_context("service", Scheme::S3.into_static())
+            .with_context("length", ops.len().to_string()));
+        }
+
+        let paths = ops.into_iter().map(|(path, _)| path).collect::<Vec<_>>();
+        let resp = self.s3_delete_objects(&paths).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let result: DeleteObjectsResult =
+                    quick_xml::de::from_reader(bs.reader()).map_err(parse_xml_deserialize_error)?;
+
+                let mut errors = result
+                    .error
+                    .into_iter()
+                    .map(|v| (v.key.clone(), v))
+                    .collect::<HashMap<_, _>>();
+
+                let results = paths
+                    .into_iter()
+                    .map(|path| {
+                        let res = match errors.remove(&build_abs_path(&self.root, &path)) {
+                            // Deleting not existing objects is ok.
+                            Some(err) if err.code != "NoSuchKey" => {
+                                Err(parse_error_code(&err.code, &err.message)
+                                    .with_operation(Operation::Delete.into_static())
+                                    .with_context("path", &path))
+                            }
+                            _ => Ok(RpDelete::default()),
+                        };
+                        (path, res)
+                    })
+                    .collect();
+
+                Ok(RpBatch::new(BatchedResults::Delete(results)))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn get_tags(&self, path: &str, _: OpGetTags) -> Result<RpGetTags> {
+        let resp = self.s3_get_object_tagging(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let result: Tagging =
+                    quick_xml::de::from_reader(bs.reader()).map_err(parse_xml_deserialize_error)?;
+
+                Ok(RpGetTags::new(
+                    result
+                        .tag_set
+                        .tag
+                        .into_iter()
+                        .map(|v| (v.key, v.value))
+                        .collect(),
+                ))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        let resp = self.s3_put_object_tagging(path, args.tags()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpSetTags::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete_tags(&self, path: &str, _: OpDeleteTags) -> Result<RpDeleteTags> {
+        let resp = self.s3_delete_object_tagging(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::NO_CONTENT | StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpDeleteTags::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
 }
 
 impl Backend {
-    fn s3_head_object_request(&self, path: &str) -> Result<Request<AsyncBody>> {
+    fn s3_head_object_request(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let mut url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        if let Some(version) = version {
+            write!(url, "?versionId={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::head(&url);
 
@@ -1031,15 +1654,27 @@ impl Backend {
         Ok(req)
     }
 
-    fn s3_get_object_request(&self, path: &str, range: BytesRange) -> Result<Request<AsyncBody>> {
+    fn s3_get_object_request(&self, path: &str, args: &OpRead) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let mut url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        if let Some(version) = args.version() {
+            write!(url, "?versionId={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::get(&url);
 
-        if !range.is_full() {
-            req = req.header(http::header::RANGE, range.to_header());
+        let range = args.range();
+        if let Some(range_header) = range.to_header() {
+            req = req.header(http::header::RANGE, range_header);
+        }
+
+        if let Some(if_match) = args.if_match() {
+            req = req.header(http::header::IF_MATCH, if_match);
+        }
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(http::header::IF_NONE_MATCH, if_none_match);
         }
 
         // Set SSE headers.
@@ -1056,11 +1691,11 @@ impl Backend {
     async fn s3_get_object(
         &self,
         path: &str,
-        range: BytesRange,
+        args: &OpRead,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.s3_get_object_request(path, range)?;
+        let mut req = self.s3_get_object_request(path, args)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         self.client.send_async(req).await
     }
@@ -1095,35 +1730,83 @@ impl Backend {
         Ok(req)
     }
 
-    async fn s3_head_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
-        let p = build_abs_path(&self.root, path);
+    async fn s3_head_object(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self.s3_head_object_request(path, version)?;
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        self.sign(&mut req)?;
 
-        let mut req = Request::head(&url);
+        self.client.send_async(req).await
+    }
 
-        // Set SSE headers.
-        req = self.insert_sse_headers(req, false);
+    async fn s3_copy_object(&self, from: &str, to: &str) -> Result<Response<IncomingAsyncBody>> {
+        let source = build_abs_path(&self.root, from);
+        let target = build_abs_path(&self.root, to);
+
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&target));
+
+        let mut req = Request::put(&url).header(
+            HeaderName::from_static(constants::X_AMZ_COPY_SOURCE),
+            format!("{}/{}", self.bucket, percent_encode_path(&source)),
+        );
+
+        // Set SSE headers for target.
+        req = self.insert_sse_headers(req, true);
+
+        // Source is encrypted by the same customer key since they are
+        // in the same bucket.
+        for (name, value) in [
+            (
+                constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
+                &self.server_side_encryption_customer_algorithm,
+            ),
+            (
+                constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY,
+                &self.server_side_encryption_customer_key,
+            ),
+            (
+                constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5,
+                &self.server_side_encryption_customer_key_md5,
+            ),
+        ] {
+            if let Some(v) = value {
+                let mut v = v.clone();
+                v.set_sensitive(true);
+
+                req = req.header(HeaderName::from_static(name), v)
+            }
+        }
 
         let mut req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         self.client.send_async(req).await
     }
 
-    async fn s3_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+    async fn s3_delete_object(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let mut url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        if let Some(version) = version {
+            write!(url, "?versionId={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::delete(&url)
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         self.client.send_async(req).await
     }
@@ -1133,15 +1816,25 @@ impl Backend {
     pub(super) async fn s3_list_objects(
         &self,
         path: &str,
+        delimiter: &str,
         continuation_token: &str,
+        limit: Option<usize>,
+        start_after: Option<&str>,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let mut url = format!(
-            "{}?list-type=2&delimiter=/&prefix={}",
+            "{}?list-type=2&prefix={}",
             self.endpoint,
             percent_encode_path(&p)
         );
+        // Empty delimiter means list all objects under prefix recursively.
+        if !delimiter.is_empty() {
+            write!(url, "&delimiter={delimiter}").expect("write into string must succeed");
+        }
+        if let Some(limit) = limit {
+            write!(url, "&max-keys={limit}").expect("write into string must succeed");
+        }
         if !continuation_token.is_empty() {
             // AWS S3 could return continuation-token that contains `=`
             // which could lead `reqsign` parse query wrongly.
@@ -1153,13 +1846,21 @@ impl Backend {
                 percent_encode_path(continuation_token)
             )
             .expect("write into string must succeed");
+        } else if let Some(start_after) = start_after {
+            // `start-after` only takes effect on the first request.
+            write!(
+                url,
+                "&start-after={}",
+                percent_encode_path(&build_abs_path(&self.root, start_after))
+            )
+            .expect("write into string must succeed");
         }
 
         let mut req = Request::get(&url)
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         self.client.send_async(req).await
     }
@@ -1167,12 +1868,17 @@ impl Backend {
     async fn s3_initiate_multipart_upload(
         &self,
         path: &str,
+        content_type: Option<&str>,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!("{}/{}?uploads", self.endpoint, percent_encode_path(&p));
 
-        let req = Request::post(&url);
+        let mut req = Request::post(&url);
+
+        if let Some(mime) = content_type {
+            req = req.header(CONTENT_TYPE, mime)
+        }
 
         // Set SSE headers.
         let req = self.insert_sse_headers(req, true);
@@ -1181,7 +1887,7 @@ impl Backend {
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         self.client.send_async(req).await
     }
@@ -1211,7 +1917,10 @@ impl Backend {
         }
 
         // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
+        //
+        // Encryption has been decided while initiating the upload, only
+        // SSE-C headers are allowed here.
+        req = self.insert_sse_headers(req, false);
 
         // Set body
         let req = req.body(body).map_err(new_request_build_error)?;
@@ -1236,29 +1945,66 @@ impl Backend {
 
         let req = Request::post(&url);
 
-        // Set SSE headers.
-        let req = self.insert_sse_headers(req, true);
+        // Set SSE headers, only SSE-C headers are allowed here.
+        let req = self.insert_sse_headers(req, false);
 
         let content = quick_xml::se::to_string(&CompleteMultipartUploadRequest {
             part: parts
                 .iter()
                 .map(|v| CompleteMultipartUploadRequestPart {
                     part_number: v.part_number(),
-                    etag: v.etag().to_string(),
+                    // s3 requires etag to be quoted.
+                    etag: format!("\"{}\"", v.etag().trim_matches('"')),
+                })
+                .collect(),
+        })
+        .map_err(parse_xml_deserialize_error)?;
+        // Make sure content length has been set to avoid post with chunked encoding.
+        let req = req.header(CONTENT_LENGTH, content.len());
+        // Set content-type to `application/xml` to avoid mixed with form post.
+        let req = req.header(CONTENT_TYPE, "application/xml");
+
+        let mut req = req
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn s3_delete_objects(&self, paths: &[String]) -> Result<Response<IncomingAsyncBody>> {
+        let url = format!("{}/?delete", self.endpoint);
+
+        let req = Request::post(&url);
+
+        let content = quick_xml::se::to_string(&DeleteObjectsRequest {
+            object: paths
+                .iter()
+                .map(|path| DeleteObjectsRequestObject {
+                    key: build_abs_path(&self.root, path),
                 })
                 .collect(),
+            // Only return failed keys.
+            quiet: true,
         })
         .map_err(parse_xml_deserialize_error)?;
+
         // Make sure content length has been set to avoid post with chunked encoding.
         let req = req.header(CONTENT_LENGTH, content.len());
         // Set content-type to `application/xml` to avoid mixed with form post.
         let req = req.header(CONTENT_TYPE, "application/xml");
+        // s3 requires `Content-MD5` for `DeleteObjects`.
+        let req = req.header(
+            HeaderName::from_static("content-md5"),
+            base64::encode(Md5::digest(content.as_bytes()).as_slice()),
+        );
 
         let mut req = req
             .body(AsyncBody::Bytes(Bytes::from(content)))
             .map_err(new_request_build_error)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
 
         self.client.send_async(req).await
     }
@@ -1281,10 +2027,201 @@ impl Backend {
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
-        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn s3_get_object_tagging(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?tagging", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn s3_put_object_tagging(
+        &self,
+        path: &str,
+        tags: &HashMap<String, String>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?tagging", self.endpoint, percent_encode_path(&p));
+
+        let req = Request::put(&url);
+
+        let mut tag = tags
+            .iter()
+            .map(|(key, value)| Tag {
+                key: key.to_string(),
+                value: value.to_string(),
+            })
+            .collect::<Vec<_>>();
+        // Sort tags to make the request body stable.
+        tag.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let content = quick_xml::se::to_string(&Tagging {
+            tag_set: TagSet { tag },
+        })
+        .map_err(parse_xml_deserialize_error)?;
+
+        let req = req.header(CONTENT_LENGTH, content.len());
+        let req = req.header(CONTENT_TYPE, "application/xml");
+        // s3 requires `Content-MD5` for `PutObjectTagging`.
+        let req = req.header(
+            HeaderName::from_static("content-md5"),
+            base64::encode(Md5::digest(content.as_bytes()).as_slice()),
+        );
+
+        let mut req = req
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn s3_delete_object_tagging(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?tagging", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::delete(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+
+    async fn s3_list_multipart_uploads(
+        &self,
+        path: &str,
+        key_marker: Option<&str>,
+        upload_id_marker: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}?uploads&prefix={}",
+            self.endpoint,
+            percent_encode_path(&p)
+        );
+        if let Some(key_marker) = key_marker {
+            write!(url, "&key-marker={}", percent_encode_path(key_marker))
+                .expect("write into string must succeed");
+        }
+        if let Some(upload_id_marker) = upload_id_marker {
+            write!(
+                url,
+                "&upload-id-marker={}",
+                percent_encode_path(upload_id_marker)
+            )
+            .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
 
         self.client.send_async(req).await
     }
+
+    async fn s3_list_object_versions(
+        &self,
+        path: &str,
+        key_marker: Option<&str>,
+        version_id_marker: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}?versions&prefix={}",
+            self.endpoint,
+            percent_encode_path(&p)
+        );
+        if let Some(key_marker) = key_marker {
+            write!(url, "&key-marker={}", percent_encode_path(key_marker))
+                .expect("write into string must succeed");
+        }
+        if let Some(version_id_marker) = version_id_marker {
+            write!(
+                url,
+                "&version-id-marker={}",
+                percent_encode_path(version_id_marker)
+            )
+            .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req)?;
+
+        self.client.send_async(req).await
+    }
+}
+
+/// Fill content md5 with etag if services don't return `Content-MD5`.
+/// Check if endpoint is the S3 API endpoint of Cloudflare R2 like
+/// `https://<account_id>.r2.cloudflarestorage.com`.
+fn is_r2_endpoint(endpoint: &str) -> bool {
+    let host = endpoint
+        .split_once("://")
+        .map_or(endpoint, |(_, v)| v)
+        .split(['/', ':'])
+        .next()
+        .unwrap_or_default();
+
+    host.ends_with(R2_ENDPOINT_SUFFIX)
+}
+
+/// Use etag as content md5 if services don't return it.
+///
+/// Etag of objects encrypted by SSE-KMS or SSE-C is not the md5 of
+/// content even if it looks like one, so it will be skipped.
+fn fill_content_md5(meta: &mut ObjectMetadata, headers: &HeaderMap) {
+    if meta.content_md5().is_some() {
+        return;
+    }
+    let encrypted_by_kms = headers
+        .get(constants::X_AMZ_SERVER_SIDE_ENCRYPTION)
+        .and_then(|v| v.to_str().ok())
+        .map_or(false, |v| v.starts_with("aws:kms"));
+    if encrypted_by_kms
+        || headers.contains_key(constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM)
+    {
+        return;
+    }
+    if let Some(v) = meta.etag().and_then(parse_content_md5_from_etag) {
+        let v = v.to_string();
+        meta.set_content_md5(&v);
+    }
+}
+
+/// Use etag as content md5 if it's a hex encoded md5.
+///
+/// Etag of objects uploaded by multipart (like `"<md5>-<parts>"`) is not
+/// the md5 of content.
+pub(super) fn parse_content_md5_from_etag(etag: &str) -> Option<&str> {
+    let etag = etag.trim_matches('"');
+    if etag.len() == 32 && etag.bytes().all(|v| v.is_ascii_hexdigit()) {
+        Some(etag)
+    } else {
+        None
+    }
 }
 
 /// Result of CreateMultipartUpload
@@ -1294,6 +2231,51 @@ struct InitiateMultipartUploadResult {
     upload_id: String,
 }
 
+/// Result of ListMultipartUploads
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct ListMultipartUploadsResult {
+    is_truncated: bool,
+    next_key_marker: String,
+    next_upload_id_marker: String,
+    upload: Vec<ListMultipartUploadsResultUpload>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct ListMultipartUploadsResultUpload {
+    key: String,
+    upload_id: String,
+    initiated: String,
+}
+
+/// Result of ListObjectVersions
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct ListVersionsResult {
+    is_truncated: bool,
+    next_key_marker: String,
+    next_version_id_marker: String,
+    /// Versions and delete markers are interleaved in the response, we
+    /// depend on `overlapped-lists` of quick-xml to collect them.
+    version: Vec<ListVersionsResultVersion>,
+    delete_marker: Vec<ListVersionsResultVersion>,
+}
+
+/// Both `Version` and `DeleteMarker` share this struct, delete markers
+/// don't have `Size` and `ETag`.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct ListVersionsResultVersion {
+    key: String,
+    version_id: String,
+    is_latest: bool,
+    last_modified: String,
+    size: u64,
+    #[serde(rename = "ETag")]
+    etag: String,
+}
+
 /// Request of CompleteMultipartUploadRequest
 #[derive(Default, Debug, Serialize)]
 #[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
@@ -1338,6 +2320,66 @@ struct CompleteMultipartUploadRequestPart {
     etag: String,
 }
 
+/// Request of DeleteObjects.
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename = "Delete", rename_all = "PascalCase")]
+struct DeleteObjectsRequest {
+    object: Vec<DeleteObjectsRequestObject>,
+    #[serde(rename = "$unflatten=Quiet")]
+    quiet: bool,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct DeleteObjectsRequestObject {
+    #[serde(rename = "$unflatten=Key")]
+    key: String,
+}
+
+/// Result of DeleteObjects.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename = "DeleteResult", rename_all = "PascalCase")]
+struct DeleteObjectsResult {
+    deleted: Vec<DeleteObjectsResultDeleted>,
+    error: Vec<DeleteObjectsResultError>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct DeleteObjectsResultDeleted {
+    key: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct DeleteObjectsResultError {
+    key: String,
+    code: String,
+    message: String,
+}
+
+/// Request and result of PutObjectTagging and GetObjectTagging.
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename = "Tagging", rename_all = "PascalCase")]
+struct Tagging {
+    tag_set: TagSet,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct TagSet {
+    tag: Vec<Tag>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct Tag {
+    #[serde(rename = "$unflatten=Key")]
+    key: String,
+    #[serde(rename = "$unflatten=Value")]
+    value: String,
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Buf;
@@ -1443,4 +2485,2137 @@ mod tests {
                 .replace('"', "&quot;")
         )
     }
+
+    #[test]
+    fn test_presign_expire_too_long() {
+        let mut builder = Builder::default();
+        builder
+            .endpoint("http://127.0.0.1:9000")
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+
+        acc.presign(
+            "file",
+            OpPresign::new(OpStat::new(), time::Duration::days(7)),
+        )
+        .expect("presign with 7 days must succeed");
+
+        let err = acc
+            .presign(
+                "file",
+                OpPresign::new(OpStat::new(), time::Duration::days(8)),
+            )
+            .expect_err("presign must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_presign_until() {
+        let mut builder = Builder::default();
+        builder
+            .endpoint("http://127.0.0.1:9000")
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+        let o = op.object("file");
+        let now = OffsetDateTime::now_utc();
+
+        let req = o
+            .presign_read_until(now + time::Duration::hours(1))
+            .expect("presign must succeed");
+        let query = req.uri().query().expect("query must exist");
+        let expires = query
+            .split('&')
+            .find_map(|v| v.strip_prefix("X-Amz-Expires="))
+            .expect("expires must exist")
+            .parse::<i64>()
+            .expect("expires must be number");
+        assert!((3590..=3600).contains(&expires), "{expires}");
+
+        o.presign_stat_until(now + time::Duration::minutes(1))
+            .expect("presign must succeed");
+        o.presign_write_until(now + time::Duration::minutes(1))
+            .expect("presign must succeed");
+
+        let err = o
+            .presign_read_until(now - time::Duration::seconds(1))
+            .expect_err("presign must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        let err = o
+            .presign_read_until(now + time::Duration::days(8))
+            .expect_err("presign must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn test_write_with_content_type() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let long = format!(
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet; name=\"{}\"",
+            "x".repeat(256)
+        );
+
+        let mock_server = MockServer::start().await;
+        for (p, content_type) in [("html", "text/html; charset=utf-8"), ("long", &long)] {
+            Mock::given(method("PUT"))
+                .and(path(format!("/test/{p}")))
+                .and(header("content-type", content_type))
+                .respond_with(ResponseTemplate::new(200))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+        // Empty content type should not be sent.
+        Mock::given(method("PUT"))
+            .and(path("/test/empty"))
+            .and(|req: &wiremock::Request| {
+                !req.headers.keys().any(|k| k.as_str() == "content-type")
+            })
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        for (p, content_type) in [
+            ("html", "text/html; charset=utf-8"),
+            ("long", &long),
+            ("empty", ""),
+        ] {
+            op.object(p)
+                .write_with(
+                    OpWrite::new(4).with_content_type(content_type),
+                    "data".as_bytes(),
+                )
+                .await
+                .expect("write must succeed");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_client_reuse_connections() {
+        use std::io::Read;
+        use std::io::Write;
+        use std::net::TcpListener;
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::atomic::Ordering;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepted = Arc::new(AtomicUsize::new(0));
+        {
+            let accepted = accepted.clone();
+            std::thread::spawn(move || {
+                for mut stream in listener.incoming().flatten() {
+                    accepted.fetch_add(1, Ordering::SeqCst);
+                    std::thread::spawn(move || {
+                        let mut buf = Vec::new();
+                        let mut chunk = [0; 1024];
+                        while let Ok(n) = stream.read(&mut chunk) {
+                            if n == 0 {
+                                break;
+                            }
+                            buf.extend_from_slice(&chunk[..n]);
+                            // Answer every complete HEAD request on this connection.
+                            while let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                                buf.drain(..pos + 4);
+                                let _ = stream
+                                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n");
+                            }
+                        }
+                    });
+                }
+            });
+        }
+
+        let client = reqwest::Client::new();
+        let mut ops = Vec::new();
+        for _ in 0..2 {
+            let mut builder = Builder::default();
+            builder
+                .endpoint(&format!("http://{addr}"))
+                .bucket("test")
+                .region("us-east-1")
+                .access_key_id("access_key_id")
+                .secret_access_key("secret_access_key")
+                .http_client(client.clone());
+            ops.push(Operator::new(builder.build().expect("build must succeed")));
+        }
+
+        for op in &ops {
+            op.object("file")
+                .metadata()
+                .await
+                .expect("stat must succeed");
+        }
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_write_with_cache_control() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::headers;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let cache_control = "public, max-age=3600";
+        // Non-ascii filenames are encoded via RFC 5987.
+        let disposition = r#"attachment; filename="a \"quoted\" file.txt"; filename*=UTF-8''%E4%BD%A0%E5%A5%BD.txt"#;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            // Values of request headers will be split by `,` in wiremock.
+            .and(headers("cache-control", vec!["public", "max-age=3600"]))
+            .and(header("content-disposition", disposition))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/test/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "5")
+                    .insert_header("cache-control", cache_control)
+                    .insert_header("content-disposition", disposition),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+
+        acc.write(
+            "file",
+            OpWrite::new(5)
+                .with_cache_control(cache_control)
+                .with_content_disposition(disposition),
+            Box::new(futures::io::Cursor::new("hello")),
+        )
+        .await
+        .expect("write must succeed");
+
+        let meta = acc
+            .stat("file", OpStat::new())
+            .await
+            .expect("stat must succeed")
+            .into_metadata();
+        assert_eq!(meta.cache_control(), Some(cache_control));
+        assert_eq!(meta.content_disposition(), Some(disposition));
+    }
+
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html#API_DeleteObjects_Examples
+    #[test]
+    fn test_serialize_delete_objects_request() {
+        let req = DeleteObjectsRequest {
+            object: vec![
+                DeleteObjectsRequestObject {
+                    key: "sample1.txt".to_string(),
+                },
+                DeleteObjectsRequestObject {
+                    key: "sample2.txt".to_string(),
+                },
+            ],
+            quiet: true,
+        };
+
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+
+        pretty_assertions::assert_eq!(
+            actual,
+            r#"<Delete>
+             <Object>
+               <Key>sample1.txt</Key>
+             </Object>
+             <Object>
+               <Key>sample2.txt</Key>
+             </Object>
+             <Quiet>true</Quiet>
+            </Delete>"#
+                // Cleanup space and new line
+                .replace([' ', '\n'], "")
+        )
+    }
+
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html#API_DeleteObjects_Examples
+    #[test]
+    fn test_deserialize_delete_objects_result() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<DeleteResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Deleted>
+    <Key>sample1.txt</Key>
+  </Deleted>
+  <Error>
+    <Key>sample2.txt</Key>
+    <Code>AccessDenied</Code>
+    <Message>Access Denied</Message>
+  </Error>
+</DeleteResult>"#,
+        );
+
+        let out: DeleteObjectsResult =
+            quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(out.deleted.len(), 1);
+        assert_eq!(out.deleted[0].key, "sample1.txt");
+        assert_eq!(out.error.len(), 1);
+        assert_eq!(out.error[0].key, "sample2.txt");
+        assert_eq!(out.error[0].code, "AccessDenied");
+        assert_eq!(out.error[0].message, "Access Denied");
+    }
+
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_GetObjectTagging.html#API_GetObjectTagging_Examples
+    #[test]
+    fn test_deserialize_tagging() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Tagging xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <TagSet>
+     <Tag>
+       <Key>tag1</Key>
+       <Value>val1</Value>
+     </Tag>
+     <Tag>
+       <Key>项目</Key>
+       <Value>数据 &amp; 湖</Value>
+     </Tag>
+  </TagSet>
+</Tagging>"#,
+        );
+
+        let out: Tagging = quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(out.tag_set.tag.len(), 2);
+        assert_eq!(out.tag_set.tag[0].key, "tag1");
+        assert_eq!(out.tag_set.tag[0].value, "val1");
+        assert_eq!(out.tag_set.tag[1].key, "项目");
+        assert_eq!(out.tag_set.tag[1].value, "数据 & 湖");
+
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Tagging xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><TagSet/></Tagging>"#,
+        );
+        let out: Tagging = quick_xml::de::from_reader(bs.reader()).expect("must success");
+        assert!(out.tag_set.tag.is_empty());
+    }
+
+    #[test]
+    fn test_serialize_tagging() {
+        let req = Tagging {
+            tag_set: TagSet {
+                tag: vec![
+                    Tag {
+                        key: "tag1".to_string(),
+                        value: "val1".to_string(),
+                    },
+                    Tag {
+                        key: "项目".to_string(),
+                        value: "数据 & 湖".to_string(),
+                    },
+                ],
+            },
+        };
+
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+
+        assert_eq!(
+            actual,
+            "<Tagging><TagSet>\
+             <Tag><Key>tag1</Key><Value>val1</Value></Tag>\
+             <Tag><Key>项目</Key><Value>数据 &amp; 湖</Value></Tag>\
+             </TagSet></Tagging>"
+        );
+
+        let req = Tagging::default();
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+        assert_eq!(actual, "<Tagging><TagSet/></Tagging>");
+    }
+
+    #[test]
+    fn test_parse_content_md5_from_etag() {
+        let cases = vec![
+            (
+                "single part",
+                "\"3858f62230ac3c915f300c664312c11f\"",
+                Some("3858f62230ac3c915f300c664312c11f"),
+            ),
+            ("multipart", "\"3858f62230ac3c915f300c664312c11f-9\"", None),
+            ("not md5", "\"abc\"", None),
+        ];
+
+        for (name, input, expected) in cases {
+            assert_eq!(parse_content_md5_from_etag(input), expected, "{name}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_with_limit() {
+        use futures::TryStreamExt;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::matchers::query_param_is_missing;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::OpList;
+        use crate::Operator;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let list_output = |keys: &[&str], next_token: Option<&str>| {
+            let contents: String = keys
+                .iter()
+                .map(|k| {
+                    format!(
+                        r#"<Contents>
+                          <Key>{k}</Key>
+                          <Size>1</Size>
+                          <LastModified>2022-12-01T00:00:00.000Z</LastModified>
+                          <ETag>"etag"</ETag>
+                        </Contents>"#
+                    )
+                })
+                .collect();
+            let token = next_token
+                .map(|v| format!("<NextContinuationToken>{v}</NextContinuationToken>"))
+                .unwrap_or_default();
+            format!(
+                r#"<ListBucketResult><IsTruncated>{}</IsTruncated>{token}{contents}</ListBucketResult>"#,
+                next_token.is_some()
+            )
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .and(query_param("max-keys", "2"))
+            .and(query_param_is_missing("continuation-token"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(list_output(&["dir/a", "dir/b"], Some("next"))),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .and(query_param("max-keys", "2"))
+            .and(query_param("continuation-token", "next"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_output(&["dir/c"], None)))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let ds = op
+            .object("dir/")
+            .list_with(OpList::new().with_limit(2))
+            .await
+            .expect("list must succeed");
+        let paths: Vec<String> = ds
+            .map_ok(|de| de.path().to_string())
+            .try_collect()
+            .await
+            .expect("list must succeed");
+        assert_eq!(paths, vec!["dir/a", "dir/b", "dir/c"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_multipart_uploads() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::matchers::query_param_is_missing;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::Operator;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let list_output = |uploads: &[(&str, &str, &str)], next: Option<(&str, &str)>| {
+            let uploads: String = uploads
+                .iter()
+                .map(|(key, upload_id, initiated)| {
+                    format!(
+                        r#"<Upload>
+                          <Key>{key}</Key>
+                          <UploadId>{upload_id}</UploadId>
+                          <StorageClass>STANDARD</StorageClass>
+                          <Initiated>{initiated}</Initiated>
+                        </Upload>"#
+                    )
+                })
+                .collect();
+            let markers = next
+                .map(|(key, upload_id)| {
+                    format!(
+                        "<NextKeyMarker>{key}</NextKeyMarker><NextUploadIdMarker>{upload_id}</NextUploadIdMarker>"
+                    )
+                })
+                .unwrap_or_default();
+            format!(
+                r#"<ListMultipartUploadsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+                  <Bucket>test</Bucket>
+                  <Prefix>root/</Prefix>
+                  <IsTruncated>{}</IsTruncated>
+                  {markers}
+                  {uploads}
+                </ListMultipartUploadsResult>"#,
+                next.is_some()
+            )
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .and(query_param("prefix", "root/"))
+            .and(query_param_is_missing("key-marker"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_output(
+                &[
+                    ("root/a", "upload-a", "2022-01-01T00:00:00.000Z"),
+                    ("root/b", "upload-b1", "2022-12-01T00:00:00.000Z"),
+                ],
+                Some(("root/b", "upload-b1")),
+            )))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .and(query_param("prefix", "root/"))
+            .and(query_param("key-marker", "root/b"))
+            .and(query_param("upload-id-marker", "upload-b1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_output(
+                &[("root/b", "upload-b2", "2021-06-01T00:00:00.000Z")],
+                None,
+            )))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/test/root/a"))
+            .and(query_param("uploadId", "upload-a"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/test/root/b"))
+            .and(query_param("uploadId", "upload-b2"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .root("/root/")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let uploads = op
+            .list_multipart_uploads("/")
+            .await
+            .expect("list must succeed");
+        let uploads: Vec<(&str, &str)> =
+            uploads.iter().map(|v| (v.path(), v.upload_id())).collect();
+        assert_eq!(
+            uploads,
+            vec![("a", "upload-a"), ("b", "upload-b1"), ("b", "upload-b2")]
+        );
+
+        let cutoff = OffsetDateTime::parse("2022-06-01T00:00:00Z", &Rfc3339)
+            .expect("parse datetime must succeed");
+        let aborted = op
+            .abort_multipart_uploads_before(cutoff)
+            .await
+            .expect("abort must succeed");
+        assert_eq!(aborted, 2);
+    }
+
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListObjectVersions.html#API_ListObjectVersions_Examples
+    #[test]
+    fn test_deserialize_list_versions_result() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListVersionsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01">
+    <Name>bucket</Name>
+    <Prefix>my</Prefix>
+    <KeyMarker/>
+    <VersionIdMarker/>
+    <MaxKeys>5</MaxKeys>
+    <IsTruncated>true</IsTruncated>
+    <NextKeyMarker>my-third-image.jpg</NextKeyMarker>
+    <NextVersionIdMarker>03jpff543dhffds434rfdsFDN943fdsFkdmqnh892</NextVersionIdMarker>
+    <Version>
+        <Key>my-image.jpg</Key>
+        <VersionId>3/L4kqtJl40Nr8X8gdRQBpUMLUo</VersionId>
+        <IsLatest>true</IsLatest>
+        <LastModified>2009-10-12T17:50:30.000Z</LastModified>
+        <ETag>"fba9dede5f27731c9771645a39863328"</ETag>
+        <Size>434234</Size>
+        <StorageClass>STANDARD</StorageClass>
+    </Version>
+    <DeleteMarker>
+        <Key>my-second-image.jpg</Key>
+        <VersionId>03jpff543dhffds434rfdsFDN943fdsFkdmqnh892</VersionId>
+        <IsLatest>true</IsLatest>
+        <LastModified>2009-11-12T17:50:30.000Z</LastModified>
+    </DeleteMarker>
+    <Version>
+        <Key>my-second-image.jpg</Key>
+        <VersionId>QUpfdndhfd8438MNFDN93jdnJFkdmqnh893</VersionId>
+        <IsLatest>false</IsLatest>
+        <LastModified>2009-10-10T17:50:30.000Z</LastModified>
+        <ETag>"9b2cf535f27731c974343645a3985328"</ETag>
+        <Size>166434</Size>
+        <StorageClass>STANDARD</StorageClass>
+    </Version>
+</ListVersionsResult>"#,
+        );
+
+        let out: ListVersionsResult =
+            quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert!(out.is_truncated);
+        assert_eq!(out.next_key_marker, "my-third-image.jpg");
+        assert_eq!(
+            out.next_version_id_marker,
+            "03jpff543dhffds434rfdsFDN943fdsFkdmqnh892"
+        );
+        assert_eq!(
+            out.version
+                .iter()
+                .map(|v| (v.key.as_str(), v.version_id.as_str(), v.is_latest, v.size))
+                .collect::<Vec<_>>(),
+            vec![
+                ("my-image.jpg", "3/L4kqtJl40Nr8X8gdRQBpUMLUo", true, 434234),
+                (
+                    "my-second-image.jpg",
+                    "QUpfdndhfd8438MNFDN93jdnJFkdmqnh893",
+                    false,
+                    166434
+                ),
+            ]
+        );
+        assert_eq!(out.version[0].etag, "\"fba9dede5f27731c9771645a39863328\"");
+        assert_eq!(out.delete_marker.len(), 1);
+        assert_eq!(out.delete_marker[0].key, "my-second-image.jpg");
+        assert_eq!(
+            out.delete_marker[0].version_id,
+            "03jpff543dhffds434rfdsFDN943fdsFkdmqnh892"
+        );
+        assert!(out.delete_marker[0].is_latest);
+        assert_eq!(out.delete_marker[0].size, 0);
+        assert_eq!(out.delete_marker[0].etag, "");
+    }
+
+    #[tokio::test]
+    async fn test_versions() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::matchers::query_param_is_missing;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::OpDelete;
+        use crate::OpRead;
+        use crate::OpStat;
+        use crate::Operator;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .and(query_param("versions", ""))
+            .and(query_param("prefix", "root/a"))
+            .and(query_param_is_missing("key-marker"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListVersionsResult>
+                  <IsTruncated>true</IsTruncated>
+                  <NextKeyMarker>root/a</NextKeyMarker>
+                  <NextVersionIdMarker>v2</NextVersionIdMarker>
+                  <DeleteMarker>
+                    <Key>root/a</Key>
+                    <VersionId>v3</VersionId>
+                    <IsLatest>true</IsLatest>
+                    <LastModified>2022-03-01T00:00:00.000Z</LastModified>
+                  </DeleteMarker>
+                  <Version>
+                    <Key>root/a</Key>
+                    <VersionId>v2</VersionId>
+                    <IsLatest>false</IsLatest>
+                    <LastModified>2022-02-01T00:00:00.000Z</LastModified>
+                    <ETag>"etag-v2"</ETag>
+                    <Size>2</Size>
+                  </Version>
+                </ListVersionsResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .and(query_param("versions", ""))
+            .and(query_param("key-marker", "root/a"))
+            .and(query_param("version-id-marker", "v2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListVersionsResult>
+                  <IsTruncated>false</IsTruncated>
+                  <Version>
+                    <Key>root/a</Key>
+                    <VersionId>v1</VersionId>
+                    <IsLatest>false</IsLatest>
+                    <LastModified>2022-01-01T00:00:00.000Z</LastModified>
+                    <ETag>"etag-v1"</ETag>
+                    <Size>1</Size>
+                  </Version>
+                  <Version>
+                    <Key>root/ab</Key>
+                    <VersionId>v0</VersionId>
+                    <IsLatest>true</IsLatest>
+                    <LastModified>2022-01-01T00:00:00.000Z</LastModified>
+                    <Size>0</Size>
+                  </Version>
+                </ListVersionsResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test/root/a"))
+            .and(query_param("versionId", "v1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "1")
+                    .insert_header("x-amz-version-id", "v1")
+                    .set_body_string("1"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/test/root/a"))
+            .and(query_param("versionId", "v2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "2")
+                    .insert_header("x-amz-version-id", "v2"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/test/root/a"))
+            .and(query_param("versionId", "v3"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .root("/root/")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+        assert!(op.metadata().can_versioning());
+
+        let o = op.object("a");
+        let versions = o.list_versions().await.expect("list must succeed");
+        let versions: Vec<_> = versions
+            .iter()
+            .map(|v| {
+                (
+                    v.path(),
+                    v.version_id(),
+                    v.is_latest(),
+                    v.is_delete_marker(),
+                    v.content_length(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("a", "v3", true, true, None),
+                ("a", "v2", false, false, Some(2)),
+                ("a", "v1", false, false, Some(1)),
+            ]
+        );
+
+        let bs = o
+            .read_with(OpRead::new().with_version("v1"))
+            .await
+            .expect("read must succeed");
+        assert_eq!(bs, b"1");
+        let meta = o
+            .stat_with(OpStat::new().with_version("v2"))
+            .await
+            .expect("stat must succeed");
+        assert_eq!(meta.content_length(), 2);
+        assert_eq!(meta.version_id(), Some("v2"));
+        o.delete_with(OpDelete::new().with_version("v3"))
+            .await
+            .expect("delete must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_list_with_start_after() {
+        use futures::TryStreamExt;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::matchers::query_param_is_missing;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::OpList;
+        use crate::Operator;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .and(query_param("start-after", "dir/b/"))
+            .and(query_param_is_missing("continuation-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListBucketResult>
+                  <IsTruncated>true</IsTruncated>
+                  <NextContinuationToken>next</NextContinuationToken>
+                  <Contents>
+                    <Key>dir/c</Key>
+                    <Size>1</Size>
+                    <LastModified>2022-12-01T00:00:00.000Z</LastModified>
+                    <ETag>"etag"</ETag>
+                  </Contents>
+                  <CommonPrefixes><Prefix>dir/b/</Prefix></CommonPrefixes>
+                  <CommonPrefixes><Prefix>dir/d/</Prefix></CommonPrefixes>
+                </ListBucketResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .and(query_param("continuation-token", "next"))
+            .and(query_param_is_missing("start-after"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListBucketResult>
+                  <IsTruncated>false</IsTruncated>
+                  <CommonPrefixes><Prefix>dir/e/</Prefix></CommonPrefixes>
+                </ListBucketResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let mut ds = op
+            .object("dir/")
+            .list_with(OpList::new().with_start_after("dir/b/"))
+            .await
+            .expect("list must succeed");
+        assert_eq!(ds.continuation_token(), None);
+
+        let mut paths = vec![];
+        while let Some(de) = ds.try_next().await.expect("list must succeed") {
+            paths.push(de.path().to_string());
+            assert_eq!(ds.continuation_token(), Some(de.path()));
+        }
+        // Returned common prefix `dir/b/` should be skipped and entries
+        // should be sorted.
+        assert_eq!(paths, vec!["dir/c", "dir/d/", "dir/e/"]);
+
+        let err = op
+            .object("other/")
+            .list_with(OpList::new().with_start_after("dir/b/"))
+            .await
+            .err()
+            .expect("start_after from other dir must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_read_and_write() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::OpRead;
+        use crate::OpWrite;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .and(header("if-none-match", "\"etag\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(header("if-match", "\"etag\""))
+            .respond_with(ResponseTemplate::new(412).insert_header("etag", "\"current\""))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(header("if-none-match", "*"))
+            .respond_with(ResponseTemplate::new(412))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+
+        let err = acc
+            .read("file", OpRead::new().with_if_none_match("\"etag\""))
+            .await
+            .err()
+            .expect("read must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+        let err = acc
+            .write(
+                "file",
+                OpWrite::new(5).with_if_match("\"etag\""),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        assert!(err.to_string().contains("etag: current"), "{err}");
+
+        let err = acc
+            .write(
+                "file",
+                OpWrite::new(5).with_if_none_match("*"),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    }
+
+    #[tokio::test]
+    async fn test_read_verify_content_md5() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::ErrorKind;
+        use crate::OpRead;
+        use crate::Operator;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        // md5 of "Hello, World!"
+        let md5 = "65a8e27d8879283831b664bd8b7f0ad4";
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-amz-meta-md5", md5)
+                    .set_body_bytes("Hello, World!"),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test/corrupted"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-amz-meta-md5", md5)
+                    .set_body_bytes("Hello, World?"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let bs = op
+            .object("file")
+            .read_with(OpRead::new().with_verify_content_md5(true))
+            .await
+            .expect("read must succeed");
+        assert_eq!(bs, b"Hello, World!");
+
+        let err = op
+            .object("corrupted")
+            .read_with(OpRead::new().with_verify_content_md5(true))
+            .await
+            .expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::ChecksumMismatch);
+        assert!(!err.is_temporary());
+    }
+
+    #[tokio::test]
+    async fn test_read_verify_content_md5_encrypted() {
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::ErrorKind;
+        use crate::OpRead;
+        use crate::Operator;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        // Looks like md5 but it's not the md5 of "Hello, World!".
+        let etag = "\"3858f62230ac3c915f300c664312c11f\"";
+
+        let mock_server = MockServer::start().await;
+        for (name, header) in [
+            ("plain", None),
+            ("kms", Some(("x-amz-server-side-encryption", "aws:kms"))),
+            (
+                "sse-c",
+                Some(("x-amz-server-side-encryption-customer-algorithm", "AES256")),
+            ),
+        ] {
+            let mut resp = ResponseTemplate::new(200)
+                .insert_header("etag", etag)
+                .set_body_bytes("Hello, World!");
+            if let Some((k, v)) = header {
+                resp = resp.insert_header(k, v);
+            }
+            Mock::given(path(format!("/test/{name}")))
+                .respond_with(resp)
+                .mount(&mock_server)
+                .await;
+        }
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let err = op
+            .object("plain")
+            .read_with(OpRead::new().with_verify_content_md5(true))
+            .await
+            .expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::ChecksumMismatch);
+
+        for name in ["kms", "sse-c"] {
+            let bs = op
+                .object(name)
+                .read_with(OpRead::new().with_verify_content_md5(true))
+                .await
+                .expect("read must succeed");
+            assert_eq!(bs, b"Hello, World!", "{name}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_suffix() {
+        use futures::AsyncReadExt;
+        use futures::AsyncSeekExt;
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::Operator;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let content: Vec<u8> = (0..100).collect();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .and(header("range", "bytes=-10"))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", "bytes 90-99/100")
+                    .set_body_bytes(&content[90..]),
+            )
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .and(header("range", "bytes=95-99"))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", "bytes 95-99/100")
+                    .set_body_bytes(&content[95..]),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let bs = op
+            .object("file")
+            .read_suffix(10)
+            .await
+            .expect("read must succeed");
+        assert_eq!(bs, content[90..]);
+
+        // Offset of the reader must be backfilled from content range.
+        let mut r = op
+            .object("file")
+            .range_reader(..10)
+            .await
+            .expect("range reader must succeed");
+        r.seek(std::io::SeekFrom::Start(5))
+            .await
+            .expect("seek must succeed");
+        let mut bs = Vec::new();
+        r.read_to_end(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, content[95..]);
+
+        // Suffix range must be sent without stat.
+        let requests = mock_server
+            .received_requests()
+            .await
+            .expect("requests must be recorded");
+        assert!(requests.iter().all(|req| req.method.as_ref() == "GET"));
+    }
+
+    #[tokio::test]
+    async fn test_write_with_expires() {
+        use time::OffsetDateTime;
+        use wiremock::matchers::header_exists;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::OpStat;
+        use crate::OpWrite;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(header_exists("expires"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/test/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "5")
+                    .insert_header("expires", "Sun, 06 Nov 1994 08:49:37 GMT"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+
+        let expires = OffsetDateTime::from_unix_timestamp(784111777).unwrap();
+        acc.write(
+            "file",
+            OpWrite::new(5).with_expires(expires),
+            Box::new(futures::io::Cursor::new("hello")),
+        )
+        .await
+        .expect("write must succeed");
+
+        let meta = acc
+            .stat("file", OpStat::new())
+            .await
+            .expect("stat must succeed")
+            .into_metadata();
+        assert_eq!(meta.expires(), Some(expires));
+    }
+
+    #[tokio::test]
+    async fn test_write_with_content_disposition() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::OpStat;
+        use crate::OpWrite;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let disposition = r#"attachment; filename="hello.txt""#;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(header("content-type", "text/plain"))
+            .and(header("content-disposition", disposition))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/test/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "5")
+                    .insert_header("content-type", "text/plain")
+                    .insert_header("content-disposition", disposition),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+
+        acc.write(
+            "file",
+            OpWrite::new(5)
+                .with_content_type("text/plain")
+                .with_content_disposition(disposition),
+            Box::new(futures::io::Cursor::new("hello")),
+        )
+        .await
+        .expect("write must succeed");
+
+        let meta = acc
+            .stat("file", OpStat::new())
+            .await
+            .expect("stat must succeed")
+            .into_metadata();
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(meta.content_disposition(), Some(disposition));
+    }
+
+    #[tokio::test]
+    async fn test_allow_anonymous() {
+        use futures::AsyncReadExt;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::OpRead;
+        use crate::OpWrite;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .respond_with(ResponseTemplate::new(403).set_body_string(
+                "<Error><Code>AccessDenied</Code><Message>Access Denied</Message></Error>",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .disable_credential_loader()
+            .allow_anonymous();
+        let acc = builder.build().expect("build must succeed");
+        assert!(!acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Presign));
+
+        let (_, mut r) = acc
+            .read("file", OpRead::new())
+            .await
+            .expect("read must succeed");
+        let mut bs = String::new();
+        r.read_to_string(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, "hello");
+
+        let err = acc
+            .write(
+                "file",
+                OpWrite::new(5),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::ObjectPermissionDenied);
+
+        let requests = mock_server
+            .received_requests()
+            .await
+            .expect("requests must be recorded");
+        assert_eq!(requests.len(), 2);
+        for req in requests {
+            assert!(
+                !req.headers.contains_key(&"authorization".into()),
+                "{} {} must not be signed",
+                req.method,
+                req.url
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_server_side_encryption_headers() {
+        use wiremock::matchers::method;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        const SSE: &str = constants::X_AMZ_SERVER_SIDE_ENCRYPTION;
+        const SSE_KMS_KEY_ID: &str = constants::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID;
+        const SSE_C_ALGORITHM: &str = constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM;
+        const SSE_C_KEY: &str = constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY;
+        const SSE_C_KEY_MD5: &str = constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5;
+
+        /// Send put, multipart upload and get, return the sse headers of
+        /// every request.
+        async fn sse_headers(
+            f: impl FnOnce(&mut Builder),
+        ) -> Vec<(String, Vec<(&'static str, String)>)> {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(|req: &wiremock::Request| {
+                    req.url.query().map_or(false, |q| q.starts_with("uploads"))
+                })
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+                ))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<CompleteMultipartUploadResult></CompleteMultipartUploadResult>",
+                ))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("PUT"))
+                .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"etag\""))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+                .mount(&mock_server)
+                .await;
+
+            let mut builder = Builder::default();
+            builder
+                .endpoint(&mock_server.uri())
+                .bucket("test")
+                .region("us-east-1")
+                .access_key_id("access_key_id")
+                .secret_access_key("secret_access_key");
+            f(&mut builder);
+            let acc = builder.build().expect("build must succeed");
+
+            acc.write(
+                "file",
+                OpWrite::new(5),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+            .await
+            .expect("write must succeed");
+            let upload_id = acc
+                .create_multipart("file", OpCreateMultipart::new())
+                .await
+                .expect("create multipart must succeed")
+                .upload_id()
+                .to_string();
+            let part = acc
+                .write_multipart(
+                    "file",
+                    OpWriteMultipart::new(upload_id.clone(), 1, 5),
+                    Box::new(futures::io::Cursor::new("hello")),
+                )
+                .await
+                .expect("write multipart must succeed")
+                .into_object_part();
+            acc.complete_multipart("file", OpCompleteMultipart::new(upload_id, vec![part]))
+                .await
+                .expect("complete multipart must succeed");
+            acc.read("file", OpRead::new())
+                .await
+                .expect("read must succeed");
+
+            mock_server
+                .received_requests()
+                .await
+                .expect("requests must be recorded")
+                .into_iter()
+                .map(|req| {
+                    let name = match (req.method.as_ref(), req.url.query()) {
+                        ("PUT", None) => "put",
+                        ("POST", Some(q)) if q.starts_with("uploads") => "initiate",
+                        ("PUT", Some(_)) => "upload_part",
+                        ("POST", Some(_)) => "complete",
+                        ("GET", None) => "get",
+                        (m, q) => unreachable!("unexpected request: {m} {q:?}"),
+                    };
+                    let headers = [
+                        SSE,
+                        SSE_KMS_KEY_ID,
+                        SSE_C_ALGORITHM,
+                        SSE_C_KEY,
+                        SSE_C_KEY_MD5,
+                    ]
+                    .into_iter()
+                    .filter_map(|k| {
+                        req.headers
+                            .get(&k.into())
+                            .map(|v| (k, v.last().as_str().to_string()))
+                    })
+                    .collect();
+                    (name.to_string(), headers)
+                })
+                .collect()
+        }
+
+        // SSE-S3
+        let headers = sse_headers(|b| {
+            b.server_side_encryption_with_s3_key();
+        })
+        .await;
+        let sse_s3 = vec![(SSE, "AES256".to_string())];
+        assert_eq!(
+            headers,
+            vec![
+                ("put".to_string(), sse_s3.clone()),
+                ("initiate".to_string(), sse_s3),
+                ("upload_part".to_string(), vec![]),
+                ("complete".to_string(), vec![]),
+                ("get".to_string(), vec![]),
+            ]
+        );
+
+        // SSE-KMS
+        let headers = sse_headers(|b| {
+            b.server_side_encryption_with_customer_managed_kms_key("kms-key-id");
+        })
+        .await;
+        let sse_kms = vec![
+            (SSE, "aws:kms".to_string()),
+            (SSE_KMS_KEY_ID, "kms-key-id".to_string()),
+        ];
+        assert_eq!(
+            headers,
+            vec![
+                ("put".to_string(), sse_kms.clone()),
+                ("initiate".to_string(), sse_kms),
+                ("upload_part".to_string(), vec![]),
+                ("complete".to_string(), vec![]),
+                ("get".to_string(), vec![]),
+            ]
+        );
+
+        // SSE-C
+        let key = [7u8; 32];
+        let headers = sse_headers(|b| {
+            b.server_side_encryption_with_customer_key("AES256", &key);
+        })
+        .await;
+        let sse_c = vec![
+            (SSE_C_ALGORITHM, "AES256".to_string()),
+            (SSE_C_KEY, base64::encode(key)),
+            (SSE_C_KEY_MD5, base64::encode(Md5::digest(key).as_slice())),
+        ];
+        assert_eq!(
+            headers,
+            vec![
+                ("put".to_string(), sse_c.clone()),
+                ("initiate".to_string(), sse_c.clone()),
+                ("upload_part".to_string(), sse_c.clone()),
+                ("complete".to_string(), sse_c.clone()),
+                ("get".to_string(), sse_c),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_presign_write_with_content_type_and_length() {
+        use crate::OpWrite;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint("http://127.0.0.1:9000")
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+
+        let op = OpPresign::new(
+            OpWrite::new(1024).with_content_type("image/png"),
+            time::Duration::hours(1),
+        );
+        let req = acc
+            .presign("file", op)
+            .expect("presign must succeed")
+            .into_presigned_request();
+
+        assert_eq!(req.header()[CONTENT_TYPE], "image/png");
+        assert_eq!(req.header()[CONTENT_LENGTH], "1024");
+        let query = req.uri().query().expect("query must exist");
+        assert!(
+            query.contains("X-Amz-SignedHeaders=content-length%3Bcontent-type%3Bhost"),
+            "{query}"
+        );
+
+        let op = OpPresign::new(OpWrite::new(0), time::Duration::hours(1));
+        let req = acc
+            .presign("file", op)
+            .expect("presign must succeed")
+            .into_presigned_request();
+        assert!(!req.header().contains_key(CONTENT_LENGTH));
+        assert!(!req.header().contains_key(CONTENT_TYPE));
+    }
+
+    #[test]
+    fn test_presign_without_static_credential() {
+        let mut builder = Builder::default();
+        builder
+            .endpoint("http://127.0.0.1:9000")
+            .bucket("test")
+            .region("us-east-1");
+        let acc = builder.build().expect("build must succeed");
+        assert!(!acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Presign));
+
+        let err = acc
+            .presign(
+                "file",
+                OpPresign::new(OpStat::new(), time::Duration::hours(1)),
+            )
+            .expect_err("presign must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_presign_write_multipart() {
+        let mut builder = Builder::default();
+        builder
+            .endpoint("http://127.0.0.1:9000")
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+
+        let presign = |part_number| {
+            let op = OpPresign::new(
+                OpWriteMultipart::new("upload_id".to_string(), part_number, 0),
+                time::Duration::hours(1),
+            );
+            acc.presign("file", op)
+                .expect("presign must succeed")
+                .into_presigned_request()
+        };
+        let signature = |req: &PresignedRequest| {
+            req.uri()
+                .query()
+                .expect("query must exist")
+                .split('&')
+                .find_map(|v| v.strip_prefix("X-Amz-Signature="))
+                .expect("signature must exist")
+                .to_string()
+        };
+
+        let req = presign(1);
+        assert_eq!(req.method(), http::Method::PUT);
+        let query = req.uri().query().expect("query must exist");
+        assert!(query.contains("partNumber=1"), "{query}");
+        assert!(query.contains("uploadId=upload_id"), "{query}");
+
+        // Query parameters are part of the canonical request, so signature
+        // changes with part number.
+        assert_ne!(signature(&req), signature(&presign(2)));
+    }
+
+    #[test]
+    fn test_build_with_invalid_config() {
+        let mut builder = Builder::default();
+        builder.region("us-east-1");
+        let err = builder.build().expect_err("build must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert_eq!(err.context("field"), Some("bucket"));
+
+        let mut builder = Builder::default();
+        builder
+            .bucket("test")
+            .region("us-east-1")
+            .server_side_encryption("invalid\nvalue");
+        let err = builder.build().expect_err("build must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert_eq!(err.context("field"), Some("server_side_encryption"));
+    }
+
+    #[tokio::test]
+    async fn test_etag() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::OpStat;
+        use crate::OpWrite;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "5")
+                    .insert_header("etag", "\"5d41402abc4b2a76b9719d911017c592\""),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"5d41402abc4b2a76b9719d911017c592\""),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+
+        let rp = acc
+            .write(
+                "file",
+                OpWrite::new(5),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+            .await
+            .expect("write must succeed");
+        assert_eq!(rp.etag(), Some("5d41402abc4b2a76b9719d911017c592"));
+
+        let meta = acc
+            .stat("file", OpStat::new())
+            .await
+            .expect("stat must succeed")
+            .into_metadata();
+        assert_eq!(meta.etag(), Some("5d41402abc4b2a76b9719d911017c592"));
+    }
+
+    #[tokio::test]
+    async fn test_tagging() {
+        use wiremock::matchers::body_string;
+        use wiremock::matchers::header_exists;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::OpDeleteTags;
+        use crate::OpGetTags;
+        use crate::OpSetTags;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("tagging", ""))
+            .and(header_exists("content-md5"))
+            .and(body_string(
+                "<Tagging><TagSet>\
+                 <Tag><Key>project</Key><Value>ingestion</Value></Tag>\
+                 <Tag><Key>项目</Key><Value>数据 &amp; 湖</Value></Tag>\
+                 </TagSet></Tagging>",
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("tagging", ""))
+            .and(body_string("<Tagging><TagSet/></Tagging>"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .and(query_param("tagging", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<Tagging xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <TagSet>
+    <Tag><Key>project</Key><Value>ingestion</Value></Tag>
+    <Tag><Key>项目</Key><Value>数据 &amp; 湖</Value></Tag>
+  </TagSet>
+</Tagging>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test/empty"))
+            .and(query_param("tagging", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<Tagging xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><TagSet/></Tagging>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/test/file"))
+            .and(query_param("tagging", ""))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+
+        let tags = HashMap::from([
+            ("项目".to_string(), "数据 & 湖".to_string()),
+            ("project".to_string(), "ingestion".to_string()),
+        ]);
+        acc.set_tags("file", OpSetTags::new(tags.clone()))
+            .await
+            .expect("set tags must succeed");
+        acc.set_tags("file", OpSetTags::new(HashMap::new()))
+            .await
+            .expect("set empty tags must succeed");
+
+        let rp = acc
+            .get_tags("file", OpGetTags::new())
+            .await
+            .expect("get tags must succeed");
+        assert_eq!(rp.into_tags(), tags);
+
+        let rp = acc
+            .get_tags("empty", OpGetTags::new())
+            .await
+            .expect("get tags must succeed");
+        assert!(rp.tags().is_empty());
+
+        acc.delete_tags("file", OpDeleteTags::new())
+            .await
+            .expect("delete tags must succeed");
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let common = AccessorCapability::Read
+            | AccessorCapability::Write
+            | AccessorCapability::List
+            | AccessorCapability::Multipart
+            | AccessorCapability::Batch
+            | AccessorCapability::ConditionalWrite
+            | AccessorCapability::ConditionalRead
+            | AccessorCapability::ListStartAfter
+            | AccessorCapability::ListRecursive
+            | AccessorCapability::Copy
+            | AccessorCapability::Tagging
+            | AccessorCapability::Versioning;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint("http://127.0.0.1:9000")
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let op = Operator::new(builder.build().expect("build must succeed"));
+        assert_eq!(
+            op.metadata().capabilities(),
+            common | AccessorCapability::Presign
+        );
+        op.object("file")
+            .presign_read(time::Duration::hours(1))
+            .expect("presign must succeed");
+
+        // Presign requires static credential.
+        let mut builder = Builder::default();
+        builder
+            .endpoint("http://127.0.0.1:9000")
+            .bucket("test")
+            .region("us-east-1")
+            .allow_anonymous();
+        let op = Operator::new(builder.build().expect("build must succeed"));
+        assert_eq!(op.metadata().capabilities(), common);
+        let err = op
+            .object("file")
+            .blocking_read()
+            .expect_err("blocking read must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_is_r2_endpoint() {
+        for (endpoint, expected) in [
+            ("https://account.r2.cloudflarestorage.com", true),
+            ("account.r2.cloudflarestorage.com", true),
+            ("https://account.eu.r2.cloudflarestorage.com:443/", true),
+            ("https://r2.cloudflarestorage.com", false),
+            ("https://s3.amazonaws.com", false),
+            ("http://127.0.0.1:9000/r2.cloudflarestorage.com", false),
+        ] {
+            assert_eq!(is_r2_endpoint(endpoint), expected, "{endpoint}");
+        }
+    }
+
+    #[test]
+    fn test_r2_compatible() {
+        let mut builder = Builder::default();
+        builder
+            .endpoint("https://account.r2.cloudflarestorage.com")
+            .bucket("test")
+            .enable_virtual_host_style()
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        // Region is not set, build will not send requests to detect it.
+        let op = Operator::new(builder.build().expect("build must succeed"));
+
+        let caps = op.metadata().capabilities();
+        assert!(caps.contains(AccessorCapability::Presign));
+        assert!(caps.contains(AccessorCapability::Multipart));
+        assert!(!caps.contains(AccessorCapability::Tagging));
+        assert!(!caps.contains(AccessorCapability::Versioning));
+
+        let req = op
+            .object("file")
+            .presign_read(time::Duration::hours(1))
+            .expect("presign must succeed");
+        assert!(
+            req.uri()
+                .to_string()
+                .starts_with("https://account.r2.cloudflarestorage.com/test/file?"),
+            "{}",
+            req.uri()
+        );
+        assert!(
+            req.uri()
+                .query()
+                .unwrap_or_default()
+                .contains("%2Fauto%2Fs3%2F"),
+            "{}",
+            req.uri()
+        );
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint("https://account.r2.cloudflarestorage.com")
+            .bucket("test")
+            .server_side_encryption_with_aws_managed_kms_key();
+        let err = builder.build().expect_err("build must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_r2_compatible_headers() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(|req: &wiremock::Request| {
+                !req.headers
+                    .iter()
+                    .any(|(k, _)| k.as_str() == constants::X_AMZ_SERVER_SIDE_ENCRYPTION)
+            })
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .enable_r2_compatible()
+            .server_side_encryption_with_s3_key()
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key");
+        let acc = builder.build().expect("build must succeed");
+
+        acc.write(
+            "file",
+            OpWrite::new(5),
+            Box::new(futures::io::Cursor::new("hello")),
+        )
+        .await
+        .expect("write must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_refresh() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        fn has_credential(access_key: &'static str) -> impl Fn(&wiremock::Request) -> bool {
+            move |req: &wiremock::Request| {
+                // Header values are split by comma.
+                req.headers.iter().any(|(k, v)| {
+                    k.as_str() == "authorization"
+                        && v.iter().any(|v| {
+                            v.as_str()
+                                .starts_with(&format!("AWS4-HMAC-SHA256 Credential={access_key}/"))
+                        })
+                })
+            }
+        }
+
+        fn sts_response(access_key: &str, ttl: time::Duration) -> ResponseTemplate {
+            let expiration = (OffsetDateTime::now_utc() + ttl)
+                .format(&Rfc3339)
+                .expect("format must succeed");
+            ResponseTemplate::new(200).set_body_string(format!(
+                r#"<AssumeRoleResponse xmlns="https://sts.amazonaws.com/doc/2011-06-15/">
+  <AssumeRoleResult>
+    <Credentials>
+      <AccessKeyId>{access_key}</AccessKeyId>
+      <SecretAccessKey>secret_access_key</SecretAccessKey>
+      <SessionToken>session_token</SessionToken>
+      <Expiration>{expiration}</Expiration>
+    </Credentials>
+  </AssumeRoleResult>
+</AssumeRoleResponse>"#
+            ))
+        }
+
+        let mock_server = MockServer::start().await;
+        // Credentials will be refreshed 2 minutes before expiration, so the
+        // first one is only valid in 3 seconds.
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(query_param("Action", "AssumeRole"))
+            .and(query_param(
+                "RoleArn",
+                "arn:aws:iam::123456789012:role/demo",
+            ))
+            .and(query_param("RoleSessionName", "opendal"))
+            .and(has_credential("source_access_key"))
+            .respond_with(sts_response(
+                "first_access_key",
+                time::Duration::seconds(123),
+            ))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(query_param("Action", "AssumeRole"))
+            .and(has_credential("source_access_key"))
+            .respond_with(sts_response("second_access_key", time::Duration::hours(1)))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(has_credential("first_access_key"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(has_credential("second_access_key"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = Builder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .bucket("test")
+            .region("us-east-1")
+            .access_key_id("source_access_key")
+            .secret_access_key("source_secret_access_key")
+            .role_arn("arn:aws:iam::123456789012:role/demo")
+            .sts_endpoint(&mock_server.uri())
+            .disable_credential_loader();
+        let acc = builder.build().expect("build must succeed");
+        // Presign requires static credentials.
+        assert!(!acc
+            .metadata()
+            .capabilities()
+            .contains(AccessorCapability::Presign));
+
+        let write = || {
+            acc.write(
+                "file",
+                OpWrite::new(5),
+                Box::new(futures::io::Cursor::new("hello")),
+            )
+        };
+
+        write().await.expect("write must succeed");
+        // Cached credentials will be used.
+        write().await.expect("write must succeed");
+
+        tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+        write().await.expect("write must succeed");
+    }
 }
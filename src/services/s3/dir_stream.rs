@@ -18,15 +18,12 @@ use async_trait::async_trait;
 use bytes::Buf;
 use quick_xml::de;
 use serde::Deserialize;
-use time::format_description::well_known::Rfc3339;
-use time::OffsetDateTime;
 
+use super::backend::parse_content_md5_from_etag;
 use super::backend::Backend;
 use super::error::parse_error;
 use super::error::parse_xml_deserialize_error;
 use crate::raw::*;
-use crate::Error;
-use crate::ErrorKind;
 use crate::ObjectMetadata;
 use crate::ObjectMode;
 use crate::Result;
@@ -35,18 +32,31 @@ pub struct DirStream {
     backend: Arc<Backend>,
     root: String,
     path: String,
+    delimiter: &'static str,
 
+    limit: Option<usize>,
+    start_after: Option<String>,
     token: String,
     done: bool,
 }
 
 impl DirStream {
-    pub fn new(backend: Arc<Backend>, root: &str, path: &str) -> Self {
+    pub fn new(
+        backend: Arc<Backend>,
+        root: &str,
+        path: &str,
+        delimiter: &'static str,
+        limit: Option<usize>,
+        start_after: Option<&str>,
+    ) -> Self {
         Self {
             backend,
             root: root.to_string(),
             path: path.to_string(),
+            delimiter,
 
+            limit,
+            start_after: start_after.map(|v| v.to_string()),
             token: "".to_string(),
             done: false,
         }
@@ -62,7 +72,13 @@ impl ObjectPage for DirStream {
 
         let resp = self
             .backend
-            .s3_list_objects(&self.path, &self.token)
+            .s3_list_objects(
+                &self.path,
+                self.delimiter,
+                &self.token,
+                self.limit,
+                self.start_after.as_deref(),
+            )
             .await?;
 
         if resp.status() != http::StatusCode::OK {
@@ -108,31 +124,34 @@ impl ObjectPage for DirStream {
 
             let mut meta = ObjectMetadata::new(ObjectMode::FILE);
 
-            meta.set_etag(&object.etag);
-            meta.set_content_md5(object.etag.trim_matches('"'));
+            meta.set_etag(&normalize_etag(&object.etag));
+            if let Some(v) = parse_content_md5_from_etag(&object.etag) {
+                meta.set_content_md5(v);
+            }
             meta.set_content_length(object.size);
 
             // object.last_modified provides more precious time that contains
             // nanosecond, let's trim them.
-            let dt = OffsetDateTime::parse(object.last_modified.as_str(), &Rfc3339)
-                .map(|v| {
-                    v.replace_nanosecond(0)
-                        .expect("replace nanosecond of last modified must succeed")
-                })
-                .map_err(|e| {
-                    Error::new(
-                        ErrorKind::Unexpected,
-                        "parse last modified RFC3339 datetime",
-                    )
-                    .set_source(e)
-                })?;
-            meta.set_last_modified(dt);
+            if let Some(dt) = parse_datetime_from_rfc3339(&object.last_modified) {
+                meta.set_last_modified(
+                    dt.replace_nanosecond(0)
+                        .expect("replace nanosecond of last modified must succeed"),
+                );
+            }
 
             let de = ObjectEntry::new(&build_rel_path(&self.root, &object.key), meta);
 
             entries.push(de);
         }
 
+        // Keep entries in lexicographic order, so that the path of last
+        // returned entry can be used as `start_after` to resume listing.
+        entries.sort_by(|a, b| a.path().cmp(b.path()));
+        // The common prefix equals to `start_after` could be returned again.
+        if let Some(start_after) = &self.start_after {
+            entries.retain(|v| v.path() != start_after);
+        }
+
         Ok(Some(entries))
     }
 }
@@ -41,6 +41,9 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (kind, retryable) = match parts.status {
         StatusCode::NOT_FOUND => (ErrorKind::ObjectNotFound, false),
         StatusCode::FORBIDDEN => (ErrorKind::ObjectPermissionDenied, false),
+        StatusCode::NOT_MODIFIED | StatusCode::PRECONDITION_FAILED => {
+            (ErrorKind::ConditionNotMatch, false)
+        }
         StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
@@ -54,6 +57,7 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     };
 
     let mut err = Error::new(kind, &message).with_context("response", format!("{:?}", parts));
+    err = with_current_metadata(err, &parts.headers);
 
     if retryable {
         err = err.set_temporary();
@@ -62,6 +66,27 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     Ok(err)
 }
 
+/// Parse error code returned inside response body into Error.
+///
+/// For example, `DeleteObjects` will return errors for every failed key
+/// while the status code is `200 OK`.
+pub fn parse_error_code(code: &str, message: &str) -> Error {
+    let (kind, retryable) = match code {
+        "NoSuchKey" => (ErrorKind::ObjectNotFound, false),
+        "AccessDenied" => (ErrorKind::ObjectPermissionDenied, false),
+        "InternalError" | "ServiceUnavailable" | "SlowDown" => (ErrorKind::Unexpected, true),
+        _ => (ErrorKind::Unexpected, false),
+    };
+
+    let mut err = Error::new(kind, message).with_context("code", code);
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    err
+}
+
 pub fn parse_xml_deserialize_error(e: quick_xml::DeError) -> Error {
     Error::new(ErrorKind::Unexpected, "deserialize xml").set_source(e)
 }
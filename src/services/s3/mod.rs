@@ -23,6 +23,11 @@
 //! - `access_key_id`: Set the access_key_id for backend.
 //! - `secret_access_key`: Set the secret_access_key for backend.
 //! - `security_token`: Set the security_token for backend.
+//! - `role_arn`: Set the role to assume via AWS STS for backend.
+//! - `role_session_name`: Set the session name of assumed role, default to `opendal`.
+//! - `external_id`: Set the external_id used while assuming role.
+//! - `web_identity_token_file`: Assume role with the web identity token in this file.
+//! - `sts_endpoint`: Set the endpoint of AWS STS, default to `https://sts.amazonaws.com`.
 //! - `server_side_encryption`: Set the server_side_encryption for backend.
 //! - `server_side_encryption_aws_kms_key_id`: Set the server_side_encryption_aws_kms_key_id for backend.
 //! - `server_side_encryption_customer_algorithm`: Set the server_side_encryption_customer_algorithm for backend.
@@ -30,6 +35,8 @@
 //! - `server_side_encryption_customer_key_md5`: Set the server_side_encryption_customer_key_md5 for backend.
 //! - `disable_credential_loader`: Disable aws credential loader from env
 //! - `enable_virtual_host_style`: Enable virtual host style.
+//! - `allow_anonymous`: Send requests without signing, used to access public buckets.
+//! - `enable_r2_compatible`: Enable Cloudflare R2 compatible mode.
 //!
 //! Refer to [`Builder`]'s public API docs for more information.
 //!
@@ -42,12 +49,19 @@
 //! - `OPENDAL_S3_ACCESS_KEY_ID`
 //! - `OPENDAL_S3_SECRET_ACCESS_KEY`
 //! - `OPENDAL_S3_SECURITY_TOKEN`
+//! - `OPENDAL_S3_ROLE_ARN`
+//! - `OPENDAL_S3_ROLE_SESSION_NAME`
+//! - `OPENDAL_S3_EXTERNAL_ID`
+//! - `OPENDAL_S3_WEB_IDENTITY_TOKEN_FILE`
+//! - `OPENDAL_S3_STS_ENDPOINT`
 //! - `OPENDAL_S3_SERVER_SIDE_ENCRYPTION`
 //! - `OPENDAL_S3_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM`
 //! - `OPENDAL_S3_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY`
 //! - `OPENDAL_S3_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5`
 //! - `OPENDAL_S3_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID`
 //! - `OPENDAL_S3_ENABLE_VIRTUAL_HOST_STYLE`
+//! - `OPENDAL_S3_ALLOW_ANONYMOUS`
+//! - `OPENDAL_S3_ENABLE_R2_COMPATIBLE`
 //!
 //! # Temporary security credentials
 //!
@@ -57,6 +71,12 @@
 //!
 //! But OpenDAL will not refresh the temporary security credentials, please keep in mind to refresh those credentials in time.
 //!
+//! To let OpenDAL refresh them, set `role_arn` instead. OpenDAL will call AWS STS
+//! `AssumeRole` (or `AssumeRoleWithWebIdentity` if `web_identity_token_file` is set)
+//! to load temporary credentials of the role, and load them again 2 minutes before
+//! they expire. Presign is not supported in this case since presigned requests will
+//! be invalid after credentials expired.
+//!
 //! # Server Side Encryption
 //!
 //! OpenDAL provides full support of S3 Server Side Encryption(SSE) features.
@@ -86,6 +106,21 @@
 //!
 //! Reference: [Protecting data using server-side encryption](https://docs.aws.amazon.com/AmazonS3/latest/userguide/serv-side-encryption.html)
 //!
+//! # Cloudflare R2
+//!
+//! R2 compatible mode will be enabled if `endpoint` is like
+//! `https://<account_id>.r2.cloudflarestorage.com` or `enable_r2_compatible`
+//! is set. In this mode:
+//!
+//! - `region` will be `auto` if not set.
+//! - Requests are always sent in path style.
+//! - `server_side_encryption="AES256"` will be ignored, SSE-KMS is not supported.
+//! - Tagging and versioning are not supported.
+//! - All parts of a multipart upload except the last one must be in the
+//!   same size, so don't call `ObjectWriter::flush` explicitly.
+//!
+//! Reference: [R2 S3 API compatibility](https://developers.cloudflare.com/r2/api/s3/api/)
+//!
 //! # Example
 //!
 //! ## Via Environment
@@ -169,5 +204,6 @@
 mod backend;
 pub use backend::Builder;
 
+mod credential;
 mod dir_stream;
-mod error;
+pub(super) mod error;
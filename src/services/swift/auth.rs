@@ -0,0 +1,365 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use http::header::CONTENT_TYPE;
+use http::Request;
+use http::StatusCode;
+use log::debug;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde_json::json;
+use time::format_description::well_known::Rfc3339;
+use time::Duration;
+use time::OffsetDateTime;
+
+use super::error::parse_error;
+use super::error::parse_json_deserialize_error;
+use super::error::parse_json_serialize_error;
+use crate::raw::*;
+use crate::*;
+
+/// Tokens will be refreshed before they are really expired.
+const TOKEN_REFRESH_AHEAD: Duration = Duration::minutes(2);
+
+/// Token returned by keystone, along with the storage url of swift.
+#[derive(Clone)]
+pub struct Token {
+    pub token: String,
+    pub storage_url: String,
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+impl Debug for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Token")
+            .field("token", &"<redacted>")
+            .field("storage_url", &self.storage_url)
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+impl Token {
+    fn is_valid(&self) -> bool {
+        match self.expires_at {
+            Some(expires_at) => expires_at - TOKEN_REFRESH_AHEAD > OffsetDateTime::now_utc(),
+            None => true,
+        }
+    }
+}
+
+/// Config to authenticate via keystone v3 password method.
+#[derive(Clone)]
+pub struct Keystone {
+    pub endpoint: String,
+    pub username: String,
+    pub password: String,
+    pub user_domain: String,
+    pub project: String,
+    pub project_domain: String,
+    pub region: Option<String>,
+    /// Use this storage url instead of the one in service catalog.
+    pub storage_url: Option<String>,
+}
+
+impl Debug for Keystone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Keystone")
+            .field("endpoint", &self.endpoint)
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .field("user_domain", &self.user_domain)
+            .field("project", &self.project)
+            .field("project_domain", &self.project_domain)
+            .field("region", &self.region)
+            .field("storage_url", &self.storage_url)
+            .finish()
+    }
+}
+
+/// Authenticator will cache the token and authenticate again while the
+/// token is expired or invalidated.
+pub struct Authenticator {
+    client: HttpClient,
+    keystone: Option<Keystone>,
+    token: Mutex<Option<Token>>,
+}
+
+impl Debug for Authenticator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Authenticator")
+            .field("keystone", &self.keystone)
+            .field("token", &self.token.lock())
+            .finish()
+    }
+}
+
+impl Authenticator {
+    /// Create an authenticator that authenticate via keystone.
+    pub fn new(client: HttpClient, keystone: Keystone) -> Self {
+        Self {
+            client,
+            keystone: Some(keystone),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Create an authenticator with a pre-authenticated token which can't
+    /// be refreshed.
+    pub fn with_token(client: HttpClient, token: Token) -> Self {
+        Self {
+            client,
+            keystone: None,
+            token: Mutex::new(Some(token)),
+        }
+    }
+
+    /// Get a valid token, authenticate via keystone if needed.
+    pub async fn token(&self) -> Result<Token> {
+        if let Some(token) = self.token.lock().as_ref() {
+            if token.is_valid() || self.keystone.is_none() {
+                return Ok(token.clone());
+            }
+        }
+
+        let keystone = self.keystone.as_ref().ok_or_else(|| {
+            Error::new(ErrorKind::ObjectPermissionDenied, "swift token is invalid")
+                .with_context("service", Scheme::Swift)
+        })?;
+
+        let token = self.authenticate(keystone).await?;
+        *self.token.lock() = Some(token.clone());
+        Ok(token)
+    }
+
+    /// Invalidate the cached token so that next [`Authenticator::token`]
+    /// will authenticate again.
+    ///
+    /// Returns `false` if the token can't be refreshed.
+    pub fn invalidate(&self) -> bool {
+        if self.keystone.is_none() {
+            return false;
+        }
+
+        *self.token.lock() = None;
+        true
+    }
+
+    async fn authenticate(&self, keystone: &Keystone) -> Result<Token> {
+        debug!("swift authenticate via keystone: {:?}", keystone);
+
+        let body = json!({
+            "auth": {
+                "identity": {
+                    "methods": ["password"],
+                    "password": {
+                        "user": {
+                            "name": keystone.username,
+                            "domain": { "name": keystone.user_domain },
+                            "password": keystone.password,
+                        }
+                    }
+                },
+                "scope": {
+                    "project": {
+                        "name": keystone.project,
+                        "domain": { "name": keystone.project_domain },
+                    }
+                }
+            }
+        });
+        let body = serde_json::to_vec(&body).map_err(parse_json_serialize_error)?;
+
+        let url = format!("{}/auth/tokens", keystone.endpoint);
+        let req = Request::post(&url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(AsyncBody::Bytes(body.into()))
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send_async(req).await?;
+        if resp.status() != StatusCode::CREATED {
+            return Err(parse_error(resp)
+                .await?
+                .with_operation("swift::authenticate"));
+        }
+
+        let token = resp
+            .headers()
+            .get("x-subject-token")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "X-Subject-Token not present in returning response",
+                )
+                .with_operation("swift::authenticate")
+            })?;
+
+        let bs = resp.into_body().bytes().await?;
+        let output: TokenOutput =
+            serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+        let storage_url = match &keystone.storage_url {
+            Some(v) => v.clone(),
+            None => output
+                .storage_url(keystone.region.as_deref())
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "object-store endpoint not found in service catalog",
+                    )
+                    .with_operation("swift::authenticate")
+                    .with_context("region", keystone.region.as_deref().unwrap_or("<any>"))
+                })?,
+        };
+
+        let expires_at = output
+            .token
+            .expires_at
+            .as_deref()
+            .map(|v| {
+                OffsetDateTime::parse(v, &Rfc3339).map_err(|e| {
+                    Error::new(ErrorKind::Unexpected, "parse token expires_at")
+                        .with_operation("swift::authenticate")
+                        .set_source(e)
+                })
+            })
+            .transpose()?;
+
+        Ok(Token {
+            token,
+            storage_url: storage_url.trim_end_matches('/').to_string(),
+            expires_at,
+        })
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct TokenOutput {
+    token: TokenBody,
+}
+
+impl TokenOutput {
+    /// Find the public url of object-store in catalog.
+    fn storage_url(&self, region: Option<&str>) -> Option<String> {
+        self.token
+            .catalog
+            .iter()
+            .filter(|v| v.typ == "object-store")
+            .flat_map(|v| v.endpoints.iter())
+            .filter(|v| v.interface == "public")
+            .find(|v| match region {
+                Some(region) => v.region == region || v.region_id == region,
+                None => true,
+            })
+            .map(|v| v.url.clone())
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct TokenBody {
+    expires_at: Option<String>,
+    catalog: Vec<CatalogEntry>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct CatalogEntry {
+    #[serde(rename = "type")]
+    typ: String,
+    endpoints: Vec<CatalogEndpoint>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct CatalogEndpoint {
+    interface: String,
+    region: String,
+    region_id: String,
+    url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token_output() {
+        let bs = r#"{
+  "token": {
+    "methods": ["password"],
+    "expires_at": "2022-12-07T10:44:12.000000Z",
+    "catalog": [
+      {
+        "type": "identity",
+        "endpoints": [
+          {"interface": "public", "region": "RegionOne", "region_id": "RegionOne", "url": "http://keystone:5000/v3/"}
+        ]
+      },
+      {
+        "type": "object-store",
+        "endpoints": [
+          {"interface": "internal", "region": "RegionOne", "region_id": "RegionOne", "url": "http://internal:8080/v1/AUTH_test"},
+          {"interface": "public", "region": "RegionOne", "region_id": "RegionOne", "url": "http://one:8080/v1/AUTH_test"},
+          {"interface": "public", "region": "RegionTwo", "region_id": "RegionTwo", "url": "http://two:8080/v1/AUTH_test"}
+        ]
+      }
+    ]
+  }
+}"#;
+
+        let out: TokenOutput = serde_json::from_str(bs).expect("must success");
+        assert_eq!(
+            out.token.expires_at.as_deref(),
+            Some("2022-12-07T10:44:12.000000Z")
+        );
+        assert_eq!(
+            out.storage_url(None).as_deref(),
+            Some("http://one:8080/v1/AUTH_test")
+        );
+        assert_eq!(
+            out.storage_url(Some("RegionTwo")).as_deref(),
+            Some("http://two:8080/v1/AUTH_test")
+        );
+        assert_eq!(out.storage_url(Some("RegionThree")), None);
+    }
+
+    #[test]
+    fn test_token_is_valid() {
+        let token = Token {
+            token: "token".to_string(),
+            storage_url: "http://127.0.0.1:8080/v1/AUTH_test".to_string(),
+            expires_at: None,
+        };
+        assert!(token.is_valid());
+
+        let token = Token {
+            expires_at: Some(OffsetDateTime::now_utc() + Duration::minutes(1)),
+            ..token
+        };
+        assert!(!token.is_valid(), "token is about to expire");
+
+        let token = Token {
+            expires_at: Some(OffsetDateTime::now_utc() + Duration::hours(1)),
+            ..token
+        };
+        assert!(token.is_valid());
+    }
+}
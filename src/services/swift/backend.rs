@@ -0,0 +1,908 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::header::ACCEPT;
+use http::header::CONTENT_LENGTH;
+use http::header::CONTENT_TYPE;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use log::debug;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::auth::Authenticator;
+use super::auth::Keystone;
+use super::auth::Token;
+use super::dir_stream::DirStream;
+use super::dir_stream::ListOutput;
+use super::error::parse_error;
+use super::error::parse_json_deserialize_error;
+use super::error::parse_json_serialize_error;
+use crate::raw::*;
+use crate::*;
+
+/// Header that carries the auth token for swift.
+const X_AUTH_TOKEN: &str = "X-Auth-Token";
+
+/// Builder for OpenStack Swift services
+#[derive(Default, Clone)]
+pub struct Builder {
+    root: Option<String>,
+    endpoint: Option<String>,
+    container: Option<String>,
+    segment_container: Option<String>,
+    region: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    user_domain: Option<String>,
+    project: Option<String>,
+    project_domain: Option<String>,
+    storage_url: Option<String>,
+    token: Option<String>,
+}
+
+impl Debug for Builder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut d = f.debug_struct("Builder");
+        d.field("root", &self.root)
+            .field("endpoint", &self.endpoint)
+            .field("container", &self.container)
+            .field("segment_container", &self.segment_container)
+            .field("region", &self.region)
+            .field("username", &self.username)
+            .field("user_domain", &self.user_domain)
+            .field("project", &self.project)
+            .field("project_domain", &self.project_domain)
+            .field("storage_url", &self.storage_url);
+        if self.password.is_some() {
+            d.field("password", &"<redacted>");
+        }
+        if self.token.is_some() {
+            d.field("token", &"<redacted>");
+        }
+        d.finish()
+    }
+}
+
+impl Builder {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "root",
+        "endpoint",
+        "container",
+        "segment_container",
+        "region",
+        "username",
+        "password",
+        "user_domain",
+        "project",
+        "project_domain",
+        "storage_url",
+        "token",
+    ];
+
+    pub(crate) fn from_iter(it: impl Iterator<Item = (String, String)>) -> Self {
+        let mut builder = Builder::default();
+
+        for (k, v) in it {
+            let v = v.as_str();
+            match k.as_ref() {
+                "root" => builder.root(v),
+                "endpoint" => builder.endpoint(v),
+                "container" => builder.container(v),
+                "segment_container" => builder.segment_container(v),
+                "region" => builder.region(v),
+                "username" => builder.username(v),
+                "password" => builder.password(v),
+                "user_domain" => builder.user_domain(v),
+                "project" => builder.project(v),
+                "project_domain" => builder.project_domain(v),
+                "storage_url" => builder.storage_url(v),
+                "token" => builder.token(v),
+                _ => continue,
+            };
+        }
+
+        builder
+    }
+
+    /// Set root of this backend.
+    ///
+    /// All operations will happen under this root.
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_string())
+        }
+
+        self
+    }
+
+    /// Set keystone v3 endpoint of this backend.
+    ///
+    /// For example: `https://keystone.example.com:5000/v3`
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        if !endpoint.is_empty() {
+            self.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        }
+
+        self
+    }
+
+    /// Set container of this backend.
+    /// The param is required.
+    pub fn container(&mut self, container: &str) -> &mut Self {
+        if !container.is_empty() {
+            self.container = Some(container.to_string());
+        }
+
+        self
+    }
+
+    /// Set the container to store segments of large objects.
+    ///
+    /// Default to `<container>_segments`, it will be created if not exist.
+    pub fn segment_container(&mut self, segment_container: &str) -> &mut Self {
+        if !segment_container.is_empty() {
+            self.segment_container = Some(segment_container.to_string());
+        }
+
+        self
+    }
+
+    /// Set region of this backend.
+    ///
+    /// Region is used to pick the object-store endpoint from the service
+    /// catalog. The first public endpoint will be used if not set.
+    pub fn region(&mut self, region: &str) -> &mut Self {
+        if !region.is_empty() {
+            self.region = Some(region.to_string());
+        }
+
+        self
+    }
+
+    /// Set username for keystone authentication.
+    pub fn username(&mut self, username: &str) -> &mut Self {
+        if !username.is_empty() {
+            self.username = Some(username.to_string());
+        }
+
+        self
+    }
+
+    /// Set password for keystone authentication.
+    pub fn password(&mut self, password: &str) -> &mut Self {
+        if !password.is_empty() {
+            self.password = Some(password.to_string());
+        }
+
+        self
+    }
+
+    /// Set domain of user, default to `Default`.
+    pub fn user_domain(&mut self, user_domain: &str) -> &mut Self {
+        if !user_domain.is_empty() {
+            self.user_domain = Some(user_domain.to_string());
+        }
+
+        self
+    }
+
+    /// Set project to scope the token.
+    pub fn project(&mut self, project: &str) -> &mut Self {
+        if !project.is_empty() {
+            self.project = Some(project.to_string());
+        }
+
+        self
+    }
+
+    /// Set domain of project, default to `Default`.
+    pub fn project_domain(&mut self, project_domain: &str) -> &mut Self {
+        if !project_domain.is_empty() {
+            self.project_domain = Some(project_domain.to_string());
+        }
+
+        self
+    }
+
+    /// Set storage url of this backend.
+    ///
+    /// For example: `https://swift.example.com/v1/AUTH_test`
+    ///
+    /// - If it is set, we will use it instead of the one in service catalog.
+    /// - If it is set along with `token`, keystone authentication will be
+    ///   skipped.
+    pub fn storage_url(&mut self, storage_url: &str) -> &mut Self {
+        if !storage_url.is_empty() {
+            self.storage_url = Some(storage_url.trim_end_matches('/').to_string());
+        }
+
+        self
+    }
+
+    /// Set a pre-authenticated token of this backend.
+    ///
+    /// The token can't be refreshed, please use keystone authentication
+    /// for long running services.
+    pub fn token(&mut self, token: &str) -> &mut Self {
+        if !token.is_empty() {
+            self.token = Some(token.to_string());
+        }
+
+        self
+    }
+
+    /// Consume builder to build a swift backend.
+    pub fn build(&mut self) -> Result<impl Accessor> {
+        debug!("backend build started: {:?}", &self);
+
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let container = match &self.container {
+            Some(container) => Ok(container.to_string()),
+            None => Err(Error::new(ErrorKind::ConfigInvalid, "container is empty")
+                .with_context("field", "container")
+                .with_context("service", Scheme::Swift)),
+        }?;
+        debug!("backend use container {}", &container);
+
+        let segment_container = self
+            .segment_container
+            .clone()
+            .unwrap_or_else(|| format!("{container}_segments"));
+        debug!("backend use segment container {}", &segment_container);
+
+        let client = HttpClient::new();
+
+        let auth = match (&self.storage_url, &self.token) {
+            (Some(storage_url), Some(token)) => Authenticator::with_token(
+                client.clone(),
+                Token {
+                    token: token.clone(),
+                    storage_url: storage_url.clone(),
+                    expires_at: None,
+                },
+            ),
+            _ => {
+                let required = |v: &Option<String>, name: &str| {
+                    v.clone().ok_or_else(|| {
+                        Error::new(ErrorKind::ConfigInvalid, &format!("{name} is empty"))
+                            .with_context("field", name)
+                            .with_context("service", Scheme::Swift)
+                    })
+                };
+
+                Authenticator::new(
+                    client.clone(),
+                    Keystone {
+                        endpoint: required(&self.endpoint, "endpoint")?,
+                        username: required(&self.username, "username")?,
+                        password: required(&self.password, "password")?,
+                        user_domain: self
+                            .user_domain
+                            .clone()
+                            .unwrap_or_else(|| "Default".to_string()),
+                        project: required(&self.project, "project")?,
+                        project_domain: self
+                            .project_domain
+                            .clone()
+                            .unwrap_or_else(|| "Default".to_string()),
+                        region: self.region.clone(),
+                        storage_url: self.storage_url.clone(),
+                    },
+                )
+            }
+        };
+
+        debug!("backend build finished: {:?}", &self);
+        Ok(apply_wrapper(Backend {
+            client,
+            root,
+            container,
+            segment_container,
+            auth: Arc::new(auth),
+        }))
+    }
+}
+
+/// Backend for OpenStack Swift services.
+#[derive(Debug, Clone)]
+pub struct Backend {
+    client: HttpClient,
+    root: String,
+    container: String,
+    segment_container: String,
+    auth: Arc<Authenticator>,
+}
+
+#[async_trait]
+impl Accessor for Backend {
+    fn metadata(&self) -> AccessorMetadata {
+        let mut am = AccessorMetadata::default();
+        am.set_scheme(Scheme::Swift)
+            .set_root(&self.root)
+            .set_name(&self.container)
+            .set_capabilities(
+                AccessorCapability::Read
+                    | AccessorCapability::Write
+                    | AccessorCapability::List
+                    | AccessorCapability::Multipart
+                    | AccessorCapability::ConditionalRead
+                    | AccessorCapability::ListRecursive,
+            );
+
+        am
+    }
+
+    async fn create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
+        let resp = self
+            .send(|token| {
+                let url = self.object_url(token, &self.container, path);
+
+                Request::put(&url)
+                    .header(X_AUTH_TOKEN, &token.token)
+                    .header(CONTENT_LENGTH, 0)
+                    .body(AsyncBody::Empty)
+                    .map_err(new_request_build_error)
+            })
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpCreate::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, BytesReader)> {
+        let resp = self
+            .send(|token| {
+                let url = self.object_url(token, &self.container, path);
+
+                let mut req = Request::get(&url).header(X_AUTH_TOKEN, &token.token);
+
+                let range = args.range();
+                if let Some(range_header) = range.to_header() {
+                    req = req.header(http::header::RANGE, range_header);
+                }
+                if let Some(if_match) = args.if_match() {
+                    req = req.header(http::header::IF_MATCH, if_match);
+                }
+                if let Some(if_none_match) = args.if_none_match() {
+                    req = req.header(http::header::IF_NONE_MATCH, if_none_match);
+                }
+
+                req.body(AsyncBody::Empty).map_err(new_request_build_error)
+            })
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let meta = parse_into_object_metadata(path, resp.headers())?;
+                Ok((RpRead::with_metadata(meta), resp.into_body().reader()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite, r: BytesReader) -> Result<RpWrite> {
+        let resp = self
+            .send_once(|token| {
+                let url = self.object_url(token, &self.container, path);
+
+                let mut req = Request::put(&url)
+                    .header(X_AUTH_TOKEN, &token.token)
+                    .header(CONTENT_LENGTH, args.size());
+                if let Some(mime) = args.content_type() {
+                    req = req.header(CONTENT_TYPE, mime);
+                }
+
+                req.body(AsyncBody::Reader(r))
+                    .map_err(new_request_build_error)
+            })
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpWrite::new(args.size()))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        // Stat root always returns a DIR.
+        if path == "/" {
+            return Ok(RpStat::new(ObjectMetadata::new(ObjectMode::DIR)));
+        }
+
+        let resp = self
+            .send(|token| {
+                let url = self.object_url(token, &self.container, path);
+
+                Request::head(&url)
+                    .header(X_AUTH_TOKEN, &token.token)
+                    .body(AsyncBody::Empty)
+                    .map_err(new_request_build_error)
+            })
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::NO_CONTENT => {
+                parse_into_object_metadata(path, resp.headers()).map(RpStat::new)
+            }
+            StatusCode::NOT_FOUND if path.ends_with('/') => {
+                Ok(RpStat::new(ObjectMetadata::new(ObjectMode::DIR)))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        // `multipart-manifest=delete` will remove the segments of large
+        // objects too, normal objects will be deleted as usual.
+        let resp = self
+            .send(|token| {
+                let url = format!(
+                    "{}?multipart-manifest=delete",
+                    self.object_url(token, &self.container, path)
+                );
+
+                Request::delete(&url)
+                    .header(X_AUTH_TOKEN, &token.token)
+                    .header(ACCEPT, "application/json")
+                    .body(AsyncBody::Empty)
+                    .map_err(new_request_build_error)
+            })
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::NO_CONTENT | StatusCode::ACCEPTED | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(RpDelete::default())
+            }
+            // Delete with `multipart-manifest` returns the result in body.
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                if bs.is_empty() {
+                    return Ok(RpDelete::default());
+                }
+
+                let output: BulkDeleteOutput =
+                    serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+                output.into_result(path).map(|_| RpDelete::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, ObjectPager)> {
+        Ok((
+            RpList::default(),
+            Box::new(DirStream::new(
+                Arc::new(self.clone()),
+                &self.root,
+                path,
+                args.recursive(),
+            )),
+        ))
+    }
+
+    async fn create_multipart(
+        &self,
+        path: &str,
+        _: OpCreateMultipart,
+    ) -> Result<RpCreateMultipart> {
+        // Make sure segment container exists, it's fine to create an
+        // existing container.
+        let resp = self
+            .send(|token| {
+                let url = format!(
+                    "{}/{}",
+                    token.storage_url,
+                    percent_encode_path(&self.segment_container)
+                );
+
+                Request::put(&url)
+                    .header(X_AUTH_TOKEN, &token.token)
+                    .header(CONTENT_LENGTH, 0)
+                    .body(AsyncBody::Empty)
+                    .map_err(new_request_build_error)
+            })
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::ACCEPTED | StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+
+                let upload_id = uuid::Uuid::new_v4().to_string();
+                debug!("swift create multipart {} with {}", path, upload_id);
+                Ok(RpCreateMultipart::new(&upload_id))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write_multipart(
+        &self,
+        path: &str,
+        args: OpWriteMultipart,
+        r: BytesReader,
+    ) -> Result<RpWriteMultipart> {
+        let segment = self.segment_path(path, args.upload_id(), args.part_number());
+
+        let resp = self
+            .send_once(|token| {
+                let url = format!(
+                    "{}/{}/{}",
+                    token.storage_url,
+                    percent_encode_path(&self.segment_container),
+                    percent_encode_path(&segment)
+                );
+
+                Request::put(&url)
+                    .header(X_AUTH_TOKEN, &token.token)
+                    .header(CONTENT_LENGTH, args.size())
+                    .body(AsyncBody::Reader(r))
+                    .map_err(new_request_build_error)
+            })
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                let etag = parse_etag(resp.headers())?.ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "ETag not present in returning response",
+                    )
+                })?;
+
+                resp.into_body().consume().await?;
+
+                Ok(RpWriteMultipart::new(args.part_number(), &etag))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        let manifest = args
+            .parts()
+            .iter()
+            .map(|v| ManifestSegment {
+                path: format!(
+                    "/{}/{}",
+                    self.segment_container,
+                    self.segment_path(path, args.upload_id(), v.part_number())
+                ),
+                etag: v.etag().trim_matches('"').to_string(),
+            })
+            .collect::<Vec<_>>();
+        let body =
+            bytes::Bytes::from(serde_json::to_vec(&manifest).map_err(parse_json_serialize_error)?);
+
+        let resp = self
+            .send(|token| {
+                let url = format!(
+                    "{}?multipart-manifest=put",
+                    self.object_url(token, &self.container, path)
+                );
+
+                Request::put(&url)
+                    .header(X_AUTH_TOKEN, &token.token)
+                    .header(CONTENT_LENGTH, body.len())
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(AsyncBody::Bytes(body.clone()))
+                    .map_err(new_request_build_error)
+            })
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                resp.into_body().consume().await?;
+
+                Ok(RpCompleteMultipart::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        let prefix = format!("{}/{}/", build_abs_path(&self.root, path), args.upload_id());
+
+        // Swift doesn't track uploads, so we need to remove all segments
+        // by ourselves.
+        let mut marker = String::new();
+        loop {
+            let resp = self
+                .swift_list(&self.segment_container, &prefix, None, &marker)
+                .await?;
+
+            match resp.status() {
+                StatusCode::OK => {}
+                // No segments have been written.
+                StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => break,
+                _ => return Err(parse_error(resp).await?),
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let output: Vec<ListOutput> =
+                serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?;
+
+            let segments = output
+                .into_iter()
+                .filter_map(|v| match v {
+                    ListOutput::Object { name, .. } => Some(name),
+                    ListOutput::Subdir { .. } => None,
+                })
+                .collect::<Vec<_>>();
+            let done = segments.len() < DirStream::LIMIT;
+
+            for segment in &segments {
+                let resp = self
+                    .send(|token| {
+                        let url = format!(
+                            "{}/{}/{}",
+                            token.storage_url,
+                            percent_encode_path(&self.segment_container),
+                            percent_encode_path(segment)
+                        );
+
+                        Request::delete(&url)
+                            .header(X_AUTH_TOKEN, &token.token)
+                            .body(AsyncBody::Empty)
+                            .map_err(new_request_build_error)
+                    })
+                    .await?;
+
+                match resp.status() {
+                    StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
+                        resp.into_body().consume().await?
+                    }
+                    _ => return Err(parse_error(resp).await?),
+                }
+            }
+
+            match segments.last() {
+                Some(last) if !done => marker = last.clone(),
+                _ => break,
+            }
+        }
+
+        Ok(RpAbortMultipart::default())
+    }
+}
+
+impl Backend {
+    /// Build url of object in container, `path` is the relative path.
+    fn object_url(&self, token: &Token, container: &str, path: &str) -> String {
+        let p = build_abs_path(&self.root, path);
+
+        format!(
+            "{}/{}/{}",
+            token.storage_url,
+            percent_encode_path(container),
+            percent_encode_path(&p)
+        )
+    }
+
+    /// Segments will be stored at `<path>/<upload_id>/<part_number>` in
+    /// segment container.
+    fn segment_path(&self, path: &str, upload_id: &str, part_number: usize) -> String {
+        format!(
+            "{}/{}/{:08}",
+            build_abs_path(&self.root, path),
+            upload_id,
+            part_number
+        )
+    }
+
+    /// Send request built by `f`, we will authenticate again and resend
+    /// it once if the token has been expired.
+    async fn send(
+        &self,
+        f: impl Fn(&Token) -> Result<Request<AsyncBody>>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let token = self.auth.token().await?;
+        let resp = self.client.send_async(f(&token)?).await?;
+
+        if resp.status() != StatusCode::UNAUTHORIZED || !self.auth.invalidate() {
+            return Ok(resp);
+        }
+        resp.into_body().consume().await?;
+
+        debug!("swift token is expired, authenticate again");
+        let token = self.auth.token().await?;
+        self.client.send_async(f(&token)?).await
+    }
+
+    /// Send request that can't be sent again like streaming uploads.
+    ///
+    /// If the token has been expired, it will be invalidated and a
+    /// temporary error will be returned so that users can retry.
+    async fn send_once(
+        &self,
+        f: impl FnOnce(&Token) -> Result<Request<AsyncBody>>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let token = self.auth.token().await?;
+        let resp = self.client.send_async(f(&token)?).await?;
+
+        if resp.status() == StatusCode::UNAUTHORIZED && self.auth.invalidate() {
+            return Err(parse_error(resp)
+                .await?
+                .with_context("service", Scheme::Swift)
+                .set_temporary());
+        }
+
+        Ok(resp)
+    }
+
+    /// List objects in container with given prefix.
+    ///
+    /// `prefix` is the absolute path.
+    pub(crate) async fn swift_list(
+        &self,
+        container: &str,
+        prefix: &str,
+        delimiter: Option<&str>,
+        marker: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        self.send(|token| {
+            let mut url = format!(
+                "{}/{}?format=json&limit={}",
+                token.storage_url,
+                percent_encode_path(container),
+                DirStream::LIMIT
+            );
+            if let Some(delimiter) = delimiter {
+                write!(url, "&delimiter={delimiter}").expect("write into string must succeed");
+            }
+            if !prefix.is_empty() {
+                write!(url, "&prefix={}", percent_encode_path(prefix))
+                    .expect("write into string must succeed");
+            }
+            if !marker.is_empty() {
+                write!(url, "&marker={}", percent_encode_path(marker))
+                    .expect("write into string must succeed");
+            }
+
+            Request::get(&url)
+                .header(X_AUTH_TOKEN, &token.token)
+                .body(AsyncBody::Empty)
+                .map_err(new_request_build_error)
+        })
+        .await
+    }
+
+    pub(crate) fn container(&self) -> &str {
+        &self.container
+    }
+}
+
+/// Segment in static large object manifest.
+#[derive(Default, Debug, Serialize)]
+struct ManifestSegment {
+    path: String,
+    etag: String,
+}
+
+/// Result of delete with `multipart-manifest=delete`.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct BulkDeleteOutput {
+    #[serde(rename = "Response Status")]
+    response_status: String,
+    #[serde(rename = "Errors")]
+    errors: Vec<Vec<String>>,
+}
+
+impl BulkDeleteOutput {
+    fn into_result(self, path: &str) -> Result<()> {
+        let ok = self.response_status.starts_with('2') || self.response_status.starts_with("404");
+        if ok && self.errors.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::new(ErrorKind::Unexpected, "delete object failed")
+            .with_operation("swift::delete")
+            .with_context("path", path)
+            .with_context("status", self.response_status)
+            .with_context("errors", format!("{:?}", self.errors)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_manifest() {
+        let manifest = vec![
+            ManifestSegment {
+                path: "/test_segments/dir/file/upload/00000001".to_string(),
+                etag: "0f343b0931126a20f133d67c2b018a3b".to_string(),
+            },
+            ManifestSegment {
+                path: "/test_segments/dir/file/upload/00000002".to_string(),
+                etag: "8b1a9953c4611296a827abf8c47804d7".to_string(),
+            },
+        ];
+
+        let actual = serde_json::to_string(&manifest).expect("must success");
+        assert_eq!(
+            actual,
+            r#"[{"path":"/test_segments/dir/file/upload/00000001","etag":"0f343b0931126a20f133d67c2b018a3b"},{"path":"/test_segments/dir/file/upload/00000002","etag":"8b1a9953c4611296a827abf8c47804d7"}]"#
+        );
+    }
+
+    #[test]
+    fn test_parse_bulk_delete_output() {
+        let cases = vec![
+            (
+                "deleted",
+                r#"{"Number Not Found": 0, "Response Status": "200 OK", "Errors": [], "Number Deleted": 3, "Response Body": ""}"#,
+                true,
+            ),
+            (
+                "not found",
+                r#"{"Number Not Found": 1, "Response Status": "404 Not Found", "Errors": [], "Number Deleted": 0, "Response Body": ""}"#,
+                true,
+            ),
+            (
+                "failed",
+                r#"{"Number Not Found": 0, "Response Status": "400 Bad Request", "Errors": [["/test_segments/a", "409 Conflict"]], "Number Deleted": 0, "Response Body": ""}"#,
+                false,
+            ),
+        ];
+
+        for (name, input, ok) in cases {
+            let out: BulkDeleteOutput = serde_json::from_str(input).expect("must success");
+            assert_eq!(out.into_result("path").is_ok(), ok, "{name}");
+        }
+    }
+}
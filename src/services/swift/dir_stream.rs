@@ -0,0 +1,229 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use super::backend::Backend;
+use super::error::parse_error;
+use super::error::parse_json_deserialize_error;
+use crate::raw::*;
+use crate::*;
+
+pub struct DirStream {
+    backend: Arc<Backend>,
+    root: String,
+    path: String,
+    recursive: bool,
+
+    marker: String,
+    done: bool,
+}
+
+impl DirStream {
+    /// Max objects returned in one page.
+    pub const LIMIT: usize = 1000;
+
+    pub fn new(backend: Arc<Backend>, root: &str, path: &str, recursive: bool) -> Self {
+        Self {
+            backend,
+            root: root.to_string(),
+            path: path.to_string(),
+            recursive,
+            marker: "".to_string(),
+            done: false,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectPage for DirStream {
+    async fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let prefix = build_abs_path(&self.root, &self.path);
+        let resp = self
+            .backend
+            .swift_list(
+                self.backend.container(),
+                &prefix,
+                if self.recursive { None } else { Some("/") },
+                &self.marker,
+            )
+            .await?;
+
+        let output: Vec<ListOutput> = match resp.status() {
+            http::StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+                serde_json::from_slice(&bs).map_err(parse_json_deserialize_error)?
+            }
+            // Swift returns `204 No Content` for empty listing.
+            http::StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+                vec![]
+            }
+            _ => return Err(parse_error(resp).await?),
+        };
+
+        // Swift doesn't tell us whether this list is done, we will take
+        // it as done if the returning entries are less than limit.
+        self.done = output.len() < Self::LIMIT;
+        if let Some(last) = output.last() {
+            self.marker = last.name().to_string();
+        }
+
+        let mut entries = Vec::with_capacity(output.len());
+        for v in output {
+            let de = match v {
+                ListOutput::Subdir { subdir } => ObjectEntry::new(
+                    &build_rel_path(&self.root, &subdir),
+                    ObjectMetadata::new(ObjectMode::DIR).with_complete(),
+                ),
+                ListOutput::Object {
+                    name,
+                    bytes,
+                    hash,
+                    content_type,
+                    last_modified,
+                } => {
+                    // Skip the dir itself, and dir markers while listing
+                    // recursively.
+                    if name == prefix || (self.recursive && name.ends_with('/')) {
+                        continue;
+                    }
+
+                    let mode = if name.ends_with('/') {
+                        ObjectMode::DIR
+                    } else {
+                        ObjectMode::FILE
+                    };
+
+                    let mut meta = ObjectMetadata::new(mode);
+                    meta.set_content_length(bytes);
+                    if !hash.is_empty() {
+                        meta.set_etag(&format!("\"{hash}\""));
+                    }
+                    if !content_type.is_empty() {
+                        meta.set_content_type(&content_type);
+                    }
+                    if let Some(v) = last_modified {
+                        meta.set_last_modified(parse_last_modified(&v)?);
+                    }
+
+                    ObjectEntry::new(&build_rel_path(&self.root, &name), meta)
+                }
+            };
+
+            entries.push(de);
+        }
+
+        Ok(Some(entries))
+    }
+}
+
+/// Swift returns last modified like `2016-01-11T08:55:29.448880` which is
+/// UTC without time offset.
+fn parse_last_modified(v: &str) -> Result<OffsetDateTime> {
+    let s = if v.ends_with('Z') {
+        v.to_string()
+    } else {
+        format!("{v}Z")
+    };
+
+    OffsetDateTime::parse(&s, &Rfc3339).map_err(|e| {
+        Error::new(ErrorKind::Unexpected, "parse last modified for swift")
+            .with_context("value", v)
+            .set_source(e)
+    })
+}
+
+/// Entry in swift container listing, which could be a prefix if delimiter
+/// has been set.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ListOutput {
+    Subdir {
+        subdir: String,
+    },
+    Object {
+        name: String,
+        #[serde(default)]
+        bytes: u64,
+        #[serde(default)]
+        hash: String,
+        #[serde(default)]
+        content_type: String,
+        #[serde(default)]
+        last_modified: Option<String>,
+    },
+}
+
+impl ListOutput {
+    fn name(&self) -> &str {
+        match self {
+            ListOutput::Subdir { subdir } => subdir,
+            ListOutput::Object { name, .. } => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list_output() {
+        let bs = r#"[
+    {"subdir": "dir/sub/"},
+    {
+        "hash": "451e372e48e0f6b1114fa0724aa79fa1",
+        "last_modified": "2014-01-15T16:41:49.390270",
+        "bytes": 14,
+        "name": "dir/file",
+        "content_type": "application/octet-stream"
+    }
+]"#;
+
+        let out: Vec<ListOutput> = serde_json::from_str(bs).expect("must success");
+        assert_eq!(
+            out,
+            vec![
+                ListOutput::Subdir {
+                    subdir: "dir/sub/".to_string()
+                },
+                ListOutput::Object {
+                    name: "dir/file".to_string(),
+                    bytes: 14,
+                    hash: "451e372e48e0f6b1114fa0724aa79fa1".to_string(),
+                    content_type: "application/octet-stream".to_string(),
+                    last_modified: Some("2014-01-15T16:41:49.390270".to_string()),
+                }
+            ]
+        );
+        assert_eq!(out[0].name(), "dir/sub/");
+        assert_eq!(out[1].name(), "dir/file");
+    }
+
+    #[test]
+    fn test_parse_last_modified() {
+        let t = parse_last_modified("2014-01-15T16:41:49.390270").expect("must success");
+        assert_eq!(t.unix_timestamp(), 1389804109);
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OpenStack Swift services support.
+//!
+//! # Configuration
+//!
+//! - `root`: Set the work directory for backend
+//! - `container`: Set the container name for backend
+//! - `segment_container`: Set the container to store large object segments, default to `<container>_segments`
+//! - `endpoint`: Set the keystone v3 endpoint like `https://keystone.example.com:5000/v3`
+//! - `region`: Set the region to pick object-store endpoint from service catalog
+//! - `username`: Set the username for keystone authentication
+//! - `password`: Set the password for keystone authentication
+//! - `user_domain`: Set the domain of user, default to `Default`
+//! - `project`: Set the project to scope the token
+//! - `project_domain`: Set the domain of project, default to `Default`
+//! - `storage_url`: Set the storage url instead of the one in service catalog
+//! - `token`: Set a pre-authenticated token, requires `storage_url`
+//!
+//! You can refer to [`Builder`]'s docs for more information
+//!
+//! # Authentication
+//!
+//! The token returned by keystone will be cached and refreshed before it
+//! has been expired. If swift returns `401 Unauthorized`, we will
+//! authenticate again and resend the request. Streaming uploads can't be
+//! resent, the token will be invalidated and a temporary error will be
+//! returned instead so that users can retry the upload.
+//!
+//! # Large Objects
+//!
+//! Multipart uploads are implemented via static large objects: every part
+//! will be uploaded as a segment object into `segment_container` and the
+//! manifest will be created while completing. Deleting the object will
+//! remove its segments too.
+//!
+//! # Environment
+//!
+//! - `OPENDAL_SWIFT_CONTAINER`    required
+//! - `OPENDAL_SWIFT_ENDPOINT`  required if token not set
+//! - `OPENDAL_SWIFT_USERNAME`  required if token not set
+//! - `OPENDAL_SWIFT_PASSWORD`  required if token not set
+//! - `OPENDAL_SWIFT_PROJECT`  required if token not set
+//! - `OPENDAL_SWIFT_REGION`  optional
+//! - `OPENDAL_SWIFT_STORAGE_URL`  optional
+//! - `OPENDAL_SWIFT_TOKEN`  optional
+//!
+//! # Example
+//!
+//! ## Initiate via environment variables
+//!
+//! Set environment correctly:
+//!
+//! ```shell
+//! export OPENDAL_SWIFT_CONTAINER=test
+//! export OPENDAL_SWIFT_ROOT=/path/to/dir/
+//! export OPENDAL_SWIFT_ENDPOINT=https://keystone.example.com:5000/v3
+//! export OPENDAL_SWIFT_USERNAME=demo
+//! export OPENDAL_SWIFT_PASSWORD=password
+//! export OPENDAL_SWIFT_PROJECT=demo
+//! ```
+//! ```no_run
+//! use anyhow::Result;
+//! use opendal::Object;
+//! use opendal::Operator;
+//! use opendal::Scheme;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let op: Operator = Operator::from_env(Scheme::Swift)?;
+//!
+//!     // create an object handler to start operation on it.
+//!     let _op: Object = op.object("test_file");
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ## Via Builder
+//!
+//! ```no_run
+//! use anyhow::Result;
+//! use opendal::services::swift;
+//! use opendal::Object;
+//! use opendal::Operator;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     // create backend builder
+//!     let mut builder = swift::Builder::default();
+//!
+//!     // set the storage container for OpenDAL
+//!     builder.container("test");
+//!     // Set the keystone endpoint and credentials.
+//!     builder.endpoint("https://keystone.example.com:5000/v3");
+//!     builder.username("demo");
+//!     builder.password("password");
+//!     builder.project("demo");
+//!
+//!     let op: Operator = Operator::new(builder.build()?);
+//!
+//!     // Create an object handle to start operation on object.
+//!     let _: Object = op.object("test_file");
+//!
+//!     Ok(())
+//! }
+//! ```
+
+mod backend;
+pub use backend::Builder;
+
+mod auth;
+mod dir_stream;
+pub(super) mod error;
@@ -0,0 +1,93 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use super::backend::Backend;
+use super::error::parse_error;
+use super::multistatus::parse_multistatus;
+use crate::raw::*;
+use crate::Result;
+
+pub struct DirStream {
+    backend: Arc<Backend>,
+    root: String,
+    path: String,
+    consumed: bool,
+}
+
+impl DirStream {
+    pub fn new(backend: Arc<Backend>, root: &str, path: &str) -> Self {
+        Self {
+            backend,
+            root: root.to_string(),
+            path: path.to_string(),
+            consumed: false,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectPage for DirStream {
+    async fn next_page(&mut self) -> Result<Option<Vec<ObjectEntry>>> {
+        if self.consumed {
+            return Ok(None);
+        }
+
+        let resp = self.backend.webdav_propfind(&self.path, "1").await?;
+
+        // Mark dir stream has been consumed.
+        self.consumed = true;
+
+        match resp.status() {
+            StatusCode::MULTI_STATUS => {}
+            // List a not exist dir should return empty.
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                return Ok(None);
+            }
+            _ => return Err(parse_error(resp).await?),
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        let ms = parse_multistatus(bs)?;
+
+        let dir = build_rooted_abs_path(&self.root, &self.path);
+
+        let mut entries = Vec::with_capacity(ms.response.len());
+        for res in ms.response {
+            let meta = res.metadata()?;
+
+            let mut path = self.backend.href_to_abs_path(&res.href);
+            if meta.mode().is_dir() && !path.ends_with('/') {
+                path.push('/');
+            }
+
+            // `Depth: 1` returns the dir itself too, skip it.
+            if path == dir || !path.starts_with(&self.root) {
+                continue;
+            }
+
+            entries.push(ObjectEntry::new(
+                &build_rel_path(&self.root, &path),
+                meta.with_complete(),
+            ))
+        }
+
+        Ok(Some(entries))
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use http::Response;
+use http::StatusCode;
+
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Parse error response into Error.
+pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    let (kind, retryable) = match parts.status {
+        StatusCode::NOT_FOUND => (ErrorKind::ObjectNotFound, false),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            (ErrorKind::ObjectPermissionDenied, false)
+        }
+        StatusCode::NOT_MODIFIED | StatusCode::PRECONDITION_FAILED => {
+            (ErrorKind::ConditionNotMatch, false)
+        }
+        // The resource is locked by others, retry could succeed after
+        // the lock has been released.
+        StatusCode::LOCKED => (ErrorKind::ObjectPermissionDenied, true),
+        // The server is out of space, retry won't help.
+        StatusCode::INSUFFICIENT_STORAGE => (ErrorKind::Unexpected, false),
+        StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+        _ => (ErrorKind::Unexpected, false),
+    };
+
+    let mut err = Error::new(kind, &String::from_utf8_lossy(&bs))
+        .with_context("response", format!("{:?}", parts));
+    err = with_current_metadata(err, &parts.headers);
+
+    if retryable {
+        err = err.set_temporary();
+    }
+
+    Ok(err)
+}
+
+pub fn parse_xml_deserialize_error(e: quick_xml::DeError) -> Error {
+    Error::new(ErrorKind::Unexpected, "deserialize xml").set_source(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::io::Cursor;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parse_error_status() {
+        for (status, kind, temporary) in [
+            (StatusCode::NOT_FOUND, ErrorKind::ObjectNotFound, false),
+            (
+                StatusCode::UNAUTHORIZED,
+                ErrorKind::ObjectPermissionDenied,
+                false,
+            ),
+            (StatusCode::LOCKED, ErrorKind::ObjectPermissionDenied, true),
+            (
+                StatusCode::INSUFFICIENT_STORAGE,
+                ErrorKind::Unexpected,
+                false,
+            ),
+            (StatusCode::SERVICE_UNAVAILABLE, ErrorKind::Unexpected, true),
+        ] {
+            let body = IncomingAsyncBody::new(Box::new(Cursor::new(Vec::new())));
+            let resp = Response::builder()
+                .status(status)
+                .body(body)
+                .expect("response must be valid");
+
+            let err = parse_error(resp).await.expect("parse must succeed");
+            assert_eq!(err.kind(), kind, "{status}");
+            assert_eq!(err.is_temporary(), temporary, "{status}");
+        }
+    }
+}
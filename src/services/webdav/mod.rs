@@ -0,0 +1,103 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! WebDAV services support.
+//!
+//! # Configuration
+//!
+//! - `endpoint`: Set the endpoint of the webdav server like `https://dav.example.com/remote.php/dav/files/admin`
+//! - `root`: Set the work directory for backend
+//! - `username`: Set the username for basic authentication
+//! - `password`: Set the password for basic authentication
+//! - `token`: Set the bearer token, will be used instead of `username` and `password`
+//!
+//! You can refer to [`Builder`]'s docs for more information
+//!
+//! # Operations
+//!
+//! - Read, write and delete are served by `GET`, `PUT` and `DELETE`.
+//! - Stat and list are served by `PROPFIND` with `Depth: 0` and `Depth: 1`.
+//! - Directories are created by `MKCOL`, missing parents will be created
+//!   before writing.
+//!
+//! # Environment
+//!
+//! - `OPENDAL_WEBDAV_ENDPOINT`  required
+//! - `OPENDAL_WEBDAV_ROOT`  optional
+//! - `OPENDAL_WEBDAV_USERNAME`  optional
+//! - `OPENDAL_WEBDAV_PASSWORD`  optional
+//! - `OPENDAL_WEBDAV_TOKEN`  optional
+//!
+//! # Example
+//!
+//! ## Initiate via environment variables
+//!
+//! Set environment correctly:
+//!
+//! ```shell
+//! export OPENDAL_WEBDAV_ENDPOINT=https://dav.example.com
+//! export OPENDAL_WEBDAV_ROOT=/path/to/dir/
+//! export OPENDAL_WEBDAV_USERNAME=admin
+//! export OPENDAL_WEBDAV_PASSWORD=password
+//! ```
+//! ```no_run
+//! use anyhow::Result;
+//! use opendal::Object;
+//! use opendal::Operator;
+//! use opendal::Scheme;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let op: Operator = Operator::from_env(Scheme::Webdav)?;
+//!
+//!     // create an object handler to start operation on it.
+//!     let _op: Object = op.object("test_file");
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ## Via Builder
+//!
+//! ```no_run
+//! use anyhow::Result;
+//! use opendal::services::webdav;
+//! use opendal::Object;
+//! use opendal::Operator;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     // create backend builder
+//!     let mut builder = webdav::Builder::default();
+//!
+//!     // set the endpoint and credentials for OpenDAL
+//!     builder.endpoint("https://dav.example.com");
+//!     builder.username("admin");
+//!     builder.password("password");
+//!
+//!     let op: Operator = Operator::new(builder.build()?);
+//!
+//!     // Create an object handle to start operation on object.
+//!     let _: Object = op.object("test_file");
+//!
+//!     Ok(())
+//! }
+//! ```
+
+mod backend;
+pub use backend::Builder;
+
+mod dir_stream;
+mod error;
+mod multistatus;
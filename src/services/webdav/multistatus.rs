@@ -0,0 +1,218 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Buf;
+use serde::Deserialize;
+
+use super::error::parse_xml_deserialize_error;
+use crate::raw::*;
+use crate::Error;
+use crate::ErrorKind;
+use crate::ObjectMetadata;
+use crate::ObjectMode;
+use crate::Result;
+
+/// Body of `PROPFIND` requests, only the properties we care about will
+/// be requested.
+pub const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<D:propfind xmlns:D="DAV:">
+  <D:prop>
+    <D:resourcetype/>
+    <D:getcontentlength/>
+    <D:getcontenttype/>
+    <D:getetag/>
+    <D:getlastmodified/>
+  </D:prop>
+</D:propfind>"#;
+
+/// Multistatus is the body returned by `PROPFIND`.
+///
+/// Namespace prefixes are ignored while deserializing, so both `d:href`
+/// (Nextcloud) and `D:href` (Apache mod_dav) will be accepted.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Multistatus {
+    pub response: Vec<MultistatusResponse>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct MultistatusResponse {
+    pub href: String,
+    propstat: Vec<Propstat>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+struct Propstat {
+    prop: Prop,
+    status: String,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+struct Prop {
+    /// Kept as string since servers like Nextcloud will return an empty
+    /// `getcontentlength` for collections.
+    getcontentlength: Option<String>,
+    getcontenttype: Option<String>,
+    getetag: Option<String>,
+    getlastmodified: Option<String>,
+    resourcetype: ResourceType,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+struct ResourceType {
+    collection: Option<()>,
+}
+
+/// Parse the body of `PROPFIND` into [`Multistatus`].
+pub fn parse_multistatus(bs: impl Buf) -> Result<Multistatus> {
+    quick_xml::de::from_reader(bs.reader()).map_err(parse_xml_deserialize_error)
+}
+
+impl MultistatusResponse {
+    /// Properties of this response, only the propstat with `200 OK` will
+    /// be taken into account.
+    fn prop(&self) -> Option<&Prop> {
+        self.propstat
+            .iter()
+            .find(|v| v.status.split_whitespace().nth(1) == Some("200"))
+            .map(|v| &v.prop)
+    }
+
+    /// Whether this response is a collection (directory).
+    pub fn is_dir(&self) -> bool {
+        self.prop()
+            .map(|v| v.resourcetype.collection.is_some())
+            .unwrap_or_else(|| self.href.ends_with('/'))
+    }
+
+    /// Convert this response into object metadata.
+    pub fn metadata(&self) -> Result<ObjectMetadata> {
+        let mut meta = if self.is_dir() {
+            ObjectMetadata::new(ObjectMode::DIR)
+        } else {
+            ObjectMetadata::new(ObjectMode::FILE)
+        };
+
+        let prop = match self.prop() {
+            Some(prop) => prop,
+            None => return Ok(meta),
+        };
+
+        if let Some(v) = prop.getcontentlength.as_deref().map(str::trim) {
+            if !v.is_empty() {
+                meta.set_content_length(v.parse().map_err(|e| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "content length is not a valid integer",
+                    )
+                    .with_context("href", &self.href)
+                    .set_source(e)
+                })?);
+            }
+        }
+        if let Some(v) = prop.getcontenttype.as_deref().filter(|v| !v.is_empty()) {
+            meta.set_content_type(v);
+        }
+        if let Some(v) = prop.getetag.as_deref().filter(|v| !v.is_empty()) {
+            meta.set_etag(v);
+        }
+        if let Some(v) = prop
+            .getlastmodified
+            .as_deref()
+            .and_then(parse_datetime_from_rfc2822)
+        {
+            meta.set_last_modified(v);
+        }
+
+        Ok(meta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nextcloud() {
+        let bs = include_str!("fixtures/nextcloud.xml").as_bytes();
+        let ms = parse_multistatus(bs).expect("must parse");
+
+        assert_eq!(ms.response.len(), 4);
+
+        let dir = &ms.response[0];
+        assert_eq!(dir.href, "/remote.php/dav/files/admin/Documents/");
+        assert!(dir.is_dir());
+        let meta = dir.metadata().expect("must be valid");
+        assert_eq!(meta.mode(), ObjectMode::DIR);
+        assert_eq!(meta.etag(), Some("\"63e20804c9b8a\""));
+        assert_eq!(
+            meta.last_modified(),
+            parse_datetime_from_rfc2822("Tue, 07 Feb 2023 08:12:36 GMT")
+        );
+
+        let file = &ms.response[1];
+        assert_eq!(
+            file.href,
+            "/remote.php/dav/files/admin/Documents/Example.md"
+        );
+        assert!(!file.is_dir());
+        let meta = file.metadata().expect("must be valid");
+        assert_eq!(meta.mode(), ObjectMode::FILE);
+        assert_eq!(meta.content_length(), 1095);
+        assert_eq!(meta.content_type(), Some("text/markdown"));
+
+        let empty = &ms.response[2];
+        assert_eq!(
+            empty.href,
+            "/remote.php/dav/files/admin/Documents/Readme%20Me.txt"
+        );
+        assert_eq!(empty.metadata().expect("must be valid").content_length(), 0);
+
+        assert!(ms.response[3].is_dir());
+    }
+
+    #[test]
+    fn test_parse_apache() {
+        let bs = include_str!("fixtures/apache.xml").as_bytes();
+        let ms = parse_multistatus(bs).expect("must parse");
+
+        assert_eq!(ms.response.len(), 3);
+
+        let dir = &ms.response[0];
+        assert_eq!(dir.href, "/webdav/dir/");
+        let meta = dir.metadata().expect("must be valid");
+        assert_eq!(meta.mode(), ObjectMode::DIR);
+        assert_eq!(meta.etag(), Some("\"1000-5f41757b8ab00\""));
+
+        let file = &ms.response[1];
+        assert_eq!(file.href, "/webdav/dir/hello%20world.txt");
+        let meta = file.metadata().expect("must be valid");
+        assert_eq!(meta.mode(), ObjectMode::FILE);
+        assert_eq!(meta.content_length(), 13);
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(meta.etag(), Some("\"d-5f41759311f40\""));
+        assert_eq!(
+            meta.last_modified(),
+            parse_datetime_from_rfc2822("Tue, 07 Feb 2023 08:13:01 GMT")
+        );
+
+        let sub = &ms.response[2];
+        assert_eq!(sub.href, "/webdav/dir/sub/");
+        assert!(sub.is_dir());
+    }
+}
@@ -13,7 +13,11 @@
 // limitations under the License.
 
 use anyhow::Result;
+use futures::executor::block_on;
+use opendal::ErrorKind;
+use opendal::OpDelete;
 use opendal::Operator;
+use time::Duration;
 
 /// All services should pass this test.
 macro_rules! behavior_base_test {
@@ -49,6 +53,7 @@ macro_rules! behavior_base_tests {
                 $service,
 
                 test_metadata,
+                test_capabilities,
                 test_object_id,
                 test_object_path,
                 test_object_name,
@@ -65,6 +70,38 @@ pub fn test_metadata(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Operations of capabilities that are not declared must return
+/// `Unsupported`, declared capabilities are covered by other behavior tests.
+pub fn test_capabilities(op: Operator) -> Result<()> {
+    let meta = op.metadata();
+    let o = op.object(&uuid::Uuid::new_v4().to_string());
+
+    if !meta.can_presign() {
+        let err = o.presign_read(Duration::hours(1)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+    if !meta.can_blocking() {
+        let err = o.blocking_metadata().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+    if !meta.can_multipart() {
+        let err = block_on(o.create_multipart()).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+    if !meta.can_write() {
+        let err = block_on(o.write("Hello, World!")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+    if !meta.can_versioning() {
+        let err = block_on(o.list_versions()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = block_on(o.delete_with(OpDelete::new().with_version("1"))).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    Ok(())
+}
+
 /// Test object id.
 pub fn test_object_id(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
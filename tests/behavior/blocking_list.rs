@@ -17,6 +17,7 @@ use std::collections::HashMap;
 use anyhow::Result;
 use log::debug;
 use opendal::ObjectMode;
+use opendal::OpList;
 use opendal::Operator;
 
 use super::utils::*;
@@ -37,6 +38,8 @@ macro_rules! behavior_blocking_list_test {
                         #[$meta]
                     )*
                     fn [< $test >]() -> anyhow::Result<()> {
+                        // Blocking API must work without a tokio runtime.
+                        assert!(tokio::runtime::Handle::try_current().is_err());
                         let op = $crate::utils::init_service(opendal::Scheme::$service, true);
                         match op {
                             Some(op) if op.metadata().can_read()
@@ -66,6 +69,7 @@ macro_rules! behavior_blocking_list_tests {
                 $service,
 
                 test_list_dir,
+                test_list_with_start_after,
                 test_list_non_exist_dir,
             );
         )*
@@ -102,6 +106,49 @@ pub fn test_list_dir(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// List with start_after of a continuation token should resume the
+/// listing without skipping or duplicating entries.
+pub fn test_list_with_start_after(op: Operator) -> Result<()> {
+    if !op.metadata().can_list_start_after() {
+        return Ok(());
+    }
+
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    for i in 0..3 {
+        op.object(&format!("{parent}file-{i}"))
+            .blocking_write("Hello, World!")
+            .expect("write must succeed");
+    }
+
+    let expected = op
+        .object(&parent)
+        .blocking_list()?
+        .map(|de| de.map(|v| v.path().to_string()))
+        .collect::<opendal::Result<Vec<_>>>()?;
+    assert_eq!(expected.len(), 3, "all files should be listed");
+
+    let mut ds = op.object(&parent).blocking_list()?;
+    let mut actual = vec![ds.next().expect("entry must exist")?.path().to_string()];
+    let token = ds
+        .continuation_token()
+        .expect("token must exist")
+        .to_string();
+
+    let rest = op
+        .object(&parent)
+        .blocking_list_with(OpList::new().with_start_after(&token))?
+        .map(|de| de.map(|v| v.path().to_string()))
+        .collect::<opendal::Result<Vec<_>>>()?;
+    actual.extend(rest);
+    assert_eq!(actual, expected, "resume after {token} must be the same");
+
+    for path in expected {
+        op.object(&path).blocking_delete()?;
+    }
+    op.object(&parent).blocking_delete()?;
+    Ok(())
+}
+
 /// List non exist dir should return nothing.
 pub fn test_list_non_exist_dir(op: Operator) -> Result<()> {
     let dir = format!("{}/", uuid::Uuid::new_v4());
@@ -34,6 +34,8 @@ macro_rules! behavior_blocking_read_test {
                         #[$meta]
                     )*
                     fn [< $test >]() -> anyhow::Result<()> {
+                        // Blocking API must work without a tokio runtime.
+                        assert!(tokio::runtime::Handle::try_current().is_err());
                         let op = $crate::utils::init_service(opendal::Scheme::$service, true);
                         match op {
                             Some(op) if op.metadata().can_read()
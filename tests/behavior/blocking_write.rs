@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Read;
+
 use anyhow::Result;
 use log::debug;
 use opendal::ErrorKind;
@@ -37,6 +39,8 @@ macro_rules! behavior_blocking_write_test {
                         #[$meta]
                     )*
                     fn [< $test >]() -> anyhow::Result<()> {
+                        // Blocking API must work without a tokio runtime.
+                        assert!(tokio::runtime::Handle::try_current().is_err());
                         let op = $crate::utils::init_service(opendal::Scheme::$service, true);
                         match op {
                             Some(op) if op.metadata().can_read()
@@ -70,6 +74,7 @@ macro_rules! behavior_blocking_write_tests {
                 test_create_file_with_special_chars,
                 test_create_dir,
                 test_create_dir_exising,
+                test_create_nested_dir,
                 test_write,
                 test_write_with_dir_path,
                 test_write_with_special_chars,
@@ -78,10 +83,12 @@ macro_rules! behavior_blocking_write_tests {
                 test_stat_with_special_chars,
                 test_stat_not_exist,
                 test_read_full,
+                test_reader,
                 test_read_range,
                 test_read_large_range,
                 test_read_not_exist,
                 test_delete,
+                test_delete_not_existing,
             );
         )*
     };
@@ -180,6 +187,31 @@ pub fn test_create_dir_exising(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Create nested dir whose parents don't exist should succeed.
+pub fn test_create_nested_dir(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    let path = format!("{parent}a/b/");
+
+    let o = op.object(&path);
+
+    o.blocking_create()?;
+
+    let meta = o.blocking_metadata()?;
+    assert_eq!(meta.mode(), ObjectMode::DIR);
+
+    // Create again should be a no-op.
+    o.blocking_create()?;
+    let meta = o.blocking_metadata()?;
+    assert_eq!(meta.mode(), ObjectMode::DIR);
+
+    for p in [path.clone(), format!("{parent}a/"), parent] {
+        op.object(&p)
+            .blocking_delete()
+            .expect("delete must succeed");
+    }
+    Ok(())
+}
+
 /// Write a single file and test with stat.
 pub fn test_write(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -324,6 +356,33 @@ pub fn test_read_full(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read via blocking reader should match.
+pub fn test_reader(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    op.object(&path)
+        .blocking_write(content.clone())
+        .expect("write must succeed");
+
+    let mut r = op.object(&path).blocking_reader()?;
+    assert_eq!(size as u64, r.content_length(), "reader content length");
+    let mut bs = Vec::new();
+    r.read_to_end(&mut bs)?;
+    assert_eq!(size, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.object(&path)
+        .blocking_delete()
+        .expect("delete must succeed");
+    Ok(())
+}
+
 /// Read range content should match.
 pub fn test_read_range(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -413,3 +472,12 @@ pub fn test_delete(op: Operator) -> Result<()> {
 
     Ok(())
 }
+
+// Delete not existing file should also succeed.
+pub fn test_delete_not_existing(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+
+    op.object(&path).blocking_delete()?;
+
+    Ok(())
+}
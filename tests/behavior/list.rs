@@ -13,15 +13,31 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
 use anyhow::Result;
+use async_trait::async_trait;
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
 use futures::TryStreamExt;
 use log::debug;
+use opendal::raw::Accessor;
+use opendal::raw::ObjectEntry;
+use opendal::raw::ObjectPage;
+use opendal::raw::ObjectPager;
+use opendal::raw::RpList;
+use opendal::raw::RpStat;
 use opendal::ErrorKind;
+use opendal::Layer;
+use opendal::ObjectMetakey;
 use opendal::ObjectMode;
+use opendal::OpList;
+use opendal::OpStat;
 use opendal::Operator;
+use opendal::Scheme;
 
 use super::utils::*;
 
@@ -69,7 +85,10 @@ macro_rules! behavior_list_tests {
                 test_check,
                 test_list_dir,
                 test_list_rich_dir,
+                test_list_with_limit,
+                test_list_with_start_after,
                 test_list_dir_metadata_cache,
+                test_list_dir_metadata_with,
                 test_list_empty_dir,
                 test_list_non_exist_dir,
                 test_list_sub_dir,
@@ -78,6 +97,7 @@ macro_rules! behavior_list_tests {
                 test_walk_top_down,
                 test_walk_top_down_within_empty_dir,
                 test_walk_bottom_up,
+                test_scan,
                 test_remove_all,
             );
         )*
@@ -122,6 +142,120 @@ pub async fn test_list_dir(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// List with limit should still return all objects in the same order.
+pub async fn test_list_with_limit(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    op.object(&parent).create().await?;
+
+    for i in 0..5 {
+        op.object(&format!("{parent}file-{i}"))
+            .write("Hello, World!")
+            .await
+            .expect("write must succeed");
+    }
+
+    let expected: Vec<String> = op
+        .object(&parent)
+        .list()
+        .await?
+        .map_ok(|de| de.path().to_string())
+        .try_collect()
+        .await?;
+    assert_eq!(expected.len(), 5, "all files should be listed");
+
+    let actual: Vec<String> = op
+        .object(&parent)
+        .list_with(OpList::new().with_limit(2))
+        .await?
+        .map_ok(|de| de.path().to_string())
+        .try_collect()
+        .await?;
+    assert_eq!(actual, expected, "list with limit must return the same");
+
+    op.batch().remove_all(&parent).await?;
+    Ok(())
+}
+
+/// List with start_after of a continuation token should resume the
+/// listing without skipping or duplicating entries.
+pub async fn test_list_with_start_after(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    op.object(&parent).create().await?;
+
+    if !op.metadata().can_list_start_after() {
+        let res = op
+            .object(&parent)
+            .list_with(OpList::new().with_start_after(&format!("{parent}file-0")))
+            .await;
+        assert_eq!(
+            res.err().expect("list with start_after must fail").kind(),
+            ErrorKind::Unsupported
+        );
+
+        op.object(&parent).delete().await?;
+        return Ok(());
+    }
+
+    for i in 0..5 {
+        op.object(&format!("{parent}file-{i}"))
+            .write("Hello, World!")
+            .await
+            .expect("write must succeed");
+    }
+    op.object(&format!("{parent}sub/file"))
+        .write("Hello, World!")
+        .await
+        .expect("write must succeed");
+
+    let expected: Vec<String> = op
+        .object(&parent)
+        .list()
+        .await?
+        .map_ok(|de| de.path().to_string())
+        .try_collect()
+        .await?;
+    assert_eq!(expected.len(), 6, "all entries should be listed");
+
+    for n in 1..=expected.len() {
+        let mut ds = op
+            .object(&parent)
+            .list_with(OpList::new().with_limit(2))
+            .await?;
+        let mut actual = vec![];
+        for _ in 0..n {
+            let de = ds.try_next().await?.expect("entry must exist");
+            actual.push(de.path().to_string());
+        }
+        let token = ds
+            .continuation_token()
+            .expect("token must exist")
+            .to_string();
+        assert_eq!(token, actual[n - 1]);
+        drop(ds);
+
+        let rest: Vec<String> = op
+            .object(&parent)
+            .list_with(OpList::new().with_limit(2).with_start_after(&token))
+            .await?
+            .map_ok(|de| de.path().to_string())
+            .try_collect()
+            .await?;
+        actual.extend(rest);
+
+        assert_eq!(actual, expected, "resume after {token} must be the same");
+    }
+
+    // Token from another dir should be rejected.
+    let res = op
+        .object(&format!("{parent}sub/"))
+        .list_with(OpList::new().with_start_after(&expected[0]))
+        .await;
+    assert!(res.is_err(), "token of different prefix must be rejected");
+
+    op.batch().remove_all(&parent).await?;
+    Ok(())
+}
+
 /// listing a directory, which contains more objects than a single page can take.
 pub async fn test_list_rich_dir(op: Operator) -> Result<()> {
     // Create dir first to avoid concurrent create parent.
@@ -185,6 +319,119 @@ pub async fn test_list_dir_metadata_cache(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// CountingLayer counts `stat` requests and non-empty `list` pages sent
+/// to the underlying service.
+#[derive(Debug, Clone, Default)]
+struct CountingLayer {
+    stats: Arc<AtomicUsize>,
+    pages: Arc<AtomicUsize>,
+}
+
+impl Layer for CountingLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        Arc::new(CountingAccessor {
+            inner,
+            layer: self.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct CountingAccessor {
+    inner: Arc<dyn Accessor>,
+    layer: CountingLayer,
+}
+
+#[async_trait]
+impl Accessor for CountingAccessor {
+    fn inner(&self) -> Option<Arc<dyn Accessor>> {
+        Some(self.inner.clone())
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> opendal::Result<RpStat> {
+        self.layer.stats.fetch_add(1, Ordering::SeqCst);
+        self.inner.stat(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> opendal::Result<(RpList, ObjectPager)> {
+        let (rp, pager) = self.inner.list(path, args).await?;
+        Ok((
+            rp,
+            Box::new(CountingPager {
+                inner: pager,
+                pages: self.layer.pages.clone(),
+            }),
+        ))
+    }
+}
+
+struct CountingPager {
+    inner: ObjectPager,
+    pages: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl ObjectPage for CountingPager {
+    async fn next_page(&mut self) -> opendal::Result<Option<Vec<ObjectEntry>>> {
+        let page = self.inner.next_page().await?;
+        if page.is_some() {
+            self.pages.fetch_add(1, Ordering::SeqCst);
+        }
+        Ok(page)
+    }
+}
+
+/// Metadata carried by list should be used without extra stat.
+pub async fn test_list_dir_metadata_with(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+    let (content, size) = gen_bytes();
+    let files = 3;
+
+    for i in 0..files {
+        op.object(&format!("{parent}/file-{i}"))
+            .write(content.clone())
+            .await
+            .expect("write must succeed");
+    }
+
+    let counting = CountingLayer::default();
+    let counted = op.clone().layer(counting.clone());
+
+    let keys = ObjectMetakey::Mode | ObjectMetakey::ContentLength;
+    let mut entries = 0;
+    let mut missing = 0;
+    let mut obs = counted.object(&format!("{parent}/")).list().await?;
+    while let Some(o) = obs.try_next().await? {
+        entries += 1;
+
+        let cached = o.metakeys();
+        if !cached.contains(ObjectMetakey::Complete) && !cached.contains(keys) {
+            missing += 1;
+        }
+
+        let meta = o.metadata_with(keys).await?;
+        assert_eq!(meta.mode(), ObjectMode::FILE);
+        assert_eq!(meta.content_length(), size as u64);
+    }
+
+    assert_eq!(entries, files);
+    assert_eq!(counting.pages.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        counting.stats.load(Ordering::SeqCst),
+        missing,
+        "only objects without cached metadata need stat"
+    );
+    if matches!(
+        op.metadata().scheme(),
+        Scheme::Fs | Scheme::S3 | Scheme::Gcs | Scheme::Azblob | Scheme::Oss | Scheme::Swift
+    ) {
+        assert_eq!(missing, 0, "list must carry mode and content length");
+    }
+
+    op.batch().remove_all(&format!("{parent}/")).await?;
+    Ok(())
+}
+
 /// List empty dir should return nothing.
 pub async fn test_list_empty_dir(op: Operator) -> Result<()> {
     let dir = format!("{}/", uuid::Uuid::new_v4());
@@ -348,6 +595,41 @@ pub async fn test_walk_top_down(op: Operator) -> Result<()> {
     Ok(())
 }
 
+// Scan should output all files under the dir without dirs.
+pub async fn test_scan(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+    let dirs = ["", "x/", "x/y/", "x/y/z/"];
+    let files = ["a", "x/b", "x/y/c", "x/y/z/d"];
+
+    for dir in dirs.iter() {
+        op.object(&format!("{parent}/{dir}")).create().await?;
+    }
+    for file in files.iter() {
+        op.object(&format!("{parent}/{file}")).write("scan").await?;
+    }
+
+    let actual = op
+        .object(&format!("{parent}/"))
+        .scan()
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .map(|v| v.path().to_string())
+        .collect::<HashSet<_>>();
+
+    debug!("scan: {:?}", actual);
+
+    let expected = files
+        .iter()
+        .map(|v| format!("{parent}/{v}"))
+        .collect::<HashSet<_>>();
+    assert_eq!(actual, expected);
+
+    op.batch().remove_all(&format!("{parent}/")).await?;
+    Ok(())
+}
+
 // Walk top down within empty dir should output as expected
 pub async fn test_walk_top_down_within_empty_dir(op: Operator) -> Result<()> {
     let mut expected = vec!["x/", "x/x/x/x/"];
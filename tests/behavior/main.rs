@@ -67,10 +67,14 @@ macro_rules! behavior_tests {
     };
 }
 
+behavior_tests!(Alluxio);
 behavior_tests!(Azblob);
 behavior_tests!(Azdfs);
+behavior_tests!(B2);
+behavior_tests!(Cos);
 behavior_tests!(Fs);
 cfg_if::cfg_if! { if #[cfg(feature = "services-ftp")] { behavior_tests!(Ftp); }}
+cfg_if::cfg_if! { if #[cfg(feature = "services-memcached")] { behavior_tests!(Memcached); }}
 behavior_tests!(Memory);
 cfg_if::cfg_if! { if #[cfg(feature = "services-moka")] { behavior_tests!(Moka); }}
 behavior_tests!(Gcs);
@@ -79,7 +83,10 @@ behavior_tests!(Ipmfs);
 cfg_if::cfg_if! { if #[cfg(feature = "services-hdfs")] { behavior_tests!(Hdfs); }}
 cfg_if::cfg_if! { if #[cfg(feature = "services-http")] { behavior_tests!(Http); }}
 behavior_tests!(Obs);
+cfg_if::cfg_if! { if #[cfg(feature = "services-postgresql")] { behavior_tests!(Postgresql); }}
 cfg_if::cfg_if! { if #[cfg(feature = "services-redis")] { behavior_tests!(Redis); }}
 cfg_if::cfg_if! { if #[cfg(feature = "services-rocksdb")] { behavior_tests!(Rocksdb); }}
 behavior_tests!(S3);
+behavior_tests!(Swift);
 behavior_tests!(Oss);
+behavior_tests!(Webdav);
@@ -18,6 +18,7 @@ use anyhow::Result;
 use http::header;
 use log::debug;
 use opendal::raw;
+use opendal::OpWrite;
 use opendal::Operator;
 use reqwest::Url;
 use sha2::Digest;
@@ -68,6 +69,7 @@ macro_rules! behavior_presign_tests {
                 $service,
 
                 test_presign_write,
+                test_presign_write_with,
                 test_presign_read,
                 test_presign_stat,
             );
@@ -115,6 +117,65 @@ pub async fn test_presign_write(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Presign write with content type and length should only accept the
+/// signed values.
+pub async fn test_presign_write_with(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    let signed_req = op.object(&path).presign_write_with(
+        OpWrite::new(size as u64).with_content_type("text/plain"),
+        Duration::hours(1),
+    )?;
+    debug!("Generated request: {signed_req:?}");
+    assert_eq!(
+        signed_req.header()[header::CONTENT_LENGTH],
+        size.to_string().as_str()
+    );
+    assert_eq!(signed_req.header()[header::CONTENT_TYPE], "text/plain");
+
+    let client = reqwest::Client::new();
+    let build = |content: Vec<u8>| {
+        let mut req = client.request(
+            signed_req.method().clone(),
+            Url::from_str(&signed_req.uri().to_string()).expect("must be valid url"),
+        );
+        for (k, v) in signed_req.header() {
+            req = req.header(k, v);
+        }
+        req.body(reqwest::Body::from(content))
+    };
+
+    // Lying about content type must be rejected.
+    let resp = build(content.clone())
+        .header(header::CONTENT_TYPE, "image/png")
+        .send()
+        .await
+        .expect("send request must succeed");
+    assert!(!resp.status().is_success(), "{}", resp.status());
+
+    let resp = build(content)
+        .send()
+        .await
+        .expect("send request must succeed");
+    assert!(resp.status().is_success(), "{}", resp.status());
+
+    let meta = op
+        .object(&path)
+        .metadata()
+        .await
+        .expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+    assert_eq!(meta.content_type(), Some("text/plain"));
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
 pub async fn test_presign_stat(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
     debug!("Generate a random file: {}", &path);
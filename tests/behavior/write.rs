@@ -13,12 +13,19 @@
 // limitations under the License.
 
 use anyhow::Result;
+use std::io::SeekFrom;
+
 use futures::io::Cursor;
+use futures::AsyncReadExt;
+use futures::AsyncSeekExt;
 use log::debug;
 use log::warn;
 use opendal::ErrorKind;
 use opendal::ObjectMode;
+use opendal::OpRead;
+use opendal::OpWrite;
 use opendal::Operator;
+use opendal::Scheme;
 use sha2::Digest;
 use sha2::Sha256;
 
@@ -69,21 +76,37 @@ macro_rules! behavior_write_tests {
                 test_create_file_with_special_chars,
                 test_create_dir,
                 test_create_dir_exising,
+                test_create_nested_dir,
                 test_write,
                 test_write_with_dir_path,
                 test_write_with_special_chars,
+                test_write_from_size_mismatch,
+                test_writer_append,
+                test_writer_close_twice,
+                test_writer_tokio_copy,
+                test_writer_copy_large,
                 test_stat,
                 test_stat_dir,
                 test_stat_with_special_chars,
                 test_stat_not_cleaned_path,
                 test_stat_not_exist,
                 test_stat_root,
+                test_stat_etag_changed,
                 test_read_full,
+                test_read_with_if_match,
+                test_write_with_if_not_exists,
+                test_read_with_if_none_match,
                 test_read_range,
+                test_read_suffix,
                 test_read_large_range,
+                test_read_range_edge_cases,
+                test_range_read_bounds,
                 test_reader_range,
                 test_reader_from,
                 test_reader_tail,
+                test_reader_seek_footer,
+                test_reader_with_range,
+                test_reader_tokio_copy,
                 test_read_not_exist,
                 test_read_with_dir_path,
                 #[cfg(feature = "compress")]
@@ -95,6 +118,15 @@ macro_rules! behavior_write_tests {
                 test_delete_empty_dir,
                 test_delete_with_special_chars,
                 test_delete_not_existing,
+                test_delete_twice,
+                test_batch_remove,
+                test_copy,
+                test_copy_overwrite,
+                test_copy_not_exist,
+                test_rename,
+                test_rename_to_nested_path,
+                test_rename_not_exist,
+                test_write_with_expire,
             );
         )*
     };
@@ -197,6 +229,29 @@ pub async fn test_create_dir_exising(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Create nested dir whose parents don't exist should succeed.
+pub async fn test_create_nested_dir(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    let path = format!("{parent}a/b/");
+
+    let o = op.object(&path);
+
+    o.create().await?;
+
+    let meta = o.metadata().await?;
+    assert_eq!(meta.mode(), ObjectMode::DIR);
+
+    // Create again should be a no-op.
+    o.create().await?;
+    let meta = o.metadata().await?;
+    assert_eq!(meta.mode(), ObjectMode::DIR);
+
+    for p in [path.clone(), format!("{parent}a/"), parent] {
+        op.object(&p).delete().await.expect("delete must succeed");
+    }
+    Ok(())
+}
+
 /// Write a single file and test with stat.
 pub async fn test_write(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -251,6 +306,121 @@ pub async fn test_write_with_special_chars(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write from reader that doesn't match the given size should fail.
+pub async fn test_write_from_size_mismatch(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    let o = op.object(&path);
+
+    let r = Cursor::new(content.clone());
+    let res = o.write_from(size as u64 + 1, r).await;
+    assert!(res.is_err(), "write from shorter reader must fail");
+    assert!(!o.is_exist().await?, "truncated object must not exist");
+
+    let r = Cursor::new(content.clone());
+    let res = o.write_from(size as u64 - 1, r).await;
+    assert!(res.is_err(), "write from longer reader must fail");
+    assert!(!o.is_exist().await?, "truncated object must not exist");
+
+    Ok(())
+}
+
+/// Append many small chunks via writer should succeed.
+pub async fn test_writer_append(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let chunks: Vec<_> = (0..16).map(|_| gen_fixed_bytes(1024)).collect();
+
+    let mut w = op.object(&path).writer().await?;
+    for chunk in chunks.iter() {
+        w.append(chunk).await?;
+    }
+    w.close().await?;
+
+    let bs = op.object(&path).read().await?;
+    assert_eq!(bs.len(), 16 * 1024, "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(chunks.concat())),
+        "read content"
+    );
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy a large content into writer should write all parts.
+pub async fn test_writer_copy_large(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let content = gen_fixed_bytes(20 * 1024 * 1024);
+
+    let mut w = op.object(&path).writer().await?;
+    let n = futures::io::copy(Cursor::new(content.clone()), &mut w).await?;
+    assert_eq!(n, content.len() as u64);
+    w.close().await?;
+
+    let bs = op.object(&path).read().await?;
+    assert_eq!(bs.len(), content.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Append or close a closed writer should return an error.
+pub async fn test_writer_close_twice(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    let mut w = op.object(&path).writer().await?;
+    w.append(&content).await?;
+    w.close().await?;
+
+    assert!(w.append(&content).await.is_err());
+    assert!(w.close().await.is_err());
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Writer should work with `tokio::io::copy` and `shutdown`.
+pub async fn test_writer_tokio_copy(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, size) = gen_bytes();
+
+    let mut w = op.object(&path).writer().await?;
+    let n = tokio::io::copy(&mut std::io::Cursor::new(content.clone()), &mut w).await?;
+    assert_eq!(n, size as u64);
+    tokio::io::AsyncWriteExt::shutdown(&mut w).await?;
+
+    let bs = op.object(&path).read().await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
 /// Stat existing file should return metadata
 pub async fn test_stat(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -272,6 +442,51 @@ pub async fn test_stat(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Rewrite file should change its etag.
+pub async fn test_stat_etag_changed(op: Operator) -> Result<()> {
+    // fs synthesizes etag from mtime and length, it's not a real etag
+    // and is not guaranteed to change on rewrite.
+    if op.metadata().scheme() == Scheme::Fs {
+        warn!("service doesn't have real etag, ignored");
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.object(&path)
+        .write(content.clone())
+        .await
+        .expect("write must succeed");
+
+    let meta = op.object(&path).stat().await?;
+    let etag = match meta.etag() {
+        Some(etag) => etag.to_string(),
+        None => {
+            warn!("service doesn't return etag, ignored");
+            return Ok(());
+        }
+    };
+    assert!(!etag.starts_with('"'), "etag should not be quoted: {etag}");
+
+    // Make sure the content has been changed.
+    let mut new_content = content;
+    new_content.push(0);
+    op.object(&path)
+        .write(new_content)
+        .await
+        .expect("write must succeed");
+
+    let meta = op.object(&path).stat().await?;
+    assert_ne!(meta.etag(), Some(etag.as_str()));
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
 /// Stat existing file should return metadata
 pub async fn test_stat_dir(op: Operator) -> Result<()> {
     let path = format!("{}/", uuid::Uuid::new_v4());
@@ -379,6 +594,138 @@ pub async fn test_read_full(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read with if_match should succeed only if etag matches.
+pub async fn test_read_with_if_match(op: Operator) -> Result<()> {
+    if !op.metadata().can_conditional_read() {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    op.object(&path)
+        .write(content.clone())
+        .await
+        .expect("write must succeed");
+
+    let meta = op.object(&path).stat().await?;
+    let etag = match meta.etag() {
+        Some(etag) => etag.to_string(),
+        None => {
+            warn!("service doesn't return etag, ignored");
+            return Ok(());
+        }
+    };
+
+    let bs = op
+        .object(&path)
+        .read_with(OpRead::new().with_if_match(&etag))
+        .await?;
+    assert_eq!(size, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    let result = op
+        .object(&path)
+        .read_with(OpRead::new().with_if_match("\"invalid_etag\""))
+        .await;
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    // Services may carry the current etag in error.
+    if let Some(v) = err.context("etag") {
+        assert_eq!(v, etag, "current etag in error");
+    }
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Write with if_not_exists should fail on existing object.
+pub async fn test_write_with_if_not_exists(op: Operator) -> Result<()> {
+    if !op.metadata().can_conditional_write() {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    let o = op.object(&path);
+    let args = OpWrite::new(size as u64).with_if_not_exists(true);
+    o.write_with(args.clone(), content.clone()).await?;
+
+    let err = o
+        .write_with(args, content.clone())
+        .await
+        .expect_err("write on existing object must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    // Services may carry the current etag in error.
+    if let Some(v) = err.context("etag") {
+        let meta = o.stat().await?;
+        assert_eq!(Some(v), meta.etag(), "current etag in error");
+    }
+
+    o.delete().await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Read with if_none_match should succeed only if etag doesn't match.
+pub async fn test_read_with_if_none_match(op: Operator) -> Result<()> {
+    if !op.metadata().can_conditional_read() {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    op.object(&path)
+        .write(content.clone())
+        .await
+        .expect("write must succeed");
+
+    let meta = op.object(&path).stat().await?;
+    let etag = match meta.etag() {
+        Some(etag) => etag.to_string(),
+        None => {
+            warn!("service doesn't return etag, ignored");
+            return Ok(());
+        }
+    };
+
+    let result = op
+        .object(&path)
+        .read_with(OpRead::new().with_if_none_match(&etag))
+        .await;
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    let bs = op
+        .object(&path)
+        .read_with(OpRead::new().with_if_none_match("\"invalid_etag\""))
+        .await?;
+    assert_eq!(size, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
 /// Read range content should match.
 pub async fn test_read_range(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -409,6 +756,44 @@ pub async fn test_read_range(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read the last bytes without knowing the size of object should match.
+pub async fn test_read_suffix(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let content = gen_fixed_bytes(100);
+
+    op.object(&path)
+        .write(content.clone())
+        .await
+        .expect("write must succeed");
+
+    // Use new object handles so that no metadata is cached.
+    let bs = match op.object(&path).read_suffix(10).await {
+        Ok(bs) => bs,
+        // Not all services support range with tail range, let's tolerate this.
+        Err(err) if err.kind() == ErrorKind::Unsupported => {
+            warn!("service doesn't support range with tail");
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    };
+    assert_eq!(bs, content[90..], "read content");
+
+    // The offset of reader should be backfilled so that seeking works.
+    let mut r = op.object(&path).range_reader(..10).await?;
+    assert_eq!(r.content_length(), 10, "read size");
+    r.seek(SeekFrom::Start(5)).await?;
+    let mut bs = Vec::new();
+    r.read_to_end(&mut bs).await?;
+    assert_eq!(bs, content[95..], "read content after seek");
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
 /// Read large range content should match.
 pub async fn test_read_large_range(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -440,6 +825,113 @@ pub async fn test_read_large_range(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read edge case ranges like zero-length ranges should match.
+pub async fn test_read_range_edge_cases(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+    let (offset, length) = gen_offset_length(size);
+    let size = size as u64;
+
+    op.object(&path)
+        .write(content.clone())
+        .await
+        .expect("write must succeed");
+
+    let o = op.object(&path);
+
+    let bs = o.range_read(offset..offset).await?;
+    assert!(bs.is_empty(), "zero-length range");
+    let bs = o.range_read(0..0).await?;
+    assert!(bs.is_empty(), "zero-length range at start");
+    let bs = o.range_read(size..size).await?;
+    assert!(bs.is_empty(), "zero-length range at end");
+
+    let bs = o.range_read(offset..=offset).await?;
+    assert_eq!(
+        bs,
+        content[offset as usize..=offset as usize],
+        "single byte range"
+    );
+    let bs = o.range_read(size - 1..).await?;
+    assert_eq!(bs, content[size as usize - 1..], "last byte range");
+    let bs = o.range_read(..=offset + length - 1).await;
+    match bs {
+        Ok(bs) => assert_eq!(
+            bs,
+            content[(size - offset - length) as usize..],
+            "suffix inclusive range"
+        ),
+        // Not all services support range with tail range, let's tolerate this.
+        Err(err) if err.kind() == ErrorKind::Unsupported => {
+            warn!("service doesn't support range with tail")
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    let mut r = o.range_reader(offset..offset).await?;
+    let mut bs = Vec::new();
+    r.read_to_end(&mut bs).await?;
+    assert!(bs.is_empty(), "zero-length range reader");
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+
+    // Zero-length range of missing objects should still fail.
+    let err = op
+        .object(&path)
+        .range_read(0..0)
+        .await
+        .expect_err("read must fail");
+    assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+    Ok(())
+}
+
+/// Read with all kinds of range bounds should match.
+pub async fn test_range_read_bounds(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+    let (offset, length) = gen_offset_length(size);
+    let (start, end) = (offset as usize, (offset + length) as usize);
+
+    op.object(&path)
+        .write(content.clone())
+        .await
+        .expect("write must succeed");
+
+    let o = op.object(&path);
+
+    let bs = o.range_read(..).await?;
+    assert_eq!(bs, content, "full range");
+    let bs = o.range_read(offset..).await?;
+    assert_eq!(bs, content[start..], "range from");
+    let bs = o.range_read(offset..offset + length).await?;
+    assert_eq!(bs, content[start..end], "range");
+    let bs = o.range_read(offset..=offset + length - 1).await?;
+    assert_eq!(bs, content[start..end], "range inclusive");
+
+    // Not all services support range with tail range, let's tolerate this.
+    match o.range_read(..length).await {
+        Ok(bs) => assert_eq!(bs, content[size - length as usize..], "range to"),
+        Err(err) if err.kind() == ErrorKind::Unsupported => {
+            warn!("service doesn't support range with tail");
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    }
+    let bs = o.range_read(..=length - 1).await?;
+    assert_eq!(bs, content[size - length as usize..], "range to inclusive");
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
 /// Read range content should match.
 pub async fn test_reader_range(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -553,6 +1045,164 @@ pub async fn test_reader_tail(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read via lazy range reader with seeking should match.
+pub async fn test_reader_with_range(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+    let (offset, length) = gen_offset_length(size);
+    let (start, end) = (offset as usize, (offset + length) as usize);
+
+    op.object(&path)
+        .write(content.clone())
+        .await
+        .expect("write must succeed");
+
+    let mut r = op
+        .object(&path)
+        .reader_with_range(offset..offset + length)
+        .await?;
+    assert_eq!(r.content_length(), length, "reader content length");
+
+    let mut bs = Vec::new();
+    r.read_to_end(&mut bs).await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content[start..end])),
+        "read range"
+    );
+
+    // Seek back into the range and read the tail.
+    let n = r.seek(SeekFrom::Start(length / 2)).await?;
+    assert_eq!(n, length / 2, "seek position");
+    let mut bs = Vec::new();
+    r.read_to_end(&mut bs).await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!(
+            "{:x}",
+            Sha256::digest(&content[start + (length / 2) as usize..end])
+        ),
+        "read after seek"
+    );
+
+    // Seek past the end of range is allowed and read returns nothing.
+    r.seek(SeekFrom::End(1)).await?;
+    let n = r.read(&mut bs).await?;
+    assert_eq!(n, 0, "read past the end");
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Read a footer-at-end format via seekable reader should match.
+pub async fn test_reader_seek_footer(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+    let content = [content, vec![0; 8]].concat();
+    let size = size as u64 + 8;
+
+    op.object(&path)
+        .write(content.clone())
+        .await
+        .expect("write must succeed");
+
+    let mut r = op.object(&path).reader().await?;
+
+    // Read the footer.
+    let n = r.seek(SeekFrom::End(-8)).await?;
+    assert_eq!(n, size - 8, "seek position");
+    let mut footer = vec![0; 8];
+    r.read_exact(&mut footer).await?;
+    assert_eq!(footer, &content[size as usize - 8..], "read footer");
+
+    // Seek back and read the footer again.
+    let n = r.seek(SeekFrom::Current(-8)).await?;
+    assert_eq!(n, size - 8, "seek position");
+    let mut bs = vec![0; 8];
+    r.read_exact(&mut bs).await?;
+    assert_eq!(bs, footer, "read footer again");
+
+    // Seek back to the start and read the body.
+    let n = r.seek(SeekFrom::Start(0)).await?;
+    assert_eq!(n, 0, "seek position");
+    let mut bs = vec![0; size as usize - 8];
+    r.read_exact(&mut bs).await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content[..size as usize - 8])),
+        "read body"
+    );
+
+    // Seek past the end is allowed and read returns nothing.
+    let n = r.seek(SeekFrom::End(1024)).await?;
+    assert_eq!(n, size + 1024, "seek position");
+    let n = r.read(&mut bs).await?;
+    assert_eq!(n, 0, "read past the end");
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Reader should work with tokio traits, piping into a `tokio::fs::File`.
+pub async fn test_reader_tokio_copy(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, size) = gen_bytes();
+
+    op.object(&path)
+        .write(content.clone())
+        .await
+        .expect("write must succeed");
+
+    let local = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    let mut f = tokio::fs::File::create(&local).await?;
+    let mut r = op.object(&path).reader().await?;
+    let n = tokio::io::copy(&mut r, &mut f).await?;
+    assert_eq!(n, size as u64);
+    tokio::io::AsyncWriteExt::flush(&mut f).await?;
+
+    let bs = tokio::fs::read(&local).await?;
+    tokio::fs::remove_file(&local).await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    // Read with an empty buffer and after EOF returns 0 immediately.
+    assert_eq!(tokio::io::AsyncReadExt::read(&mut r, &mut []).await?, 0);
+    let mut buf = vec![0; 16];
+    assert_eq!(tokio::io::AsyncReadExt::read(&mut r, &mut buf).await?, 0);
+    assert_eq!(tokio::io::AsyncReadExt::read(&mut r, &mut buf).await?, 0);
+
+    // `AsyncBufRead` should return the same content.
+    let mut r = op.object(&path).reader().await?;
+    let mut buffered = Vec::new();
+    loop {
+        let chunk = tokio::io::AsyncBufReadExt::fill_buf(&mut r).await?;
+        if chunk.is_empty() {
+            break;
+        }
+        let n = chunk.len().min(1000);
+        buffered.extend_from_slice(&chunk[..n]);
+        tokio::io::AsyncBufReadExt::consume(&mut r, n);
+    }
+    assert_eq!(buffered, content, "fill buf");
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
 /// Read not exist file should return NotFound
 pub async fn test_read_not_exist(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -738,3 +1388,202 @@ pub async fn test_delete_not_existing(op: Operator) -> Result<()> {
 
     Ok(())
 }
+
+// Delete the same file twice should also succeed.
+pub async fn test_delete_twice(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.object(&path)
+        .write(content)
+        .await
+        .expect("write must succeed");
+
+    op.object(&path).delete().await?;
+    op.object(&path).delete().await?;
+
+    assert!(!op.object(&path).is_exist().await?);
+
+    Ok(())
+}
+
+// Batch remove files should succeed, not existing files will be ignored.
+pub async fn test_batch_remove(op: Operator) -> Result<()> {
+    let mut paths = Vec::new();
+    for _ in 0..10 {
+        let path = uuid::Uuid::new_v4().to_string();
+        let (content, _) = gen_bytes();
+
+        op.object(&path)
+            .write(content)
+            .await
+            .expect("write must succeed");
+        paths.push(path);
+    }
+    paths.push(uuid::Uuid::new_v4().to_string());
+
+    let results = op.batch().with_limit(4).remove(paths.clone()).await;
+    assert_eq!(
+        results.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+        paths.iter().collect::<Vec<_>>(),
+        "results must be in the same order as input"
+    );
+    for (path, res) in results {
+        res.map_err(|e| anyhow::anyhow!("remove {path} failed: {e}"))?;
+    }
+
+    for path in paths {
+        assert!(
+            !op.object(&path).is_exist().await?,
+            "{path} must be removed"
+        );
+    }
+
+    Ok(())
+}
+
+/// Copy a file should succeed and keep the source.
+pub async fn test_copy(op: Operator) -> Result<()> {
+    let source = uuid::Uuid::new_v4().to_string();
+    let target = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.object(&source).write(content.clone()).await?;
+
+    op.object(&source).copy(&target).await?;
+
+    let bs = op.object(&target).read().await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content))
+    );
+    assert!(op.object(&source).is_exist().await?);
+
+    op.object(&source).delete().await?;
+    op.object(&target).delete().await?;
+    Ok(())
+}
+
+/// Copy to an existing file should overwrite it.
+pub async fn test_copy_overwrite(op: Operator) -> Result<()> {
+    let source = uuid::Uuid::new_v4().to_string();
+    let target = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.object(&source).write(content.clone()).await?;
+    op.object(&target).write("old content").await?;
+
+    op.object(&source).copy(&target).await?;
+
+    let bs = op.object(&target).read().await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content))
+    );
+
+    op.object(&source).delete().await?;
+    op.object(&target).delete().await?;
+    Ok(())
+}
+
+/// Copy a not existing file should return ObjectNotFound.
+pub async fn test_copy_not_exist(op: Operator) -> Result<()> {
+    let source = uuid::Uuid::new_v4().to_string();
+    let target = uuid::Uuid::new_v4().to_string();
+
+    let err = op
+        .object(&source)
+        .copy(&target)
+        .await
+        .expect_err("copy must fail");
+    assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+    assert!(!op.object(&target).is_exist().await?);
+
+    Ok(())
+}
+
+/// Rename a file should move the content and remove the source.
+pub async fn test_rename(op: Operator) -> Result<()> {
+    let source = uuid::Uuid::new_v4().to_string();
+    let target = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.object(&source).write(content.clone()).await?;
+
+    op.object(&source).rename(&target).await?;
+
+    let bs = op.object(&target).read().await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content))
+    );
+    assert!(!op.object(&source).is_exist().await?);
+
+    op.object(&target).delete().await?;
+    Ok(())
+}
+
+/// Rename a file into a dir that doesn't exist should succeed.
+pub async fn test_rename_to_nested_path(op: Operator) -> Result<()> {
+    let source = uuid::Uuid::new_v4().to_string();
+    let target = format!("{}/{}", uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let (content, _) = gen_bytes();
+
+    op.object(&source).write(content.clone()).await?;
+
+    op.object(&source).rename(&target).await?;
+
+    let bs = op.object(&target).read().await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content))
+    );
+    assert!(!op.object(&source).is_exist().await?);
+
+    op.object(&target).delete().await?;
+    Ok(())
+}
+
+/// Rename a not existing file should return `ObjectNotFound`.
+pub async fn test_rename_not_exist(op: Operator) -> Result<()> {
+    let source = uuid::Uuid::new_v4().to_string();
+    let target = uuid::Uuid::new_v4().to_string();
+
+    let err = op
+        .object(&source)
+        .rename(&target)
+        .await
+        .expect_err("rename must fail");
+    assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+    assert!(!op.object(&target).is_exist().await?);
+
+    Ok(())
+}
+
+/// Write with expire should remove the object after expire.
+pub async fn test_write_with_expire(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, size) = gen_bytes();
+    let o = op.object(&path);
+
+    let args = OpWrite::new(size as u64).with_expire(std::time::Duration::from_secs(1));
+    if !op.metadata().can_expire() {
+        let err = o
+            .write_with(args, content)
+            .await
+            .expect_err("write with expire must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(!o.is_exist().await?);
+        return Ok(());
+    }
+
+    o.write_with(args, content.clone()).await?;
+    assert_eq!(o.read().await?, content, "read before expire");
+
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+
+    let err = o.read().await.expect_err("read after expire must fail");
+    assert_eq!(err.kind(), ErrorKind::ObjectNotFound);
+
+    Ok(())
+}